@@ -0,0 +1,89 @@
+"""Measure how well synchronous obstore calls scale across Python threads.
+
+Each worker thread repeatedly streams a file with `GetResult.stream()` and lists a
+prefix with `obstore.list()`, while a separate thread runs pure Python code. When
+obstore releases the GIL while waiting on I/O and copying data, aggregate throughput
+grows with the number of workers, and the Python thread keeps making progress.
+
+Run with:
+
+```
+python benchmarks/threaded_scaling.py --threads 1 2 4 8
+```
+"""
+
+from __future__ import annotations
+
+import argparse
+import tempfile
+import threading
+import time
+from concurrent.futures import ThreadPoolExecutor
+
+import obstore as obs
+from obstore.store import LocalStore
+
+
+def setup(store: LocalStore, file_size: int, num_files: int) -> None:
+    data = b"x" * file_size
+    obs.put(store, "large.bin", data)
+    for i in range(num_files):
+        obs.put(store, f"listing/{i:06}.txt", b"")
+
+
+def work(store: LocalStore, iterations: int, min_chunk_size: int) -> int:
+    nbytes = 0
+    for _ in range(iterations):
+        for chunk in obs.get(store, "large.bin").stream(min_chunk_size=min_chunk_size):
+            nbytes += len(chunk)
+        for _batch in obs.list(store, "listing", chunk_size=1000):
+            pass
+    return nbytes
+
+
+def spin(stop: threading.Event) -> int:
+    count = 0
+    while not stop.is_set():
+        count += 1
+    return count
+
+
+def run(store: LocalStore, threads: int, iterations: int, min_chunk_size: int) -> None:
+    stop = threading.Event()
+    with ThreadPoolExecutor(max_workers=threads + 1) as pool:
+        spinner = pool.submit(spin, stop)
+        start = time.perf_counter()
+        workers = [
+            pool.submit(work, store, iterations, min_chunk_size) for _ in range(threads)
+        ]
+        total = sum(w.result() for w in workers)
+        elapsed = time.perf_counter() - start
+        stop.set()
+        spins = spinner.result()
+
+    print(
+        f"threads={threads:<3} "
+        f"elapsed={elapsed:7.3f}s "
+        f"throughput={total / elapsed / 1e6:9.1f} MB/s "
+        f"python_spins={spins / elapsed / 1e6:6.2f} M/s"
+    )
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--threads", type=int, nargs="+", default=[1, 2, 4, 8])
+    parser.add_argument("--iterations", type=int, default=10)
+    parser.add_argument("--file-size", type=int, default=64 * 1024 * 1024)
+    parser.add_argument("--num-files", type=int, default=10_000)
+    parser.add_argument("--min-chunk-size", type=int, default=8 * 1024 * 1024)
+    args = parser.parse_args()
+
+    with tempfile.TemporaryDirectory() as tmp_dir:
+        store = LocalStore(tmp_dir)
+        setup(store, args.file_size, args.num_files)
+        for threads in args.threads:
+            run(store, threads, args.iterations, args.min_chunk_size)
+
+
+if __name__ == "__main__":
+    main()
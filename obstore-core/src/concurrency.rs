@@ -0,0 +1,285 @@
+//! Running many operations at once, with a fixed or adaptive limit on how many are in flight.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, FuturesUnordered, Stream, StreamExt};
+
+/// How many operations a bulk helper runs at once.
+#[derive(Debug, Clone, Copy)]
+pub enum Concurrency {
+    /// Run up to this many operations at once.
+    Fixed(usize),
+    /// Adjust how many operations run at once from how they complete.
+    Adaptive(AdaptiveConcurrency),
+}
+
+/// The limits of an additive-increase/multiplicative-decrease concurrency controller.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveConcurrency {
+    initial: usize,
+    min: usize,
+    max: usize,
+    /// If `None`, twice the lowest latency observed so far
+    latency_threshold: Option<Duration>,
+}
+
+impl AdaptiveConcurrency {
+    /// Start with `initial` operations at once, staying between `min` and `max`.
+    ///
+    /// An operation that takes longer than `latency_threshold` counts as a failure. If `None`,
+    /// the threshold is twice the lowest latency observed so far.
+    pub fn try_new(
+        initial: usize,
+        min: usize,
+        max: usize,
+        latency_threshold: Option<Duration>,
+    ) -> Result<Self, InvalidConcurrency> {
+        if min == 0 || min > max || !(min..=max).contains(&initial) {
+            return Err(InvalidConcurrency);
+        }
+        Ok(Self {
+            initial,
+            min,
+            max,
+            latency_threshold,
+        })
+    }
+}
+
+/// The error returned for adaptive concurrency limits that aren't `1 <= min <= initial <= max`.
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidConcurrency;
+
+impl Display for InvalidConcurrency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Adaptive concurrency requires 1 <= min <= initial <= max"
+        )
+    }
+}
+
+impl std::error::Error for InvalidConcurrency {}
+
+#[cfg(feature = "pyo3")]
+mod python {
+    use std::collections::HashMap;
+
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+
+    use super::{AdaptiveConcurrency, Concurrency};
+
+    impl<'py> FromPyObject<'py> for Concurrency {
+        fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+            if let Ok(n) = ob.extract::<usize>() {
+                return Ok(Self::Fixed(n));
+            }
+            // Update to use derive(FromPyObject) when default is implemented:
+            // https://github.com/PyO3/pyo3/issues/4643
+            let dict = ob.extract::<HashMap<String, Bound<PyAny>>>()?;
+            let min = dict
+                .get("min")
+                .map(|x| x.extract())
+                .transpose()?
+                .unwrap_or(1);
+            let max = dict
+                .get("max")
+                .map(|x| x.extract())
+                .transpose()?
+                .unwrap_or(64);
+            let initial = dict
+                .get("initial")
+                .map(|x| x.extract())
+                .transpose()?
+                .unwrap_or(4);
+            let latency_threshold = dict
+                .get("latency_threshold")
+                .map(|x| x.extract())
+                .transpose()?;
+            AdaptiveConcurrency::try_new(initial, min, max, latency_threshold)
+                .map(Self::Adaptive)
+                .map_err(|err| PyValueError::new_err(err.to_string()))
+        }
+    }
+}
+
+/// Adjusts the concurrency limit from the outcome of each completed operation.
+#[derive(Debug)]
+struct Controller {
+    config: AdaptiveConcurrency,
+    limit: usize,
+    /// Operations that completed within the threshold since the limit last changed
+    successes: usize,
+    fastest: Option<Duration>,
+    last_decrease: Instant,
+}
+
+impl Controller {
+    fn new(config: AdaptiveConcurrency) -> Self {
+        Self {
+            config,
+            limit: config.initial,
+            successes: 0,
+            fastest: None,
+            last_decrease: Instant::now(),
+        }
+    }
+
+    fn record(&mut self, started: Instant, latency: Duration, ok: bool) {
+        let threshold = self
+            .config
+            .latency_threshold
+            .or(self.fastest.map(|fastest| fastest * 2));
+        if ok {
+            self.fastest = Some(self.fastest.map_or(latency, |fastest| fastest.min(latency)));
+        }
+
+        if !ok || threshold.is_some_and(|threshold| latency > threshold) {
+            // Operations that started before the last decrease ran under the previous limit,
+            // so they don't call for another one
+            if started >= self.last_decrease {
+                self.limit = (self.limit / 2).max(self.config.min);
+                self.successes = 0;
+                self.last_decrease = Instant::now();
+            }
+        } else {
+            // Grow by one for each full round of operations at the current limit
+            self.successes += 1;
+            if self.successes >= self.limit {
+                self.limit = (self.limit + 1).min(self.config.max);
+                self.successes = 0;
+            }
+        }
+    }
+}
+
+async fn timed<Fut: Future>(fut: Fut) -> (Instant, Duration, Fut::Output) {
+    let started = Instant::now();
+    let output = fut.await;
+    (started, started.elapsed(), output)
+}
+
+/// Run `futures` with the given concurrency, yielding their outputs as they complete.
+///
+/// This is the equivalent of [`StreamExt::buffer_unordered`], where an adaptive concurrency
+/// raises the limit while operations succeed within the latency threshold, and halves it when
+/// one fails or exceeds the threshold, e.g. because the store is throttling requests and the
+/// client's retries slow them down.
+pub fn buffer_unordered<'a, I, Fut, T, E>(
+    futures: I,
+    concurrency: Concurrency,
+) -> impl Stream<Item = Result<T, E>> + 'a
+where
+    I: IntoIterator<Item = Fut>,
+    I::IntoIter: 'a,
+    Fut: Future<Output = Result<T, E>> + 'a,
+    T: 'a,
+    E: 'a,
+{
+    let config = match concurrency {
+        Concurrency::Fixed(n) => {
+            return stream::iter(futures)
+                .buffer_unordered(n.max(1))
+                .left_stream()
+        }
+        Concurrency::Adaptive(config) => config,
+    };
+
+    let state = (
+        futures.into_iter().map(timed),
+        FuturesUnordered::new(),
+        Controller::new(config),
+    );
+    stream::unfold(
+        state,
+        |(mut pending, mut in_flight, mut controller)| async move {
+            while in_flight.len() < controller.limit {
+                match pending.next() {
+                    Some(fut) => in_flight.push(fut),
+                    None => break,
+                }
+            }
+            let (started, latency, result) = in_flight.next().await?;
+            controller.record(started, latency, result.is_ok());
+            Some((result, (pending, in_flight, controller)))
+        },
+    )
+    .right_stream()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_adaptive_limits() {
+        assert!(AdaptiveConcurrency::try_new(4, 1, 8, None).is_ok());
+        assert!(AdaptiveConcurrency::try_new(4, 0, 8, None).is_err());
+        assert!(AdaptiveConcurrency::try_new(4, 8, 1, None).is_err());
+        assert!(AdaptiveConcurrency::try_new(9, 1, 8, None).is_err());
+    }
+
+    #[test]
+    fn grows_after_a_round_of_successes_and_halves_on_failure() {
+        let config = AdaptiveConcurrency::try_new(4, 1, 8, Some(Duration::from_secs(1))).unwrap();
+        let mut controller = Controller::new(config);
+        let fast = Duration::from_millis(1);
+        for _ in 0..4 {
+            controller.record(Instant::now(), fast, true);
+        }
+        assert_eq!(controller.limit, 5);
+
+        let before_decrease = Instant::now();
+        controller.record(Instant::now(), fast, false);
+        assert_eq!(controller.limit, 2);
+
+        // Operations started under the previous limit don't halve it again
+        controller.record(before_decrease, fast, false);
+        assert_eq!(controller.limit, 2);
+
+        // Nor below the minimum
+        controller.record(Instant::now(), Duration::from_secs(2), true);
+        controller.record(Instant::now(), fast, false);
+        assert_eq!(controller.limit, 1);
+    }
+
+    async fn run(concurrency: Concurrency) -> (Vec<usize>, usize) {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let futures = (0..20).map(|i| {
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                let n = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(n, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, ()>(i)
+            }
+        });
+        let mut results = buffer_unordered(futures, concurrency)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        results.sort_unstable();
+        (results, max_in_flight.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn limits_operations_in_flight() {
+        let (results, max_in_flight) = run(Concurrency::Fixed(3)).await;
+        assert_eq!(results, (0..20).collect::<Vec<_>>());
+        assert_eq!(max_in_flight, 3);
+
+        let config = AdaptiveConcurrency::try_new(2, 1, 4, Some(Duration::from_secs(1))).unwrap();
+        let (results, max_in_flight) = run(Concurrency::Adaptive(config)).await;
+        assert_eq!(results, (0..20).collect::<Vec<_>>());
+        assert!((2..=4).contains(&max_in_flight));
+    }
+}
@@ -0,0 +1,101 @@
+//! Copying objects too large to be copied with a single request.
+
+use std::ops::Range;
+
+use object_store::path::Path;
+use object_store::{GetOptions, ObjectMeta, ObjectStore, PutResult};
+
+use crate::put::put_multipart_stream;
+
+/// The most parts a multipart upload can have.
+pub const MAX_PARTS: usize = 10_000;
+
+/// The size of the parts of copies streamed through the client.
+const STREAM_PART_SIZE: usize = 16 * 1024 * 1024;
+
+/// Split an object of `size` bytes into the byte ranges of the parts of a multipart upload,
+/// with the parts made larger than `part_size` if needed to fit in [`MAX_PARTS`].
+pub fn part_ranges(size: usize, part_size: usize) -> Vec<Range<usize>> {
+    let part_size = part_size.max(size.div_ceil(MAX_PARTS)).max(1);
+    (0..size)
+        .step_by(part_size)
+        .map(|start| start..(start + part_size).min(size))
+        .collect()
+}
+
+/// Copy `source` to `to` through the client, with up to `max_concurrency` parts in flight at
+/// once, for stores that can't copy large objects themselves.
+///
+/// The source is read with its e-tag as an `If-Match` condition, so the copy fails rather
+/// than mixing versions if the source is overwritten while it's being copied.
+pub async fn stream_copy(
+    store: &dyn ObjectStore,
+    source: &ObjectMeta,
+    to: &Path,
+    max_concurrency: usize,
+) -> object_store::Result<PutResult> {
+    let get_options = GetOptions {
+        if_match: source.e_tag.clone(),
+        ..Default::default()
+    };
+    let stream = store
+        .get_opts(&source.location, get_options)
+        .await?
+        .into_stream();
+    // Smaller than the parts of multipart copies, as these parts are buffered in memory
+    let part_size = STREAM_PART_SIZE.max(source.size.div_ceil(MAX_PARTS));
+    put_multipart_stream(
+        store,
+        to,
+        stream,
+        Default::default(),
+        part_size,
+        max_concurrency,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    #[test]
+    fn splits_objects_into_parts() {
+        assert_eq!(part_ranges(10, 4), vec![0..4, 4..8, 8..10]);
+        assert_eq!(part_ranges(8, 4), vec![0..4, 4..8]);
+        assert_eq!(part_ranges(0, 4), vec![]);
+    }
+
+    #[test]
+    fn enlarges_parts_to_fit_the_part_limit() {
+        let ranges = part_ranges(MAX_PARTS * 10 + 1, 1);
+        assert!(ranges.len() <= MAX_PARTS);
+        assert_eq!(ranges[0], 0..11);
+        assert_eq!(ranges.last().unwrap().end, MAX_PARTS * 10 + 1);
+    }
+
+    #[tokio::test]
+    async fn copies_through_the_client() {
+        let store = InMemory::new();
+        let (from, to) = (Path::from("from.bin"), Path::from("to.bin"));
+        store.put(&from, vec![7; 1000].into()).await.unwrap();
+        let source = store.head(&from).await.unwrap();
+        stream_copy(&store, &source, &to, 2).await.unwrap();
+        let bytes = store.get(&to).await.unwrap().bytes().await.unwrap();
+        assert_eq!(bytes.as_ref(), vec![7; 1000]);
+    }
+
+    #[tokio::test]
+    async fn fails_if_the_source_is_overwritten() {
+        let store = InMemory::new();
+        let (from, to) = (Path::from("from.bin"), Path::from("to.bin"));
+        store.put(&from, "old".into()).await.unwrap();
+        let source = store.head(&from).await.unwrap();
+        store.put(&from, "new".into()).await.unwrap();
+        let err = stream_copy(&store, &source, &to, 2).await.unwrap_err();
+        assert!(matches!(err, object_store::Error::Precondition { .. }));
+        assert!(store.head(&to).await.is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! Uploading objects from a sequence of chunks.
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use object_store::path::Path;
+use object_store::{ObjectStore, PutMultipartOpts, PutResult, WriteMultipart};
+
+/// Upload `chunks` to `location` as a multipart upload of `chunk_size` parts, with up to
+/// `max_concurrency` parts in flight at once.
+///
+/// Chunks are pulled from the stream only while fewer than `max_concurrency` parts are in
+/// flight, so a slow store applies backpressure to the source. The upload is aborted if the
+/// stream yields an error or a part fails, in which case that error is returned.
+pub async fn put_multipart_stream<S, E>(
+    store: &dyn ObjectStore,
+    location: &Path,
+    chunks: S,
+    opts: PutMultipartOpts,
+    chunk_size: usize,
+    max_concurrency: usize,
+) -> Result<PutResult, E>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: From<object_store::Error>,
+{
+    let upload = store.put_multipart_opts(location, opts).await?;
+    let mut writer = WriteMultipart::new_with_chunk_size(upload, chunk_size);
+
+    let mut chunks = std::pin::pin!(chunks);
+    let written = async {
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            writer.wait_for_capacity(max_concurrency).await?;
+            writer.put(chunk);
+        }
+        Ok::<_, E>(())
+    }
+    .await;
+
+    // Make sure to call abort if the multipart upload failed for any reason
+    match written {
+        Ok(()) => Ok(writer.finish().await?),
+        Err(err) => {
+            writer.abort().await?;
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    fn chunks(chunks: &[&'static [u8]]) -> Vec<object_store::Result<Bytes>> {
+        chunks.iter().map(|chunk| Ok(Bytes::from(*chunk))).collect()
+    }
+
+    #[tokio::test]
+    async fn uploads_chunks_in_order() {
+        let store = InMemory::new();
+        let path = Path::from("file.bin");
+        let source = stream::iter(chunks(&[b"foo", b"barbaz", b"", b"qux"]));
+        put_multipart_stream(&store, &path, source, Default::default(), 4, 2)
+            .await
+            .unwrap();
+        let bytes = store.get(&path).await.unwrap().bytes().await.unwrap();
+        assert_eq!(bytes.as_ref(), b"foobarbazqux");
+    }
+
+    #[tokio::test]
+    async fn aborts_when_the_stream_fails() {
+        let store = InMemory::new();
+        let path = Path::from("file.bin");
+        let mut source = chunks(&[b"foo"]);
+        source.push(Err(object_store::Error::Generic {
+            store: "test",
+            source: "the source failed".into(),
+        }));
+        let err = put_multipart_stream(
+            &store,
+            &path,
+            stream::iter(source),
+            Default::default(),
+            4,
+            2,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("the source failed"));
+        assert!(matches!(
+            store.head(&path).await,
+            Err(object_store::Error::NotFound { .. })
+        ));
+    }
+}
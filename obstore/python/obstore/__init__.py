@@ -1,9 +1,10 @@
 from typing import TYPE_CHECKING
 
+from . import types
 from ._obstore import *
 from ._obstore import ___version
 
 if TYPE_CHECKING:
-    from . import store
+    from . import metrics, store
 
 __version__: str = ___version()
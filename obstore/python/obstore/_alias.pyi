@@ -0,0 +1,143 @@
+from ._put import PutResult
+from .store import ObjectStore
+
+def put_alias(store: ObjectStore, alias_path: str, target_path: str) -> PutResult:
+    """Create or replace an alias object pointing at another path in the same store.
+
+    An alias is a small object whose content names its target. Reading it with
+    [`get`][obstore.get] and `resolve_aliases=True` transparently returns the target
+    instead. This provides mutable pointers, such as a `latest` path, for immutable
+    versioned artifacts:
+
+    ```py
+    import obstore as obs
+
+    obs.put(store, "models/v2/weights.bin", weights)
+    obs.put_alias(store, "models/latest/weights.bin", "models/v2/weights.bin")
+
+    obs.get(store, "models/latest/weights.bin", resolve_aliases=True).bytes()
+    ```
+
+    The target is not required to exist. Aliases may point at other aliases.
+
+    Args:
+        store: The ObjectStore instance to use.
+        alias_path: The path of the alias object to write.
+        target_path: The path the alias points to.
+
+    Returns:
+        The result of writing the alias object.
+    """
+
+async def put_alias_async(
+    store: ObjectStore, alias_path: str, target_path: str
+) -> PutResult:
+    """Call `put_alias` asynchronously.
+
+    Refer to the documentation for [put_alias][obstore.put_alias].
+    """
+
+def resolve_alias(store: ObjectStore, path: str) -> str:
+    """Follow aliases starting at `path` and return the final target path.
+
+    If `path` is not an alias, it is returned unchanged.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path to resolve.
+
+    Raises:
+        FileNotFoundError: if `path` or any alias target does not exist.
+        ValueError: if the aliases form a loop or are nested too deeply.
+
+    Returns:
+        The path of the first object that is not an alias.
+    """
+
+async def resolve_alias_async(store: ObjectStore, path: str) -> str:
+    """Call `resolve_alias` asynchronously.
+
+    Refer to the documentation for [resolve_alias][obstore.resolve_alias].
+    """
+
+def set_pointer(
+    store: ObjectStore,
+    pointer_path: str,
+    target: str,
+    *,
+    expected_current: str | None = None,
+) -> PutResult:
+    """Atomically point a pointer object at a new target.
+
+    This formalizes the "current symlink" pattern used in model and artifact
+    registries, where a small pointer object names the currently published version:
+
+    ```py
+    import obstore as obs
+
+    obs.put(store, "models/v3/weights.bin", weights)
+    obs.set_pointer(
+        store,
+        "models/current",
+        "models/v3/weights.bin",
+        expected_current="models/v2/weights.bin",
+    )
+    ```
+
+    Pointers use the same format as aliases created with
+    [`put_alias`][obstore.put_alias], so they can be read transparently with
+    [`get`][obstore.get] and `resolve_aliases=True`.
+
+    Args:
+        store: The ObjectStore instance to use.
+        pointer_path: The path of the pointer object.
+        target: The path the pointer should point to.
+
+    Keyword Args:
+        expected_current: If provided, only update the pointer if it currently points
+            to this path. The update uses a conditional put against the version that
+            was read, so concurrent writers can't overwrite each other's swaps. If
+            `None`, the pointer is overwritten unconditionally. Defaults to `None`.
+
+    Raises:
+        PreconditionError: if `expected_current` was provided and the pointer is
+            missing, points elsewhere, or was changed concurrently.
+
+    Returns:
+        The result of writing the pointer object.
+    """
+
+async def set_pointer_async(
+    store: ObjectStore,
+    pointer_path: str,
+    target: str,
+    *,
+    expected_current: str | None = None,
+) -> PutResult:
+    """Call `set_pointer` asynchronously.
+
+    Refer to the documentation for [set_pointer][obstore.set_pointer].
+    """
+
+def read_pointer(store: ObjectStore, pointer_path: str) -> str:
+    """Return the target of a pointer object.
+
+    Unlike [`resolve_alias`][obstore.resolve_alias], this only reads a single pointer
+    and does not follow nested pointers.
+
+    Args:
+        store: The ObjectStore instance to use.
+        pointer_path: The path of the pointer object.
+
+    Raises:
+        ValueError: if the object at `pointer_path` is not a pointer.
+
+    Returns:
+        The path the pointer points to.
+    """
+
+async def read_pointer_async(store: ObjectStore, pointer_path: str) -> str:
+    """Call `read_pointer` asynchronously.
+
+    Refer to the documentation for [read_pointer][obstore.read_pointer].
+    """
@@ -0,0 +1,186 @@
+from typing import List, Sequence, TypedDict
+
+from .store import AzureStore, GCSStore, S3Store
+
+class CorsRule(TypedDict, total=False):
+    """A rule allowing cross-origin requests to a bucket, such as uploads from a
+    browser.
+    """
+
+    allowed_origins: List[str]
+    """The origins allowed to make requests, such as `https://example.com` or `*`.
+    Required."""
+
+    allowed_methods: List[str]
+    """The HTTP methods allowed, such as `GET` or `PUT`. Required."""
+
+    allowed_headers: List[str]
+    """The request headers allowed in preflight requests.
+
+    GCS allows any request header, so this is ignored by GCS stores.
+    """
+
+    expose_headers: List[str]
+    """The response headers that browsers are allowed to read."""
+
+    max_age_seconds: int | None
+    """How long browsers may cache the response to preflight requests."""
+
+class Transition(TypedDict):
+    """A transition of objects to another storage class."""
+
+    days: int
+    """The age in days at which objects are transitioned."""
+
+    storage_class: str
+    """The storage class to transition objects to, such as `GLACIER` on S3 or
+    `COLDLINE` on GCS."""
+
+class LifecycleRule(TypedDict, total=False):
+    """A rule expiring or transitioning the objects of a bucket as they age."""
+
+    id: str | None
+    """The ID of the rule. Ignored by GCS stores."""
+
+    prefix: str | None
+    """Only apply the rule to objects whose path starts with this prefix."""
+
+    enabled: bool
+    """Whether the rule is applied. Defaults to `True`. GCS has no disabled rules."""
+
+    expiration_days: int | None
+    """Delete objects this many days after they were created."""
+
+    noncurrent_expiration_days: int | None
+    """Delete noncurrent versions of objects this many days after they were
+    replaced."""
+
+    abort_incomplete_multipart_days: int | None
+    """Abort multipart uploads that aren't completed this many days after they
+    started."""
+
+    transitions: List[Transition]
+    """Transitions of objects to other storage classes."""
+
+def get_bucket_cors(store: S3Store | GCSStore | AzureStore) -> List[CorsRule]:
+    """Get the CORS rules of the bucket of a store.
+
+    The request is made with the credentials of the store, which must be allowed to
+    read the configuration of the bucket.
+
+    Args:
+        store: The store whose bucket to read the CORS rules of.
+
+    Raises:
+        NotSupportedError: For an `AzureStore`, as CORS rules are a setting of the storage
+            account.
+
+    Returns:
+        The CORS rules of the bucket, which are empty if it has none.
+    """
+
+async def get_bucket_cors_async(
+    store: S3Store | GCSStore | AzureStore,
+) -> List[CorsRule]:
+    """Call `get_bucket_cors` asynchronously.
+
+    Refer to the documentation for [get_bucket_cors][obstore.get_bucket_cors].
+    """
+
+def put_bucket_cors(
+    store: S3Store | GCSStore | AzureStore, rules: Sequence[CorsRule]
+) -> None:
+    """Replace the CORS rules of the bucket of a store.
+
+    ```py
+    import obstore as obs
+
+    obs.put_bucket_cors(
+        store,
+        [
+            {
+                "allowed_origins": ["https://example.com"],
+                "allowed_methods": ["GET", "PUT"],
+                "allowed_headers": ["*"],
+                "max_age_seconds": 3600,
+            }
+        ],
+    )
+    ```
+
+    Args:
+        store: The store whose bucket to set the CORS rules of.
+        rules: The new CORS rules. Pass an empty list to remove all rules.
+
+    Raises:
+        NotSupportedError: For an `AzureStore`, as CORS rules are a setting of the storage
+            account.
+    """
+
+async def put_bucket_cors_async(
+    store: S3Store | GCSStore | AzureStore, rules: Sequence[CorsRule]
+) -> None:
+    """Call `put_bucket_cors` asynchronously.
+
+    Refer to the documentation for [put_bucket_cors][obstore.put_bucket_cors].
+    """
+
+def get_lifecycle_rules(store: S3Store | GCSStore | AzureStore) -> List[LifecycleRule]:
+    """Get the lifecycle rules of the bucket of a store.
+
+    GCS rules have a single action each, so each is returned as a separate rule, and
+    conditions that have no equivalent in [`LifecycleRule`][obstore.LifecycleRule] are
+    omitted.
+
+    Args:
+        store: The store whose bucket to read the lifecycle rules of.
+
+    Raises:
+        NotSupportedError: For an `AzureStore`, as lifecycle rules are managed through
+            Azure Resource Manager.
+
+    Returns:
+        The lifecycle rules of the bucket, which are empty if it has none.
+    """
+
+async def get_lifecycle_rules_async(
+    store: S3Store | GCSStore | AzureStore,
+) -> List[LifecycleRule]:
+    """Call `get_lifecycle_rules` asynchronously.
+
+    Refer to the documentation for [get_lifecycle_rules][obstore.get_lifecycle_rules].
+    """
+
+def put_lifecycle_rules(
+    store: S3Store | GCSStore | AzureStore, rules: Sequence[LifecycleRule]
+) -> None:
+    """Replace the lifecycle rules of the bucket of a store.
+
+    ```py
+    import obstore as obs
+
+    obs.put_lifecycle_rules(
+        store,
+        [
+            {"id": "expire-logs", "prefix": "logs/", "expiration_days": 30},
+            {"id": "cleanup", "abort_incomplete_multipart_days": 7},
+        ],
+    )
+    ```
+
+    Args:
+        store: The store whose bucket to set the lifecycle rules of.
+        rules: The new lifecycle rules. Pass an empty list to remove all rules.
+
+    Raises:
+        NotSupportedError: For an `AzureStore`, as lifecycle rules are managed through
+            Azure Resource Manager.
+    """
+
+async def put_lifecycle_rules_async(
+    store: S3Store | GCSStore | AzureStore, rules: Sequence[LifecycleRule]
+) -> None:
+    """Call `put_lifecycle_rules` asynchronously.
+
+    Refer to the documentation for [put_lifecycle_rules][obstore.put_lifecycle_rules].
+    """
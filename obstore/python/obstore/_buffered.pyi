@@ -1,21 +1,45 @@
 import os
-from typing import List
+import sys
+from datetime import timedelta
+from types import TracebackType
+from typing import Dict, List, Self
 
+from ._attributes import Attributes
 from ._bytes import Bytes
 from .store import ObjectStore
 
-def open(store: ObjectStore, path: str) -> ReadableFile:
+if sys.version_info >= (3, 12):
+    from collections.abc import Buffer
+else:
+    from typing_extensions import Buffer
+
+def open(
+    store: ObjectStore, path: str, *, shared_cache: bool = False
+) -> ReadableFile:
     """Open a file object from the specified location.
 
     Args:
         store: The ObjectStore instance to use.
         path: The path within ObjectStore to retrieve.
 
+    Keyword Args:
+        shared_cache: Read the object in 1MiB blocks through a cache shared by every file
+            opened with `shared_cache=True` on the same store, path and e-tag in this
+            process. Concurrent readers of the same block wait on a single range request
+            rather than each making their own, which helps when several workers scan the
+            same file, such as readers of different columns of a Parquet file. Blocks are
+            requested with an `If-Match` condition on the object's e-tag, so a reader never
+            sees data from a newer version of the object. The cache holds up to 256MiB,
+            evicting the least recently used blocks first. Objects without an e-tag are
+            read without the cache. Defaults to `False`.
+
     Returns:
         ReadableFile
     """
 
-async def open_async(store: ObjectStore, path: str) -> AsyncReadableFile:
+async def open_async(
+    store: ObjectStore, path: str, *, shared_cache: bool = False
+) -> AsyncReadableFile:
     """Call `open` asynchronously, returning a file object with asynchronous operations.
 
     Refer to the documentation for [open][obstore.open].
@@ -112,3 +136,153 @@ class AsyncReadableFile:
 
     async def tell(self) -> int:
         """Return the current stream position."""
+
+def open_writer(
+    store: ObjectStore,
+    path: str,
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    buffer_size: int = 10 * 1024 * 1024,
+    max_concurrency: int = 12,
+    lease: timedelta | None = None,
+) -> WritableFile:
+    """Open a writable file object at the specified location.
+
+    Data is buffered in memory until `buffer_size` bytes have been written, after which
+    it's uploaded with a multipart upload as more is written. Files that are closed
+    before reaching `buffer_size` are uploaded with a single request. The object only
+    appears once the file is closed.
+
+    Using the file as a context manager closes it at the end of the block, or aborts
+    the upload if the block raises an exception:
+
+    ```py
+    with obs.open_writer(store, "data.csv") as f:
+        for row in rows:
+            f.write(row)
+    ```
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path within ObjectStore to write to.
+
+    Keyword Args:
+        attributes: Provide a set of `Attributes`. Defaults to `None`.
+        tags: Provide tags for this object. Defaults to `None`.
+        buffer_size: The number of bytes buffered before the multipart upload starts,
+            which is also the size of each part. Defaults to 10 MiB.
+        max_concurrency: The maximum number of parts to upload concurrently. Defaults
+            to 12.
+        lease: Hold a lease on `path` for as long as the file is open, refreshed every
+            third of this duration. The lease is a lock object at `path` with a
+            `.lease` suffix, created when the file is opened and deleted when it's
+            closed or aborted, so another writer opening `path` with a lease raises
+            `AlreadyExistsError` while it's held. A lease left behind by a writer that
+            stopped refreshing it can be taken over once expired, in stores supporting
+            conditional updates. If another writer takes over the lease, writing to the
+            file raises `IOError`, and closing it aborts the upload. Leases compare the
+            clocks of the writers, and need a store supporting conditional puts, such
+            as an `S3Store` with `aws_conditional_put` configured. Defaults to `None`.
+
+    Returns:
+        WritableFile
+    """
+
+async def open_writer_async(
+    store: ObjectStore,
+    path: str,
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    buffer_size: int = 10 * 1024 * 1024,
+    max_concurrency: int = 12,
+    lease: timedelta | None = None,
+) -> AsyncWritableFile:
+    """Call `open_writer` asynchronously, returning a file object with asynchronous
+    operations.
+
+    Refer to the documentation for [open_writer][obstore.open_writer].
+    """
+
+class WritableFile:
+    """A writable file object with synchronous operations.
+
+    This implements a similar interface as a generic writable Python binary file-like
+    object.
+    """
+
+    def __enter__(self) -> Self: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> None: ...
+    @property
+    def closed(self) -> bool:
+        """Whether the file has been closed or aborted."""
+
+    def write(self, buffer: Buffer, /) -> int:
+        """Write the bytes in `buffer`, returning the number of bytes written.
+
+        Raises `ValueError` if the file has been closed.
+        """
+
+    def flush(self) -> None:
+        """Wait for the parts uploaded so far to complete."""
+
+    def close(self) -> None:
+        """Upload any buffered data and complete the upload, then release the lease, if
+        any.
+
+        Closing a file more than once has no effect.
+        """
+
+    def abort(self) -> None:
+        """Abort the upload, discarding the data written so far."""
+
+    def writable(self) -> bool:
+        """Return True, as the stream supports writing."""
+
+    def seekable(self) -> bool:
+        """Return False, as the stream doesn't support random access."""
+
+class AsyncWritableFile:
+    """A writable file object with **asynchronous** operations."""
+
+    async def __aenter__(self) -> Self: ...
+    async def __aexit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> None: ...
+    @property
+    def closed(self) -> bool:
+        """Whether the file has been closed or aborted."""
+
+    async def write(self, buffer: Buffer, /) -> int:
+        """Write the bytes in `buffer`, returning the number of bytes written.
+
+        Raises `ValueError` if the file has been closed.
+        """
+
+    async def flush(self) -> None:
+        """Wait for the parts uploaded so far to complete."""
+
+    async def close(self) -> None:
+        """Upload any buffered data and complete the upload, then release the lease, if
+        any.
+
+        Closing a file more than once has no effect.
+        """
+
+    async def abort(self) -> None:
+        """Abort the upload, discarding the data written so far."""
+
+    def writable(self) -> bool:
+        """Return True, as the stream supports writing."""
+
+    def seekable(self) -> bool:
+        """Return False, as the stream doesn't support random access."""
@@ -0,0 +1,102 @@
+from datetime import timedelta
+from typing import Sequence, TypedDict
+
+from .store import S3Store
+
+class CloudFrontKeyPair(TypedDict):
+    """A key pair used to sign CloudFront URLs."""
+
+    key_pair_id: str
+    """The ID of the public key, as registered in a trusted key group of the
+    distribution."""
+
+    private_key: str
+    """The PEM-encoded RSA private key, in PKCS#1 or PKCS#8 format."""
+
+def sign_cdn_url(
+    distribution_url: str,
+    path: str,
+    expires_in: timedelta,
+    key_pair: CloudFrontKeyPair,
+) -> str:
+    """Create a CloudFront signed URL for an object served by a distribution.
+
+    Unlike [`sign`][obstore.sign], which signs requests to the bucket itself, this signs
+    a URL for a CloudFront distribution with restricted viewer access, using a canned
+    policy that allows reading `path` until the URL expires. No request is made.
+
+    ```py
+    import obstore as obs
+    from datetime import timedelta
+
+    url = obs.sign_cdn_url(
+        "https://d111111abcdef8.cloudfront.net",
+        "videos/intro.mp4",
+        timedelta(hours=1),
+        {"key_pair_id": "K2JCJMDEHXQW5F", "private_key": private_key_pem},
+    )
+    ```
+
+    Args:
+        distribution_url: The base URL of the distribution, such as
+            `https://d111111abcdef8.cloudfront.net`.
+        path: The path of the object, relative to the distribution URL.
+        expires_in: How long the URL remains valid.
+        key_pair: The key pair to sign the URL with.
+
+    Raises:
+        ValueError: If the URL is invalid or the private key can't be parsed.
+
+    Returns:
+        The signed URL.
+    """
+
+def invalidate(
+    store: S3Store,
+    distribution_id: str,
+    paths: Sequence[str],
+    *,
+    caller_reference: str | None = None,
+    endpoint: str | None = None,
+) -> str:
+    """Invalidate paths in the cache of a CloudFront distribution.
+
+    The request is signed with the credentials of `store`, which must be allowed to
+    call `cloudfront:CreateInvalidation`. This completes publishing workflows that
+    upload objects with [`put`][obstore.put] and then invalidate their cached copies.
+
+    ```py
+    import obstore as obs
+
+    obs.put(store, "site/index.html", html)
+    obs.invalidate(store, "E2QWRUHAPOMQZL", ["site/index.html"])
+    ```
+
+    Args:
+        store: The S3Store whose credentials to sign the request with.
+        distribution_id: The ID of the distribution.
+        paths: The paths to invalidate, relative to the distribution. Paths may end with
+            `*` to invalidate all paths starting with a prefix.
+
+    Keyword Args:
+        caller_reference: A unique value identifying the invalidation, so that retrying a
+            request doesn't create a second one. Defaults to the current time.
+        endpoint: The CloudFront API endpoint. Defaults to
+            `https://cloudfront.amazonaws.com`.
+
+    Returns:
+        The ID of the invalidation.
+    """
+
+async def invalidate_async(
+    store: S3Store,
+    distribution_id: str,
+    paths: Sequence[str],
+    *,
+    caller_reference: str | None = None,
+    endpoint: str | None = None,
+) -> str:
+    """Call `invalidate` asynchronously.
+
+    Refer to the documentation for [invalidate][obstore.invalidate].
+    """
@@ -0,0 +1,68 @@
+from datetime import datetime, timedelta
+
+def set_clock(start: datetime | None, /) -> None:
+    """Use a virtual clock starting at `start` for retries and other time-based
+    decisions, or go back to the system clock with `None`.
+
+    Time passes on the virtual clock only when obstore waits before retrying an
+    operation, which advances the clock by the wait and returns right away, or when
+    it's advanced with [`advance_clock`][obstore.advance_clock]. Together with
+    [`seed_jitter`][obstore.seed_jitter], this makes tests of retry behavior
+    deterministic and fast, and lets timing issues seen in production be replayed
+    locally:
+
+    ```py
+    from datetime import datetime, timedelta, timezone
+
+    import obstore as obs
+    from obstore.store import MemoryStore, TrashStore
+
+    obs.set_clock(datetime(2025, 1, 1, tzinfo=timezone.utc))
+    obs.seed_jitter(42)
+
+    store = TrashStore(MemoryStore())
+    obs.put(store, "data.bin", b"...")
+    obs.delete(store, "data.bin")
+
+    obs.advance_clock(timedelta(days=8))
+    # The object was moved to the trash more than a week ago
+    assert obs.purge_trash(store, timedelta(days=7)) == 1
+    ```
+
+    The virtual clock is used by:
+
+    - the waits between the attempts of
+      [`put_and_confirm`][obstore.put_and_confirm] and
+      [`run_manifest`][obstore.run_manifest], and the timestamps of manifest reports;
+    - the deletion times of [`TrashStore`][obstore.store.TrashStore] and the cutoff
+      of [`purge_trash`][obstore.purge_trash];
+    - the age of rolling writers and of snapshots, and the cutoff of
+      [`gc`][obstore.gc].
+
+    Requests are always signed with the system time, and the retries object_store
+    makes within each request always wait in real time with unseeded jitter, as
+    configured with a store's `retry_config`.
+
+    Args:
+        start: The timezone-aware time the virtual clock starts at.
+    """
+
+def advance_clock(delta: timedelta, /) -> None:
+    """Advance the virtual clock set with [`set_clock`][obstore.set_clock] by `delta`.
+
+    Raises:
+        RuntimeError: if no virtual clock has been set.
+    """
+
+def seed_jitter(seed: int | None, /) -> None:
+    """Seed the random jitter of the waits between retries, or go back to unseeded
+    jitter with `None`.
+
+    With a seed, the same sequence of operations waits for the same sequence of
+    delays. Jitter is drawn from a single generator for the whole process, so
+    operations running concurrently can still draw their delays in a different
+    order.
+
+    Args:
+        seed: The seed of the generator.
+    """
@@ -0,0 +1,46 @@
+from datetime import timedelta
+from typing import TypedDict
+
+class AdaptiveConcurrency(TypedDict, total=False):
+    """Adjust the concurrency of a bulk operation while it runs.
+
+    Pass this as `max_concurrency` to bulk helpers such as
+    [`total_size`][obstore.total_size], [`find_duplicates`][obstore.find_duplicates] and
+    [`mirror_http`][obstore.mirror_http] instead of a fixed number.
+
+    The number of concurrent operations starts at `initial`. Each time as many
+    operations as the current limit complete within `latency_threshold`, the limit is
+    raised by one, up to `max`. When an operation fails or takes longer than
+    `latency_threshold`, e.g. because the store throttles requests and the client has to
+    retry them, the limit is halved, down to `min`. This finds a near-optimal level of
+    parallelism for each backend without manual tuning.
+
+    ```py
+    import obstore as obs
+    from datetime import timedelta
+
+    obs.total_size(
+        store,
+        "data/",
+        max_concurrency={"max": 128, "latency_threshold": timedelta(seconds=2)},
+    )
+    ```
+
+    An empty dict uses the defaults.
+    """
+
+    initial: int
+    """The number of concurrent operations to start with. Defaults to `4`."""
+
+    min: int
+    """The lowest number of concurrent operations. Must be at least 1. Defaults to `1`."""
+
+    max: int
+    """The highest number of concurrent operations. Defaults to `64`."""
+
+    latency_threshold: timedelta
+    """The latency above which an operation is considered slowed down by the store.
+
+    Defaults to twice the latency of the fastest operation so far. Set this when the
+    operations vary in duration, e.g. when downloading objects of very different sizes.
+    """
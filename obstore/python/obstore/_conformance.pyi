@@ -0,0 +1,55 @@
+from typing import List, TypedDict
+
+from .store import ObjectStore
+
+class ConformanceCheck(TypedDict):
+    """The result of a single check of
+    [`check_store_conformance`][obstore.check_store_conformance]."""
+
+    name: str
+    """The name of the check, e.g. `"suffix_of_zero_length_object"`."""
+
+    passed: bool
+    """Whether the store behaved as documented."""
+
+    error: str | None
+    """How the outcome differed from the documented one, if it did."""
+
+def check_store_conformance(
+    store: ObjectStore, prefix: str = "obstore-conformance"
+) -> List[ConformanceCheck]:
+    """Check that a store follows the documented byte range semantics.
+
+    The range semantics of [`get`][obstore.get], [`get_range`][obstore.get_range] and
+    [`get_ranges`][obstore.get_ranges] are normalized so that they are the same for
+    every store. This runs each edge case, such as suffixes larger than the object,
+    offsets at the end of the object and ranges of zero-length objects, against a
+    store, which is useful to verify that an S3-compatible endpoint is supported:
+
+    ```py
+    import obstore as obs
+
+    failed = [c for c in obs.check_store_conformance(store) if not c["passed"]]
+    assert not failed, failed
+    ```
+
+    Two small objects are written under `prefix` and deleted afterwards, so the
+    credentials need write and delete access to it.
+
+    Args:
+        store: The ObjectStore instance to check.
+        prefix: The prefix to write the test objects under. Defaults to
+            `"obstore-conformance"`.
+
+    Returns:
+        The result of each check.
+    """
+
+async def check_store_conformance_async(
+    store: ObjectStore, prefix: str = "obstore-conformance"
+) -> List[ConformanceCheck]:
+    """Call `check_store_conformance` asynchronously.
+
+    Refer to the documentation for
+    [check_store_conformance][obstore.check_store_conformance].
+    """
@@ -1,8 +1,40 @@
+from typing import Callable
+
 from .store import ObjectStore
 
-def copy(store: ObjectStore, from_: str, to: str, *, overwrite: bool = True) -> None:
+def copy(
+    store: ObjectStore,
+    from_: str,
+    to: str,
+    *,
+    overwrite: bool = True,
+    multipart_threshold: int = 5 * 1024 * 1024 * 1024,
+    part_size: int = 512 * 1024 * 1024,
+    max_concurrency: int = 12,
+    on_part_complete: Callable[[int, int], None] | None = None,
+) -> None:
     """Copy an object from one path to another in the same object store.
 
+    S3 copies objects of up to 5 GiB with a single request. Larger objects are copied
+    with a multipart upload whose parts are each copied from a range of the source
+    (`UploadPartCopy`), which happens automatically for objects larger than
+    `multipart_threshold`. The parts are copied on the server, so no data passes
+    through the client:
+
+    ```py
+    import obstore as obs
+
+    def on_part_complete(part_index: int, size: int) -> None:
+        print(f"copied part {part_index} ({size} bytes)")
+
+    obs.copy(store, "huge.bin", "huge-copy.bin", on_part_complete=on_part_complete)
+    ```
+
+    If a part fails to copy, the upload is aborted. Multipart copies head the source
+    first to find its size, and are only made by an `S3Store` itself rather than a
+    store wrapping one, and only with `overwrite=True`. Other stores copy objects of
+    any size with a single request.
+
     Args:
         store: The ObjectStore instance to use.
         from_: Source path
@@ -14,11 +46,33 @@ def copy(store: ObjectStore, from_: str, to: str, *, overwrite: bool = True) ->
 
             If `False`: will copy only if destination is empty. Performs an atomic operation if the underlying object storage supports it. If atomic operations are not supported by the underlying object storage (like S3) it will return an error.
 
-            Will return an error if the destination already has an object.
+            Raises [`AlreadyExistsError`][obstore.exceptions.AlreadyExistsError] if the
+            destination already has an object.
+        multipart_threshold: The size in bytes above which an `S3Store` copies objects
+            with a multipart copy. Can't be larger than 5 GiB. Defaults to 5 GiB.
+        part_size: The size of each part of a multipart copy, between 5 MiB and 5 GiB.
+            It's increased if needed to fit the object in 10,000 parts. Defaults to 512
+            MiB.
+        max_concurrency: The maximum number of parts to copy concurrently. Defaults to
+            12.
+        on_part_complete: A callback called with the index and size in bytes of each
+            part of a multipart copy once it has been copied. If it raises an exception,
+            the copy is aborted and the exception is raised. It isn't called for
+            endpoints registered as lacking `UploadPartCopy` with
+            [`register_quirks`][obstore.register_quirks], whose large objects are
+            streamed through the client instead.
     """
 
 async def copy_async(
-    store: ObjectStore, from_: str, to: str, *, overwrite: bool = True
+    store: ObjectStore,
+    from_: str,
+    to: str,
+    *,
+    overwrite: bool = True,
+    multipart_threshold: int = 5 * 1024 * 1024 * 1024,
+    part_size: int = 512 * 1024 * 1024,
+    max_concurrency: int = 12,
+    on_part_complete: Callable[[int, int], None] | None = None,
 ) -> None:
     """Call `copy` asynchronously.
 
@@ -0,0 +1,142 @@
+import sys
+from pathlib import Path
+from typing import (
+    IO,
+    AsyncIterable,
+    AsyncIterator,
+    Iterable,
+    Iterator,
+    TypedDict,
+)
+
+from ._bytes import Bytes
+from .store import ObjectStore
+
+if sys.version_info >= (3, 12):
+    from collections.abc import Buffer
+else:
+    from typing_extensions import Buffer
+
+class DedupResult(TypedDict):
+    """Result for a [`put_dedup`][obstore.put_dedup] request."""
+
+    size: int
+    """The total size of the uploaded payload in bytes."""
+
+    chunks: int
+    """The number of chunks the payload was split into."""
+
+    new_chunks: int
+    """The number of chunks that did not yet exist in the chunk pool and were uploaded."""
+
+    new_bytes: int
+    """The number of bytes that were actually uploaded to the chunk pool."""
+
+def put_dedup(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes] | Path | bytes | Buffer | Iterator[Buffer] | Iterable[Buffer],
+    *,
+    pool_prefix: str = ".chunks",
+    min_chunk_size: int = 256 * 1024,
+    avg_chunk_size: int = 1024 * 1024,
+    max_chunk_size: int = 4 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> DedupResult:
+    """Upload a payload as deduplicated, content-addressed chunks.
+
+    !!! warning "Experimental"
+        This API and the manifest format it writes are experimental and may change.
+
+    The payload is split into chunks with content-defined chunking
+    ([FastCDC](https://www.usenix.org/conference/atc16/technical-sessions/presentation/xia)),
+    so that inserting or removing bytes only changes the chunks around the edit. Each
+    chunk is stored once under `pool_prefix`, named by the hex SHA-256 digest of its
+    content, and chunks that already exist in the pool are not uploaded again. A JSON
+    manifest listing the chunks is then written to `path`.
+
+    Storing many near-identical large files this way only uploads and stores the
+    chunks that differ between them. Use [`get_dedup`][obstore.get_dedup] to
+    reassemble the payload from its manifest.
+
+    Chunks are never deleted by this API, even when no manifest references them
+    anymore.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path within ObjectStore for where to save the manifest.
+        file: The object to upload. Supports the same input as
+            [`put`][obstore.put].
+
+    Keyword args:
+        pool_prefix: The prefix under which chunks are stored. Manifests sharing a
+            pool prefix share chunks. Defaults to `".chunks"`.
+        min_chunk_size: The minimum chunk size in bytes. Must be at least 64. Defaults
+            to 256 KiB.
+        avg_chunk_size: The targeted average chunk size in bytes. Defaults to 1 MiB.
+        max_chunk_size: The maximum chunk size in bytes. Defaults to 4 MiB.
+        max_concurrency: The maximum number of chunks to upload concurrently. Defaults
+            to 12.
+
+    Returns:
+        A summary of how much data was uploaded.
+    """
+
+async def put_dedup_async(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes]
+    | Path
+    | bytes
+    | Buffer
+    | AsyncIterator[Buffer]
+    | AsyncIterable[Buffer]
+    | Iterator[Buffer]
+    | Iterable[Buffer],
+    *,
+    pool_prefix: str = ".chunks",
+    min_chunk_size: int = 256 * 1024,
+    avg_chunk_size: int = 1024 * 1024,
+    max_chunk_size: int = 4 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> DedupResult:
+    """Call `put_dedup` asynchronously.
+
+    Refer to the documentation for [`put_dedup`][obstore.put_dedup]. In addition to
+    what the synchronous `put_dedup` allows for the `file` parameter, this also
+    supports an async iterator or iterable of objects implementing the Python buffer
+    protocol.
+    """
+
+def get_dedup(store: ObjectStore, path: str, *, max_concurrency: int = 12) -> Bytes:
+    """Reassemble a payload uploaded with [`put_dedup`][obstore.put_dedup].
+
+    !!! warning "Experimental"
+        This API and the manifest format it reads are experimental and may change.
+
+    The manifest at `path` is read and its chunks are fetched from the chunk pool and
+    concatenated. Each chunk is verified against the size and SHA-256 digest recorded
+    in the manifest.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the manifest.
+
+    Keyword args:
+        max_concurrency: The maximum number of chunks to fetch concurrently. Defaults
+            to 12.
+
+    Raises:
+        ValueError: if the manifest is invalid or a chunk does not match the manifest.
+
+    Returns:
+        The reassembled payload.
+    """
+
+async def get_dedup_async(
+    store: ObjectStore, path: str, *, max_concurrency: int = 12
+) -> Bytes:
+    """Call `get_dedup` asynchronously.
+
+    Refer to the documentation for [get_dedup][obstore.get_dedup].
+    """
@@ -1,8 +1,31 @@
-from typing import Sequence
+from datetime import timedelta
+from typing import List, Literal, Sequence, overload
 
-from .store import ObjectStore
+from .store import ObjectStore, TrashStore
 
-def delete(store: ObjectStore, paths: str | Sequence[str]) -> None:
+@overload
+def delete(
+    store: ObjectStore,
+    paths: str | Sequence[str],
+    *,
+    ignore_not_found: bool = False,
+    return_results: Literal[True],
+) -> List[Exception | None]: ...
+@overload
+def delete(
+    store: ObjectStore,
+    paths: str | Sequence[str],
+    *,
+    ignore_not_found: bool = False,
+    return_results: Literal[False] = False,
+) -> None: ...
+def delete(
+    store: ObjectStore,
+    paths: str | Sequence[str],
+    *,
+    ignore_not_found: bool = False,
+    return_results: bool = False,
+) -> List[Exception | None] | None:
     """Delete the object at the specified location(s).
 
     Args:
@@ -16,10 +39,116 @@ def delete(store: ObjectStore, paths: str | Sequence[str]) -> None:
             depending on the behavior of the underlying store. For example, local
             filesystems, GCP, and Azure return an error, while S3 and in-memory will
             return Ok.
+
+    Keyword Args:
+        ignore_not_found: Whether deleting an object that doesn't exist succeeds on
+            every store, instead of raising `FileNotFoundError` on some. Defaults to
+            `False`.
+        return_results: If `True`, attempt to delete every path and return the outcome
+            of each, instead of raising the first error. Defaults to `False`.
+
+    Returns:
+        `None`, unless `return_results` is `True`. Then, a list with an item for each
+            path, in the order of `paths`: `None` if the object was deleted, or the
+            exception deleting it failed with. The paths of a bulk delete request that
+            failed as a whole, such as one of S3's requests deleting up to 1000 objects,
+            share the exception it failed with.
+
+            ```py
+            results = obs.delete(store, paths, return_results=True)
+            failed = [p for p, err in zip(paths, results) if err is not None]
+            ```
     """
 
-async def delete_async(store: ObjectStore, paths: str | Sequence[str]) -> None:
+@overload
+async def delete_async(
+    store: ObjectStore,
+    paths: str | Sequence[str],
+    *,
+    ignore_not_found: bool = False,
+    return_results: Literal[True],
+) -> List[Exception | None]: ...
+@overload
+async def delete_async(
+    store: ObjectStore,
+    paths: str | Sequence[str],
+    *,
+    ignore_not_found: bool = False,
+    return_results: Literal[False] = False,
+) -> None: ...
+async def delete_async(
+    store: ObjectStore,
+    paths: str | Sequence[str],
+    *,
+    ignore_not_found: bool = False,
+    return_results: bool = False,
+) -> List[Exception | None] | None:
     """Call `delete` asynchronously.
 
     Refer to the documentation for [delete][obstore.delete].
     """
+
+def delete_prefix(
+    store: ObjectStore,
+    prefix: str,
+    *,
+    max_concurrency: int = 4,
+    ignore_not_found: bool = False,
+) -> int:
+    """Delete all objects under a prefix, like a recursive delete of a directory.
+
+    The objects are listed and deleted as the listing streams in, without collecting
+    their paths in Python first. They're deleted in batches of up to 1000 objects, with
+    bulk requests when the store supports them.
+
+    As with [`list`][obstore.list], the prefix matches whole path segments, so that
+    `"data"` deletes `"data/file.txt"` but not `"database.txt"`. An empty prefix
+    deletes every object in the store.
+
+    Args:
+        store: The ObjectStore instance to use.
+        prefix: The prefix of the objects to delete.
+
+    Keyword Args:
+        max_concurrency: The maximum number of batches deleted at once. Defaults to `4`.
+        ignore_not_found: Whether to skip objects that no longer exist when they're
+            deleted, e.g. because another process deleted them after they were listed,
+            instead of raising `FileNotFoundError`. Defaults to `False`.
+
+    Returns:
+        The number of objects deleted.
+    """
+
+async def delete_prefix_async(
+    store: ObjectStore,
+    prefix: str,
+    *,
+    max_concurrency: int = 4,
+    ignore_not_found: bool = False,
+) -> int:
+    """Call `delete_prefix` asynchronously.
+
+    Refer to the documentation for [delete_prefix][obstore.delete_prefix].
+    """
+
+def purge_trash(store: TrashStore, older_than: timedelta) -> int:
+    """Permanently delete objects that were moved to the trash of a `TrashStore`.
+
+    Only objects deleted more than `older_than` ago are purged. Anything within the
+    trash prefix that wasn't put there by a [`TrashStore`][obstore.store.TrashStore]
+    is left untouched.
+
+    Args:
+        store: The TrashStore instance to use.
+        older_than: The minimum time since the objects were deleted. Pass
+            `timedelta(0)` to empty the trash.
+
+    Returns:
+        The number of objects permanently deleted.
+    """
+
+async def purge_trash_async(store: TrashStore, older_than: timedelta) -> int:
+    """Call `purge_trash` asynchronously.
+
+    Refer to the documentation for [purge_trash][obstore.purge_trash].
+    """
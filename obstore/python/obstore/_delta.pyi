@@ -0,0 +1,78 @@
+from pathlib import Path
+from typing import TypedDict
+
+from .store import S3Store
+
+class DeltaResult(TypedDict):
+    """The outcome of [`put_delta`][obstore.put_delta]."""
+
+    e_tag: str | None
+    """The unique identifier for the newly created object."""
+
+    version: str | None
+    """A version indicator for the newly created object."""
+
+    bytes_uploaded: int
+    """The number of bytes uploaded from the local file."""
+
+    bytes_copied: int
+    """The number of bytes copied server-side from the existing object."""
+
+def put_delta(
+    store: S3Store,
+    path: str,
+    local_file: str | Path,
+    *,
+    block_size: int = 8 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> DeltaResult:
+    """Upload a local file over an existing object, sending only the blocks that changed.
+
+    The existing object is split into blocks of `block_size` bytes and read with ranged
+    requests, to compute a checksum of each block. The local file is then scanned with
+    a rolling checksum, in the manner of rsync, so blocks are found even if data was
+    inserted or removed before them. The new object is assembled with a multipart
+    upload, in which the blocks found are copied server-side with `UploadPartCopy` and
+    only the bytes in between are uploaded.
+
+    This trades downloading the existing object for uploading less, so it pays off
+    where uploads are slower than downloads. As every part but the last must be at
+    least 5 MiB, a changed run shorter than that takes in the blocks after it, which are
+    then uploaded instead of copied.
+
+    If the object doesn't exist, or the endpoint lacks `UploadPartCopy`, the whole file
+    is uploaded. The existing object is read and copied with `if_match` set to its
+    `e_tag`, so the upload fails instead of mixing two versions of the object if it is
+    overwritten concurrently.
+
+    Args:
+        store: The S3Store to upload to.
+        path: The path within the store of the object to replace.
+        local_file: The path of the local file to upload.
+
+    Keyword args:
+        block_size: The size of the blocks compared, and of the parts uploaded. Must be
+            between 5 MiB and 5 GiB. Defaults to 8 MiB.
+        max_concurrency: The maximum number of reads, copies and uploads in flight at
+            once. Defaults to 12.
+
+    Raises:
+        ValueError: if `block_size` is out of range, or the upload would need more than
+            10,000 parts.
+
+    Returns:
+        The result of the upload, with how many bytes were uploaded and copied.
+    """
+
+async def put_delta_async(
+    store: S3Store,
+    path: str,
+    local_file: str | Path,
+    *,
+    block_size: int = 8 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> DeltaResult:
+    """Call `put_delta` asynchronously.
+
+    Refer to the documentation for [`put_delta`][obstore.put_delta].
+    """
@@ -0,0 +1,49 @@
+from typing import List, Tuple
+
+from .store import ObjectStore
+
+def diff_objects(
+    store_a: ObjectStore,
+    path_a: str,
+    store_b: ObjectStore,
+    path_b: str,
+    *,
+    block_size: int = 1024 * 1024,
+) -> List[Tuple[int, int]]:
+    """Compare two objects and return the byte ranges in which they differ.
+
+    Both objects are streamed concurrently and compared block by block, so neither
+    object is fully materialized in memory and each is only downloaded once. This is
+    useful for verifying copies and debugging replication issues.
+
+    The two objects may live in different stores.
+
+    Args:
+        store_a: The ObjectStore instance holding the first object.
+        path_a: The path of the first object.
+        store_b: The ObjectStore instance holding the second object.
+        path_b: The path of the second object.
+
+    Keyword Args:
+        block_size: The granularity of the comparison in bytes. Reported ranges are
+            aligned to this block size. Defaults to 1 MiB.
+
+    Returns:
+        A list of `(start, end)` tuples of differing byte ranges, where `start` is
+        inclusive and `end` is exclusive. Adjacent differing blocks are merged into a
+        single range. If one object is longer than the other, the extra bytes are
+        reported as differing. An empty list means the objects are identical.
+    """
+
+async def diff_objects_async(
+    store_a: ObjectStore,
+    path_a: str,
+    store_b: ObjectStore,
+    path_b: str,
+    *,
+    block_size: int = 1024 * 1024,
+) -> List[Tuple[int, int]]:
+    """Call `diff_objects` asynchronously.
+
+    Refer to the documentation for [diff_objects][obstore.diff_objects].
+    """
@@ -0,0 +1,62 @@
+from typing import List, Literal
+
+from ._concurrency import AdaptiveConcurrency
+from ._list import ObjectMeta
+from .store import ObjectStore
+
+def find_duplicates(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    by: Literal["etag", "sha256"] = "etag",
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> List[List[ObjectMeta]]:
+    """Find sets of identical objects under a prefix.
+
+    This is useful for auditing storage for duplicated data.
+
+    ```py
+    import obstore as obs
+
+    for duplicates in obs.find_duplicates(store, "data/", by="sha256"):
+        print([meta["path"] for meta in duplicates])
+    ```
+
+    Args:
+        store: The ObjectStore instance to use.
+        prefix: The prefix to search. Defaults to `None`, searching the entire store.
+
+    Keyword Args:
+        by: How to compare objects.
+
+            - `"etag"`: objects with the same ETag and size are duplicates. This only
+                needs a listing, but objects uploaded in a different way, e.g. as a
+                multipart upload with other part sizes, may have different ETags despite
+                identical content. Objects without an ETag are skipped.
+            - `"sha256"`: objects with the same SHA-256 digest are duplicates. Objects
+                that have the same size as another object are downloaded to compute
+                their digest. Content is streamed, so objects are never fully held in
+                memory.
+
+            Defaults to `"etag"`.
+        max_concurrency: The maximum number of objects to download concurrently when
+            computing digests, or an
+            [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency] to adjust it while
+            downloading. Defaults to 12.
+
+    Returns:
+        The sets of duplicate objects, each containing at least two objects. Sets and
+        the objects within them are sorted by path.
+    """
+
+async def find_duplicates_async(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    by: Literal["etag", "sha256"] = "etag",
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> List[List[ObjectMeta]]:
+    """Call `find_duplicates` asynchronously.
+
+    Refer to the documentation for [find_duplicates][obstore.find_duplicates].
+    """
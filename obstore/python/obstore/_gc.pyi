@@ -0,0 +1,95 @@
+from datetime import timedelta
+from typing import Iterable, List, TypedDict
+
+from .store import ObjectStore
+
+class GcReport(TypedDict):
+    """The outcome of a [`gc`][obstore.gc] run."""
+
+    unreferenced: List[str]
+    """The paths of the unreferenced objects, which were deleted unless this was a dry
+    run."""
+
+    unreferenced_bytes: int
+    """The total size in bytes of the unreferenced objects."""
+
+    referenced: int
+    """The number of listed objects that are referenced."""
+
+    missing: int
+    """The number of referenced paths that weren't found under the prefix.
+
+    A large number usually means that the references don't match the prefix, e.g.
+    they're relative to another root.
+    """
+
+    skipped_recent: int
+    """The number of unreferenced objects kept because they're newer than `min_age`."""
+
+    deleted: int
+    """The number of objects deleted. Always 0 for a dry run."""
+
+def gc(
+    store: ObjectStore,
+    prefix: str | None,
+    referenced: Iterable[str],
+    *,
+    dry_run: bool = True,
+    min_age: timedelta | None = None,
+) -> GcReport:
+    """Delete the objects under a prefix that aren't referenced.
+
+    This is the garbage collection step of table and artifact formats that reference
+    data files from manifests: objects under `prefix` whose path isn't in `referenced`
+    are deleted.
+
+    The reference set is consumed in Rust, and only a 128-bit digest of each path is
+    kept in memory, so sets of hundreds of millions of paths are practical. The listing
+    is streamed and compared as it arrives.
+
+    ```py
+    import obstore as obs
+
+    report = obs.gc(store, "table/data/", manifest_paths, min_age=timedelta(days=1))
+    print(f"Would delete {len(report['unreferenced'])} objects")
+    report = obs.gc(
+        store, "table/data/", manifest_paths, dry_run=False, min_age=timedelta(days=1)
+    )
+    ```
+
+    !!! warning
+        Every unreferenced object under `prefix` is deleted, so an empty or incomplete
+        `referenced` deletes data that is still in use. Inspect the report of a dry run,
+        in particular `missing`, before deleting.
+
+    Args:
+        store: The ObjectStore instance to use.
+        prefix: The prefix to collect. `None` collects the entire store.
+        referenced: The full paths of the objects that are still in use, e.g. read
+            from manifests. This may be any iterable, such as a generator.
+
+    Keyword Args:
+        dry_run: If `True`, only report the unreferenced objects without deleting them.
+            Defaults to `True`.
+        min_age: Keep unreferenced objects modified more recently than this. This
+            protects objects written by concurrent writers that haven't yet been
+            referenced. Defaults to `None`, collecting objects of any age.
+
+    Returns:
+        A report of the objects that were, or would be, deleted.
+    """
+
+async def gc_async(
+    store: ObjectStore,
+    prefix: str | None,
+    referenced: Iterable[str],
+    *,
+    dry_run: bool = True,
+    min_age: timedelta | None = None,
+) -> GcReport:
+    """Call `gc` asynchronously.
+
+    `referenced` is consumed before the returned coroutine is awaited.
+
+    Refer to the documentation for [gc][obstore.gc].
+    """
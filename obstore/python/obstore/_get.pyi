@@ -1,9 +1,11 @@
 from datetime import datetime
-from typing import List, Sequence, Tuple, TypedDict
+from typing import List, Literal, Sequence, Tuple, TypedDict, overload
 
 from ._attributes import Attributes
 from ._bytes import Bytes
+from ._concurrency import AdaptiveConcurrency
 from ._list import ObjectMeta
+from ._stats import TransferStats
 from .store import ObjectStore
 
 class OffsetRange(TypedDict):
@@ -83,20 +85,29 @@ class GetOptions(TypedDict, total=False):
 
     - `(int, int)`: Request a specific range of bytes `(start, end)`.
 
-        If the given range is zero-length or starts after the end of the object, an
-        error will be returned. Additionally, if the range ends after the end of the
-        object, the entire remainder of the object will be returned. Otherwise, the
-        exact requested range will be returned.
+        If the range ends after the end of the object, the entire remainder of the
+        object will be returned. A zero-length range, or a range starting exactly at
+        the end of the object, returns no bytes. Otherwise, the exact requested range
+        will be returned.
 
         The `end` offset is _exclusive_.
 
     - `{"offset": int}`: Request all bytes starting from a given byte offset.
 
-        This is equivalent to `bytes={int}-` as an HTTP header.
+        This is equivalent to `bytes={int}-` as an HTTP header. An offset equal to the
+        size of the object returns no bytes.
 
     - `{"suffix": int}`: Request the last `int` bytes. Note that here, `int` is _the
         size of the request_, not the byte offset. This is equivalent to `bytes=-{int}`
-        as an HTTP header.
+        as an HTTP header. A suffix larger than the object returns the entire object.
+
+    Stores disagree on these edge cases, e.g. on whether zero-length objects have any
+    satisfiable range, so they are normalized to the semantics above, at the cost of an
+    extra metadata request when a store rejects a range. Ranges whose start is after
+    their end, or that start past the end of the object, raise
+    [`InvalidRangeError`][obstore.exceptions.InvalidRangeError]. Use
+    [`check_store_conformance`][obstore.check_store_conformance] to verify these
+    semantics against a particular endpoint.
 
     <https://datatracker.ietf.org/doc/html/rfc9110#name-range>
     """
@@ -240,36 +251,122 @@ class BytesStream:
     def __next__(self) -> bytes:
         """Return the next chunk of bytes in the stream."""
 
+@overload
+def get(
+    store: ObjectStore,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+    resolve_aliases: bool = False,
+    return_stats: Literal[True],
+) -> Tuple[GetResult, TransferStats]: ...
+@overload
+def get(
+    store: ObjectStore,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+    resolve_aliases: bool = False,
+    return_stats: Literal[False] = False,
+) -> GetResult: ...
 def get(
-    store: ObjectStore, path: str, *, options: GetOptions | None = None
-) -> GetResult:
+    store: ObjectStore,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+    resolve_aliases: bool = False,
+    return_stats: bool = False,
+) -> GetResult | Tuple[GetResult, TransferStats]:
     """Return the bytes that are stored at the specified location.
 
     Args:
         store: The ObjectStore instance to use.
         path: The path within ObjectStore to retrieve.
         options: options for accessing the file. Defaults to None.
+        resolve_aliases: If `True`, and `path` is an alias created with
+            [`put_alias`][obstore.put_alias], return the object the alias points to.
+            Nested aliases are followed, and a `ValueError` is raised if they form a
+            loop. This costs an additional metadata request per alias followed.
+            Defaults to `False`.
+        return_stats: If `True`, return a tuple of the result and the
+            [`TransferStats`][obstore.TransferStats] of this operation. Defaults to
+            `False`.
 
     Returns:
         GetResult
     """
 
+@overload
+async def get_async(
+    store: ObjectStore,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+    resolve_aliases: bool = False,
+    return_stats: Literal[True],
+) -> Tuple[GetResult, TransferStats]: ...
+@overload
+async def get_async(
+    store: ObjectStore,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+    resolve_aliases: bool = False,
+    return_stats: Literal[False] = False,
+) -> GetResult: ...
 async def get_async(
-    store: ObjectStore, path: str, *, options: GetOptions | None = None
-) -> GetResult:
+    store: ObjectStore,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+    resolve_aliases: bool = False,
+    return_stats: bool = False,
+) -> GetResult | Tuple[GetResult, TransferStats]:
     """Call `get` asynchronously.
 
     Refer to the documentation for [get][obstore.get].
     """
 
-def get_range(store: ObjectStore, path: str, start: int, end: int) -> Bytes:
+@overload
+def get_range(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[True],
+) -> Tuple[Bytes, TransferStats]: ...
+@overload
+def get_range(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[False] = False,
+) -> Bytes: ...
+def get_range(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    options: GetOptions | None = None,
+    return_stats: bool = False,
+) -> Bytes | Tuple[Bytes, TransferStats]:
     """
     Return the bytes that are stored at the specified location in the given byte range.
 
-    If the given range is zero-length or starts after the end of the object, an error
-    will be returned. Additionally, if the range ends after the end of the object, the
-    entire remainder of the object will be returned. Otherwise, the exact requested
-    range will be returned.
+    If the range ends after the end of the object, the entire remainder of the object
+    will be returned. A zero-length range, or a range starting exactly at the end of the
+    object, returns no bytes. Otherwise, the exact requested range will be returned.
+
+    Ranges whose start is after their end, or that start past the end of the object,
+    raise [`InvalidRangeError`][obstore.exceptions.InvalidRangeError]. These semantics
+    are the same for every store, refer to the `range` option of
+    [`GetOptions`][obstore.GetOptions] for details.
 
     Args:
         store: The ObjectStore instance to use.
@@ -277,44 +374,198 @@ def get_range(store: ObjectStore, path: str, start: int, end: int) -> Bytes:
         start: The start of the byte range.
         end: The end of the byte range (exclusive).
 
+    Keyword args:
+        options: Options for the request, such as a `version` or an `if_match`
+            precondition, as accepted by [`get`][obstore.get]. The `range` and `head`
+            options can't be set. Defaults to `None`.
+        return_stats: If `True`, return a tuple of the result and the
+            [`TransferStats`][obstore.TransferStats] of this operation. Defaults to
+            `False`.
+
     Returns:
         A `Bytes` object implementing the Python buffer protocol, allowing
             zero-copy access to the underlying memory provided by Rust.
     """
 
-async def get_range_async(store: ObjectStore, path: str, start: int, end: int) -> Bytes:
+@overload
+async def get_range_async(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[True],
+) -> Tuple[Bytes, TransferStats]: ...
+@overload
+async def get_range_async(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[False] = False,
+) -> Bytes: ...
+async def get_range_async(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    options: GetOptions | None = None,
+    return_stats: bool = False,
+) -> Bytes | Tuple[Bytes, TransferStats]:
     """Call `get_range` asynchronously.
 
     Refer to the documentation for [get_range][obstore.get_range].
     """
 
+@overload
+def get_ranges(
+    store: ObjectStore,
+    path: str,
+    starts: Sequence[int],
+    ends: Sequence[int],
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[True],
+) -> Tuple[List[Bytes], TransferStats]: ...
+@overload
+def get_ranges(
+    store: ObjectStore,
+    path: str,
+    starts: Sequence[int],
+    ends: Sequence[int],
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[False] = False,
+) -> List[Bytes]: ...
 def get_ranges(
-    store: ObjectStore, path: str, starts: Sequence[int], ends: Sequence[int]
-) -> List[Bytes]:
+    store: ObjectStore,
+    path: str,
+    starts: Sequence[int],
+    ends: Sequence[int],
+    *,
+    options: GetOptions | None = None,
+    return_stats: bool = False,
+) -> List[Bytes] | Tuple[List[Bytes], TransferStats]:
     """
     Return the bytes that are stored at the specified location in the given byte ranges
 
     To improve performance this will:
 
-    - Combine ranges less than 10MB apart into a single call to `fetch`
+    - Combine ranges less than 1MB apart into a single call to `fetch`, as planned by
+      [`plan_ranges`][obstore.plan_ranges]
     - Make multiple `fetch` requests in parallel (up to maximum of 10)
 
+    Each range follows the semantics of [`get_range`][obstore.get_range].
+
     Args:
         store: The ObjectStore instance to use.
         path: The path within ObjectStore to retrieve.
         starts: A sequence of `int` where each offset starts.
         ends: A sequence of `int` where each offset ends (exclusive).
 
+    Keyword args:
+        options: Options for the requests, such as a `version` or an `if_match`
+            precondition, as accepted by [`get`][obstore.get]. They apply to every
+            request made for the ranges. The `range` and `head` options can't be set.
+            Defaults to `None`.
+        return_stats: If `True`, return a tuple of the result and the
+            [`TransferStats`][obstore.TransferStats] of this operation. Defaults to
+            `False`.
+
     Returns:
         A sequence of `Bytes`, one for each range. This `Bytes` object implements the
             Python buffer protocol, allowing zero-copy access to the underlying memory
             provided by Rust.
     """
 
+@overload
+async def get_ranges_async(
+    store: ObjectStore,
+    path: str,
+    starts: Sequence[int],
+    ends: Sequence[int],
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[True],
+) -> Tuple[List[Bytes], TransferStats]: ...
+@overload
 async def get_ranges_async(
-    store: ObjectStore, path: str, starts: Sequence[int], ends: Sequence[int]
-) -> List[Bytes]:
+    store: ObjectStore,
+    path: str,
+    starts: Sequence[int],
+    ends: Sequence[int],
+    *,
+    options: GetOptions | None = None,
+    return_stats: Literal[False] = False,
+) -> List[Bytes]: ...
+async def get_ranges_async(
+    store: ObjectStore,
+    path: str,
+    starts: Sequence[int],
+    ends: Sequence[int],
+    *,
+    options: GetOptions | None = None,
+    return_stats: bool = False,
+) -> List[Bytes] | Tuple[List[Bytes], TransferStats]:
     """Call `get_ranges` asynchronously.
 
     Refer to the documentation for [get_ranges][obstore.get_ranges].
     """
+
+class GetManyStream:
+    """A stream of the objects downloaded by [`get_many`][obstore.get_many].
+
+    Objects are yielded as `(path, bytes)` tuples in the order their downloads complete,
+    which may differ from the order of the paths passed in.
+    """
+
+    def __aiter__(self) -> GetManyStream:
+        """Return `Self` as an async iterator."""
+
+    def __iter__(self) -> GetManyStream:
+        """Return `Self` as an async iterator."""
+
+    async def __anext__(self) -> Tuple[str, Bytes]:
+        """Return the next downloaded object in the stream."""
+
+    def __next__(self) -> Tuple[str, Bytes]:
+        """Return the next downloaded object in the stream."""
+
+def get_many(
+    store: ObjectStore,
+    paths: Sequence[str],
+    *,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> GetManyStream:
+    """Download many objects concurrently, yielding each as soon as it completes.
+
+    The returned stream can be iterated synchronously or asynchronously:
+
+    ```py
+    for path, buffer in obs.get_many(store, paths):
+        print(path, len(buffer))
+
+    async for path, buffer in obs.get_many(store, paths):
+        print(path, len(buffer))
+    ```
+
+    Downloads start when the stream is first iterated. If a download fails, its error is
+    raised by the iteration that would have yielded it, and later iterations continue with
+    the remaining objects.
+
+    Args:
+        store: The ObjectStore instance to use.
+        paths: The paths of the objects to download.
+
+    Keyword Args:
+        max_concurrency: The maximum number of objects downloaded at once, or an
+            [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency] to adjust it while
+            the downloads run. Defaults to `12`.
+
+    Returns:
+        A stream of `(path, bytes)` tuples, in the order the downloads complete.
+    """
@@ -0,0 +1,140 @@
+from typing import List, Tuple
+
+from ._bytes import Bytes
+from .store import ObjectStore
+
+class GzipIndex:
+    """The index of the gzip members of an object.
+
+    Created by [`create_gzip_index`][obstore.create_gzip_index] or read back with
+    [`read_gzip_index`][obstore.read_gzip_index].
+    """
+
+    @property
+    def members(self) -> List[Tuple[int, int]]:
+        """The `(compressed_offset, uncompressed_offset)` of the start of each member."""
+    @property
+    def compressed_size(self) -> int:
+        """The size of the object in bytes."""
+    @property
+    def uncompressed_size(self) -> int:
+        """The size of the decompressed content of the object in bytes."""
+    def compressed_range(self, start: int, end: int) -> Tuple[int, int]:
+        """The compressed byte range to decompress to read the uncompressed bytes from
+        `start` (inclusive) to `end` (exclusive).
+
+        The returned range starts at the beginning of a gzip member, so decompressing it
+        yields the requested bytes preceded by the part of the member before `start`.
+        """
+
+def create_gzip_index(
+    store: ObjectStore, path: str, *, index_path: str | None = None
+) -> GzipIndex:
+    """Index the gzip members of an object and store the index next to it.
+
+    The object is streamed and decompressed once to find where each gzip member starts,
+    and the index is written in the `.gzi` format used by `bgzip`. Random access is only
+    efficient for objects made of many gzip members, such as files compressed with
+    `bgzip` or logs written by appending independently compressed chunks: an object
+    with a single member has to be decompressed from its start to read any range.
+
+    ```py
+    import obstore as obs
+
+    obs.create_gzip_index(store, "logs/app.log.gz")
+    obs.get_gzip_range(store, "logs/app.log.gz", 1_000_000, 1_001_000)
+    ```
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the gzip-compressed object.
+
+    Keyword Args:
+        index_path: The path to write the index to. Defaults to `path` with a `.gzi`
+            suffix.
+
+    Returns:
+        The index of the object.
+    """
+
+async def create_gzip_index_async(
+    store: ObjectStore, path: str, *, index_path: str | None = None
+) -> GzipIndex:
+    """Call `create_gzip_index` asynchronously.
+
+    Refer to the documentation for [create_gzip_index][obstore.create_gzip_index].
+    """
+
+def read_gzip_index(
+    store: ObjectStore, path: str, *, index_path: str | None = None
+) -> GzipIndex:
+    """Read the `.gzi` index of a gzip-compressed object.
+
+    The uncompressed size of the object is computed from the index and the size stored
+    in the trailer of its last gzip member, without decompressing anything.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the gzip-compressed object.
+
+    Keyword Args:
+        index_path: The path of the index. Defaults to `path` with a `.gzi` suffix.
+
+    Raises:
+        FileNotFoundError: If the object or its index doesn't exist.
+        ValueError: If the index is malformed.
+
+    Returns:
+        The index of the object.
+    """
+
+async def read_gzip_index_async(
+    store: ObjectStore, path: str, *, index_path: str | None = None
+) -> GzipIndex:
+    """Call `read_gzip_index` asynchronously.
+
+    Refer to the documentation for [read_gzip_index][obstore.read_gzip_index].
+    """
+
+def get_gzip_range(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    index: GzipIndex | None = None,
+) -> Bytes:
+    """Return the decompressed bytes of a gzip-compressed object from `start`
+    (inclusive) to `end` (exclusive).
+
+    Only the gzip members containing the range are fetched and decompressed. `end` is
+    clamped to the uncompressed size of the object.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the gzip-compressed object.
+        start: The start of the range in the uncompressed content.
+        end: The end of the range in the uncompressed content.
+
+    Keyword Args:
+        index: The index of the object. Pass an index obtained from
+            [`read_gzip_index`][obstore.read_gzip_index] to read many ranges without
+            fetching the index each time. Defaults to reading the `.gzi` index next to
+            the object.
+
+    Returns:
+        The decompressed bytes.
+    """
+
+async def get_gzip_range_async(
+    store: ObjectStore,
+    path: str,
+    start: int,
+    end: int,
+    *,
+    index: GzipIndex | None = None,
+) -> Bytes:
+    """Call `get_gzip_range` asynchronously.
+
+    Refer to the documentation for [get_gzip_range][obstore.get_gzip_range].
+    """
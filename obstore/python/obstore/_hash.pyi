@@ -0,0 +1,54 @@
+import sys
+
+if sys.version_info >= (3, 12):
+    from collections.abc import Buffer
+else:
+    from typing_extensions import Buffer
+
+def crc32c(buf: Buffer, /, value: int = 0) -> int:
+    """Compute the CRC32C (Castagnoli) checksum of a buffer.
+
+    This is the checksum S3 and GCS can store for objects. It's computed in Rust on the
+    buffer's memory, without copying it and without holding the GIL.
+
+    Args:
+        buf: Any object implementing the buffer protocol, such as `bytes`,
+            `memoryview` or [`Bytes`][obstore.Bytes].
+
+    Keyword Args:
+        value: The checksum of preceding data, to compute a running checksum over
+            several buffers, as with [`zlib.crc32`][zlib.crc32]. Defaults to `0`.
+
+    Returns:
+        The checksum, as an unsigned 32-bit integer.
+    """
+
+def xxh3(buf: Buffer, /, seed: int = 0) -> int:
+    """Compute the 64-bit XXH3 hash of a buffer.
+
+    XXH3 is a fast non-cryptographic hash, suited to detecting accidental corruption or
+    comparing the contents of objects.
+
+    Args:
+        buf: Any object implementing the buffer protocol.
+
+    Keyword Args:
+        seed: The seed of the hash. Defaults to `0`.
+
+    Returns:
+        The hash, as an unsigned 64-bit integer.
+    """
+
+def md5(buf: Buffer, /) -> bytes:
+    """Compute the MD5 digest of a buffer.
+
+    The hex encoding of this digest is the `e_tag` of objects uploaded to S3 in a single
+    request without server-side encryption, and its base64 encoding is the `md5Hash` GCS
+    reports for objects.
+
+    Args:
+        buf: Any object implementing the buffer protocol.
+
+    Returns:
+        The 16-byte digest, as with [`hashlib.md5(buf).digest()`][hashlib.md5].
+    """
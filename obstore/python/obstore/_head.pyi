@@ -1,3 +1,5 @@
+from typing import Dict, Sequence
+
 from ._list import ObjectMeta
 from .store import ObjectStore
 
@@ -17,3 +19,43 @@ async def head_async(store: ObjectStore, path: str) -> ObjectMeta:
 
     Refer to the documentation for [head][obstore.head].
     """
+
+def warm_up(
+    store: ObjectStore,
+    paths: Sequence[str] | None = None,
+    *,
+    connections: int = 1,
+) -> Dict[str, ObjectMeta]:
+    """Prepare a store for low-latency requests, e.g. right after a service starts.
+
+    The first request to a store pays for resolving DNS and setting up a TLS connection.
+    This makes `connections` concurrent `head` requests up front, so that their
+    connections are kept in the store's connection pool for later requests to reuse.
+    Requests are made for `paths`, and for a path that isn't expected to exist when
+    there are fewer `paths` than `connections`.
+
+    Errors other than missing objects, such as invalid credentials, are raised, so this
+    also checks that the store is usable.
+
+    Args:
+        store: The ObjectStore instance to use.
+        paths: Paths whose metadata to fetch. Defaults to `None`.
+
+    Keyword args:
+        connections: The number of requests to make concurrently, and so the number of
+            connections to open. Defaults to `1`.
+
+    Returns:
+        The metadata of each of `paths` that exists, keyed by path.
+    """
+
+async def warm_up_async(
+    store: ObjectStore,
+    paths: Sequence[str] | None = None,
+    *,
+    connections: int = 1,
+) -> Dict[str, ObjectMeta]:
+    """Call `warm_up` asynchronously.
+
+    Refer to the documentation for [warm_up][obstore.warm_up].
+    """
@@ -0,0 +1,87 @@
+from datetime import timedelta
+
+from .store import S3Store
+
+class MultipartJanitor:
+    """A handle to the task started by
+    [`start_multipart_janitor`][obstore.start_multipart_janitor].
+    """
+
+    @property
+    def running(self) -> bool:
+        """Whether the janitor is still running."""
+
+    @property
+    def sweeps(self) -> int:
+        """The number of times the janitor has listed and aborted stale uploads."""
+
+    @property
+    def aborted(self) -> int:
+        """The number of uploads the janitor has aborted."""
+
+    @property
+    def last_error(self) -> str | None:
+        """The last error of the last sweep, if any.
+
+        An upload that can't be aborted doesn't stop the sweep, which goes on with the
+        next upload. The janitor keeps running after an error, and tries again at the
+        next interval.
+        """
+
+    def stop(self) -> None:
+        """Stop the janitor, waiting for it to end if it's in the middle of a sweep.
+
+        Stopping a janitor that has already been stopped does nothing.
+        """
+
+    async def stop_async(self) -> None:
+        """Call `stop` asynchronously."""
+
+def start_multipart_janitor(
+    store: S3Store,
+    prefix: str | None,
+    older_than: timedelta,
+    interval: timedelta,
+) -> MultipartJanitor:
+    """Periodically abort the stale multipart uploads of a bucket, in the background.
+
+    Incomplete multipart uploads are billed by the provider until they're aborted, and
+    a long-running service whose writers crash or are killed leaves them behind. The
+    janitor lists the uploads in progress under `prefix` with
+    `ListMultipartUploads`, and aborts those initiated more than `older_than` ago, once
+    straight away and then every `interval`, until it's stopped or
+    [`shutdown`][obstore.shutdown] is called.
+
+    Unlike a lifecycle rule such as `AbortIncompleteMultipartUpload`, this needs no
+    permission to change the configuration of the bucket: only
+    `s3:ListBucketMultipartUploads` and `s3:AbortMultipartUpload`, which a bucket
+    policy can restrict to `prefix`.
+
+    `older_than` must be longer than any upload in progress may take, as the uploads
+    listed can't be told apart from those of other writers, in this process or others.
+
+    ```py
+    from datetime import timedelta
+
+    import obstore as obs
+
+    janitor = obs.start_multipart_janitor(
+        store, "uploads/", older_than=timedelta(days=1), interval=timedelta(hours=1)
+    )
+    ...
+    janitor.stop()
+    ```
+
+    Args:
+        store: The S3Store whose bucket to clean up.
+        prefix: Only abort uploads to paths starting with this prefix. If `None`, the
+            uploads of the whole bucket are aborted.
+        older_than: How long ago an upload must have been initiated to be aborted.
+        interval: How long to wait between sweeps.
+
+    Raises:
+        ValueError: if `interval` is zero.
+
+    Returns:
+        A handle to stop the janitor with, and to read what it has done so far.
+    """
@@ -0,0 +1,78 @@
+from pathlib import Path
+from typing import List
+
+from .store import AzureStore, GCSStore, S3Store
+
+def set_journal(path: str | Path | None, /) -> None:
+    """Record in-progress multipart uploads to a journal file on the local disk.
+
+    Once set, every multipart upload obstore starts is recorded when it begins, along
+    with its ID and the bucket or container it's in, and again once it's completed or
+    aborted. This includes the uploads of [`put`][obstore.put],
+    [`open_writer`][obstore.open_writer],
+    [`open_multipart_writer`][obstore.open_multipart_writer],
+    [`open_sparse_writer`][obstore.open_sparse_writer], [`copy`][obstore.copy],
+    [`patch_range`][obstore.patch_range], [`put_delta`][obstore.put_delta],
+    [`put_from_url`][obstore.put_from_url],
+    [`run_manifest`][obstore.run_manifest] and
+    [`list_to_ndjson`][obstore.list_to_ndjson]. Records are synced to disk before the
+    upload proceeds.
+
+    If the process crashes, the incomplete uploads it leaves behind are still billed
+    by the provider until they're aborted. Pass the same journal to
+    [`recover`][obstore.recover] when the process restarts to abort them:
+
+    ```py
+    import obstore as obs
+
+    obs.recover("uploads.journal", store)
+    obs.set_journal("uploads.journal")
+
+    writer = obs.open_multipart_writer(store, "output.bin")
+    ```
+
+    Only uploads to an [`S3Store`][obstore.store.S3Store],
+    [`GCSStore`][obstore.store.GCSStore] or [`AzureStore`][obstore.store.AzureStore]
+    are journaled, as other stores don't expose the IDs of their uploads. Uploads
+    through middleware wrapping one of these stores aren't journaled either, nor are
+    uploads with `attributes` or `tags`, as uploads started by their ID can't set
+    them.
+
+    Journaled uploads are made by their ID with the client of the bucket or container,
+    bypassing the layers that obstore adds to its stores. Their requests aren't counted
+    in [`metrics`][obstore.metrics], their errors don't set the
+    [`store`][obstore.exceptions.ObstoreError.store] they were raised by or carry
+    permission hints, and an S3 bucket in another region than the store's `region` isn't
+    followed to its region.
+
+    Args:
+        path: The path of the journal, which is created if it doesn't exist and
+            appended to otherwise. Pass `None` to stop journaling.
+    """
+
+def recover(journal_path: str | Path, store: S3Store | GCSStore | AzureStore) -> List[str]:
+    """Abort the uploads to `store` left incomplete in a journal.
+
+    The data of an interrupted upload only partially made it to the store, so uploads
+    are never resumed, only aborted. Uploads to other stores recorded in the same
+    journal are left as they are, to be recovered with their own store.
+
+    Call this before starting new uploads: any upload to `store` that's still in
+    progress in the journal is aborted, including those started by this process.
+
+    Args:
+        journal_path: The path of the journal, as passed to
+            [`set_journal`][obstore.set_journal].
+        store: The store the uploads were made to.
+
+    Returns:
+        The paths of the objects whose uploads were aborted.
+    """
+
+async def recover_async(
+    journal_path: str | Path, store: S3Store | GCSStore | AzureStore
+) -> List[str]:
+    """Call `recover` asynchronously.
+
+    Refer to the documentation for [recover][obstore.recover].
+    """
@@ -1,8 +1,10 @@
 from datetime import datetime
-from typing import Generic, List, Literal, Self, TypedDict, TypeVar, overload
+from pathlib import Path
+from typing import Any, Generic, List, Literal, Self, TypedDict, TypeVar, overload
 
 from arro3.core import RecordBatch
 
+from ._concurrency import AdaptiveConcurrency
 from .store import ObjectStore
 
 class ObjectMeta(TypedDict):
@@ -26,7 +28,9 @@ class ObjectMeta(TypedDict):
     version: str | None
     """A version indicator for this object"""
 
-class ListResult(TypedDict):
+ChunkType = TypeVar("ChunkType", List[ObjectMeta], RecordBatch)
+
+class ListResult(TypedDict, Generic[ChunkType]):
     """
     Result of a list call that includes objects, prefixes (directories) and a token for
     the next set of results. Individual result sets may be limited to 1,000 objects
@@ -36,10 +40,21 @@ class ListResult(TypedDict):
     common_prefixes: List[str]
     """Prefixes that are common (like directories)"""
 
-    objects: List[ObjectMeta]
-    """Object metadata for the listing"""
+    objects: ChunkType
+    """Object metadata for the listing, as an Arrow `RecordBatch` if listed with
+    `return_arrow=True`"""
 
-ChunkType = TypeVar("ChunkType", List[ObjectMeta], RecordBatch)
+class TotalSize(TypedDict):
+    """The number and total size of the objects under a prefix.
+
+    Returned by [`total_size`][obstore.total_size].
+    """
+
+    count: int
+    """The number of objects"""
+
+    size: int
+    """The total size in bytes of the objects"""
 
 class ListStream(Generic[ChunkType]):
     """
@@ -52,18 +67,74 @@ class ListStream(Generic[ChunkType]):
     def __iter__(self) -> Self:
         """Return `Self` as an async iterator."""
 
-    async def collect_async(self) -> ChunkType:
+    async def collect_async(
+        self,
+        *,
+        limit: int | None = None,
+        timeout: float | None = None,
+        partial: bool = True,
+    ) -> ChunkType:
         """Collect all remaining ObjectMeta objects in the stream.
 
-        This ignores the `chunk_size` parameter from the `list` call and collects all
-        remaining data into a single chunk.
+        Refer to the documentation for [collect][obstore.ListStream.collect].
         """
 
-    def collect(self) -> ChunkType:
+    def collect(
+        self,
+        *,
+        limit: int | None = None,
+        timeout: float | None = None,
+        partial: bool = True,
+    ) -> ChunkType:
         """Collect all remaining ObjectMeta objects in the stream.
 
         This ignores the `chunk_size` parameter from the `list` call and collects all
         remaining data into a single chunk.
+
+        Long listings can be bounded with `limit` and `timeout`. Objects that weren't
+        collected stay in the stream, so iterating or collecting again continues where
+        this call stopped.
+
+        Keyword Args:
+            limit: Stop after collecting this many objects. Defaults to `None` (no
+                limit).
+            timeout: Stop after this many seconds. Defaults to `None` (no timeout).
+            partial: When `timeout` is reached, return the objects collected so far if
+                `True`, or raise a `TimeoutError` if `False`. Defaults to `True`.
+        """
+
+    def collect_dataframe(self, engine: Literal["polars", "pandas"] = "polars") -> Any:
+        """Collect all remaining ObjectMeta objects in the stream into a DataFrame.
+
+        The listing is converted to a columnar Arrow batch in Rust and handed to the
+        DataFrame library through [pyarrow](https://arrow.apache.org/docs/python/),
+        avoiding the creation of a Python dict per object. This is much faster than
+        building a DataFrame from the output of [`collect`][obstore.ListStream.collect]
+        for large listings.
+
+        The DataFrame has the columns `path`, `last_modified`, `size`, `e_tag` and
+        `version`.
+
+        This ignores the `chunk_size` and `return_arrow` parameters from the `list`
+        call.
+
+        Args:
+            engine: The DataFrame library to use. Either `"polars"` to return a
+                `polars.DataFrame`, or `"pandas"` to return a `pandas.DataFrame`.
+                Defaults to `"polars"`.
+
+        Returns:
+            A DataFrame of the listing. `pyarrow` and the chosen library must be
+            installed.
+        """
+
+    async def collect_dataframe_async(
+        self, engine: Literal["polars", "pandas"] = "polars"
+    ) -> Any:
+        """Collect all remaining ObjectMeta objects in the stream into a DataFrame.
+
+        Refer to the documentation for
+        [collect_dataframe][obstore.ListStream.collect_dataframe].
         """
 
     async def __anext__(self) -> ChunkType:
@@ -188,7 +259,26 @@ def list(
         A ListStream, which you can iterate through to access list results.
     """
 
-def list_with_delimiter(store: ObjectStore, prefix: str | None = None) -> ListResult:
+@overload
+def list_with_delimiter(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    return_arrow: Literal[True],
+) -> ListResult[RecordBatch]: ...
+@overload
+def list_with_delimiter(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    return_arrow: Literal[False] = False,
+) -> ListResult[List[ObjectMeta]]: ...
+def list_with_delimiter(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    return_arrow: bool = False,
+) -> ListResult[RecordBatch] | ListResult[List[ObjectMeta]]:
     """
     List objects with the given prefix and an implementation specific
     delimiter. Returns common prefixes (directories) in addition to object
@@ -202,15 +292,132 @@ def list_with_delimiter(store: ObjectStore, prefix: str | None = None) -> ListRe
         store: The ObjectStore instance to use.
         prefix: The prefix within ObjectStore to use for listing. Defaults to None.
 
+    Keyword Args:
+        return_arrow: If `True`, return the objects as an Arrow `RecordBatch` with the
+            columns of [`ObjectMeta`][obstore.ObjectMeta], rather than as a list of
+            Python `dict`s. The common prefixes are still returned as a list. Requires
+            `arro3-core` to be installed. Defaults to `False`.
+
     Returns:
         ListResult
     """
 
+@overload
 async def list_with_delimiter_async(
-    store: ObjectStore, prefix: str | None = None
-) -> ListResult:
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    return_arrow: Literal[True],
+) -> ListResult[RecordBatch]: ...
+@overload
+async def list_with_delimiter_async(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    return_arrow: Literal[False] = False,
+) -> ListResult[List[ObjectMeta]]: ...
+async def list_with_delimiter_async(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    return_arrow: bool = False,
+) -> ListResult[RecordBatch] | ListResult[List[ObjectMeta]]:
     """Call `list_with_delimiter` asynchronously.
 
     Refer to the documentation for
     [list_with_delimiter][obstore.list_with_delimiter].
     """
+
+def list_to_ndjson(
+    store: ObjectStore,
+    prefix: str | None,
+    dest: str | Path,
+    *,
+    dest_store: ObjectStore | None = None,
+) -> int:
+    """Export a listing as newline-delimited JSON, without per-object Python overhead.
+
+    Each line is a JSON object with the keys of [`ObjectMeta`][obstore.ObjectMeta],
+    where `last_modified` is an RFC 3339 timestamp string. This is useful for feeding
+    external inventory tooling with very large listings.
+
+    ```py
+    import obstore as obs
+
+    count = obs.list_to_ndjson(store, "data/", "inventory.ndjson")
+    ```
+
+    Args:
+        store: The ObjectStore instance to list.
+        prefix: The prefix to list. `None` lists the entire store.
+        dest: The local file to write to, or the `str` path within `dest_store` if
+            provided. An existing file is overwritten.
+
+    Keyword Args:
+        dest_store: If provided, upload the export to this store at `dest` instead of
+            writing a local file. Defaults to `None`.
+
+    Raises:
+        TypeError: if `dest_store` is provided and `dest` is not a `str`.
+
+    Returns:
+        The number of records written.
+    """
+
+async def list_to_ndjson_async(
+    store: ObjectStore,
+    prefix: str | None,
+    dest: str | Path,
+    *,
+    dest_store: ObjectStore | None = None,
+) -> int:
+    """Call `list_to_ndjson` asynchronously.
+
+    Refer to the documentation for [list_to_ndjson][obstore.list_to_ndjson].
+    """
+
+def total_size(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> TotalSize:
+    """Count the objects under a prefix and sum their sizes.
+
+    This replaces iterating over [`list`][obstore.list] in Python to add up object
+    sizes. The listing is consumed in Rust, without creating Python objects for each
+    object.
+
+    The top level of `prefix` is listed first, then the "directories" within it are
+    listed in parallel.
+
+    ```py
+    import obstore as obs
+
+    total = obs.total_size(store, "data/")
+    print(f"{total['count']} objects, {total['size']} bytes")
+    ```
+
+    Args:
+        store: The ObjectStore instance to use.
+        prefix: The prefix to sum. Defaults to `None`, summing the entire store.
+
+    Keyword Args:
+        max_concurrency: The maximum number of listings to run concurrently, or an
+            [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency] to adjust it while
+            listing. Defaults to 12.
+
+    Returns:
+        The number and total size of the objects.
+    """
+
+async def total_size_async(
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> TotalSize:
+    """Call `total_size` asynchronously.
+
+    Refer to the documentation for [total_size][obstore.total_size].
+    """
@@ -0,0 +1,157 @@
+from typing import List, Literal, TypedDict
+
+from ._concurrency import AdaptiveConcurrency
+from .store import ObjectStore
+
+ManifestFormat = Literal["csv", "ndjson", "arrow"]
+"""The format of a manifest.
+
+- `"csv"`: a CSV file with a header row.
+- `"ndjson"`: one JSON object per line.
+- `"arrow"`: an Arrow IPC file or stream, e.g. written by `pyarrow.feather`.
+"""
+
+class ManifestError(TypedDict):
+    """An operation of a manifest that failed."""
+
+    row: int
+    """The index of the row in the manifest, starting at `0`."""
+
+    source: str
+    destination: str
+    operation: Literal["copy", "move", "delete"]
+    error: str
+    """The error message of the last attempt."""
+
+class ManifestReport(TypedDict):
+    """The outcome of [`run_manifest`][obstore.run_manifest]."""
+
+    manifest: str
+    """The path of the manifest."""
+
+    total: int
+    """The number of rows in the manifest."""
+
+    succeeded: int
+    """The number of operations that succeeded in this run."""
+
+    skipped: int
+    """The number of operations skipped because their destination already existed."""
+
+    resumed: int
+    """The number of rows skipped because a previous run had already processed them,
+    according to the checkpoint."""
+
+    failed: int
+    """The number of operations that failed, after retries."""
+
+    errors: List[ManifestError]
+    """The operations that failed, ordered by row."""
+
+    started_at: str
+    """When the run started, in RFC 3339 format."""
+
+    finished_at: str
+    """When the run finished, in RFC 3339 format."""
+
+def run_manifest(
+    store: ObjectStore,
+    manifest: str,
+    *,
+    destination_store: ObjectStore | None = None,
+    format: ManifestFormat | None = None,
+    checkpoint: str | None = None,
+    checkpoint_every: int = 1000,
+    report: str | None = None,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+    max_retries: int = 3,
+) -> ManifestReport:
+    """Run the operations listed in a manifest, such as the objects to copy in a migration.
+
+    The manifest is an object in `store`, with one row per operation and these columns:
+
+    - `source` (required): the path of the object in `store`.
+    - `destination`: the path to copy or move the object to. Defaults to `source`,
+      which is mostly useful along with `destination_store`.
+    - `operation`: `"copy"`, `"move"` or `"delete"`. Defaults to `"copy"`.
+    - `if_not_exists`: if true, the operation is skipped when its destination already
+      exists. Defaults to false.
+
+    Other columns are ignored. For example, as CSV:
+
+    ```csv
+    source,destination,operation
+    raw/2024/01.parquet,archive/2024/01.parquet,move
+    tmp/scratch.bin,,delete
+    ```
+
+    The job runs entirely in Rust:
+
+    ```py
+    import obstore as obs
+
+    report = obs.run_manifest(
+        store, "jobs/migration.csv", checkpoint="jobs/migration.checkpoint"
+    )
+    print(report["succeeded"], report["failed"])
+    ```
+
+    Operations that fail with a transient error, e.g. a connection error or a server
+    error that persisted through the store's own retries, are retried with exponential
+    backoff. Operations that still fail are listed in the report, and don't stop the
+    rest of the job.
+
+    **Checkpointing**: with `checkpoint`, the rows that have been processed are
+    periodically saved to that path in `store`. Running the same manifest again with
+    the same checkpoint skips those rows, so an interrupted job can be resumed, and a
+    job can be re-run to retry only the operations that failed.
+
+    Once all rows are processed, the report is written as JSON to `report` in `store`,
+    and returned.
+
+    Args:
+        store: The store holding the manifest, and the objects it lists.
+        manifest: The path of the manifest in `store`.
+
+    Keyword Args:
+        destination_store: The store to copy or move objects to. Objects are then
+            streamed from `store` to `destination_store`. Defaults to `store`, using
+            server-side copies and renames.
+        format: The format of the manifest. Defaults to one inferred from its
+            extension: `.csv`, `.ndjson` or `.jsonl`, or `.arrow`, `.arrows`, `.ipc` or
+            `.feather`.
+        checkpoint: The path in `store` to save progress to. Defaults to `None`, not
+            checkpointing.
+        checkpoint_every: How many operations to complete between checkpoints. Defaults
+            to 1000.
+        report: The path in `store` to write the report to. Defaults to the path of the
+            manifest followed by `.report.json`.
+        max_concurrency: The maximum number of operations to run concurrently, or an
+            [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency]. Defaults to 12.
+        max_retries: The number of times to retry an operation that failed with a
+            transient error. Defaults to 3.
+
+    Raises:
+        ValueError: If the manifest is invalid, or the checkpoint was written for a
+            different version of the manifest.
+
+    Returns:
+        The report of the job.
+    """
+
+async def run_manifest_async(
+    store: ObjectStore,
+    manifest: str,
+    *,
+    destination_store: ObjectStore | None = None,
+    format: ManifestFormat | None = None,
+    checkpoint: str | None = None,
+    checkpoint_every: int = 1000,
+    report: str | None = None,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+    max_retries: int = 3,
+) -> ManifestReport:
+    """Call `run_manifest` asynchronously.
+
+    Refer to the documentation for [run_manifest][obstore.run_manifest].
+    """
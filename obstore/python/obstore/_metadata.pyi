@@ -0,0 +1,74 @@
+from arro3.core.types import ArrowArrayExportable, ArrowStreamExportable
+
+from ._concurrency import AdaptiveConcurrency
+from .store import ObjectStore
+
+def apply_metadata(
+    store: ObjectStore,
+    updates: ArrowArrayExportable | ArrowStreamExportable,
+    *,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> int:
+    """Update the attributes and tags of many objects at once.
+
+    `updates` is an Arrow table, such as a `pyarrow.Table` or a `polars.DataFrame`, with
+    one row per object. Its `path` column holds the path of each object and its other
+    columns the values to set:
+
+    - `content_type`, `content_disposition`, `content_encoding`, `content_language` and
+      `cache_control` set the corresponding [`Attribute`][obstore.Attribute].
+    - `metadata:<key>` sets the user-defined metadata `<key>`.
+    - `tag:<key>` sets the tag `<key>`.
+
+    Null values leave the current value of an attribute unchanged, and attributes
+    without a column are preserved.
+
+    Object stores don't support changing the metadata of an object in place, so each
+    object is downloaded and written back onto itself with its new metadata. This is
+    meant for bulk-fixing metadata, e.g. after a migration, and any write to an object
+    while it is being rewritten may be lost.
+
+    ```py
+    import pyarrow as pa
+    import obstore as obs
+
+    updates = pa.table(
+        {
+            "path": ["site/index.html", "site/app.js"],
+            "content_type": ["text/html", "text/javascript"],
+            "cache_control": ["no-cache", "max-age=31536000"],
+        }
+    )
+    obs.apply_metadata(store, updates)
+    ```
+
+    !!! warning
+        Tags can't be read from object stores, so the existing tags of an object are
+        removed when it is rewritten, unless the row sets at least one tag.
+
+    Args:
+        store: The ObjectStore instance to use.
+        updates: The metadata to set on each object.
+
+    Keyword Args:
+        max_concurrency: The maximum number of objects to rewrite concurrently, or an
+            [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency]. Defaults to 12.
+
+    Raises:
+        ValueError: If `updates` has no `path` column, or an unexpected column.
+        FileNotFoundError: If an object doesn't exist.
+
+    Returns:
+        The number of objects updated.
+    """
+
+async def apply_metadata_async(
+    store: ObjectStore,
+    updates: ArrowArrayExportable | ArrowStreamExportable,
+    *,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> int:
+    """Call `apply_metadata` asynchronously.
+
+    Refer to the documentation for [apply_metadata][obstore.apply_metadata].
+    """
@@ -0,0 +1,272 @@
+import sys
+from typing import Callable, Sequence
+
+from ._put import PutResult
+from .store import ObjectStore
+
+if sys.version_info >= (3, 12):
+    from collections.abc import Buffer
+else:
+    from typing_extensions import Buffer
+
+def open_multipart_writer(
+    store: ObjectStore,
+    path: str,
+    *,
+    part_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    on_part_complete: Callable[[int, int], None] | None = None,
+) -> MultipartWriter:
+    """Open a writer that streams data to an object as a multipart upload.
+
+    Writes are buffered until a full part is available, which is then uploaded in the
+    background. The writer exposes how far along the upload is, which is useful to
+    display upload progress or to find out why an upload has stalled:
+
+    ```py
+    import obstore as obs
+
+    def on_part_complete(part_index: int, size: int) -> None:
+        print(f"uploaded part {part_index} ({size} bytes)")
+
+    writer = obs.open_multipart_writer(
+        store, "output.bin", on_part_complete=on_part_complete
+    )
+    for chunk in chunks:
+        writer.write(chunk)
+        print(writer.parts_in_flight, writer.bytes_buffered)
+    writer.finish()
+    ```
+
+    Parts are numbered in the order they are filled, no matter in which order their
+    uploads complete.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path within ObjectStore for where to save the object.
+
+    Keyword args:
+        part_size: The size of each part of the multipart upload. Defaults to 5 MB.
+        max_concurrency: The maximum number of parts to upload concurrently. Once this
+            many parts are in flight, each `write` that fills another part waits for an
+            upload to complete. Defaults to 12.
+        on_part_complete: A callback called with the index and size in bytes of each
+            part once it has been uploaded. It's called from a background thread. If it
+            raises an exception, the next call to `write`, `flush` or `finish` raises
+            it.
+
+    Returns:
+        MultipartWriter
+    """
+
+async def open_multipart_writer_async(
+    store: ObjectStore,
+    path: str,
+    *,
+    part_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    on_part_complete: Callable[[int, int], None] | None = None,
+) -> AsyncMultipartWriter:
+    """Call `open_multipart_writer` asynchronously, returning a writer with asynchronous
+    operations.
+
+    Refer to the documentation for
+    [open_multipart_writer][obstore.open_multipart_writer].
+    """
+
+class MultipartWriter:
+    """A multipart upload with synchronous operations."""
+
+    @property
+    def parts_in_flight(self) -> int:
+        """The number of parts currently being uploaded."""
+
+    @property
+    def parts_completed(self) -> int:
+        """The number of parts that have been uploaded."""
+
+    @property
+    def bytes_buffered(self) -> int:
+        """The number of bytes written that don't yet make up a full part."""
+
+    @property
+    def bytes_uploaded(self) -> int:
+        """The number of bytes in the parts that have been uploaded."""
+
+    def write(self, buf: Buffer, /) -> None:
+        """Write `buf` to the end of the object."""
+
+    def flush(self) -> None:
+        """Wait for all parts currently in flight to be uploaded.
+
+        Data that doesn't make up a full part yet stays buffered, since most stores
+        require every part but the last to be at least a minimum size.
+        """
+
+    def finish(self) -> PutResult:
+        """Upload any buffered data and complete the upload, making the object visible."""
+
+    def abort(self) -> None:
+        """Abort the upload and discard all data written so far."""
+
+class AsyncMultipartWriter:
+    """A multipart upload with **asynchronous** operations."""
+
+    @property
+    def parts_in_flight(self) -> int:
+        """The number of parts currently being uploaded."""
+
+    @property
+    def parts_completed(self) -> int:
+        """The number of parts that have been uploaded."""
+
+    @property
+    def bytes_buffered(self) -> int:
+        """The number of bytes written that don't yet make up a full part."""
+
+    @property
+    def bytes_uploaded(self) -> int:
+        """The number of bytes in the parts that have been uploaded."""
+
+    async def write(self, buf: Buffer, /) -> None:
+        """Write `buf` to the end of the object."""
+
+    async def flush(self) -> None:
+        """Wait for all parts currently in flight to be uploaded.
+
+        Data that doesn't make up a full part yet stays buffered, since most stores
+        require every part but the last to be at least a minimum size.
+        """
+
+    async def finish(self) -> PutResult:
+        """Upload any buffered data and complete the upload, making the object visible."""
+
+    async def abort(self) -> None:
+        """Abort the upload and discard all data written so far."""
+
+def create_multipart(store: ObjectStore, path: str) -> str:
+    """Start a multipart upload, returning its upload ID.
+
+    Unlike [`open_multipart_writer`][obstore.open_multipart_writer], which manages an
+    upload for you, these functions give explicit control over each step of an upload.
+    As an upload is identified by its path and upload ID alone, its parts can be
+    uploaded from different processes or machines, and an interrupted upload can be
+    resumed by a later process:
+
+    ```py
+    import obstore as obs
+
+    upload_id = obs.create_multipart(store, "output.bin")
+    # Possibly in other processes, saving each part ID as a checkpoint
+    parts = [
+        obs.put_part(store, "output.bin", upload_id, i, chunk)
+        for i, chunk in enumerate(chunks)
+    ]
+    obs.complete_multipart(store, "output.bin", upload_id, parts)
+    ```
+
+    Multipart uploads by upload ID are supported by
+    [`S3Store`][obstore.store.S3Store], [`GCSStore`][obstore.store.GCSStore],
+    [`AzureStore`][obstore.store.AzureStore] and
+    [`MemoryStore`][obstore.store.MemoryStore]. Other stores raise
+    [`NotSupportedError`][obstore.exceptions.NotSupportedError].
+
+    Uploads that are never completed or aborted can leave hidden parts behind, refer
+    to [`put`][obstore.put] for details.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path within ObjectStore for where to save the object.
+
+    Returns:
+        The ID of the upload.
+    """
+
+async def create_multipart_async(store: ObjectStore, path: str) -> str:
+    """Call `create_multipart` asynchronously.
+
+    Refer to the documentation for [create_multipart][obstore.create_multipart].
+    """
+
+def put_part(
+    store: ObjectStore,
+    path: str,
+    upload_id: str,
+    part_idx: int,
+    data: Buffer,
+) -> str:
+    """Upload a part of a multipart upload started with
+    [`create_multipart`][obstore.create_multipart].
+
+    Parts can be uploaded concurrently and in any order. Uploading a part again with
+    the same index replaces it. Most stores require every part but the last to be at
+    least 5 MiB.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the upload.
+        upload_id: The ID of the upload.
+        part_idx: The index of the part, starting at 0.
+        data: The contents of the part.
+
+    Returns:
+        The ID of the part, to pass to
+            [`complete_multipart`][obstore.complete_multipart].
+    """
+
+async def put_part_async(
+    store: ObjectStore,
+    path: str,
+    upload_id: str,
+    part_idx: int,
+    data: Buffer,
+) -> str:
+    """Call `put_part` asynchronously.
+
+    Refer to the documentation for [put_part][obstore.put_part].
+    """
+
+def complete_multipart(
+    store: ObjectStore,
+    path: str,
+    upload_id: str,
+    parts: Sequence[str],
+) -> PutResult:
+    """Complete a multipart upload, making the object visible.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the upload.
+        upload_id: The ID of the upload.
+        parts: The IDs returned by [`put_part`][obstore.put_part], ordered by the index
+            of their part.
+
+    Returns:
+        The result of the upload.
+    """
+
+async def complete_multipart_async(
+    store: ObjectStore,
+    path: str,
+    upload_id: str,
+    parts: Sequence[str],
+) -> PutResult:
+    """Call `complete_multipart` asynchronously.
+
+    Refer to the documentation for [complete_multipart][obstore.complete_multipart].
+    """
+
+def abort_multipart(store: ObjectStore, path: str, upload_id: str) -> None:
+    """Abort a multipart upload, discarding the parts uploaded so far.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the upload.
+        upload_id: The ID of the upload.
+    """
+
+async def abort_multipart_async(store: ObjectStore, path: str, upload_id: str) -> None:
+    """Call `abort_multipart` asynchronously.
+
+    Refer to the documentation for [abort_multipart][obstore.abort_multipart].
+    """
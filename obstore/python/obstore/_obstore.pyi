@@ -1,43 +1,198 @@
+from ._alias import put_alias as put_alias
+from ._alias import put_alias_async as put_alias_async
+from ._alias import read_pointer as read_pointer
+from ._alias import read_pointer_async as read_pointer_async
+from ._alias import resolve_alias as resolve_alias
+from ._alias import resolve_alias_async as resolve_alias_async
+from ._alias import set_pointer as set_pointer
+from ._alias import set_pointer_async as set_pointer_async
 from ._attributes import Attribute as Attribute
 from ._attributes import Attributes as Attributes
 from ._buffered import AsyncReadableFile as AsyncReadableFile
+from ._buffered import AsyncWritableFile as AsyncWritableFile
 from ._buffered import ReadableFile as ReadableFile
+from ._buffered import WritableFile as WritableFile
 from ._buffered import open as open
 from ._buffered import open_async as open_async
+from ._buffered import open_writer as open_writer
+from ._buffered import open_writer_async as open_writer_async
+from ._bucket import CorsRule as CorsRule
+from ._bucket import LifecycleRule as LifecycleRule
+from ._bucket import Transition as Transition
+from ._bucket import get_bucket_cors as get_bucket_cors
+from ._bucket import get_bucket_cors_async as get_bucket_cors_async
+from ._bucket import get_lifecycle_rules as get_lifecycle_rules
+from ._bucket import get_lifecycle_rules_async as get_lifecycle_rules_async
+from ._bucket import put_bucket_cors as put_bucket_cors
+from ._bucket import put_bucket_cors_async as put_bucket_cors_async
+from ._bucket import put_lifecycle_rules as put_lifecycle_rules
+from ._bucket import put_lifecycle_rules_async as put_lifecycle_rules_async
 from ._bytes import Bytes as Bytes
+from ._bytes import BytesReader as BytesReader
+from ._concurrency import AdaptiveConcurrency as AdaptiveConcurrency
+from ._conformance import ConformanceCheck as ConformanceCheck
+from ._conformance import check_store_conformance as check_store_conformance
+from ._conformance import (
+    check_store_conformance_async as check_store_conformance_async,
+)
+from ._cdn import CloudFrontKeyPair as CloudFrontKeyPair
+from ._cdn import invalidate as invalidate
+from ._cdn import invalidate_async as invalidate_async
+from ._cdn import sign_cdn_url as sign_cdn_url
+from ._clock import advance_clock as advance_clock
+from ._clock import seed_jitter as seed_jitter
+from ._clock import set_clock as set_clock
 from ._copy import copy as copy
 from ._copy import copy_async as copy_async
+from ._dedup import DedupResult as DedupResult
+from ._dedup import get_dedup as get_dedup
+from ._dedup import get_dedup_async as get_dedup_async
+from ._dedup import put_dedup as put_dedup
+from ._dedup import put_dedup_async as put_dedup_async
 from ._delete import delete as delete
 from ._delete import delete_async as delete_async
+from ._delete import delete_prefix as delete_prefix
+from ._delete import delete_prefix_async as delete_prefix_async
+from ._delete import purge_trash as purge_trash
+from ._delete import purge_trash_async as purge_trash_async
+from ._delta import DeltaResult as DeltaResult
+from ._delta import put_delta as put_delta
+from ._delta import put_delta_async as put_delta_async
+from ._diff import diff_objects as diff_objects
+from ._diff import diff_objects_async as diff_objects_async
+from ._duplicates import find_duplicates as find_duplicates
+from ._duplicates import find_duplicates_async as find_duplicates_async
+from ._gc import GcReport as GcReport
+from ._gc import gc as gc
+from ._gc import gc_async as gc_async
 from ._get import BytesStream as BytesStream
+from ._get import GetManyStream as GetManyStream
 from ._get import GetOptions as GetOptions
 from ._get import GetResult as GetResult
 from ._get import OffsetRange as OffsetRange
 from ._get import SuffixRange as SuffixRange
 from ._get import get as get
 from ._get import get_async as get_async
+from ._get import get_many as get_many
 from ._get import get_range as get_range
 from ._get import get_range_async as get_range_async
 from ._get import get_ranges as get_ranges
 from ._get import get_ranges_async as get_ranges_async
+from ._gzip import GzipIndex as GzipIndex
+from ._gzip import create_gzip_index as create_gzip_index
+from ._gzip import create_gzip_index_async as create_gzip_index_async
+from ._gzip import get_gzip_range as get_gzip_range
+from ._gzip import get_gzip_range_async as get_gzip_range_async
+from ._gzip import read_gzip_index as read_gzip_index
+from ._gzip import read_gzip_index_async as read_gzip_index_async
+from ._hash import crc32c as crc32c
+from ._hash import md5 as md5
+from ._hash import xxh3 as xxh3
 from ._head import head as head
 from ._head import head_async as head_async
+from ._head import warm_up as warm_up
+from ._head import warm_up_async as warm_up_async
+from ._janitor import MultipartJanitor as MultipartJanitor
+from ._janitor import start_multipart_janitor as start_multipart_janitor
+from ._journal import recover as recover
+from ._journal import recover_async as recover_async
+from ._journal import set_journal as set_journal
 from ._list import ListResult as ListResult
 from ._list import ListStream as ListStream
 from ._list import ObjectMeta as ObjectMeta
+from ._list import TotalSize as TotalSize
 from ._list import list as list
+from ._list import list_to_ndjson as list_to_ndjson
+from ._list import list_to_ndjson_async as list_to_ndjson_async
 from ._list import list_with_delimiter as list_with_delimiter
 from ._list import list_with_delimiter_async as list_with_delimiter_async
+from ._list import total_size as total_size
+from ._list import total_size_async as total_size_async
+from ._manifest import ManifestError as ManifestError
+from ._manifest import ManifestFormat as ManifestFormat
+from ._manifest import ManifestReport as ManifestReport
+from ._manifest import run_manifest as run_manifest
+from ._manifest import run_manifest_async as run_manifest_async
+from ._metadata import apply_metadata as apply_metadata
+from ._metadata import apply_metadata_async as apply_metadata_async
+from ._multipart import AsyncMultipartWriter as AsyncMultipartWriter
+from ._multipart import MultipartWriter as MultipartWriter
+from ._multipart import abort_multipart as abort_multipart
+from ._multipart import abort_multipart_async as abort_multipart_async
+from ._multipart import complete_multipart as complete_multipart
+from ._multipart import complete_multipart_async as complete_multipart_async
+from ._multipart import create_multipart as create_multipart
+from ._multipart import create_multipart_async as create_multipart_async
+from ._multipart import open_multipart_writer as open_multipart_writer
+from ._multipart import open_multipart_writer_async as open_multipart_writer_async
+from ._multipart import put_part as put_part
+from ._multipart import put_part_async as put_part_async
+from ._patch import patch_range as patch_range
+from ._patch import patch_range_async as patch_range_async
+from ._path import StoreSpec as StoreSpec
+from ._path import parse_object_url as parse_object_url
+from ._probe import PermissionReport as PermissionReport
+from ._probe import ProbeLatency as ProbeLatency
+from ._probe import ProbeResult as ProbeResult
+from ._probe import diagnose_permissions as diagnose_permissions
+from ._probe import diagnose_permissions_async as diagnose_permissions_async
+from ._probe import probe as probe
+from ._probe import probe_async as probe_async
+from ._probe import set_permission_hints as set_permission_hints
+from ._public import open_public as open_public
 from ._put import PutMode as PutMode
 from ._put import PutResult as PutResult
 from ._put import UpdateVersion as UpdateVersion
 from ._put import put as put
+from ._put import put_and_confirm as put_and_confirm
+from ._put import put_and_confirm_async as put_and_confirm_async
 from ._put import put_async as put_async
+from ._put import put_many as put_many
+from ._put import put_many_async as put_many_async
+from ._quirks import register_quirks as register_quirks
+from ._ranges import plan_ranges as plan_ranges
+from ._remote import mirror_http as mirror_http
+from ._remote import mirror_http_async as mirror_http_async
+from ._remote import put_from_url as put_from_url
+from ._remote import put_from_url_async as put_from_url_async
 from ._rename import rename as rename
 from ._rename import rename_async as rename_async
+from ._retries import RetryEvent as RetryEvent
+from ._retries import set_retry_hook as set_retry_hook
+from ._rolling import AsyncRollingWriter as AsyncRollingWriter
+from ._rolling import RollingNaming as RollingNaming
+from ._rolling import RollingWriter as RollingWriter
+from ._rolling import open_rolling_writer as open_rolling_writer
+from ._rolling import open_rolling_writer_async as open_rolling_writer_async
+from ._shutdown import ShutdownReport as ShutdownReport
+from ._shutdown import shutdown as shutdown
 from ._sign import HTTP_METHOD as HTTP_METHOD
 from ._sign import SignCapableStore as SignCapableStore
 from ._sign import sign as sign
 from ._sign import sign_async as sign_async
+from ._sink import AsyncRecordSink as AsyncRecordSink
+from ._sink import RecordFormat as RecordFormat
+from ._sink import RecordSink as RecordSink
+from ._sink import open_record_sink as open_record_sink
+from ._sink import open_record_sink_async as open_record_sink_async
+from ._snapshot import get_pinned as get_pinned
+from ._snapshot import get_pinned_async as get_pinned_async
+from ._snapshot import snapshot_token as snapshot_token
+from ._snapshot import snapshot_token_async as snapshot_token_async
+from ._sparse import AsyncSparseWriter as AsyncSparseWriter
+from ._sparse import SparseWriter as SparseWriter
+from ._sparse import open_sparse_writer as open_sparse_writer
+from ._sparse import open_sparse_writer_async as open_sparse_writer_async
+from ._stats import TransferStats as TransferStats
+from ._tags import get_tags as get_tags
+from ._tags import get_tags_async as get_tags_async
+from ._tags import put_tags as put_tags
+from ._tags import put_tags_async as put_tags_async
+from ._tracing import disable_tracing as disable_tracing
+from ._tracing import enable_tracing as enable_tracing
+from ._versions import get_at as get_at
+from ._versions import get_at_async as get_at_async
+from ._versions import undelete as undelete
+from ._versions import undelete_async as undelete_async
 
 def ___version() -> str: ...
@@ -0,0 +1,70 @@
+import sys
+
+from ._put import PutResult
+from .store import ObjectStore
+
+if sys.version_info >= (3, 12):
+    from collections.abc import Buffer
+else:
+    from typing_extensions import Buffer
+
+def patch_range(
+    store: ObjectStore,
+    path: str,
+    offset: int,
+    data: bytes | Buffer,
+    *,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> PutResult:
+    """Overwrite part of an existing object, starting at byte `offset`.
+
+    Object stores don't support modifying objects in place, so this rewrites the whole
+    object. Objects no larger than `chunk_size` are downloaded, patched in memory and
+    uploaded with a single request, as a conditional update of the version that was
+    read, where the store supports it, so a concurrent write isn't silently lost.
+
+    Larger objects are rewritten with a multipart upload. On S3 the unchanged bytes
+    before and after the patched range are copied server-side with `UploadPartCopy`,
+    and only the part holding the patch is uploaded. Other stores, and S3-compatible
+    stores that lack `UploadPartCopy`, stream the unchanged bytes from the existing
+    object, so the object is never held in memory in full.
+
+    The existing object is read and copied with `if_match` set to its `e_tag`, so the
+    patch fails instead of mixing two versions of the object if it is overwritten
+    concurrently. Writing past the end of the object extends it.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path within ObjectStore of the object to patch.
+        offset: The byte offset at which to write `data`. Must not be past the end of
+            the object.
+        data: The bytes to write.
+
+    Keyword args:
+        chunk_size: The size of chunks to use within each part of the multipart
+            upload, and the largest object that is patched with a single request.
+            Defaults to 5 MB.
+        max_concurrency: The maximum number of chunks to upload concurrently. Defaults
+            to 12.
+
+    Raises:
+        ValueError: if `offset` is past the end of the object.
+
+    Returns:
+        The result of uploading the patched object.
+    """
+
+async def patch_range_async(
+    store: ObjectStore,
+    path: str,
+    offset: int,
+    data: bytes | Buffer,
+    *,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> PutResult:
+    """Call `patch_range` asynchronously.
+
+    Refer to the documentation for [`patch_range`][obstore.patch_range].
+    """
@@ -0,0 +1,36 @@
+from typing import Literal, Tuple, TypedDict
+
+class StoreSpec(TypedDict):
+    """The store that an object URL refers to."""
+
+    store: Literal[
+        "AzureStore", "GCSStore", "HTTPStore", "LocalStore", "MemoryStore", "S3Store"
+    ]
+    """The name of the store class that can access the object."""
+
+    url: str
+    """The URL of the store, which can be passed to `from_url` of that store class."""
+
+def parse_object_url(url: str) -> Tuple[StoreSpec, str]:
+    """Split the URL of an object into the store that contains it and its path.
+
+    This recognizes the same URLs as the `from_url` constructors of each store, and
+    is the inverse of `url_for` on each store:
+
+    ```py
+    import obstore as obs
+
+    spec, path = obs.parse_object_url("s3://bucket/path/to/file.txt")
+    assert spec == {"store": "S3Store", "url": "s3://bucket"}
+    assert path == "path/to/file.txt"
+    ```
+
+    Args:
+        url: The URL of an object.
+
+    Raises:
+        ValueError: if the URL isn't the URL of an object in a known store.
+
+    Returns:
+        The store containing the object, and the path of the object within it.
+    """
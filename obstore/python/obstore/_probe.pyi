@@ -0,0 +1,149 @@
+from typing import TypedDict
+
+from .store import ObjectStore
+
+class PermissionReport(TypedDict):
+    """The result of [`diagnose_permissions`][obstore.diagnose_permissions].
+
+    Each probe is `True` if the request was authorized (a missing object still counts
+    as authorized), `False` if it was rejected with a permission or authentication
+    error, and `None` if it failed for an unrelated reason.
+    """
+
+    list: bool | None
+    """Whether listing the parent prefix of the path is allowed."""
+
+    head: bool | None
+    """Whether fetching the metadata of the object is allowed."""
+
+    get: bool | None
+    """Whether reading the object is allowed."""
+
+    hint: str | None
+    """A human-readable guess at which permission is missing, if any."""
+
+def diagnose_permissions(store: ObjectStore, path: str) -> PermissionReport:
+    """Probe which read permissions the store's credentials have on a path.
+
+    This issues a few cheap, read-only requests (listing the parent prefix, and a head
+    and get request against `path`) and summarizes which of them were denied, along
+    with a hint about which permission is likely missing. No data is written and the
+    body of the get request is not downloaded.
+
+    This is intended to be called after an operation failed with a
+    [`PermissionDeniedError`][obstore.exceptions.PermissionDeniedError], to shorten
+    the time it takes to diagnose IAM misconfigurations. To have these probes issued
+    for every permission error instead, use
+    [`set_permission_hints`][obstore.set_permission_hints].
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path that an operation failed on.
+
+    Returns:
+        A report of the probes that were issued.
+    """
+
+async def diagnose_permissions_async(
+    store: ObjectStore, path: str
+) -> PermissionReport:
+    """Call `diagnose_permissions` asynchronously.
+
+    Refer to the documentation for
+    [diagnose_permissions][obstore.diagnose_permissions].
+    """
+
+def set_permission_hints(enabled: bool) -> None:
+    """Add hints about which permission is likely missing to permission errors.
+
+    Once enabled, whenever a request is denied, the probes of
+    [`diagnose_permissions`][obstore.diagnose_permissions] are issued for the path
+    denied, and the resulting hint is appended to the message of the
+    [`PermissionDeniedError`][obstore.exceptions.PermissionDeniedError] raised, and set
+    as its `hint` attribute. Copies and renames are probed at their source. Hints are
+    disabled by default, as the probes add a few requests to every denied operation.
+
+    ```py
+    import obstore as obs
+    from obstore.exceptions import PermissionDeniedError
+
+    obs.set_permission_hints(True)
+    try:
+        obs.get(store, path)
+    except PermissionDeniedError as err:
+        print(err.hint)
+    ```
+
+    Args:
+        enabled: Whether to add hints to permission errors.
+    """
+
+class ProbeLatency(TypedDict):
+    """Round-trip latency statistics in milliseconds."""
+
+    min: float
+    p50: float
+    p90: float
+    max: float
+
+class ProbeResult(TypedDict):
+    """The result of [`probe`][obstore.probe]."""
+
+    reachable: bool
+    """Whether the store returned any response at all, whatever its status.
+
+    A store that responded with an error unrelated to the credentials, such as a 503,
+    is reachable, with that error set in `error`. A store that couldn't be connected
+    to, e.g. because its host name doesn't resolve, is not."""
+
+    authenticated: bool | None
+    """Whether the store accepted the credentials, or `None` if it was unreachable."""
+
+    authorized: bool | None
+    """Whether the credentials are allowed to read metadata, or `None` if unknown."""
+
+    error: str | None
+    """The last error encountered, if any."""
+
+    region: str | None
+    """The region of the bucket, for an S3Store that is reachable, as reported in the
+    `x-amz-bucket-region` header of a `HeadBucket` request. `None` for other stores,
+    or if the endpoint doesn't report it."""
+
+    latency_ms: ProbeLatency | None
+    """Latency statistics of the requests that got a response, or `None` if the store
+    was unreachable."""
+
+def probe(store: ObjectStore, *, requests: int = 5) -> ProbeResult:
+    """Check that a store is reachable and measure its round-trip latency.
+
+    This issues `requests` sequential metadata requests for an object that is not
+    expected to exist, which is one of the cheapest requests every store supports. It is
+    intended for readiness checks in services that depend on object storage.
+
+    ```py
+    import obstore as obs
+
+    result = obs.probe(store)
+    if not (result["reachable"] and result["authenticated"]):
+        raise RuntimeError(f"Object storage is not ready: {result['error']}")
+    ```
+
+    For an S3Store, one more `HeadBucket` request is issued to look up the region of
+    the bucket, which S3 reports even to credentials that aren't allowed to read it.
+
+    Args:
+        store: The ObjectStore instance to use.
+
+    Keyword Args:
+        requests: The number of requests to issue. Defaults to 5.
+
+    Returns:
+        A summary of the store's health.
+    """
+
+async def probe_async(store: ObjectStore, *, requests: int = 5) -> ProbeResult:
+    """Call `probe` asynchronously.
+
+    Refer to the documentation for [probe][obstore.probe].
+    """
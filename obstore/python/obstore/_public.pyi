@@ -0,0 +1,52 @@
+from .store import GuardrailStore
+
+def open_public(
+    url: str,
+    *,
+    region: str | None = None,
+    max_requests: int = 8,
+) -> GuardrailStore:
+    """Open a public dataset for anonymous, read-only access.
+
+    This is a shortcut for examples and teaching material that read from public
+    buckets, where setting up credentials is unnecessary friction. The returned store:
+
+    - Sends unsigned requests, so no credentials are looked up in the environment.
+    - Never opts in to requester-pays, so it can't incur charges on your account.
+    - Allows at most `max_requests` requests in flight at once, to stay well within
+      the rate limits of shared public endpoints.
+    - Is read-only: writes raise
+      [`PermissionDeniedError`][obstore.exceptions.PermissionDeniedError].
+
+    Supported URL schemes:
+
+    - `s3://bucket/prefix` for AWS S3.
+    - `az://container/prefix` (and the other URLs accepted by
+      [`AzureStore.from_url`][obstore.store.AzureStore.from_url]) for Azure Blob
+      Storage containers with anonymous read access.
+    - `http://` and `https://` for any other HTTP server, read with
+      [`HTTPStore`][obstore.store.HTTPStore].
+
+    Anonymous GCS access is not supported, but objects in public GCS buckets can be
+    read through `https://storage.googleapis.com/<bucket>`.
+
+    **Example**:
+
+    ```py
+    import obstore as obs
+
+    store = obs.open_public("s3://sentinel-cogs/sentinel-s2-l2a-cogs", region="us-west-2")
+    obs.list(store, chunk_size=10).__next__()
+    ```
+
+    Args:
+        url: The URL of the public dataset.
+
+    Keyword Args:
+        region: The AWS region of an `s3://` bucket. Defaults to `"us-east-1"`.
+        max_requests: The maximum number of concurrent requests. Defaults to 8.
+
+    Returns:
+        A read-only [`GuardrailStore`][obstore.store.GuardrailStore] wrapping the
+        anonymous store.
+    """
@@ -1,4 +1,5 @@
 import sys
+from datetime import timedelta
 from pathlib import Path
 from typing import (
     IO,
@@ -7,11 +8,18 @@ from typing import (
     Dict,
     Iterable,
     Iterator,
+    List,
     Literal,
+    Sequence,
+    Tuple,
     TypedDict,
+    overload,
 )
 
 from ._attributes import Attributes
+from ._concurrency import AdaptiveConcurrency
+from ._list import ObjectMeta
+from ._stats import TransferStats
 from .store import ObjectStore
 
 if sys.version_info >= (3, 12):
@@ -54,21 +62,52 @@ If a `dict` is provided, it must meet the criteria of
 [`UpdateVersion`][obstore.UpdateVersion].
 """
 
-class PutResult(TypedDict):
+class PutResult:
     """
     Result for a put request.
-    """
 
-    e_tag: str | None
+    For compatibility with earlier versions, in which this was a dict, the fields can
+    also be read by indexing, e.g. `result["e_tag"]`.
     """
-    The unique identifier for the newly created object
 
-    <https://datatracker.ietf.org/doc/html/rfc9110#name-etag>
-    """
+    @property
+    def e_tag(self) -> str | None:
+        """
+        The unique identifier for the newly created object
 
-    version: str | None
-    """A version indicator for the newly created object."""
+        <https://datatracker.ietf.org/doc/html/rfc9110#name-etag>
+        """
+
+    @property
+    def version(self) -> str | None:
+        """A version indicator for the newly created object."""
+
+    def update_mode(self) -> UpdateVersion:
+        """The `mode` to pass to `put` to overwrite the new object only if it hasn't
+        changed since.
+
+        This makes compare-and-swap loops straightforward:
 
+        ```py
+        from obstore.exceptions import PreconditionError
+
+        mode = obs.put(store, "counter", b"0").update_mode()
+        value = 0
+        while True:
+            try:
+                obs.put(store, "counter", str(value + 1).encode(), mode=mode)
+                break
+            except PreconditionError:
+                # The counter changed since it was read, so read it again
+                result = obs.get(store, "counter")
+                mode = {"e_tag": result.meta["e_tag"], "version": result.meta["version"]}
+                value = int(result.bytes())
+        ```
+        """
+
+    def __getitem__(self, key: Literal["e_tag", "version"]) -> str | None: ...
+
+@overload
 def put(
     store: ObjectStore,
     path: str,
@@ -80,7 +119,35 @@ def put(
     use_multipart: bool | None = None,
     chunk_size: int = 5 * 1024 * 1024,
     max_concurrency: int = 12,
-) -> PutResult:
+    return_stats: Literal[True],
+) -> Tuple[PutResult, TransferStats]: ...
+@overload
+def put(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes] | Path | bytes | Buffer | Iterator[Buffer] | Iterable[Buffer],
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    mode: PutMode | None = None,
+    use_multipart: bool | None = None,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    return_stats: Literal[False] = False,
+) -> PutResult: ...
+def put(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes] | Path | bytes | Buffer | Iterator[Buffer] | Iterable[Buffer],
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    mode: PutMode | None = None,
+    use_multipart: bool | None = None,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    return_stats: bool = False,
+) -> PutResult | Tuple[PutResult, TransferStats]:
     """Save the provided bytes to the specified location
 
     The operation is guaranteed to be atomic, it will either successfully write the
@@ -125,11 +192,36 @@ def put(
             If this provided and is not `"overwrite"`, a non-multipart upload will be performed. Defaults to `"overwrite"`.
         attributes: Provide a set of `Attributes`. Defaults to `None`.
         tags: Provide tags for this object. Defaults to `None`.
-        use_multipart: Whether to use a multipart upload under the hood. Defaults using a multipart upload if the length of the file is greater than `chunk_size`. When `use_multipart` is `False`, the entire input will be materialized in memory as part of the upload.
+        use_multipart: Whether to use a multipart upload under the hood. Defaults using a multipart upload if the length of the file is greater than `chunk_size`. Iterables always use a multipart upload unless they yield no more than `chunk_size` bytes in total, in which case they're uploaded with a single request. When `use_multipart` is `False`, the entire input will be materialized in memory as part of the upload.
         chunk_size: The size of chunks to use within each part of the multipart upload. Defaults to 5 MB.
         max_concurrency: The maximum number of chunks to upload concurrently. Defaults to 12.
+        return_stats: If `True`, return a tuple of the result and the
+            [`TransferStats`][obstore.TransferStats] of this operation. Defaults to
+            `False`.
     """
 
+@overload
+async def put_async(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes]
+    | Path
+    | bytes
+    | Buffer
+    | AsyncIterator[Buffer]
+    | AsyncIterable[Buffer]
+    | Iterator[Buffer]
+    | Iterable[Buffer],
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    mode: PutMode | None = None,
+    use_multipart: bool | None = None,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    return_stats: Literal[True],
+) -> Tuple[PutResult, TransferStats]: ...
+@overload
 async def put_async(
     store: ObjectStore,
     path: str,
@@ -148,7 +240,28 @@ async def put_async(
     use_multipart: bool | None = None,
     chunk_size: int = 5 * 1024 * 1024,
     max_concurrency: int = 12,
-) -> PutResult:
+    return_stats: Literal[False] = False,
+) -> PutResult: ...
+async def put_async(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes]
+    | Path
+    | bytes
+    | Buffer
+    | AsyncIterator[Buffer]
+    | AsyncIterable[Buffer]
+    | Iterator[Buffer]
+    | Iterable[Buffer],
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    mode: PutMode | None = None,
+    use_multipart: bool | None = None,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    return_stats: bool = False,
+) -> PutResult | Tuple[PutResult, TransferStats]:
     """Call `put` asynchronously.
 
     Refer to the documentation for [`put`][obstore.put]. In addition to what the
@@ -168,3 +281,156 @@ async def put_async(
     await obs.put_async(store2, path2)
     ```
     """
+
+def put_and_confirm(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes] | Path | bytes | Buffer | Iterator[Buffer] | Iterable[Buffer],
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    use_multipart: bool | None = None,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    max_attempts: int = 10,
+    init_backoff: timedelta = timedelta(milliseconds=100),
+    max_backoff: timedelta = timedelta(seconds=5),
+) -> ObjectMeta:
+    """Upload an object and wait until it is visible to reads.
+
+    Eventually consistent gateways and caches in front of a store may keep serving a
+    previous version of an object, or no object at all, for a while after an upload
+    completes. This uploads `file` like [`put`][obstore.put], and then calls
+    [`head`][obstore.head] until it returns the `e_tag` and `version` reported by the
+    upload, as well as the expected size when the size of `file` is known up front.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path within ObjectStore for where to save the file.
+        file: The object to upload. Refer to [`put`][obstore.put].
+
+    Keyword args:
+        attributes: Provide a set of `Attributes`. Defaults to `None`.
+        tags: Provide tags for this object. Defaults to `None`.
+        use_multipart: Whether to use a multipart upload under the hood. Refer to
+            [`put`][obstore.put].
+        chunk_size: The size of chunks to use within each part of the multipart
+            upload. Defaults to 5 MB.
+        max_concurrency: The maximum number of chunks to upload concurrently. Defaults
+            to 12.
+        max_attempts: The maximum number of `head` requests to make. Defaults to 10.
+        init_backoff: The time to wait after the first `head` request that doesn't
+            see the upload. The wait doubles after each further attempt, and each
+            wait is drawn at random from the upper half of the current one (see
+            [`seed_jitter`][obstore.seed_jitter]). Defaults to 100 milliseconds.
+        max_backoff: The maximum time to wait between `head` requests. Defaults to 5
+            seconds.
+
+    Raises:
+        GenericError: if the upload still isn't visible after `max_attempts` requests.
+
+    Returns:
+        The metadata of the uploaded object, as returned by `head`.
+    """
+
+async def put_and_confirm_async(
+    store: ObjectStore,
+    path: str,
+    file: IO[bytes]
+    | Path
+    | bytes
+    | Buffer
+    | AsyncIterator[Buffer]
+    | AsyncIterable[Buffer]
+    | Iterator[Buffer]
+    | Iterable[Buffer],
+    *,
+    attributes: Attributes | None = None,
+    tags: Dict[str, str] | None = None,
+    use_multipart: bool | None = None,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    max_attempts: int = 10,
+    init_backoff: timedelta = timedelta(milliseconds=100),
+    max_backoff: timedelta = timedelta(seconds=5),
+) -> ObjectMeta:
+    """Call `put_and_confirm` asynchronously.
+
+    Refer to the documentation for [`put_and_confirm`][obstore.put_and_confirm]. Like
+    [`put_async`][obstore.put_async], this also supports an async iterator or iterable
+    as `file`.
+    """
+
+def put_many(
+    store: ObjectStore,
+    items: Sequence[
+        Tuple[
+            str,
+            IO[bytes] | Path | bytes | Buffer | Iterator[Buffer] | Iterable[Buffer],
+        ]
+    ],
+    *,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> List[PutResult | Exception]:
+    """Upload many objects concurrently.
+
+    Each item is a `(path, file)` pair, where `file` is any input accepted by
+    [`put`][obstore.put]. At most `max_concurrency` uploads are in flight at once, and
+    the rest wait for one to finish, so that the uploads don't overwhelm the store or
+    the network.
+
+    A failed upload doesn't stop the others. Its exception is returned in place of its
+    result instead of being raised:
+
+    ```py
+    items = [("a.txt", b"a"), ("b.txt", b"b")]
+    results = obs.put_many(store, items)
+    for (path, _), result in zip(items, results):
+        if isinstance(result, Exception):
+            print(f"Failed to upload {path}: {result}")
+    ```
+
+    Inputs larger than `chunk_size` are uploaded with multipart uploads, whose parts are
+    uploaded one at a time, as the objects themselves are uploaded concurrently.
+
+    Args:
+        store: The ObjectStore instance to use.
+        items: The paths to upload to and the objects to upload to them.
+
+    Keyword Args:
+        chunk_size: The size of the parts of multipart uploads. Defaults to 5 MB.
+        max_concurrency: The maximum number of objects uploaded at once, or an
+            [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency] to adjust it while
+            the uploads run. Defaults to `12`.
+
+    Returns:
+        The [`PutResult`][obstore.PutResult] of each upload, or the exception it failed
+            with, in the order of `items`.
+    """
+
+async def put_many_async(
+    store: ObjectStore,
+    items: Sequence[
+        Tuple[
+            str,
+            IO[bytes]
+            | Path
+            | bytes
+            | Buffer
+            | AsyncIterator[Buffer]
+            | AsyncIterable[Buffer]
+            | Iterator[Buffer]
+            | Iterable[Buffer],
+        ]
+    ],
+    *,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> List[PutResult | Exception]:
+    """Call `put_many` asynchronously.
+
+    Refer to the documentation for [`put_many`][obstore.put_many]. Like
+    [`put_async`][obstore.put_async], this also supports async iterators or iterables
+    as input.
+    """
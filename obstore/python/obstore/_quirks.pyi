@@ -0,0 +1,51 @@
+def register_quirks(
+    pattern: str,
+    *,
+    ignores_if_none_match: bool = False,
+    emulate_if_none_match: bool = False,
+    lacks_delete_objects: bool = False,
+    lacks_upload_part_copy: bool = False,
+) -> None:
+    """Register the gaps of an S3-compatible implementation, so that stores using it
+    fall back to requests it does support.
+
+    Quirks apply to every [`S3Store`][obstore.store.S3Store] whose
+    [`endpoint_url`][obstore.store.S3Store.endpoint_url] has a host matching `pattern`,
+    including stores created before they were registered:
+
+    ```py
+    import obstore as obs
+    from obstore.store import S3Store
+
+    obs.register_quirks("*.storage.internal:9000", lacks_delete_objects=True)
+
+    store = S3Store("bucket", endpoint="http://minio.storage.internal:9000")
+    assert store.quirks["lacks_delete_objects"]
+    ```
+
+    The quirks of all patterns matching an endpoint are combined. Google Cloud
+    Storage's S3-compatible API (`storage.googleapis.com`) is registered by default as
+    lacking `DeleteObjects` and `UploadPartCopy`.
+
+    Args:
+        pattern: The host of the endpoints, including its port if it has one. `*`
+            matches any run of characters, such as `*.example.com`. Matched without
+            regard to case.
+
+    Keyword Args:
+        ignores_if_none_match: Conditional puts with `If-None-Match: *` overwrite
+            existing objects, so puts with `mode="create"` raise `NotImplementedError`
+            rather than risk overwriting an object.
+        emulate_if_none_match: Puts with `mode="create"` check that the object doesn't
+            exist first, for endpoints that overwrite objects on conditional puts. This
+            isn't atomic, so a concurrent put can still be overwritten. Implies
+            `ignores_if_none_match`.
+        lacks_delete_objects: Bulk deletes with `DeleteObjects` aren't supported, so
+            deleting many paths makes a request for each path.
+        lacks_upload_part_copy: Multipart copies with `UploadPartCopy` aren't supported,
+            so [`copy`][obstore.copy] streams objects above its `multipart_threshold`
+            through the client.
+
+    Registering a pattern again replaces its quirks. Registering it without any quirks
+    removes it, which also removes the quirks registered by default.
+    """
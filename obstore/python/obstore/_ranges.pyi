@@ -0,0 +1,38 @@
+from typing import List, Sequence, Tuple
+
+def plan_ranges(
+    ranges: Sequence[Tuple[int, int]],
+    *,
+    max_gap: int = 1024 * 1024,
+    max_request_size: int | None = None,
+) -> List[Tuple[int, int]]:
+    """Plan the requests to make to fetch the given byte ranges.
+
+    This exposes the coalescing applied by [`get_ranges`][obstore.get_ranges], so that
+    readers of formats like Parquet or Zarr can plan their requests the same way:
+    ranges are sorted by their start, and ranges that overlap or are at most `max_gap`
+    bytes apart are merged into a single request. Requests larger than
+    `max_request_size` are split into several requests.
+
+    ```py
+    import obstore as obs
+
+    obs.plan_ranges([(0, 10), (20, 30), (5_000_000, 5_000_010)])
+    # [(0, 30), (5000000, 5000010)]
+    obs.plan_ranges([(0, 100)], max_request_size=40)
+    # [(0, 40), (40, 80), (80, 100)]
+    ```
+
+    Args:
+        ranges: A sequence of `(start, end)` byte ranges, where `end` is exclusive.
+
+    Keyword args:
+        max_gap: The largest gap in bytes between two ranges that are merged into one
+            request. Defaults to 1 MiB, the gap used by `get_ranges`.
+        max_request_size: The maximum size in bytes of a single request. Ranges aren't
+            merged if the merged request would exceed this, and larger ranges are split.
+            Defaults to `None`, for no maximum.
+
+    Returns:
+        The `(start, end)` byte ranges to request, sorted by their start.
+    """
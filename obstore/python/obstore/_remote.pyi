@@ -0,0 +1,130 @@
+from typing import List
+
+from ._concurrency import AdaptiveConcurrency
+from ._put import PutResult
+from .store import ObjectStore
+
+def put_from_url(
+    store: ObjectStore,
+    path: str,
+    url: str,
+    *,
+    streaming: bool = True,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> PutResult:
+    """Download an HTTP(S) URL and save it to the specified location.
+
+    The download happens entirely in Rust: the response body never passes through
+    Python. With `streaming=True`, each chunk of the response is uploaded as it
+    arrives, so memory use stays bounded regardless of the size of the remote file.
+    Responses whose `Content-Length` is at most `chunk_size` are saved with a single
+    request instead.
+
+    !!! warning "Aborted multipart uploads"
+        Streaming uses a multipart upload, which is aborted if the download fails. See
+        [`put`][obstore.put] for why lifecycle rules for aborted multipart uploads are
+        still recommended.
+
+    Args:
+        store: The ObjectStore instance to save the file to.
+        path: The path within ObjectStore for where to save the file.
+        url: The `http://` or `https://` URL to download.
+
+    Keyword args:
+        streaming: Whether to stream the download through a multipart upload. When
+            `False`, the entire response is buffered in memory and saved with a single
+            request. Defaults to `True`.
+        chunk_size: The size of chunks to use within each part of the multipart upload.
+            Defaults to 5 MB.
+        max_concurrency: The maximum number of chunks to upload concurrently. Defaults
+            to 12.
+
+    Raises:
+        FileNotFoundError: If the URL returns a 404 response.
+    """
+
+async def put_from_url_async(
+    store: ObjectStore,
+    path: str,
+    url: str,
+    *,
+    streaming: bool = True,
+    chunk_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+) -> PutResult:
+    """Call `put_from_url` asynchronously.
+
+    Refer to the documentation for [put_from_url][obstore.put_from_url].
+    """
+
+def mirror_http(
+    index_url: str,
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    include_glob: str | None = None,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> List[str]:
+    """Mirror the files of an HTTP directory index into a store.
+
+    The index at `index_url` is crawled recursively: links ending in `/` are followed
+    as subdirectories, and every other link is downloaded with
+    [`put_from_url`][obstore.put_from_url] and saved under `prefix`, preserving its
+    path relative to `index_url`. Links that resolve outside of `index_url` (such as
+    parent directories or absolute links to other sites) and links with a query string
+    (such as column sorting links) are ignored.
+
+    Both HTML and JSON indices are supported:
+
+    - HTML indices, such as those generated by Apache, nginx, or Python's `http.server`,
+      are scanned for `href` attributes.
+    - JSON indices must be an array whose entries are either file names, or objects
+      with a `name` key and an optional `type` key, where `"directory"` marks a
+      subdirectory. This matches nginx's `autoindex_format json`.
+
+    **Example**:
+
+    ```py
+    import obstore as obs
+    from obstore.store import S3Store
+
+    store = S3Store("my-bucket")
+    obs.mirror_http(
+        "https://example.com/data/",
+        store,
+        "mirror/data",
+        include_glob="**/*.csv",
+    )
+    ```
+
+    Args:
+        index_url: The URL of the directory index to mirror.
+        store: The ObjectStore instance to save the files to.
+        prefix: The path within the store to save the files under. Defaults to the root
+            of the store.
+
+    Keyword args:
+        include_glob: Only mirror files whose path relative to `index_url` matches this
+            glob pattern. `*` doesn't match across `/`, use `**` for that. Defaults to
+            `None` (mirror all files).
+        max_concurrency: The maximum number of files to download concurrently, or an
+            [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency] to adjust it while
+            mirroring. Defaults to 12.
+
+    Returns:
+        The sorted paths within the store of the mirrored files.
+    """
+
+async def mirror_http_async(
+    index_url: str,
+    store: ObjectStore,
+    prefix: str | None = None,
+    *,
+    include_glob: str | None = None,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> List[str]:
+    """Call `mirror_http` asynchronously.
+
+    Refer to the documentation for [mirror_http][obstore.mirror_http].
+    """
@@ -14,8 +14,13 @@ def rename(store: ObjectStore, from_: str, to: str, *, overwrite: bool = True) -
 
     Keyword Args:
         overwrite: If `True`, if there exists an object at the destination, it will be
-            overwritten. If `False`, will return an error if the destination already has
-            an object.
+            overwritten. If `False`, the object is copied only if the destination is
+            empty, atomically where the store supports it, and then the source is
+            deleted. Raises
+            [`AlreadyExistsError`][obstore.exceptions.AlreadyExistsError] if the
+            destination already has an object, and
+            [`NotSupportedError`][obstore.exceptions.NotSupportedError] if the store
+            can't copy atomically, like S3 without `copy_if_not_exists` configured.
     """
 
 async def rename_async(
@@ -0,0 +1,59 @@
+from datetime import timedelta
+from typing import Callable, TypedDict
+
+class RetryEvent(TypedDict):
+    """A request retried by object_store, passed to the hook set with
+    [`set_retry_hook`][obstore.set_retry_hook]."""
+
+    attempt: int | None
+    """The number of the retry about to be made, starting at 1."""
+
+    max_retries: int | None
+    """The `max_retries` of the store's `retry_config`."""
+
+    delay: timedelta | None
+    """How long object_store waits before retrying."""
+
+    message: str
+    """The message object_store reported the retry with, which includes the error of
+    transport failures."""
+
+def set_retry_hook(hook: Callable[[RetryEvent], None] | None, /) -> None:
+    """Call `hook` each time object_store retries a request, or stop with `None`.
+
+    object_store retries requests that fail with server errors, dropped connections and
+    timeouts within each call, as configured with a store's `retry_config`, and only
+    raises once the retries are exhausted. The hook makes these retries visible, to log
+    or count them:
+
+    ```py
+    import logging
+
+    import obstore as obs
+
+    logger = logging.getLogger("obstore.retries")
+
+    def on_retry(event):
+        logger.warning(
+            "Retry %s of %s in %s: %s",
+            event["attempt"],
+            event["max_retries"],
+            event["delay"],
+            event["message"],
+        )
+
+    obs.set_retry_hook(on_retry)
+    ```
+
+    The hook is called from the thread making the request, before waiting for `delay`,
+    and is shared by all stores. object_store doesn't report which store or path is
+    being retried, so neither is passed to the hook. The fields that can't be read from
+    object_store's message are `None`. Exceptions raised by the hook are reported with
+    [`sys.unraisablehook`][sys.unraisablehook] and don't fail the request.
+
+    Args:
+        hook: The callable to call with each retry.
+
+    Raises:
+        RuntimeError: if another `tracing` subscriber was installed in the process.
+    """
@@ -0,0 +1,153 @@
+import sys
+from datetime import datetime, timedelta
+from typing import Callable, Literal
+
+from .store import ObjectStore
+
+if sys.version_info >= (3, 12):
+    from collections.abc import Buffer
+else:
+    from typing_extensions import Buffer
+
+RollingNaming = Literal["timestamp", "sequence"] | Callable[[int, datetime], str]
+"""How a rolling writer names the objects it publishes.
+
+- `"timestamp"`: the UTC time the first record of the object was written, e.g.
+  `20250114T093012.123456Z`.
+- `"sequence"`: a 20-digit zero-padded counter, continuing after the highest one
+  already under the prefix, so that objects sort in the order they were written.
+- A callable of `(index, opened_at)` returning the name, where `index` counts the
+  objects published by this writer and `opened_at` is the UTC time the first record of
+  the object was written. The name may contain `/` to publish into nested prefixes.
+"""
+
+def open_rolling_writer(
+    store: ObjectStore,
+    prefix: str,
+    *,
+    max_size: int = 64 * 1024 * 1024,
+    max_interval: timedelta | None = None,
+    naming: RollingNaming = "timestamp",
+    suffix: str = "",
+) -> RollingWriter:
+    """Open a writer that appends records to a sequence of objects under `prefix`.
+
+    Records are buffered in memory, and published as a new object once `max_size`
+    bytes have been written or the oldest buffered record is older than
+    `max_interval`. This covers the common pattern of shipping logs or events to a
+    bucket:
+
+    ```py
+    from datetime import timedelta
+
+    import obstore as obs
+
+    writer = obs.open_rolling_writer(
+        store,
+        "logs/app",
+        max_size=16 * 1024 * 1024,
+        max_interval=timedelta(minutes=5),
+        suffix=".log",
+    )
+    for line in lines:
+        writer.write(line)
+    writer.close()
+    ```
+
+    Each object is uploaded in a single request, so readers never see a partially
+    written object. Objects are created with `mode="create"` where the store supports
+    it, so that writers sharing a prefix don't overwrite each other's objects; on a
+    name collision the next name is tried.
+
+    `max_interval` is checked when records are written. Call
+    [`flush`][obstore.RollingWriter.flush] periodically to publish buffered records
+    when writes may pause for longer.
+
+    Args:
+        store: The ObjectStore instance to use.
+        prefix: The prefix to publish objects under.
+
+    Keyword args:
+        max_size: The number of bytes after which to publish the buffered records.
+            Defaults to 64 MB.
+        max_interval: The age of the oldest buffered record after which to publish
+            them. Defaults to `None`, which only rolls on size.
+        naming: How to name the published objects. Defaults to `"timestamp"`.
+        suffix: A suffix appended to each object name, e.g. a file extension.
+
+    Returns:
+        RollingWriter
+    """
+
+async def open_rolling_writer_async(
+    store: ObjectStore,
+    prefix: str,
+    *,
+    max_size: int = 64 * 1024 * 1024,
+    max_interval: timedelta | None = None,
+    naming: RollingNaming = "timestamp",
+    suffix: str = "",
+) -> AsyncRollingWriter:
+    """Call `open_rolling_writer` asynchronously, returning a writer with asynchronous
+    operations.
+
+    Refer to the documentation for [open_rolling_writer][obstore.open_rolling_writer].
+    """
+
+class RollingWriter:
+    """A rolling writer with synchronous operations."""
+
+    @property
+    def bytes_buffered(self) -> int:
+        """The number of bytes written that haven't been published yet."""
+
+    @property
+    def objects_published(self) -> int:
+        """The number of objects published by this writer."""
+
+    def write(self, buf: Buffer, /) -> str | None:
+        """Append `buf` to the current object.
+
+        Returns the path of the object published as a result of this write, if any.
+        """
+
+    def flush(self) -> str | None:
+        """Publish the buffered records now, returning the path of the new object.
+
+        Returns `None` if no records are buffered.
+        """
+
+    def close(self) -> str | None:
+        """Publish the buffered records and close the writer.
+
+        Returns the path of the last object published, if any records were buffered.
+        """
+
+class AsyncRollingWriter:
+    """A rolling writer with **asynchronous** operations."""
+
+    @property
+    def bytes_buffered(self) -> int:
+        """The number of bytes written that haven't been published yet."""
+
+    @property
+    def objects_published(self) -> int:
+        """The number of objects published by this writer."""
+
+    async def write(self, buf: Buffer, /) -> str | None:
+        """Append `buf` to the current object.
+
+        Returns the path of the object published as a result of this write, if any.
+        """
+
+    async def flush(self) -> str | None:
+        """Publish the buffered records now, returning the path of the new object.
+
+        Returns `None` if no records are buffered.
+        """
+
+    async def close(self) -> str | None:
+        """Publish the buffered records and close the writer.
+
+        Returns the path of the last object published, if any records were buffered.
+        """
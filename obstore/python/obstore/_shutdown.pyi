@@ -0,0 +1,69 @@
+from datetime import timedelta
+from typing import List, TypedDict
+
+class ShutdownReport(TypedDict):
+    """What [`shutdown`][obstore.shutdown] had to cancel or clean up."""
+
+    cancelled: int
+    """The number of async operations that were cancelled after the timeout."""
+
+    still_running: int
+    """The number of sync calls, from other threads, still running after the timeout.
+
+    These can't be cancelled, but fail if they try to start another operation.
+    """
+
+    flushed: List[str]
+    """The paths of the objects published by rolling writers and record sinks that were
+    still open."""
+
+    aborted_uploads: List[str]
+    """The paths of the multipart and sparse uploads that were still open, and whose
+    uploads were aborted."""
+
+    errors: List[str]
+    """Errors raised while finalizing open writers."""
+
+def shutdown(*, timeout: timedelta | None = None) -> ShutdownReport:
+    """Stop obstore, before terminating the process.
+
+    Once called, every new operation raises a `RuntimeError`, whether it's sync or async.
+    Operations already in flight, including those submitted through
+    [`obstore.background`][obstore.background], are then given `timeout` to complete.
+    Async operations that are still pending after it are cancelled, raising a
+    `RuntimeError` to whoever awaits them.
+
+    Finally, writers that were opened but not closed are finalized, so that they don't
+    leave incomplete multipart uploads behind, which are billed until they're aborted:
+
+    - [`MultipartWriter`][obstore.MultipartWriter] and
+      [`SparseWriter`][obstore.SparseWriter] uploads are aborted, as the data written so
+      far may not be complete.
+    - [`RollingWriter`][obstore.RollingWriter] and [`RecordSink`][obstore.RecordSink]
+      publish their buffered records, as on `close()`.
+
+    Finalizing is also bounded by `timeout`.
+
+    Shutting down can't be undone: obstore can't be used again in the same process.
+
+    ```py
+    import signal
+    from datetime import timedelta
+
+    import obstore as obs
+
+    def on_sigterm(signum, frame):
+        report = obs.shutdown(timeout=timedelta(seconds=20))
+        if report["cancelled"] or report["aborted_uploads"]:
+            print("Not everything completed before shutdown:", report)
+
+    signal.signal(signal.SIGTERM, on_sigterm)
+    ```
+
+    Keyword Args:
+        timeout: How long to wait for in-flight operations to complete. Defaults to
+            `None`, waiting until they have all completed.
+
+    Returns:
+        What was cancelled or cleaned up.
+    """
@@ -36,14 +36,45 @@ def sign(
     doesn't have access to the object store's credentials, to allow limited access to
     the object store.
 
+    How URLs are signed depends on the store:
+
+    - `S3Store`: URLs are presigned with AWS Signature Version 4, using the
+      credentials of the store.
+    - `GCSStore`: URLs are signed with V4 signing. With a service account key, they're
+      signed locally with its private key. With other credentials, such as those of a
+      VM or workload identity, they're signed by the IAM `signBlob` API as the service
+      account of the credentials, which needs the
+      `iam.serviceAccounts.signBlob` permission. Paths are under the prefix of the
+      store, if any.
+    - `AzureStore`: URLs carry a service SAS token. With an account key, the token is
+      signed locally with it. With Entra ID credentials, it's signed with a user
+      delegation key fetched for the credentials. Stores configured with a SAS token
+      can't sign URLs.
+
+    ```py
+    from datetime import timedelta
+
+    url = obs.sign(store, "GET", "data/file.parquet", timedelta(hours=1))
+    ```
+
+    To sign many URLs, pass all of their paths at once. They're signed in a single
+    call, without releasing and reacquiring the GIL or entering the runtime for each
+    path:
+
+    ```py
+    paths = [f"data/part-{i}.parquet" for i in range(10_000)]
+    urls = obs.sign(store, "GET", paths, timedelta(hours=1))
+    ```
+
     Args:
         store: The ObjectStore instance to use.
         method: The HTTP method to use.
-        paths: The path(s) within ObjectStore to retrieve. If
+        paths: The path(s) within ObjectStore to retrieve. If a sequence of paths is
+            passed, a list of URLs is returned, in the same order.
         expires_in: How long the signed URL(s) should be valid.
 
     Returns:
-        _description_
+        The signed URL, or the signed URLs of `paths` if a sequence was passed.
     """
 
 @overload
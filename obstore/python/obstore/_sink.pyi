@@ -0,0 +1,161 @@
+from datetime import timedelta
+from typing import Any, Iterable, Literal, Mapping, Sequence
+
+from ._rolling import RollingNaming
+from .store import ObjectStore
+
+RecordFormat = Literal["ndjson", "csv"]
+"""The formats a record sink can serialize rows to."""
+
+def open_record_sink(
+    store: ObjectStore,
+    prefix: str,
+    *,
+    format: RecordFormat = "ndjson",
+    columns: Sequence[str] | None = None,
+    max_size: int = 64 * 1024 * 1024,
+    max_interval: timedelta | None = None,
+    naming: RollingNaming = "timestamp",
+    suffix: str | None = None,
+) -> RecordSink:
+    """Open a sink that serializes rows and appends them to objects under `prefix`.
+
+    Rows are serialized to NDJSON or CSV in Rust, and published through a
+    [rolling writer][obstore.open_rolling_writer], which starts a new object once the
+    current one reaches `max_size` bytes or `max_interval` age. Writes that fill an
+    object wait for it to be uploaded, so a producer can't outrun the store.
+
+    ```py
+    import obstore as obs
+
+    sink = obs.open_record_sink(store, "events", format="csv", max_size=8 * 1024 * 1024)
+    for event in events:
+        sink.write_row({"time": event.time, "kind": event.kind, "value": event.value})
+    sink.close()
+    ```
+
+    Values may be `None`, `bool`, `int`, `float`, `str`, lists, tuples and dicts of
+    these, and objects with an `isoformat` method such as
+    [`datetime`][datetime.datetime], which are written in ISO 8601 format. In CSV,
+    `None` is written as an empty cell and lists and dicts as JSON.
+
+    Rows passed to a single call to [`write_rows`][obstore.RecordSink.write_rows] are
+    always published in the same object, and no row is ever split across objects.
+
+    Args:
+        store: The ObjectStore instance to use.
+        prefix: The prefix to publish objects under.
+
+    Keyword args:
+        format: The format to serialize rows to. Defaults to `"ndjson"`.
+        columns: The columns of the rows. CSV objects start with a header row of
+            these columns, and rows with other keys are rejected. Rows may omit
+            columns, which are written as empty cells in CSV. Defaults to the keys of
+            the first row for CSV. For NDJSON, rows are only checked if `columns` is
+            passed.
+        max_size: The number of bytes after which to publish a new object. Defaults to
+            64 MB.
+        max_interval: The age of the oldest buffered row after which to publish a new
+            object. Defaults to `None`, which only rolls on size.
+        naming: How to name the published objects. Defaults to `"timestamp"`.
+        suffix: A suffix appended to each object name. Defaults to `".ndjson"` or
+            `".csv"`, depending on `format`.
+
+    Returns:
+        RecordSink
+    """
+
+async def open_record_sink_async(
+    store: ObjectStore,
+    prefix: str,
+    *,
+    format: RecordFormat = "ndjson",
+    columns: Sequence[str] | None = None,
+    max_size: int = 64 * 1024 * 1024,
+    max_interval: timedelta | None = None,
+    naming: RollingNaming = "timestamp",
+    suffix: str | None = None,
+) -> AsyncRecordSink:
+    """Call `open_record_sink` asynchronously, returning a sink with asynchronous
+    operations.
+
+    Refer to the documentation for [open_record_sink][obstore.open_record_sink].
+    """
+
+class RecordSink:
+    """A record sink with synchronous operations."""
+
+    @property
+    def bytes_buffered(self) -> int:
+        """The number of serialized bytes that haven't been published yet."""
+
+    @property
+    def objects_published(self) -> int:
+        """The number of objects published by this sink."""
+
+    @property
+    def columns(self) -> list[str] | None:
+        """The columns of the rows, if known."""
+
+    def write_row(self, row: Mapping[str, Any], /) -> str | None:
+        """Append a row.
+
+        Returns the path of the object published as a result of this write, if any.
+        """
+
+    def write_rows(self, rows: Iterable[Mapping[str, Any]], /) -> str | None:
+        """Append several rows, which are published in the same object.
+
+        Returns the path of the object published as a result of this write, if any.
+        """
+
+    def flush(self) -> str | None:
+        """Publish the buffered rows now, returning the path of the new object.
+
+        Returns `None` if no rows are buffered.
+        """
+
+    def close(self) -> str | None:
+        """Publish the buffered rows and close the sink.
+
+        Returns the path of the last object published, if any rows were buffered.
+        """
+
+class AsyncRecordSink:
+    """A record sink with **asynchronous** operations."""
+
+    @property
+    def bytes_buffered(self) -> int:
+        """The number of serialized bytes that haven't been published yet."""
+
+    @property
+    def objects_published(self) -> int:
+        """The number of objects published by this sink."""
+
+    @property
+    def columns(self) -> list[str] | None:
+        """The columns of the rows, if known."""
+
+    async def write_row(self, row: Mapping[str, Any], /) -> str | None:
+        """Append a row.
+
+        Returns the path of the object published as a result of this write, if any.
+        """
+
+    async def write_rows(self, rows: Iterable[Mapping[str, Any]], /) -> str | None:
+        """Append several rows, which are published in the same object.
+
+        Returns the path of the object published as a result of this write, if any.
+        """
+
+    async def flush(self) -> str | None:
+        """Publish the buffered rows now, returning the path of the new object.
+
+        Returns `None` if no rows are buffered.
+        """
+
+    async def close(self) -> str | None:
+        """Publish the buffered rows and close the sink.
+
+        Returns the path of the last object published, if any rows were buffered.
+        """
@@ -0,0 +1,106 @@
+from datetime import timedelta
+from typing import Sequence
+
+from ._concurrency import AdaptiveConcurrency
+from ._get import GetOptions, GetResult
+from .store import ObjectStore
+
+def snapshot_token(
+    store: ObjectStore,
+    paths: Sequence[str],
+    *,
+    expires_in: timedelta | None = None,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> str:
+    """Pin the current version of a set of objects in a compact token.
+
+    The token records the ETag, and the version where the store reports one, of each
+    object in `paths`. Readers that share the token and read the objects through
+    [`get_pinned`][obstore.get_pinned] are guaranteed to see the same content, or an
+    error if an object was modified since. This gives distributed readers, such as the
+    workers of a batch job, a consistent view of a set of objects that may be
+    overwritten while they read.
+
+    The token is a URL-safe string that can be passed between processes.
+
+    ```py
+    import obstore as obs
+
+    token = obs.snapshot_token(store, ["data/a.parquet", "data/b.parquet"])
+
+    # In another process
+    obs.get_pinned(store, token, "data/a.parquet").bytes()
+    ```
+
+    Args:
+        store: The ObjectStore instance to use.
+        paths: The paths of the objects to pin.
+
+    Keyword Args:
+        expires_in: How long the token remains valid. Defaults to `None`, which creates
+            a token that doesn't expire.
+        max_concurrency: The maximum number of objects to fetch the metadata of
+            concurrently, or an [`AdaptiveConcurrency`][obstore.AdaptiveConcurrency].
+            Defaults to 12.
+
+    Raises:
+        FileNotFoundError: If an object doesn't exist.
+        ValueError: If the store doesn't report an ETag for an object.
+
+    Returns:
+        The snapshot token.
+    """
+
+async def snapshot_token_async(
+    store: ObjectStore,
+    paths: Sequence[str],
+    *,
+    expires_in: timedelta | None = None,
+    max_concurrency: int | AdaptiveConcurrency = 12,
+) -> str:
+    """Call `snapshot_token` asynchronously.
+
+    Refer to the documentation for [snapshot_token][obstore.snapshot_token].
+    """
+
+def get_pinned(
+    store: ObjectStore,
+    token: str,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+) -> GetResult:
+    """Read an object as pinned by a snapshot token.
+
+    This behaves like [`get`][obstore.get], with `if_match` set to the ETag recorded
+    in `token`, and `version` set to the recorded version if there is one.
+
+    Args:
+        store: The ObjectStore instance to use.
+        token: A token created by [`snapshot_token`][obstore.snapshot_token].
+        path: The path of the object to read. It must be pinned by `token`.
+
+    Keyword Args:
+        options: Additional options for the request. `if_match` is always replaced by
+            the pinned ETag.
+
+    Raises:
+        ValueError: If `token` is invalid or has expired.
+        KeyError: If `path` is not pinned by `token`.
+        PreconditionError: If the object was modified since the token was created.
+
+    Returns:
+        The result of the request.
+    """
+
+async def get_pinned_async(
+    store: ObjectStore,
+    token: str,
+    path: str,
+    *,
+    options: GetOptions | None = None,
+) -> GetResult:
+    """Call `get_pinned` asynchronously.
+
+    Refer to the documentation for [get_pinned][obstore.get_pinned].
+    """
@@ -0,0 +1,115 @@
+import sys
+
+from ._put import PutResult
+from .store import ObjectStore
+
+if sys.version_info >= (3, 12):
+    from collections.abc import Buffer
+else:
+    from typing_extensions import Buffer
+
+def open_sparse_writer(
+    store: ObjectStore,
+    path: str,
+    *,
+    part_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    max_pending_size: int = 256 * 1024 * 1024,
+) -> SparseWriter:
+    """Open a writer that assembles one object from regions written in any order.
+
+    This enables parallel producers that each generate a different byte range of the
+    same output object:
+
+    ```py
+    import obstore as obs
+
+    writer = obs.open_sparse_writer(store, "output.bin")
+    writer.write(1024, second_half)
+    writer.write(0, first_half)
+    writer.finish()
+    ```
+
+    On an [`S3Store`][obstore.store.S3Store], [`GCSStore`][obstore.store.GCSStore] or
+    [`AzureStore`][obstore.store.AzureStore], each part of the multipart upload is
+    uploaded as soon as all of its bytes have been written, in any order, so only the
+    parts that are partially written are buffered in memory. Other stores can only
+    upload parts in order, so regions are buffered until they form a contiguous run
+    from the start of the object.
+
+    Either way, at most `max_pending_size` bytes are buffered. Writing a region that
+    would exceed that raises an error, and the region can be written again once the
+    regions before it have been.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path within ObjectStore for where to save the object.
+
+    Keyword args:
+        part_size: The size of each part of the multipart upload. Defaults to 5 MB.
+        max_concurrency: The maximum number of parts to upload concurrently. Defaults
+            to 12.
+        max_pending_size: The maximum number of bytes buffered waiting for other
+            regions to be written. Defaults to 256 MiB.
+
+    Returns:
+        SparseWriter
+    """
+
+async def open_sparse_writer_async(
+    store: ObjectStore,
+    path: str,
+    *,
+    part_size: int = 5 * 1024 * 1024,
+    max_concurrency: int = 12,
+    max_pending_size: int = 256 * 1024 * 1024,
+) -> AsyncSparseWriter:
+    """Call `open_sparse_writer` asynchronously, returning a writer with asynchronous
+    operations.
+
+    Refer to the documentation for [open_sparse_writer][obstore.open_sparse_writer].
+    """
+
+class SparseWriter:
+    """A writer of object regions with synchronous operations."""
+
+    def write(self, offset: int, buf: Buffer, /) -> None:
+        """Write `buf` at byte `offset` of the object.
+
+        Raises:
+            ValueError: if the region overlaps a region that was already written, or
+                buffering it would exceed `max_pending_size`.
+        """
+
+    def finish(self) -> PutResult:
+        """Complete the upload, making the object visible.
+
+        Raises:
+            ValueError: if there are gaps between the written regions. The writer is
+                left open, so the gaps can still be filled in.
+        """
+
+    def abort(self) -> None:
+        """Abort the upload and discard all regions written so far."""
+
+class AsyncSparseWriter:
+    """A writer of object regions with **asynchronous** operations."""
+
+    async def write(self, offset: int, buf: Buffer, /) -> None:
+        """Write `buf` at byte `offset` of the object.
+
+        Raises:
+            ValueError: if the region overlaps a region that was already written, or
+                buffering it would exceed `max_pending_size`.
+        """
+
+    async def finish(self) -> PutResult:
+        """Complete the upload, making the object visible.
+
+        Raises:
+            ValueError: if there are gaps between the written regions. The writer is
+                left open, so the gaps can still be filled in.
+        """
+
+    async def abort(self) -> None:
+        """Abort the upload and discard all regions written so far."""
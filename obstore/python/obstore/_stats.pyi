@@ -0,0 +1,48 @@
+from datetime import timedelta
+
+class TransferStats:
+    """Bytes transferred by a single operation, and how long the server took to respond.
+
+    Returned alongside the result of operations called with `return_stats=True`.
+
+    The counts include the payload of each successful request and response made by the
+    operation. The bytes of retried requests, HTTP headers and TLS overhead are handled
+    inside the underlying Rust client and aren't visible to obstore, so these counts are
+    a lower bound of the bytes transferred over the network. How many requests were
+    retried is counted in [`retries`][obstore.TransferStats.retries], to tell how far off
+    they may be.
+
+    The counts are updated as data is transferred, so for a download returning a
+    [`GetResult`][obstore.GetResult], they keep growing while its data is read.
+
+    [`time_to_first_byte`][obstore.TransferStats.time_to_first_byte] is measured by
+    the client, and includes both network and server latency. Compare it with the
+    time it takes to read the rest of the response to tell them apart. Server-side
+    timing and billing headers aren't exposed by the underlying Rust client, so they
+    can't be reported here.
+    """
+
+    @property
+    def bytes_sent(self) -> int:
+        """The number of payload bytes uploaded."""
+
+    @property
+    def bytes_received(self) -> int:
+        """The number of payload bytes downloaded."""
+
+    @property
+    def retries(self) -> int:
+        """The number of times a request of the operation was retried.
+
+        These are the retries reported to the hook set with
+        [`set_retry_hook`][obstore.set_retry_hook].
+        """
+
+    @property
+    def time_to_first_byte(self) -> timedelta | None:
+        """The time from the start of the operation until its first response arrived.
+
+        For downloads, this is when the first bytes of data were received. For
+        uploads, it's when the response to the first request uploading data was
+        received. `None` if no response has been received yet.
+        """
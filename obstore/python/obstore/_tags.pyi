@@ -0,0 +1,55 @@
+from typing import Dict
+
+from .store import ObjectStore
+
+def get_tags(store: ObjectStore, path: str) -> Dict[str, str]:
+    """Read the tags of an object.
+
+    Tags can be set when uploading an object, with the `tags` argument of
+    [`put`][obstore.put], and replaced afterwards with
+    [`put_tags`][obstore.put_tags].
+
+    Only [`S3Store`][obstore.store.S3Store] supports reading tags. Tag requests are
+    signed with the credentials of the store.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the object.
+
+    Raises:
+        NotSupportedError: if the store isn't an `S3Store`.
+        FileNotFoundError: if there's no object at `path`.
+
+    Returns:
+        The tags of the object, in the order the store returned them.
+    """
+
+async def get_tags_async(store: ObjectStore, path: str) -> Dict[str, str]:
+    """Call `get_tags` asynchronously.
+
+    Refer to the documentation for [get_tags][obstore.get_tags].
+    """
+
+def put_tags(store: ObjectStore, path: str, tags: Dict[str, str]) -> None:
+    """Replace the tags of an object.
+
+    All existing tags of the object are replaced, and passing no tags removes them.
+
+    Only [`S3Store`][obstore.store.S3Store] supports replacing tags. Tag requests are
+    signed with the credentials of the store.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the object.
+        tags: The new tags of the object.
+
+    Raises:
+        NotSupportedError: if the store isn't an `S3Store`.
+        FileNotFoundError: if there's no object at `path`.
+    """
+
+async def put_tags_async(store: ObjectStore, path: str, tags: Dict[str, str]) -> None:
+    """Call `put_tags` asynchronously.
+
+    Refer to the documentation for [put_tags][obstore.put_tags].
+    """
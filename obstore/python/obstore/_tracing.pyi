@@ -0,0 +1,81 @@
+from typing import Any, Literal
+
+def enable_tracing(
+    *,
+    output: Literal["logging", "opentelemetry"] = "logging",
+    level: Literal["trace", "debug", "info", "warning", "error"] = "info",
+    tracer_provider: Any | None = None,
+) -> None:
+    """Forward the spans and events reported by obstore and object_store to Python.
+
+    Each request made through a store is traced with a span named after its kind of
+    request, such as `get`, `put`, `list`, `head`, `delete`, `copy` or `rename`, with the
+    `path` it's made to, the `from` path of copies and renames, and the `error` it
+    failed with. The events object_store reports, such as the retries it makes within a
+    request, are forwarded along with them.
+
+    With `output="logging"`, spans are logged as they close, and events as they occur,
+    to [`logging`][logging] loggers named after their target, such as `obstore` or
+    `object_store.client.retry`. Messages are prefixed with the spans they occur in.
+    Records have a `fields` attribute with the fields of their span or event, and those
+    of spans also have a `duration` attribute:
+
+    ```py
+    import logging
+
+    import obstore as obs
+    from obstore.store import S3Store
+
+    logging.basicConfig(level=logging.INFO)
+    obs.enable_tracing()
+
+    store = S3Store("bucket")
+    obs.put(store, "file.txt", b"foo")
+    # INFO:obstore:put{path=file.txt}: closed after 41.2ms
+    ```
+
+    With `output="opentelemetry"`, spans are exported as spans of an OpenTelemetry
+    tracer, from the global tracer provider or from `tracer_provider`, and events are
+    added to the span they occur in. The spans are then exported by the processors of
+    the provider, such as an OTLP exporter:
+
+    ```py
+    from opentelemetry import trace
+    from opentelemetry.exporter.otlp.proto.grpc.trace_exporter import OTLPSpanExporter
+    from opentelemetry.sdk.trace import TracerProvider
+    from opentelemetry.sdk.trace.export import BatchSpanProcessor
+
+    import obstore as obs
+
+    provider = TracerProvider()
+    provider.add_span_processor(BatchSpanProcessor(OTLPSpanExporter()))
+    trace.set_tracer_provider(provider)
+
+    obs.enable_tracing(output="opentelemetry")
+    ```
+
+    Requests run on obstore's runtime threads, so their spans aren't children of the
+    OpenTelemetry span active when the operation was called, and start their own
+    traces. Calling `enable_tracing` again replaces the previous configuration.
+
+    Keyword Args:
+        output: Where spans and events are forwarded. Defaults to `"logging"`.
+        level: The most verbose level of spans and events to forward. The spans of
+            requests are at the `"info"` level, and retries are reported at the
+            `"info"` level too. Defaults to `"info"`.
+        tracer_provider: The OpenTelemetry tracer provider to export spans with, with
+            `output="opentelemetry"`. Defaults to the global tracer provider.
+
+    Raises:
+        ImportError: if `output="opentelemetry"` and `opentelemetry-api` isn't
+            installed.
+        RuntimeError: if another `tracing` subscriber was installed in the process.
+    """
+
+def disable_tracing() -> None:
+    """Stop forwarding spans and events, as enabled with
+    [`enable_tracing`][obstore.enable_tracing].
+
+    The retry hook set with [`set_retry_hook`][obstore.set_retry_hook] keeps being
+    called.
+    """
@@ -0,0 +1,104 @@
+from datetime import datetime
+
+from ._get import GetOptions, GetResult
+from .store import ObjectStore
+
+def get_at(
+    store: ObjectStore,
+    path: str,
+    as_of: datetime,
+    *,
+    options: GetOptions | None = None,
+) -> GetResult:
+    """Read the version of an object that was current at a point in time.
+
+    This lists the versions of the object in a versioned bucket, and reads the newest
+    one written at or before `as_of`, as [`get`][obstore.get] does with its `version`
+    set. Datasets identified by a timestamp can be read reproducibly, without
+    recording the version of each object:
+
+    ```py
+    from datetime import datetime, timezone
+
+    import obstore as obs
+
+    as_of = datetime(2024, 10, 1, tzinfo=timezone.utc)
+    obs.get_at(store, "data/file.parquet", as_of).bytes()
+    ```
+
+    Only an [`S3Store`][obstore.store.S3Store] for a bucket with versioning enabled
+    can list the versions of an object, which needs the `s3:ListBucketVersions`
+    permission.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the object to read.
+        as_of: The point in time to read the object at. Must be timezone-aware.
+
+    Keyword Args:
+        options: Additional options for the request. `version` is always replaced by
+            the version current at `as_of`.
+
+    Raises:
+        NotSupportedError: If `store` isn't an S3Store.
+        FileNotFoundError: If the object didn't exist yet, or was deleted, at `as_of`.
+
+    Returns:
+        The result of the request.
+    """
+
+async def get_at_async(
+    store: ObjectStore,
+    path: str,
+    as_of: datetime,
+    *,
+    options: GetOptions | None = None,
+) -> GetResult:
+    """Call `get_at` asynchronously.
+
+    Refer to the documentation for [get_at][obstore.get_at].
+    """
+
+
+def undelete(store: ObjectStore, path: str, *, copy: bool = False) -> str:
+    """Restore a deleted object in a versioned bucket.
+
+    Deleting an object in a versioned bucket adds a delete marker in front of its
+    versions rather than removing them. By default, `undelete` removes the delete
+    markers in front of the newest version of the object, so that it's current again:
+
+    ```py
+    import obstore as obs
+
+    obs.delete(store, "data/file.parquet")
+    version = obs.undelete(store, "data/file.parquet")
+    ```
+
+    If the object isn't deleted, nothing is changed.
+
+    Only an [`S3Store`][obstore.store.S3Store] for a bucket with versioning enabled
+    can restore objects. Removing delete markers needs the `s3:DeleteObjectVersion`
+    permission, and listing them `s3:ListBucketVersions`.
+
+    Args:
+        store: The ObjectStore instance to use.
+        path: The path of the object to restore.
+
+    Keyword Args:
+        copy: Restore the object by copying its newest version over the delete markers
+            instead, which keeps the markers in its history and only needs the
+            `s3:PutObject` and `s3:GetObjectVersion` permissions. Defaults to `False`.
+
+    Raises:
+        NotSupportedError: If `store` isn't an S3Store.
+        FileNotFoundError: If the object has no version to restore.
+
+    Returns:
+        The ID of the version that is current once the object is restored.
+    """
+
+async def undelete_async(store: ObjectStore, path: str, *, copy: bool = False) -> str:
+    """Call `undelete` asynchronously.
+
+    Refer to the documentation for [undelete][obstore.undelete].
+    """
@@ -0,0 +1,208 @@
+"""Run async operations in the background, without awaiting them.
+
+Some transfers shouldn't hold up the caller, for example an upload that should
+continue after a web handler has returned its response.
+[`submit`][obstore.background.submit] schedules such an operation on a background
+event loop, owned by `obstore`, and returns a [`Task`][obstore.background.Task] that
+can be used to check on, wait for, or cancel it. Requests still run on the same
+background runtime used by the rest of `obstore`.
+
+```py
+import obstore as obs
+from obstore.background import submit
+from obstore.store import MemoryStore
+
+store = MemoryStore()
+
+task = submit(
+    lambda: obs.put_async(store, "upload.bin", b"foo"),
+    marker=(store, "upload.bin.done"),
+)
+task.status()  # "pending", "running", "done", "failed" or "cancelled"
+task.result()  # Block until the upload has completed
+```
+
+Background tasks run on a daemon thread, so tasks that haven't completed when the
+interpreter exits are abandoned. Call [`drain`][obstore.background.drain] before
+exiting to wait for them.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import json
+import threading
+from concurrent.futures import Future
+from concurrent.futures import wait as wait_futures
+from typing import (
+    TYPE_CHECKING,
+    Any,
+    Awaitable,
+    Callable,
+    Coroutine,
+    Generic,
+    Literal,
+    Set,
+    Tuple,
+    TypeVar,
+    Union,
+)
+
+import obstore as obs
+
+if TYPE_CHECKING:
+    from obstore.store import ObjectStore
+
+T = TypeVar("T")
+
+TaskStatus = Literal["pending", "running", "done", "failed", "cancelled"]
+"""The status of a background [`Task`][obstore.background.Task]."""
+
+Operation = Union[Coroutine[Any, Any, T], Callable[[], Awaitable[T]]]
+"""An operation to run in the background.
+
+Either a coroutine, or a function without arguments returning an awaitable.
+"""
+
+_lock = threading.Lock()
+_loop: asyncio.AbstractEventLoop | None = None
+_pending: Set[Future] = set()
+
+
+def _get_loop() -> asyncio.AbstractEventLoop:
+    global _loop  # noqa: PLW0603
+    with _lock:
+        if _loop is None:
+            _loop = asyncio.new_event_loop()
+            thread = threading.Thread(
+                target=_loop.run_forever,
+                name="obstore-background",
+                daemon=True,
+            )
+            thread.start()
+        return _loop
+
+
+class Task(Generic[T]):
+    """A handle to an operation running in the background.
+
+    Returned by [`submit`][obstore.background.submit].
+    """
+
+    def __init__(self, future: Future[T], started: threading.Event) -> None:
+        self._future = future
+        self._started = started
+
+    def __repr__(self) -> str:
+        return f"Task(status={self.status()!r})"
+
+    def status(self) -> TaskStatus:
+        """The current status of the operation."""
+        if self._future.cancelled():
+            return "cancelled"
+        if self._future.done():
+            return "failed" if self._future.exception() is not None else "done"
+        return "running" if self._started.is_set() else "pending"
+
+    def result(self, timeout: float | None = None) -> T:
+        """Wait for the operation to complete and return its result.
+
+        Args:
+            timeout: The maximum number of seconds to wait. Defaults to `None`, waiting
+                indefinitely.
+
+        Raises:
+            TimeoutError: if the operation didn't complete within `timeout`.
+            CancelledError: if the operation was cancelled.
+
+        Returns:
+            The result of the operation. If the operation raised, this raises the same
+            exception.
+        """
+        return self._future.result(timeout)
+
+    def cancel(self) -> bool:
+        """Request cancellation of the operation.
+
+        Cancelling an operation that has already started stops it at its next `await`.
+        Some requests may already have been made, e.g. an upload may be interrupted
+        after some of its parts have been written.
+
+        Returns:
+            `False` if the operation has already completed, otherwise `True`.
+        """
+        return self._future.cancel()
+
+
+def submit(
+    op: Operation[T],
+    *,
+    marker: Tuple[ObjectStore, str] | None = None,
+) -> Task[T]:
+    """Run an async operation in the background.
+
+    Awaitables returned by the `_async` functions of `obstore` are bound to the event
+    loop they were created on, so pass a function creating the awaitable, e.g.
+    `lambda: obs.put_async(store, path, data)`, rather than the awaitable itself.
+    Coroutines of `async def` functions can be passed directly.
+
+    Args:
+        op: The operation to run.
+
+    Keyword args:
+        marker: A store and path at which to write a completion marker. When the
+            operation completes or fails, a JSON object with `status` set to `"done"`
+            or `"failed"`, and the `error` message if it failed, is written to this
+            path. This makes completion observable from other processes. No marker is
+            written if the operation is cancelled. Defaults to `None`.
+
+    Returns:
+        A handle to the background operation.
+    """
+    started = threading.Event()
+
+    async def run() -> T:
+        started.set()
+        awaitable = op if asyncio.iscoroutine(op) else op()
+        try:
+            result = await awaitable
+        except Exception as err:
+            if marker is not None:
+                await _write_marker(marker, {"status": "failed", "error": str(err)})
+            raise
+        if marker is not None:
+            await _write_marker(marker, {"status": "done", "error": None})
+        return result
+
+    future = asyncio.run_coroutine_threadsafe(run(), _get_loop())
+    with _lock:
+        _pending.add(future)
+    future.add_done_callback(_discard)
+    return Task(future, started)
+
+
+def drain(timeout: float | None = None) -> bool:
+    """Wait for all background operations to complete.
+
+    Args:
+        timeout: The maximum number of seconds to wait. Defaults to `None`, waiting
+            indefinitely.
+
+    Returns:
+        `True` if all operations completed, or `False` if some were still running
+        after `timeout`.
+    """
+    with _lock:
+        futures = set(_pending)
+    _, not_done = wait_futures(futures, timeout)
+    return not not_done
+
+
+def _discard(future: Future) -> None:
+    with _lock:
+        _pending.discard(future)
+
+
+async def _write_marker(marker: Tuple[ObjectStore, str], content: dict) -> None:
+    store, path = marker
+    await obs.put_async(store, path, json.dumps(content).encode())
@@ -0,0 +1,133 @@
+"""A blocking client that is safe to share with code using the async API.
+
+The synchronous functions in `obstore` block the calling thread until the request
+completes. That's what you want in a worker thread, but calling them on a thread that
+runs an asyncio event loop stalls every other coroutine on that loop until the request
+finishes, and can hang outright if the request depends on work scheduled on that loop.
+
+[`Client`][obstore.blocking.Client] makes that mistake loud: its methods behave like
+their counterparts in `obstore`, but raise a `RuntimeError` when called from a thread
+with a running event loop. Requests run on the same background runtime used by the
+rest of `obstore`, so a `Client` can be used from worker threads (for example through
+[`asyncio.to_thread`][asyncio.to_thread]) of an application that also uses the async
+API.
+
+```py
+import asyncio
+
+import obstore as obs
+from obstore.blocking import Client
+from obstore.store import MemoryStore
+
+store = MemoryStore()
+client = Client(store)
+
+async def main():
+    await obs.put_async(store, "file.txt", b"foo")
+    # Fine: runs on a worker thread
+    await asyncio.to_thread(client.get, "file.txt")
+    # Raises RuntimeError instead of blocking the event loop
+    client.get("file.txt")
+```
+"""
+
+from __future__ import annotations
+
+import asyncio
+from typing import TYPE_CHECKING, Any, List, Sequence
+
+import obstore as obs
+
+if TYPE_CHECKING:
+    from obstore import Bytes, GetResult, ListResult, ObjectMeta, PutResult
+    from obstore.store import ObjectStore
+
+
+class Client:
+    """Blocking access to a store that refuses to run on an event loop thread.
+
+    Each method forwards its arguments to the function of the same name in `obstore`.
+    Refer to those functions for the available keyword arguments.
+    """
+
+    def __init__(self, store: ObjectStore) -> None:
+        """Create a new blocking client.
+
+        Args:
+            store: The ObjectStore instance to use.
+        """
+        self.store = store
+
+    def __repr__(self) -> str:
+        return f"Client({self.store!r})"
+
+    def _check_thread(self, method: str) -> None:
+        try:
+            asyncio.get_running_loop()
+        except RuntimeError:
+            return
+        raise RuntimeError(
+            f"Client.{method} was called from a thread running an asyncio event loop, "
+            "which would block the loop until the request completes. Use "
+            f"obstore.{method}_async instead, or call this method from another thread, "
+            "e.g. with asyncio.to_thread."
+        )
+
+    def copy(self, from_: str, to: str, *, overwrite: bool = True) -> None:
+        """Copy an object. Refer to [`obstore.copy`][obstore.copy]."""
+        self._check_thread("copy")
+        return obs.copy(self.store, from_, to, overwrite=overwrite)
+
+    def delete(self, paths: str | Sequence[str]) -> None:
+        """Delete objects. Refer to [`obstore.delete`][obstore.delete]."""
+        self._check_thread("delete")
+        return obs.delete(self.store, paths)
+
+    def get(self, path: str, **kwargs: Any) -> GetResult:
+        """Download an object. Refer to [`obstore.get`][obstore.get]."""
+        self._check_thread("get")
+        return obs.get(self.store, path, **kwargs)
+
+    def get_range(self, path: str, start: int, end: int) -> Bytes:
+        """Download a byte range. Refer to [`obstore.get_range`][obstore.get_range]."""
+        self._check_thread("get_range")
+        return obs.get_range(self.store, path, start, end)
+
+    def get_ranges(
+        self, path: str, starts: Sequence[int], ends: Sequence[int]
+    ) -> List[Bytes]:
+        """Download byte ranges. Refer to [`obstore.get_ranges`][obstore.get_ranges]."""
+        self._check_thread("get_ranges")
+        return obs.get_ranges(self.store, path, starts, ends)
+
+    def head(self, path: str) -> ObjectMeta:
+        """Fetch object metadata. Refer to [`obstore.head`][obstore.head]."""
+        self._check_thread("head")
+        return obs.head(self.store, path)
+
+    def list(self, prefix: str | None = None, **kwargs: Any) -> List[ObjectMeta]:
+        """List all objects under `prefix`.
+
+        Unlike [`obstore.list`][obstore.list], this collects the whole listing before
+        returning, so that no request is made when the result is iterated.
+        """
+        self._check_thread("list")
+        return obs.list(self.store, prefix, **kwargs).collect()
+
+    def list_with_delimiter(self, prefix: str | None = None) -> ListResult:
+        """List objects and prefixes under `prefix`.
+
+        Refer to [`obstore.list_with_delimiter`][obstore.list_with_delimiter].
+        """
+        self._check_thread("list_with_delimiter")
+        return obs.list_with_delimiter(self.store, prefix)
+
+    def put(self, path: str, file: Any, **kwargs: Any) -> PutResult:
+        """Upload an object. Refer to [`obstore.put`][obstore.put]."""
+        self._check_thread("put")
+        return obs.put(self.store, path, file, **kwargs)
+
+    def rename(self, from_: str, to: str, *, overwrite: bool = True) -> None:
+        """Rename an object. Refer to [`obstore.rename`][obstore.rename]."""
+        self._check_thread("rename")
+        return obs.rename(self.store, from_, to, overwrite=overwrite)
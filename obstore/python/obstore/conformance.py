@@ -0,0 +1,327 @@
+"""Check that a store behaves the way `obstore` expects.
+
+S3-compatible services such as MinIO, Ceph or Cloudflare R2 implement the S3 API to
+varying degrees. [`run`][obstore.conformance.run] executes a battery of behavioral
+checks against a store and reports where it deviates, which helps assess whether an
+endpoint is suitable before using it in production.
+
+```py
+import obstore as obs
+from obstore.conformance import run
+from obstore.store import S3Store
+
+store = S3Store("bucket", endpoint="http://localhost:9000")
+report = run(store, suites=["get", "put", "conditional"])
+for check in report["checks"]:
+    if not check["passed"]:
+        print(f"{check['suite']}.{check['name']}: {check['error']}")
+```
+
+The checks write and delete objects under a dedicated prefix, so the credentials need
+read, write, list and delete access to it. Run the checks against a test bucket rather
+than one holding production data.
+"""
+
+from __future__ import annotations
+
+import uuid
+from typing import TYPE_CHECKING, Callable, Dict, List, Literal, Sequence, TypedDict
+
+import obstore as obs
+from obstore.exceptions import (
+    AlreadyExistsError,
+    NotModifiedError,
+    PreconditionError,
+)
+
+if TYPE_CHECKING:
+    from obstore.store import ObjectStore
+
+Suite = Literal["get", "put", "multipart", "list", "conditional"]
+"""A group of related checks run by [`run`][obstore.conformance.run]."""
+
+SUITES: Sequence[Suite] = ("get", "put", "multipart", "list", "conditional")
+"""All available suites, in the order they are run."""
+
+
+class CheckResult(TypedDict):
+    """The outcome of a single check."""
+
+    suite: Suite
+    """The suite the check belongs to."""
+
+    name: str
+    """The name of the check, e.g. `"create_existing_object"`."""
+
+    passed: bool
+    """Whether the store behaved as expected."""
+
+    error: str | None
+    """How the store deviated from the expected behavior, if it did."""
+
+
+class Report(TypedDict):
+    """The result of [`run`][obstore.conformance.run]."""
+
+    passed: bool
+    """Whether every check passed."""
+
+    checks: List[CheckResult]
+    """The outcome of each check, in the order they were run."""
+
+
+class Deviation(Exception):
+    """Raised by a check when the store doesn't behave as expected."""
+
+
+Check = Callable[["ObjectStore", str], None]
+
+_CHECKS: Dict[Suite, List[Check]] = {suite: [] for suite in SUITES}
+
+# S3 requires all parts but the last one of a multipart upload to be at least 5MiB
+_PART_SIZE = 5 * 1024 * 1024
+
+
+def _check(suite: Suite) -> Callable[[Check], Check]:
+    def register(check: Check) -> Check:
+        _CHECKS[suite].append(check)
+        return check
+
+    return register
+
+
+def _expect(condition: bool, message: str) -> None:
+    if not condition:
+        raise Deviation(message)
+
+
+def _expect_raises(
+    error: type[Exception],
+    op: Callable[[], object],
+    description: str,
+) -> None:
+    try:
+        op()
+    except error:
+        return
+    except Exception as err:
+        raise Deviation(
+            f"Expected {error.__name__} when {description}, got "
+            f"{type(err).__name__}: {err}",
+        ) from err
+    raise Deviation(f"Expected {error.__name__} when {description}, but it succeeded")
+
+
+@_check("get")
+def _roundtrip(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/object"
+    obs.put(store, path, b"foo")
+    _expect(obs.get(store, path).bytes() == b"foo", "Read different bytes than written")
+
+
+@_check("get")
+def _get_missing_object(store: ObjectStore, prefix: str) -> None:
+    _expect_raises(
+        FileNotFoundError,
+        lambda: obs.get(store, f"{prefix}/missing"),
+        "getting a missing object",
+    )
+
+
+@_check("get")
+def _head_metadata(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/object"
+    obs.put(store, path, b"foo")
+    meta = obs.head(store, path)
+    _expect(meta["size"] == 3, f"Expected a size of 3, got {meta['size']}")
+    _expect(meta["e_tag"] is not None, "No e_tag was returned")
+
+
+@_check("get")
+def _byte_ranges(store: ObjectStore, prefix: str) -> None:
+    failed = [
+        f"{check['name']}: {check['error']}"
+        for check in obs.check_store_conformance(store, f"{prefix}/ranges")
+        if not check["passed"]
+    ]
+    _expect(not failed, "; ".join(failed))
+
+
+@_check("put")
+def _overwrite(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/object"
+    obs.put(store, path, b"foo")
+    obs.put(store, path, b"barbaz")
+    _expect(obs.get(store, path).bytes() == b"barbaz", "The object wasn't overwritten")
+
+
+@_check("put")
+def _empty_object(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/empty"
+    obs.put(store, path, b"")
+    size = obs.head(store, path)["size"]
+    _expect(size == 0, f"Expected a size of 0, got {size}")
+
+
+@_check("put")
+def _put_returns_e_tag(store: ObjectStore, prefix: str) -> None:
+    result = obs.put(store, f"{prefix}/object", b"foo")
+    _expect(result["e_tag"] is not None, "No e_tag was returned")
+
+
+@_check("multipart")
+def _single_part(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/single-part"
+    obs.put(store, path, b"foo", use_multipart=True)
+    _expect(obs.get(store, path).bytes() == b"foo", "Read different bytes than written")
+
+
+@_check("multipart")
+def _multiple_parts(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/multiple-parts"
+    data = b"0123456789" * (_PART_SIZE // 10 + 1)
+    obs.put(store, path, data, use_multipart=True, chunk_size=_PART_SIZE)
+    _expect(obs.get(store, path).bytes() == data, "Read different bytes than written")
+
+
+@_check("list")
+def _list_prefix(store: ObjectStore, prefix: str) -> None:
+    for path in ["a/1", "a/2", "a/nested/3", "ab/4"]:
+        obs.put(store, f"{prefix}/listing/{path}", b"foo")
+    listing = obs.list(store, f"{prefix}/listing/a").collect()
+    paths = sorted(meta["path"] for meta in listing)
+    expected = [f"{prefix}/listing/a/{path}" for path in ["1", "2", "nested/3"]]
+    _expect(paths == expected, f"Expected {expected}, got {paths}")
+
+
+@_check("list")
+def _list_with_delimiter(store: ObjectStore, prefix: str) -> None:
+    for path in ["1", "nested/2"]:
+        obs.put(store, f"{prefix}/delimited/{path}", b"foo")
+    result = obs.list_with_delimiter(store, f"{prefix}/delimited")
+    objects = [meta["path"] for meta in result["objects"]]
+    _expect(
+        objects == [f"{prefix}/delimited/1"],
+        f"Expected a single object, got {objects}",
+    )
+    _expect(
+        result["common_prefixes"] == [f"{prefix}/delimited/nested"],
+        f"Expected a single common prefix, got {result['common_prefixes']}",
+    )
+
+
+@_check("list")
+def _list_with_offset(store: ObjectStore, prefix: str) -> None:
+    for path in ["1", "2", "3"]:
+        obs.put(store, f"{prefix}/offset/{path}", b"foo")
+    listing = obs.list(store, f"{prefix}/offset", offset=f"{prefix}/offset/1").collect()
+    paths = sorted(meta["path"] for meta in listing)
+    expected = [f"{prefix}/offset/2", f"{prefix}/offset/3"]
+    _expect(paths == expected, f"Expected {expected}, got {paths}")
+
+
+@_check("conditional")
+def _create_existing_object(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/create"
+    obs.put(store, path, b"foo", mode="create")
+    _expect_raises(
+        AlreadyExistsError,
+        lambda: obs.put(store, path, b"bar", mode="create"),
+        "creating an existing object",
+    )
+
+
+@_check("conditional")
+def _update_version(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/update"
+    result = obs.put(store, path, b"foo")
+    obs.put(store, path, b"bar", mode={"e_tag": result["e_tag"]})
+    _expect_raises(
+        PreconditionError,
+        lambda: obs.put(store, path, b"baz", mode={"e_tag": result["e_tag"]}),
+        "updating an object with an outdated e_tag",
+    )
+
+
+@_check("conditional")
+def _get_if_match(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/if-match"
+    obs.put(store, path, b"foo")
+    _expect_raises(
+        PreconditionError,
+        lambda: obs.get(store, path, options={"if_match": '"does-not-match"'}),
+        "getting an object with a mismatched if_match",
+    )
+
+
+@_check("conditional")
+def _get_if_none_match(store: ObjectStore, prefix: str) -> None:
+    path = f"{prefix}/if-none-match"
+    e_tag = obs.put(store, path, b"foo")["e_tag"]
+    _expect_raises(
+        NotModifiedError,
+        lambda: obs.get(store, path, options={"if_none_match": e_tag}),
+        "getting an object with a matching if_none_match",
+    )
+
+
+def _cleanup(store: ObjectStore, prefix: str) -> None:
+    paths = [meta["path"] for meta in obs.list(store, prefix).collect()]
+    if paths:
+        obs.delete(store, paths)
+
+
+def run(
+    store: ObjectStore,
+    *,
+    suites: Sequence[Suite] = SUITES,
+    prefix: str = "obstore-conformance",
+) -> Report:
+    """Run the conformance checks against a store.
+
+    Every check runs in a fresh, randomly named directory under `prefix`, which is
+    deleted once all checks have run. Checks that raise are reported as deviations, so
+    a single unsupported feature doesn't prevent the remaining checks from running.
+
+    Args:
+        store: The ObjectStore instance to check.
+
+    Keyword Args:
+        suites: The suites to run. Defaults to all of `"get"`, `"put"`, `"multipart"`,
+            `"list"` and `"conditional"`.
+        prefix: The prefix to write test objects under. Defaults to
+            `"obstore-conformance"`.
+
+    Returns:
+        A report of the outcome of each check.
+    """
+    unknown = [suite for suite in suites if suite not in _CHECKS]
+    if unknown:
+        msg = f"Unknown suites: {unknown}. Expected any of {list(SUITES)}."
+        raise ValueError(msg)
+
+    root = f"{prefix}/{uuid.uuid4().hex}"
+    checks: List[CheckResult] = []
+    try:
+        for suite in suites:
+            for check in _CHECKS[suite]:
+                name = check.__name__.lstrip("_")
+                error = None
+                try:
+                    check(store, f"{root}/{name}")
+                except Deviation as err:
+                    error = str(err)
+                except Exception as err:
+                    error = f"{type(err).__name__}: {err}"
+                checks.append(
+                    {
+                        "suite": suite,
+                        "name": name,
+                        "passed": error is None,
+                        "error": error,
+                    },
+                )
+    finally:
+        _cleanup(store, root)
+
+    return {"passed": all(check["passed"] for check in checks), "checks": checks}
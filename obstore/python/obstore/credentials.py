@@ -0,0 +1,147 @@
+"""Cache short-lived S3 credentials across processes.
+
+Credentials from SSO or an AssumeRole call are valid for an hour or more, but a
+[credential provider][obstore.store.S3CredentialProvider] only caches them for the
+lifetime of its store, so every new process authenticates again. A
+[`KeyringCredentialCache`][obstore.credentials.KeyringCredentialCache] wraps a
+provider, keeping the credentials it returns in the OS keyring until shortly before
+they expire, so that later processes reuse them:
+
+```py
+import boto3
+from obstore.credentials import KeyringCredentialCache
+from obstore.store import S3Store
+
+sts = boto3.client("sts")
+
+def assume_role():
+    creds = sts.assume_role(RoleArn=role_arn, RoleSessionName="obstore")
+    creds = creds["Credentials"]
+    return {
+        "access_key_id": creds["AccessKeyId"],
+        "secret_access_key": creds["SecretAccessKey"],
+        "token": creds["SessionToken"],
+        "expires_at": creds["Expiration"],
+    }
+
+provider = KeyringCredentialCache(assume_role, name=role_arn)
+store = S3Store("bucket-name", credential_provider=provider)
+```
+
+This requires the [keyring](https://pypi.org/project/keyring/) package, unless
+another backend is passed.
+"""
+
+from __future__ import annotations
+
+import json
+from datetime import datetime, timedelta, timezone
+from typing import TYPE_CHECKING, Any, Awaitable
+
+if TYPE_CHECKING:
+    from obstore.store import S3Credential, S3CredentialProvider
+
+MIN_TTL = timedelta(minutes=5)
+"""Cached credentials are refreshed this long before they expire, as `S3Store` does."""
+
+
+class KeyringCredentialCache:
+    """A credential provider caching the credentials of another in the OS keyring.
+
+    Credentials are read from the keyring if they're valid for at least another five
+    minutes, and are otherwise fetched from the wrapped provider and stored. Only
+    credentials with an `expires_at` are stored, as credentials that don't expire
+    aren't short-lived.
+    """
+
+    def __init__(
+        self,
+        provider: S3CredentialProvider,
+        *,
+        name: str,
+        service: str = "obstore",
+        backend: Any | None = None,
+    ) -> None:
+        """Wrap a credential provider.
+
+        Args:
+            provider: The credential provider to fetch credentials from when none are
+                cached, sync or async.
+
+        Keyword Args:
+            name: The name the credentials are stored under, which identifies them
+                within `service`, such as the ARN of the role they're for.
+            service: The keyring service the credentials are stored in. Defaults to
+                `"obstore"`.
+            backend: The keyring backend to store the credentials in, with
+                `get_password`, `set_password` and `delete_password` methods. Defaults
+                to the `keyring` module, using the default backend for the OS.
+        """
+        if backend is None:
+            try:
+                import keyring
+            except ImportError as err:
+                msg = "KeyringCredentialCache requires the keyring package"
+                raise ImportError(msg) from err
+            backend = keyring
+        self._provider = provider
+        self._name = name
+        self._service = service
+        self._backend = backend
+
+    def __call__(self) -> S3Credential | Awaitable[S3Credential]:
+        """Return the cached credentials, or fetch them from the wrapped provider.
+
+        This returns an awaitable if the credentials aren't cached and the wrapped
+        provider is async.
+        """
+        cached = self._load()
+        if cached is not None:
+            return cached
+        credential = self._provider()
+        if hasattr(credential, "__await__"):
+            return self._fetch_async(credential)
+        self._store(credential)
+        return credential
+
+    def clear(self) -> None:
+        """Remove the cached credentials from the keyring, if any.
+
+        The next process to call the provider authenticates again.
+        """
+        if self._backend.get_password(self._service, self._name) is not None:
+            self._backend.delete_password(self._service, self._name)
+
+    async def _fetch_async(self, credential: Awaitable[S3Credential]) -> S3Credential:
+        credential = await credential
+        self._store(credential)
+        return credential
+
+    def _load(self) -> S3Credential | None:
+        cached = self._backend.get_password(self._service, self._name)
+        if cached is None:
+            return None
+        try:
+            credential = json.loads(cached)
+            expires_at = datetime.fromisoformat(credential["expires_at"])
+        except (ValueError, KeyError, TypeError):
+            # Left by something else, or an older format, so replaced when fetched
+            return None
+        if expires_at.tzinfo is None:
+            return None
+        if expires_at - datetime.now(timezone.utc) <= MIN_TTL:
+            return None
+        credential["expires_at"] = expires_at
+        return credential
+
+    def _store(self, credential: S3Credential) -> None:
+        expires_at = credential.get("expires_at")
+        if expires_at is None:
+            return
+        cached = {
+            "access_key_id": credential["access_key_id"],
+            "secret_access_key": credential["secret_access_key"],
+            "token": credential.get("token"),
+            "expires_at": expires_at.isoformat(),
+        }
+        self._backend.set_password(self._service, self._name, json.dumps(cached))
@@ -1,5 +1,29 @@
 class ObstoreError(Exception):
-    """The base exception class"""
+    """The base exception class.
+
+    Errors raised by stores carry structured details of what failed, so that they can
+    be handled without parsing their message, which is the only item of `args`.
+    Missing objects raise the builtin `FileNotFoundError`, which carries the same
+    attributes.
+
+    ```py
+    try:
+        obs.get(store, "file.txt")
+    except PermissionDeniedError as err:
+        print(err.path, err.status_code)
+    ```
+    """
+
+    path: str | None
+    """The path of the object the error is about, if known."""
+    store: str | None
+    """The name of the store implementation that raised the error, as object_store
+    names it, e.g. `"S3"` or `"InMemory"`. `None` for errors raised by a wrapper such
+    as a [`GuardrailStore`][obstore.store.GuardrailStore] itself, or not raised by a
+    store."""
+    status_code: int | None
+    """The HTTP status of the response that caused the error, if it was caused by
+    one."""
 
 class GenericError(ObstoreError):
     """A fallback error type when no variant matches."""
@@ -20,7 +44,27 @@ class AlreadyExistsError(ObstoreError):
     """Error when the object already exists."""
 
 class PreconditionError(ObstoreError):
-    """Error when the required conditions failed for the operation."""
+    """Error when the required conditions failed for the operation.
+
+    When a `put` with an [`UpdateVersion`][obstore.UpdateVersion] mode fails because
+    the object has changed, the error carries the versions involved, so that the put
+    can be retried without parsing the message. The current version of the object is
+    read with a `head` request after the put fails.
+    """
+
+    path: str
+    """The path of the object. Only set for conditional puts."""
+    expected_e_tag: str | None
+    """The e-tag the put expected the object to have. Only set for conditional puts."""
+    expected_version: str | None
+    """The version the put expected the object to have. Only set for conditional
+    puts."""
+    actual_e_tag: str | None
+    """The e-tag of the object after the put failed, or `None` if it couldn't be
+    read, e.g. because the object was deleted. Only set for conditional puts."""
+    actual_version: str | None
+    """The version of the object after the put failed, if the store has versions.
+    Only set for conditional puts."""
 
 class NotModifiedError(ObstoreError):
     """Error when the object at the location isn't modified."""
@@ -31,8 +75,29 @@ class PermissionDeniedError(ObstoreError):
     to perform the requested operation
     """
 
+    hint: str | None
+    """A guess at which permission is missing, also appended to the message.
+
+    This is only set once hints are enabled with
+    [`set_permission_hints`][obstore.set_permission_hints].
+    """
+
 class UnauthenticatedError(ObstoreError):
     """Error when the used credentials lack valid authentication."""
 
 class UnknownConfigurationKeyError(ObstoreError):
     """Error when a configuration key is invalid for the store used."""
+
+class InvalidRangeError(ObstoreError):
+    """Error when a byte range is invalid or starts past the end of the object.
+
+    Byte range requests are normalized so that they behave the same across stores:
+
+    - A range that starts at or before the end of the object is truncated to the
+      object. A range that starts exactly at the end returns no bytes.
+    - A suffix range larger than the object returns the entire object, and any range
+      on a zero-length object that starts at 0 returns no bytes.
+
+    This error is raised for everything else: ranges whose start is after their end,
+    and ranges that start past the end of the object.
+    """
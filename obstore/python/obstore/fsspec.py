@@ -144,8 +144,32 @@ class AsyncFsspecStore(fsspec.asyn.AsyncFileSystem):
             async for buffer in resp.stream():
                 f.write(buffer)
 
+    async def _mv_file(self, path1, path2, **kwargs):
+        return await obs.rename_async(self.store, path1, path2)
+
+    def mv(self, path1, path2, recursive=False, maxdepth=None, **kwargs):
+        # Move single files with one rename, rather than a copy and a delete
+        if not recursive and isinstance(path1, str) and self.isfile(path1):
+            return fsspec.asyn.sync(self.loop, self._mv_file, path1, path2)
+        return super().mv(path1, path2, recursive=recursive, maxdepth=maxdepth, **kwargs)
+
+    async def _mkdir(self, path, create_parents=True, **kwargs):
+        # Directories only exist as the prefixes of objects
+        pass
+
+    async def _makedirs(self, path, exist_ok=False):
+        pass
+
     async def _info(self, path, **kwargs):
-        head = await obs.head_async(self.store, path)
+        try:
+            head = await obs.head_async(self.store, path)
+        except FileNotFoundError:
+            # Emulate directories: a path is one if any object has it as a prefix
+            prefix = path.rstrip("/")
+            result = await obs.list_with_delimiter_async(self.store, prefix)
+            if result["objects"] or result["common_prefixes"]:
+                return {"name": prefix, "size": 0, "type": "directory"}
+            raise
         return {
             # Required of `info`: (?)
             "name": head["path"],
@@ -181,10 +205,20 @@ class AsyncFsspecStore(fsspec.asyn.AsyncFileSystem):
 
 class BufferedFileSimple(fsspec.spec.AbstractBufferedFile):
     def __init__(self, fs, path, mode="rb", **kwargs):
-        if mode != "rb":
-            raise ValueError("Only 'rb' mode is currently supported")
+        if mode not in ("rb", "wb"):
+            raise ValueError("Only 'rb' and 'wb' modes are currently supported")
         super().__init__(fs, path, mode, **kwargs)
 
+    def _initiate_upload(self):
+        self._writer = obs.open_writer(self.fs.store, self.path)
+
+    def _upload_chunk(self, final=False):
+        self._writer.write(self.buffer.getvalue())
+        if final:
+            # The object only appears once the upload is complete
+            self._writer.close()
+        return True
+
     def read(self, length: int = -1):
         """Return bytes from the remote file
 
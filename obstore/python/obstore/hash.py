@@ -0,0 +1,20 @@
+"""Checksums for verifying the integrity of objects.
+
+These are implemented in Rust and operate on any object implementing the buffer
+protocol, such as `bytes`, `memoryview` or the [`Bytes`][obstore.Bytes] returned by
+[`get`][obstore.get], without copying the buffer or holding the GIL:
+
+```py
+import obstore as obs
+from obstore.hash import md5
+from obstore.store import S3Store
+
+store = S3Store("bucket")
+buffer = obs.get(store, "file.bin").bytes()
+assert md5(buffer).hex() == obs.head(store, "file.bin")["e_tag"].strip('"')
+```
+"""
+
+from ._obstore import crc32c, md5, xxh3
+
+__all__ = ["crc32c", "md5", "xxh3"]
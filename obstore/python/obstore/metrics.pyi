@@ -0,0 +1,104 @@
+"""Process-wide metrics of the requests made through every store.
+
+Metrics are opt-in. Once enabled, the count, errors, bytes and latency of each request
+made through any store are recorded, whichever store wrappers it passes through:
+
+```py
+import obstore as obs
+import obstore.metrics
+from obstore.store import S3Store
+
+obstore.metrics.enable()
+
+store = S3Store("bucket")
+obs.put(store, "file.txt", b"foo")
+obs.get(store, "file.txt").bytes()
+
+snapshot = obstore.metrics.snapshot()
+print(snapshot["get"]["count"], snapshot["get"]["bytes"])
+print(snapshot["put"]["total_latency"] / snapshot["put"]["count"])
+```
+
+Requests are recorded as object_store makes them through the underlying store, so a
+call to a wrapper such as [`TrashStore`][obstore.store.TrashStore] is recorded as each
+request it makes. The retries object_store makes within a request aren't counted
+separately, and their waits are included in its latency. Requests obstore makes
+without going through object_store, such as bucket configuration and multipart copies,
+aren't recorded.
+"""
+
+from datetime import timedelta
+from typing import List, Tuple, TypedDict
+
+class OperationMetrics(TypedDict):
+    """The aggregated metrics of one kind of request."""
+
+    count: int
+    """The number of requests."""
+
+    errors: int
+    """The number of requests that failed."""
+
+    bytes: int
+    """The number of payload bytes uploaded by puts, or downloaded by gets.
+
+    The bytes of a download are counted as its data is read.
+    """
+
+    total_latency: timedelta
+    """The total latency of the requests."""
+
+    max_latency: timedelta
+    """The highest latency of a request."""
+
+    latency_histogram: List[Tuple[timedelta | None, int]]
+    """The number of requests by latency, as `(upper_bound, count)` pairs.
+
+    Each request is counted in the first bucket whose upper bound it doesn't exceed.
+    The bounds range from 1 millisecond to 10 seconds, and the last bucket, whose
+    bound is `None`, counts the requests slower than that.
+    """
+
+class MetricsSnapshot(TypedDict):
+    """The metrics of each kind of request."""
+
+    get: OperationMetrics
+    """Downloads, timed until their response arrives."""
+
+    put: OperationMetrics
+    """Uploads. Each part of a multipart upload, and the requests starting and
+    completing it, are recorded as separate puts."""
+
+    list: OperationMetrics
+    """Listings, timed until their first result arrives."""
+
+    head: OperationMetrics
+    """Requests for the metadata of an object."""
+
+    delete: OperationMetrics
+    """Deletions, counting each path of a bulk deletion."""
+
+    copy: OperationMetrics
+    """Copies within a store."""
+
+    rename: OperationMetrics
+    """Renames within a store."""
+
+def enable() -> None:
+    """Start recording the requests made through every store."""
+
+def disable() -> None:
+    """Stop recording requests, keeping the metrics recorded so far."""
+
+def is_enabled() -> bool:
+    """Whether requests are being recorded."""
+
+def snapshot() -> MetricsSnapshot:
+    """The metrics recorded since metrics were first enabled, or last reset.
+
+    Returns:
+        A copy of the metrics of each kind of request.
+    """
+
+def reset() -> None:
+    """Clear the metrics recorded so far."""
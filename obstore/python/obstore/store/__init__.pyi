@@ -1,17 +1,44 @@
 # TODO: move to reusable types package
 from pathlib import Path
+from typing import TypedDict, Unpack
 
 from ._aws import S3Config as S3Config
+from ._aws import S3Credential as S3Credential
+from ._aws import S3CredentialProvider as S3CredentialProvider
+from ._aws import S3Quirks as S3Quirks
 from ._aws import S3Store as S3Store
 from ._azure import AzureConfig as AzureConfig
 from ._azure import AzureStore as AzureStore
+from ._circuit_breaker import CircuitBreakerStore as CircuitBreakerStore
+from ._circuit_breaker import CircuitState as CircuitState
 from ._client import ClientConfig as ClientConfig
 from ._gcs import GCSConfig as GCSConfig
 from ._gcs import GCSStore as GCSStore
+from ._get_defaults import DefaultGetOptions as DefaultGetOptions
+from ._get_defaults import DefaultGetOptionsStore as DefaultGetOptionsStore
+from ._guardrails import GuardrailOperation as GuardrailOperation
+from ._guardrails import GuardrailStore as GuardrailStore
 from ._http import HTTPStore as HTTPStore
+from ._lanes import Lane as Lane
+from ._lanes import LaneStore as LaneStore
 from ._prefix import PrefixStore as PrefixStore
+from ._prefix_stats import PrefixHotspot as PrefixHotspot
+from ._prefix_stats import PrefixStatsStore as PrefixStatsStore
+from ._resolving import ResolvingStore as ResolvingStore
 from ._retry import BackoffConfig as BackoffConfig
 from ._retry import RetryConfig as RetryConfig
+from ._signed_url import SignedURLStore as SignedURLStore
+from ._trash import TrashStore as TrashStore
+
+class LocalConfig(TypedDict, total=False):
+    """Configuration parameters for LocalStore."""
+
+    automatic_cleanup: bool
+    """Whether to remove empty directories when the objects in them are deleted.
+    Defaults to `False`."""
+    mkdir: bool
+    """Whether to create the prefix directory if it doesn't exist. Defaults to
+    `False`."""
 
 class LocalStore:
     """
@@ -24,12 +51,37 @@ class LocalStore:
     store = LocalStore()
     store = LocalStore(prefix="/path/to/directory")
     store = LocalStore(prefix=Path("."))
+    store = LocalStore(prefix="/path/to/new/directory", mkdir=True)
     ```
+
+    Stores can be pickled, e.g. to send them to worker processes. The prefix is kept as
+    an absolute path, so an unpickled store is rooted at the same directory regardless
+    of the working directory of the process.
     """
-    def __init__(self, prefix: str | Path | None = None) -> None: ...
+    def __init__(
+        self,
+        prefix: str | Path | None = None,
+        *,
+        config: LocalConfig | None = None,
+        **kwargs: Unpack[LocalConfig],
+    ) -> None: ...
     def __repr__(self) -> str: ...
+    def url_for(self, path: str) -> str:
+        """Get the `file://` URL of the object at `path`."""
+    @property
+    def prefix(self) -> Path | None:
+        """The absolute path of the directory the store is rooted at, if any."""
+    @property
+    def config(self) -> LocalConfig:
+        """The configuration the store was constructed with."""
     @classmethod
-    def from_url(cls, url: str) -> LocalStore:
+    def from_url(
+        cls,
+        url: str,
+        *,
+        config: LocalConfig | None = None,
+        **kwargs: Unpack[LocalConfig],
+    ) -> LocalStore:
         """Construct a new LocalStore from a `file://` URL.
 
         **Examples:**
@@ -54,11 +106,48 @@ class MemoryStore:
     ```py
     store = MemoryStore()
     ```
+
+    Or one holding some objects:
+    ```py
+    store = MemoryStore({"data/a.txt": b"a", "data/b.txt": b"b"})
+    ```
+
+    Stores can be pickled, e.g. to send a store set up in a test fixture to worker
+    processes. The unpickled store holds a copy of the objects in the store when it was
+    pickled, which is independent of the original store. Only the paths and contents of
+    objects are kept, not their attributes or e-tags.
     """
-    def __init__(self) -> None: ...
+    def __init__(self, objects: dict[str, bytes | bytearray] | None = None) -> None:
+        """Construct a new MemoryStore.
+
+        Args:
+            objects: Objects to put in the store, by their path. Defaults to None.
+        """
     def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, bytes]:
+        """Get the paths and contents of all objects in the store.
+
+        Returns:
+            The contents of each object by its path, in order of their paths.
+        """
+    def url_for(self, path: str) -> str:
+        """Get the `memory:///` URL of the object at `path`."""
 
 ObjectStore = (
-    AzureStore | GCSStore | HTTPStore | S3Store | LocalStore | MemoryStore | PrefixStore
+    AzureStore
+    | GCSStore
+    | HTTPStore
+    | S3Store
+    | LocalStore
+    | MemoryStore
+    | PrefixStore
+    | GuardrailStore
+    | TrashStore
+    | CircuitBreakerStore
+    | ResolvingStore
+    | PrefixStatsStore
+    | SignedURLStore
+    | DefaultGetOptionsStore
+    | LaneStore
 )
 """All supported ObjectStore implementations."""
@@ -1,4 +1,5 @@
-from typing import TypedDict, Unpack
+from datetime import datetime
+from typing import Awaitable, Callable, Literal, NotRequired, TypedDict, Unpack, overload
 
 import boto3
 import boto3.session
@@ -6,6 +7,7 @@ import botocore
 import botocore.session
 
 from ._client import ClientConfig
+from ._prefix import PrefixStore
 from ._retry import RetryConfig
 
 # Note: we removed `bucket` because it overlaps with an existing named arg in the
@@ -486,6 +488,62 @@ class S3Config(TypedDict, total=False):
     VIRTUAL_HOSTED_STYLE_REQUEST: bool
     """If virtual hosted style request has to be used."""
 
+class S3Credential(TypedDict):
+    """Temporary credentials returned by an
+    [`S3CredentialProvider`][obstore.store.S3CredentialProvider]."""
+
+    access_key_id: str
+    """AWS access key ID."""
+    secret_access_key: str
+    """AWS secret access key."""
+    token: NotRequired[str | None]
+    """AWS session token, for temporary credentials."""
+    expires_at: NotRequired[datetime | None]
+    """When the credentials expire, as a timezone-aware datetime.
+
+    The provider is called again five minutes before this time. If `None` or missing,
+    the credentials are used for the lifetime of the store.
+    """
+
+S3CredentialProvider = Callable[[], S3Credential | Awaitable[S3Credential]]
+"""A callable, sync or async, that returns credentials for an
+[`S3Store`][obstore.store.S3Store].
+
+It's called when the store first needs credentials and again whenever the credentials
+it returned are about to expire. Exceptions raised by the provider are raised from the
+request that needed credentials.
+"""
+
+class S3Quirks(TypedDict):
+    """The gaps of an S3-compatible endpoint, which stores work around.
+
+    Register the quirks of an endpoint with
+    [`register_quirks`][obstore.register_quirks].
+    """
+
+    ignores_if_none_match: bool
+    """Conditional puts with `If-None-Match: *` overwrite existing objects.
+
+    Puts with `mode="create"` raise `NotImplementedError` instead, unless
+    `emulate_if_none_match` is set.
+    """
+    emulate_if_none_match: bool
+    """Puts with `mode="create"` check that the object doesn't exist first.
+
+    This isn't atomic, so a concurrent put can still be overwritten.
+    """
+    lacks_delete_objects: bool
+    """Bulk deletes with `DeleteObjects` aren't supported.
+
+    Deleting many paths makes a request for each path instead.
+    """
+    lacks_upload_part_copy: bool
+    """Multipart copies with `UploadPartCopy` aren't supported.
+
+    [`copy`][obstore.copy] streams objects above its `multipart_threshold` through the
+    client instead.
+    """
+
 class S3Store:
     """
     Configure a connection to Amazon S3 using the specified credentials in the specified
@@ -502,6 +560,51 @@ class S3Store:
 
     Pass `skip_signature=True` as a keyword argument. Or, if you're using
     `S3Store.from_env`, have `AWS_SKIP_SIGNATURE=True` set in the environment.
+
+    **Web identity credentials**:
+
+    When no other credentials are configured, and both `AWS_WEB_IDENTITY_TOKEN_FILE`
+    and `AWS_ROLE_ARN` are set in the environment, credentials are obtained by calling
+    `AssumeRoleWithWebIdentity` with the token in that file. This is how IAM roles for
+    service accounts (IRSA) provide credentials on Kubernetes, and doesn't require
+    `boto3`. The session name can be set with `AWS_ROLE_SESSION_NAME`.
+
+    **Custom credential providers**:
+
+    Pass a callable returning [`S3Credential`][obstore.store.S3Credential] as
+    `credential_provider`, and it's called to refresh credentials as they expire. The
+    callable may be async, in which case it's awaited on the calling event loop in async
+    operations and run with `asyncio.run` in sync operations.
+
+    ```py
+    import boto3
+
+    sts = boto3.client("sts")
+
+    def assume_role() -> S3Credential:
+        creds = sts.assume_role(RoleArn=role_arn, RoleSessionName="obstore")
+        creds = creds["Credentials"]
+        return {
+            "access_key_id": creds["AccessKeyId"],
+            "secret_access_key": creds["SecretAccessKey"],
+            "token": creds["SessionToken"],
+            "expires_at": creds["Expiration"],
+        }
+
+    store = S3Store("bucket-name", credential_provider=assume_role)
+    ```
+
+    Wrap the provider in a
+    [`KeyringCredentialCache`][obstore.credentials.KeyringCredentialCache] to reuse
+    its credentials in later processes until they expire.
+
+    **Bucket region discovery**:
+
+    If S3 redirects a request because the bucket is in a different region than the
+    one configured, the bucket's region is looked up and the request is retried
+    against that region. This happens once per store, and never when a custom
+    `endpoint` is configured. The discovered region is available as
+    [`region`][obstore.store.S3Store.region].
     """
 
     def __init__(
@@ -511,6 +614,8 @@ class S3Store:
         config: S3Config | None = None,
         client_options: ClientConfig | None = None,
         retry_config: RetryConfig | None = None,
+        force_path_style: bool | None = None,
+        credential_provider: S3CredentialProvider | None = None,
         **kwargs: Unpack[S3Config],
     ) -> None:
         """Create a new S3Store
@@ -522,6 +627,18 @@ class S3Store:
             config: AWS Configuration. Values in this config will override values inferred from the environment. Defaults to None.
             client_options: HTTP Client options. Defaults to None.
             retry_config: Retry configuration. Defaults to None.
+            force_path_style: If `True`, put the bucket in the path of request URLs
+                (`https://s3.<region>.amazonaws.com/<bucket>`) rather than in the host
+                name (`https://<bucket>.s3.<region>.amazonaws.com`), as most
+                S3-compatible services require. If `False`, use virtual-hosted-style
+                requests. Raises `ValueError` if `True` and the configuration requires
+                virtual-hosted-style requests, e.g. through
+                `virtual_hosted_style_request` or a virtual-hosted-style URL or
+                endpoint. Defaults to None, which leaves the addressing style
+                configured by `virtual_hosted_style_request`.
+            credential_provider: A callable, sync or async, returning the credentials to
+                sign requests with, which is called again as they expire. Overrides any
+                credentials in `config`. Defaults to None.
 
         Returns:
             S3Store
@@ -535,6 +652,8 @@ class S3Store:
         config: S3Config | None = None,
         client_options: ClientConfig | None = None,
         retry_config: RetryConfig | None = None,
+        force_path_style: bool | None = None,
+        credential_provider: S3CredentialProvider | None = None,
         **kwargs: Unpack[S3Config],
     ) -> S3Store:
         """Construct a new S3Store with regular AWS environment variables
@@ -560,6 +679,18 @@ class S3Store:
             config: AWS Configuration. Values in this config will override values inferred from the environment. Defaults to None.
             client_options: HTTP Client options. Defaults to None.
             retry_config: Retry configuration. Defaults to None.
+            force_path_style: If `True`, put the bucket in the path of request URLs
+                (`https://s3.<region>.amazonaws.com/<bucket>`) rather than in the host
+                name (`https://<bucket>.s3.<region>.amazonaws.com`), as most
+                S3-compatible services require. If `False`, use virtual-hosted-style
+                requests. Raises `ValueError` if `True` and the configuration requires
+                virtual-hosted-style requests, e.g. through
+                `virtual_hosted_style_request` or a virtual-hosted-style URL or
+                endpoint. Defaults to None, which leaves the addressing style
+                configured by `virtual_hosted_style_request`.
+            credential_provider: A callable, sync or async, returning the credentials to
+                sign requests with, which is called again as they expire. Overrides any
+                credentials in `config`. Defaults to None.
 
         Returns:
             S3Store
@@ -574,12 +705,16 @@ class S3Store:
         config: S3Config | None = None,
         client_options: ClientConfig | None = None,
         retry_config: RetryConfig | None = None,
+        force_path_style: bool | None = None,
         **kwargs: Unpack[S3Config],
     ) -> S3Store:
         """Construct a new S3Store with credentials inferred from a boto3 Session
 
         This can be useful to read S3 credentials from [disk-based credentials sources](https://docs.aws.amazon.com/cli/v1/userguide/cli-configure-files.html).
 
+        The session's credentials are read again every five minutes, so credentials that
+        botocore refreshes, such as those of an assumed role, don't expire.
+
         !!! note
             This is a convenience function for users who are already using `boto3` or
             `botocore`. If you're not already using `boto3` or `botocore`, use other
@@ -602,6 +737,15 @@ class S3Store:
             config: AWS Configuration. Values in this config will override values inferred from the session. Defaults to None.
             client_options: HTTP Client options. Defaults to None.
             retry_config: Retry configuration. Defaults to None.
+            force_path_style: If `True`, put the bucket in the path of request URLs
+                (`https://s3.<region>.amazonaws.com/<bucket>`) rather than in the host
+                name (`https://<bucket>.s3.<region>.amazonaws.com`), as most
+                S3-compatible services require. If `False`, use virtual-hosted-style
+                requests. Raises `ValueError` if `True` and the configuration requires
+                virtual-hosted-style requests, e.g. through
+                `virtual_hosted_style_request` or a virtual-hosted-style URL or
+                endpoint. Defaults to None, which leaves the addressing style
+                configured by `virtual_hosted_style_request`.
 
         Returns:
             S3Store
@@ -614,6 +758,8 @@ class S3Store:
         config: S3Config | None = None,
         client_options: ClientConfig | None = None,
         retry_config: RetryConfig | None = None,
+        force_path_style: bool | None = None,
+        credential_provider: S3CredentialProvider | None = None,
         **kwargs: Unpack[S3Config],
     ) -> S3Store:
         """
@@ -639,6 +785,18 @@ class S3Store:
             config: AWS Configuration. Values in this config will override values inferred from the url. Defaults to None.
             client_options: HTTP Client options. Defaults to None.
             retry_config: Retry configuration. Defaults to None.
+            force_path_style: If `True`, put the bucket in the path of request URLs
+                (`https://s3.<region>.amazonaws.com/<bucket>`) rather than in the host
+                name (`https://<bucket>.s3.<region>.amazonaws.com`), as most
+                S3-compatible services require. If `False`, use virtual-hosted-style
+                requests. Raises `ValueError` if `True` and the configuration requires
+                virtual-hosted-style requests, e.g. through
+                `virtual_hosted_style_request` or a virtual-hosted-style URL or
+                endpoint. Defaults to None, which leaves the addressing style
+                configured by `virtual_hosted_style_request`.
+            credential_provider: A callable, sync or async, returning the credentials to
+                sign requests with, which is called again as they expire. Overrides any
+                credentials in `config`. Defaults to None.
 
 
         Returns:
@@ -646,3 +804,106 @@ class S3Store:
         """
 
     def __repr__(self) -> str: ...
+    @overload
+    def with_options(
+        self,
+        *,
+        client_options: ClientConfig | None = None,
+        retry_config: RetryConfig | None = None,
+        prefix: None = None,
+    ) -> S3Store: ...
+    @overload
+    def with_options(
+        self,
+        *,
+        client_options: ClientConfig | None = None,
+        retry_config: RetryConfig | None = None,
+        prefix: str,
+    ) -> PrefixStore: ...
+    def with_options(
+        self,
+        *,
+        client_options: ClientConfig | None = None,
+        retry_config: RetryConfig | None = None,
+        prefix: str | None = None,
+    ) -> S3Store | PrefixStore:
+        """Create a copy of this store with modified options.
+
+        This makes it cheap to derive variants of a configured store, e.g. one with more
+        retries for background jobs. The rest of the configuration, including
+        credentials and any region discovered after a redirect, is kept. If neither
+        `client_options` nor `retry_config` is set, the copy shares this store's
+        connection pool.
+
+        ```py
+        from datetime import timedelta
+
+        background_store = store.with_options(
+            retry_config={
+                "max_retries": 20,
+                "backoff": {
+                    "base": 2,
+                    "init_backoff": timedelta(seconds=1),
+                    "max_backoff": timedelta(minutes=1),
+                },
+                "retry_timeout": timedelta(minutes=30),
+            },
+        )
+        ```
+
+        Keyword Args:
+            client_options: HTTP client options replacing the options of this store.
+                Defaults to `None`, keeping the current options.
+            retry_config: Retry configuration replacing the configuration of this store.
+                Defaults to `None`, keeping the current configuration.
+            prefix: A prefix to apply to every path. If set, the copy is wrapped in a
+                [`PrefixStore`][obstore.store.PrefixStore]. Defaults to `None`.
+
+        Returns:
+            The modified copy of this store, or a `PrefixStore` wrapping it if `prefix`
+            is set.
+        """
+
+    def url_for(self, path: str) -> str:
+        """Get the canonical `s3://` URL of the object at `path`, e.g.
+        `s3://bucket/path/to/file.txt`.
+
+        Each part of `path` is percent-encoded as needed, so that the URL can be passed
+        to [`parse_object_url`][obstore.parse_object_url] to get back the same path.
+        """
+
+    @property
+    def endpoint_url(self) -> str:
+        """The endpoint requests are sent to, e.g. `https://s3.us-east-1.amazonaws.com`.
+
+        This is the effective endpoint after applying environment variables, the
+        configuration, and the URL passed to `from_url`. For path-style requests, the
+        bucket is appended to this endpoint.
+        """
+
+    @property
+    def quirks(self) -> S3Quirks:
+        """The quirks registered for the endpoint of the store.
+
+        These are looked up on every request, so they include quirks registered after
+        the store was created.
+        """
+
+    @property
+    def region(self) -> str:
+        """The region requests are signed for.
+
+        This is the configured region, the region taken from a URL passed to `from_url`,
+        or the region discovered after a request was redirected to it. Defaults to
+        `"us-east-1"` if none of those is set.
+        """
+
+    @property
+    def addressing_style(self) -> Literal["virtual", "path"]:
+        """Whether the bucket is addressed in the host name (`"virtual"`) or in the path
+        (`"path"`) of requests.
+        """
+
+    @property
+    def using_https(self) -> bool:
+        """Whether requests are sent over HTTPS."""
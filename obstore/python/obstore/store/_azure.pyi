@@ -1,6 +1,7 @@
-from typing import TypedDict, Unpack
+from typing import TypedDict, Unpack, overload
 
 from ._client import ClientConfig
+from ._prefix import PrefixStore
 from ._retry import RetryConfig
 
 class AzureConfig(TypedDict, total=False):
@@ -365,3 +366,82 @@ class AzureStore:
         """
 
     def __repr__(self) -> str: ...
+    @overload
+    def with_options(
+        self,
+        *,
+        client_options: ClientConfig | None = None,
+        retry_config: RetryConfig | None = None,
+        prefix: None = None,
+    ) -> AzureStore: ...
+    @overload
+    def with_options(
+        self,
+        *,
+        client_options: ClientConfig | None = None,
+        retry_config: RetryConfig | None = None,
+        prefix: str,
+    ) -> PrefixStore: ...
+    def with_options(
+        self,
+        *,
+        client_options: ClientConfig | None = None,
+        retry_config: RetryConfig | None = None,
+        prefix: str | None = None,
+    ) -> AzureStore | PrefixStore:
+        """Create a copy of this store with modified options.
+
+        This makes it cheap to derive variants of a configured store, e.g. one with more
+        retries for background jobs. The rest of the configuration, including
+        credentials, is kept. If neither `client_options` nor `retry_config` is set, the
+        copy shares this store's connection pool and cached credentials.
+
+        ```py
+        from datetime import timedelta
+
+        background_store = store.with_options(
+            retry_config={
+                "max_retries": 20,
+                "backoff": {
+                    "base": 2,
+                    "init_backoff": timedelta(seconds=1),
+                    "max_backoff": timedelta(minutes=1),
+                },
+                "retry_timeout": timedelta(minutes=30),
+            },
+        )
+        ```
+
+        Keyword Args:
+            client_options: HTTP client options replacing the options of this store.
+                Defaults to `None`, keeping the current options.
+            retry_config: Retry configuration replacing the configuration of this store.
+                Defaults to `None`, keeping the current configuration.
+            prefix: A prefix to apply to every path. If set, the copy is wrapped in a
+                [`PrefixStore`][obstore.store.PrefixStore]. Defaults to `None`.
+
+        Returns:
+            The modified copy of this store, or a `PrefixStore` wrapping it if `prefix`
+            is set.
+        """
+
+    def url_for(self, path: str) -> str:
+        """Get the canonical `https://` URL of the object at `path`, e.g.
+        `https://account.blob.core.windows.net/container/path/to/file.txt`.
+
+        Each part of `path` is percent-encoded as needed, so that the URL can be passed
+        to [`parse_object_url`][obstore.parse_object_url] to get back the same path.
+        """
+
+    @property
+    def endpoint_url(self) -> str:
+        """The endpoint requests are sent to, e.g.
+        `https://account.blob.core.windows.net`.
+
+        This is the effective endpoint after applying environment variables and the
+        configuration, including `use_emulator`.
+        """
+
+    @property
+    def using_https(self) -> bool:
+        """Whether requests are sent over HTTPS."""
@@ -0,0 +1,102 @@
+from datetime import timedelta
+from typing import Callable, Literal
+
+from obstore.store import ObjectStore
+
+CircuitState = Literal["closed", "open", "half_open"]
+"""The state of a [`CircuitBreakerStore`][obstore.store.CircuitBreakerStore].
+
+- `"closed"`: requests are sent to the wrapped store.
+- `"open"`: requests fail immediately, without being sent.
+- `"half_open"`: a single request is sent to probe whether the store has recovered.
+"""
+
+class CircuitBreakerStore:
+    """Store wrapper that fails fast while the wrapped store is failing.
+
+    During a sustained outage, retrying every request against a failing store only adds
+    load to it. This tracks the outcome of requests within a time `window`. Once at
+    least `min_requests` requests were made in the window, and the fraction of them
+    that failed reaches `failure_threshold`, the circuit **opens**: for `open_duration`,
+    every request fails immediately with a
+    [`GenericError`][obstore.exceptions.GenericError], without being sent.
+
+    After `open_duration`, the circuit is **half-open**: the next request is sent to
+    probe the store, while others keep failing immediately. If the probe succeeds the
+    circuit **closes** again, otherwise it reopens.
+
+    Only errors reported by the store's HTTP client count as failures, e.g. connection
+    errors, timeouts or server errors that persisted through the
+    [`RetryConfig`][obstore.store.RetryConfig] of the wrapped store. Responses like a
+    missing object or a failed precondition don't count.
+
+    **Retry budget**:
+
+    With `max_retries` greater than 0, failed requests are retried with exponential
+    backoff while the circuit is closed. Retries are shared across all requests made
+    through this store: within each `window`, at most `retry_budget` times the number
+    of requests, and at least `min_retries`, may be retried. This bounds the extra load
+    caused by retries when many requests fail at once. Consider lowering the
+    `max_retries` of the wrapped store's `retry_config` when using this, as its
+    retries aren't limited by the budget. Listings and multipart upload parts aren't
+    retried.
+
+    **Example**:
+
+    ```py
+    import obstore as obs
+    from obstore.store import CircuitBreakerStore, S3Store
+
+    store = CircuitBreakerStore(
+        S3Store("bucket"),
+        failure_threshold=0.5,
+        on_state_change=lambda old, new: print(f"circuit {old} -> {new}"),
+    )
+    obs.get(store, "file.txt")
+    ```
+    """
+    def __init__(
+        self,
+        store: ObjectStore,
+        *,
+        failure_threshold: float = 0.5,
+        min_requests: int = 10,
+        window: timedelta = timedelta(seconds=60),
+        open_duration: timedelta = timedelta(seconds=30),
+        max_retries: int = 0,
+        retry_budget: float = 0.1,
+        min_retries: int = 10,
+        on_state_change: Callable[[CircuitState, CircuitState], None] | None = None,
+    ) -> None:
+        """Create a new CircuitBreakerStore wrapping an existing store.
+
+        Args:
+            store: The underlying store to wrap.
+
+        Keyword Args:
+            failure_threshold: The fraction of failed requests within `window` at which
+                the circuit opens. Must be greater than 0 and at most 1. Defaults to
+                `0.5`.
+            min_requests: The minimum number of requests within `window` before the
+                circuit may open. Defaults to `10`.
+            window: The duration over which request outcomes and retries are counted.
+                Counts are reset at the start of each window. Defaults to 60 seconds.
+            open_duration: How long the circuit stays open before letting a probe
+                request through. Defaults to 30 seconds.
+            max_retries: The maximum number of times a failed request is retried by
+                this store. Defaults to `0`.
+            retry_budget: The maximum number of retries within `window`, as a fraction
+                of the requests made within it. Defaults to `0.1`.
+            min_retries: The number of retries allowed within `window` regardless of
+                `retry_budget`. Defaults to `10`.
+            on_state_change: A callable of `(old_state, new_state)` called whenever the
+                circuit changes state. The callable is invoked with the GIL held and
+                should not block. Exceptions raised by it are reported through
+                [`sys.unraisablehook`][sys.unraisablehook]. Defaults to `None`.
+        """
+
+    @property
+    def state(self) -> CircuitState:
+        """The current state of the circuit."""
+
+    def __repr__(self) -> str: ...
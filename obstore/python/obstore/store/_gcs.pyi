@@ -65,12 +65,37 @@ class GCSStore:
     If no credentials are explicitly provided, they will be sourced from the environment
     as documented
     [here](https://cloud.google.com/docs/authentication/application-default-credentials).
+
+    **Workload identity federation**:
+
+    Application credentials of type `external_account`, as used by [workload identity
+    federation](https://cloud.google.com/iam/docs/workload-identity-federation) e.g. on
+    Kubernetes, are supported with a file credential source. The token in that file is
+    exchanged for a Google access token, impersonating a service account if the
+    credentials configure one. Point `GOOGLE_APPLICATION_CREDENTIALS` (with
+    `GCSStore.from_env`) or `google_application_credentials` at the credentials file.
+    Explicit service account credentials take precedence.
+
+    **Pickling**:
+
+    Stores can be pickled, e.g. to send them to worker processes. A store is
+    reconstructed from the bucket, prefix and configuration it was constructed with,
+    including any configuration read from the environment.
+
+    **Token caching**:
+
+    Stores configured with the same credentials share them within a process, so an
+    OAuth token fetched by one store is reused by the others until it expires. This
+    avoids a request to the token endpoint per store when many stores are constructed,
+    e.g. one per bucket or per request. Token requests use the client options of the
+    first store constructed with those credentials.
     """
 
     def __init__(
         self,
         bucket: str,
         *,
+        prefix: str | None = None,
         config: GCSConfig | None = None,
         client_options: ClientConfig | None = None,
         retry_config: RetryConfig | None = None,
@@ -82,6 +107,7 @@ class GCSStore:
             bucket: The GCS bucket to use.
 
         Keyword Args:
+            prefix: A prefix to apply to all paths. Defaults to None.
             config: GCS Configuration. Values in this config will override values inferred from the environment. Defaults to None.
             client_options: HTTP Client options. Defaults to None.
             retry_config: Retry configuration. Defaults to None.
@@ -95,6 +121,7 @@ class GCSStore:
         cls,
         bucket: str,
         *,
+        prefix: str | None = None,
         config: GCSConfig | None = None,
         client_options: ClientConfig | None = None,
         retry_config: RetryConfig | None = None,
@@ -115,6 +142,7 @@ class GCSStore:
             bucket: The GCS bucket to use.
 
         Keyword Args:
+            prefix: A prefix to apply to all paths. Defaults to None.
             config: GCS Configuration. Values in this config will override values inferred from the environment. Defaults to None.
             client_options: HTTP Client options. Defaults to None.
             retry_config: Retry configuration. Defaults to None.
@@ -139,10 +167,9 @@ class GCSStore:
 
         - `gs://<bucket>/<path>`
 
-        !!! note
-            Note that `from_url` will not use any additional parts of the path as a
-            bucket prefix. It will only extract the bucket name. If you wish to use a
-            path prefix, consider wrapping this with `PrefixStore`.
+        The path of the URL, if any, is used as the prefix of the store, so that
+        `GCSStore.from_url("gs://bucket/data")` reads `data/file.txt` when asked for
+        `file.txt`.
 
         Args:
             url: well-known storage URL.
@@ -157,3 +184,86 @@ class GCSStore:
         """
 
     def __repr__(self) -> str: ...
+    def with_options(
+        self,
+        *,
+        client_options: ClientConfig | None = None,
+        retry_config: RetryConfig | None = None,
+        prefix: str | None = None,
+    ) -> GCSStore:
+        """Create a copy of this store with modified options.
+
+        This makes it cheap to derive variants of a configured store, e.g. one with more
+        retries for background jobs. The rest of the configuration, including
+        credentials, is kept. If neither `client_options` nor `retry_config` is set, the
+        copy shares this store's connection pool and cached credentials.
+
+        ```py
+        from datetime import timedelta
+
+        background_store = store.with_options(
+            retry_config={
+                "max_retries": 20,
+                "backoff": {
+                    "base": 2,
+                    "init_backoff": timedelta(seconds=1),
+                    "max_backoff": timedelta(minutes=1),
+                },
+                "retry_timeout": timedelta(minutes=30),
+            },
+        )
+        ```
+
+        Keyword Args:
+            client_options: HTTP client options replacing the options of this store.
+                Defaults to `None`, keeping the current options.
+            retry_config: Retry configuration replacing the configuration of this store.
+                Defaults to `None`, keeping the current configuration.
+            prefix: A prefix to apply to every path, under the prefix of this store if
+                it has one. Defaults to `None`.
+
+        Returns:
+            The modified copy of this store.
+        """
+
+    def url_for(self, path: str) -> str:
+        """Get the canonical `gs://` URL of the object at `path`, e.g.
+        `gs://bucket/path/to/file.txt`. The URL includes the prefix of the store.
+
+        Each part of `path` is percent-encoded as needed, so that the URL can be passed
+        to [`parse_object_url`][obstore.parse_object_url] to get back the same path.
+        """
+
+    @property
+    def bucket(self) -> str:
+        """The bucket of the store."""
+
+    @property
+    def prefix(self) -> str | None:
+        """The prefix applied to all paths, if any."""
+
+    @property
+    def config(self) -> GCSConfig:
+        """The configuration the store was constructed with.
+
+        This includes any configuration read from the environment by `from_env` and
+        `from_url`.
+        """
+
+    @property
+    def client_options(self) -> ClientConfig | None:
+        """The HTTP client options the store was constructed with."""
+
+    @property
+    def retry_config(self) -> RetryConfig | None:
+        """The retry configuration the store was constructed with."""
+
+    @property
+    def endpoint_url(self) -> str:
+        """The endpoint requests are sent to, which is always
+        `https://storage.googleapis.com`.
+        """
+
+    @property
+    def using_https(self) -> bool:
+        """Whether requests are sent over HTTPS."""
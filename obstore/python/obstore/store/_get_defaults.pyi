@@ -0,0 +1,84 @@
+from datetime import datetime
+from typing import TypedDict
+
+from obstore.store import ObjectStore
+
+class DefaultGetOptions(TypedDict, total=False):
+    """The get options a [`DefaultGetOptionsStore`][obstore.store.DefaultGetOptionsStore]
+    applies to every read.
+
+    These have the same meaning as the corresponding keys of
+    [`GetOptions`][obstore.GetOptions].
+    """
+
+    if_match: str
+    """Only read the object if its `e_tag` matches."""
+
+    if_none_match: str
+    """Only read the object if its `e_tag` does not match."""
+
+    if_modified_since: datetime
+    """Only read the object if it has been modified since this time."""
+
+    if_unmodified_since: datetime
+    """Only read the object if it has not been modified since this time."""
+
+    version: str
+    """The version of the object to read."""
+
+class DefaultGetOptionsStore:
+    """Store wrapper that applies default get options to every read.
+
+    Applications that always read with the same conditions, e.g. pinned to an object
+    version or guarded by an `e_tag`, can set them once on the store instead of on every
+    call. Options passed to an individual call take precedence over the defaults, key by
+    key. The defaults apply to [`get`][obstore.get], [`get_range`][obstore.get_range],
+    [`get_ranges`][obstore.get_ranges] and [`head`][obstore.head], and to any other
+    function that reads through the store.
+
+    With `range_chunk_size`, ranges larger than it are fetched as concurrent requests of
+    at most that many bytes each, which can increase throughput for large ranges.
+
+    Request headers such as `Accept-Encoding` are not get options, and must be
+    configured on the underlying store's client options instead.
+
+    **Example**:
+
+    ```py
+    import obstore as obs
+    from obstore.store import DefaultGetOptionsStore, S3Store
+
+    store = DefaultGetOptionsStore(
+        S3Store("bucket"),
+        options={"version": "3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY"},
+        range_chunk_size=8 * 1024 * 1024,
+    )
+    obs.get_range(store, "data/file.parquet", start=0, end=64 * 1024 * 1024)
+    ```
+    """
+    def __init__(
+        self,
+        store: ObjectStore,
+        *,
+        options: DefaultGetOptions | None = None,
+        range_chunk_size: int | None = None,
+    ) -> None:
+        """Create a new DefaultGetOptionsStore.
+
+        Args:
+            store: The store to wrap.
+
+        Keyword Args:
+            options: The get options to apply when a call doesn't set them. Ranges and
+                `head` can't be set as defaults.
+            range_chunk_size: If set, split ranges larger than this many bytes into
+                concurrent requests. Defaults to `None`, which fetches each range in a
+                single request.
+        """
+    @property
+    def options(self) -> DefaultGetOptions:
+        """The default get options."""
+    @property
+    def range_chunk_size(self) -> int | None:
+        """The size of the chunks large ranges are split into, if any."""
+    def __repr__(self) -> str: ...
@@ -0,0 +1,93 @@
+from typing import Callable, Literal, Sequence
+
+from obstore.store import ObjectStore
+
+GuardrailOperation = Literal["get", "head", "put", "delete", "list"]
+"""The kind of access passed to the `authorize` callback of a
+[`GuardrailStore`][obstore.store.GuardrailStore].
+
+- `"get"`: reading object bytes, including range requests and the source of a copy.
+- `"head"`: reading object metadata.
+- `"put"`: writing an object, including the destination of a copy or rename.
+- `"delete"`: deleting an object, including the source of a rename.
+- `"list"`: listing a prefix. Listing the root of the store passes an empty path.
+"""
+
+class GuardrailStore:
+    """Store wrapper that enforces client-side usage limits.
+
+    Limits are checked before any request is sent to the wrapped store, so a rejected
+    operation never reaches the network. This is useful when embedding obstore in
+    user-facing tools that need safety bounds.
+
+    - Paths within one of `deny_prefixes` raise
+      [`PermissionDeniedError`][obstore.exceptions.PermissionDeniedError] for every
+      operation, and are filtered out of list results.
+    - With `read_only=True`, writes raise
+      [`PermissionDeniedError`][obstore.exceptions.PermissionDeniedError].
+    - Uploads larger than `max_object_size` raise
+      [`GenericError`][obstore.exceptions.GenericError].
+    - Uploads that would push the total uploaded bytes in the current hour above
+      `max_total_upload_bytes_per_hour` raise
+      [`GenericError`][obstore.exceptions.GenericError].
+    - Operations for which the `authorize` callback returns a falsy value raise
+      [`PermissionDeniedError`][obstore.exceptions.PermissionDeniedError].
+
+    **Example**:
+
+    ```py
+    import obstore as obs
+    from obstore.exceptions import PermissionDeniedError
+    from obstore.store import GuardrailStore, MemoryStore
+
+    store = GuardrailStore(
+        MemoryStore(),
+        max_object_size=1024,
+        deny_prefixes=["internal"],
+    )
+
+    obs.put(store, "data/file.txt", b"foo")
+
+    try:
+        obs.put(store, "internal/secret.txt", b"foo")
+    except PermissionDeniedError:
+        pass
+    ```
+    """
+    def __init__(
+        self,
+        store: ObjectStore,
+        *,
+        max_object_size: int | None = None,
+        max_total_upload_bytes_per_hour: int | None = None,
+        deny_prefixes: Sequence[str] | None = None,
+        read_only: bool = False,
+        authorize: Callable[[str, GuardrailOperation], bool] | None = None,
+    ) -> None:
+        """Create a new GuardrailStore wrapping an existing store.
+
+        Args:
+            store: The underlying store to wrap.
+
+        Keyword Args:
+            max_object_size: The maximum size in bytes of a single uploaded object. For
+                multipart uploads, this is checked as each part is uploaded. Defaults to
+                `None` (no limit).
+            max_total_upload_bytes_per_hour: The maximum number of bytes that may be
+                uploaded through this store within a one-hour window. Defaults to `None`
+                (no limit).
+            deny_prefixes: Path prefixes that may not be accessed through this store.
+                Prefixes are evaluated on a path segment basis. Defaults to `None`.
+            read_only: Whether to reject every operation that writes to the store: puts,
+                deletes, and the destination of copies and renames. Defaults to `False`.
+            authorize: A callable of `(path, operation)` consulted before each
+                operation, after `deny_prefixes` and `read_only` have been checked. Return `False` to
+                reject the operation. This lets embedding applications enforce
+                per-tenant path access control in one place. The callable is invoked
+                with the GIL held and should not block. Defaults to `None`.
+
+                An exception raised by the callable fails the operation with a
+                [`GenericError`][obstore.exceptions.GenericError].
+        """
+
+    def __repr__(self) -> str: ...
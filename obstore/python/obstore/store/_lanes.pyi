@@ -0,0 +1,70 @@
+from typing import Literal
+
+from obstore.store import ObjectStore
+
+Lane = Literal["interactive", "batch"]
+"""A class of traffic with its own concurrency limit.
+
+- `"interactive"`: latency-sensitive requests, such as metadata lookups made while
+  serving a user. Defaults to 64 concurrent requests.
+- `"batch"`: throughput-oriented requests, such as bulk transfers. Defaults to 16
+  concurrent requests.
+"""
+
+class LaneStore:
+    """Store wrapper that assigns all requests made through it to a traffic lane.
+
+    Each lane has a limit on the number of requests in flight at once, shared by every
+    `LaneStore` in that lane across the process. Requests over the limit wait for a
+    request of the same lane to complete, so a spike in batch transfers can't starve
+    interactive operations of the limit.
+
+    Requests only count against the limit while they're in flight: a download holds
+    its slot until its body has been read, a listing until it has been consumed, and a
+    multipart upload takes a slot for each part.
+
+    Each store instance has its own connection pool, so wrap a separate instance of the
+    store for each lane to keep their connections apart too:
+
+    ```py
+    import obstore as obs
+    from obstore.store import LaneStore, S3Store
+
+    interactive = LaneStore(S3Store("bucket"), lane="interactive")
+    batch = LaneStore(S3Store("bucket"), lane="batch", max_concurrency=8)
+
+    obs.head(interactive, "index.json")
+    obs.put(batch, "archive/2024.parquet", data)
+    ```
+    """
+    def __init__(
+        self,
+        store: ObjectStore,
+        *,
+        lane: Lane,
+        max_concurrency: int | None = None,
+    ) -> None:
+        """Create a new LaneStore.
+
+        Args:
+            store: The store to wrap.
+
+        Keyword Args:
+            lane: The lane of the requests made through this store.
+            max_concurrency: The number of concurrent requests of the lane. The limit
+                is set by the first store created in a lane, and passing a different
+                value for a later store raises a `ValueError`. Defaults to the lane's
+                default limit.
+        """
+
+    @property
+    def lane(self) -> Lane:
+        """The lane of this store."""
+
+    @property
+    def max_concurrency(self) -> int:
+        """The number of concurrent requests of the lane."""
+
+    @property
+    def in_flight(self) -> int:
+        """The number of requests of the lane in flight, across all of its stores."""
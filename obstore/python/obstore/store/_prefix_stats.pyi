@@ -0,0 +1,94 @@
+from datetime import timedelta
+from typing import List, TypedDict
+
+from obstore.store import ObjectStore
+
+class PrefixHotspot(TypedDict):
+    """The aggregated statistics of sampled requests to one prefix."""
+
+    prefix: str
+    """The first `depth` path segments shared by the requests."""
+
+    requests: int
+    """The number of sampled requests."""
+
+    errors: int
+    """The number of sampled requests that failed."""
+
+    mean_latency: timedelta
+    """The mean latency of the sampled requests."""
+
+    max_latency: timedelta
+    """The highest latency of the sampled requests."""
+
+class PrefixStatsStore:
+    """Store wrapper that aggregates request statistics by path prefix.
+
+    S3 and other stores scale request rates per key prefix, and throttle requests when
+    a single prefix receives too much traffic. This samples requests made through the
+    store and groups their count, errors and latency by the first `depth` segments of
+    their path, so that the prefixes causing throttling can be found and data can be
+    partitioned accordingly.
+
+    Latency is measured until the response arrives. For downloads, this excludes the
+    time spent reading the response body. Listings are recorded under their prefix once
+    their first result arrives, and copies and renames under their destination.
+
+    **Example**:
+
+    ```py
+    import obstore as obs
+    from obstore.store import PrefixStatsStore, S3Store
+
+    store = PrefixStatsStore(S3Store("bucket"), depth=2, sample_rate=0.1)
+    obs.put(store, "logs/2025/file.txt", b"foo")
+
+    for hotspot in store.hotspots(limit=5):
+        print(hotspot["prefix"], hotspot["requests"], hotspot["mean_latency"])
+    ```
+    """
+    def __init__(
+        self,
+        store: ObjectStore,
+        *,
+        depth: int = 1,
+        sample_rate: float = 1.0,
+    ) -> None:
+        """Create a new PrefixStatsStore wrapping an existing store.
+
+        Args:
+            store: The underlying store to wrap.
+
+        Keyword Args:
+            depth: The number of leading path segments to group requests by. Must be at
+                least 1. Defaults to `1`.
+            sample_rate: The fraction of requests to record. Evenly spaced requests are
+                sampled, so counts can be scaled by `1 / sample_rate` to estimate the
+                total number of requests. Must be greater than 0 and at most 1.
+                Defaults to `1.0`.
+        """
+
+    @property
+    def depth(self) -> int:
+        """The number of leading path segments requests are grouped by."""
+
+    @property
+    def sample_rate(self) -> float:
+        """The fraction of requests that are recorded."""
+
+    def hotspots(self, limit: int | None = 10) -> List[PrefixHotspot]:
+        """The prefixes receiving the most requests.
+
+        Args:
+            limit: The maximum number of prefixes to return, or `None` to return every
+                sampled prefix. Defaults to `10`.
+
+        Returns:
+            The statistics of each prefix, ordered by their number of sampled requests,
+            highest first.
+        """
+
+    def reset(self) -> None:
+        """Clear the statistics recorded so far."""
+
+    def __repr__(self) -> str: ...
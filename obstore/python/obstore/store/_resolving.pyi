@@ -0,0 +1,55 @@
+from typing import Callable, Sequence
+
+from obstore.store import ObjectStore
+
+class ResolvingStore:
+    """Store wrapper that resolves the endpoint of its store at runtime.
+
+    This supports S3-compatible gateways behind service discovery, e.g. Consul or
+    Kubernetes, whose addresses change over time without a proxy in front of them.
+    The wrapped store is created by `factory` for an endpoint found through
+    `endpoints`. When a request fails to connect to the endpoint, the endpoint is
+    resolved again, a new store is created for it, and the request is retried once
+    with the new store.
+
+    Listings and the parts of multipart uploads aren't retried, but later requests
+    use the newly resolved endpoint.
+
+    **Example**:
+
+    ```py
+    from obstore.store import ResolvingStore, S3Store
+
+    def discover() -> str:
+        # For example, look up a healthy gateway in Consul
+        return "http://10.0.0.12:9000"
+
+    store = ResolvingStore(
+        lambda endpoint: S3Store("bucket", endpoint=endpoint),
+        discover,
+    )
+    ```
+    """
+    def __init__(
+        self,
+        factory: Callable[[str], ObjectStore],
+        endpoints: Callable[[], str] | Sequence[str],
+    ) -> None:
+        """Create a new ResolvingStore, resolving its first endpoint.
+
+        Args:
+            factory: A callable creating the store to use for an endpoint.
+            endpoints: Either a callable returning the endpoint to use, called every
+                time the endpoint is resolved, or a static sequence of endpoints, which
+                are tried in turn.
+
+        An exception raised by `factory` or `endpoints` while re-resolving fails the
+        request with a [`GenericError`][obstore.exceptions.GenericError]. Both are
+        invoked with the GIL held and should not block for long.
+        """
+
+    @property
+    def endpoint(self) -> str:
+        """The endpoint currently in use."""
+
+    def __repr__(self) -> str: ...
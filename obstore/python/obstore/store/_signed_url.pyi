@@ -0,0 +1,54 @@
+from typing import Callable
+
+from obstore._sign import HTTP_METHOD
+
+class SignedURLStore:
+    """Store that sends every request to a pre-signed URL.
+
+    This lets untrusted worker processes read and write objects without ever holding
+    credentials. Instead of signing requests itself, the store calls `sign` with the
+    HTTP method and path of each request, which returns a pre-signed URL for it, e.g.
+    by asking a signing service that holds the credentials and decides which paths the
+    worker may access.
+
+    Objects can be fetched, including ranges, their metadata read, uploaded in a single
+    request, and deleted. Listing, copying, renaming and multipart uploads can't be
+    expressed as requests to a single pre-signed URL, so they raise
+    `NotImplementedError`.
+
+    Conditional requests send the corresponding `If-Match`, `If-None-Match`,
+    `If-Modified-Since` and `If-Unmodified-Since` headers, and `mode="create"` sends
+    `If-None-Match: *`. Whether these are honored depends on the backend. The
+    `Cache-Control`, `Content-Disposition`, `Content-Encoding`, `Content-Language` and
+    `Content-Type` attributes are sent as headers, so the URL must be signed in a way
+    that allows them. Other attributes and tags are ignored.
+
+    **Example**:
+
+    ```py
+    import obstore as obs
+    import requests
+    from obstore.store import SignedURLStore
+
+    def sign(method: str, path: str) -> str:
+        response = requests.post(
+            "https://signer.internal/sign", json={"method": method, "path": path}
+        )
+        response.raise_for_status()
+        return response.json()["url"]
+
+    store = SignedURLStore(sign)
+    obs.get(store, "data/file.parquet")
+    ```
+    """
+    def __init__(self, sign: Callable[[HTTP_METHOD, str], str]) -> None:
+        """Create a new SignedURLStore.
+
+        Args:
+            sign: A callable of `(method, path)` returning the pre-signed URL to send
+                the request to. It is called with `"GET"`, `"HEAD"`, `"PUT"` or
+                `"DELETE"`, once for every request. The callable is invoked with the GIL
+                held and should not block for long. An exception raised by it fails the
+                request with a [`GenericError`][obstore.exceptions.GenericError].
+        """
+    def __repr__(self) -> str: ...
@@ -0,0 +1,47 @@
+from obstore.store import ObjectStore
+
+class TrashStore:
+    """Store wrapper that turns deletes into soft deletes.
+
+    Instead of removing an object, [`delete`][obstore.delete] moves it to
+    `{trash_prefix}/{timestamp}/{path}`, where `timestamp` is the UTC time of the
+    delete formatted as `YYYYMMDDTHHMMSS.ffffffZ`. Deleted objects can be restored by
+    copying or renaming them back out of the trash.
+
+    - Objects within `trash_prefix` are hidden from list results, unless the listed
+      prefix is itself within `trash_prefix`.
+    - Deleting an object that is already within `trash_prefix` deletes it permanently.
+    - Objects in the trash are kept until
+      [`purge_trash`][obstore.purge_trash] removes them.
+
+    Moving an object is implemented with the rename of the underlying store, which for
+    most object stores is a copy followed by a delete.
+
+    **Example**:
+
+    ```py
+    from datetime import timedelta
+
+    import obstore as obs
+    from obstore.store import MemoryStore, TrashStore
+
+    store = TrashStore(MemoryStore())
+
+    obs.put(store, "data/file.txt", b"foo")
+    obs.delete(store, "data/file.txt")
+
+    # Permanently delete anything moved to the trash over a week ago
+    obs.purge_trash(store, timedelta(days=7))
+    ```
+    """
+    def __init__(self, store: ObjectStore, *, trash_prefix: str = ".trash") -> None:
+        """Create a new TrashStore wrapping an existing store.
+
+        Args:
+            store: The underlying store to wrap.
+
+        Keyword Args:
+            trash_prefix: The prefix that deleted objects are moved into. Defaults to
+                `".trash"`.
+        """
+    def __repr__(self) -> str: ...
@@ -0,0 +1,54 @@
+"""The classes of the objects returned by obstore, for use in type annotations.
+
+Functions such as [`list`][obstore.list] and [`get`][obstore.get] return instances of
+classes implemented in Rust. This module exports them under stable names, so that
+downstream libraries can annotate functions that accept obstore streams and files:
+
+```py
+from obstore.types import BytesStream
+
+async def total_size(stream: BytesStream) -> int:
+    return sum([len(chunk) async for chunk in stream])
+```
+
+The streams are registered with the abstract base classes of
+[`collections.abc`][collections.abc], so that checks like
+`isinstance(stream, collections.abc.AsyncIterator)` work as they do for Python
+iterators.
+"""
+
+from collections.abc import AsyncIterable, AsyncIterator, Iterable, Iterator
+from typing import TYPE_CHECKING
+
+from ._obstore import (
+    Bytes,
+    BytesReader,
+    BytesStream,
+    GetResult,
+    ListStream,
+    ReadableFile,
+)
+
+if TYPE_CHECKING:
+    from ._buffered import AsyncReadableFile
+else:
+    # Asynchronous files are instances of the same class as synchronous ones
+    AsyncReadableFile = ReadableFile
+
+__all__ = [
+    "AsyncReadableFile",
+    "Bytes",
+    "BytesReader",
+    "BytesStream",
+    "GetResult",
+    "ListStream",
+    "ReadableFile",
+]
+
+for _stream in (ListStream, BytesStream):
+    AsyncIterator.register(_stream)
+    Iterator.register(_stream)
+
+# A GetResult can be iterated over once, returning a BytesStream
+AsyncIterable.register(GetResult)
+Iterable.register(GetResult)
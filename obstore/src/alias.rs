@@ -0,0 +1,260 @@
+//! Alias objects: small pointer objects whose content names another path in the same store.
+//!
+//! Pointers managed with `set_pointer` use the same format, so `get(..., resolve_aliases=True)`
+//! follows them too.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::{ObjectStore, PutMode, UpdateVersion};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+
+use crate::put::PyPutResult;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// Prefix identifying the content of an alias object. The target path follows it.
+const ALIAS_MAGIC: &[u8] = b"obstore-alias:";
+
+/// Objects larger than this are never treated as aliases, so that resolving a path doesn't
+/// download the content of regular objects.
+const MAX_ALIAS_SIZE: usize = 4096;
+
+/// The maximum number of aliases followed before giving up.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+pub(crate) fn encode_alias(target: &Path) -> Bytes {
+    let mut buf = ALIAS_MAGIC.to_vec();
+    buf.extend_from_slice(target.as_ref().as_bytes());
+    buf.into()
+}
+
+pub(crate) fn decode_alias(buf: &[u8]) -> Option<Path> {
+    let target = buf.strip_prefix(ALIAS_MAGIC)?;
+    let target = std::str::from_utf8(target).ok()?;
+    Path::parse(target).ok()
+}
+
+/// Read the target of the alias at `path`, or `None` if it is a regular object.
+async fn read_alias(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+) -> PyObjectStoreResult<Option<Path>> {
+    let meta = store.head(path).await?;
+    if meta.size > MAX_ALIAS_SIZE || meta.size < ALIAS_MAGIC.len() {
+        return Ok(None);
+    }
+    let buf = store.get(path).await?.bytes().await?;
+    Ok(decode_alias(&buf))
+}
+
+/// Follow aliases starting at `path` until reaching an object that is not an alias.
+pub(crate) async fn resolve_alias_inner(
+    store: &Arc<dyn ObjectStore>,
+    path: Path,
+) -> PyObjectStoreResult<Path> {
+    let mut visited = HashSet::new();
+    let mut current = path;
+    loop {
+        if !visited.insert(current.clone()) {
+            return Err(PyValueError::new_err(format!("Alias loop detected at {current}")).into());
+        }
+        if visited.len() > MAX_ALIAS_DEPTH {
+            return Err(PyValueError::new_err(format!(
+                "Exceeded the maximum of {MAX_ALIAS_DEPTH} nested aliases at {current}"
+            ))
+            .into());
+        }
+        match read_alias(store, &current).await? {
+            Some(target) => current = target,
+            None => return Ok(current),
+        }
+    }
+}
+
+async fn put_alias_inner(
+    store: Arc<dyn ObjectStore>,
+    alias_path: Path,
+    target_path: Path,
+) -> PyObjectStoreResult<PyPutResult> {
+    if alias_path == target_path {
+        return Err(PyValueError::new_err("An alias cannot point to itself").into());
+    }
+    let result = store
+        .put(&alias_path, encode_alias(&target_path).into())
+        .await?;
+    Ok(PyPutResult::new(result))
+}
+
+#[pyfunction]
+pub(crate) fn put_alias(
+    py: Python,
+    store: PyObjectStore,
+    alias_path: String,
+    target_path: String,
+) -> PyObjectStoreResult<PyPutResult> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(put_alias_inner(
+            store.into_inner(),
+            alias_path.into(),
+            target_path.into(),
+        ))
+    })
+}
+
+#[pyfunction]
+pub(crate) fn put_alias_async(
+    py: Python,
+    store: PyObjectStore,
+    alias_path: String,
+    target_path: String,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let result =
+            put_alias_inner(store.into_inner(), alias_path.into(), target_path.into()).await?;
+        Ok(result)
+    })
+}
+
+#[pyfunction]
+pub(crate) fn resolve_alias(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+) -> PyObjectStoreResult<String> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let resolved = runtime.block_on(resolve_alias_inner(store.as_ref(), path.into()))?;
+        Ok::<_, PyObjectStoreError>(resolved.to_string())
+    })
+}
+
+#[pyfunction]
+pub(crate) fn resolve_alias_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let resolved = resolve_alias_inner(store.as_ref(), path.into()).await?;
+        Ok(resolved.to_string())
+    })
+}
+
+async fn set_pointer_inner(
+    store: Arc<dyn ObjectStore>,
+    pointer_path: Path,
+    target: Path,
+    expected_current: Option<Path>,
+) -> PyObjectStoreResult<PyPutResult> {
+    let mode = match expected_current {
+        None => PutMode::Overwrite,
+        Some(expected) => {
+            let current = store.get(&pointer_path).await.map_err(|err| match err {
+                object_store::Error::NotFound { path, source } => {
+                    object_store::Error::Precondition { path, source }
+                }
+                err => err,
+            })?;
+            let version = UpdateVersion {
+                e_tag: current.meta.e_tag.clone(),
+                version: current.meta.version.clone(),
+            };
+            let current = decode_alias(&current.bytes().await?).ok_or_else(|| {
+                PyValueError::new_err(format!("{pointer_path} is not a pointer object"))
+            })?;
+            if current != expected {
+                return Err(object_store::Error::Precondition {
+                    path: pointer_path.to_string(),
+                    source: format!("pointer targets {current}, expected {expected}").into(),
+                }
+                .into());
+            }
+            // Fails if another writer swapped the pointer since we read it
+            PutMode::Update(version)
+        }
+    };
+    let result = store
+        .put_opts(&pointer_path, encode_alias(&target).into(), mode.into())
+        .await?;
+    Ok(PyPutResult::new(result))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, pointer_path, target, *, expected_current = None))]
+pub(crate) fn set_pointer(
+    py: Python,
+    store: PyObjectStore,
+    pointer_path: String,
+    target: String,
+    expected_current: Option<String>,
+) -> PyObjectStoreResult<PyPutResult> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(set_pointer_inner(
+            store.into_inner(),
+            pointer_path.into(),
+            target.into(),
+            expected_current.map(|path| path.into()),
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, pointer_path, target, *, expected_current = None))]
+pub(crate) fn set_pointer_async(
+    py: Python,
+    store: PyObjectStore,
+    pointer_path: String,
+    target: String,
+    expected_current: Option<String>,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let result = set_pointer_inner(
+            store.into_inner(),
+            pointer_path.into(),
+            target.into(),
+            expected_current.map(|path| path.into()),
+        )
+        .await?;
+        Ok(result)
+    })
+}
+
+async fn read_pointer_inner(
+    store: Arc<dyn ObjectStore>,
+    pointer_path: Path,
+) -> PyObjectStoreResult<String> {
+    let buf = store.get(&pointer_path).await?.bytes().await?;
+    let target = decode_alias(&buf)
+        .ok_or_else(|| PyValueError::new_err(format!("{pointer_path} is not a pointer object")))?;
+    Ok(target.to_string())
+}
+
+#[pyfunction]
+pub(crate) fn read_pointer(
+    py: Python,
+    store: PyObjectStore,
+    pointer_path: String,
+) -> PyObjectStoreResult<String> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(read_pointer_inner(store.into_inner(), pointer_path.into()))
+    })
+}
+
+#[pyfunction]
+pub(crate) fn read_pointer_async(
+    py: Python,
+    store: PyObjectStore,
+    pointer_path: String,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let target = read_pointer_inner(store.into_inner(), pointer_path.into()).await?;
+        Ok(target)
+    })
+}
@@ -0,0 +1,727 @@
+//! Bucket-level configuration: CORS rules and lifecycle rules.
+//!
+//! These administrative APIs aren't part of object_store, so the requests are made here with
+//! the credentials of the store.
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use object_store::gcp::GoogleCloudStorage;
+use object_store::CredentialProvider;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_object_store::{PyObjectStoreResult, RegionAwareS3};
+use reqwest::{Client, Method, Response, StatusCode};
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::runtime::{future_into_py, get_runtime};
+use crate::signer::SignCapableStore;
+use crate::sigv4::signed_request;
+
+const GCS_API: &str = "https://storage.googleapis.com/storage/v1/b";
+
+/// A CORS rule, in the form shared by all supported stores.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CorsRule {
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+    expose_headers: Vec<String>,
+    max_age_seconds: Option<u64>,
+}
+
+/// Extract an optional key of a dict argument.
+fn get_key<'py, T: FromPyObject<'py>>(
+    dict: &HashMap<String, Bound<'py, PyAny>>,
+    key: &str,
+) -> PyResult<Option<T>> {
+    dict.get(key).map(|x| x.extract()).transpose()
+}
+
+impl<'py> FromPyObject<'py> for CorsRule {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Update to use derive(FromPyObject) when default is implemented:
+        // https://github.com/PyO3/pyo3/issues/4643
+        let dict = ob.extract::<HashMap<String, Bound<PyAny>>>()?;
+        Ok(Self {
+            allowed_origins: get_key(&dict, "allowed_origins")?
+                .ok_or_else(|| PyValueError::new_err("CORS rules require allowed_origins"))?,
+            allowed_methods: get_key(&dict, "allowed_methods")?
+                .ok_or_else(|| PyValueError::new_err("CORS rules require allowed_methods"))?,
+            allowed_headers: get_key(&dict, "allowed_headers")?.unwrap_or_default(),
+            expose_headers: get_key(&dict, "expose_headers")?.unwrap_or_default(),
+            max_age_seconds: get_key(&dict, "max_age_seconds")?,
+        })
+    }
+}
+
+impl<'py> IntoPyObject<'py> for CorsRule {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("allowed_origins", self.allowed_origins)?;
+        dict.set_item("allowed_methods", self.allowed_methods)?;
+        dict.set_item("allowed_headers", self.allowed_headers)?;
+        dict.set_item("expose_headers", self.expose_headers)?;
+        dict.set_item("max_age_seconds", self.max_age_seconds)?;
+        Ok(dict)
+    }
+}
+
+/// A transition of objects to another storage class.
+#[derive(Debug, Clone, IntoPyObject)]
+pub(crate) struct Transition {
+    days: u64,
+    storage_class: String,
+}
+
+impl<'py> FromPyObject<'py> for Transition {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let dict = ob.extract::<HashMap<String, Bound<PyAny>>>()?;
+        let missing = |key| PyValueError::new_err(format!("Transitions require {key}"));
+        Ok(Self {
+            days: get_key(&dict, "days")?.ok_or_else(|| missing("days"))?,
+            storage_class: get_key(&dict, "storage_class")?
+                .ok_or_else(|| missing("storage_class"))?,
+        })
+    }
+}
+
+/// A lifecycle rule, in the form shared by all supported stores.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LifecycleRule {
+    id: Option<String>,
+    prefix: Option<String>,
+    enabled: bool,
+    expiration_days: Option<u64>,
+    noncurrent_expiration_days: Option<u64>,
+    abort_incomplete_multipart_days: Option<u64>,
+    transitions: Vec<Transition>,
+}
+
+impl<'py> FromPyObject<'py> for LifecycleRule {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Update to use derive(FromPyObject) when default is implemented:
+        // https://github.com/PyO3/pyo3/issues/4643
+        let dict = ob.extract::<HashMap<String, Bound<PyAny>>>()?;
+        Ok(Self {
+            id: get_key(&dict, "id")?,
+            prefix: get_key(&dict, "prefix")?,
+            enabled: get_key(&dict, "enabled")?.unwrap_or(true),
+            expiration_days: get_key(&dict, "expiration_days")?,
+            noncurrent_expiration_days: get_key(&dict, "noncurrent_expiration_days")?,
+            abort_incomplete_multipart_days: get_key(&dict, "abort_incomplete_multipart_days")?,
+            transitions: get_key(&dict, "transitions")?.unwrap_or_default(),
+        })
+    }
+}
+
+impl<'py> IntoPyObject<'py> for LifecycleRule {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("id", self.id)?;
+        dict.set_item("prefix", self.prefix)?;
+        dict.set_item("enabled", self.enabled)?;
+        dict.set_item("expiration_days", self.expiration_days)?;
+        dict.set_item(
+            "noncurrent_expiration_days",
+            self.noncurrent_expiration_days,
+        )?;
+        dict.set_item(
+            "abort_incomplete_multipart_days",
+            self.abort_incomplete_multipart_days,
+        )?;
+        dict.set_item("transitions", self.transitions)?;
+        Ok(dict)
+    }
+}
+
+fn generic_error(store: &'static str, message: String) -> object_store::Error {
+    object_store::Error::Generic {
+        store,
+        source: message.into(),
+    }
+}
+
+pub(crate) fn request_error(store: &'static str, err: reqwest::Error) -> object_store::Error {
+    object_store::Error::Generic {
+        store,
+        source: Box::new(err),
+    }
+}
+
+/// Fail on any unsuccessful response, with the error the store returned.
+pub(crate) async fn check_response(
+    store: &'static str,
+    response: Response,
+) -> object_store::Result<String> {
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| request_error(store, err))?;
+    if status.is_success() {
+        return Ok(text);
+    }
+    let path = String::new();
+    let source = format!("Request failed with status {status}: {text}").into();
+    Err(match status {
+        StatusCode::NOT_FOUND => object_store::Error::NotFound { path, source },
+        StatusCode::UNAUTHORIZED => object_store::Error::Unauthenticated { path, source },
+        StatusCode::FORBIDDEN => object_store::Error::PermissionDenied { path, source },
+        _ => object_store::Error::Generic { store, source },
+    })
+}
+
+fn not_supported(what: &str) -> object_store::Error {
+    object_store::Error::NotSupported {
+        source: format!("{what} can't be managed with the credentials of an AzureStore").into(),
+    }
+}
+
+// S3 uses XML documents, which are (de)serialized from these mirrors of its schema.
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename = "CORSConfiguration")]
+struct S3CorsConfiguration {
+    #[serde(rename = "CORSRule", default)]
+    rules: Vec<S3CorsRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct S3CorsRule {
+    #[serde(rename = "AllowedHeader", default)]
+    allowed_headers: Vec<String>,
+    #[serde(rename = "AllowedMethod", default)]
+    allowed_methods: Vec<String>,
+    #[serde(rename = "AllowedOrigin", default)]
+    allowed_origins: Vec<String>,
+    #[serde(rename = "ExposeHeader", default)]
+    expose_headers: Vec<String>,
+    #[serde(
+        rename = "MaxAgeSeconds",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    max_age_seconds: Option<u64>,
+}
+
+impl From<CorsRule> for S3CorsRule {
+    fn from(rule: CorsRule) -> Self {
+        Self {
+            allowed_headers: rule.allowed_headers,
+            allowed_methods: rule.allowed_methods,
+            allowed_origins: rule.allowed_origins,
+            expose_headers: rule.expose_headers,
+            max_age_seconds: rule.max_age_seconds,
+        }
+    }
+}
+
+impl From<S3CorsRule> for CorsRule {
+    fn from(rule: S3CorsRule) -> Self {
+        Self {
+            allowed_origins: rule.allowed_origins,
+            allowed_methods: rule.allowed_methods,
+            allowed_headers: rule.allowed_headers,
+            expose_headers: rule.expose_headers,
+            max_age_seconds: rule.max_age_seconds,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename = "LifecycleConfiguration")]
+struct S3LifecycleConfiguration {
+    #[serde(rename = "Rule", default)]
+    rules: Vec<S3LifecycleRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct S3LifecycleRule {
+    #[serde(rename = "ID", default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(rename = "Filter", default)]
+    filter: S3Filter,
+    /// Rules created before filters were introduced have a prefix instead
+    #[serde(rename = "Prefix", default, skip_serializing)]
+    prefix: Option<String>,
+    #[serde(rename = "Status")]
+    status: String,
+    #[serde(rename = "Transition", default)]
+    transitions: Vec<S3Transition>,
+    #[serde(
+        rename = "Expiration",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    expiration: Option<S3Expiration>,
+    #[serde(
+        rename = "NoncurrentVersionExpiration",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    noncurrent_expiration: Option<S3NoncurrentExpiration>,
+    #[serde(
+        rename = "AbortIncompleteMultipartUpload",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    abort_incomplete_multipart: Option<S3AbortIncompleteMultipart>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct S3Filter {
+    #[serde(rename = "Prefix", default, skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct S3Transition {
+    #[serde(rename = "Days")]
+    days: u64,
+    #[serde(rename = "StorageClass")]
+    storage_class: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct S3Expiration {
+    /// Expirations may be set by date instead, which isn't supported
+    #[serde(rename = "Days", default, skip_serializing_if = "Option::is_none")]
+    days: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct S3NoncurrentExpiration {
+    #[serde(rename = "NoncurrentDays")]
+    noncurrent_days: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct S3AbortIncompleteMultipart {
+    #[serde(rename = "DaysAfterInitiation")]
+    days_after_initiation: u64,
+}
+
+impl From<LifecycleRule> for S3LifecycleRule {
+    fn from(rule: LifecycleRule) -> Self {
+        Self {
+            id: rule.id,
+            filter: S3Filter {
+                prefix: rule.prefix,
+            },
+            prefix: None,
+            status: if rule.enabled { "Enabled" } else { "Disabled" }.to_string(),
+            transitions: rule
+                .transitions
+                .into_iter()
+                .map(|transition| S3Transition {
+                    days: transition.days,
+                    storage_class: transition.storage_class,
+                })
+                .collect(),
+            expiration: rule
+                .expiration_days
+                .map(|days| S3Expiration { days: Some(days) }),
+            noncurrent_expiration: rule
+                .noncurrent_expiration_days
+                .map(|noncurrent_days| S3NoncurrentExpiration { noncurrent_days }),
+            abort_incomplete_multipart: rule.abort_incomplete_multipart_days.map(
+                |days_after_initiation| S3AbortIncompleteMultipart {
+                    days_after_initiation,
+                },
+            ),
+        }
+    }
+}
+
+impl From<S3LifecycleRule> for LifecycleRule {
+    fn from(rule: S3LifecycleRule) -> Self {
+        Self {
+            id: rule.id,
+            prefix: rule.filter.prefix.or(rule.prefix),
+            enabled: rule.status == "Enabled",
+            expiration_days: rule.expiration.and_then(|expiration| expiration.days),
+            noncurrent_expiration_days: rule
+                .noncurrent_expiration
+                .map(|expiration| expiration.noncurrent_days),
+            abort_incomplete_multipart_days: rule
+                .abort_incomplete_multipart
+                .map(|abort| abort.days_after_initiation),
+            transitions: rule
+                .transitions
+                .into_iter()
+                .map(|transition| Transition {
+                    days: transition.days,
+                    storage_class: transition.storage_class,
+                })
+                .collect(),
+        }
+    }
+}
+
+const S3: &str = "S3";
+
+/// Send a request for the `subresource` of the bucket of `store`, such as `?cors`.
+///
+/// Returns `None` if the bucket has no such configuration.
+async fn s3_request(
+    store: &RegionAwareS3,
+    method: Method,
+    subresource: &str,
+    body: Option<String>,
+) -> object_store::Result<Option<String>> {
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let url = Url::parse(&format!("{bucket_url}/?{subresource}"))
+        .map_err(|err| generic_error(S3, err.to_string()))?;
+
+    let mut headers = vec![];
+    let body = body.map(String::into_bytes).unwrap_or_default();
+    if method == Method::PUT {
+        // S3 requires a checksum of the configuration documents
+        let checksum = STANDARD.encode(digest(&SHA256, &body));
+        headers.push(("content-type", "application/xml".to_string()));
+        headers.push(("x-amz-checksum-sha256", checksum));
+        headers.push(("x-amz-sdk-checksum-algorithm", "SHA256".to_string()));
+    }
+    let response = signed_request(&credential, method, url, &region, "s3", headers, body)
+        .send()
+        .await
+        .map_err(|err| request_error(S3, err))?;
+    match check_response(S3, response).await {
+        Ok(text) => Ok(Some(text)),
+        // e.g. NoSuchCORSConfiguration, but not NoSuchBucket
+        Err(object_store::Error::NotFound { source, .. })
+            if source.to_string().contains("<Code>NoSuch")
+                && !source.to_string().contains("<Code>NoSuchBucket") =>
+        {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+pub(crate) fn parse_xml<'de, T: Deserialize<'de>>(xml: &'de str) -> object_store::Result<T> {
+    quick_xml::de::from_str(xml).map_err(|err| generic_error(S3, err.to_string()))
+}
+
+pub(crate) fn to_xml<T: Serialize>(value: &T) -> object_store::Result<String> {
+    quick_xml::se::to_string(value).map_err(|err| generic_error(S3, err.to_string()))
+}
+
+const GCS: &str = "GCS";
+
+/// Read or update the `field` of the metadata of `bucket`, the bucket of a GCS store.
+async fn gcs_request(
+    store: &GoogleCloudStorage,
+    bucket: &str,
+    field: &str,
+    patch: Option<Value>,
+) -> object_store::Result<Value> {
+    let credential = store.credentials().get_credential().await?;
+    let url = format!("{GCS_API}/{bucket}?fields={field}");
+    let request = match patch {
+        Some(patch) => Client::new().patch(url).json(&json!({ field: patch })),
+        None => Client::new().get(url),
+    };
+    let response = request
+        .bearer_auth(&credential.bearer)
+        .send()
+        .await
+        .map_err(|err| request_error(GCS, err))?;
+    let text = check_response(GCS, response).await?;
+    let mut value: Value =
+        serde_json::from_str(&text).map_err(|err| generic_error(GCS, err.to_string()))?;
+    Ok(value[field].take())
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn gcs_cors(rule: &CorsRule) -> Value {
+    let mut value = json!({
+        "origin": rule.allowed_origins,
+        "method": rule.allowed_methods,
+        "responseHeader": rule.expose_headers,
+    });
+    if let Some(max_age_seconds) = rule.max_age_seconds {
+        value["maxAgeSeconds"] = max_age_seconds.into();
+    }
+    value
+}
+
+fn from_gcs_cors(value: &Value) -> CorsRule {
+    CorsRule {
+        allowed_origins: strings(&value["origin"]),
+        allowed_methods: strings(&value["method"]),
+        allowed_headers: vec![],
+        expose_headers: strings(&value["responseHeader"]),
+        max_age_seconds: value["maxAgeSeconds"].as_u64(),
+    }
+}
+
+/// GCS rules have a single action each, so each of our rules may map to several of them.
+fn gcs_lifecycle(rule: &LifecycleRule) -> PyResult<Vec<Value>> {
+    if !rule.enabled {
+        return Err(PyValueError::new_err(
+            "GCS doesn't support disabled lifecycle rules",
+        ));
+    }
+    let with_prefix = |mut condition: Value| {
+        if let Some(prefix) = &rule.prefix {
+            condition["matchesPrefix"] = json!([prefix]);
+        }
+        condition
+    };
+    let mut rules = vec![];
+    if let Some(days) = rule.expiration_days {
+        rules.push(json!({
+            "action": {"type": "Delete"},
+            "condition": with_prefix(json!({"age": days})),
+        }));
+    }
+    if let Some(days) = rule.noncurrent_expiration_days {
+        rules.push(json!({
+            "action": {"type": "Delete"},
+            "condition": with_prefix(json!({"daysSinceNoncurrentTime": days, "isLive": false})),
+        }));
+    }
+    if let Some(days) = rule.abort_incomplete_multipart_days {
+        rules.push(json!({
+            "action": {"type": "AbortIncompleteMultipartUpload"},
+            "condition": with_prefix(json!({"age": days})),
+        }));
+    }
+    for transition in &rule.transitions {
+        rules.push(json!({
+            "action": {"type": "SetStorageClass", "storageClass": transition.storage_class},
+            "condition": with_prefix(json!({"age": transition.days})),
+        }));
+    }
+    Ok(rules)
+}
+
+fn from_gcs_lifecycle(value: &Value) -> LifecycleRule {
+    let action = &value["action"];
+    let condition = &value["condition"];
+    let age = condition["age"].as_u64();
+    let mut rule = LifecycleRule {
+        prefix: strings(&condition["matchesPrefix"]).into_iter().next(),
+        enabled: true,
+        ..Default::default()
+    };
+    match action["type"].as_str() {
+        Some("Delete") => match condition["daysSinceNoncurrentTime"].as_u64() {
+            Some(days) => rule.noncurrent_expiration_days = Some(days),
+            None => rule.expiration_days = age,
+        },
+        Some("AbortIncompleteMultipartUpload") => rule.abort_incomplete_multipart_days = age,
+        Some("SetStorageClass") => rule.transitions.push(Transition {
+            days: age.unwrap_or_default(),
+            storage_class: action["storageClass"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        }),
+        _ => {}
+    }
+    rule
+}
+
+async fn get_bucket_cors_inner(store: SignCapableStore) -> PyObjectStoreResult<Vec<CorsRule>> {
+    match store {
+        SignCapableStore::S3(store) => {
+            let Some(xml) = s3_request(&store, Method::GET, "cors", None).await? else {
+                return Ok(vec![]);
+            };
+            let config: S3CorsConfiguration = parse_xml(&xml)?;
+            Ok(config.rules.into_iter().map(CorsRule::from).collect())
+        }
+        SignCapableStore::Gcs(store, bucket, _) => {
+            let cors = gcs_request(&store, &bucket, "cors", None).await?;
+            Ok(cors
+                .as_array()
+                .map(|rules| rules.iter().map(from_gcs_cors).collect())
+                .unwrap_or_default())
+        }
+        SignCapableStore::Azure(_) => Err(not_supported("CORS rules").into()),
+    }
+}
+
+async fn put_bucket_cors_inner(
+    store: SignCapableStore,
+    rules: Vec<CorsRule>,
+) -> PyObjectStoreResult<()> {
+    match store {
+        SignCapableStore::S3(store) => {
+            if rules.is_empty() {
+                s3_request(&store, Method::DELETE, "cors", None).await?;
+            } else {
+                let config = S3CorsConfiguration {
+                    rules: rules.into_iter().map(S3CorsRule::from).collect(),
+                };
+                s3_request(&store, Method::PUT, "cors", Some(to_xml(&config)?)).await?;
+            }
+        }
+        SignCapableStore::Gcs(store, bucket, _) => {
+            let rules = rules.iter().map(gcs_cors).collect::<Vec<_>>();
+            gcs_request(&store, &bucket, "cors", Some(rules.into())).await?;
+        }
+        SignCapableStore::Azure(_) => return Err(not_supported("CORS rules").into()),
+    }
+    Ok(())
+}
+
+async fn get_lifecycle_rules_inner(
+    store: SignCapableStore,
+) -> PyObjectStoreResult<Vec<LifecycleRule>> {
+    match store {
+        SignCapableStore::S3(store) => {
+            let Some(xml) = s3_request(&store, Method::GET, "lifecycle", None).await? else {
+                return Ok(vec![]);
+            };
+            let config: S3LifecycleConfiguration = parse_xml(&xml)?;
+            Ok(config.rules.into_iter().map(LifecycleRule::from).collect())
+        }
+        SignCapableStore::Gcs(store, bucket, _) => {
+            let lifecycle = gcs_request(&store, &bucket, "lifecycle", None).await?;
+            Ok(lifecycle["rule"]
+                .as_array()
+                .map(|rules| rules.iter().map(from_gcs_lifecycle).collect())
+                .unwrap_or_default())
+        }
+        SignCapableStore::Azure(_) => Err(not_supported("Lifecycle rules").into()),
+    }
+}
+
+async fn put_lifecycle_rules_inner(
+    store: SignCapableStore,
+    rules: Vec<LifecycleRule>,
+) -> PyObjectStoreResult<()> {
+    match store {
+        SignCapableStore::S3(store) => {
+            if rules.is_empty() {
+                s3_request(&store, Method::DELETE, "lifecycle", None).await?;
+            } else {
+                let config = S3LifecycleConfiguration {
+                    rules: rules.into_iter().map(S3LifecycleRule::from).collect(),
+                };
+                s3_request(&store, Method::PUT, "lifecycle", Some(to_xml(&config)?)).await?;
+            }
+        }
+        SignCapableStore::Gcs(store, bucket, _) => {
+            let mut gcs_rules = vec![];
+            for rule in &rules {
+                gcs_rules.extend(gcs_lifecycle(rule)?);
+            }
+            gcs_request(
+                &store,
+                &bucket,
+                "lifecycle",
+                Some(json!({ "rule": gcs_rules })),
+            )
+            .await?;
+        }
+        SignCapableStore::Azure(_) => return Err(not_supported("Lifecycle rules").into()),
+    }
+    Ok(())
+}
+
+#[pyfunction]
+pub(crate) fn get_bucket_cors(
+    py: Python,
+    store: SignCapableStore,
+) -> PyObjectStoreResult<Vec<CorsRule>> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(get_bucket_cors_inner(store)))
+}
+
+#[pyfunction]
+pub(crate) fn get_bucket_cors_async(py: Python, store: SignCapableStore) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move { Ok(get_bucket_cors_inner(store).await?) })
+}
+
+#[pyfunction]
+pub(crate) fn put_bucket_cors(
+    py: Python,
+    store: SignCapableStore,
+    rules: Vec<CorsRule>,
+) -> PyObjectStoreResult<()> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(put_bucket_cors_inner(store, rules)))
+}
+
+#[pyfunction]
+pub(crate) fn put_bucket_cors_async(
+    py: Python,
+    store: SignCapableStore,
+    rules: Vec<CorsRule>,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(
+        py,
+        async move { Ok(put_bucket_cors_inner(store, rules).await?) },
+    )
+}
+
+#[pyfunction]
+pub(crate) fn get_lifecycle_rules(
+    py: Python,
+    store: SignCapableStore,
+) -> PyObjectStoreResult<Vec<LifecycleRule>> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(get_lifecycle_rules_inner(store)))
+}
+
+#[pyfunction]
+pub(crate) fn get_lifecycle_rules_async(
+    py: Python,
+    store: SignCapableStore,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(
+        py,
+        async move { Ok(get_lifecycle_rules_inner(store).await?) },
+    )
+}
+
+#[pyfunction]
+pub(crate) fn put_lifecycle_rules(
+    py: Python,
+    store: SignCapableStore,
+    rules: Vec<LifecycleRule>,
+) -> PyObjectStoreResult<()> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(put_lifecycle_rules_inner(store, rules)))
+}
+
+#[pyfunction]
+pub(crate) fn put_lifecycle_rules_async(
+    py: Python,
+    store: SignCapableStore,
+    rules: Vec<LifecycleRule>,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        Ok(put_lifecycle_rules_inner(store, rules).await?)
+    })
+}
@@ -1,40 +1,67 @@
 use std::io::SeekFrom;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::Bytes;
-use object_store::buffered::BufReader;
-use pyo3::exceptions::{PyIOError, PyStopAsyncIteration, PyStopIteration};
+use futures::future::BoxFuture;
+use object_store::buffered::{BufReader, BufWriter};
+use object_store::{ObjectMeta, ObjectStore};
+use pyo3::exceptions::{PyIOError, PyStopAsyncIteration, PyStopIteration, PyValueError};
 use pyo3::prelude::*;
-use pyo3_async_runtimes::tokio::future_into_py;
+use pyo3::types::PyType;
 use pyo3_bytes::PyBytes;
 use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, Lines};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, Lines};
 use tokio::sync::Mutex;
 
-use crate::runtime::get_runtime;
+use crate::attributes::PyAttributes;
+use crate::journal::JournaledStore;
+use crate::lease::Lease;
+use crate::read_cache::{SharedCacheStore, BLOCK_SIZE};
+use crate::runtime::{future_into_py, get_runtime};
+use crate::shutdown::{register_writer, Finalize, Finalized};
+use crate::tags::PyTagSet;
+
+/// Create a reader of the object described by `meta`, reading through the block cache shared
+/// by the readers of the same version of the object if `shared_cache` is set.
+fn new_reader(store: Arc<dyn ObjectStore>, meta: &ObjectMeta, shared_cache: bool) -> BufReader {
+    if shared_cache {
+        BufReader::with_capacity(SharedCacheStore::wrap(store, meta), meta, BLOCK_SIZE)
+    } else {
+        BufReader::new(store, meta)
+    }
+}
 
 #[pyfunction]
+#[pyo3(signature = (store, path, *, shared_cache = false))]
 pub(crate) fn open(
     py: Python,
     store: PyObjectStore,
     path: String,
+    shared_cache: bool,
 ) -> PyObjectStoreResult<PyReadableFile> {
     let store = store.into_inner();
     let runtime = get_runtime(py)?;
     let meta = py.allow_threads(|| runtime.block_on(store.head(&path.into())))?;
-    let reader = Arc::new(Mutex::new(BufReader::new(store, &meta)));
+    let reader = Arc::new(Mutex::new(new_reader(store, &meta, shared_cache)));
     Ok(PyReadableFile::new(reader, false))
 }
 
 #[pyfunction]
-pub(crate) fn open_async(py: Python, store: PyObjectStore, path: String) -> PyResult<Bound<PyAny>> {
+#[pyo3(signature = (store, path, *, shared_cache = false))]
+pub(crate) fn open_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    shared_cache: bool,
+) -> PyResult<Bound<PyAny>> {
     let store = store.into_inner();
     future_into_py(py, async move {
         let meta = store
             .head(&path.into())
             .await
             .map_err(PyObjectStoreError::ObjectStoreError)?;
-        let reader = Arc::new(Mutex::new(BufReader::new(store, &meta)));
+        let reader = Arc::new(Mutex::new(new_reader(store, &meta, shared_cache)));
         Ok(PyReadableFile::new(reader, true))
     })
 }
@@ -246,3 +273,326 @@ async fn next_line(reader: Arc<Mutex<Lines<BufReader>>>, r#async: bool) -> PyRes
         Err(PyStopIteration::new_err("stream exhausted"))
     }
 }
+
+/// A writer of an object opened with `open_writer`.
+struct OpenWriter {
+    path: String,
+    writer: BufWriter,
+    lease: Option<Lease>,
+}
+
+impl OpenWriter {
+    /// Release the lease on the path, if any, after the upload has completed or been aborted.
+    async fn release(self) -> object_store::Result<()> {
+        match self.lease {
+            Some(lease) => lease.release().await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// Create a writer to `path`, which buffers up to `buffer_size` bytes before starting a
+/// multipart upload, holding `lease` on the path if any.
+fn new_writer(
+    store: Arc<dyn ObjectStore>,
+    path: String,
+    attributes: Option<PyAttributes>,
+    tags: Option<PyTagSet>,
+    buffer_size: usize,
+    max_concurrency: usize,
+    lease: Option<Lease>,
+) -> OpenWriter {
+    let mut writer = BufWriter::with_capacity(store, path.clone().into(), buffer_size)
+        .with_max_concurrency(max_concurrency);
+    if let Some(attributes) = attributes {
+        writer = writer.with_attributes(attributes.into_inner());
+    }
+    if let Some(tags) = tags {
+        writer = writer.with_tags(tags.into_inner());
+    }
+    OpenWriter {
+        path,
+        writer,
+        lease,
+    }
+}
+
+/// Check the arguments of `open_writer`, before any lease is acquired.
+fn check_writer_args(buffer_size: usize, lease: Option<Duration>) -> PyResult<()> {
+    if buffer_size == 0 {
+        return Err(PyValueError::new_err("buffer_size must be greater than 0"));
+    }
+    if lease.is_some_and(|lease| lease.is_zero()) {
+        return Err(PyValueError::new_err("lease must be greater than 0"));
+    }
+    Ok(())
+}
+
+async fn acquire_lease(
+    store: &Arc<dyn ObjectStore>,
+    path: &str,
+    lease: Option<Duration>,
+) -> PyObjectStoreResult<Option<Lease>> {
+    let Some(duration) = lease else {
+        return Ok(None);
+    };
+    Ok(Some(
+        Lease::acquire(store.clone(), &path.into(), duration).await?,
+    ))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, attributes = None, tags = None, buffer_size = 10 * 1024 * 1024, max_concurrency = 12, lease = None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_writer(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    attributes: Option<PyAttributes>,
+    tags: Option<PyTagSet>,
+    buffer_size: usize,
+    max_concurrency: usize,
+    lease: Option<Duration>,
+) -> PyResult<PyWritableFile> {
+    check_writer_args(buffer_size, lease)?;
+    let store = store.into_inner();
+    let lease = match lease {
+        Some(_) => {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(acquire_lease(&store, &path, lease)))?
+        }
+        None => None,
+    };
+    let writer = new_writer(
+        store,
+        path,
+        attributes,
+        tags,
+        buffer_size,
+        max_concurrency,
+        lease,
+    );
+    Ok(PyWritableFile::new(writer, false))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, attributes = None, tags = None, buffer_size = 10 * 1024 * 1024, max_concurrency = 12, lease = None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_writer_async(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    attributes: Option<PyAttributes>,
+    tags: Option<PyTagSet>,
+    buffer_size: usize,
+    max_concurrency: usize,
+    lease: Option<Duration>,
+) -> PyResult<Bound<PyAny>> {
+    check_writer_args(buffer_size, lease)?;
+    let store = store.into_inner();
+    future_into_py(py, async move {
+        let lease = acquire_lease(&store, &path, lease).await?;
+        let writer = new_writer(
+            store,
+            path,
+            attributes,
+            tags,
+            buffer_size,
+            max_concurrency,
+            lease,
+        );
+        Ok(PyWritableFile::new(writer, true))
+    })
+}
+
+#[pyclass(name = "WritableFile", frozen)]
+pub(crate) struct PyWritableFile {
+    writer: Arc<Mutex<Option<OpenWriter>>>,
+    r#async: bool,
+}
+
+impl PyWritableFile {
+    fn new(writer: OpenWriter, r#async: bool) -> Self {
+        let writer = Arc::new(Mutex::new(Some(writer)));
+        let weak = Arc::downgrade(&writer);
+        register_writer(weak);
+        Self { writer, r#async }
+    }
+}
+
+impl Finalize for Mutex<Option<OpenWriter>> {
+    /// Abort the upload, as there is no way to tell whether the data written so far is
+    /// complete.
+    fn finalize(self: Arc<Self>) -> BoxFuture<'static, Finalized> {
+        Box::pin(async move {
+            let Some(mut writer) = self.lock().await.take() else {
+                return Finalized::Closed;
+            };
+            let path = writer.path.clone();
+            if let Err(err) = writer.writer.abort().await {
+                return Finalized::Failed(format!("Failed to abort upload to {path}: {err}"));
+            }
+            match writer.release().await {
+                Ok(()) => Finalized::Aborted(path),
+                Err(err) => Finalized::Failed(format!("Failed to release lease on {path}: {err}")),
+            }
+        })
+    }
+}
+
+#[pymethods]
+impl PyWritableFile {
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (exc_type, _exc_value, _traceback))]
+    fn __exit__(
+        &self,
+        py: Python,
+        exc_type: Option<Bound<PyType>>,
+        _exc_value: Option<Bound<PyAny>>,
+        _traceback: Option<Bound<PyAny>>,
+    ) -> PyResult<PyObject> {
+        // Don't publish a partially written object if the block raised
+        if exc_type.is_some() {
+            self.abort(py)
+        } else {
+            self.close(py)
+        }
+    }
+
+    fn __aenter__(slf: Py<Self>, py: Python) -> PyResult<Bound<PyAny>> {
+        future_into_py(py, async move { Ok(slf) })
+    }
+
+    #[pyo3(signature = (exc_type, exc_value, traceback))]
+    fn __aexit__(
+        &self,
+        py: Python,
+        exc_type: Option<Bound<PyType>>,
+        exc_value: Option<Bound<PyAny>>,
+        traceback: Option<Bound<PyAny>>,
+    ) -> PyResult<PyObject> {
+        self.__exit__(py, exc_type, exc_value, traceback)
+    }
+
+    #[getter]
+    fn closed(&self) -> bool {
+        // A writer that's locked is in use, and so isn't closed
+        self.writer.try_lock().is_ok_and(|writer| writer.is_none())
+    }
+
+    fn write<'py>(&'py self, py: Python<'py>, buf: PyBytes) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, write(writer, buf.into_inner()))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            let out = py.allow_threads(|| runtime.block_on(write(writer, buf.into_inner())))?;
+            Ok(out.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+
+    fn flush<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, flush(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(flush(writer)))?;
+            Ok(py.None())
+        }
+    }
+
+    fn close<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, close(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(close(writer)))?;
+            Ok(py.None())
+        }
+    }
+
+    fn abort<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, abort(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(abort(writer)))?;
+            Ok(py.None())
+        }
+    }
+
+    fn writable(&self) -> bool {
+        true
+    }
+
+    fn seekable(&self) -> bool {
+        false
+    }
+}
+
+async fn write(writer: Arc<Mutex<Option<OpenWriter>>>, buf: Bytes) -> PyResult<usize> {
+    let mut writer = writer.lock().await;
+    let writer = writer
+        .as_mut()
+        .ok_or(PyValueError::new_err("I/O operation on closed file."))?;
+    if let Some(lease) = &writer.lease {
+        lease.check()?;
+    }
+    writer.writer.write_all(&buf).await?;
+    Ok(buf.len())
+}
+
+async fn flush(writer: Arc<Mutex<Option<OpenWriter>>>) -> PyResult<()> {
+    let mut writer = writer.lock().await;
+    let writer = writer
+        .as_mut()
+        .ok_or(PyValueError::new_err("I/O operation on closed file."))?;
+    writer.writer.flush().await?;
+    Ok(())
+}
+
+/// Upload any buffered data and complete the upload, then release the lease on the path, if
+/// any. Closing a closed file does nothing.
+///
+/// The upload is aborted instead if another writer has taken over the lease.
+async fn close(writer: Arc<Mutex<Option<OpenWriter>>>) -> PyResult<()> {
+    let Some(mut writer) = writer.lock().await.take() else {
+        return Ok(());
+    };
+    if let Some(Err(err)) = writer.lease.as_ref().map(Lease::check) {
+        writer
+            .writer
+            .abort()
+            .await
+            .map_err(PyObjectStoreError::from)?;
+        return Err(err);
+    }
+    // The lease is released whether or not the upload completes
+    let completed = writer.writer.shutdown().await;
+    writer.release().await.map_err(PyObjectStoreError::from)?;
+    completed?;
+    Ok(())
+}
+
+async fn abort(writer: Arc<Mutex<Option<OpenWriter>>>) -> PyResult<()> {
+    let Some(mut writer) = writer.lock().await.take() else {
+        return Ok(());
+    };
+    writer
+        .writer
+        .abort()
+        .await
+        .map_err(PyObjectStoreError::from)?;
+    writer.release().await.map_err(PyObjectStoreError::from)?;
+    Ok(())
+}
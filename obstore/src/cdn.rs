@@ -0,0 +1,219 @@
+//! Publishing helpers for S3 buckets served through Amazon CloudFront.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use object_store::CredentialProvider;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_object_store::{PyObjectStoreResult, PyS3Store, RegionAwareS3};
+use reqwest::Method;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use sha1::{Digest, Sha1};
+use url::Url;
+
+use crate::remote::parse_url;
+use crate::runtime::{future_into_py, get_runtime};
+use crate::sigv4::signed_request;
+
+const STORE: &str = "CloudFront";
+
+const CLOUDFRONT_ENDPOINT: &str = "https://cloudfront.amazonaws.com";
+
+/// CloudFront is a global service, whose API is signed for this region.
+const CLOUDFRONT_REGION: &str = "us-east-1";
+
+/// A CloudFront key pair: the ID of a public key in a trusted key group, and its
+/// PEM-encoded private key.
+pub(crate) struct CloudFrontKeyPair {
+    key_pair_id: String,
+    private_key: RsaPrivateKey,
+}
+
+impl<'py> FromPyObject<'py> for CloudFrontKeyPair {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Update to use derive(FromPyObject) when default is implemented:
+        // https://github.com/PyO3/pyo3/issues/4643
+        let dict = ob.extract::<HashMap<String, Bound<PyAny>>>()?;
+        let get = |key: &str| -> PyResult<String> {
+            dict.get(key)
+                .ok_or_else(|| PyValueError::new_err(format!("key_pair is missing {key}")))?
+                .extract()
+        };
+        let pem = get("private_key")?;
+        let private_key = RsaPrivateKey::from_pkcs1_pem(&pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs8_pem(&pem))
+            .map_err(|_| {
+                PyValueError::new_err("private_key must be a PEM-encoded RSA private key")
+            })?;
+        Ok(Self {
+            key_pair_id: get("key_pair_id")?,
+            private_key,
+        })
+    }
+}
+
+/// Base64 with the substitutions CloudFront expects in query strings.
+fn cloudfront_base64(data: &[u8]) -> String {
+    STANDARD
+        .encode(data)
+        .replace('+', "-")
+        .replace('=', "_")
+        .replace('/', "~")
+}
+
+#[pyfunction]
+pub(crate) fn sign_cdn_url(
+    distribution_url: &str,
+    path: &str,
+    expires_in: Duration,
+    key_pair: CloudFrontKeyPair,
+) -> PyResult<String> {
+    let mut url = parse_url(distribution_url)?;
+    url.path_segments_mut()
+        .map_err(|_| PyValueError::new_err(format!("Invalid URL {distribution_url}")))?
+        .pop_if_empty()
+        .extend(path.trim_start_matches('/').split('/'));
+
+    // A canned policy, which CloudFront reconstructs from the URL and its expiry
+    let expires = Utc::now().timestamp() + expires_in.as_secs() as i64;
+    let policy = format!(
+        r#"{{"Statement":[{{"Resource":"{url}","Condition":{{"DateLessThan":{{"AWS:EpochTime":{expires}}}}}}}]}}"#
+    );
+    let hashed = Sha1::digest(policy.as_bytes());
+    let signature = key_pair
+        .private_key
+        .sign(Pkcs1v15Sign::new::<Sha1>(), &hashed)
+        .map_err(|err| PyValueError::new_err(format!("Failed to sign URL: {err}")))?;
+
+    url.query_pairs_mut()
+        .append_pair("Expires", &expires.to_string())
+        .append_pair("Signature", &cloudfront_base64(&signature))
+        .append_pair("Key-Pair-Id", &key_pair.key_pair_id);
+    Ok(url.into())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The text of the first `tag` element in `xml`.
+pub(crate) fn xml_text<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{tag}>"))?;
+    Some(&xml[start..end])
+}
+
+fn invalidation_batch(paths: &[String], caller_reference: &str) -> String {
+    let items = paths
+        .iter()
+        .map(|path| {
+            let path = format!("/{}", path.trim_start_matches('/'));
+            format!("<Path>{}</Path>", xml_escape(&path))
+        })
+        .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><InvalidationBatch xmlns="http://cloudfront.amazonaws.com/doc/2020-05-31/"><Paths><Quantity>{}</Quantity><Items>{items}</Items></Paths><CallerReference>{}</CallerReference></InvalidationBatch>"#,
+        paths.len(),
+        xml_escape(caller_reference)
+    )
+}
+
+async fn invalidate_inner(
+    store: Arc<RegionAwareS3>,
+    distribution_id: String,
+    paths: Vec<String>,
+    caller_reference: Option<String>,
+    endpoint: Url,
+) -> PyObjectStoreResult<String> {
+    let credential = store.current().credentials().get_credential().await?;
+    let caller_reference = caller_reference
+        .unwrap_or_else(|| Utc::now().timestamp_nanos_opt().unwrap_or(0).to_string());
+    let body = invalidation_batch(&paths, &caller_reference);
+    let url = endpoint
+        .join(&format!(
+            "/2020-05-31/distribution/{distribution_id}/invalidation"
+        ))
+        .map_err(|err| {
+            PyValueError::new_err(format!("Invalid distribution ID {distribution_id}: {err}"))
+        })?;
+    let request = signed_request(
+        &credential,
+        Method::POST,
+        url,
+        CLOUDFRONT_REGION,
+        "cloudfront",
+        vec![("content-type", "application/xml".to_string())],
+        body.into_bytes(),
+    );
+    let generic = |source: Box<dyn std::error::Error + Send + Sync>| object_store::Error::Generic {
+        store: STORE,
+        source,
+    };
+    let response = request.send().await.map_err(|err| generic(Box::new(err)))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| generic(Box::new(err)))?;
+    if !status.is_success() {
+        let message = xml_text(&text, "Message").unwrap_or(&text);
+        return Err(generic(
+            format!("Invalidation of {distribution_id} failed with status {status}: {message}")
+                .into(),
+        )
+        .into());
+    }
+    Ok(xml_text(&text, "Id").unwrap_or_default().to_string())
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, distribution_id, paths, *, caller_reference = None, endpoint = None))]
+pub(crate) fn invalidate(
+    py: Python,
+    store: &Bound<PyS3Store>,
+    distribution_id: String,
+    paths: Vec<String>,
+    caller_reference: Option<String>,
+    endpoint: Option<String>,
+) -> PyObjectStoreResult<String> {
+    let store = store.borrow().region_aware().clone();
+    let endpoint = parse_url(endpoint.as_deref().unwrap_or(CLOUDFRONT_ENDPOINT))?;
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(invalidate_inner(
+            store,
+            distribution_id,
+            paths,
+            caller_reference,
+            endpoint,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, distribution_id, paths, *, caller_reference = None, endpoint = None))]
+pub(crate) fn invalidate_async<'py>(
+    py: Python<'py>,
+    store: &Bound<PyS3Store>,
+    distribution_id: String,
+    paths: Vec<String>,
+    caller_reference: Option<String>,
+    endpoint: Option<String>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let store = store.borrow().region_aware().clone();
+    let endpoint = parse_url(endpoint.as_deref().unwrap_or(CLOUDFRONT_ENDPOINT))?;
+    future_into_py(py, async move {
+        let id =
+            invalidate_inner(store, distribution_id, paths, caller_reference, endpoint).await?;
+        Ok(id)
+    })
+}
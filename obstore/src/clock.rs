@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3_object_store::clock;
+
+#[pyfunction]
+#[pyo3(signature = (start, /))]
+pub(crate) fn set_clock(start: Option<DateTime<Utc>>) {
+    clock::set_clock(start);
+}
+
+#[pyfunction]
+#[pyo3(signature = (delta, /))]
+pub(crate) fn advance_clock(delta: Duration) -> PyResult<()> {
+    if !clock::advance_clock(delta) {
+        return Err(PyRuntimeError::new_err(
+            "No virtual clock has been set with set_clock",
+        ));
+    }
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (seed, /))]
+pub(crate) fn seed_jitter(seed: Option<u64>) {
+    clock::seed_jitter(seed);
+}
@@ -0,0 +1,241 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use indexmap::IndexMap;
+use object_store::path::Path;
+use object_store::{GetOptions, GetRange, ObjectStore, PutPayload};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_object_store::{
+    InvalidRangeError, PyObjectStore, PyObjectStoreError, PyObjectStoreResult,
+};
+
+use crate::ranges;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The content of the small object the checks are run against.
+const SMALL: &[u8] = b"abc";
+
+/// Which of the two objects written by [`check_store_conformance`] a check reads.
+#[derive(Clone, Copy)]
+enum Object {
+    Small,
+    Empty,
+}
+
+/// The request issued by a check.
+enum Request {
+    Get(GetRange),
+    GetRange(Range<usize>),
+    GetRanges(&'static [Range<usize>]),
+}
+
+struct Check {
+    name: &'static str,
+    object: Object,
+    request: Request,
+    /// The expected bytes of each requested range, or `None` if an [`InvalidRangeError`] is
+    /// expected.
+    expected: Option<&'static [&'static [u8]]>,
+}
+
+const CHECKS: &[Check] = &[
+    Check {
+        name: "suffix_larger_than_object",
+        object: Object::Small,
+        request: Request::Get(GetRange::Suffix(10)),
+        expected: Some(&[b"abc"]),
+    },
+    Check {
+        name: "suffix_of_zero_bytes",
+        object: Object::Small,
+        request: Request::Get(GetRange::Suffix(0)),
+        expected: Some(&[b""]),
+    },
+    Check {
+        name: "suffix_of_zero_length_object",
+        object: Object::Empty,
+        request: Request::Get(GetRange::Suffix(10)),
+        expected: Some(&[b""]),
+    },
+    Check {
+        name: "offset_at_end",
+        object: Object::Small,
+        request: Request::Get(GetRange::Offset(3)),
+        expected: Some(&[b""]),
+    },
+    Check {
+        name: "offset_past_end",
+        object: Object::Small,
+        request: Request::Get(GetRange::Offset(4)),
+        expected: None,
+    },
+    Check {
+        name: "offset_of_zero_length_object",
+        object: Object::Empty,
+        request: Request::Get(GetRange::Offset(0)),
+        expected: Some(&[b""]),
+    },
+    Check {
+        name: "empty_range",
+        object: Object::Small,
+        request: Request::GetRange(1..1),
+        expected: Some(&[b""]),
+    },
+    Check {
+        name: "range_ending_past_end",
+        object: Object::Small,
+        request: Request::GetRange(1..10),
+        expected: Some(&[b"bc"]),
+    },
+    Check {
+        name: "range_starting_past_end",
+        object: Object::Small,
+        request: Request::GetRange(5..10),
+        expected: None,
+    },
+    Check {
+        name: "range_of_zero_length_object",
+        object: Object::Empty,
+        request: Request::GetRange(0..10),
+        expected: Some(&[b""]),
+    },
+    Check {
+        name: "ranges_with_edge_cases",
+        object: Object::Small,
+        request: Request::GetRanges(&[0..1, 1..1, 2..10]),
+        expected: Some(&[b"a", b"", b"c"]),
+    },
+];
+
+pub(crate) struct CheckResult {
+    name: &'static str,
+    error: Option<String>,
+}
+
+impl<'py> IntoPyObject<'py> for CheckResult {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let mut dict = IndexMap::with_capacity(3);
+        dict.insert("name", self.name.into_pyobject(py)?.into_any());
+        dict.insert(
+            "passed",
+            self.error
+                .is_none()
+                .into_pyobject(py)?
+                .to_owned()
+                .into_any(),
+        );
+        dict.insert("error", self.error.into_pyobject(py)?.into_any());
+        dict.into_pyobject(py)
+    }
+}
+
+async fn request(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+    request: &Request,
+) -> PyObjectStoreResult<Vec<Bytes>> {
+    match request {
+        Request::Get(range) => {
+            let options = GetOptions {
+                range: Some(range.clone()),
+                ..Default::default()
+            };
+            let result = ranges::get_opts(store, path, options).await?;
+            Ok(vec![result.bytes().await?])
+        }
+        Request::GetRange(range) => Ok(vec![ranges::get_range(store, path, range.clone()).await?]),
+        Request::GetRanges(bounds) => Ok(ranges::get_ranges(store, path, bounds).await?),
+    }
+}
+
+/// Describe how the outcome of a check differs from the expected one, if it does.
+fn mismatch(
+    expected: Option<&'static [&'static [u8]]>,
+    actual: PyObjectStoreResult<Vec<Bytes>>,
+) -> Option<String> {
+    let expected = expected.map(|expected| {
+        expected
+            .iter()
+            .copied()
+            .map(Bytes::from_static)
+            .collect::<Vec<_>>()
+    });
+    match (expected, actual) {
+        (Some(expected), Ok(actual)) if actual == expected => None,
+        (Some(expected), Ok(actual)) => Some(format!("Expected {expected:?}, got {actual:?}")),
+        (None, Ok(actual)) => Some(format!("Expected InvalidRangeError, got {actual:?}")),
+        (expected, Err(err)) => {
+            let err = PyErr::from(err);
+            let is_invalid_range =
+                Python::with_gil(|py| err.is_instance_of::<InvalidRangeError>(py));
+            match expected {
+                None if is_invalid_range => None,
+                _ => Some(err.to_string()),
+            }
+        }
+    }
+}
+
+async fn check_store_conformance_inner(
+    store: Arc<dyn ObjectStore>,
+    prefix: Path,
+) -> PyObjectStoreResult<Vec<CheckResult>> {
+    let small = prefix.child("small");
+    let empty = prefix.child("empty");
+    store.put(&small, PutPayload::from_static(SMALL)).await?;
+    store.put(&empty, PutPayload::new()).await?;
+
+    let mut results = Vec::with_capacity(CHECKS.len());
+    for check in CHECKS {
+        let path = match check.object {
+            Object::Small => &small,
+            Object::Empty => &empty,
+        };
+        let actual = request(&store, path, &check.request).await;
+        results.push(CheckResult {
+            name: check.name,
+            error: mismatch(check.expected, actual),
+        });
+    }
+
+    // Clean up on a best-effort basis, the results are more useful than a cleanup error
+    let _ = store.delete(&small).await;
+    let _ = store.delete(&empty).await;
+    Ok(results)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix = "obstore-conformance".to_string()))]
+pub(crate) fn check_store_conformance(
+    py: Python,
+    store: PyObjectStore,
+    prefix: String,
+) -> PyObjectStoreResult<Vec<CheckResult>> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let out = runtime.block_on(check_store_conformance_inner(
+            store.into_inner(),
+            prefix.into(),
+        ))?;
+        Ok::<_, PyObjectStoreError>(out)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix = "obstore-conformance".to_string()))]
+pub(crate) fn check_store_conformance_async(
+    py: Python,
+    store: PyObjectStore,
+    prefix: String,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let out = check_store_conformance_inner(store.into_inner(), prefix.into()).await?;
+        Ok(out)
+    })
+}
@@ -1,50 +1,311 @@
-use object_store::ObjectStore;
+use std::ops::Range;
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use object_store::multipart::{MultipartStore, PartId};
+use object_store::path::Path;
+use object_store::{CredentialProvider, ObjectMeta, ObjectStore, PutResult};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use obstore_core::copy::{part_ranges, stream_copy};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use pyo3_object_store::{PyObjectStoreError, PyObjectStoreResult, PyS3Store, RegionAwareS3};
+use reqwest::Method;
+use url::Url;
+
+use crate::bucket::{check_response, request_error};
+use crate::cdn::xml_text;
+use crate::journal::{JournaledStore, Uploads};
+use crate::runtime::{future_into_py, get_runtime};
+use crate::sigv4::{signed_request, uri_encode};
+
+const S3: &str = "S3";
+
+/// The largest object S3 copies with a single request.
+pub(crate) const MAX_SINGLE_COPY: usize = 5 * 1024 * 1024 * 1024;
+
+/// The smallest part of a multipart upload, other than the last.
+pub(crate) const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// A store to copy within, which is copied with multipart copies if it's an S3 store.
+///
+/// Stores wrapping an S3 store, such as a `PrefixStore`, are copied with single requests.
+pub(crate) struct CopyStore {
+    pub(crate) store: Arc<dyn ObjectStore>,
+    pub(crate) s3: Option<Arc<RegionAwareS3>>,
+}
+
+impl<'py> FromPyObject<'py> for CopyStore {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            store: ob.extract::<JournaledStore>()?.into_inner(),
+            s3: ob
+                .downcast::<PyS3Store>()
+                .ok()
+                .map(|store| store.get().region_aware().clone()),
+        })
+    }
+}
+
+/// How to copy objects too large to be copied with a single request.
+struct MultipartCopy {
+    threshold: usize,
+    part_size: usize,
+    max_concurrency: usize,
+    on_part_complete: Option<PyObject>,
+}
+
+impl MultipartCopy {
+    fn try_new(
+        threshold: usize,
+        part_size: usize,
+        max_concurrency: usize,
+        on_part_complete: Option<PyObject>,
+    ) -> PyResult<Self> {
+        if threshold > MAX_SINGLE_COPY {
+            return Err(PyValueError::new_err(
+                "multipart_threshold can't be larger than 5 GiB, the largest single copy",
+            ));
+        }
+        if !(MIN_PART_SIZE..=MAX_SINGLE_COPY).contains(&part_size) {
+            return Err(PyValueError::new_err(
+                "part_size must be between 5 MiB and 5 GiB",
+            ));
+        }
+        Ok(Self {
+            threshold,
+            part_size,
+            max_concurrency,
+            on_part_complete,
+        })
+    }
+}
+
+/// Encode the segments of `path`, as S3 expects them in request paths and copy sources.
+pub(crate) fn encode_path(path: &Path) -> String {
+    path.parts()
+        .map(|part| uri_encode(part.as_ref()))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Split `range` into parts of at least `part_size` bytes, unless it's shorter than that.
+pub(crate) fn split_range(range: Range<usize>, part_size: usize) -> Vec<Range<usize>> {
+    let (start, len) = (range.start, range.len());
+    let count = (len / part_size).max(1);
+    (0..count)
+        .map(|i| start + len * i / count..start + len * (i + 1) / count)
+        .filter(|range| !range.is_empty())
+        .collect()
+}
+
+/// Copy the `range` of `source` to the part at `index` of an upload.
+pub(crate) async fn copy_part(
+    store: &RegionAwareS3,
+    source: &ObjectMeta,
+    to: &Path,
+    upload_id: &str,
+    index: usize,
+    range: Range<usize>,
+) -> object_store::Result<PartId> {
+    // Fetched for each part, as a large copy can outlive temporary credentials
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let url = Url::parse(&format!(
+        "{bucket_url}/{}?partNumber={}&uploadId={}",
+        encode_path(to),
+        index + 1,
+        uri_encode(upload_id)
+    ))
+    .map_err(|err| object_store::Error::Generic {
+        store: S3,
+        source: Box::new(err),
+    })?;
+    let mut headers = vec![
+        (
+            "x-amz-copy-source",
+            format!("/{}/{}", store.bucket(), encode_path(&source.location)),
+        ),
+        (
+            "x-amz-copy-source-range",
+            format!("bytes={}-{}", range.start, range.end - 1),
+        ),
+    ];
+    // Don't assemble parts of different versions if the source is overwritten mid-copy
+    if let Some(e_tag) = &source.e_tag {
+        headers.push(("x-amz-copy-source-if-match", e_tag.clone()));
+    }
+    let response = signed_request(
+        &credential,
+        Method::PUT,
+        url,
+        &region,
+        "s3",
+        headers,
+        vec![],
+    )
+    .send()
+    .await
+    .map_err(|err| request_error(S3, err))?;
+    let text = check_response(S3, response).await?;
+    // S3 can report a failed copy in the body of a successful response
+    match xml_text(&text, "ETag") {
+        Some(e_tag) if !text.contains("<Error>") => Ok(PartId {
+            content_id: e_tag.replace("&quot;", "\""),
+        }),
+        _ => Err(object_store::Error::Generic {
+            store: S3,
+            source: format!("Copying part {} failed: {text}", index + 1).into(),
+        }),
+    }
+}
+
+/// Copy `source` to `to` with a multipart upload whose parts are copied from ranges of the
+/// source, aborting the upload if any part fails.
+async fn multipart_copy(
+    store: &RegionAwareS3,
+    source: &ObjectMeta,
+    to: &Path,
+    options: &MultipartCopy,
+) -> PyObjectStoreResult<PutResult> {
+    let s3 = store.current();
+    let upload = Uploads::s3(store).create(to).await?;
+    let upload_id = &upload.upload_id;
+    let copy_parts = async {
+        let mut parts = buffer_unordered(
+            part_ranges(source.size, options.part_size)
+                .into_iter()
+                .enumerate()
+                .map(|(index, range)| async move {
+                    let size = range.len();
+                    let part = copy_part(store, source, to, upload_id, index, range).await?;
+                    if let Some(on_part_complete) = &options.on_part_complete {
+                        Python::with_gil(|py| on_part_complete.call1(py, (index, size)))?;
+                    }
+                    Ok::<_, PyObjectStoreError>((index, part))
+                }),
+            Concurrency::Fixed(options.max_concurrency),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+        parts.sort_unstable_by_key(|(index, _)| *index);
+        let parts = parts.into_iter().map(|(_, part)| part).collect();
+        Ok::<_, PyObjectStoreError>(s3.complete_multipart(to, upload_id, parts).await?)
+    };
+    match copy_parts.await {
+        Ok(result) => {
+            upload.end().await;
+            Ok(result)
+        }
+        Err(err) => {
+            if s3.abort_multipart(to, upload_id).await.is_ok() {
+                upload.end().await;
+            }
+            Err(err)
+        }
+    }
+}
 
-use crate::runtime::get_runtime;
+async fn copy_inner(
+    store: CopyStore,
+    from_: Path,
+    to: Path,
+    overwrite: bool,
+    options: MultipartCopy,
+) -> PyObjectStoreResult<()> {
+    if let (Some(s3), true) = (&store.s3, overwrite) {
+        let source = store.store.head(&from_).await?;
+        if source.size > options.threshold {
+            if s3.quirks().lacks_upload_part_copy {
+                stream_copy(store.store.as_ref(), &source, &to, options.max_concurrency).await?;
+            } else {
+                multipart_copy(s3, &source, &to, &options).await?;
+            }
+            return Ok(());
+        }
+    }
+    if overwrite {
+        store.store.copy(&from_, &to).await?;
+    } else {
+        store.store.copy_if_not_exists(&from_, &to).await?;
+    }
+    Ok(())
+}
 
 #[pyfunction]
-#[pyo3(signature = (store, from_, to, *, overwrite = true))]
+#[pyo3(signature = (
+    store,
+    from_,
+    to,
+    *,
+    overwrite = true,
+    multipart_threshold = MAX_SINGLE_COPY,
+    part_size = 512 * 1024 * 1024,
+    max_concurrency = 12,
+    on_part_complete = None,
+))]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn copy(
     py: Python,
-    store: PyObjectStore,
+    store: CopyStore,
     from_: String,
     to: String,
     overwrite: bool,
+    multipart_threshold: usize,
+    part_size: usize,
+    max_concurrency: usize,
+    on_part_complete: Option<PyObject>,
 ) -> PyObjectStoreResult<()> {
     let runtime = get_runtime(py)?;
-    let from_ = from_.into();
-    let to = to.into();
+    let options = MultipartCopy::try_new(
+        multipart_threshold,
+        part_size,
+        max_concurrency,
+        on_part_complete,
+    )?;
     py.allow_threads(|| {
-        let fut = if overwrite {
-            store.as_ref().copy(&from_, &to)
-        } else {
-            store.as_ref().copy_if_not_exists(&from_, &to)
-        };
-        runtime.block_on(fut)?;
-        Ok::<_, PyObjectStoreError>(())
+        runtime.block_on(copy_inner(
+            store,
+            from_.into(),
+            to.into(),
+            overwrite,
+            options,
+        ))
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (store, from_, to, *, overwrite = true))]
+#[pyo3(signature = (
+    store,
+    from_,
+    to,
+    *,
+    overwrite = true,
+    multipart_threshold = MAX_SINGLE_COPY,
+    part_size = 512 * 1024 * 1024,
+    max_concurrency = 12,
+    on_part_complete = None,
+))]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn copy_async(
     py: Python,
-    store: PyObjectStore,
+    store: CopyStore,
     from_: String,
     to: String,
     overwrite: bool,
+    multipart_threshold: usize,
+    part_size: usize,
+    max_concurrency: usize,
+    on_part_complete: Option<PyObject>,
 ) -> PyResult<Bound<PyAny>> {
-    let from_ = from_.into();
-    let to = to.into();
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let fut = if overwrite {
-            store.as_ref().copy(&from_, &to)
-        } else {
-            store.as_ref().copy_if_not_exists(&from_, &to)
-        };
-        fut.await.map_err(PyObjectStoreError::ObjectStoreError)?;
+    let options = MultipartCopy::try_new(
+        multipart_threshold,
+        part_size,
+        max_concurrency,
+        on_part_complete,
+    )?;
+    future_into_py(py, async move {
+        copy_inner(store, from_.into(), to.into(), overwrite, options).await?;
         Ok(())
     })
 }
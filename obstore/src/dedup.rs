@@ -0,0 +1,396 @@
+//! Experimental deduplicating uploads using content-defined chunking.
+//!
+//! Payloads are split into chunks with [FastCDC](https://www.usenix.org/conference/atc16/technical-sessions/presentation/xia),
+//! each chunk is stored once under a pool prefix keyed by its SHA-256 digest, and a small JSON
+//! manifest listing the chunks is written to the requested path.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use futures::stream::{FuturesUnordered, StreamExt, TryStreamExt};
+use indexmap::IndexMap;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_bytes::PyBytes;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+
+use crate::put::PutInput;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The version of the manifest format written by `put_dedup`.
+const MANIFEST_VERSION: u32 = 1;
+
+/// Random values used by the gear rolling hash, generated with splitmix64.
+///
+/// These must never change, otherwise chunk boundaries of newly uploaded data will no longer line
+/// up with previously uploaded chunks.
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0u64;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// A mask with the highest `bits` bits set.
+///
+/// The gear hash shifts left, so the high bits are influenced by the most recent bytes.
+const fn high_bits_mask(bits: u32) -> u64 {
+    ((1u64 << bits) - 1) << (64 - bits)
+}
+
+/// FastCDC chunker with normalized chunking.
+struct Chunker {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    /// Stricter mask used before reaching the average size
+    mask_s: u64,
+    /// Looser mask used after reaching the average size
+    mask_l: u64,
+}
+
+impl Chunker {
+    fn try_new(min_size: usize, avg_size: usize, max_size: usize) -> PyResult<Self> {
+        if min_size < 64 {
+            return Err(PyValueError::new_err(
+                "min_chunk_size must be at least 64 bytes",
+            ));
+        }
+        if !(min_size <= avg_size && avg_size <= max_size) {
+            return Err(PyValueError::new_err(
+                "Chunk sizes must satisfy min_chunk_size <= avg_chunk_size <= max_chunk_size",
+            ));
+        }
+        let bits = avg_size.ilog2();
+        Ok(Self {
+            min_size,
+            avg_size,
+            max_size,
+            mask_s: high_bits_mask(bits + 1),
+            mask_l: high_bits_mask(bits - 1),
+        })
+    }
+
+    /// The length of the first chunk in `data`.
+    ///
+    /// Boundaries only depend on the first `max_size` bytes, so the result is final as long as
+    /// `data` holds at least `max_size` bytes or is the remainder of the input.
+    fn cut_point(&self, data: &[u8]) -> usize {
+        let len = data.len();
+        if len <= self.min_size {
+            return len;
+        }
+        let end = len.min(self.max_size);
+        let normal = self.avg_size.min(end);
+
+        let mut hash = 0u64;
+        let mut i = self.min_size;
+        while i < normal {
+            hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+            if hash & self.mask_s == 0 {
+                return i + 1;
+            }
+            i += 1;
+        }
+        while i < end {
+            hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+            if hash & self.mask_l == 0 {
+                return i + 1;
+            }
+            i += 1;
+        }
+        end
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    size: usize,
+    pool_prefix: String,
+    chunks: Vec<ManifestChunk>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ManifestChunk {
+    hash: String,
+    size: usize,
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    digest(&SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Upload a chunk unless an identical one already exists in the pool.
+///
+/// Returns the number of bytes uploaded, if any.
+async fn put_chunk(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    chunk: Bytes,
+) -> PyObjectStoreResult<Option<usize>> {
+    match store.head(&path).await {
+        Ok(_) => Ok(None),
+        Err(object_store::Error::NotFound { .. }) => {
+            let size = chunk.len();
+            store.put(&path, chunk.into()).await?;
+            Ok(Some(size))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct PyDedupResult {
+    size: usize,
+    chunks: usize,
+    new_chunks: usize,
+    new_bytes: usize,
+}
+
+impl<'py> IntoPyObject<'py> for PyDedupResult {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let mut dict = IndexMap::with_capacity(4);
+        dict.insert("size", self.size);
+        dict.insert("chunks", self.chunks);
+        dict.insert("new_chunks", self.new_chunks);
+        dict.insert("new_bytes", self.new_bytes);
+        dict.into_pyobject(py)
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, file, *, pool_prefix = ".chunks".to_string(), min_chunk_size = 262144, avg_chunk_size = 1048576, max_chunk_size = 4194304, max_concurrency = 12))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn put_dedup(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    file: PutInput,
+    pool_prefix: String,
+    min_chunk_size: usize,
+    avg_chunk_size: usize,
+    max_chunk_size: usize,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<PyDedupResult> {
+    if matches!(file, PutInput::AsyncPush(_)) {
+        return Err(PyValueError::new_err(
+            "Async input not allowed in 'put_dedup'. Use 'put_dedup_async'.",
+        )
+        .into());
+    }
+
+    let chunker = Chunker::try_new(min_chunk_size, avg_chunk_size, max_chunk_size)?;
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(put_dedup_inner(
+            store.into_inner(),
+            path.into(),
+            file,
+            pool_prefix.into(),
+            chunker,
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, file, *, pool_prefix = ".chunks".to_string(), min_chunk_size = 262144, avg_chunk_size = 1048576, max_chunk_size = 4194304, max_concurrency = 12))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn put_dedup_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    file: PutInput,
+    pool_prefix: String,
+    min_chunk_size: usize,
+    avg_chunk_size: usize,
+    max_chunk_size: usize,
+    max_concurrency: usize,
+) -> PyResult<Bound<PyAny>> {
+    let chunker = Chunker::try_new(min_chunk_size, avg_chunk_size, max_chunk_size)?;
+    future_into_py(py, async move {
+        let result = put_dedup_inner(
+            store.into_inner(),
+            path.into(),
+            file,
+            pool_prefix.into(),
+            chunker,
+            max_concurrency,
+        )
+        .await?;
+        Ok(result)
+    })
+}
+
+async fn put_dedup_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    mut file: PutInput,
+    pool_prefix: Path,
+    chunker: Chunker,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<PyDedupResult> {
+    let mut result = PyDedupResult::default();
+    let mut manifest_chunks = vec![];
+    let mut seen = HashSet::new();
+    let mut uploads = FuturesUnordered::new();
+
+    let mut buffer = BytesMut::new();
+    let mut eof = false;
+    loop {
+        while !eof && buffer.len() < chunker.max_size {
+            match file.next_buffer(chunker.max_size).await? {
+                Some(buf) => buffer.extend_from_slice(&buf),
+                None => eof = true,
+            }
+        }
+        if buffer.is_empty() {
+            break;
+        }
+
+        let chunk = buffer.split_to(chunker.cut_point(&buffer)).freeze();
+        let hash = sha256_hex(&chunk);
+        result.size += chunk.len();
+        result.chunks += 1;
+        manifest_chunks.push(ManifestChunk {
+            hash: hash.clone(),
+            size: chunk.len(),
+        });
+
+        // Skip chunks that repeat within this payload
+        if !seen.insert(hash.clone()) {
+            continue;
+        }
+
+        while uploads.len() >= max_concurrency.max(1) {
+            if let Some(upload) = uploads.next().await {
+                record_upload(&mut result, upload?);
+            }
+        }
+        let chunk_path = pool_prefix.child(hash);
+        uploads.push(put_chunk(store.clone(), chunk_path, chunk));
+    }
+
+    while let Some(upload) = uploads.next().await {
+        record_upload(&mut result, upload?);
+    }
+
+    let manifest = Manifest {
+        version: MANIFEST_VERSION,
+        size: result.size,
+        pool_prefix: pool_prefix.to_string(),
+        chunks: manifest_chunks,
+    };
+    let manifest = serde_json::to_vec(&manifest)
+        .map_err(|err| PyValueError::new_err(format!("Could not serialize manifest: {err}")))?;
+    store.put(&path, Bytes::from(manifest).into()).await?;
+
+    Ok(result)
+}
+
+fn record_upload(result: &mut PyDedupResult, uploaded: Option<usize>) {
+    if let Some(size) = uploaded {
+        result.new_chunks += 1;
+        result.new_bytes += size;
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, max_concurrency = 12))]
+pub(crate) fn get_dedup(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<PyBytes> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let out = runtime.block_on(get_dedup_inner(
+            store.into_inner(),
+            path.into(),
+            max_concurrency,
+        ))?;
+        Ok::<_, PyObjectStoreError>(PyBytes::new(out))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, max_concurrency = 12))]
+pub(crate) fn get_dedup_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    max_concurrency: usize,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let out = get_dedup_inner(store.into_inner(), path.into(), max_concurrency).await?;
+        Ok(PyBytes::new(out))
+    })
+}
+
+async fn get_dedup_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<Bytes> {
+    let manifest = store.get(&path).await?.bytes().await?;
+    let manifest: Manifest = serde_json::from_slice(&manifest)
+        .map_err(|err| PyValueError::new_err(format!("Invalid dedup manifest at {path}: {err}")))?;
+    if manifest.version != MANIFEST_VERSION {
+        return Err(PyValueError::new_err(format!(
+            "Unsupported dedup manifest version {} at {path}",
+            manifest.version
+        ))
+        .into());
+    }
+
+    let pool_prefix = Path::from(manifest.pool_prefix);
+    let chunks = futures::stream::iter(manifest.chunks.iter().map(|chunk| {
+        let store = store.clone();
+        let chunk_path = pool_prefix.child(chunk.hash.as_str());
+        async move {
+            let buf = store.get(&chunk_path).await?.bytes().await?;
+            Ok::<_, PyObjectStoreError>((chunk_path, buf))
+        }
+    }))
+    .buffered(max_concurrency.max(1))
+    .try_collect::<Vec<_>>()
+    .await?;
+
+    let mut out = BytesMut::with_capacity(manifest.size);
+    for (expected, (chunk_path, buf)) in manifest.chunks.iter().zip(chunks) {
+        if buf.len() != expected.size || sha256_hex(&buf) != expected.hash {
+            return Err(PyValueError::new_err(format!(
+                "Chunk {chunk_path} does not match the manifest at {path}"
+            ))
+            .into());
+        }
+        out.extend_from_slice(&buf);
+    }
+    Ok(out.freeze())
+}
@@ -1,55 +1,284 @@
+use std::sync::Arc;
+
+use chrono::TimeDelta;
 use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore};
 use pyo3::prelude::*;
-use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use pyo3::types::PyList;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult, PyTrashStore};
 
 use crate::path::PyPaths;
-use crate::runtime::get_runtime;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The outcome of deleting each path passed to `delete`, in the order of the paths.
+///
+/// The paths of a bulk delete request that failed as a whole share its error.
+#[derive(Default)]
+pub(crate) struct PyDeleteResults {
+    errors: Vec<object_store::Error>,
+    /// The index in `errors` of the error deleting each path failed with
+    outcomes: Vec<Option<usize>>,
+}
+
+impl PyDeleteResults {
+    /// Record the outcome of deleting `count` paths.
+    fn push(&mut self, result: object_store::Result<()>, count: usize, ignore_not_found: bool) {
+        let outcome = match result {
+            Ok(()) => None,
+            Err(object_store::Error::NotFound { .. }) if ignore_not_found => None,
+            Err(err) => {
+                self.errors.push(err);
+                Some(self.errors.len() - 1)
+            }
+        };
+        self.outcomes.extend(std::iter::repeat(outcome).take(count));
+    }
+
+    /// Record the outcomes of a later batch of paths.
+    fn append(&mut self, batch: Self) {
+        let offset = self.errors.len();
+        self.errors.extend(batch.errors);
+        self.outcomes.extend(
+            batch
+                .outcomes
+                .into_iter()
+                .map(|outcome| outcome.map(|index| index + offset)),
+        );
+    }
+
+    /// Fail with the error deleting the first path that failed, if any.
+    fn into_result(self) -> object_store::Result<()> {
+        match self.errors.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for PyDeleteResults {
+    type Target = PyList;
+    type Output = Bound<'py, PyList>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        // Failed deletes are returned as their exception, rather than raised
+        let errors = self
+            .errors
+            .into_iter()
+            .map(|err| PyErr::from(PyObjectStoreError::from(err)).into_value(py))
+            .collect::<Vec<_>>();
+        let results = self.outcomes.into_iter().map(|outcome| match outcome {
+            Some(index) => errors[index].clone_ref(py).into_bound(py).into_any(),
+            None => py.None().into_bound(py),
+        });
+        PyList::new(py, results)
+    }
+}
+
+/// Delete `paths` with a single call to `delete_stream`, recording the outcome of each.
+async fn delete_batch_results(
+    store: &Arc<dyn ObjectStore>,
+    paths: Vec<Path>,
+    ignore_not_found: bool,
+) -> PyDeleteResults {
+    let count = paths.len();
+    let results = store
+        .delete_stream(futures::stream::iter(paths.into_iter().map(Ok)).boxed())
+        .map_ok(|_| ())
+        .collect::<Vec<_>>()
+        .await;
+    let mut batch = PyDeleteResults::default();
+    if results.len() == count {
+        // Results are yielded in the order of the paths
+        for result in results {
+            batch.push(result, 1, ignore_not_found);
+        }
+    } else {
+        // S3 yields a single error for the paths of a bulk request that failed as a whole
+        let result = results.into_iter().find(Result::is_err).unwrap_or(Ok(()));
+        batch.push(result, count, ignore_not_found);
+    }
+    batch
+}
+
+async fn delete_inner(
+    store: Arc<dyn ObjectStore>,
+    paths: PyPaths,
+    ignore_not_found: bool,
+    return_results: bool,
+) -> PyObjectStoreResult<Option<PyDeleteResults>> {
+    let results = match paths {
+        PyPaths::One(path) => {
+            let mut results = PyDeleteResults::default();
+            results.push(store.delete(&path).await, 1, ignore_not_found);
+            results
+        }
+        PyPaths::Many(paths) => {
+            // Batched as S3 deletes them, so that a failed request only covers its batch
+            let batches = paths
+                .chunks(DELETE_BATCH_SIZE)
+                .map(|batch| delete_batch_results(&store, batch.to_vec(), ignore_not_found))
+                .collect::<Vec<_>>();
+            let mut results = PyDeleteResults::default();
+            let mut batches = futures::stream::iter(batches).buffered(DELETE_CONCURRENCY);
+            while let Some(batch) = batches.next().await {
+                results.append(batch);
+            }
+            results
+        }
+    };
+    if return_results {
+        Ok(Some(results))
+    } else {
+        results.into_result()?;
+        Ok(None)
+    }
+}
 
 #[pyfunction]
-pub(crate) fn delete(py: Python, store: PyObjectStore, paths: PyPaths) -> PyObjectStoreResult<()> {
+#[pyo3(signature = (store, paths, *, ignore_not_found = false, return_results = false))]
+pub(crate) fn delete(
+    py: Python,
+    store: PyObjectStore,
+    paths: PyPaths,
+    ignore_not_found: bool,
+    return_results: bool,
+) -> PyObjectStoreResult<Option<PyDeleteResults>> {
     let runtime = get_runtime(py)?;
-    let store = store.into_inner();
     py.allow_threads(|| {
-        match paths {
-            PyPaths::One(path) => {
-                runtime.block_on(store.delete(&path))?;
-            }
-            PyPaths::Many(paths) => {
-                // TODO: add option to allow some errors here?
-                let stream =
-                    store.delete_stream(futures::stream::iter(paths.into_iter().map(Ok)).boxed());
-                runtime.block_on(stream.try_collect::<Vec<_>>())?;
-            }
-        };
-        Ok::<_, PyObjectStoreError>(())
+        runtime.block_on(delete_inner(
+            store.into_inner(),
+            paths,
+            ignore_not_found,
+            return_results,
+        ))
     })
 }
 
 #[pyfunction]
+#[pyo3(signature = (store, paths, *, ignore_not_found = false, return_results = false))]
 pub(crate) fn delete_async(
     py: Python,
     store: PyObjectStore,
     paths: PyPaths,
+    ignore_not_found: bool,
+    return_results: bool,
 ) -> PyResult<Bound<PyAny>> {
-    let store = store.into_inner();
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        match paths {
-            PyPaths::One(path) => {
-                store
-                    .delete(&path)
-                    .await
-                    .map_err(PyObjectStoreError::ObjectStoreError)?;
-            }
-            PyPaths::Many(paths) => {
-                // TODO: add option to allow some errors here?
-                let stream =
-                    store.delete_stream(futures::stream::iter(paths.into_iter().map(Ok)).boxed());
-                stream
-                    .try_collect::<Vec<_>>()
-                    .await
-                    .map_err(PyObjectStoreError::ObjectStoreError)?;
-            }
+    future_into_py(py, async move {
+        Ok(delete_inner(store.into_inner(), paths, ignore_not_found, return_results).await?)
+    })
+}
+
+/// The number of objects deleted by each batch of `delete` and `delete_prefix`, which is the
+/// most that S3 deletes with a single request.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+/// How many batches of `delete` are deleted at once, as S3 does within `delete_stream`.
+const DELETE_CONCURRENCY: usize = 20;
+
+async fn delete_batch(
+    store: &Arc<dyn ObjectStore>,
+    batch: Vec<object_store::Result<ObjectMeta>>,
+    ignore_not_found: bool,
+) -> PyObjectStoreResult<usize> {
+    let locations = futures::stream::iter(batch)
+        .map_ok(|meta| meta.location)
+        .boxed();
+    let mut results = store.delete_stream(locations);
+    let mut deleted = 0;
+    while let Some(result) = results.next().await {
+        match result {
+            Ok(_) => deleted += 1,
+            // e.g. objects that were deleted by someone else since they were listed
+            Err(object_store::Error::NotFound { .. }) if ignore_not_found => {}
+            Err(err) => return Err(err.into()),
         }
-        Ok(())
+    }
+    Ok(deleted)
+}
+
+async fn delete_prefix_inner(
+    store: Arc<dyn ObjectStore>,
+    prefix: Path,
+    max_concurrency: usize,
+    ignore_not_found: bool,
+) -> PyObjectStoreResult<usize> {
+    store
+        .list(Some(&prefix))
+        .chunks(DELETE_BATCH_SIZE)
+        .map(|batch| delete_batch(&store, batch, ignore_not_found))
+        .buffer_unordered(max_concurrency.max(1))
+        .try_fold(0, |total, deleted| async move { Ok(total + deleted) })
+        .await
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, *, max_concurrency = 4, ignore_not_found = false))]
+pub(crate) fn delete_prefix(
+    py: Python,
+    store: PyObjectStore,
+    prefix: String,
+    max_concurrency: usize,
+    ignore_not_found: bool,
+) -> PyObjectStoreResult<usize> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(delete_prefix_inner(
+            store.into_inner(),
+            prefix.into(),
+            max_concurrency,
+            ignore_not_found,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, *, max_concurrency = 4, ignore_not_found = false))]
+pub(crate) fn delete_prefix_async(
+    py: Python,
+    store: PyObjectStore,
+    prefix: String,
+    max_concurrency: usize,
+    ignore_not_found: bool,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        Ok(delete_prefix_inner(
+            store.into_inner(),
+            prefix.into(),
+            max_concurrency,
+            ignore_not_found,
+        )
+        .await?)
+    })
+}
+
+#[pyfunction]
+pub(crate) fn purge_trash(
+    py: Python,
+    store: Bound<PyTrashStore>,
+    older_than: TimeDelta,
+) -> PyObjectStoreResult<usize> {
+    let runtime = get_runtime(py)?;
+    let store = store.get().as_ref().clone();
+    py.allow_threads(|| {
+        let purged = runtime.block_on(store.purge_trash(older_than))?;
+        Ok::<_, PyObjectStoreError>(purged)
+    })
+}
+
+#[pyfunction]
+pub(crate) fn purge_trash_async(
+    py: Python,
+    store: Bound<PyTrashStore>,
+    older_than: TimeDelta,
+) -> PyResult<Bound<PyAny>> {
+    let store = store.get().as_ref().clone();
+    future_into_py(py, async move {
+        let purged = store
+            .purge_trash(older_than)
+            .await
+            .map_err(PyObjectStoreError::ObjectStoreError)?;
+        Ok(purged)
     })
 }
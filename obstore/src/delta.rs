@@ -0,0 +1,466 @@
+//! Uploading a new version of an object by sending only the blocks of a local file that changed,
+//! in the manner of rsync.
+//!
+//! The existing object is split into blocks, whose signatures are computed from ranged reads.
+//! The local file is then scanned with a rolling checksum, so blocks are found at any offset,
+//! even if data was inserted before them. Blocks found in the local file are copied into the new
+//! object server-side with `UploadPartCopy`, and only the bytes in between are uploaded.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use indexmap::IndexMap;
+use object_store::multipart::MultipartStore;
+use object_store::path::Path;
+use object_store::{GetOptions, GetRange, ObjectMeta, ObjectStore, PutResult};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use obstore_core::copy::MAX_PARTS;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_object_store::{PyObjectStoreError, PyObjectStoreResult, PyS3Store, RegionAwareS3};
+use xxhash_rust::xxh3::xxh3_128;
+
+use crate::copy::{copy_part, split_range, MAX_SINGLE_COPY, MIN_PART_SIZE};
+use crate::journal::Uploads;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The modulus of the sums of the rolling checksum, as in rsync.
+const MODULUS: u32 = 1 << 16;
+
+/// How much of the local file is read at once while scanning it.
+const READ_SIZE: usize = 1024 * 1024;
+
+/// The rolling checksum of a window of bytes, which can be moved one byte at a time.
+#[derive(Debug, Clone, Copy)]
+struct Rolling {
+    a: u32,
+    b: u32,
+    len: u32,
+}
+
+impl Rolling {
+    fn new(window: &[u8]) -> Self {
+        let len = window.len() as u32;
+        let (mut a, mut b) = (0u32, 0u32);
+        for (i, &byte) in window.iter().enumerate() {
+            a = a.wrapping_add(byte as u32);
+            b = b.wrapping_add(len.wrapping_sub(i as u32).wrapping_mul(byte as u32));
+        }
+        Self {
+            a: a % MODULUS,
+            b: b % MODULUS,
+            len,
+        }
+    }
+
+    /// Move the window forward by one byte, dropping `out` and appending `next`.
+    fn roll(&mut self, out: u8, next: u8) {
+        self.a = (self.a + MODULUS - out as u32 + next as u32) % MODULUS;
+        let dropped = (self.len.wrapping_mul(out as u32)) % MODULUS;
+        self.b = (self.b + MODULUS - dropped + self.a) % MODULUS;
+    }
+
+    fn value(&self) -> u32 {
+        self.a | (self.b << 16)
+    }
+}
+
+/// The checksums of the full blocks of the existing object.
+struct Signature {
+    block_size: usize,
+    /// The indices of the blocks with each rolling checksum
+    weak: HashMap<u32, Vec<usize>>,
+    strong: Vec<u128>,
+}
+
+impl Signature {
+    /// The index of the block whose contents are `window`, if any.
+    fn find(&self, rolling: &Rolling, window: &[u8]) -> Option<usize> {
+        let candidates = self.weak.get(&rolling.value())?;
+        let strong = xxh3_128(window);
+        candidates
+            .iter()
+            .copied()
+            .find(|&index| self.strong[index] == strong)
+    }
+}
+
+/// Read the blocks of `meta` and compute their checksums, with up to `max_concurrency` reads in
+/// flight at once.
+///
+/// A short last block is left out, as a copied part must be a full block to be followed by
+/// other parts.
+async fn signature(
+    store: &dyn ObjectStore,
+    meta: &ObjectMeta,
+    block_size: usize,
+    max_concurrency: usize,
+) -> object_store::Result<Signature> {
+    let blocks = meta.size / block_size;
+    let reads = (0..blocks).map(|index| async move {
+        let options = GetOptions {
+            // Don't mix the blocks of two versions if the object is overwritten meanwhile
+            if_match: meta.e_tag.clone(),
+            range: Some(GetRange::Bounded(
+                index * block_size..(index + 1) * block_size,
+            )),
+            ..Default::default()
+        };
+        let bytes = store
+            .get_opts(&meta.location, options)
+            .await?
+            .bytes()
+            .await?;
+        Ok::<_, object_store::Error>((index, Rolling::new(&bytes).value(), xxh3_128(&bytes)))
+    });
+    let mut checksums = buffer_unordered(reads, Concurrency::Fixed(max_concurrency))
+        .try_collect::<Vec<_>>()
+        .await?;
+    checksums.sort_unstable_by_key(|(index, _, _)| *index);
+
+    let mut weak: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (index, rolling, _) in &checksums {
+        weak.entry(*rolling).or_default().push(*index);
+    }
+    Ok(Signature {
+        block_size,
+        weak,
+        strong: checksums.into_iter().map(|(_, _, strong)| strong).collect(),
+    })
+}
+
+/// A run of the new object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Bytes of the local file that aren't in the existing object, which are uploaded.
+    Upload(Range<usize>),
+    /// A block of the existing object, at `local` in the local file, which is copied.
+    Copy {
+        remote: Range<usize>,
+        local: Range<usize>,
+    },
+}
+
+impl Segment {
+    fn local(&self) -> &Range<usize> {
+        match self {
+            Self::Upload(local) | Self::Copy { local, .. } => local,
+        }
+    }
+}
+
+/// The bytes of a file being scanned, from the start of the scan window onwards.
+struct Window {
+    file: File,
+    buf: Vec<u8>,
+    /// The offset in the file of the start of `buf`
+    base: usize,
+    eof: bool,
+}
+
+impl Window {
+    /// Read until `buf` holds the file up to `end`, or all of it if it's shorter.
+    fn fill(&mut self, end: usize) -> io::Result<()> {
+        while !self.eof && self.base + self.buf.len() < end {
+            let len = self.buf.len();
+            self.buf.resize(len + READ_SIZE, 0);
+            let read = self.file.read(&mut self.buf[len..])?;
+            self.buf.truncate(len + read);
+            self.eof = read == 0;
+        }
+        Ok(())
+    }
+
+    fn end(&self) -> usize {
+        self.base + self.buf.len()
+    }
+
+    fn get(&self, range: Range<usize>) -> &[u8] {
+        &self.buf[range.start - self.base..range.end - self.base]
+    }
+
+    fn byte(&self, offset: usize) -> u8 {
+        self.buf[offset - self.base]
+    }
+
+    /// Drop the bytes before `offset`, once enough of them have accumulated.
+    fn discard(&mut self, offset: usize) {
+        if offset - self.base >= READ_SIZE {
+            self.buf.drain(..offset - self.base);
+            self.base = offset;
+        }
+    }
+}
+
+/// Scan `file` for the blocks of `signature`, returning the segments the new object is made of.
+fn scan(file: File, signature: &Signature) -> io::Result<Vec<Segment>> {
+    let len = file.metadata()?.len() as usize;
+    let block_size = signature.block_size;
+    let mut segments = vec![];
+    // The start of the bytes not yet matched to a block
+    let mut unmatched = 0;
+    if !signature.strong.is_empty() {
+        let mut window = Window {
+            file,
+            buf: vec![],
+            base: 0,
+            eof: false,
+        };
+        let mut offset = 0;
+        let mut rolling = None;
+        while offset + block_size <= len {
+            window.fill(offset + block_size + 1)?;
+            if window.end() < offset + block_size {
+                // The file was truncated meanwhile, which reading it to upload will report
+                break;
+            }
+            let block = offset..offset + block_size;
+            let checksum = rolling.get_or_insert_with(|| Rolling::new(window.get(block.clone())));
+            if let Some(index) = signature.find(checksum, window.get(block.clone())) {
+                if unmatched < offset {
+                    segments.push(Segment::Upload(unmatched..offset));
+                }
+                segments.push(Segment::Copy {
+                    remote: index * block_size..(index + 1) * block_size,
+                    local: block.clone(),
+                });
+                offset = block.end;
+                unmatched = offset;
+                rolling = None;
+            } else if block.end < window.end() {
+                checksum.roll(window.byte(offset), window.byte(block.end));
+                offset += 1;
+            } else {
+                break;
+            }
+            window.discard(offset);
+        }
+    }
+    if unmatched < len {
+        segments.push(Segment::Upload(unmatched..len));
+    }
+    Ok(segments)
+}
+
+/// Turn `segments` into the parts of a multipart upload, where every part but the last must be
+/// at least 5 MiB and at most 5 GiB.
+///
+/// Uploads shorter than 5 MiB take in the blocks after them, which are uploaded from the local
+/// file instead of copied, until they're long enough. Longer uploads are split into parts of up
+/// to twice `part_size`.
+fn into_parts(segments: Vec<Segment>, part_size: usize) -> Vec<Segment> {
+    let mut merged: Vec<Segment> = vec![];
+    for segment in segments {
+        match (merged.last_mut(), &segment) {
+            // Extend an upload that's too short, or that this upload continues
+            (Some(Segment::Upload(last)), Segment::Upload(next)) => last.end = next.end,
+            (Some(Segment::Upload(last)), Segment::Copy { local, .. })
+                if last.len() < MIN_PART_SIZE =>
+            {
+                last.end = local.end
+            }
+            _ => merged.push(segment),
+        }
+    }
+    merged
+        .into_iter()
+        .flat_map(|segment| match segment {
+            Segment::Upload(range) => split_range(range, part_size)
+                .into_iter()
+                .map(Segment::Upload)
+                .collect(),
+            copy => vec![copy],
+        })
+        .collect()
+}
+
+/// Read `range` of the file at `path`.
+fn read_range(path: &std::path::Path, range: &Range<usize>) -> io::Result<Bytes> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(range.start as u64))?;
+    let mut buf = vec![0; range.len()];
+    file.read_exact(&mut buf)?;
+    Ok(buf.into())
+}
+
+/// The outcome of [`put_delta`].
+pub(crate) struct DeltaResult {
+    result: PutResult,
+    bytes_uploaded: usize,
+    bytes_copied: usize,
+}
+
+impl<'py> IntoPyObject<'py> for DeltaResult {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let mut dict = IndexMap::with_capacity(4);
+        dict.insert("e_tag", self.result.e_tag.into_pyobject(py)?.into_any());
+        dict.insert("version", self.result.version.into_pyobject(py)?.into_any());
+        dict.insert(
+            "bytes_uploaded",
+            self.bytes_uploaded.into_pyobject(py)?.into_any(),
+        );
+        dict.insert(
+            "bytes_copied",
+            self.bytes_copied.into_pyobject(py)?.into_any(),
+        );
+        dict.into_pyobject(py)
+    }
+}
+
+fn check_block_size(block_size: usize) -> PyResult<()> {
+    if !(MIN_PART_SIZE..=MAX_SINGLE_COPY).contains(&block_size) {
+        return Err(PyValueError::new_err(
+            "block_size must be between 5 MiB and 5 GiB",
+        ));
+    }
+    Ok(())
+}
+
+async fn put_delta_inner(
+    s3: Arc<RegionAwareS3>,
+    path: Path,
+    local_file: PathBuf,
+    block_size: usize,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<DeltaResult> {
+    let current = s3.current();
+    let meta = match current.head(&path).await {
+        Ok(meta) => Some(meta),
+        // Everything is uploaded for a new object
+        Err(object_store::Error::NotFound { .. }) => None,
+        Err(err) => return Err(err.into()),
+    };
+    let signature = match &meta {
+        Some(meta) if !s3.quirks().lacks_upload_part_copy => {
+            signature(current.as_ref(), meta, block_size, max_concurrency).await?
+        }
+        _ => Signature {
+            block_size,
+            weak: HashMap::new(),
+            strong: vec![],
+        },
+    };
+
+    let file = local_file.clone();
+    let segments = tokio::task::spawn_blocking(move || scan(File::open(file)?, &signature))
+        .await
+        .map_err(io::Error::other)??;
+    // Split into parts short enough to upload
+    let parts = into_parts(segments, block_size.min(MAX_SINGLE_COPY / 2));
+    if parts.len() > MAX_PARTS {
+        return Err(PyValueError::new_err(format!(
+            "The delta needs {} parts, more than the {MAX_PARTS} a multipart upload can have. \
+             Use a larger block_size.",
+            parts.len()
+        ))
+        .into());
+    }
+    let (mut bytes_uploaded, mut bytes_copied) = (0, 0);
+    for part in &parts {
+        match part {
+            Segment::Upload(range) => bytes_uploaded += range.len(),
+            Segment::Copy { remote, .. } => bytes_copied += remote.len(),
+        }
+    }
+
+    let upload = Uploads::s3(&s3).create(&path).await?;
+    let upload_id = &upload.upload_id;
+    let write_parts = async {
+        let parts = parts.iter().enumerate().map(|(index, part)| {
+            let (s3, current, meta, path, local_file) = (&s3, &current, &meta, &path, &local_file);
+            async move {
+                let part = match (part, meta) {
+                    (Segment::Copy { remote, .. }, Some(meta)) => {
+                        copy_part(s3, meta, path, upload_id, index, remote.clone()).await?
+                    }
+                    (part, _) => {
+                        let (file, range) = (local_file.clone(), part.local().clone());
+                        let bytes = tokio::task::spawn_blocking(move || read_range(&file, &range))
+                            .await
+                            .map_err(io::Error::other)??;
+                        current
+                            .put_part(path, upload_id, index, bytes.into())
+                            .await?
+                    }
+                };
+                Ok::<_, PyObjectStoreError>((index, part))
+            }
+        });
+        let mut parts = buffer_unordered(parts, Concurrency::Fixed(max_concurrency))
+            .try_collect::<Vec<_>>()
+            .await?;
+        parts.sort_unstable_by_key(|(index, _)| *index);
+        let parts = parts.into_iter().map(|(_, part)| part).collect();
+        Ok::<_, PyObjectStoreError>(current.complete_multipart(&path, upload_id, parts).await?)
+    };
+    match write_parts.await {
+        Ok(result) => {
+            upload.end().await;
+            Ok(DeltaResult {
+                result,
+                bytes_uploaded,
+                bytes_copied,
+            })
+        }
+        Err(err) => {
+            if current.abort_multipart(&path, upload_id).await.is_ok() {
+                upload.end().await;
+            }
+            Err(err)
+        }
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, local_file, *, block_size = 8388608, max_concurrency = 12))]
+pub(crate) fn put_delta(
+    py: Python,
+    store: &Bound<PyS3Store>,
+    path: String,
+    local_file: PathBuf,
+    block_size: usize,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<DeltaResult> {
+    check_block_size(block_size)?;
+    let store = store.borrow().region_aware().clone();
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(put_delta_inner(
+            store,
+            path.into(),
+            local_file,
+            block_size,
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, local_file, *, block_size = 8388608, max_concurrency = 12))]
+pub(crate) fn put_delta_async<'py>(
+    py: Python<'py>,
+    store: &Bound<PyS3Store>,
+    path: String,
+    local_file: PathBuf,
+    block_size: usize,
+    max_concurrency: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    check_block_size(block_size)?;
+    let store = store.borrow().region_aware().clone();
+    future_into_py(py, async move {
+        let result =
+            put_delta_inner(store, path.into(), local_file, block_size, max_concurrency).await?;
+        Ok(result)
+    })
+}
@@ -0,0 +1,140 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+
+use crate::runtime::{future_into_py, get_runtime};
+
+/// Re-chunks a byte stream into fixed-size blocks.
+struct BlockReader {
+    stream: BoxStream<'static, object_store::Result<Bytes>>,
+    buffer: BytesMut,
+    block_size: usize,
+}
+
+impl BlockReader {
+    async fn try_new(
+        store: &Arc<dyn ObjectStore>,
+        path: &Path,
+        block_size: usize,
+    ) -> PyObjectStoreResult<Self> {
+        let stream = store.get(path).await?.into_stream();
+        Ok(Self {
+            stream,
+            buffer: BytesMut::new(),
+            block_size,
+        })
+    }
+
+    /// The next block, which is only shorter than `block_size` at the end of the object.
+    async fn next_block(&mut self) -> PyObjectStoreResult<Bytes> {
+        while self.buffer.len() < self.block_size {
+            match self.stream.next().await {
+                Some(buf) => self.buffer.extend_from_slice(&buf?),
+                None => break,
+            }
+        }
+        let len = self.buffer.len().min(self.block_size);
+        Ok(self.buffer.split_to(len).freeze())
+    }
+}
+
+/// Push `range` onto `ranges`, merging it with the last range if they are adjacent.
+fn push_range(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+async fn diff_objects_inner(
+    store_a: Arc<dyn ObjectStore>,
+    path_a: Path,
+    store_b: Arc<dyn ObjectStore>,
+    path_b: Path,
+    block_size: usize,
+) -> PyObjectStoreResult<Vec<(usize, usize)>> {
+    let (mut reader_a, mut reader_b) = futures::try_join!(
+        BlockReader::try_new(&store_a, &path_a, block_size),
+        BlockReader::try_new(&store_b, &path_b, block_size),
+    )?;
+
+    let mut ranges = vec![];
+    let mut offset = 0;
+    loop {
+        let (block_a, block_b) = futures::try_join!(reader_a.next_block(), reader_b.next_block())?;
+        if block_a.is_empty() && block_b.is_empty() {
+            break;
+        }
+
+        let len = block_a.len().max(block_b.len());
+        if block_a != block_b {
+            push_range(&mut ranges, offset..offset + len);
+        }
+        offset += len;
+    }
+
+    Ok(ranges
+        .into_iter()
+        .map(|range| (range.start, range.end))
+        .collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (store_a, path_a, store_b, path_b, *, block_size = 1048576))]
+pub(crate) fn diff_objects(
+    py: Python,
+    store_a: PyObjectStore,
+    path_a: String,
+    store_b: PyObjectStore,
+    path_b: String,
+    block_size: usize,
+) -> PyObjectStoreResult<Vec<(usize, usize)>> {
+    if block_size == 0 {
+        return Err(PyValueError::new_err("block_size must be greater than 0").into());
+    }
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let ranges = runtime.block_on(diff_objects_inner(
+            store_a.into_inner(),
+            path_a.into(),
+            store_b.into_inner(),
+            path_b.into(),
+            block_size,
+        ))?;
+        Ok::<_, PyObjectStoreError>(ranges)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store_a, path_a, store_b, path_b, *, block_size = 1048576))]
+pub(crate) fn diff_objects_async(
+    py: Python,
+    store_a: PyObjectStore,
+    path_a: String,
+    store_b: PyObjectStore,
+    path_b: String,
+    block_size: usize,
+) -> PyResult<Bound<PyAny>> {
+    if block_size == 0 {
+        return Err(PyValueError::new_err("block_size must be greater than 0"));
+    }
+    future_into_py(py, async move {
+        let ranges = diff_objects_inner(
+            store_a.into_inner(),
+            path_a.into(),
+            store_b.into_inner(),
+            path_b.into(),
+            block_size,
+        )
+        .await?;
+        Ok(ranges)
+    })
+}
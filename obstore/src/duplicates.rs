@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::stream::TryStreamExt;
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::pybacked::PyBackedStr;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use ring::digest::{Context, SHA256};
+
+use crate::list::PyObjectMeta;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// How objects are compared by `find_duplicates`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DuplicateKey {
+    ETag,
+    Sha256,
+}
+
+impl<'py> FromPyObject<'py> for DuplicateKey {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let s = ob.extract::<PyBackedStr>()?;
+        match s.as_ref() {
+            "etag" => Ok(Self::ETag),
+            "sha256" => Ok(Self::Sha256),
+            _ => Err(PyValueError::new_err(format!(
+                "Unexpected input for by: {}. Expected \"etag\" or \"sha256\".",
+                &*s
+            ))),
+        }
+    }
+}
+
+/// Keep the groups with more than one object, sorted by path.
+fn duplicate_sets<K>(groups: HashMap<K, Vec<ObjectMeta>>) -> Vec<Vec<ObjectMeta>> {
+    let mut sets = groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+            group
+        })
+        .collect::<Vec<_>>();
+    sets.sort_unstable_by(|a, b| a[0].location.cmp(&b[0].location));
+    sets
+}
+
+/// Compute the hex-encoded SHA-256 digest of an object, streaming its content.
+async fn sha256(store: &Arc<dyn ObjectStore>, location: &Path) -> object_store::Result<String> {
+    let mut stream = store.get(location).await?.into_stream();
+    let mut context = Context::new(&SHA256);
+    while let Some(chunk) = stream.try_next().await? {
+        context.update(&chunk);
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+async fn find_duplicates_inner(
+    store: Arc<dyn ObjectStore>,
+    prefix: Option<Path>,
+    by: DuplicateKey,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<Vec<Vec<PyObjectMeta>>> {
+    let metas = store.list(prefix.as_ref()).try_collect::<Vec<_>>().await?;
+
+    let sets = match by {
+        DuplicateKey::ETag => {
+            let mut groups = HashMap::new();
+            for meta in metas {
+                if let Some(e_tag) = meta.e_tag.clone() {
+                    groups
+                        .entry((e_tag, meta.size))
+                        .or_insert_with(Vec::new)
+                        .push(meta);
+                }
+            }
+            duplicate_sets(groups)
+        }
+        DuplicateKey::Sha256 => {
+            // Objects of different sizes can't be identical, so only read objects whose size
+            // matches that of another object
+            let mut by_size = HashMap::<usize, Vec<ObjectMeta>>::new();
+            for meta in metas {
+                by_size.entry(meta.size).or_default().push(meta);
+            }
+            let candidates = by_size
+                .into_values()
+                .filter(|group| group.len() > 1)
+                .flatten();
+            let hashed = candidates.map(|meta| {
+                let store = store.clone();
+                async move {
+                    let hash = sha256(&store, &meta.location).await?;
+                    Ok::<_, object_store::Error>((hash, meta))
+                }
+            });
+            let hashed = buffer_unordered(hashed, max_concurrency)
+                .try_collect::<Vec<_>>()
+                .await?;
+            let mut groups = HashMap::<String, Vec<ObjectMeta>>::new();
+            for (hash, meta) in hashed {
+                groups.entry(hash).or_default().push(meta);
+            }
+            duplicate_sets(groups)
+        }
+    };
+
+    Ok(sets
+        .into_iter()
+        .map(|set| set.into_iter().map(PyObjectMeta::new).collect())
+        .collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix = None, *, by = DuplicateKey::ETag, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn find_duplicates(
+    py: Python,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    by: DuplicateKey,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<Vec<Vec<PyObjectMeta>>> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let out = runtime.block_on(find_duplicates_inner(
+            store.into_inner(),
+            prefix.map(Path::from),
+            by,
+            max_concurrency,
+        ))?;
+        Ok::<_, PyObjectStoreError>(out)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix = None, *, by = DuplicateKey::ETag, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn find_duplicates_async(
+    py: Python,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    by: DuplicateKey,
+    max_concurrency: Concurrency,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let out = find_duplicates_inner(
+            store.into_inner(),
+            prefix.map(Path::from),
+            by,
+            max_concurrency,
+        )
+        .await?;
+        Ok(out)
+    })
+}
@@ -0,0 +1,745 @@
+//! The spans and events reported through `tracing`, surfaced in Python.
+//!
+//! obstore traces each request made through a store with a span, and object_store reports the
+//! retries it makes within each request with events rather than to the caller. A global
+//! subscriber is installed the first time a retry hook is set, tracing is enabled, or an operation
+//! is called with `return_stats`. It passes retries to the hook and counts them in the stats of
+//! the operation retried, and, while tracing is enabled, forwards spans and events to Python
+//! logging or to an OpenTelemetry tracer.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use crate::stats;
+
+/// The module object_store reports its retries from.
+const RETRY_TARGET: &str = "object_store::client::retry";
+
+/// The targets whose spans and events are forwarded to Python.
+const TARGETS: [&str; 2] = ["obstore", "object_store"];
+
+/// The hook called on each retry, if one has been set.
+static RETRY_HOOK: RwLock<Option<PyObject>> = RwLock::new(None);
+
+/// Set once an operation has been called with `return_stats`, after which retries are counted.
+static COUNT_RETRIES: AtomicBool = AtomicBool::new(false);
+
+/// Where spans and events are forwarded, if tracing is enabled.
+static TRACING: RwLock<Option<Tracing>> = RwLock::new(None);
+
+/// The ID of the next span, as IDs must not be zero.
+static NEXT_SPAN_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// The spans entered on this thread, innermost last.
+    static ENTERED: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A retry reported by object_store.
+#[derive(Debug)]
+struct RetryEvent {
+    attempt: Option<usize>,
+    max_retries: Option<usize>,
+    delay: Option<Duration>,
+    message: String,
+}
+
+impl RetryEvent {
+    /// Parse the message of a retry event, such as "Encountered server error, backing off for
+    /// 0.1 seconds, retry 1 of 10", or `None` if it isn't one.
+    fn parse(message: String) -> Option<Self> {
+        let (_, backoff) = message.split_once("backing off for ")?;
+        let delay = backoff
+            .split_once(" seconds")
+            .and_then(|(secs, _)| secs.trim().parse::<f64>().ok())
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+        let (attempt, max_retries) = backoff
+            .split_once("retry ")
+            .and_then(|(_, retry)| {
+                let (attempt, max_retries) = retry.split_once(" of ")?;
+                let max_retries = max_retries.split(|c: char| !c.is_ascii_digit()).next()?;
+                Some((attempt.parse().ok()?, max_retries.parse().ok()?))
+            })
+            .unzip();
+        Some(Self {
+            attempt,
+            max_retries,
+            delay,
+            message,
+        })
+    }
+}
+
+impl<'py> IntoPyObject<'py> for RetryEvent {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("attempt", self.attempt)?;
+        dict.set_item("max_retries", self.max_retries)?;
+        dict.set_item("delay", self.delay)?;
+        dict.set_item("message", self.message)?;
+        Ok(dict)
+    }
+}
+
+/// How spans and events are forwarded, as set with `enable_tracing`.
+struct Tracing {
+    level: LevelFilter,
+    output: Output,
+}
+
+enum Output {
+    /// Records of loggers named after the target of each span and event
+    Logging,
+    /// Spans of an OpenTelemetry tracer, with events added to the span they occur in
+    OpenTelemetry {
+        tracer: PyObject,
+        /// The `opentelemetry.trace` module
+        trace: PyObject,
+    },
+}
+
+/// A value recorded on a span or event.
+#[derive(Debug, Clone)]
+enum FieldValue {
+    Str(String),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Str(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::UInt(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &FieldValue {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(match self {
+            FieldValue::Str(value) => value.as_str().into_pyobject(py)?.into_any(),
+            FieldValue::Int(value) => (*value).into_pyobject(py)?.into_any(),
+            FieldValue::UInt(value) => (*value).into_pyobject(py)?.into_any(),
+            FieldValue::Float(value) => (*value).into_pyobject(py)?.into_any(),
+            FieldValue::Bool(value) => (*value).into_pyobject(py)?.to_owned().into_any(),
+        })
+    }
+}
+
+/// Records the message and other fields of a span or event.
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Vec<(&'static str, FieldValue)>,
+}
+
+impl FieldVisitor {
+    fn push(&mut self, field: &Field, value: FieldValue) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.fields.push((field.name(), value));
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, FieldValue::Str(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, FieldValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.push(field, FieldValue::UInt(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, FieldValue::Float(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, FieldValue::Bool(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.push(field, FieldValue::Str(format!("{value:?}")));
+    }
+}
+
+/// Format `fields` as `key=value` pairs separated by spaces.
+fn format_fields(fields: &[(&'static str, FieldValue)]) -> String {
+    fields
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn fields_dict<'py>(
+    py: Python<'py>,
+    fields: &[(&'static str, FieldValue)],
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (name, value) in fields {
+        dict.set_item(*name, value)?;
+    }
+    Ok(dict)
+}
+
+/// A span that hasn't been closed yet.
+struct SpanData {
+    metadata: &'static Metadata<'static>,
+    fields: Vec<(&'static str, FieldValue)>,
+    parent: Option<u64>,
+    started: Instant,
+    /// The number of handles to the span, which is closed once none are left
+    refs: usize,
+    /// The OpenTelemetry span it's exported as, shared so that it's cloned without the GIL
+    otel: Option<Arc<PyObject>>,
+}
+
+impl SpanData {
+    /// The span formatted as `name{key=value}`.
+    fn format(&self) -> String {
+        if self.fields.is_empty() {
+            self.metadata.name().to_string()
+        } else {
+            format!(
+                "{}{{{}}}",
+                self.metadata.name(),
+                format_fields(&self.fields)
+            )
+        }
+    }
+}
+
+fn spans() -> &'static Mutex<HashMap<u64, SpanData>> {
+    static SPANS: OnceLock<Mutex<HashMap<u64, SpanData>>> = OnceLock::new();
+    SPANS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The innermost span entered on this thread.
+fn entered() -> Option<u64> {
+    ENTERED.with(|entered| entered.borrow().last().copied())
+}
+
+/// The OpenTelemetry span that the span `id` is exported as.
+fn otel_span(id: Option<u64>) -> Option<Arc<PyObject>> {
+    spans().lock().unwrap().get(&id?)?.otel.clone()
+}
+
+/// The spans from the root to `id`, formatted and separated by colons.
+fn span_context(id: Option<u64>) -> String {
+    let spans = spans().lock().unwrap();
+    let mut context = vec![];
+    let mut next = id;
+    while let Some(span) = next.and_then(|id| spans.get(&id)) {
+        context.push(span.format());
+        next = span.parent;
+    }
+    context.reverse();
+    context.join(":")
+}
+
+/// The current time, in nanoseconds since the Unix epoch, as OpenTelemetry expects.
+fn epoch_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
+
+/// The `logging` level of records of `level`, with trace mapped below debug.
+fn logging_level(level: &Level) -> u8 {
+    match *level {
+        Level::TRACE => 5,
+        Level::DEBUG => 10,
+        Level::INFO => 20,
+        Level::WARN => 30,
+        Level::ERROR => 40,
+    }
+}
+
+/// Log `message` to the logger named after the target of `metadata`, with `::` replaced by
+/// dots.
+fn log(py: Python, metadata: &Metadata, message: String, extra: Bound<PyDict>) -> PyResult<()> {
+    let logger = py.import(intern!(py, "logging"))?.call_method1(
+        intern!(py, "getLogger"),
+        (metadata.target().replace("::", "."),),
+    )?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("extra", extra)?;
+    logger.call_method(
+        intern!(py, "log"),
+        (logging_level(metadata.level()), message),
+        Some(&kwargs),
+    )?;
+    Ok(())
+}
+
+/// Whether retries are passed to the hook or counted.
+fn wants_retries() -> bool {
+    COUNT_RETRIES.load(Ordering::Relaxed) || RETRY_HOOK.read().unwrap().is_some()
+}
+
+/// The most verbose level that retries or tracing are wanted at.
+fn max_level() -> LevelFilter {
+    let retries = if wants_retries() {
+        LevelFilter::INFO
+    } else {
+        LevelFilter::OFF
+    };
+    let tracing = TRACING
+        .read()
+        .unwrap()
+        .as_ref()
+        .map_or(LevelFilter::OFF, |tracing| tracing.level);
+    retries.max(tracing)
+}
+
+/// Whether tracing is enabled, at a level including `metadata`.
+fn is_traced(metadata: &Metadata) -> bool {
+    TRACING
+        .read()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|tracing| *metadata.level() <= tracing.level)
+}
+
+/// Whether spans are exported to OpenTelemetry.
+fn exports_otel() -> bool {
+    matches!(
+        TRACING.read().unwrap().as_ref(),
+        Some(Tracing {
+            output: Output::OpenTelemetry { .. },
+            ..
+        })
+    )
+}
+
+fn is_retry(metadata: &Metadata) -> bool {
+    metadata.is_event() && metadata.target().starts_with(RETRY_TARGET)
+}
+
+/// Forwards the spans and events of obstore and object_store to Python.
+struct PySubscriber;
+
+impl PySubscriber {
+    fn call_retry_hook(&self, py: Python, message: Option<&String>) {
+        let Some(retry) = message.cloned().and_then(RetryEvent::parse) else {
+            return;
+        };
+        // Cloned so that the hook can replace itself
+        let Some(hook) = RETRY_HOOK
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|hook| hook.clone_ref(py))
+        else {
+            return;
+        };
+        // Raising from the hook can't fail the request being retried
+        if let Err(err) = hook.call1(py, (retry,)) {
+            err.write_unraisable(py, Some(hook.bind(py)));
+        }
+    }
+
+    /// Start the OpenTelemetry span of a new span, as a child of the span of `parent`.
+    fn start_otel_span(
+        &self,
+        py: Python,
+        metadata: &Metadata,
+        fields: &[(&'static str, FieldValue)],
+        parent: Option<u64>,
+    ) -> PyResult<Option<Arc<PyObject>>> {
+        let (tracer, trace) = match TRACING.read().unwrap().as_ref() {
+            Some(Tracing {
+                output: Output::OpenTelemetry { tracer, trace },
+                ..
+            }) => (tracer.clone_ref(py), trace.clone_ref(py)),
+            _ => return Ok(None),
+        };
+        let kwargs = PyDict::new(py);
+        if let Some(parent) = otel_span(parent) {
+            let context = trace.call_method1(
+                py,
+                intern!(py, "set_span_in_context"),
+                (parent.clone_ref(py),),
+            )?;
+            kwargs.set_item("context", context)?;
+        }
+        kwargs.set_item("attributes", fields_dict(py, fields)?)?;
+        kwargs.set_item("start_time", epoch_nanos())?;
+        let span = tracer.call_method(
+            py,
+            intern!(py, "start_span"),
+            (metadata.name(),),
+            Some(&kwargs),
+        )?;
+        Ok(Some(Arc::new(span)))
+    }
+
+    /// Export a closed span, by ending its OpenTelemetry span or as a log record.
+    fn close(&self, py: Python, id: u64, span: SpanData) -> PyResult<()> {
+        if let Some(otel) = &span.otel {
+            let otel = otel.bind(py);
+            let error = span.fields.iter().find(|(name, _)| *name == "error");
+            if let Some((_, error)) = error {
+                let trace = py.import(intern!(py, "opentelemetry.trace"))?;
+                let code = trace
+                    .getattr(intern!(py, "StatusCode"))?
+                    .getattr(intern!(py, "ERROR"))?;
+                let status = trace
+                    .getattr(intern!(py, "Status"))?
+                    .call1((code, error.to_string()))?;
+                otel.call_method1(intern!(py, "set_status"), (status,))?;
+            }
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("end_time", epoch_nanos())?;
+            otel.call_method(intern!(py, "end"), (), Some(&kwargs))?;
+            return Ok(());
+        }
+        if !is_traced(span.metadata) || exports_otel() {
+            return Ok(());
+        }
+        let duration = span.started.elapsed();
+        // The span has been removed, so its context is that of its parent
+        let context = match span_context(span.parent) {
+            context if context.is_empty() => span.format(),
+            context => format!("{context}:{}", span.format()),
+        };
+        let extra = PyDict::new(py);
+        extra.set_item("duration", duration)?;
+        extra.set_item("fields", fields_dict(py, &span.fields)?)?;
+        extra.set_item("span_id", id)?;
+        log(
+            py,
+            span.metadata,
+            format!("{context}: closed after {duration:?}"),
+            extra,
+        )
+    }
+
+    /// Forward an event, as an event of the OpenTelemetry span it occurred in or as a log
+    /// record.
+    fn forward_event(
+        &self,
+        py: Python,
+        metadata: &Metadata,
+        visitor: FieldVisitor,
+        parent: Option<u64>,
+    ) -> PyResult<()> {
+        let message = visitor
+            .message
+            .unwrap_or_else(|| metadata.name().to_string());
+        if let Some(otel) = otel_span(parent) {
+            let attributes = fields_dict(py, &visitor.fields)?;
+            attributes.set_item("level", metadata.level().as_str())?;
+            otel.call_method1(py, intern!(py, "add_event"), (message, attributes))?;
+            return Ok(());
+        }
+        let mut text = match span_context(parent) {
+            context if context.is_empty() => message,
+            context => format!("{context}: {message}"),
+        };
+        if !visitor.fields.is_empty() {
+            text = format!("{text} {}", format_fields(&visitor.fields));
+        }
+        let extra = PyDict::new(py);
+        extra.set_item("fields", fields_dict(py, &visitor.fields)?)?;
+        extra.set_item("span_id", parent)?;
+        log(py, metadata, text, extra)
+    }
+}
+
+impl Subscriber for PySubscriber {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if TARGETS
+            .iter()
+            .any(|target| metadata.target().starts_with(target))
+        {
+            // Checked each time, as the retry hook and tracing can be set and unset
+            Interest::sometimes()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        (is_retry(metadata) && wants_retries()) || is_traced(metadata)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(max_level())
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = NEXT_SPAN_ID.fetch_add(1, Ordering::Relaxed);
+        let mut visitor = FieldVisitor::default();
+        span.record(&mut visitor);
+        let parent = match span.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if span.is_contextual() => entered(),
+            None => None,
+        };
+        let otel = if exports_otel() {
+            Python::with_gil(|py| {
+                self.start_otel_span(py, span.metadata(), &visitor.fields, parent)
+                    .unwrap_or_else(|err| {
+                        err.write_unraisable(py, None);
+                        None
+                    })
+            })
+        } else {
+            None
+        };
+        spans().lock().unwrap().insert(
+            id,
+            SpanData {
+                metadata: span.metadata(),
+                fields: visitor.fields,
+                parent,
+                started: Instant::now(),
+                refs: 1,
+                otel,
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        let otel = {
+            let mut spans = spans().lock().unwrap();
+            let Some(span) = spans.get_mut(&span.into_u64()) else {
+                return;
+            };
+            span.fields.extend(visitor.fields.iter().cloned());
+            span.otel.clone()
+        };
+        if let Some(otel) = otel {
+            Python::with_gil(|py| {
+                for (name, value) in &visitor.fields {
+                    if let Err(err) =
+                        otel.call_method1(py, intern!(py, "set_attribute"), (*name, value))
+                    {
+                        err.write_unraisable(py, None);
+                    }
+                }
+            });
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let retry = is_retry(metadata);
+        if retry {
+            stats::retried();
+        }
+        // Checked before taking the GIL, which counting retries doesn't need
+        if !(retry && RETRY_HOOK.read().unwrap().is_some()) && !is_traced(metadata) {
+            return;
+        }
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let parent = match event.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if event.is_contextual() => entered(),
+            None => None,
+        };
+        Python::with_gil(|py| {
+            if retry {
+                self.call_retry_hook(py, visitor.message.as_ref());
+            }
+            if is_traced(metadata) {
+                if let Err(err) = self.forward_event(py, metadata, visitor, parent) {
+                    err.write_unraisable(py, None);
+                }
+            }
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|entered| entered.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        let id = span.into_u64();
+        ENTERED.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some(index) = entered.iter().rposition(|entered| *entered == id) {
+                entered.remove(index);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(data) = spans().lock().unwrap().get_mut(&span.into_u64()) {
+            data.refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let id = span.into_u64();
+        let closed = {
+            let mut spans = spans().lock().unwrap();
+            let refs = spans.get_mut(&id).map(|data| {
+                data.refs -= 1;
+                data.refs
+            });
+            match refs {
+                Some(0) => spans.remove(&id),
+                _ => None,
+            }
+        };
+        let Some(data) = closed else {
+            return false;
+        };
+        Python::with_gil(|py| {
+            if let Err(err) = self.close(py, id, data) {
+                err.write_unraisable(py, None);
+            }
+        });
+        true
+    }
+}
+
+/// Install the subscriber, the first time it's called, and update the levels it's interested
+/// in.
+fn install() -> PyResult<()> {
+    static INSTALLED: OnceLock<Result<(), String>> = OnceLock::new();
+    INSTALLED
+        .get_or_init(|| {
+            tracing::subscriber::set_global_default(PySubscriber).map_err(|err| err.to_string())
+        })
+        .clone()
+        .map_err(|err| {
+            PyRuntimeError::new_err(format!("Couldn't install the tracing subscriber: {err}"))
+        })?;
+    tracing::callsite::rebuild_interest_cache();
+    Ok(())
+}
+
+/// Start counting retries in the stats of operations called with `return_stats`.
+pub(crate) fn count_retries() {
+    if !COUNT_RETRIES.swap(true, Ordering::Relaxed) {
+        // Only fails if another subscriber was installed first, which only Rust code linked into
+        // this module could do, in which case retries can't be counted
+        let _ = install();
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (hook, /))]
+pub(crate) fn set_retry_hook(py: Python, hook: Option<PyObject>) -> PyResult<()> {
+    if let Some(hook) = &hook {
+        if !hook.bind(py).is_callable() {
+            return Err(PyTypeError::new_err("The retry hook must be callable"));
+        }
+    }
+    let installing = hook.is_some();
+    *RETRY_HOOK.write().unwrap() = hook;
+    if installing {
+        install()?;
+    } else {
+        tracing::callsite::rebuild_interest_cache();
+    }
+    Ok(())
+}
+
+fn parse_level(level: &str) -> PyResult<LevelFilter> {
+    match level.to_lowercase().as_str() {
+        "trace" => Ok(LevelFilter::TRACE),
+        "debug" => Ok(LevelFilter::DEBUG),
+        "info" => Ok(LevelFilter::INFO),
+        "warn" | "warning" => Ok(LevelFilter::WARN),
+        "error" => Ok(LevelFilter::ERROR),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown tracing level {level:?}, expected one of 'trace', 'debug', 'info', \
+             'warning' or 'error'"
+        ))),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (*, output = "logging", level = "info", tracer_provider = None))]
+pub(crate) fn enable_tracing(
+    py: Python,
+    output: &str,
+    level: &str,
+    tracer_provider: Option<PyObject>,
+) -> PyResult<()> {
+    let level = parse_level(level)?;
+    let output = match output {
+        "logging" => {
+            if tracer_provider.is_some() {
+                return Err(PyValueError::new_err(
+                    "tracer_provider can only be passed with output='opentelemetry'",
+                ));
+            }
+            Output::Logging
+        }
+        "opentelemetry" => {
+            let trace = py.import(intern!(py, "opentelemetry.trace"))?;
+            let args = ("obstore", env!("CARGO_PKG_VERSION"));
+            let tracer = match tracer_provider {
+                Some(provider) => provider.call_method1(py, intern!(py, "get_tracer"), args)?,
+                None => trace
+                    .call_method1(intern!(py, "get_tracer"), args)?
+                    .unbind(),
+            };
+            Output::OpenTelemetry {
+                tracer,
+                trace: trace.into_any().unbind(),
+            }
+        }
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown tracing output {output:?}, expected 'logging' or 'opentelemetry'"
+            )))
+        }
+    };
+    *TRACING.write().unwrap() = Some(Tracing { level, output });
+    install()
+}
+
+#[pyfunction]
+pub(crate) fn disable_tracing() {
+    *TRACING.write().unwrap() = None;
+    tracing::callsite::rebuild_interest_cache();
+}
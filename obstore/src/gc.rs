@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::TimeDelta;
+use futures::{StreamExt, TryStreamExt};
+use indexmap::IndexMap;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use pyo3::prelude::*;
+use pyo3::pybacked::PyBackedStr;
+use pyo3::types::PyDict;
+use pyo3_object_store::clock;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use ring::digest::{digest, SHA256};
+
+use crate::runtime::{future_into_py, get_runtime};
+
+/// A compact, collision-resistant key for a path.
+///
+/// Reference sets can hold hundreds of millions of paths, so only a 128-bit digest of each path
+/// is kept in memory. A collision could only make an unreferenced object look referenced, which
+/// keeps it rather than deleting it.
+fn path_key(path: &str) -> u128 {
+    let digest = digest(&SHA256, path.as_bytes());
+    let mut key = [0; 16];
+    key.copy_from_slice(&digest.as_ref()[..16]);
+    u128::from_le_bytes(key)
+}
+
+/// Collect the keys of the referenced paths from a Python iterable.
+fn referenced_keys(referenced: &Bound<PyAny>) -> PyResult<HashSet<u128>> {
+    let mut keys = HashSet::new();
+    for path in referenced.try_iter()? {
+        let path = path?.extract::<PyBackedStr>()?;
+        keys.insert(path_key(&path));
+    }
+    Ok(keys)
+}
+
+pub(crate) struct GcReport {
+    unreferenced: Vec<String>,
+    unreferenced_bytes: usize,
+    referenced: usize,
+    missing: usize,
+    skipped_recent: usize,
+    deleted: usize,
+}
+
+impl<'py> IntoPyObject<'py> for GcReport {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let mut dict = IndexMap::with_capacity(6);
+        dict.insert(
+            "unreferenced",
+            self.unreferenced.into_pyobject(py)?.into_any(),
+        );
+        dict.insert(
+            "unreferenced_bytes",
+            self.unreferenced_bytes.into_pyobject(py)?.into_any(),
+        );
+        dict.insert("referenced", self.referenced.into_pyobject(py)?.into_any());
+        dict.insert("missing", self.missing.into_pyobject(py)?.into_any());
+        dict.insert(
+            "skipped_recent",
+            self.skipped_recent.into_pyobject(py)?.into_any(),
+        );
+        dict.insert("deleted", self.deleted.into_pyobject(py)?.into_any());
+        dict.into_pyobject(py)
+    }
+}
+
+async fn gc_inner(
+    store: Arc<dyn ObjectStore>,
+    prefix: Option<Path>,
+    referenced: HashSet<u128>,
+    dry_run: bool,
+    min_age: Option<TimeDelta>,
+) -> PyObjectStoreResult<GcReport> {
+    let cutoff = min_age.map(|min_age| clock::now() - min_age);
+    let mut report = GcReport {
+        unreferenced: vec![],
+        unreferenced_bytes: 0,
+        referenced: 0,
+        missing: 0,
+        skipped_recent: 0,
+        deleted: 0,
+    };
+    let mut unreferenced = vec![];
+    let mut listing = store.list(prefix.as_ref());
+    while let Some(meta) = listing.try_next().await? {
+        if referenced.contains(&path_key(meta.location.as_ref())) {
+            report.referenced += 1;
+        } else if cutoff.is_some_and(|cutoff| meta.last_modified > cutoff) {
+            // The object may have been written by a writer whose manifest isn't committed yet
+            report.skipped_recent += 1;
+        } else {
+            report.unreferenced_bytes += meta.size;
+            unreferenced.push(meta.location);
+        }
+    }
+    report.missing = referenced.len().saturating_sub(report.referenced);
+
+    if !dry_run && !unreferenced.is_empty() {
+        let locations = futures::stream::iter(unreferenced.clone().into_iter().map(Ok)).boxed();
+        report.deleted = store
+            .delete_stream(locations)
+            .try_collect::<Vec<_>>()
+            .await?
+            .len();
+    }
+    report.unreferenced = unreferenced.into_iter().map(String::from).collect();
+    Ok(report)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, referenced, *, dry_run = true, min_age = None))]
+pub(crate) fn gc(
+    py: Python,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    referenced: &Bound<PyAny>,
+    dry_run: bool,
+    min_age: Option<TimeDelta>,
+) -> PyObjectStoreResult<GcReport> {
+    let runtime = get_runtime(py)?;
+    let referenced = referenced_keys(referenced)?;
+    py.allow_threads(|| {
+        let out = runtime.block_on(gc_inner(
+            store.into_inner(),
+            prefix.map(Path::from),
+            referenced,
+            dry_run,
+            min_age,
+        ))?;
+        Ok::<_, PyObjectStoreError>(out)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, referenced, *, dry_run = true, min_age = None))]
+pub(crate) fn gc_async<'py>(
+    py: Python<'py>,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    referenced: &Bound<'py, PyAny>,
+    dry_run: bool,
+    min_age: Option<TimeDelta>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let referenced = referenced_keys(referenced)?;
+    future_into_py(py, async move {
+        let out = gc_inner(
+            store.into_inner(),
+            prefix.map(Path::from),
+            referenced,
+            dry_run,
+            min_age,
+        )
+        .await?;
+        Ok(out)
+    })
+}
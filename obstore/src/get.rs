@@ -1,20 +1,26 @@
 use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::Arc;
 
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use futures::stream::{BoxStream, Fuse};
 use futures::StreamExt;
+use object_store::path::Path;
 use object_store::{GetOptions, GetRange, GetResult, ObjectStore};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
 use pyo3::exceptions::{PyStopAsyncIteration, PyStopIteration, PyValueError};
 use pyo3::prelude::*;
 use pyo3_bytes::PyBytes;
 use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
 use tokio::sync::Mutex;
 
+use crate::alias::resolve_alias_inner;
 use crate::attributes::PyAttributes;
 use crate::list::PyObjectMeta;
-use crate::runtime::get_runtime;
+use crate::ranges::{self, check_bounded};
+use crate::runtime::{future_into_py, get_runtime};
+use crate::stats::{track, WithStats};
 
 /// 10MB default chunk size
 const DEFAULT_BYTES_CHUNK_SIZE: usize = 10 * 1024 * 1024;
@@ -105,7 +111,9 @@ pub(crate) struct PyGetRange(GetRange);
 impl<'py> FromPyObject<'py> for PyGetRange {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
         if let Ok(bounded) = ob.extract::<[usize; 2]>() {
-            Ok(Self(GetRange::Bounded(bounded[0]..bounded[1])))
+            let range = bounded[0]..bounded[1];
+            check_bounded(&range)?;
+            Ok(Self(GetRange::Bounded(range)))
         } else if let Ok(offset_range) = ob.extract::<PyOffsetRange>() {
             Ok(Self(offset_range.into()))
         } else if let Ok(suffix_range) = ob.extract::<PySuffixRange>() {
@@ -120,7 +128,7 @@ impl<'py> FromPyObject<'py> for PyGetRange {
 pub(crate) struct PyGetResult(std::sync::Mutex<Option<GetResult>>);
 
 impl PyGetResult {
-    fn new(result: GetResult) -> Self {
+    pub(crate) fn new(result: GetResult) -> Self {
         Self(std::sync::Mutex::new(Some(result)))
     }
 }
@@ -148,7 +156,7 @@ impl PyGetResult {
             .unwrap()
             .take()
             .ok_or(PyValueError::new_err("Result has already been disposed."))?;
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        future_into_py(py, async move {
             let bytes = get_result
                 .bytes()
                 .await
@@ -211,6 +219,7 @@ impl PyGetResult {
 pub struct PyBytesStream {
     stream: Arc<Mutex<Fuse<BoxStream<'static, object_store::Result<Bytes>>>>>,
     min_chunk_size: usize,
+    pool: ChunkPool,
 }
 
 impl PyBytesStream {
@@ -218,6 +227,7 @@ impl PyBytesStream {
         Self {
             stream: Arc::new(Mutex::new(stream.fuse())),
             min_chunk_size,
+            pool: ChunkPool::default(),
         }
     }
 }
@@ -225,18 +235,17 @@ impl PyBytesStream {
 async fn next_stream(
     stream: Arc<Mutex<Fuse<BoxStream<'static, object_store::Result<Bytes>>>>>,
     min_chunk_size: usize,
+    pool: ChunkPool,
     sync: bool,
 ) -> PyResult<PyBytesWrapper> {
     let mut stream = stream.lock().await;
-    let mut buffers: Vec<Bytes> = vec![];
-    let mut total_buffer_len = 0;
+    let mut buffers = PyBytesWrapper::default();
     loop {
         match stream.next().await {
             Some(Ok(bytes)) => {
-                total_buffer_len += bytes.len();
-                buffers.push(bytes);
-                if total_buffer_len >= min_chunk_size {
-                    return Ok(PyBytesWrapper::new_multiple(buffers));
+                buffers.push(bytes, &pool);
+                if buffers.len() >= min_chunk_size {
+                    return Ok(buffers);
                 }
             }
             Some(Err(e)) => return Err(PyObjectStoreError::from(e).into()),
@@ -250,7 +259,7 @@ async fn next_stream(
                         return Err(PyStopAsyncIteration::new_err("stream exhausted"));
                     }
                 } else {
-                    return Ok(PyBytesWrapper::new_multiple(buffers));
+                    return Ok(buffers);
                 }
             }
         };
@@ -269,24 +278,103 @@ impl PyBytesStream {
 
     fn __anext__<'py>(&'py self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let stream = self.stream.clone();
-        pyo3_async_runtimes::tokio::future_into_py(
-            py,
-            next_stream(stream, self.min_chunk_size, false),
-        )
+        let pool = self.pool.clone();
+        future_into_py(py, next_stream(stream, self.min_chunk_size, pool, false))
     }
 
     fn __next__<'py>(&'py self, py: Python<'py>) -> PyResult<PyBytesWrapper> {
         let runtime = get_runtime(py)?;
         let stream = self.stream.clone();
-        runtime.block_on(next_stream(stream, self.min_chunk_size, true))
+        let pool = self.pool.clone();
+        py.allow_threads(|| runtime.block_on(next_stream(stream, self.min_chunk_size, pool, true)))
     }
 }
 
-struct PyBytesWrapper(Vec<Bytes>);
+/// Copies of at least this many bytes into Python are done without holding the GIL.
+const RELEASE_GIL_COPY_THRESHOLD: usize = 1024 * 1024;
+
+/// The `Vec` that the chunks of a [`PyBytesStream`] item made of several chunks are gathered in,
+/// kept between items so that each of them doesn't allocate its own.
+///
+/// It's put back empty once the item has been copied into Python, so it never keeps chunks
+/// alive. Items are read one at a time, so a single spare is enough, unless several items read
+/// asynchronously are awaiting their copy at once, in which case the others allocate.
+#[derive(Clone, Default)]
+struct ChunkPool(Arc<std::sync::Mutex<Option<Vec<Bytes>>>>);
+
+impl ChunkPool {
+    fn take(&self) -> Vec<Bytes> {
+        self.0.lock().unwrap().take().unwrap_or_default()
+    }
+
+    fn put(&self, mut buffers: Vec<Bytes>) {
+        buffers.clear();
+        *self.0.lock().unwrap() = Some(buffers);
+    }
+}
+
+/// The chunks making up one item of a [`PyBytesStream`].
+///
+/// Most items are a single chunk from the underlying stream, which is held as is. Items of
+/// several chunks are gathered in the `Vec` of the stream's [`ChunkPool`]. Either way, the chunks
+/// are copied exactly once, straight into the Python `bytes` object.
+#[derive(Default)]
+enum PyBytesWrapper {
+    #[default]
+    Empty,
+    Single(Bytes),
+    Multiple {
+        buffers: Vec<Bytes>,
+        len: usize,
+        pool: ChunkPool,
+    },
+}
 
 impl PyBytesWrapper {
-    fn new_multiple(buffers: Vec<Bytes>) -> Self {
-        Self(buffers)
+    fn push(&mut self, bytes: Bytes, pool: &ChunkPool) {
+        *self = match std::mem::take(self) {
+            Self::Empty => Self::Single(bytes),
+            Self::Single(first) => {
+                let mut buffers = pool.take();
+                let len = first.len() + bytes.len();
+                buffers.push(first);
+                buffers.push(bytes);
+                Self::Multiple {
+                    buffers,
+                    len,
+                    pool: pool.clone(),
+                }
+            }
+            Self::Multiple {
+                mut buffers,
+                len,
+                pool,
+            } => {
+                let len = len + bytes.len();
+                buffers.push(bytes);
+                Self::Multiple { buffers, len, pool }
+            }
+        };
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Empty => 0,
+            Self::Single(bytes) => bytes.len(),
+            Self::Multiple { len, .. } => *len,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    fn buffers(&self) -> &[Bytes] {
+        match self {
+            Self::Empty => &[],
+            Self::Single(bytes) => std::slice::from_ref(bytes),
+            Self::Multiple { buffers, .. } => buffers,
+        }
     }
 }
 
@@ -299,103 +387,172 @@ impl<'py> IntoPyObject<'py> for PyBytesWrapper {
     type Error = PyErr;
 
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        let total_len = self.0.iter().fold(0, |acc, buf| acc + buf.len());
-
-        // Copy all internal Bytes objects into a single PyBytes
-        // Since our inner callback is infallible, this will only panic on out of memory
-        pyo3::types::PyBytes::new_with(py, total_len, |target| {
+        let copy = |target: &mut [u8]| {
             let mut offset = 0;
-            for buf in self.0.iter() {
+            for buf in self.buffers() {
                 target[offset..offset + buf.len()].copy_from_slice(buf);
                 offset += buf.len();
             }
+        };
+
+        // Copy all internal Bytes objects into a single PyBytes
+        // Since our inner callback is infallible, this will only panic on out of memory
+        let bytes = pyo3::types::PyBytes::new_with(py, self.len(), |target| {
+            // The new object isn't visible to any other thread yet, so large copies can run
+            // without the GIL. For small ones, releasing it costs more than the copy itself.
+            if target.len() >= RELEASE_GIL_COPY_THRESHOLD {
+                py.allow_threads(|| copy(target));
+            } else {
+                copy(target);
+            }
             Ok(())
-        })
+        });
+        if let Self::Multiple { buffers, pool, .. } = self {
+            pool.put(buffers);
+        }
+        bytes
     }
 }
 
 #[pyfunction]
-#[pyo3(signature = (store, path, *, options = None))]
+#[pyo3(signature = (store, path, *, options = None, resolve_aliases = false, return_stats = false))]
 pub(crate) fn get(
     py: Python,
     store: PyObjectStore,
     path: String,
     options: Option<PyGetOptions>,
-) -> PyObjectStoreResult<PyGetResult> {
+    resolve_aliases: bool,
+    return_stats: bool,
+) -> PyObjectStoreResult<WithStats<PyGetResult>> {
+    let (store, stats) = track(store.into_inner(), return_stats);
     let runtime = get_runtime(py)?;
     py.allow_threads(|| {
-        let path = &path.into();
-        let fut = if let Some(options) = options {
-            store.as_ref().get_opts(path, options.into())
-        } else {
-            store.as_ref().get(path)
-        };
-        let out = runtime.block_on(fut)?;
-        Ok::<_, PyObjectStoreError>(PyGetResult::new(out))
+        let out = runtime.block_on(get_inner(store, path.into(), options, resolve_aliases))?;
+        Ok::<_, PyObjectStoreError>(WithStats::new(PyGetResult::new(out), stats))
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (store, path, *, options = None))]
+#[pyo3(signature = (store, path, *, options = None, resolve_aliases = false, return_stats = false))]
 pub(crate) fn get_async(
     py: Python,
     store: PyObjectStore,
     path: String,
     options: Option<PyGetOptions>,
+    resolve_aliases: bool,
+    return_stats: bool,
 ) -> PyResult<Bound<PyAny>> {
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let path = &path.into();
-        let fut = if let Some(options) = options {
-            store.as_ref().get_opts(path, options.into())
-        } else {
-            store.as_ref().get(path)
-        };
-        let out = fut.await.map_err(PyObjectStoreError::ObjectStoreError)?;
-        Ok(PyGetResult::new(out))
+    let (store, stats) = track(store.into_inner(), return_stats);
+    future_into_py(py, async move {
+        let out = get_inner(store, path.into(), options, resolve_aliases).await?;
+        Ok(WithStats::new(PyGetResult::new(out), stats))
     })
 }
 
+async fn get_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    options: Option<PyGetOptions>,
+    resolve_aliases: bool,
+) -> PyObjectStoreResult<GetResult> {
+    let path = if resolve_aliases {
+        resolve_alias_inner(&store, path).await?
+    } else {
+        path
+    };
+    let out = if let Some(options) = options {
+        ranges::get_opts(&store, &path, options.into()).await?
+    } else {
+        store.get(&path).await?
+    };
+    Ok(out)
+}
+
+/// The options of a range request, which can't include a range of their own.
+fn range_options(options: Option<PyGetOptions>) -> PyResult<Option<GetOptions>> {
+    match options {
+        Some(options) if options.range.is_some() || options.head => Err(PyValueError::new_err(
+            "options for range requests can't include 'range' or 'head'",
+        )),
+        options => Ok(options.map(GetOptions::from)),
+    }
+}
+
+async fn get_ranges_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    ranges: Vec<Range<usize>>,
+    options: Option<GetOptions>,
+) -> PyObjectStoreResult<Vec<Bytes>> {
+    match options {
+        Some(options) => ranges::get_ranges_opts(&store, &path, &ranges, options).await,
+        None => ranges::get_ranges(&store, &path, &ranges).await,
+    }
+}
+
 #[pyfunction]
+#[pyo3(signature = (store, path, start, end, *, options = None, return_stats = false))]
 pub(crate) fn get_range(
     py: Python,
     store: PyObjectStore,
     path: String,
     start: usize,
     end: usize,
-) -> PyObjectStoreResult<pyo3_bytes::PyBytes> {
+    options: Option<PyGetOptions>,
+    return_stats: bool,
+) -> PyObjectStoreResult<WithStats<pyo3_bytes::PyBytes>> {
+    let options = range_options(options)?;
+    let (store, stats) = track(store.into_inner(), return_stats);
     let runtime = get_runtime(py)?;
     py.allow_threads(|| {
-        let out = runtime.block_on(store.as_ref().get_range(&path.into(), start..end))?;
-        Ok::<_, PyObjectStoreError>(pyo3_bytes::PyBytes::new(out))
+        let mut out = runtime.block_on(get_ranges_inner(
+            store,
+            path.into(),
+            vec![start..end],
+            options,
+        ))?;
+        Ok::<_, PyObjectStoreError>(WithStats::new(
+            pyo3_bytes::PyBytes::new(out.remove(0)),
+            stats,
+        ))
     })
 }
 
 #[pyfunction]
+#[pyo3(signature = (store, path, start, end, *, options = None, return_stats = false))]
 pub(crate) fn get_range_async(
     py: Python,
     store: PyObjectStore,
     path: String,
     start: usize,
     end: usize,
+    options: Option<PyGetOptions>,
+    return_stats: bool,
 ) -> PyResult<Bound<PyAny>> {
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let out = store
-            .as_ref()
-            .get_range(&path.into(), start..end)
-            .await
-            .map_err(PyObjectStoreError::ObjectStoreError)?;
-        Ok(pyo3_bytes::PyBytes::new(out))
+    let options = range_options(options)?;
+    let (store, stats) = track(store.into_inner(), return_stats);
+    future_into_py(py, async move {
+        let mut out = get_ranges_inner(store, path.into(), vec![start..end], options).await?;
+        Ok(WithStats::new(
+            pyo3_bytes::PyBytes::new(out.remove(0)),
+            stats,
+        ))
     })
 }
 
 #[pyfunction]
+#[pyo3(signature = (store, path, starts, ends, *, options = None, return_stats = false))]
 pub(crate) fn get_ranges(
     py: Python,
     store: PyObjectStore,
     path: String,
     starts: Vec<usize>,
     ends: Vec<usize>,
-) -> PyObjectStoreResult<Vec<pyo3_bytes::PyBytes>> {
+    options: Option<PyGetOptions>,
+    return_stats: bool,
+) -> PyObjectStoreResult<WithStats<Vec<pyo3_bytes::PyBytes>>> {
+    let options = range_options(options)?;
+    let (store, stats) = track(store.into_inner(), return_stats);
     let runtime = get_runtime(py)?;
     let ranges = starts
         .into_iter()
@@ -403,33 +560,104 @@ pub(crate) fn get_ranges(
         .map(|(start, end)| start..end)
         .collect::<Vec<_>>();
     py.allow_threads(|| {
-        let out = runtime.block_on(store.as_ref().get_ranges(&path.into(), &ranges))?;
-        Ok::<_, PyObjectStoreError>(out.into_iter().map(|buf| buf.into()).collect())
+        let out = runtime.block_on(get_ranges_inner(store, path.into(), ranges, options))?;
+        let out = out.into_iter().map(|buf| buf.into()).collect();
+        Ok::<_, PyObjectStoreError>(WithStats::new(out, stats))
     })
 }
 
 #[pyfunction]
+#[pyo3(signature = (store, path, starts, ends, *, options = None, return_stats = false))]
 pub(crate) fn get_ranges_async(
     py: Python,
     store: PyObjectStore,
     path: String,
     starts: Vec<usize>,
     ends: Vec<usize>,
+    options: Option<PyGetOptions>,
+    return_stats: bool,
 ) -> PyResult<Bound<PyAny>> {
+    let options = range_options(options)?;
+    let (store, stats) = track(store.into_inner(), return_stats);
     let ranges = starts
         .into_iter()
         .zip(ends)
         .map(|(start, end)| start..end)
         .collect::<Vec<_>>();
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let out = store
-            .as_ref()
-            .get_ranges(&path.into(), &ranges)
-            .await
-            .map_err(PyObjectStoreError::ObjectStoreError)?;
-        Ok(out
+    future_into_py(py, async move {
+        let out = get_ranges_inner(store, path.into(), ranges, options).await?;
+        let out = out
             .into_iter()
             .map(pyo3_bytes::PyBytes::new)
-            .collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        Ok(WithStats::new(out, stats))
     })
 }
+
+#[pyfunction]
+#[pyo3(signature = (store, paths, *, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn get_many(
+    store: PyObjectStore,
+    paths: Vec<String>,
+    max_concurrency: Concurrency,
+) -> PyGetManyStream {
+    let store = store.into_inner();
+    let downloads = paths.into_iter().map(move |path| {
+        let store = store.clone();
+        async move {
+            let bytes = store.get(&path.as_str().into()).await?.bytes().await?;
+            Ok((path, bytes))
+        }
+    });
+    PyGetManyStream::new(buffer_unordered(downloads, max_concurrency).boxed())
+}
+
+// Note: we fuse the underlying stream so that we can get `None` multiple times.
+// See the note on PyListStream for more background.
+#[pyclass(name = "GetManyStream", frozen)]
+pub(crate) struct PyGetManyStream {
+    stream: Arc<Mutex<Fuse<BoxStream<'static, object_store::Result<(String, Bytes)>>>>>,
+}
+
+impl PyGetManyStream {
+    fn new(stream: BoxStream<'static, object_store::Result<(String, Bytes)>>) -> Self {
+        Self {
+            stream: Arc::new(Mutex::new(stream.fuse())),
+        }
+    }
+}
+
+async fn next_download(
+    stream: Arc<Mutex<Fuse<BoxStream<'static, object_store::Result<(String, Bytes)>>>>>,
+    sync: bool,
+) -> PyResult<(String, PyBytes)> {
+    let mut stream = stream.lock().await;
+    match stream.next().await {
+        Some(Ok((path, bytes))) => Ok((path, bytes.into())),
+        Some(Err(e)) => Err(PyObjectStoreError::from(e).into()),
+        None if sync => Err(PyStopIteration::new_err("stream exhausted")),
+        None => Err(PyStopAsyncIteration::new_err("stream exhausted")),
+    }
+}
+
+#[pymethods]
+impl PyGetManyStream {
+    fn __aiter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __iter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&'py self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let stream = self.stream.clone();
+        future_into_py(py, next_download(stream, false))
+    }
+
+    fn __next__<'py>(&'py self, py: Python<'py>) -> PyResult<(String, PyBytes)> {
+        let runtime = get_runtime(py)?;
+        let stream = self.stream.clone();
+        py.allow_threads(|| runtime.block_on(next_download(stream, true)))
+    }
+}
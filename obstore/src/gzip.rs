@@ -0,0 +1,398 @@
+//! Random access into gzip objects through `.gzi` index sidecars.
+//!
+//! The index uses the format written by `bgzip`: a little-endian `u64` count of entries,
+//! followed by a `(compressed offset, uncompressed offset)` pair of `u64`s for the start of
+//! every gzip member except the first. Any range of the uncompressed data can then be read by
+//! decompressing the members containing it.
+
+use std::io::{self, BufRead, BufReader, Read};
+use std::sync::Arc;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use flate2::bufread::GzDecoder;
+use flate2::read::MultiGzDecoder;
+use futures::stream::{BoxStream, StreamExt};
+use object_store::path::Path;
+use object_store::{GetOptions, GetRange, ObjectStore};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_bytes::PyBytes;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use tokio::runtime::Handle;
+
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The buffer size used while scanning an object for its gzip members.
+const SCAN_BUFFER_SIZE: usize = 1024 * 1024;
+
+fn index_path(path: &Path, index_path: Option<String>) -> Path {
+    index_path
+        .map(Path::from)
+        .unwrap_or_else(|| Path::from(format!("{path}.gzi")))
+}
+
+/// A blocking reader over the content of an object, for use on a blocking thread.
+struct StreamReader {
+    stream: BoxStream<'static, object_store::Result<Bytes>>,
+    buf: Bytes,
+    handle: Handle,
+}
+
+impl Read for StreamReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        while self.buf.is_empty() {
+            match self.handle.block_on(self.stream.next()) {
+                Some(chunk) => self.buf = chunk.map_err(io::Error::other)?,
+                None => return Ok(0),
+            }
+        }
+        let n = out.len().min(self.buf.len());
+        self.buf.copy_to_slice(&mut out[..n]);
+        Ok(n)
+    }
+}
+
+/// A reader counting the bytes consumed from it.
+struct CountingReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R: BufRead> Read for CountingReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(out)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position += amt as u64;
+        self.inner.consume(amt)
+    }
+}
+
+/// Decompress `reader` member by member, returning the index of its members and the total
+/// uncompressed size.
+fn scan_members(reader: impl Read) -> io::Result<(Vec<(u64, u64)>, u64)> {
+    let mut reader = CountingReader {
+        inner: BufReader::with_capacity(SCAN_BUFFER_SIZE, reader),
+        position: 0,
+    };
+    let mut members = vec![];
+    let mut uncompressed = 0;
+    while !reader.fill_buf()?.is_empty() {
+        members.push((reader.position, uncompressed));
+        // The bufread decoder only consumes the bytes of a single member
+        let mut decoder = GzDecoder::new(reader);
+        uncompressed += io::copy(&mut decoder, &mut io::sink())?;
+        reader = decoder.into_inner();
+    }
+    Ok((members, uncompressed))
+}
+
+/// The gzip members of an object, and where they start in its compressed and uncompressed
+/// data.
+#[pyclass(name = "GzipIndex", frozen)]
+pub(crate) struct PyGzipIndex {
+    /// The `(compressed, uncompressed)` offsets of each member, starting with `(0, 0)`
+    members: Vec<(u64, u64)>,
+    compressed_size: u64,
+    uncompressed_size: u64,
+}
+
+impl PyGzipIndex {
+    fn encode(&self) -> Bytes {
+        let entries = &self.members[1.min(self.members.len())..];
+        let mut buf = BytesMut::with_capacity(8 + entries.len() * 16);
+        buf.put_u64_le(entries.len() as u64);
+        for (compressed, uncompressed) in entries {
+            buf.put_u64_le(*compressed);
+            buf.put_u64_le(*uncompressed);
+        }
+        buf.freeze()
+    }
+
+    fn decode(
+        mut buf: Bytes,
+        compressed_size: u64,
+        last_member_size: u32,
+    ) -> PyObjectStoreResult<Self> {
+        let invalid = || PyValueError::new_err("Invalid gzip index");
+        if buf.len() < 8 {
+            return Err(invalid().into());
+        }
+        let count = buf.get_u64_le() as usize;
+        if buf.len() != count * 16 {
+            return Err(invalid().into());
+        }
+        let mut members = vec![(0, 0)];
+        for _ in 0..count {
+            members.push((buf.get_u64_le(), buf.get_u64_le()));
+        }
+        let (_, last_start) = members[members.len() - 1];
+        Ok(Self {
+            members,
+            compressed_size,
+            // ISIZE is the size of the member modulo 2^32
+            uncompressed_size: last_start + last_member_size as u64,
+        })
+    }
+
+    /// The compressed byte range containing the uncompressed `start..end`, and the offset of
+    /// `start` within the decompressed data of that range.
+    fn map_range(&self, start: u64, end: u64) -> (u64, u64, u64) {
+        let first = self
+            .members
+            .partition_point(|(_, uncompressed)| *uncompressed <= start)
+            .saturating_sub(1);
+        let compressed_end = self
+            .members
+            .iter()
+            .find(|(_, uncompressed)| *uncompressed >= end)
+            .map_or(self.compressed_size, |(compressed, _)| *compressed);
+        let (compressed_start, uncompressed_start) = self.members[first];
+        (
+            compressed_start,
+            compressed_end.max(compressed_start),
+            start - uncompressed_start,
+        )
+    }
+}
+
+#[pymethods]
+impl PyGzipIndex {
+    /// The `(compressed_offset, uncompressed_offset)` of the start of each gzip member.
+    #[getter]
+    fn members(&self) -> Vec<(u64, u64)> {
+        self.members.clone()
+    }
+
+    #[getter]
+    fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    #[getter]
+    fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// The compressed byte range that must be decompressed to read the uncompressed
+    /// `start..end`.
+    fn compressed_range(&self, start: u64, end: u64) -> PyResult<(u64, u64)> {
+        if start > end {
+            return Err(PyValueError::new_err(
+                "start offset must not be greater than end offset",
+            ));
+        }
+        let (start, end, _) = self.map_range(start, end.min(self.uncompressed_size));
+        Ok((start, end))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GzipIndex(members={}, compressed_size={}, uncompressed_size={})",
+            self.members.len(),
+            self.compressed_size,
+            self.uncompressed_size
+        )
+    }
+}
+
+async fn create_gzip_index_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    index_path: Path,
+) -> PyObjectStoreResult<PyGzipIndex> {
+    let result = store.get(&path).await?;
+    let compressed_size = result.meta.size as u64;
+    let reader = StreamReader {
+        stream: result.into_stream(),
+        buf: Bytes::new(),
+        handle: Handle::current(),
+    };
+    let (members, uncompressed_size) = tokio::task::spawn_blocking(move || scan_members(reader))
+        .await
+        .map_err(io::Error::other)??;
+    let index = PyGzipIndex {
+        members,
+        compressed_size,
+        uncompressed_size,
+    };
+    store.put(&index_path, index.encode().into()).await?;
+    Ok(index)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, index_path = None))]
+pub(crate) fn create_gzip_index(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    index_path: Option<String>,
+) -> PyObjectStoreResult<PyGzipIndex> {
+    let path = Path::from(path);
+    let index_path = self::index_path(&path, index_path);
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(create_gzip_index_inner(
+            store.into_inner(),
+            path,
+            index_path,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, index_path = None))]
+pub(crate) fn create_gzip_index_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    index_path: Option<String>,
+) -> PyResult<Bound<PyAny>> {
+    let path = Path::from(path);
+    let index_path = self::index_path(&path, index_path);
+    future_into_py(py, async move {
+        let index = create_gzip_index_inner(store.into_inner(), path, index_path).await?;
+        Ok(index)
+    })
+}
+
+async fn read_gzip_index_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    index_path: Path,
+) -> PyObjectStoreResult<PyGzipIndex> {
+    let buf = store.get(&index_path).await?.bytes().await?;
+    // The trailer of the last member holds its uncompressed size
+    let options = GetOptions {
+        range: Some(GetRange::Suffix(4)),
+        ..Default::default()
+    };
+    let trailer = store.get_opts(&path, options).await?;
+    let compressed_size = trailer.meta.size as u64;
+    let trailer = trailer.bytes().await?;
+    let last_member_size = <[u8; 4]>::try_from(trailer.as_ref())
+        .map(u32::from_le_bytes)
+        .map_err(|_| PyValueError::new_err(format!("{path} is not a gzip object")))?;
+    PyGzipIndex::decode(buf, compressed_size, last_member_size)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, index_path = None))]
+pub(crate) fn read_gzip_index(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    index_path: Option<String>,
+) -> PyObjectStoreResult<PyGzipIndex> {
+    let path = Path::from(path);
+    let index_path = self::index_path(&path, index_path);
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(read_gzip_index_inner(store.into_inner(), path, index_path))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, index_path = None))]
+pub(crate) fn read_gzip_index_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    index_path: Option<String>,
+) -> PyResult<Bound<PyAny>> {
+    let path = Path::from(path);
+    let index_path = self::index_path(&path, index_path);
+    future_into_py(py, async move {
+        let index = read_gzip_index_inner(store.into_inner(), path, index_path).await?;
+        Ok(index)
+    })
+}
+
+async fn get_gzip_range_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    start: u64,
+    end: u64,
+    index: Option<Py<PyGzipIndex>>,
+) -> PyObjectStoreResult<Bytes> {
+    let index = match index {
+        Some(index) => index,
+        None => {
+            let index_path = index_path(&path, None);
+            let index = read_gzip_index_inner(store.clone(), path.clone(), index_path).await?;
+            Python::with_gil(|py| Py::new(py, index))?
+        }
+    };
+    let index = index.get();
+    let end = end.min(index.uncompressed_size);
+    if start >= end {
+        return Ok(Bytes::new());
+    }
+    let (compressed_start, compressed_end, skip) = index.map_range(start, end);
+    let compressed = store
+        .get_range(&path, compressed_start as usize..compressed_end as usize)
+        .await?;
+    let mut decoder = MultiGzDecoder::new(compressed.reader());
+    io::copy(&mut (&mut decoder).take(skip), &mut io::sink())?;
+    let mut out = Vec::with_capacity((end - start) as usize);
+    decoder.take(end - start).read_to_end(&mut out)?;
+    Ok(out.into())
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, start, end, *, index = None))]
+pub(crate) fn get_gzip_range(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    start: u64,
+    end: u64,
+    index: Option<Py<PyGzipIndex>>,
+) -> PyObjectStoreResult<PyBytes> {
+    if start > end {
+        return Err(
+            PyValueError::new_err("start offset must not be greater than end offset").into(),
+        );
+    }
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let out = runtime.block_on(get_gzip_range_inner(
+            store.into_inner(),
+            path.into(),
+            start,
+            end,
+            index,
+        ))?;
+        Ok::<_, PyObjectStoreError>(PyBytes::new(out))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, start, end, *, index = None))]
+pub(crate) fn get_gzip_range_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+    start: u64,
+    end: u64,
+    index: Option<Py<PyGzipIndex>>,
+) -> PyResult<Bound<PyAny>> {
+    if start > end {
+        return Err(PyValueError::new_err(
+            "start offset must not be greater than end offset",
+        ));
+    }
+    future_into_py(py, async move {
+        let out = get_gzip_range_inner(store.into_inner(), path.into(), start, end, index).await?;
+        Ok(PyBytes::new(out))
+    })
+}
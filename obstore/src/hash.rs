@@ -0,0 +1,27 @@
+//! Checksums over buffer-protocol objects, for verifying the integrity of objects.
+
+// The functions share their names with the crates implementing them, so those are referred to
+// by absolute paths
+use ::md5::{Digest, Md5};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes as PythonBytes;
+use pyo3_bytes::PyBytes;
+
+#[pyfunction]
+#[pyo3(signature = (buf, /, value = 0))]
+pub(crate) fn crc32c(py: Python, buf: PyBytes, value: u32) -> u32 {
+    py.allow_threads(|| ::crc32c::crc32c_append(value, buf.as_slice()))
+}
+
+#[pyfunction]
+#[pyo3(signature = (buf, /, seed = 0))]
+pub(crate) fn xxh3(py: Python, buf: PyBytes, seed: u64) -> u64 {
+    py.allow_threads(|| xxhash_rust::xxh3::xxh3_64_with_seed(buf.as_slice(), seed))
+}
+
+#[pyfunction]
+#[pyo3(signature = (buf, /))]
+pub(crate) fn md5(py: Python, buf: PyBytes) -> Bound<PythonBytes> {
+    let digest = py.allow_threads(|| Md5::digest(buf.as_slice()));
+    PythonBytes::new(py, &digest)
+}
@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::future;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::ObjectStore;
 use pyo3::prelude::*;
 use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
 
 use crate::list::PyObjectMeta;
-use crate::runtime::get_runtime;
+use crate::runtime::{future_into_py, get_runtime};
 
 #[pyfunction]
 pub fn head(py: Python, store: PyObjectStore, path: String) -> PyObjectStoreResult<PyObjectMeta> {
@@ -18,7 +25,7 @@ pub fn head(py: Python, store: PyObjectStore, path: String) -> PyObjectStoreResu
 #[pyfunction]
 pub fn head_async(py: Python, store: PyObjectStore, path: String) -> PyResult<Bound<PyAny>> {
     let store = store.into_inner().clone();
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    future_into_py(py, async move {
         let meta = store
             .head(&path.into())
             .await
@@ -26,3 +33,71 @@ pub fn head_async(py: Python, store: PyObjectStore, path: String) -> PyResult<Bo
         Ok(PyObjectMeta::new(meta))
     })
 }
+
+/// A path that is used to open connections when there are fewer paths to warm up than
+/// connections. It is not expected to exist.
+const WARM_UP_PATH: &str = ".obstore-warm-up";
+
+async fn warm_up_inner(
+    store: Arc<dyn ObjectStore>,
+    paths: Vec<String>,
+    connections: usize,
+) -> PyObjectStoreResult<HashMap<String, PyObjectMeta>> {
+    // Each concurrent request resolves DNS and sets up a connection in the client's pool,
+    // which later requests reuse
+    let probes = connections.saturating_sub(paths.len());
+    let requests = paths
+        .into_iter()
+        .map(Some)
+        .chain(std::iter::repeat(None).take(probes))
+        .map(|path| {
+            let store = store.clone();
+            async move {
+                let location = Path::from(path.as_deref().unwrap_or(WARM_UP_PATH));
+                match store.head(&location).await {
+                    Ok(meta) => Ok(path.map(|path| (path, PyObjectMeta::new(meta)))),
+                    Err(object_store::Error::NotFound { .. }) => Ok(None),
+                    Err(err) => Err(err),
+                }
+            }
+        });
+    let found = stream::iter(requests)
+        .buffer_unordered(connections.max(1))
+        .try_filter_map(|found| future::ready(Ok(found)))
+        .try_collect()
+        .await?;
+    Ok(found)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, paths = None, *, connections = 1))]
+pub(crate) fn warm_up(
+    py: Python,
+    store: PyObjectStore,
+    paths: Option<Vec<String>>,
+    connections: usize,
+) -> PyObjectStoreResult<HashMap<String, PyObjectMeta>> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(warm_up_inner(
+            store.into_inner(),
+            paths.unwrap_or_default(),
+            connections,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, paths = None, *, connections = 1))]
+pub(crate) fn warm_up_async(
+    py: Python,
+    store: PyObjectStore,
+    paths: Option<Vec<String>>,
+    connections: usize,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let found =
+            warm_up_inner(store.into_inner(), paths.unwrap_or_default(), connections).await?;
+        Ok(found)
+    })
+}
@@ -0,0 +1,304 @@
+//! A background task that aborts stale multipart uploads, for long-running services whose
+//! crashed writers leave incomplete uploads behind.
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::future::BoxFuture;
+use object_store::CredentialProvider;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_object_store::{PyObjectStoreResult, PyS3Store, RegionAwareS3};
+use reqwest::Method;
+use serde::Deserialize;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use url::Url;
+
+use crate::bucket::{check_response, parse_xml, request_error};
+use crate::runtime::{future_into_py, get_runtime};
+use crate::shutdown::{register_writer, Finalize, Finalized};
+use crate::sigv4::{signed_request, uri_encode};
+
+const S3: &str = "S3";
+
+// Mirrors of the schema of the ListMultipartUploads response.
+
+#[derive(Debug, Deserialize)]
+struct S3ListMultipartUploads {
+    #[serde(rename = "Upload", default)]
+    uploads: Vec<S3Upload>,
+    #[serde(rename = "IsTruncated", default)]
+    is_truncated: bool,
+    #[serde(rename = "NextKeyMarker", default)]
+    next_key_marker: Option<String>,
+    #[serde(rename = "NextUploadIdMarker", default)]
+    next_upload_id_marker: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct S3Upload {
+    #[serde(rename = "Key")]
+    key: String,
+    #[serde(rename = "UploadId")]
+    upload_id: String,
+    #[serde(rename = "Initiated")]
+    initiated: String,
+}
+
+/// List a page of the multipart uploads in progress under `prefix`, after the `marker` key and
+/// upload ID if it's set.
+async fn list_uploads(
+    store: &RegionAwareS3,
+    prefix: Option<&str>,
+    marker: Option<&(String, String)>,
+) -> object_store::Result<S3ListMultipartUploads> {
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let mut url = format!("{bucket_url}/?uploads");
+    if let Some(prefix) = prefix {
+        url.push_str(&format!("&prefix={}", uri_encode(prefix)));
+    }
+    if let Some((key, upload_id)) = marker {
+        url.push_str(&format!(
+            "&key-marker={}&upload-id-marker={}",
+            uri_encode(key),
+            uri_encode(upload_id)
+        ));
+    }
+    let url = Url::parse(&url).map_err(|err| object_store::Error::Generic {
+        store: S3,
+        source: Box::new(err),
+    })?;
+    let response = signed_request(&credential, Method::GET, url, &region, "s3", vec![], vec![])
+        .send()
+        .await
+        .map_err(|err| request_error(S3, err))?;
+    parse_xml(&check_response(S3, response).await?)
+}
+
+/// Abort the multipart upload `upload_id` of the listed `key`.
+///
+/// The key is used as listed rather than as a [`Path`][object_store::path::Path], which would
+/// normalize keys written by other clients, e.g. with empty segments, and abort nothing.
+async fn abort_upload(
+    store: &RegionAwareS3,
+    key: &str,
+    upload_id: &str,
+) -> object_store::Result<()> {
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let key = key.split('/').map(uri_encode).collect::<Vec<_>>().join("/");
+    let url = Url::parse(&format!(
+        "{bucket_url}/{key}?uploadId={}",
+        uri_encode(upload_id)
+    ))
+    .map_err(|err| object_store::Error::Generic {
+        store: S3,
+        source: Box::new(err),
+    })?;
+    if !url.path().ends_with(&format!("/{key}")) {
+        // URLs resolve `.` and `..` segments, even when they're percent-encoded
+        return Err(object_store::Error::Generic {
+            store: S3,
+            source: format!("The key of upload {upload_id} can't be addressed in a URL").into(),
+        });
+    }
+    let response = signed_request(
+        &credential,
+        Method::DELETE,
+        url,
+        &region,
+        "s3",
+        vec![],
+        vec![],
+    )
+    .send()
+    .await
+    .map_err(|err| request_error(S3, err))?;
+    check_response(S3, response).await?;
+    Ok(())
+}
+
+/// What the janitor has done so far.
+#[derive(Debug, Default)]
+struct Progress {
+    sweeps: usize,
+    aborted: usize,
+    last_error: Option<String>,
+}
+
+struct Janitor {
+    stop: Notify,
+    task: Mutex<Option<JoinHandle<()>>>,
+    progress: Mutex<Progress>,
+}
+
+impl Janitor {
+    /// Abort the uploads under `prefix` initiated more than `older_than` ago.
+    ///
+    /// An upload that can't be aborted doesn't stop the sweep, which returns the error of the
+    /// last one that couldn't.
+    async fn sweep(
+        &self,
+        store: &RegionAwareS3,
+        prefix: Option<&str>,
+        older_than: TimeDelta,
+    ) -> object_store::Result<()> {
+        let stale = Utc::now() - older_than;
+        let mut marker = None;
+        let mut failed = None;
+        loop {
+            let page = list_uploads(store, prefix, marker.as_ref()).await?;
+            for upload in page.uploads {
+                let initiated = match DateTime::parse_from_rfc3339(&upload.initiated) {
+                    Ok(initiated) => initiated,
+                    Err(err) => {
+                        failed = Some(object_store::Error::Generic {
+                            store: S3,
+                            source: Box::new(err),
+                        });
+                        continue;
+                    }
+                };
+                if initiated >= stale {
+                    continue;
+                }
+                match abort_upload(store, &upload.key, &upload.upload_id).await {
+                    Ok(()) => self.progress.lock().unwrap().aborted += 1,
+                    // Completed or aborted since it was listed
+                    Err(object_store::Error::NotFound { .. }) => {}
+                    Err(err) => failed = Some(err),
+                }
+            }
+            marker = match (page.next_key_marker, page.next_upload_id_marker) {
+                (Some(key), Some(upload_id)) if page.is_truncated && !key.is_empty() => {
+                    Some((key, upload_id))
+                }
+                _ => return failed.map_or(Ok(()), Err),
+            };
+        }
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        store: Arc<RegionAwareS3>,
+        prefix: Option<String>,
+        older_than: TimeDelta,
+        interval: Duration,
+    ) {
+        loop {
+            tokio::select! {
+                _ = self.stop.notified() => return,
+                result = self.sweep(&store, prefix.as_deref(), older_than) => {
+                    let mut progress = self.progress.lock().unwrap();
+                    progress.sweeps += 1;
+                    // Kept running, as the error may be transient
+                    progress.last_error = result.err().map(|err| err.to_string());
+                }
+            }
+            tokio::select! {
+                _ = self.stop.notified() => return,
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+
+    /// Stop the task, returning it to wait on unless it has already been stopped.
+    fn stop(&self) -> Option<JoinHandle<()>> {
+        let task = self.task.lock().unwrap().take()?;
+        self.stop.notify_one();
+        Some(task)
+    }
+}
+
+impl Finalize for Janitor {
+    fn finalize(self: Arc<Self>) -> BoxFuture<'static, Finalized> {
+        Box::pin(async move {
+            if let Some(task) = self.stop() {
+                let _ = task.await;
+            }
+            Finalized::Closed
+        })
+    }
+}
+
+/// A handle to the task started by `start_multipart_janitor`.
+#[pyclass(name = "MultipartJanitor", frozen)]
+pub(crate) struct PyMultipartJanitor(Arc<Janitor>);
+
+#[pymethods]
+impl PyMultipartJanitor {
+    #[getter]
+    fn running(&self) -> bool {
+        self.0
+            .task
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+
+    #[getter]
+    fn sweeps(&self) -> usize {
+        self.0.progress.lock().unwrap().sweeps
+    }
+
+    #[getter]
+    fn aborted(&self) -> usize {
+        self.0.progress.lock().unwrap().aborted
+    }
+
+    #[getter]
+    fn last_error(&self) -> Option<String> {
+        self.0.progress.lock().unwrap().last_error.clone()
+    }
+
+    fn stop(&self, py: Python) -> PyObjectStoreResult<()> {
+        let Some(task) = self.0.stop() else {
+            return Ok(());
+        };
+        let runtime = get_runtime(py)?;
+        py.allow_threads(|| runtime.block_on(task))
+            .map_err(io::Error::other)?;
+        Ok(())
+    }
+
+    fn stop_async<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let task = self.0.stop();
+        future_into_py(py, async move {
+            if let Some(task) = task {
+                task.await.map_err(io::Error::other)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, older_than, interval))]
+pub(crate) fn start_multipart_janitor(
+    py: Python,
+    store: &Bound<PyS3Store>,
+    prefix: Option<String>,
+    older_than: TimeDelta,
+    interval: Duration,
+) -> PyResult<PyMultipartJanitor> {
+    if interval.is_zero() {
+        return Err(PyValueError::new_err("interval must be greater than 0"));
+    }
+    let store = store.borrow().region_aware().clone();
+    let runtime = get_runtime(py)?;
+    let janitor = Arc::new(Janitor {
+        stop: Notify::new(),
+        task: Mutex::new(None),
+        progress: Mutex::new(Progress::default()),
+    });
+    let task = runtime.spawn(janitor.clone().run(store, prefix, older_than, interval));
+    *janitor.task.lock().unwrap() = Some(task);
+    let weak = Arc::downgrade(&janitor);
+    register_writer(weak);
+    Ok(PyMultipartJanitor(janitor))
+}
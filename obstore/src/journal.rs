@@ -0,0 +1,501 @@
+//! A local journal of in-progress multipart uploads, so that the uploads left behind by a crash
+//! can be aborted when the process restarts.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use object_store::multipart::{MultipartStore, PartId};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartId, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult, UploadPart,
+};
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3_object_store::{
+    PyAzureStore, PyGCSStore, PyObjectStore, PyObjectStoreResult, PyS3Store, RegionAwareS3,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The journal that uploads are recorded to, if one has been set with `set_journal`.
+static JOURNAL: Mutex<Option<Journal>> = Mutex::new(None);
+
+/// An upload recorded in a journal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Entry {
+    /// The URL of the bucket or container, such as `s3://bucket`
+    store: String,
+    path: String,
+    upload_id: MultipartId,
+}
+
+/// A line of a journal.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+enum Record {
+    Begin(Entry),
+    End(Entry),
+}
+
+struct Journal {
+    path: PathBuf,
+    file: File,
+}
+
+impl Journal {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file })
+    }
+
+    /// Append records, syncing them to disk before returning.
+    fn append(&mut self, records: &[Record]) -> std::io::Result<()> {
+        let mut lines = vec![];
+        for record in records {
+            serde_json::to_writer(&mut lines, record)?;
+            lines.push(b'\n');
+        }
+        self.file.write_all(&lines)?;
+        self.file.sync_data()
+    }
+}
+
+/// Append records to the journal, if one has been set.
+///
+/// Syncing blocks, with the journal locked, so it's done on a blocking thread rather than on
+/// the runtime.
+async fn append_to_journal(records: Vec<Record>) -> std::io::Result<()> {
+    tokio::task::spawn_blocking(move || match JOURNAL.lock().unwrap().as_mut() {
+        Some(journal) => journal.append(&records),
+        None => Ok(()),
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/// Read the entries of the journal at `path` that were begun but never ended.
+fn read_pending(path: &std::path::Path) -> PyResult<Vec<Entry>> {
+    let file = File::open(path)
+        .map_err(|err| PyIOError::new_err(format!("Could not open journal: {err}")))?;
+    let mut pending = vec![];
+    let mut ended = HashSet::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Record>(&line) {
+            Ok(Record::Begin(entry)) => pending.push(entry),
+            Ok(Record::End(entry)) => {
+                ended.insert(entry);
+            }
+            // A crash can leave the last line partially written
+            Err(_) if line.ends_with('}') => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid journal record on line {}",
+                    i + 1
+                )))
+            }
+            Err(_) => {}
+        }
+    }
+    pending.retain(|entry| !ended.contains(entry));
+    Ok(pending)
+}
+
+/// The multipart uploads of a bucket or container, which are recorded in the journal as they
+/// begin if one has been set.
+///
+/// Uploads are made with the client of the bucket or container by their ID, so they bypass
+/// the layers an extracted store is wrapped in: they don't count towards its metrics, their
+/// errors don't name the store or carry permission hints, and an S3 bucket in another region
+/// than the store's isn't followed.
+#[derive(Clone)]
+pub(crate) struct Uploads {
+    store: Arc<dyn MultipartStore>,
+    /// The URL of the bucket or container, which identifies its uploads in the journal
+    key: String,
+}
+
+// `MultipartStore` doesn't require `Debug`
+impl std::fmt::Debug for Uploads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Uploads").field("key", &self.key).finish()
+    }
+}
+
+impl Uploads {
+    /// The uploads of the bucket of an S3 store.
+    pub(crate) fn s3(store: &RegionAwareS3) -> Self {
+        Self {
+            store: store.current(),
+            key: format!("s3://{}", store.bucket()),
+        }
+    }
+
+    /// The store the uploads are made to.
+    pub(crate) fn store(&self) -> &Arc<dyn MultipartStore> {
+        &self.store
+    }
+
+    /// Start a multipart upload, recording it in the journal first.
+    ///
+    /// This is for uploads whose parts aren't written through a [`MultipartUpload`], such as
+    /// part copies. The returned upload must be ended once it's completed or aborted.
+    pub(crate) async fn create(&self, path: &Path) -> object_store::Result<PendingUpload> {
+        let upload_id = self.store.create_multipart(path).await?;
+        if JOURNAL.lock().unwrap().is_none() {
+            return Ok(PendingUpload {
+                upload_id,
+                entry: None,
+            });
+        }
+        let entry = Entry {
+            store: self.key.clone(),
+            path: path.to_string(),
+            upload_id: upload_id.clone(),
+        };
+        if let Err(err) = append_to_journal(vec![Record::Begin(entry.clone())]).await {
+            // The upload can't be recovered, so don't leave it behind
+            let _ = self.store.abort_multipart(path, &upload_id).await;
+            return Err(object_store::Error::Generic {
+                store: "Journal",
+                source: Box::new(err),
+            });
+        }
+        Ok(PendingUpload {
+            upload_id,
+            entry: Some(entry),
+        })
+    }
+}
+
+/// A multipart upload started by [`Uploads::create`].
+#[derive(Debug)]
+pub(crate) struct PendingUpload {
+    pub(crate) upload_id: MultipartId,
+    /// The journal entry of the upload, if it was journaled
+    entry: Option<Entry>,
+}
+
+impl PendingUpload {
+    /// End the journal entry of the upload, once it has been completed or aborted.
+    pub(crate) async fn end(&self) {
+        // The upload is done either way, and `recover` skips uploads that no longer exist
+        if let Some(entry) = &self.entry {
+            let _ = append_to_journal(vec![Record::End(entry.clone())]).await;
+        }
+    }
+}
+
+/// A store whose multipart uploads are journaled, if a journal has been set and the store
+/// exposes the IDs of its uploads.
+///
+/// Only [`PyS3Store`], [`PyGCSStore`] and [`PyAzureStore`] do. Uploads to any other store,
+/// including middleware wrapping one of these or a `GCSStore` with a prefix, aren't
+/// journaled. Nor are uploads with attributes or tags, which can't be set on uploads started
+/// by their ID.
+pub(crate) struct JournaledStore {
+    store: Arc<dyn ObjectStore>,
+    uploads: Option<Uploads>,
+}
+
+impl<'py> FromPyObject<'py> for JournaledStore {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let uploads = if let Ok(store) = ob.downcast::<PyS3Store>() {
+            Some(Uploads::s3(store.get().region_aware()))
+        } else if let Ok(store) = ob.downcast::<PyGCSStore>() {
+            // Parts are uploaded by their path in the bucket, without the prefix
+            let store = store.get();
+            store.prefix().is_none().then(|| Uploads {
+                store: store.as_ref().clone(),
+                key: format!("gs://{}", store.bucket()),
+            })
+        } else if let Ok(store) = ob.downcast::<PyAzureStore>() {
+            let store = store.get();
+            Some(Uploads {
+                store: store.as_ref().clone(),
+                key: store.base_url().to_string(),
+            })
+        } else {
+            None
+        };
+        let store = ob.extract::<PyObjectStore>()?.into_inner();
+        let store: Arc<dyn ObjectStore> = match &uploads {
+            Some(uploads) => Arc::new(JournalingStore {
+                inner: store,
+                uploads: uploads.clone(),
+            }),
+            None => store,
+        };
+        Ok(Self { store, uploads })
+    }
+}
+
+impl JournaledStore {
+    /// The store, which records the multipart uploads started through it in the journal.
+    pub(crate) fn into_inner(self) -> Arc<dyn ObjectStore> {
+        self.store
+    }
+
+    /// The uploads of the store, if it exposes their IDs.
+    pub(crate) fn uploads(&self) -> Option<&Uploads> {
+        self.uploads.as_ref()
+    }
+}
+
+/// A store that records the multipart uploads started through it in the journal.
+#[derive(Debug)]
+struct JournalingStore {
+    inner: Arc<dyn ObjectStore>,
+    uploads: Uploads,
+}
+
+impl Display for JournalingStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "JournalingStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for JournalingStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let journaled = JOURNAL.lock().unwrap().is_some();
+        if !journaled || opts != PutMultipartOpts::default() {
+            return self.inner.put_multipart_opts(location, opts).await;
+        }
+        let upload = self.uploads.create(location).await?;
+        Ok(Box::new(JournaledUpload {
+            uploads: self.uploads.clone(),
+            path: location.clone(),
+            upload,
+            parts: Default::default(),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.inner.get_ranges(location, ranges).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+/// A multipart upload that ends its journal entry once it has been completed or aborted.
+#[derive(Debug)]
+struct JournaledUpload {
+    uploads: Uploads,
+    path: Path,
+    upload: PendingUpload,
+    /// The IDs of the parts, in order, once they have been uploaded
+    parts: Arc<Mutex<Vec<Option<PartId>>>>,
+}
+
+#[async_trait]
+impl MultipartUpload for JournaledUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let index = {
+            let mut parts = self.parts.lock().unwrap();
+            parts.push(None);
+            parts.len() - 1
+        };
+        let store = self.uploads.store.clone();
+        let path = self.path.clone();
+        let upload_id = self.upload.upload_id.clone();
+        let parts = self.parts.clone();
+        Box::pin(async move {
+            let part = store.put_part(&path, &upload_id, index, data).await?;
+            parts.lock().unwrap()[index] = Some(part);
+            Ok(())
+        })
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        let parts = self
+            .parts
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| object_store::Error::Generic {
+                store: "Journal",
+                source: "Cannot complete an upload before all of its parts are uploaded".into(),
+            })?;
+        let result = self
+            .uploads
+            .store
+            .complete_multipart(&self.path, &self.upload.upload_id, parts)
+            .await?;
+        self.upload.end().await;
+        Ok(result)
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.uploads
+            .store
+            .abort_multipart(&self.path, &self.upload.upload_id)
+            .await?;
+        self.upload.end().await;
+        Ok(())
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (path, /))]
+pub(crate) fn set_journal(path: Option<PathBuf>) -> PyResult<()> {
+    let journal = path
+        .map(Journal::open)
+        .transpose()
+        .map_err(|err| PyIOError::new_err(format!("Could not open journal: {err}")))?;
+    *JOURNAL.lock().unwrap() = journal;
+    Ok(())
+}
+
+async fn recover_inner(
+    store: JournaledStore,
+    journal_path: PathBuf,
+) -> PyObjectStoreResult<Vec<String>> {
+    let Uploads {
+        store: multipart,
+        key,
+    } = store.uploads.ok_or_else(|| {
+        PyValueError::new_err("Only S3Store, GCSStore and AzureStore uploads can be recovered")
+    })?;
+    let pending = read_pending(&journal_path)?;
+
+    let journal = match JOURNAL.lock().unwrap().as_ref() {
+        // Append through the active journal's file, rather than a second handle to it
+        Some(journal) if journal.path == journal_path => None,
+        _ => Some(Journal::open(journal_path)?),
+    };
+    let mut aborted = vec![];
+    let mut ended = vec![];
+    let mut failed = None;
+    for entry in pending.into_iter().filter(|entry| entry.store == key) {
+        let path = Path::from(entry.path.as_str());
+        match multipart.abort_multipart(&path, &entry.upload_id).await {
+            Ok(()) => aborted.push(entry.path.clone()),
+            // Completed or aborted after its last journal record was written
+            Err(object_store::Error::NotFound { .. }) => {}
+            Err(err) => {
+                failed = Some(err);
+                break;
+            }
+        }
+        ended.push(Record::End(entry));
+    }
+    // Ended even if an abort failed, so that the uploads aborted before it aren't recovered
+    // again
+    match journal {
+        Some(mut journal) => tokio::task::spawn_blocking(move || journal.append(&ended))
+            .await
+            .map_err(std::io::Error::other)??,
+        None => append_to_journal(ended).await?,
+    }
+    match failed {
+        Some(err) => Err(err.into()),
+        None => Ok(aborted),
+    }
+}
+
+#[pyfunction]
+pub(crate) fn recover(
+    py: Python,
+    journal_path: PathBuf,
+    store: JournaledStore,
+) -> PyObjectStoreResult<Vec<String>> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(recover_inner(store, journal_path)))
+}
+
+#[pyfunction]
+pub(crate) fn recover_async(
+    py: Python,
+    journal_path: PathBuf,
+    store: JournaledStore,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(
+        py,
+        async move { Ok(recover_inner(store, journal_path).await?) },
+    )
+}
@@ -0,0 +1,220 @@
+//! Leases on the paths written with `open_writer`, so that a second writer of a path fails when
+//! it opens the path, rather than the two racing to complete their uploads.
+//!
+//! A lease is a lock object next to the path, created only if it doesn't exist and refreshed by a
+//! heartbeat until the writer closes. A lease whose holder stopped refreshing it, such as because
+//! it crashed, can be taken over once it has expired, in stores with conditional updates.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::DateTime;
+use object_store::path::Path;
+use object_store::{ObjectStore, PutMode, PutOptions, PutPayload, PutResult, UpdateVersion};
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+use pyo3_object_store::clock;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+/// The suffix of the lock object of a path.
+const SUFFIX: &str = ".lease";
+
+/// The contents of a lock object.
+#[derive(Serialize, Deserialize)]
+struct LeaseRecord {
+    /// Unix timestamp in milliseconds, by the clock of the holder
+    expires_at: i64,
+}
+
+fn record(duration: Duration) -> PutPayload {
+    let expires_at = clock::now().timestamp_millis() + duration.as_millis() as i64;
+    serde_json::to_vec(&LeaseRecord { expires_at })
+        .expect("lease record serializes")
+        .into()
+}
+
+fn held(
+    lock: &Path,
+    reason: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> object_store::Error {
+    object_store::Error::AlreadyExists {
+        path: lock.to_string(),
+        source: reason.into(),
+    }
+}
+
+fn update(result: &PutResult) -> PutOptions {
+    PutMode::Update(UpdateVersion {
+        e_tag: result.e_tag.clone(),
+        version: result.version.clone(),
+    })
+    .into()
+}
+
+/// The state of a lease, shared with its heartbeat.
+struct Held {
+    /// The last write of the lock object
+    last: PutResult,
+    /// Set by the heartbeat once another writer has taken over the lease
+    lost: bool,
+}
+
+/// A lease on a path, held until released or, if the holder is dropped, until it expires.
+pub(crate) struct Lease {
+    store: Arc<dyn ObjectStore>,
+    lock: Path,
+    heartbeat: JoinHandle<()>,
+    held: Arc<Mutex<Held>>,
+}
+
+impl Lease {
+    /// Acquire a lease on `path` lasting `duration` between heartbeats, failing with
+    /// `AlreadyExists` if another writer holds it.
+    pub(crate) async fn acquire(
+        store: Arc<dyn ObjectStore>,
+        path: &Path,
+        duration: Duration,
+    ) -> object_store::Result<Self> {
+        let lock = Path::from(format!("{path}{SUFFIX}"));
+        let create = PutMode::Create.into();
+        let result = match store.put_opts(&lock, record(duration), create).await {
+            Ok(result) => result,
+            Err(object_store::Error::AlreadyExists { .. }) => {
+                take_over(store.as_ref(), &lock, duration).await?
+            }
+            Err(err) => return Err(err),
+        };
+        let held = Arc::new(Mutex::new(Held {
+            last: result,
+            lost: false,
+        }));
+        // Spawned on the runtime acquiring the lease, which outlives the writer
+        let heartbeat = tokio::spawn(heartbeat(
+            store.clone(),
+            lock.clone(),
+            duration,
+            held.clone(),
+        ));
+        Ok(Self {
+            store,
+            lock,
+            heartbeat,
+            held,
+        })
+    }
+
+    /// Fail if another writer has taken over the lease, so that nothing more is written under
+    /// it.
+    pub(crate) fn check(&self) -> PyResult<()> {
+        if self.held.lock().unwrap().lost {
+            return Err(PyIOError::new_err(format!(
+                "The lease {} was taken over by another writer",
+                self.lock
+            )));
+        }
+        Ok(())
+    }
+
+    /// Stop refreshing the lease and delete its lock object, unless another writer has taken it
+    /// over.
+    pub(crate) async fn release(self) -> object_store::Result<()> {
+        self.heartbeat.abort();
+        let (last, lost) = {
+            let held = self.held.lock().unwrap();
+            (held.last.clone(), held.lost)
+        };
+        if lost {
+            return Ok(());
+        }
+        // Taken over since the last heartbeat, if the lock object has been written since
+        match self.store.head(&self.lock).await {
+            Ok(meta) if last.e_tag.is_some() && meta.e_tag != last.e_tag => return Ok(()),
+            Ok(_) => {}
+            Err(object_store::Error::NotFound { .. }) => return Ok(()),
+            Err(err) => return Err(err),
+        }
+        self.store.delete(&self.lock).await
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        // Left to expire, if not released
+        self.heartbeat.abort();
+    }
+}
+
+/// Take over the lease `lock` if its holder let it expire.
+async fn take_over(
+    store: &dyn ObjectStore,
+    lock: &Path,
+    duration: Duration,
+) -> object_store::Result<PutResult> {
+    let current = match store.get(lock).await {
+        Ok(current) => current,
+        // Released since it was found
+        Err(object_store::Error::NotFound { .. }) => {
+            return Err(held(lock, "The lease was released while being taken over"))
+        }
+        Err(err) => return Err(err),
+    };
+    let meta = current.meta.clone();
+    let bytes = current.bytes().await?;
+    let Ok(held_by) = serde_json::from_slice::<LeaseRecord>(&bytes) else {
+        return Err(held(lock, "The lock object isn't a lease"));
+    };
+    if clock::now().timestamp_millis() < held_by.expires_at {
+        let expires_at = DateTime::from_timestamp_millis(held_by.expires_at)
+            .map_or_else(|| held_by.expires_at.to_string(), |at| at.to_rfc3339());
+        return Err(held(
+            lock,
+            format!("The lease is held by another writer until {expires_at}"),
+        ));
+    }
+    let options = update(&PutResult {
+        e_tag: meta.e_tag,
+        version: meta.version,
+    });
+    match store.put_opts(lock, record(duration), options).await {
+        Ok(result) => Ok(result),
+        Err(object_store::Error::Precondition { .. }) => Err(held(
+            lock,
+            "The expired lease was taken over by another writer",
+        )),
+        Err(object_store::Error::NotImplemented) => Err(held(
+            lock,
+            "The lease has expired, but the store can't take it over; delete it instead",
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// Refresh the lease `lock` every third of its duration, until aborted or taken over.
+async fn heartbeat(
+    store: Arc<dyn ObjectStore>,
+    lock: Path,
+    duration: Duration,
+    held: Arc<Mutex<Held>>,
+) {
+    // Stores without conditional updates can't take over leases, so they're overwritten there
+    let mut conditional = true;
+    loop {
+        tokio::time::sleep(duration / 3).await;
+        let options = if conditional {
+            update(&held.lock().unwrap().last)
+        } else {
+            PutOptions::default()
+        };
+        match store.put_opts(&lock, record(duration), options).await {
+            Ok(result) => held.lock().unwrap().last = result,
+            Err(object_store::Error::NotImplemented) => conditional = false,
+            Err(object_store::Error::Precondition { .. }) => {
+                held.lock().unwrap().lost = true;
+                return;
+            }
+            // Retried at the next heartbeat, before the lease expires
+            Err(_) => {}
+        }
+    }
+}
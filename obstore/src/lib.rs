@@ -1,18 +1,53 @@
 use pyo3::prelude::*;
 
+mod alias;
 mod attributes;
+mod bucket;
 mod buffered;
+mod cdn;
+mod clock;
+mod conformance;
 mod copy;
+mod dedup;
 mod delete;
+mod delta;
+mod diff;
+mod duplicates;
+mod events;
+mod gc;
 mod get;
+mod gzip;
+mod hash;
 mod head;
+mod janitor;
+mod journal;
+mod lease;
 mod list;
+mod manifest;
+mod metadata;
+mod multipart;
+mod ndjson;
+mod patch;
 mod path;
+mod probe;
+mod public;
 mod put;
+mod quirks;
+mod ranges;
+mod read_cache;
+mod remote;
 mod rename;
+mod rolling;
 mod runtime;
+mod shutdown;
 mod signer;
+mod sigv4;
+mod sink;
+mod snapshot;
+mod sparse;
+mod stats;
 mod tags;
+mod versions;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -50,30 +85,160 @@ fn _obstore(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
 
     pyo3_object_store::register_store_module(py, m, "obstore")?;
     pyo3_object_store::register_exceptions_module(py, m, "obstore")?;
+    pyo3_object_store::register_metrics_module(py, m, "obstore")?;
 
+    // Classes of returned objects, exported for use in type annotations
+    m.add_class::<buffered::PyReadableFile>()?;
+    m.add_class::<buffered::PyWritableFile>()?;
+    m.add_class::<get::PyBytesStream>()?;
+    m.add_class::<get::PyGetManyStream>()?;
+    m.add_class::<get::PyGetResult>()?;
+    m.add_class::<janitor::PyMultipartJanitor>()?;
+    m.add_class::<list::PyListStream>()?;
+    m.add_class::<put::PyPutResult>()?;
+    m.add_class::<pyo3_bytes::PyBytes>()?;
+    m.add_class::<pyo3_bytes::PyBytesReader>()?;
+
+    m.add_wrapped(wrap_pyfunction!(bucket::get_bucket_cors_async))?;
+    m.add_wrapped(wrap_pyfunction!(bucket::get_bucket_cors))?;
+    m.add_wrapped(wrap_pyfunction!(bucket::get_lifecycle_rules_async))?;
+    m.add_wrapped(wrap_pyfunction!(bucket::get_lifecycle_rules))?;
+    m.add_wrapped(wrap_pyfunction!(bucket::put_bucket_cors_async))?;
+    m.add_wrapped(wrap_pyfunction!(bucket::put_bucket_cors))?;
+    m.add_wrapped(wrap_pyfunction!(bucket::put_lifecycle_rules_async))?;
+    m.add_wrapped(wrap_pyfunction!(bucket::put_lifecycle_rules))?;
     m.add_wrapped(wrap_pyfunction!(buffered::open))?;
     m.add_wrapped(wrap_pyfunction!(buffered::open_async))?;
+    m.add_wrapped(wrap_pyfunction!(buffered::open_writer))?;
+    m.add_wrapped(wrap_pyfunction!(buffered::open_writer_async))?;
+    m.add_wrapped(wrap_pyfunction!(journal::recover_async))?;
+    m.add_wrapped(wrap_pyfunction!(journal::recover))?;
+    m.add_wrapped(wrap_pyfunction!(journal::set_journal))?;
+    m.add_wrapped(wrap_pyfunction!(janitor::start_multipart_janitor))?;
+    m.add_wrapped(wrap_pyfunction!(manifest::run_manifest_async))?;
+    m.add_wrapped(wrap_pyfunction!(manifest::run_manifest))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::abort_multipart_async))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::abort_multipart))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::complete_multipart_async))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::complete_multipart))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::create_multipart_async))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::create_multipart))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::open_multipart_writer_async))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::open_multipart_writer))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::put_part_async))?;
+    m.add_wrapped(wrap_pyfunction!(multipart::put_part))?;
+    m.add_wrapped(wrap_pyfunction!(sparse::open_sparse_writer))?;
+    m.add_wrapped(wrap_pyfunction!(sparse::open_sparse_writer_async))?;
+    m.add_wrapped(wrap_pyfunction!(rolling::open_rolling_writer_async))?;
+    m.add_wrapped(wrap_pyfunction!(rolling::open_rolling_writer))?;
+    m.add_wrapped(wrap_pyfunction!(sink::open_record_sink_async))?;
+    m.add_wrapped(wrap_pyfunction!(sink::open_record_sink))?;
+    m.add_wrapped(wrap_pyfunction!(cdn::invalidate_async))?;
+    m.add_wrapped(wrap_pyfunction!(cdn::invalidate))?;
+    m.add_wrapped(wrap_pyfunction!(cdn::sign_cdn_url))?;
+    m.add_wrapped(wrap_pyfunction!(clock::advance_clock))?;
+    m.add_wrapped(wrap_pyfunction!(clock::seed_jitter))?;
+    m.add_wrapped(wrap_pyfunction!(clock::set_clock))?;
+    m.add_wrapped(wrap_pyfunction!(conformance::check_store_conformance_async))?;
+    m.add_wrapped(wrap_pyfunction!(conformance::check_store_conformance))?;
     m.add_wrapped(wrap_pyfunction!(copy::copy_async))?;
     m.add_wrapped(wrap_pyfunction!(copy::copy))?;
+    m.add_wrapped(wrap_pyfunction!(dedup::get_dedup_async))?;
+    m.add_wrapped(wrap_pyfunction!(dedup::get_dedup))?;
+    m.add_wrapped(wrap_pyfunction!(dedup::put_dedup_async))?;
+    m.add_wrapped(wrap_pyfunction!(dedup::put_dedup))?;
     m.add_wrapped(wrap_pyfunction!(delete::delete_async))?;
     m.add_wrapped(wrap_pyfunction!(delete::delete))?;
+    m.add_wrapped(wrap_pyfunction!(delete::delete_prefix_async))?;
+    m.add_wrapped(wrap_pyfunction!(delete::delete_prefix))?;
+    m.add_wrapped(wrap_pyfunction!(delete::purge_trash_async))?;
+    m.add_wrapped(wrap_pyfunction!(delete::purge_trash))?;
+    m.add_wrapped(wrap_pyfunction!(delta::put_delta_async))?;
+    m.add_wrapped(wrap_pyfunction!(delta::put_delta))?;
+    m.add_wrapped(wrap_pyfunction!(diff::diff_objects_async))?;
+    m.add_wrapped(wrap_pyfunction!(diff::diff_objects))?;
+    m.add_wrapped(wrap_pyfunction!(duplicates::find_duplicates_async))?;
+    m.add_wrapped(wrap_pyfunction!(duplicates::find_duplicates))?;
+    m.add_wrapped(wrap_pyfunction!(events::disable_tracing))?;
+    m.add_wrapped(wrap_pyfunction!(events::enable_tracing))?;
+    m.add_wrapped(wrap_pyfunction!(events::set_retry_hook))?;
+    m.add_wrapped(wrap_pyfunction!(gc::gc_async))?;
+    m.add_wrapped(wrap_pyfunction!(gc::gc))?;
     m.add_wrapped(wrap_pyfunction!(get::get_async))?;
+    m.add_wrapped(wrap_pyfunction!(get::get_many))?;
     m.add_wrapped(wrap_pyfunction!(get::get_range_async))?;
     m.add_wrapped(wrap_pyfunction!(get::get_range))?;
     m.add_wrapped(wrap_pyfunction!(get::get_ranges_async))?;
     m.add_wrapped(wrap_pyfunction!(get::get_ranges))?;
     m.add_wrapped(wrap_pyfunction!(get::get))?;
+    m.add_wrapped(wrap_pyfunction!(gzip::create_gzip_index_async))?;
+    m.add_wrapped(wrap_pyfunction!(gzip::create_gzip_index))?;
+    m.add_wrapped(wrap_pyfunction!(gzip::get_gzip_range_async))?;
+    m.add_wrapped(wrap_pyfunction!(gzip::get_gzip_range))?;
+    m.add_wrapped(wrap_pyfunction!(gzip::read_gzip_index_async))?;
+    m.add_wrapped(wrap_pyfunction!(gzip::read_gzip_index))?;
+    m.add_wrapped(wrap_pyfunction!(hash::crc32c))?;
+    m.add_wrapped(wrap_pyfunction!(hash::md5))?;
+    m.add_wrapped(wrap_pyfunction!(hash::xxh3))?;
     m.add_wrapped(wrap_pyfunction!(head::head_async))?;
     m.add_wrapped(wrap_pyfunction!(head::head))?;
+    m.add_wrapped(wrap_pyfunction!(head::warm_up_async))?;
+    m.add_wrapped(wrap_pyfunction!(head::warm_up))?;
     m.add_wrapped(wrap_pyfunction!(list::list_with_delimiter_async))?;
     m.add_wrapped(wrap_pyfunction!(list::list_with_delimiter))?;
     m.add_wrapped(wrap_pyfunction!(list::list))?;
+    m.add_wrapped(wrap_pyfunction!(list::total_size_async))?;
+    m.add_wrapped(wrap_pyfunction!(list::total_size))?;
+    m.add_wrapped(wrap_pyfunction!(metadata::apply_metadata_async))?;
+    m.add_wrapped(wrap_pyfunction!(metadata::apply_metadata))?;
+    m.add_wrapped(wrap_pyfunction!(ndjson::list_to_ndjson_async))?;
+    m.add_wrapped(wrap_pyfunction!(ndjson::list_to_ndjson))?;
+    m.add_wrapped(wrap_pyfunction!(patch::patch_range_async))?;
+    m.add_wrapped(wrap_pyfunction!(patch::patch_range))?;
+    m.add_wrapped(wrap_pyfunction!(path::parse_object_url))?;
+    m.add_wrapped(wrap_pyfunction!(probe::diagnose_permissions_async))?;
+    m.add_wrapped(wrap_pyfunction!(probe::diagnose_permissions))?;
+    m.add_wrapped(wrap_pyfunction!(probe::probe_async))?;
+    m.add_wrapped(wrap_pyfunction!(probe::probe))?;
+    m.add_wrapped(wrap_pyfunction!(probe::set_permission_hints))?;
+    m.add_wrapped(wrap_pyfunction!(public::open_public))?;
     m.add_wrapped(wrap_pyfunction!(put::put_async))?;
     m.add_wrapped(wrap_pyfunction!(put::put))?;
+    m.add_wrapped(wrap_pyfunction!(put::put_and_confirm_async))?;
+    m.add_wrapped(wrap_pyfunction!(put::put_and_confirm))?;
+    m.add_wrapped(wrap_pyfunction!(put::put_many_async))?;
+    m.add_wrapped(wrap_pyfunction!(put::put_many))?;
+    m.add_wrapped(wrap_pyfunction!(quirks::register_quirks))?;
+    m.add_wrapped(wrap_pyfunction!(ranges::plan_ranges))?;
+    m.add_wrapped(wrap_pyfunction!(remote::mirror_http_async))?;
+    m.add_wrapped(wrap_pyfunction!(remote::mirror_http))?;
+    m.add_wrapped(wrap_pyfunction!(remote::put_from_url_async))?;
+    m.add_wrapped(wrap_pyfunction!(remote::put_from_url))?;
+    m.add_wrapped(wrap_pyfunction!(alias::put_alias_async))?;
+    m.add_wrapped(wrap_pyfunction!(alias::put_alias))?;
+    m.add_wrapped(wrap_pyfunction!(alias::read_pointer_async))?;
+    m.add_wrapped(wrap_pyfunction!(alias::read_pointer))?;
+    m.add_wrapped(wrap_pyfunction!(alias::resolve_alias_async))?;
+    m.add_wrapped(wrap_pyfunction!(alias::resolve_alias))?;
+    m.add_wrapped(wrap_pyfunction!(alias::set_pointer_async))?;
+    m.add_wrapped(wrap_pyfunction!(alias::set_pointer))?;
     m.add_wrapped(wrap_pyfunction!(rename::rename_async))?;
     m.add_wrapped(wrap_pyfunction!(rename::rename))?;
     m.add_wrapped(wrap_pyfunction!(signer::sign_async))?;
     m.add_wrapped(wrap_pyfunction!(signer::sign))?;
+    m.add_wrapped(wrap_pyfunction!(shutdown::shutdown))?;
+    m.add_wrapped(wrap_pyfunction!(snapshot::get_pinned_async))?;
+    m.add_wrapped(wrap_pyfunction!(snapshot::get_pinned))?;
+    m.add_wrapped(wrap_pyfunction!(snapshot::snapshot_token_async))?;
+    m.add_wrapped(wrap_pyfunction!(snapshot::snapshot_token))?;
+    m.add_wrapped(wrap_pyfunction!(tags::get_tags_async))?;
+    m.add_wrapped(wrap_pyfunction!(tags::get_tags))?;
+    m.add_wrapped(wrap_pyfunction!(tags::put_tags_async))?;
+    m.add_wrapped(wrap_pyfunction!(tags::put_tags))?;
+    m.add_wrapped(wrap_pyfunction!(versions::get_at_async))?;
+    m.add_wrapped(wrap_pyfunction!(versions::get_at))?;
+    m.add_wrapped(wrap_pyfunction!(versions::undelete_async))?;
+    m.add_wrapped(wrap_pyfunction!(versions::undelete))?;
 
     Ok(())
 }
@@ -1,24 +1,31 @@
+use std::collections::HashMap;
 use std::ops::AddAssign;
 use std::sync::Arc;
+use std::time::Duration;
 
 use arrow::array::{
     ArrayRef, RecordBatch, StringBuilder, TimestampMicrosecondBuilder, UInt64Builder,
 };
 use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use chrono::{DateTime, Utc};
 use futures::stream::{BoxStream, Fuse};
 use futures::StreamExt;
 use indexmap::IndexMap;
 use object_store::path::Path;
 use object_store::{ListResult, ObjectMeta, ObjectStore};
-use pyo3::exceptions::{PyImportError, PyStopAsyncIteration, PyStopIteration};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use pyo3::exceptions::{
+    PyImportError, PyStopAsyncIteration, PyStopIteration, PyTimeoutError, PyValueError,
+};
 use pyo3::intern;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyList, PyString, PyType};
 use pyo3_arrow::PyRecordBatch;
 use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
 use tokio::sync::Mutex;
+use tokio::time::Instant;
 
-use crate::runtime::get_runtime;
+use crate::runtime::{future_into_py, get_runtime};
 
 pub(crate) struct PyObjectMeta(ObjectMeta);
 
@@ -34,27 +41,93 @@ impl AsRef<ObjectMeta> for PyObjectMeta {
     }
 }
 
+impl PyObjectMeta {
+    fn into_dict<'py>(
+        self,
+        py: Python<'py>,
+        values: &mut SharedValues<'py>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        // Listings can produce millions of these dicts, so use interned keys to share one Python
+        // string per key across all of them instead of allocating new key strings for each dict.
+        let dict = PyDict::new(py);
+        // Note, this uses "path" instead of "location" because we standardize the API to accept
+        // the keyword "path" everywhere.
+        dict.set_item(intern!(py, "path"), self.0.location.as_ref())?;
+        dict.set_item(
+            intern!(py, "last_modified"),
+            values.timestamp(py, self.0.last_modified)?,
+        )?;
+        dict.set_item(intern!(py, "size"), self.0.size)?;
+        dict.set_item(
+            intern!(py, "e_tag"),
+            self.0.e_tag.map(|e_tag| values.e_tag(py, e_tag)),
+        )?;
+        dict.set_item(intern!(py, "version"), self.0.version)?;
+        Ok(dict)
+    }
+}
+
 impl<'py> IntoPyObject<'py> for PyObjectMeta {
     type Target = PyDict;
     type Output = Bound<'py, PyDict>;
     type Error = PyErr;
 
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        let mut dict = IndexMap::with_capacity(5);
-        // Note, this uses "path" instead of "location" because we standardize the API to accept
-        // the keyword "path" everywhere.
-        dict.insert(
-            "path",
-            self.0.location.as_ref().into_pyobject(py)?.into_any(),
-        );
-        dict.insert(
-            "last_modified",
-            self.0.last_modified.into_pyobject(py)?.into_any(),
-        );
-        dict.insert("size", self.0.size.into_pyobject(py)?.into_any());
-        dict.insert("e_tag", self.0.e_tag.into_pyobject(py)?.into_any());
-        dict.insert("version", self.0.version.into_pyobject(py)?);
-        dict.into_pyobject(py)
+        self.into_dict(py, &mut SharedValues::default())
+    }
+}
+
+/// The Python objects made for the values of the objects converted together, so that equal
+/// values share one Python object.
+///
+/// Objects written in bulk often share a modification time, to the second on most stores, and
+/// objects with the same contents share an e-tag, so large listings hold far fewer distinct
+/// values than objects. Paths aren't shared: a Python `str` owns its characters, so paths with a
+/// common prefix can't share its storage, and paths within a listing never repeat.
+#[derive(Default)]
+struct SharedValues<'py> {
+    timestamps: HashMap<DateTime<Utc>, Bound<'py, PyAny>>,
+    e_tags: HashMap<String, Bound<'py, PyString>>,
+}
+
+impl<'py> SharedValues<'py> {
+    fn timestamp(
+        &mut self,
+        py: Python<'py>,
+        timestamp: DateTime<Utc>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if let Some(timestamp) = self.timestamps.get(&timestamp) {
+            return Ok(timestamp.clone());
+        }
+        let object = timestamp.into_pyobject(py)?.into_any();
+        self.timestamps.insert(timestamp, object.clone());
+        Ok(object)
+    }
+
+    fn e_tag(&mut self, py: Python<'py>, e_tag: String) -> Bound<'py, PyString> {
+        self.e_tags
+            .entry(e_tag)
+            .or_insert_with_key(|e_tag| PyString::new(py, e_tag))
+            .clone()
+    }
+}
+
+/// Objects converted to a list of dicts, sharing equal values between them.
+struct PyObjectMetas(Vec<PyObjectMeta>);
+
+impl<'py> IntoPyObject<'py> for PyObjectMetas {
+    type Target = PyList;
+    type Output = Bound<'py, PyList>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let mut values = SharedValues::default();
+        let dicts = self
+            .0
+            .into_iter()
+            .map(|meta| meta.into_dict(py, &mut values))
+            .collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, dicts)
     }
 }
 
@@ -97,6 +170,19 @@ impl PyListStream {
 
 #[pymethods]
 impl PyListStream {
+    /// Support `ListStream[...]` in annotations, as the stubs declare the class generic over
+    /// the type of its chunks.
+    #[classmethod]
+    fn __class_getitem__<'py>(
+        cls: &Bound<'py, PyType>,
+        item: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = cls.py();
+        py.import(intern!(py, "types"))?
+            .getattr(intern!(py, "GenericAlias"))?
+            .call1((cls, item))
+    }
+
     fn __aiter__(slf: Py<Self>) -> Py<Self> {
         slf
     }
@@ -105,20 +191,60 @@ impl PyListStream {
         slf
     }
 
-    fn collect(&self, py: Python) -> PyResult<PyListIterResult> {
+    #[pyo3(signature = (*, limit = None, timeout = None, partial = true))]
+    fn collect(
+        &self,
+        py: Python,
+        limit: Option<usize>,
+        timeout: Option<f64>,
+        partial: bool,
+    ) -> PyResult<PyListIterResult> {
+        let options = CollectOptions::try_new(limit, timeout, partial)?;
+        let runtime = get_runtime(py)?;
+        let stream = self.stream.clone();
+        py.allow_threads(|| runtime.block_on(collect_stream(stream, options, self.return_arrow)))
+    }
+
+    #[pyo3(signature = (*, limit = None, timeout = None, partial = true))]
+    fn collect_async<'py>(
+        &'py self,
+        py: Python<'py>,
+        limit: Option<usize>,
+        timeout: Option<f64>,
+        partial: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let options = CollectOptions::try_new(limit, timeout, partial)?;
+        let stream = self.stream.clone();
+        future_into_py(py, collect_stream(stream, options, self.return_arrow))
+    }
+
+    #[pyo3(signature = (engine = "polars"))]
+    fn collect_dataframe(&self, py: Python, engine: &str) -> PyResult<PyObject> {
+        let engine = DataFrameEngine::try_from(engine)?;
         let runtime = get_runtime(py)?;
         let stream = self.stream.clone();
-        runtime.block_on(collect_stream(stream, self.return_arrow))
+        let metas = py
+            .allow_threads(|| runtime.block_on(collect_metas(stream, CollectOptions::default())))?;
+        engine.convert(py, object_meta_to_arrow(&metas))
     }
 
-    fn collect_async<'py>(&'py self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+    #[pyo3(signature = (engine = "polars"))]
+    fn collect_dataframe_async<'py>(
+        &'py self,
+        py: Python<'py>,
+        engine: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let engine = DataFrameEngine::try_from(engine)?;
         let stream = self.stream.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, collect_stream(stream, self.return_arrow))
+        future_into_py(py, async move {
+            let metas = collect_metas(stream, CollectOptions::default()).await?;
+            Python::with_gil(|py| engine.convert(py, object_meta_to_arrow(&metas)))
+        })
     }
 
     fn __anext__<'py>(&'py self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let stream = self.stream.clone();
-        pyo3_async_runtimes::tokio::future_into_py(
+        future_into_py(
             py,
             next_stream(stream, self.chunk_size, false, self.return_arrow),
         )
@@ -127,19 +253,21 @@ impl PyListStream {
     fn __next__<'py>(&'py self, py: Python<'py>) -> PyResult<PyListIterResult> {
         let runtime = get_runtime(py)?;
         let stream = self.stream.clone();
-        runtime.block_on(next_stream(
-            stream,
-            self.chunk_size,
-            true,
-            self.return_arrow,
-        ))
+        py.allow_threads(|| {
+            runtime.block_on(next_stream(
+                stream,
+                self.chunk_size,
+                true,
+                self.return_arrow,
+            ))
+        })
     }
 }
 
 #[derive(IntoPyObject)]
 enum PyListIterResult {
     Arrow(PyRecordBatchWrapper),
-    Native(Vec<PyObjectMeta>),
+    Native(PyObjectMetas),
 }
 
 async fn next_stream(
@@ -160,7 +288,7 @@ async fn next_stream(
                             return Ok(PyListIterResult::Arrow(object_meta_to_arrow(&metas)));
                         }
                         false => {
-                            return Ok(PyListIterResult::Native(metas));
+                            return Ok(PyListIterResult::Native(PyObjectMetas(metas)));
                         }
                     }
                 }
@@ -181,7 +309,7 @@ async fn next_stream(
                             return Ok(PyListIterResult::Arrow(object_meta_to_arrow(&metas)));
                         }
                         false => {
-                            return Ok(PyListIterResult::Native(metas));
+                            return Ok(PyListIterResult::Native(PyObjectMetas(metas)));
                         }
                     }
                 }
@@ -190,30 +318,119 @@ async fn next_stream(
     }
 }
 
-async fn collect_stream(
+/// Bounds on how much of a stream `collect` gathers.
+#[derive(Default)]
+struct CollectOptions {
+    /// Stop after this many objects. The rest of the stream can still be consumed afterwards.
+    limit: Option<usize>,
+    /// Stop after this much time has passed.
+    timeout: Option<Duration>,
+    /// Whether hitting the timeout returns the objects gathered so far instead of raising.
+    partial: bool,
+}
+
+impl CollectOptions {
+    fn try_new(limit: Option<usize>, timeout: Option<f64>, partial: bool) -> PyResult<Self> {
+        let timeout = timeout
+            .map(|secs| {
+                Duration::try_from_secs_f64(secs).map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "timeout must be a non-negative number of seconds, got {secs}"
+                    ))
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            limit,
+            timeout,
+            partial,
+        })
+    }
+}
+
+async fn collect_metas(
     stream: Arc<Mutex<Fuse<BoxStream<'static, object_store::Result<ObjectMeta>>>>>,
-    return_arrow: bool,
-) -> PyResult<PyListIterResult> {
+    options: CollectOptions,
+) -> PyResult<Vec<PyObjectMeta>> {
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
     let mut stream = stream.lock().await;
     let mut metas: Vec<PyObjectMeta> = vec![];
     loop {
-        match stream.next().await {
+        if options.limit.is_some_and(|limit| metas.len() >= limit) {
+            return Ok(metas);
+        }
+        // Dropping `next()` on timeout is fine: the stream keeps any in-progress request, so no
+        // objects are lost if iteration continues later.
+        let next = match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, stream.next()).await {
+                Ok(next) => next,
+                Err(_) if options.partial => return Ok(metas),
+                Err(_) => {
+                    return Err(PyTimeoutError::new_err(format!(
+                        "Listing did not complete within the timeout ({} objects collected)",
+                        metas.len()
+                    )))
+                }
+            },
+            None => stream.next().await,
+        };
+        match next {
             Some(Ok(meta)) => {
                 metas.push(PyObjectMeta(meta));
             }
             Some(Err(e)) => return Err(PyObjectStoreError::from(e).into()),
-            None => match return_arrow {
-                true => {
-                    return Ok(PyListIterResult::Arrow(object_meta_to_arrow(&metas)));
-                }
-                false => {
-                    return Ok(PyListIterResult::Native(metas));
-                }
-            },
+            None => return Ok(metas),
         };
     }
 }
 
+async fn collect_stream(
+    stream: Arc<Mutex<Fuse<BoxStream<'static, object_store::Result<ObjectMeta>>>>>,
+    options: CollectOptions,
+    return_arrow: bool,
+) -> PyResult<PyListIterResult> {
+    let metas = collect_metas(stream, options).await?;
+    match return_arrow {
+        true => Ok(PyListIterResult::Arrow(object_meta_to_arrow(&metas))),
+        false => Ok(PyListIterResult::Native(PyObjectMetas(metas))),
+    }
+}
+
+/// The DataFrame library that `collect_dataframe` hands the listing to.
+enum DataFrameEngine {
+    Pandas,
+    Polars,
+}
+
+impl TryFrom<&str> for DataFrameEngine {
+    type Error = PyErr;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "pandas" => Ok(Self::Pandas),
+            "polars" => Ok(Self::Polars),
+            _ => Err(PyValueError::new_err(format!(
+                "Unexpected DataFrame engine: {}. Expected 'polars' or 'pandas'.",
+                value
+            ))),
+        }
+    }
+}
+
+impl DataFrameEngine {
+    /// Convert the batch via pyarrow, without creating a Python object per row.
+    fn convert(&self, py: Python, batch: PyRecordBatchWrapper) -> PyResult<PyObject> {
+        let batch = batch.0.to_pyarrow(py)?;
+        match self {
+            Self::Pandas => batch.call_method0(py, intern!(py, "to_pandas")),
+            Self::Polars => Ok(py
+                .import(intern!(py, "polars"))?
+                .call_method1(intern!(py, "from_arrow"), (batch,))?
+                .unbind()),
+        }
+    }
+}
+
 struct PyRecordBatchWrapper(PyRecordBatch);
 
 impl PyRecordBatchWrapper {
@@ -313,7 +530,10 @@ fn object_meta_to_arrow(metas: &[PyObjectMeta]) -> PyRecordBatchWrapper {
     PyRecordBatchWrapper::new(batch)
 }
 
-pub(crate) struct PyListResult(ListResult);
+pub(crate) struct PyListResult {
+    result: ListResult,
+    return_arrow: bool,
+}
 
 impl<'py> IntoPyObject<'py> for PyListResult {
     type Target = PyDict;
@@ -324,7 +544,7 @@ impl<'py> IntoPyObject<'py> for PyListResult {
         let mut dict = IndexMap::with_capacity(2);
         dict.insert(
             "common_prefixes",
-            self.0
+            self.result
                 .common_prefixes
                 .into_iter()
                 .map(String::from)
@@ -332,20 +552,35 @@ impl<'py> IntoPyObject<'py> for PyListResult {
                 .into_pyobject(py)?
                 .into_any(),
         );
-        dict.insert(
-            "objects",
-            self.0
-                .objects
-                .into_iter()
-                .map(PyObjectMeta)
-                .collect::<Vec<_>>()
-                .into_pyobject(py)?
-                .into_any(),
-        );
+        let objects = self
+            .result
+            .objects
+            .into_iter()
+            .map(PyObjectMeta)
+            .collect::<Vec<_>>();
+        let objects = if self.return_arrow {
+            object_meta_to_arrow(&objects).into_pyobject(py)?
+        } else {
+            PyObjectMetas(objects).into_pyobject(py)?.into_any()
+        };
+        dict.insert("objects", objects);
         dict.into_pyobject(py)
     }
 }
 
+/// Check that arro3.core is installed, as it's needed to return results as Arrow.
+fn check_arro3(py: Python) -> PyResult<()> {
+    // The IntoPy impl is infallible, but `PyRecordBatch::to_arro3` can fail if arro3 is not
+    // installed.
+    let msg = concat!(
+        "arro3.core is a required dependency for returning results as arrow.\n",
+        "\nInstall with `pip install arro3-core`."
+    );
+    py.import(intern!(py, "arro3.core"))
+        .map_err(|err| PyImportError::new_err(format!("{}\n\n{}", msg, err)))?;
+    Ok(())
+}
+
 #[pyfunction]
 #[pyo3(signature = (store, prefix = None, *, offset = None, chunk_size = 50, return_arrow = false))]
 pub(crate) fn list(
@@ -357,15 +592,7 @@ pub(crate) fn list(
     return_arrow: bool,
 ) -> PyObjectStoreResult<PyListStream> {
     if return_arrow {
-        // Ensure that arro3.core is installed if returning as arrow.
-        // The IntoPy impl is infallible, but `PyRecordBatch::to_arro3` can fail if arro3 is not
-        // installed.
-        let msg = concat!(
-            "arro3.core is a required dependency for returning results as arrow.\n",
-            "\nInstall with `pip install arro3-core`."
-        );
-        py.import(intern!(py, "arro3.core"))
-            .map_err(|err| PyImportError::new_err(format!("{}\n\n{}", msg, err)))?;
+        check_arro3(py)?;
     }
 
     let store = store.into_inner().clone();
@@ -379,33 +606,45 @@ pub(crate) fn list(
 }
 
 #[pyfunction]
-#[pyo3(signature = (store, prefix = None))]
+#[pyo3(signature = (store, prefix = None, *, return_arrow = false))]
 pub(crate) fn list_with_delimiter(
     py: Python,
     store: PyObjectStore,
     prefix: Option<String>,
+    return_arrow: bool,
 ) -> PyObjectStoreResult<PyListResult> {
+    if return_arrow {
+        check_arro3(py)?;
+    }
     let runtime = get_runtime(py)?;
     py.allow_threads(|| {
         let out = runtime.block_on(list_with_delimiter_materialize(
             store.into_inner(),
             prefix.map(|s| s.into()).as_ref(),
+            return_arrow,
         ))?;
         Ok::<_, PyObjectStoreError>(out)
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (store, prefix = None))]
+#[pyo3(signature = (store, prefix = None, *, return_arrow = false))]
 pub(crate) fn list_with_delimiter_async(
     py: Python,
     store: PyObjectStore,
     prefix: Option<String>,
+    return_arrow: bool,
 ) -> PyResult<Bound<PyAny>> {
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let out =
-            list_with_delimiter_materialize(store.into_inner(), prefix.map(|s| s.into()).as_ref())
-                .await?;
+    if return_arrow {
+        check_arro3(py)?;
+    }
+    future_into_py(py, async move {
+        let out = list_with_delimiter_materialize(
+            store.into_inner(),
+            prefix.map(|s| s.into()).as_ref(),
+            return_arrow,
+        )
+        .await?;
         Ok(out)
     })
 }
@@ -413,7 +652,104 @@ pub(crate) fn list_with_delimiter_async(
 async fn list_with_delimiter_materialize(
     store: Arc<dyn ObjectStore>,
     prefix: Option<&Path>,
+    return_arrow: bool,
 ) -> PyObjectStoreResult<PyListResult> {
-    let list_result = store.list_with_delimiter(prefix).await?;
-    Ok(PyListResult(list_result))
+    let result = store.list_with_delimiter(prefix).await?;
+    Ok(PyListResult {
+        result,
+        return_arrow,
+    })
+}
+
+/// The number and total size of the objects under a prefix.
+#[derive(Debug, Default)]
+pub(crate) struct TotalSize {
+    count: usize,
+    size: usize,
+}
+
+impl AddAssign for TotalSize {
+    fn add_assign(&mut self, other: Self) {
+        self.count += other.count;
+        self.size += other.size;
+    }
+}
+
+impl<'py> IntoPyObject<'py> for TotalSize {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let mut dict = IndexMap::with_capacity(2);
+        dict.insert("count", self.count);
+        dict.insert("size", self.size);
+        dict.into_pyobject(py)
+    }
+}
+
+/// Sum the sizes of the objects under `prefix`.
+///
+/// The top level of the prefix is listed with a delimiter, and each of its common prefixes is
+/// then listed recursively, up to `max_concurrency` at a time.
+async fn total_size_inner(
+    store: Arc<dyn ObjectStore>,
+    prefix: Option<Path>,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<TotalSize> {
+    let top = store.list_with_delimiter(prefix.as_ref()).await?;
+    let mut total = TotalSize {
+        count: top.objects.len(),
+        size: top.objects.iter().map(|meta| meta.size).sum(),
+    };
+    let prefixes = top.common_prefixes.into_iter().map(|prefix| {
+        let store = store.clone();
+        async move {
+            let mut total = TotalSize::default();
+            let mut stream = store.list(Some(&prefix));
+            while let Some(meta) = stream.next().await {
+                total.count += 1;
+                total.size += meta?.size;
+            }
+            Ok::<_, object_store::Error>(total)
+        }
+    });
+    let mut prefixes = std::pin::pin!(buffer_unordered(prefixes, max_concurrency));
+    while let Some(prefix_total) = prefixes.next().await {
+        total += prefix_total?;
+    }
+    Ok(total)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix = None, *, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn total_size(
+    py: Python,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<TotalSize> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(total_size_inner(
+            store.into_inner(),
+            prefix.map(Path::from),
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix = None, *, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn total_size_async(
+    py: Python,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    max_concurrency: Concurrency,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let out =
+            total_size_inner(store.into_inner(), prefix.map(Path::from), max_concurrency).await?;
+        Ok(out)
+    })
 }
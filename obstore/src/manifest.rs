@@ -0,0 +1,651 @@
+//! Bulk jobs described by a manifest of operations, such as the objects to copy in a migration.
+
+use std::io::{BufRead, Cursor};
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::array::{Array, AsArray, RecordBatch, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use bytes::Bytes;
+use futures::StreamExt;
+use indexmap::IndexMap;
+use object_store::path::Path;
+use object_store::{ObjectStore, PutMode, PutOptions, WriteMultipart};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_object_store::clock::{self, Backoff};
+use pyo3_object_store::{PyObjectStore, PyObjectStoreResult};
+use serde::{Deserialize, Serialize};
+
+use crate::journal::JournaledStore;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The part size used to stream objects between stores.
+const TRANSFER_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// The wait before the first retry of an operation, doubled for each further retry.
+const INIT_BACKOFF: Duration = Duration::from_millis(100);
+
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ManifestFormat {
+    Csv,
+    Ndjson,
+    Arrow,
+}
+
+impl ManifestFormat {
+    fn from_path(path: &str) -> PyResult<Self> {
+        let extension = path.rsplit_once('.').map(|(_, extension)| extension);
+        match extension {
+            Some("csv") => Ok(Self::Csv),
+            Some("ndjson" | "jsonl") => Ok(Self::Ndjson),
+            Some("arrow" | "arrows" | "ipc" | "feather") => Ok(Self::Arrow),
+            _ => Err(PyValueError::new_err(format!(
+                "Cannot tell the format of manifest {path}, pass format explicitly"
+            ))),
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for ManifestFormat {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let s = ob.extract::<String>()?.to_lowercase();
+        match s.as_str() {
+            "csv" => Ok(Self::Csv),
+            "ndjson" => Ok(Self::Ndjson),
+            "arrow" => Ok(Self::Arrow),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown manifest format {s}, expected 'csv', 'ndjson' or 'arrow'"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Copy,
+    Move,
+    Delete,
+}
+
+impl Operation {
+    fn parse(s: &str) -> PyResult<Self> {
+        match s.to_lowercase().as_str() {
+            "copy" => Ok(Self::Copy),
+            "move" => Ok(Self::Move),
+            "delete" => Ok(Self::Delete),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown manifest operation {s}, expected 'copy', 'move' or 'delete'"
+            ))),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Copy => "copy",
+            Self::Move => "move",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// One operation of a manifest.
+#[derive(Debug, Clone)]
+struct Row {
+    source: Path,
+    destination: Path,
+    operation: Operation,
+    /// Skip the operation if the destination already exists
+    if_not_exists: bool,
+}
+
+impl Row {
+    fn try_new(
+        source: &str,
+        destination: Option<&str>,
+        operation: Option<&str>,
+        if_not_exists: Option<bool>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            source: source.into(),
+            destination: destination.unwrap_or(source).into(),
+            operation: operation
+                .map(Operation::parse)
+                .transpose()?
+                .unwrap_or(Operation::Copy),
+            if_not_exists: if_not_exists.unwrap_or(false),
+        })
+    }
+}
+
+/// An NDJSON line of a manifest.
+#[derive(Deserialize)]
+struct JsonRow {
+    source: String,
+    destination: Option<String>,
+    operation: Option<String>,
+    if_not_exists: Option<bool>,
+}
+
+fn parse_ndjson(data: &[u8]) -> PyResult<Vec<Row>> {
+    let mut rows = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row: JsonRow = serde_json::from_str(&line).map_err(|err| {
+            PyValueError::new_err(format!("Invalid manifest line {}: {err}", i + 1))
+        })?;
+        rows.push(Row::try_new(
+            &row.source,
+            row.destination.as_deref(),
+            row.operation.as_deref(),
+            row.if_not_exists,
+        )?);
+    }
+    Ok(rows)
+}
+
+fn string_column(batch: &RecordBatch, name: &str) -> PyResult<Option<StringArray>> {
+    let Some(column) = batch.column_by_name(name) else {
+        return Ok(None);
+    };
+    let column = cast(column, &DataType::Utf8).map_err(|err| {
+        PyValueError::new_err(format!(
+            "Column {name} of the manifest must contain strings: {err}"
+        ))
+    })?;
+    Ok(Some(column.as_string::<i32>().clone()))
+}
+
+/// The value of `column` at `row`, where empty strings count as missing values.
+fn value(column: &Option<StringArray>, row: usize) -> Option<&str> {
+    column
+        .as_ref()
+        .filter(|column| column.is_valid(row))
+        .map(|column| column.value(row))
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_batches(
+    batches: impl Iterator<Item = Result<RecordBatch, arrow::error::ArrowError>>,
+) -> PyResult<Vec<Row>> {
+    let mut rows = vec![];
+    for batch in batches {
+        let batch = batch.map_err(|err| PyValueError::new_err(err.to_string()))?;
+        let sources = string_column(&batch, "source")?
+            .ok_or_else(|| PyValueError::new_err("Manifest must have a \"source\" column"))?;
+        let destinations = string_column(&batch, "destination")?;
+        let operations = string_column(&batch, "operation")?;
+        let if_not_exists = string_column(&batch, "if_not_exists")?;
+        for row in 0..batch.num_rows() {
+            if sources.is_null(row) {
+                return Err(PyValueError::new_err("Manifest has a null source"));
+            }
+            let if_not_exists = value(&if_not_exists, row)
+                .map(|value| match value.to_lowercase().as_str() {
+                    "true" | "1" => Ok(true),
+                    "false" | "0" => Ok(false),
+                    _ => Err(PyValueError::new_err(format!(
+                        "Invalid if_not_exists value {value} in manifest"
+                    ))),
+                })
+                .transpose()?;
+            rows.push(Row::try_new(
+                sources.value(row),
+                value(&destinations, row),
+                value(&operations, row),
+                if_not_exists,
+            )?);
+        }
+    }
+    Ok(rows)
+}
+
+fn parse_manifest(data: Bytes, format: ManifestFormat) -> PyResult<Vec<Row>> {
+    let to_py_err = |err: arrow::error::ArrowError| PyValueError::new_err(err.to_string());
+    match format {
+        ManifestFormat::Ndjson => parse_ndjson(&data),
+        ManifestFormat::Csv => {
+            let csv_format = arrow::csv::reader::Format::default().with_header(true);
+            let (schema, _) = csv_format
+                .infer_schema(Cursor::new(&data), None)
+                .map_err(to_py_err)?;
+            let reader = arrow::csv::ReaderBuilder::new(Arc::new(schema))
+                .with_format(csv_format)
+                .build(Cursor::new(&data))
+                .map_err(to_py_err)?;
+            parse_batches(reader)
+        }
+        ManifestFormat::Arrow => {
+            // Arrow IPC comes in a file format and a stream format
+            if let Ok(reader) = arrow::ipc::reader::FileReader::try_new(Cursor::new(&data), None) {
+                parse_batches(reader)
+            } else {
+                let reader = arrow::ipc::reader::StreamReader::try_new(Cursor::new(&data), None)
+                    .map_err(to_py_err)?;
+                parse_batches(reader)
+            }
+        }
+    }
+}
+
+/// The rows of a manifest that have been processed, stored so that a job can be resumed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    /// The e-tag of the manifest, to detect a checkpoint of a different manifest
+    manifest_e_tag: Option<String>,
+    /// Ranges of rows that have been processed, as `[start, end)` pairs
+    done: Vec<(usize, usize)>,
+}
+
+impl Checkpoint {
+    fn from_done(manifest_e_tag: Option<String>, done: &[bool]) -> Self {
+        let mut ranges: Vec<(usize, usize)> = vec![];
+        for (row, _) in done.iter().enumerate().filter(|(_, done)| **done) {
+            match ranges.last_mut() {
+                Some((_, end)) if *end == row => *end += 1,
+                _ => ranges.push((row, row + 1)),
+            }
+        }
+        Self {
+            manifest_e_tag,
+            done: ranges,
+        }
+    }
+
+    fn mark(&self, done: &mut [bool]) {
+        for &(start, end) in &self.done {
+            let end = end.min(done.len());
+            if start < end {
+                done[start..end].fill(true);
+            }
+        }
+    }
+}
+
+async fn load_checkpoint(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+) -> PyObjectStoreResult<Option<Checkpoint>> {
+    match store.get(path).await {
+        Ok(result) => {
+            let data = result.bytes().await?;
+            let checkpoint = serde_json::from_slice(&data)
+                .map_err(|err| PyValueError::new_err(format!("Invalid checkpoint: {err}")))?;
+            Ok(Some(checkpoint))
+        }
+        Err(object_store::Error::NotFound { .. }) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn save_json(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+    value: &impl Serialize,
+) -> PyObjectStoreResult<()> {
+    let data = serde_json::to_vec(value)
+        .map_err(|err| PyValueError::new_err(format!("Could not serialize: {err}")))?;
+    store.put(path, data.into()).await?;
+    Ok(())
+}
+
+/// The outcome of an operation that didn't fail.
+enum Outcome {
+    Done,
+    /// The destination already existed and the row set `if_not_exists`
+    Skipped,
+}
+
+/// Whether `err` may go away when the operation is retried.
+fn is_transient(err: &object_store::Error) -> bool {
+    matches!(
+        err,
+        object_store::Error::Generic { .. } | object_store::Error::JoinError { .. }
+    )
+}
+
+/// Stream an object between stores.
+async fn transfer(
+    source_store: &Arc<dyn ObjectStore>,
+    destination_store: &Arc<dyn ObjectStore>,
+    row: &Row,
+) -> object_store::Result<()> {
+    if row.if_not_exists {
+        match destination_store.head(&row.destination).await {
+            Ok(_) => {
+                return Err(object_store::Error::AlreadyExists {
+                    path: row.destination.to_string(),
+                    source: "Destination already exists".into(),
+                })
+            }
+            Err(object_store::Error::NotFound { .. }) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    let result = source_store.get(&row.source).await?;
+    let attributes = result.attributes.clone();
+    if result.meta.size <= TRANSFER_CHUNK_SIZE {
+        let data = result.bytes().await?;
+        let opts = PutOptions {
+            mode: if row.if_not_exists {
+                PutMode::Create
+            } else {
+                PutMode::Overwrite
+            },
+            attributes,
+            ..Default::default()
+        };
+        destination_store
+            .put_opts(&row.destination, data.into(), opts)
+            .await?;
+        return Ok(());
+    }
+
+    let upload = destination_store.put_multipart(&row.destination).await?;
+    let mut writer = WriteMultipart::new_with_chunk_size(upload, TRANSFER_CHUNK_SIZE);
+    let mut stream = result.into_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                writer.abort().await?;
+                return Err(err);
+            }
+        };
+        writer.wait_for_capacity(4).await?;
+        writer.write(&chunk);
+    }
+    writer.finish().await?;
+    Ok(())
+}
+
+async fn apply_once(
+    source_store: &Arc<dyn ObjectStore>,
+    destination_store: &Option<Arc<dyn ObjectStore>>,
+    row: &Row,
+) -> object_store::Result<()> {
+    match (row.operation, destination_store) {
+        (Operation::Delete, _) => source_store.delete(&row.source).await,
+        (Operation::Copy, None) if row.if_not_exists => {
+            source_store
+                .copy_if_not_exists(&row.source, &row.destination)
+                .await
+        }
+        (Operation::Copy, None) => source_store.copy(&row.source, &row.destination).await,
+        (Operation::Move, None) if row.if_not_exists => {
+            source_store
+                .rename_if_not_exists(&row.source, &row.destination)
+                .await
+        }
+        (Operation::Move, None) => source_store.rename(&row.source, &row.destination).await,
+        (Operation::Copy, Some(destination_store)) => {
+            transfer(source_store, destination_store, row).await
+        }
+        (Operation::Move, Some(destination_store)) => {
+            transfer(source_store, destination_store, row).await?;
+            source_store.delete(&row.source).await
+        }
+    }
+}
+
+/// Apply the operation of `row`, retrying transient errors up to `max_retries` times.
+async fn apply(
+    source_store: Arc<dyn ObjectStore>,
+    destination_store: Option<Arc<dyn ObjectStore>>,
+    row: Row,
+    max_retries: usize,
+) -> Result<Outcome, object_store::Error> {
+    let mut backoff = Backoff::new(INIT_BACKOFF, MAX_BACKOFF);
+    let mut attempt = 0;
+    loop {
+        match apply_once(&source_store, &destination_store, &row).await {
+            Ok(()) => return Ok(Outcome::Done),
+            Err(object_store::Error::AlreadyExists { .. }) if row.if_not_exists => {
+                return Ok(Outcome::Skipped)
+            }
+            Err(err) if is_transient(&err) && attempt < max_retries => {
+                backoff.wait().await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RowError {
+    row: usize,
+    source: String,
+    destination: String,
+    operation: &'static str,
+    error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ManifestReport {
+    manifest: String,
+    total: usize,
+    succeeded: usize,
+    skipped: usize,
+    /// Rows processed by a previous run, according to the checkpoint
+    resumed: usize,
+    failed: usize,
+    errors: Vec<RowError>,
+    started_at: String,
+    finished_at: String,
+}
+
+impl<'py> IntoPyObject<'py> for ManifestReport {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let errors = self
+            .errors
+            .into_iter()
+            .map(|error| {
+                let dict = PyDict::new(py);
+                dict.set_item("row", error.row)?;
+                dict.set_item("source", error.source)?;
+                dict.set_item("destination", error.destination)?;
+                dict.set_item("operation", error.operation)?;
+                dict.set_item("error", error.error)?;
+                Ok(dict)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut dict = IndexMap::with_capacity(9);
+        dict.insert("manifest", self.manifest.into_pyobject(py)?.into_any());
+        dict.insert("total", self.total.into_pyobject(py)?.into_any());
+        dict.insert("succeeded", self.succeeded.into_pyobject(py)?.into_any());
+        dict.insert("skipped", self.skipped.into_pyobject(py)?.into_any());
+        dict.insert("resumed", self.resumed.into_pyobject(py)?.into_any());
+        dict.insert("failed", self.failed.into_pyobject(py)?.into_any());
+        dict.insert("errors", errors.into_pyobject(py)?.into_any());
+        dict.insert("started_at", self.started_at.into_pyobject(py)?.into_any());
+        dict.insert(
+            "finished_at",
+            self.finished_at.into_pyobject(py)?.into_any(),
+        );
+        dict.into_pyobject(py)
+    }
+}
+
+struct JobOptions {
+    destination_store: Option<Arc<dyn ObjectStore>>,
+    format: Option<ManifestFormat>,
+    checkpoint: Option<Path>,
+    checkpoint_every: usize,
+    report: Option<Path>,
+    max_concurrency: Concurrency,
+    max_retries: usize,
+}
+
+async fn run_manifest_inner(
+    store: Arc<dyn ObjectStore>,
+    manifest: String,
+    options: JobOptions,
+) -> PyObjectStoreResult<ManifestReport> {
+    let started_at = clock::now();
+    let format = match options.format {
+        Some(format) => format,
+        None => ManifestFormat::from_path(&manifest)?,
+    };
+    let result = store.get(&Path::from(manifest.as_str())).await?;
+    let manifest_e_tag = result.meta.e_tag.clone();
+    let rows = parse_manifest(result.bytes().await?, format)?;
+
+    let mut done = vec![false; rows.len()];
+    if let Some(path) = &options.checkpoint {
+        if let Some(checkpoint) = load_checkpoint(&store, path).await? {
+            if checkpoint.manifest_e_tag != manifest_e_tag {
+                return Err(PyValueError::new_err(
+                    "The checkpoint was written for a different version of the manifest",
+                )
+                .into());
+            }
+            checkpoint.mark(&mut done);
+        }
+    }
+
+    let mut report = ManifestReport {
+        manifest: manifest.clone(),
+        total: rows.len(),
+        succeeded: 0,
+        skipped: 0,
+        resumed: done.iter().filter(|done| **done).count(),
+        failed: 0,
+        errors: vec![],
+        started_at: started_at.to_rfc3339(),
+        finished_at: String::new(),
+    };
+
+    let pending = rows
+        .iter()
+        .enumerate()
+        .filter(|(row, _)| !done[*row])
+        .map(|(index, row)| {
+            let source_store = store.clone();
+            let destination_store = options.destination_store.clone();
+            let row = row.clone();
+            let max_retries = options.max_retries;
+            async move {
+                apply(source_store, destination_store, row, max_retries)
+                    .await
+                    .map(|outcome| (index, outcome))
+                    .map_err(|err| (index, err))
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut results = Box::pin(buffer_unordered(pending, options.max_concurrency));
+    let mut since_checkpoint = 0;
+    while let Some(result) = results.next().await {
+        match result {
+            Ok((index, outcome)) => {
+                done[index] = true;
+                match outcome {
+                    Outcome::Done => report.succeeded += 1,
+                    Outcome::Skipped => report.skipped += 1,
+                }
+            }
+            // Failed rows aren't marked as done, so that they are retried when the job is
+            // resumed from its checkpoint
+            Err((index, err)) => {
+                let row = &rows[index];
+                report.failed += 1;
+                report.errors.push(RowError {
+                    row: index,
+                    source: row.source.to_string(),
+                    destination: row.destination.to_string(),
+                    operation: row.operation.as_str(),
+                    error: err.to_string(),
+                });
+            }
+        }
+        since_checkpoint += 1;
+        if let Some(path) = &options.checkpoint {
+            if since_checkpoint >= options.checkpoint_every {
+                let checkpoint = Checkpoint::from_done(manifest_e_tag.clone(), &done);
+                save_json(&store, path, &checkpoint).await?;
+                since_checkpoint = 0;
+            }
+        }
+    }
+    drop(results);
+
+    if let Some(path) = &options.checkpoint {
+        save_json(&store, path, &Checkpoint::from_done(manifest_e_tag, &done)).await?;
+    }
+    report.errors.sort_by_key(|error| error.row);
+    report.finished_at = clock::now().to_rfc3339();
+    let report_path = options
+        .report
+        .unwrap_or_else(|| Path::from(format!("{manifest}.report.json")));
+    save_json(&store, &report_path, &report).await?;
+    Ok(report)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, manifest, *, destination_store = None, format = None, checkpoint = None, checkpoint_every = 1000, report = None, max_concurrency = Concurrency::Fixed(12), max_retries = 3))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_manifest(
+    py: Python,
+    store: PyObjectStore,
+    manifest: String,
+    destination_store: Option<JournaledStore>,
+    format: Option<ManifestFormat>,
+    checkpoint: Option<String>,
+    checkpoint_every: usize,
+    report: Option<String>,
+    max_concurrency: Concurrency,
+    max_retries: usize,
+) -> PyObjectStoreResult<ManifestReport> {
+    let options = JobOptions {
+        destination_store: destination_store.map(JournaledStore::into_inner),
+        format,
+        checkpoint: checkpoint.map(Path::from),
+        checkpoint_every: checkpoint_every.max(1),
+        report: report.map(Path::from),
+        max_concurrency,
+        max_retries,
+    };
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(run_manifest_inner(store.into_inner(), manifest, options)))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, manifest, *, destination_store = None, format = None, checkpoint = None, checkpoint_every = 1000, report = None, max_concurrency = Concurrency::Fixed(12), max_retries = 3))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_manifest_async(
+    py: Python,
+    store: PyObjectStore,
+    manifest: String,
+    destination_store: Option<JournaledStore>,
+    format: Option<ManifestFormat>,
+    checkpoint: Option<String>,
+    checkpoint_every: usize,
+    report: Option<String>,
+    max_concurrency: Concurrency,
+    max_retries: usize,
+) -> PyResult<Bound<PyAny>> {
+    let options = JobOptions {
+        destination_store: destination_store.map(JournaledStore::into_inner),
+        format,
+        checkpoint: checkpoint.map(Path::from),
+        checkpoint_every: checkpoint_every.max(1),
+        report: report.map(Path::from),
+        max_concurrency,
+        max_retries,
+    };
+    future_into_py(py, async move {
+        Ok(run_manifest_inner(store.into_inner(), manifest, options).await?)
+    })
+}
@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use arrow::array::{Array, AsArray, RecordBatch};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use futures::stream::TryStreamExt;
+use object_store::path::Path;
+use object_store::{Attribute, Attributes, ObjectStore, PutOptions, TagSet};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_arrow::input::AnyRecordBatch;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreResult};
+
+use crate::runtime::{future_into_py, get_runtime};
+
+/// What a column of the updates table sets on each object.
+enum Target {
+    Attribute(Attribute),
+    Tag(String),
+}
+
+impl Target {
+    fn from_column(name: &str) -> PyResult<Self> {
+        let target = match name {
+            "content_disposition" => Self::Attribute(Attribute::ContentDisposition),
+            "content_encoding" => Self::Attribute(Attribute::ContentEncoding),
+            "content_language" => Self::Attribute(Attribute::ContentLanguage),
+            "content_type" => Self::Attribute(Attribute::ContentType),
+            "cache_control" => Self::Attribute(Attribute::CacheControl),
+            _ => {
+                if let Some(key) = name.strip_prefix("metadata:") {
+                    Self::Attribute(Attribute::Metadata(Cow::Owned(key.to_string())))
+                } else if let Some(key) = name.strip_prefix("tag:") {
+                    Self::Tag(key.to_string())
+                } else {
+                    return Err(PyValueError::new_err(format!(
+                        "Unexpected column in metadata updates: {name}"
+                    )));
+                }
+            }
+        };
+        Ok(target)
+    }
+}
+
+/// The attributes and tags to set on one object.
+struct Update {
+    path: Path,
+    attributes: Attributes,
+    tags: Option<TagSet>,
+}
+
+fn string_column(batch: &RecordBatch, index: usize) -> PyResult<arrow::array::StringArray> {
+    let column = cast(batch.column(index), &DataType::Utf8).map_err(|err| {
+        PyValueError::new_err(format!(
+            "Column {} of metadata updates must contain strings: {err}",
+            batch.schema().field(index).name()
+        ))
+    })?;
+    Ok(column.as_string::<i32>().clone())
+}
+
+fn parse_updates(updates: AnyRecordBatch) -> PyResult<Vec<Update>> {
+    let reader = updates.into_reader()?;
+    let schema = reader.schema();
+    let path_index = schema
+        .index_of("path")
+        .map_err(|_| PyValueError::new_err("Metadata updates must have a \"path\" column"))?;
+    let targets = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != path_index)
+        .map(|(index, field)| Ok((index, Target::from_column(field.name())?)))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let mut out = vec![];
+    for batch in reader {
+        let batch = batch.map_err(|err| PyValueError::new_err(err.to_string()))?;
+        let paths = string_column(&batch, path_index)?;
+        let columns = targets
+            .iter()
+            .map(|(index, target)| Ok((string_column(&batch, *index)?, target)))
+            .collect::<PyResult<Vec<_>>>()?;
+        for row in 0..batch.num_rows() {
+            if paths.is_null(row) {
+                return Err(PyValueError::new_err("Metadata updates have a null path"));
+            }
+            let mut update = Update {
+                path: paths.value(row).into(),
+                attributes: Attributes::new(),
+                tags: None,
+            };
+            // Nulls leave the current value unchanged
+            for (column, target) in columns.iter().filter(|(column, _)| column.is_valid(row)) {
+                let value = column.value(row);
+                match target {
+                    Target::Attribute(attribute) => {
+                        update
+                            .attributes
+                            .insert(attribute.clone(), value.to_string().into());
+                    }
+                    Target::Tag(key) => update
+                        .tags
+                        .get_or_insert_with(TagSet::default)
+                        .push(key, value),
+                }
+            }
+            out.push(update);
+        }
+    }
+    Ok(out)
+}
+
+/// Rewrite an object onto itself with its attributes merged with those of `update`.
+async fn apply_update(store: Arc<dyn ObjectStore>, update: Update) -> object_store::Result<()> {
+    let result = store.get(&update.path).await?;
+    let mut attributes = result.attributes.clone();
+    let payload = result.bytes().await?;
+    for (attribute, value) in update.attributes.iter() {
+        attributes.insert(attribute.clone(), value.clone());
+    }
+    // Tags can't be read back, so objects without tag updates are rewritten without tags
+    let opts = PutOptions {
+        attributes,
+        tags: update.tags.unwrap_or_default(),
+        ..Default::default()
+    };
+    store.put_opts(&update.path, payload.into(), opts).await?;
+    Ok(())
+}
+
+async fn apply_metadata_inner(
+    store: Arc<dyn ObjectStore>,
+    updates: Vec<Update>,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<usize> {
+    let count = updates.len();
+    let futures = updates
+        .into_iter()
+        .map(|update| apply_update(store.clone(), update));
+    buffer_unordered(futures, max_concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(count)
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, updates, *, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn apply_metadata(
+    py: Python,
+    store: PyObjectStore,
+    updates: AnyRecordBatch,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<usize> {
+    let updates = parse_updates(updates)?;
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(apply_metadata_inner(
+            store.into_inner(),
+            updates,
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, updates, *, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn apply_metadata_async(
+    py: Python,
+    store: PyObjectStore,
+    updates: AnyRecordBatch,
+    max_concurrency: Concurrency,
+) -> PyResult<Bound<PyAny>> {
+    let updates = parse_updates(updates)?;
+    future_into_py(py, async move {
+        let count = apply_metadata_inner(store.into_inner(), updates, max_concurrency).await?;
+        Ok(count)
+    })
+}
@@ -0,0 +1,543 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use object_store::multipart::{MultipartStore, PartId};
+use object_store::path::Path;
+use object_store::{MultipartUpload, PutPayloadMut};
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3_bytes::PyBytes;
+use pyo3_object_store::{
+    prefixed_path, PyAzureStore, PyGCSStore, PyMemoryStore, PyObjectStoreError,
+    PyObjectStoreResult, PyS3Store,
+};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+use crate::journal::JournaledStore;
+use crate::put::PyPutResult;
+use crate::runtime::{future_into_py, get_runtime};
+use crate::shutdown::{register_writer, Finalize, Finalized};
+
+/// Progress of a [`MultipartWriter`].
+///
+/// This lives outside of the writer's lock, so that it can be read while a write is waiting for
+/// capacity.
+#[derive(Default)]
+struct UploadStats {
+    parts_in_flight: AtomicUsize,
+    parts_completed: AtomicUsize,
+    bytes_buffered: AtomicUsize,
+    bytes_uploaded: AtomicUsize,
+}
+
+/// A multipart upload that, unlike [`object_store::WriteMultipart`], reports its progress.
+///
+/// Data is buffered until a full part is available, which is then uploaded in the background.
+/// Parts are numbered in the order they are filled, regardless of the order in which their
+/// uploads complete.
+struct MultipartWriter {
+    path: Path,
+    upload: Box<dyn MultipartUpload>,
+    buffer: PutPayloadMut,
+    tasks: JoinSet<PyObjectStoreResult<()>>,
+    part_size: usize,
+    max_concurrency: usize,
+    next_part: usize,
+    on_part_complete: Option<Arc<PyObject>>,
+    stats: Arc<UploadStats>,
+}
+
+impl MultipartWriter {
+    async fn try_new(
+        store: JournaledStore,
+        path: Path,
+        part_size: usize,
+        max_concurrency: usize,
+        on_part_complete: Option<PyObject>,
+    ) -> PyObjectStoreResult<Self> {
+        let upload = store.into_inner().put_multipart(&path).await?;
+        Ok(Self {
+            path,
+            upload,
+            buffer: PutPayloadMut::new(),
+            tasks: JoinSet::new(),
+            part_size,
+            max_concurrency,
+            next_part: 0,
+            on_part_complete: on_part_complete.map(Arc::new),
+            stats: Default::default(),
+        })
+    }
+
+    /// Wait until fewer than `max_concurrency` parts are in flight.
+    ///
+    /// With `max_concurrency` set to 0, this waits for all outstanding parts.
+    async fn wait_for_capacity(&mut self, max_concurrency: usize) -> PyObjectStoreResult<()> {
+        while !self.tasks.is_empty() && self.tasks.len() >= max_concurrency {
+            self.tasks
+                .join_next()
+                .await
+                .unwrap()
+                .map_err(|source| object_store::Error::JoinError { source })??;
+        }
+        Ok(())
+    }
+
+    /// Start uploading the buffered data as the next part.
+    fn put_part(&mut self) {
+        let part = std::mem::take(&mut self.buffer).freeze();
+        let size = part.content_length();
+        let index = self.next_part;
+        self.next_part += 1;
+
+        let upload = self.upload.put_part(part);
+        let stats = self.stats.clone();
+        let on_part_complete = self.on_part_complete.clone();
+        stats.parts_in_flight.fetch_add(1, Ordering::Relaxed);
+        stats.bytes_buffered.store(0, Ordering::Relaxed);
+        self.tasks.spawn(async move {
+            let result = upload.await;
+            stats.parts_in_flight.fetch_sub(1, Ordering::Relaxed);
+            result?;
+            stats.parts_completed.fetch_add(1, Ordering::Relaxed);
+            stats.bytes_uploaded.fetch_add(size, Ordering::Relaxed);
+            if let Some(on_part_complete) = on_part_complete {
+                Python::with_gil(|py| on_part_complete.call1(py, (index, size)))?;
+            }
+            Ok(())
+        });
+    }
+
+    async fn write(&mut self, mut buf: Bytes) -> PyObjectStoreResult<()> {
+        while !buf.is_empty() {
+            let remaining = self.part_size - self.buffer.content_length();
+            if buf.len() < remaining {
+                self.buffer.push(buf);
+                break;
+            }
+            self.buffer.push(buf.split_to(remaining));
+            self.wait_for_capacity(self.max_concurrency).await?;
+            self.put_part();
+        }
+        self.stats
+            .bytes_buffered
+            .store(self.buffer.content_length(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn finish(mut self) -> PyObjectStoreResult<PyPutResult> {
+        if self.buffer.content_length() > 0 {
+            self.put_part();
+        }
+        let result = match self.wait_for_capacity(0).await {
+            Ok(()) => self.upload.complete().await.map_err(Into::into),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(result) => Ok(PyPutResult::new(result)),
+            Err(err) => {
+                // The writer is closed either way, so abort here rather than leave the parts
+                // uploaded so far behind
+                self.tasks.shutdown().await;
+                let _ = self.upload.abort().await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn abort(mut self) -> PyObjectStoreResult<()> {
+        self.tasks.shutdown().await;
+        self.upload.abort().await?;
+        Ok(())
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, part_size = 5242880, max_concurrency = 12, on_part_complete = None))]
+pub(crate) fn open_multipart_writer(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    part_size: usize,
+    max_concurrency: usize,
+    on_part_complete: Option<PyObject>,
+) -> PyObjectStoreResult<PyMultipartWriter> {
+    check_part_size(part_size)?;
+    let runtime = get_runtime(py)?;
+    let writer = py.allow_threads(|| {
+        runtime.block_on(MultipartWriter::try_new(
+            store,
+            path.into(),
+            part_size,
+            max_concurrency,
+            on_part_complete,
+        ))
+    })?;
+    Ok(PyMultipartWriter::new(writer, false))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, part_size = 5242880, max_concurrency = 12, on_part_complete = None))]
+pub(crate) fn open_multipart_writer_async(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    part_size: usize,
+    max_concurrency: usize,
+    on_part_complete: Option<PyObject>,
+) -> PyResult<Bound<PyAny>> {
+    check_part_size(part_size)?;
+    future_into_py(py, async move {
+        let writer = MultipartWriter::try_new(
+            store,
+            path.into(),
+            part_size,
+            max_concurrency,
+            on_part_complete,
+        )
+        .await?;
+        Ok(PyMultipartWriter::new(writer, true))
+    })
+}
+
+pub(crate) fn check_part_size(part_size: usize) -> PyResult<()> {
+    if part_size == 0 {
+        return Err(PyValueError::new_err("part_size must be greater than 0"));
+    }
+    Ok(())
+}
+
+#[pyclass(name = "MultipartWriter", frozen)]
+pub(crate) struct PyMultipartWriter {
+    writer: Arc<Mutex<Option<MultipartWriter>>>,
+    stats: Arc<UploadStats>,
+    r#async: bool,
+}
+
+impl PyMultipartWriter {
+    fn new(writer: MultipartWriter, r#async: bool) -> Self {
+        let stats = writer.stats.clone();
+        let writer = Arc::new(Mutex::new(Some(writer)));
+        let weak = Arc::downgrade(&writer);
+        register_writer(weak);
+        Self {
+            writer,
+            stats,
+            r#async,
+        }
+    }
+}
+
+impl Finalize for Mutex<Option<MultipartWriter>> {
+    /// Abort the upload, as there is no way to tell whether the data written so far is
+    /// complete.
+    fn finalize(self: Arc<Self>) -> BoxFuture<'static, Finalized> {
+        Box::pin(async move {
+            let Some(writer) = self.lock().await.take() else {
+                return Finalized::Closed;
+            };
+            let path = writer.path.to_string();
+            match writer.abort().await {
+                Ok(()) => Finalized::Aborted(path),
+                Err(err) => Finalized::Failed(format!("Failed to abort upload to {path}: {err}")),
+            }
+        })
+    }
+}
+
+#[pymethods]
+impl PyMultipartWriter {
+    #[getter]
+    fn parts_in_flight(&self) -> usize {
+        self.stats.parts_in_flight.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn parts_completed(&self) -> usize {
+        self.stats.parts_completed.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn bytes_buffered(&self) -> usize {
+        self.stats.bytes_buffered.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn bytes_uploaded(&self) -> usize {
+        self.stats.bytes_uploaded.load(Ordering::Relaxed)
+    }
+
+    fn write<'py>(&'py self, py: Python<'py>, buf: PyBytes) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, write(writer, buf.into_inner()))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(write(writer, buf.into_inner())))?;
+            Ok(py.None())
+        }
+    }
+
+    fn flush<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, flush(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(flush(writer)))?;
+            Ok(py.None())
+        }
+    }
+
+    fn finish<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, finish(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            let out = py.allow_threads(|| runtime.block_on(finish(writer)))?;
+            Ok(out.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+
+    fn abort<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, abort(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(abort(writer)))?;
+            Ok(py.None())
+        }
+    }
+}
+
+/// Take the writer out of `writer`, closing it.
+pub(crate) async fn take_writer<W>(writer: &Mutex<Option<W>>) -> PyResult<W> {
+    writer
+        .lock()
+        .await
+        .take()
+        .ok_or_else(|| PyIOError::new_err("Writer has already been closed."))
+}
+
+/// The writer in `writer`, if it hasn't been closed.
+pub(crate) fn writer_mut<W>(writer: &mut Option<W>) -> PyResult<&mut W> {
+    writer
+        .as_mut()
+        .ok_or_else(|| PyIOError::new_err("Writer has already been closed."))
+}
+
+async fn write(writer: Arc<Mutex<Option<MultipartWriter>>>, buf: Bytes) -> PyResult<()> {
+    let mut writer = writer.lock().await;
+    writer_mut(&mut writer)?.write(buf).await?;
+    Ok(())
+}
+
+async fn flush(writer: Arc<Mutex<Option<MultipartWriter>>>) -> PyResult<()> {
+    let mut writer = writer.lock().await;
+    writer_mut(&mut writer)?.wait_for_capacity(0).await?;
+    Ok(())
+}
+
+async fn finish(writer: Arc<Mutex<Option<MultipartWriter>>>) -> PyResult<PyPutResult> {
+    let writer = take_writer(&writer).await?;
+    Ok(writer.finish().await?)
+}
+
+async fn abort(writer: Arc<Mutex<Option<MultipartWriter>>>) -> PyResult<()> {
+    let writer = take_writer(&writer).await?;
+    writer.abort().await?;
+    Ok(())
+}
+
+/// A store whose multipart uploads can be driven part by part, identified by their upload ID.
+///
+/// Unlike the uploads of [`MultipartWriter`], these can be started in one process and
+/// continued or completed in another.
+pub(crate) struct PyMultipartStore {
+    store: Arc<dyn MultipartStore>,
+    /// The prefix of a `GCSStore`, applied to paths as in all other operations on it
+    prefix: Option<Path>,
+}
+
+impl<'py> FromPyObject<'py> for PyMultipartStore {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let (store, prefix): (Arc<dyn MultipartStore>, _) =
+            if let Ok(store) = ob.downcast::<PyS3Store>() {
+                (store.get().region_aware().current(), None)
+            } else if let Ok(store) = ob.downcast::<PyGCSStore>() {
+                let store = store.get();
+                (store.as_ref().clone(), store.prefix().cloned())
+            } else if let Ok(store) = ob.downcast::<PyAzureStore>() {
+                (store.get().as_ref().clone(), None)
+            } else if let Ok(store) = ob.downcast::<PyMemoryStore>() {
+                (store.get().as_ref().clone(), None)
+            } else {
+                return Err(PyObjectStoreError::from(object_store::Error::NotSupported {
+                    source: "Multipart uploads by upload ID require an S3Store, GCSStore, \
+                             AzureStore or MemoryStore"
+                        .into(),
+                })
+                .into());
+            };
+        Ok(Self { store, prefix })
+    }
+}
+
+impl PyMultipartStore {
+    fn path(&self, path: String) -> Path {
+        prefixed_path(self.prefix.as_ref(), &Path::from(path))
+    }
+}
+
+#[pyfunction]
+pub(crate) fn create_multipart(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+) -> PyObjectStoreResult<String> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| Ok(runtime.block_on(store.store.create_multipart(&store.path(path)))?))
+}
+
+#[pyfunction]
+pub(crate) fn create_multipart_async(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        Ok(store
+            .store
+            .create_multipart(&store.path(path))
+            .await
+            .map_err(PyObjectStoreError::from)?)
+    })
+}
+
+#[pyfunction]
+pub(crate) fn put_part(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+    upload_id: String,
+    part_idx: usize,
+    data: PyBytes,
+) -> PyObjectStoreResult<String> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let part = runtime.block_on(store.store.put_part(
+            &store.path(path),
+            &upload_id,
+            part_idx,
+            data.into_inner().into(),
+        ))?;
+        Ok(part.content_id)
+    })
+}
+
+#[pyfunction]
+pub(crate) fn put_part_async(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+    upload_id: String,
+    part_idx: usize,
+    data: PyBytes,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let part = store
+            .store
+            .put_part(
+                &store.path(path),
+                &upload_id,
+                part_idx,
+                data.into_inner().into(),
+            )
+            .await
+            .map_err(PyObjectStoreError::from)?;
+        Ok(part.content_id)
+    })
+}
+
+fn part_ids(parts: Vec<String>) -> Vec<PartId> {
+    parts
+        .into_iter()
+        .map(|content_id| PartId { content_id })
+        .collect()
+}
+
+#[pyfunction]
+pub(crate) fn complete_multipart(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+    upload_id: String,
+    parts: Vec<String>,
+) -> PyObjectStoreResult<PyPutResult> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let result = runtime.block_on(store.store.complete_multipart(
+            &store.path(path),
+            &upload_id,
+            part_ids(parts),
+        ))?;
+        Ok(PyPutResult::new(result))
+    })
+}
+
+#[pyfunction]
+pub(crate) fn complete_multipart_async(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+    upload_id: String,
+    parts: Vec<String>,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let result = store
+            .store
+            .complete_multipart(&store.path(path), &upload_id, part_ids(parts))
+            .await
+            .map_err(PyObjectStoreError::from)?;
+        Ok(PyPutResult::new(result))
+    })
+}
+
+#[pyfunction]
+pub(crate) fn abort_multipart(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+    upload_id: String,
+) -> PyObjectStoreResult<()> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        Ok(runtime.block_on(store.store.abort_multipart(&store.path(path), &upload_id))?)
+    })
+}
+
+#[pyfunction]
+pub(crate) fn abort_multipart_async(
+    py: Python,
+    store: PyMultipartStore,
+    path: String,
+    upload_id: String,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        store
+            .store
+            .abort_multipart(&store.path(path), &upload_id)
+            .await
+            .map_err(PyObjectStoreError::from)?;
+        Ok(())
+    })
+}
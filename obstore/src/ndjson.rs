@@ -0,0 +1,201 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore, WriteMultipart};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use serde::Serialize;
+
+use crate::journal::JournaledStore;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The part size used when writing the export to an object store.
+const UPLOAD_CHUNK_SIZE: usize = 5 * 1024 * 1024;
+
+/// How many bytes of records are buffered before they're written out.
+const WRITE_SIZE: usize = 1024 * 1024;
+
+/// One NDJSON line, with the same keys as the Python `ObjectMeta` dict.
+#[derive(Serialize)]
+struct Record<'a> {
+    path: &'a str,
+    last_modified: String,
+    size: usize,
+    e_tag: Option<&'a str>,
+    version: Option<&'a str>,
+}
+
+fn write_record(buf: &mut Vec<u8>, meta: &ObjectMeta) -> PyResult<()> {
+    let record = Record {
+        path: meta.location.as_ref(),
+        last_modified: meta.last_modified.to_rfc3339(),
+        size: meta.size,
+        e_tag: meta.e_tag.as_deref(),
+        version: meta.version.as_deref(),
+    };
+    serde_json::to_writer(&mut *buf, &record)
+        .map_err(|err| PyValueError::new_err(format!("Could not serialize record: {err}")))?;
+    buf.push(b'\n');
+    Ok(())
+}
+
+/// Where the NDJSON export is written to, as given by the `dest` and `dest_store` arguments.
+enum DestPath {
+    File(PathBuf),
+    Store(Arc<dyn ObjectStore>, Path),
+}
+
+impl DestPath {
+    fn extract(dest: &Bound<PyAny>, dest_store: Option<JournaledStore>) -> PyResult<Self> {
+        match dest_store {
+            Some(store) => {
+                let path = dest.extract::<String>().map_err(|_| {
+                    PyTypeError::new_err("dest must be a str path within dest_store")
+                })?;
+                Ok(Self::Store(store.into_inner(), path.into()))
+            }
+            None => Ok(Self::File(dest.extract()?)),
+        }
+    }
+}
+
+/// The destination being written to.
+enum Destination {
+    /// Written to from blocking tasks, as the export may run on the async runtime
+    File(Arc<File>),
+    Store(WriteMultipart),
+}
+
+impl Destination {
+    async fn open(dest: DestPath) -> PyObjectStoreResult<Self> {
+        match dest {
+            DestPath::File(path) => {
+                let file = tokio::task::spawn_blocking(move || File::create(path))
+                    .await
+                    .map_err(io::Error::other)??;
+                Ok(Self::File(Arc::new(file)))
+            }
+            DestPath::Store(store, path) => {
+                let upload = store.put_multipart(&path).await?;
+                Ok(Self::Store(WriteMultipart::new_with_chunk_size(
+                    upload,
+                    UPLOAD_CHUNK_SIZE,
+                )))
+            }
+        }
+    }
+
+    async fn write(&mut self, buf: Vec<u8>) -> PyObjectStoreResult<()> {
+        match self {
+            Self::File(file) => {
+                let file = file.clone();
+                tokio::task::spawn_blocking(move || file.as_ref().write_all(&buf))
+                    .await
+                    .map_err(io::Error::other)??;
+            }
+            Self::Store(writer) => {
+                writer.wait_for_capacity(8).await?;
+                writer.write(&buf);
+            }
+        }
+        Ok(())
+    }
+
+    async fn finish(self) -> PyObjectStoreResult<()> {
+        if let Self::Store(writer) = self {
+            writer.finish().await?;
+        }
+        Ok(())
+    }
+
+    async fn abort(self) -> PyObjectStoreResult<()> {
+        if let Self::Store(writer) = self {
+            writer.abort().await?;
+        }
+        Ok(())
+    }
+}
+
+async fn export(
+    store: &Arc<dyn ObjectStore>,
+    prefix: Option<&Path>,
+    dest: &mut Destination,
+) -> PyObjectStoreResult<usize> {
+    let mut stream = store.list(prefix);
+    let mut count = 0;
+    let mut buf = vec![];
+    while let Some(meta) = stream.next().await {
+        write_record(&mut buf, &meta?)?;
+        count += 1;
+        if buf.len() >= WRITE_SIZE {
+            dest.write(std::mem::take(&mut buf)).await?;
+        }
+    }
+    if !buf.is_empty() {
+        dest.write(buf).await?;
+    }
+    Ok(count)
+}
+
+async fn list_to_ndjson_inner(
+    store: Arc<dyn ObjectStore>,
+    prefix: Option<Path>,
+    dest: DestPath,
+) -> PyObjectStoreResult<usize> {
+    let mut dest = Destination::open(dest).await?;
+
+    // Make sure to call abort if the multipart upload failed for any reason
+    match export(&store, prefix.as_ref(), &mut dest).await {
+        Ok(count) => {
+            dest.finish().await?;
+            Ok(count)
+        }
+        Err(err) => {
+            dest.abort().await?;
+            Err(err)
+        }
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, dest, *, dest_store = None))]
+pub(crate) fn list_to_ndjson(
+    py: Python,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    dest: &Bound<PyAny>,
+    dest_store: Option<JournaledStore>,
+) -> PyObjectStoreResult<usize> {
+    let dest = DestPath::extract(dest, dest_store)?;
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let count = runtime.block_on(list_to_ndjson_inner(
+            store.into_inner(),
+            prefix.map(|s| s.into()),
+            dest,
+        ))?;
+        Ok::<_, PyObjectStoreError>(count)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, dest, *, dest_store = None))]
+pub(crate) fn list_to_ndjson_async<'py>(
+    py: Python<'py>,
+    store: PyObjectStore,
+    prefix: Option<String>,
+    dest: &Bound<'py, PyAny>,
+    dest_store: Option<JournaledStore>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let dest = DestPath::extract(dest, dest_store)?;
+    future_into_py(py, async move {
+        let count =
+            list_to_ndjson_inner(store.into_inner(), prefix.map(|s| s.into()), dest).await?;
+        Ok(count)
+    })
+}
@@ -0,0 +1,306 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use futures::{StreamExt, TryStreamExt};
+use object_store::multipart::MultipartStore;
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetRange, ObjectMeta, ObjectStore, PutMode, PutPayload, PutResult, UpdateVersion,
+    WriteMultipart,
+};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_bytes::PyBytes;
+use pyo3_object_store::{PyObjectStoreResult, RegionAwareS3};
+
+use crate::copy::{copy_part, split_range, CopyStore, MAX_SINGLE_COPY, MIN_PART_SIZE};
+use crate::journal::Uploads;
+use crate::put::PyPutResult;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// The size of the parts unchanged ranges are copied in on S3.
+const COPY_PART_SIZE: usize = 512 * 1024 * 1024;
+
+/// Get `range` of the version of the object described by `meta`.
+///
+/// Pinning the e-tag makes the request fail instead of mixing two versions of the object if it
+/// is overwritten while being patched.
+fn range_options(meta: &ObjectMeta, range: Range<usize>) -> GetOptions {
+    GetOptions {
+        if_match: meta.e_tag.clone(),
+        range: Some(GetRange::Bounded(range)),
+        ..Default::default()
+    }
+}
+
+/// Read `range` of the object described by `meta` into memory.
+async fn read_range(
+    store: &dyn ObjectStore,
+    meta: &ObjectMeta,
+    range: Range<usize>,
+) -> object_store::Result<Bytes> {
+    if range.is_empty() {
+        return Ok(Bytes::new());
+    }
+    store
+        .get_opts(&meta.location, range_options(meta, range))
+        .await?
+        .bytes()
+        .await
+}
+
+/// Copy `range` of the object at `path` into `writer`.
+async fn copy_range(
+    store: &Arc<dyn ObjectStore>,
+    meta: &ObjectMeta,
+    range: Range<usize>,
+    writer: &mut WriteMultipart,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<()> {
+    if range.is_empty() {
+        return Ok(());
+    }
+    let mut stream = store
+        .get_opts(&meta.location, range_options(meta, range))
+        .await?
+        .into_stream();
+    while let Some(chunk) = stream.next().await {
+        writer.wait_for_capacity(max_concurrency).await?;
+        writer.put(chunk?);
+    }
+    Ok(())
+}
+
+/// Write the patched object into `writer`, streaming the unchanged ranges.
+async fn write_patched(
+    store: &Arc<dyn ObjectStore>,
+    meta: &ObjectMeta,
+    offset: usize,
+    data: Bytes,
+    writer: &mut WriteMultipart,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<()> {
+    let end = offset + data.len();
+    copy_range(store, meta, 0..offset, writer, max_concurrency).await?;
+    writer.wait_for_capacity(max_concurrency).await?;
+    writer.put(data);
+    copy_range(
+        store,
+        meta,
+        end..meta.size.max(end),
+        writer,
+        max_concurrency,
+    )
+    .await
+}
+
+/// A part of the multipart upload rewriting a patched object on S3.
+enum PatchPart {
+    /// A range of the existing object, copied server-side.
+    Copy(Range<usize>),
+    /// Bytes uploaded from the client.
+    Upload(Bytes),
+}
+
+/// Rewrite the object described by `meta` on S3, copying its unchanged ranges server-side
+/// with `UploadPartCopy` and uploading only the part holding the patch.
+///
+/// Every part but the last must be at least 5 MiB, so the uploaded part is padded with the
+/// unchanged bytes around the patch up to that size, which are read from the object.
+///
+/// Returns `None` if the uploaded part would be larger than S3 allows.
+async fn patch_s3(
+    s3: &RegionAwareS3,
+    meta: &ObjectMeta,
+    offset: usize,
+    data: Bytes,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<Option<PutResult>> {
+    let store = s3.current();
+    let end = offset + data.len();
+    let head = if offset >= MIN_PART_SIZE {
+        0..offset
+    } else {
+        0..0
+    };
+    let tail = end.max(meta.size.min(head.end + MIN_PART_SIZE))..meta.size;
+    if tail.start - head.end > MAX_SINGLE_COPY {
+        return Ok(None);
+    }
+
+    let mut patched = BytesMut::with_capacity(tail.start - head.end);
+    patched.extend_from_slice(&read_range(store.as_ref(), meta, head.end..offset).await?);
+    patched.extend_from_slice(&data);
+    patched.extend_from_slice(&read_range(store.as_ref(), meta, end..tail.start).await?);
+
+    let parts = split_range(head, COPY_PART_SIZE)
+        .into_iter()
+        .map(PatchPart::Copy)
+        .chain([PatchPart::Upload(patched.freeze())])
+        .chain(
+            split_range(tail, COPY_PART_SIZE)
+                .into_iter()
+                .map(PatchPart::Copy),
+        );
+
+    let upload = Uploads::s3(s3).create(&meta.location).await?;
+    let upload_id = &upload.upload_id;
+    let write_parts = async {
+        let mut parts = buffer_unordered(
+            parts.enumerate().map(|(index, part)| {
+                let store = &store;
+                async move {
+                    let part = match part {
+                        PatchPart::Copy(range) => {
+                            copy_part(s3, meta, &meta.location, upload_id, index, range).await?
+                        }
+                        PatchPart::Upload(bytes) => {
+                            store
+                                .put_part(&meta.location, upload_id, index, bytes.into())
+                                .await?
+                        }
+                    };
+                    Ok::<_, object_store::Error>((index, part))
+                }
+            }),
+            Concurrency::Fixed(max_concurrency),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+        parts.sort_unstable_by_key(|(index, _)| *index);
+        let parts = parts.into_iter().map(|(_, part)| part).collect();
+        store
+            .complete_multipart(&meta.location, upload_id, parts)
+            .await
+    };
+    match write_parts.await {
+        Ok(result) => {
+            upload.end().await;
+            Ok(Some(result))
+        }
+        Err(err) => {
+            if store
+                .abort_multipart(&meta.location, upload_id)
+                .await
+                .is_ok()
+            {
+                upload.end().await;
+            }
+            Err(err.into())
+        }
+    }
+}
+
+async fn patch_range_inner(
+    store: CopyStore,
+    path: Path,
+    offset: usize,
+    data: Bytes,
+    chunk_size: usize,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<PyPutResult> {
+    let CopyStore { store, s3 } = store;
+    let meta = store.head(&path).await?;
+    if offset > meta.size {
+        return Err(PyValueError::new_err(format!(
+            "Cannot patch at offset {offset}, past the end of {path} ({} bytes)",
+            meta.size
+        ))
+        .into());
+    }
+    let end = offset + data.len();
+
+    if meta.size <= chunk_size {
+        // Small objects are cheaper to rewrite in a single request
+        let existing = read_range(store.as_ref(), &meta, 0..meta.size).await?;
+        let mut buf = BytesMut::from(existing.as_ref());
+        buf.resize(meta.size.max(end), 0);
+        buf[offset..end].copy_from_slice(&data);
+        let payload = PutPayload::from(buf.freeze());
+        // Fails instead of losing a concurrent writer's update made since the object was read
+        let mode = match (&meta.e_tag, &meta.version) {
+            (None, None) => PutMode::Overwrite,
+            (e_tag, version) => PutMode::Update(UpdateVersion {
+                e_tag: e_tag.clone(),
+                version: version.clone(),
+            }),
+        };
+        let result = match store.put_opts(&path, payload.clone(), mode.into()).await {
+            // Stores without conditional updates, e.g. S3 without conditional puts configured
+            Err(object_store::Error::NotImplemented) => store.put(&path, payload).await?,
+            result => result?,
+        };
+        return Ok(PyPutResult::new(result));
+    }
+
+    if let Some(s3) = s3.filter(|s3| !s3.quirks().lacks_upload_part_copy) {
+        if let Some(result) = patch_s3(&s3, &meta, offset, data.clone(), max_concurrency).await? {
+            return Ok(PyPutResult::new(result));
+        }
+    }
+
+    // Object stores can't modify objects in place, and other stores can't copy parts of an
+    // object server-side, so stream the unchanged ranges through a new multipart upload
+    let upload = store.put_multipart(&path).await?;
+    let mut writer = WriteMultipart::new_with_chunk_size(upload, chunk_size);
+
+    // Make sure to call abort if the download or upload failed for any reason
+    match write_patched(&store, &meta, offset, data, &mut writer, max_concurrency).await {
+        Ok(()) => Ok(PyPutResult::new(writer.finish().await?)),
+        Err(err) => {
+            writer.abort().await?;
+            Err(err)
+        }
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, offset, data, *, chunk_size = 5242880, max_concurrency = 12))]
+pub(crate) fn patch_range(
+    py: Python,
+    store: CopyStore,
+    path: String,
+    offset: usize,
+    data: PyBytes,
+    chunk_size: usize,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<PyPutResult> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(patch_range_inner(
+            store,
+            path.into(),
+            offset,
+            data.into_inner(),
+            chunk_size,
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, offset, data, *, chunk_size = 5242880, max_concurrency = 12))]
+pub(crate) fn patch_range_async(
+    py: Python,
+    store: CopyStore,
+    path: String,
+    offset: usize,
+    data: PyBytes,
+    chunk_size: usize,
+    max_concurrency: usize,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let result = patch_range_inner(
+            store,
+            path.into(),
+            offset,
+            data.into_inner(),
+            chunk_size,
+            max_concurrency,
+        )
+        .await?;
+        Ok(result)
+    })
+}
@@ -1,6 +1,10 @@
 use object_store::path::Path;
-use pyo3::exceptions::PyTypeError;
+use object_store::ObjectStoreScheme;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_object_store::PyObjectStoreResult;
+use url::Url;
 
 pub(crate) enum PyPaths {
     One(Path),
@@ -23,3 +27,62 @@ impl<'py> FromPyObject<'py> for PyPaths {
         }
     }
 }
+
+/// The store that an object URL refers to, as returned by [`parse_object_url`].
+pub(crate) struct PyStoreSpec {
+    store: &'static str,
+    url: String,
+}
+
+impl<'py> IntoPyObject<'py> for PyStoreSpec {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("store", self.store)?;
+        dict.set_item("url", self.url)?;
+        Ok(dict)
+    }
+}
+
+#[pyfunction]
+pub(crate) fn parse_object_url(url: &str) -> PyObjectStoreResult<(PyStoreSpec, String)> {
+    let mut parsed = Url::parse(url)
+        .map_err(|err| PyValueError::new_err(format!("Invalid URL {url}: {err}")))?;
+    let (scheme, path) = ObjectStoreScheme::parse(&parsed).map_err(object_store::Error::from)?;
+    let store = match &scheme {
+        ObjectStoreScheme::AmazonS3 => "S3Store",
+        ObjectStoreScheme::GoogleCloudStorage => "GCSStore",
+        ObjectStoreScheme::MicrosoftAzure => "AzureStore",
+        ObjectStoreScheme::Http => "HTTPStore",
+        ObjectStoreScheme::Local => "LocalStore",
+        ObjectStoreScheme::Memory => "MemoryStore",
+        scheme => {
+            return Err(PyValueError::new_err(format!("Unsupported store {scheme:?}")).into())
+        }
+    };
+
+    let url = match scheme {
+        ObjectStoreScheme::Local => "file:///".to_string(),
+        ObjectStoreScheme::Memory => "memory:///".to_string(),
+        // The object path is always a suffix of the URL path, so strip that many segments to get
+        // the URL of the store itself
+        _ => {
+            let segments = parsed
+                .path_segments()
+                .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+                .unwrap_or_default();
+            let num_store_segments = segments.len().saturating_sub(path.parts().count());
+            let store_path = segments[..num_store_segments].join("/");
+            parsed.set_path(&store_path);
+            parsed.set_query(None);
+            parsed.set_fragment(None);
+            parsed.as_str().trim_end_matches('/').to_string()
+        }
+    };
+
+    let spec = PyStoreSpec { store, url };
+    Ok((spec, path.to_string()))
+}
@@ -0,0 +1,241 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use indexmap::IndexMap;
+use object_store::path::Path;
+use object_store::{CredentialProvider, ObjectStore};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_object_store::{
+    diagnose_permissions as diagnose, disable_permission_hints, enable_permission_hints,
+    status_code, PermissionReport, PyObjectStore, PyObjectStoreError, PyObjectStoreResult,
+    PyS3Store, RegionAwareS3,
+};
+use reqwest::{Client, Method};
+use url::Url;
+
+use crate::runtime::{future_into_py, get_runtime};
+use crate::sigv4::signed_request;
+
+/// Add hints about which permission is likely missing to permission errors, from probes issued
+/// once a request is denied.
+#[pyfunction]
+pub(crate) fn set_permission_hints(enabled: bool) {
+    if enabled {
+        enable_permission_hints();
+    } else {
+        disable_permission_hints();
+    }
+}
+
+#[pyfunction]
+pub(crate) fn diagnose_permissions(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+) -> PyObjectStoreResult<PermissionReport> {
+    let runtime = get_runtime(py)?;
+    let path = Path::from(path);
+    py.allow_threads(|| {
+        let report = runtime.block_on(diagnose(store.as_ref().as_ref(), &path));
+        Ok::<_, PyObjectStoreError>(report)
+    })
+}
+
+#[pyfunction]
+pub(crate) fn diagnose_permissions_async(
+    py: Python,
+    store: PyObjectStore,
+    path: String,
+) -> PyResult<Bound<PyAny>> {
+    let path = Path::from(path);
+    future_into_py(py, async move {
+        Ok(diagnose(store.as_ref().as_ref(), &path).await)
+    })
+}
+
+/// A path that is not expected to exist, used for cheap round trips in [`probe`].
+const PROBE_PATH: &str = "obstore-probe/does-not-exist";
+
+/// A store to probe, with the S3 store it is, if any, to look up the region of its bucket.
+pub(crate) struct ProbeStore {
+    store: Arc<dyn ObjectStore>,
+    s3: Option<Arc<RegionAwareS3>>,
+}
+
+impl<'py> FromPyObject<'py> for ProbeStore {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            store: ob.extract::<PyObjectStore>()?.into_inner(),
+            s3: ob
+                .downcast::<PyS3Store>()
+                .ok()
+                .map(|store| store.get().region_aware().clone()),
+        })
+    }
+}
+
+pub(crate) struct PyProbeResult {
+    reachable: bool,
+    authenticated: Option<bool>,
+    authorized: Option<bool>,
+    error: Option<String>,
+    region: Option<String>,
+    /// Round-trip latencies in milliseconds, sorted ascending
+    latencies: Vec<f64>,
+}
+
+impl PyProbeResult {
+    /// Nearest-rank percentile of the sorted latencies.
+    fn percentile(&self, p: f64) -> Option<f64> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = (p / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+}
+
+impl<'py> IntoPyObject<'py> for PyProbeResult {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let latency = if self.latencies.is_empty() {
+            None
+        } else {
+            let mut latency = IndexMap::with_capacity(4);
+            latency.insert("min", self.latencies.first().copied());
+            latency.insert("p50", self.percentile(50.0));
+            latency.insert("p90", self.percentile(90.0));
+            latency.insert("max", self.latencies.last().copied());
+            Some(latency)
+        };
+
+        let mut dict = IndexMap::with_capacity(6);
+        dict.insert(
+            "reachable",
+            self.reachable.into_pyobject(py)?.to_owned().into_any(),
+        );
+        dict.insert(
+            "authenticated",
+            self.authenticated.into_pyobject(py)?.into_any(),
+        );
+        dict.insert("authorized", self.authorized.into_pyobject(py)?.into_any());
+        dict.insert("error", self.error.into_pyobject(py)?.into_any());
+        dict.insert("region", self.region.into_pyobject(py)?.into_any());
+        dict.insert("latency_ms", latency.into_pyobject(py)?.into_any());
+        dict.into_pyobject(py)
+    }
+}
+
+/// The region of the bucket of `store`, from the `x-amz-bucket-region` header S3 returns for
+/// HeadBucket requests, even those it denies or redirects.
+async fn bucket_region(store: &RegionAwareS3) -> Option<String> {
+    let (bucket_url, region) = store.bucket_url();
+    let url = Url::parse(&bucket_url).ok()?;
+    let request = match store.current().credentials().get_credential().await {
+        Ok(credential) => signed_request(
+            &credential,
+            Method::HEAD,
+            url,
+            &region,
+            "s3",
+            vec![],
+            vec![],
+        ),
+        // The header is returned for anonymous requests too
+        Err(_) => Client::new().head(url),
+    };
+    let response = request.send().await.ok()?;
+    let region = response.headers().get("x-amz-bucket-region")?;
+    region.to_str().ok().map(String::from)
+}
+
+async fn probe_inner(store: ProbeStore, requests: usize) -> PyProbeResult {
+    let path = Path::from(PROBE_PATH);
+    let mut result = PyProbeResult {
+        reachable: false,
+        authenticated: None,
+        authorized: None,
+        error: None,
+        region: None,
+        latencies: Vec::with_capacity(requests),
+    };
+
+    for _ in 0..requests {
+        let start = Instant::now();
+        let response = store.store.head(&path).await;
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+
+        match response {
+            Ok(_) | Err(object_store::Error::NotFound { .. }) => {
+                result.authenticated = Some(true);
+                result.authorized = Some(true);
+            }
+            Err(err @ object_store::Error::PermissionDenied { .. }) => {
+                result.authenticated = Some(true);
+                result.authorized = Some(false);
+                result.error = Some(err.to_string());
+            }
+            Err(err @ object_store::Error::Unauthenticated { .. }) => {
+                result.authenticated = Some(false);
+                result.error = Some(err.to_string());
+            }
+            // The store responded, but with an error that says nothing about the credentials,
+            // such as a 500 or a 503
+            Err(err) if status_code(&err).is_some() => {
+                result.reachable = true;
+                result.latencies.push(elapsed);
+                result.error = Some(err.to_string());
+                break;
+            }
+            // Anything else, e.g. DNS or connection failures, means we never got a response,
+            // so there's no point in issuing further requests.
+            Err(err) => {
+                result.error = Some(err.to_string());
+                break;
+            }
+        }
+        result.reachable = true;
+        result.latencies.push(elapsed);
+    }
+
+    if let (true, Some(s3)) = (result.reachable, &store.s3) {
+        result.region = bucket_region(s3).await;
+    }
+    result.latencies.sort_by(f64::total_cmp);
+    result
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, *, requests = 5))]
+pub(crate) fn probe(
+    py: Python,
+    store: ProbeStore,
+    requests: usize,
+) -> PyObjectStoreResult<PyProbeResult> {
+    if requests == 0 {
+        return Err(PyValueError::new_err("requests must be greater than 0").into());
+    }
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        let result = runtime.block_on(probe_inner(store, requests));
+        Ok::<_, PyObjectStoreError>(result)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, *, requests = 5))]
+pub(crate) fn probe_async(
+    py: Python,
+    store: ProbeStore,
+    requests: usize,
+) -> PyResult<Bound<PyAny>> {
+    if requests == 0 {
+        return Err(PyValueError::new_err("requests must be greater than 0"));
+    }
+    future_into_py(py, async move { Ok(probe_inner(store, requests).await) })
+}
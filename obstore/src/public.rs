@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use object_store::aws::{AmazonS3Builder, AmazonS3ConfigKey};
+use object_store::azure::{AzureConfigKey, MicrosoftAzureBuilder};
+use object_store::http::HttpBuilder;
+use object_store::limit::LimitStore;
+use object_store::{ClientOptions, ObjectStore};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_object_store::{GuardrailStore, PyGuardrailStore, PyObjectStoreResult};
+
+use crate::remote::parse_url;
+
+/// Build an anonymous store for the public dataset at `url`.
+fn build_anonymous(url: &str, region: Option<String>) -> PyObjectStoreResult<Arc<dyn ObjectStore>> {
+    let parsed = parse_url(url)?;
+    let store: Arc<dyn ObjectStore> = match parsed.scheme() {
+        "s3" | "s3a" => Arc::new(
+            AmazonS3Builder::new()
+                .with_url(url)
+                .with_region(region.unwrap_or_else(|| "us-east-1".to_string()))
+                .with_config(AmazonS3ConfigKey::SkipSignature, "true")
+                // Requester-pays buckets bill the caller, which anonymous requests can't be
+                .with_config(AmazonS3ConfigKey::RequestPayer, "false")
+                .build()?,
+        ),
+        "az" | "adl" | "azure" | "abfs" | "abfss" => Arc::new(
+            MicrosoftAzureBuilder::new()
+                .with_url(url)
+                .with_config(AzureConfigKey::SkipSignature, "true")
+                .build()?,
+        ),
+        "http" | "https" => Arc::new(
+            HttpBuilder::new()
+                .with_url(url)
+                // Pass through the scheme chosen by the caller
+                .with_client_options(ClientOptions::new().with_allow_http(true))
+                .build()?,
+        ),
+        scheme => {
+            return Err(PyValueError::new_err(format!(
+                "open_public does not support {scheme}:// URLs. Objects in public GCS buckets can be read through https://storage.googleapis.com/<bucket> instead."
+            ))
+            .into())
+        }
+    };
+    Ok(store)
+}
+
+#[pyfunction]
+#[pyo3(signature = (url, *, region = None, max_requests = 8))]
+pub(crate) fn open_public(
+    url: &str,
+    region: Option<String>,
+    max_requests: usize,
+) -> PyObjectStoreResult<PyGuardrailStore> {
+    if max_requests == 0 {
+        return Err(PyValueError::new_err("max_requests must be greater than 0").into());
+    }
+    let store = build_anonymous(url, region)?;
+    let store = Arc::new(LimitStore::new(store, max_requests));
+    Ok(GuardrailStore::new(store, None, None, vec![], true, None).into())
+}
@@ -1,27 +1,36 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::fs::File;
 use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::Bytes;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
 use indexmap::IndexMap;
 use object_store::path::Path;
 use object_store::{
-    ObjectStore, PutMode, PutMultipartOpts, PutOptions, PutPayload, PutResult, UpdateVersion,
-    WriteMultipart,
+    ObjectMeta, ObjectStore, PutMode, PutMultipartOpts, PutOptions, PutPayload, PutResult,
+    UpdateVersion,
 };
-use pyo3::exceptions::{PyStopAsyncIteration, PyStopIteration, PyValueError};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use obstore_core::put::put_multipart_stream;
+use pyo3::exceptions::{PyKeyError, PyStopAsyncIteration, PyStopIteration, PyValueError};
 use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyList};
 use pyo3_bytes::PyBytes;
 use pyo3_file::PyFileLikeObject;
-use pyo3_object_store::{PyObjectStore, PyObjectStoreResult};
+use pyo3_object_store::clock::Backoff;
+use pyo3_object_store::{PyObjectStoreError, PyObjectStoreResult};
 
 use crate::attributes::PyAttributes;
-use crate::runtime::get_runtime;
+use crate::journal::JournaledStore;
+use crate::list::PyObjectMeta;
+use crate::runtime::{future_into_py, get_runtime};
+use crate::stats::{track, WithStats};
 use crate::tags::PyTagSet;
 
 pub(crate) struct PyPutMode(PutMode);
@@ -51,10 +60,12 @@ impl<'py> FromPyObject<'py> for PyUpdateVersion {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
         // Update to use derive(FromPyObject) when default is implemented:
         // https://github.com/PyO3/pyo3/issues/4643
-        let dict = ob.extract::<HashMap<String, Bound<PyAny>>>()?;
+        // `None` values are treated as missing, as in the modes of `PutResult.update_mode`
+        let dict = ob.extract::<HashMap<String, Option<Bound<PyAny>>>>()?;
+        let get = |key: &str| dict.get(key).cloned().flatten();
         Ok(Self(UpdateVersion {
-            e_tag: dict.get("e_tag").map(|x| x.extract()).transpose()?,
-            version: dict.get("version").map(|x| x.extract()).transpose()?,
+            e_tag: get("e_tag").map(|x| x.extract()).transpose()?,
+            version: get("version").map(|x| x.extract()).transpose()?,
         }))
     }
 }
@@ -208,6 +219,40 @@ pub(crate) enum PutInput {
 }
 
 impl PutInput {
+    /// The chunks of this input, with chunks of pull-based input read `chunk_size` bytes at a
+    /// time.
+    fn into_chunks(self, chunk_size: usize) -> BoxStream<'static, PyObjectStoreResult<Bytes>> {
+        // Match across pull, push, async push
+        match self {
+            Self::Pull(pull_reader) => {
+                stream::unfold(pull_reader, move |mut pull_reader| async move {
+                    let mut scratch_buffer = vec![0; chunk_size];
+                    match pull_reader.read(&mut scratch_buffer) {
+                        Ok(0) => None,
+                        Ok(read_size) => {
+                            scratch_buffer.truncate(read_size);
+                            Some((Ok(scratch_buffer.into()), pull_reader))
+                        }
+                        Err(err) => Some((Err(err.into()), pull_reader)),
+                    }
+                })
+                .boxed()
+            }
+            Self::SyncPush(push_reader) => stream::iter(push_reader).boxed(),
+            // Note: I believe that only one __anext__ call can happen at a time
+            Self::AsyncPush(push_reader) => {
+                stream::unfold(push_reader, |mut push_reader| async move {
+                    push_reader
+                        .next_chunk()
+                        .await
+                        .transpose()
+                        .map(|chunk| (chunk, push_reader))
+                })
+                .boxed()
+            }
+        }
+    }
+
     /// Whether to use multipart uploads.
     fn use_multipart(&mut self, chunk_size: usize) -> PyObjectStoreResult<bool> {
         match self {
@@ -218,6 +263,30 @@ impl PutInput {
         }
     }
 
+    /// Read the next buffer from the input, or `None` once the input is exhausted.
+    ///
+    /// Pull-based sources return at most `max_size` bytes. Push-based sources return whatever
+    /// buffer the Python iterator yields next.
+    pub(crate) async fn next_buffer(
+        &mut self,
+        max_size: usize,
+    ) -> PyObjectStoreResult<Option<Bytes>> {
+        match self {
+            Self::Pull(pull_source) => {
+                let mut scratch_buffer = vec![0; max_size];
+                let read_size = pull_source.read(&mut scratch_buffer)?;
+                if read_size == 0 {
+                    Ok(None)
+                } else {
+                    scratch_buffer.truncate(read_size);
+                    Ok(Some(scratch_buffer.into()))
+                }
+            }
+            Self::SyncPush(push_source) => push_source.next_chunk(),
+            Self::AsyncPush(push_source) => push_source.next_chunk().await,
+        }
+    }
+
     async fn read_all(&mut self) -> PyObjectStoreResult<PutPayload> {
         match self {
             Self::Pull(pull_source) => match pull_source {
@@ -275,27 +344,90 @@ impl<'py> FromPyObject<'py> for PutInput {
     }
 }
 
+/// The result of a put, with the e-tag and version of the new object.
+#[pyclass(name = "PutResult", frozen)]
 pub(crate) struct PyPutResult(PutResult);
 
-impl<'py> IntoPyObject<'py> for PyPutResult {
-    type Target = PyDict;
-    type Output = Bound<'py, PyDict>;
-    type Error = PyErr;
+impl PyPutResult {
+    pub(crate) fn new(result: PutResult) -> Self {
+        Self(result)
+    }
+}
 
-    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        let mut dict = IndexMap::with_capacity(2);
-        dict.insert("e_tag", self.0.e_tag.into_pyobject(py)?.into_any());
-        dict.insert("version", self.0.version.into_pyobject(py)?.into_any());
-        dict.into_pyobject(py)
+#[pymethods]
+impl PyPutResult {
+    #[getter]
+    fn e_tag(&self) -> Option<&str> {
+        self.0.e_tag.as_deref()
+    }
+
+    #[getter]
+    fn version(&self) -> Option<&str> {
+        self.0.version.as_deref()
     }
+
+    /// The mode to overwrite the new object with, if it hasn't changed since.
+    fn update_mode<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("e_tag", self.e_tag())?;
+        dict.set_item("version", self.version())?;
+        Ok(dict)
+    }
+
+    // Results used to be dicts, so they can still be indexed like one
+    fn __getitem__(&self, key: &str) -> PyResult<Option<&str>> {
+        match key {
+            "e_tag" => Ok(self.e_tag()),
+            "version" => Ok(self.version()),
+            _ => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __eq__(&self, other: &Bound<PyAny>) -> bool {
+        other
+            .downcast::<Self>()
+            .is_ok_and(|other| other.get().0 == self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PutResult(e_tag={:?}, version={:?})",
+            self.0.e_tag, self.0.version
+        )
+    }
+}
+
+/// Raise a `PreconditionError` for a put to `path` that expected the object to be at the
+/// `expected` version, with the e-tag and version the object has now, if it still exists.
+async fn update_precondition_error(
+    store: &dyn ObjectStore,
+    path: &Path,
+    err: object_store::Error,
+    expected: UpdateVersion,
+) -> PyObjectStoreError {
+    let actual = store.head(path).await.ok();
+    let err = PyErr::from(PyObjectStoreError::from(err));
+    Python::with_gil(|py| {
+        let value = err.value(py);
+        value.setattr(intern!(py, "path"), path.as_ref())?;
+        value.setattr(intern!(py, "expected_e_tag"), expected.e_tag)?;
+        value.setattr(intern!(py, "expected_version"), expected.version)?;
+        let (e_tag, version) = actual.map_or((None, None), |meta| (meta.e_tag, meta.version));
+        value.setattr(intern!(py, "actual_e_tag"), e_tag)?;
+        value.setattr(intern!(py, "actual_version"), version)?;
+        Ok::<_, PyErr>(())
+    })
+    .err()
+    .unwrap_or(err)
+    .into()
 }
 
 #[pyfunction]
-#[pyo3(signature = (store, path, file, *, attributes = None, tags = None, mode = None, use_multipart = None, chunk_size = 5242880, max_concurrency = 12))]
+#[pyo3(signature = (store, path, file, *, attributes = None, tags = None, mode = None, use_multipart = None, chunk_size = 5242880, max_concurrency = 12, return_stats = false))]
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn put(
     py: Python,
-    store: PyObjectStore,
+    store: JournaledStore,
     path: String,
     mut file: PutInput,
     attributes: Option<PyAttributes>,
@@ -304,7 +436,8 @@ pub(crate) fn put(
     use_multipart: Option<bool>,
     chunk_size: usize,
     max_concurrency: usize,
-) -> PyObjectStoreResult<PyPutResult> {
+    return_stats: bool,
+) -> PyObjectStoreResult<WithStats<PyPutResult>> {
     if matches!(file, PutInput::AsyncPush(_)) {
         return Err(
             PyValueError::new_err("Async input not allowed in 'put'. Use 'put_async'.").into(),
@@ -324,35 +457,33 @@ pub(crate) fn put(
         }
     }
 
+    let (store, stats) = track(store.into_inner(), return_stats);
     let runtime = get_runtime(py)?;
-    if use_multipart {
-        runtime.block_on(put_multipart_inner(
-            store.into_inner(),
-            &path.into(),
-            file,
-            chunk_size,
-            max_concurrency,
-            attributes,
-            tags,
-        ))
-    } else {
-        runtime.block_on(put_inner(
-            store.into_inner(),
-            &path.into(),
-            file,
-            attributes,
-            tags,
-            mode,
-        ))
-    }
+    // Reading from Python file-like objects and iterators re-acquires the GIL for each read
+    let result = py.allow_threads(|| {
+        if use_multipart {
+            runtime.block_on(put_multipart_inner(
+                store,
+                &path.into(),
+                file,
+                chunk_size,
+                max_concurrency,
+                attributes,
+                tags,
+            ))
+        } else {
+            runtime.block_on(put_inner(store, &path.into(), file, attributes, tags, mode))
+        }
+    })?;
+    Ok(WithStats::new(result, stats))
 }
 
 #[pyfunction]
-#[pyo3(signature = (store, path, file, *, attributes = None, tags = None, mode = None, use_multipart = None, chunk_size = 5242880, max_concurrency = 12))]
+#[pyo3(signature = (store, path, file, *, attributes = None, tags = None, mode = None, use_multipart = None, chunk_size = 5242880, max_concurrency = 12, return_stats = false))]
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn put_async(
     py: Python,
-    store: PyObjectStore,
+    store: JournaledStore,
     path: String,
     mut file: PutInput,
     attributes: Option<PyAttributes>,
@@ -361,6 +492,7 @@ pub(crate) fn put_async(
     use_multipart: Option<bool>,
     chunk_size: usize,
     max_concurrency: usize,
+    return_stats: bool,
 ) -> PyResult<Bound<PyAny>> {
     let mut use_multipart = if let Some(use_multipart) = use_multipart {
         use_multipart
@@ -375,10 +507,11 @@ pub(crate) fn put_async(
         }
     }
 
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    let (store, stats) = track(store.into_inner(), return_stats);
+    future_into_py(py, async move {
         let result = if use_multipart {
             put_multipart_inner(
-                store.into_inner(),
+                store,
                 &path.into(),
                 file,
                 chunk_size,
@@ -388,17 +521,9 @@ pub(crate) fn put_async(
             )
             .await?
         } else {
-            put_inner(
-                store.into_inner(),
-                &path.into(),
-                file,
-                attributes,
-                tags,
-                mode,
-            )
-            .await?
+            put_inner(store, &path.into(), file, attributes, tags, mode).await?
         };
-        Ok(result)
+        Ok(WithStats::new(result, stats))
     })
 }
 
@@ -421,9 +546,22 @@ async fn put_inner(
     if let Some(mode) = mode {
         opts.mode = mode.0;
     }
+    let expected = match &opts.mode {
+        PutMode::Update(expected) => Some(expected.clone()),
+        _ => None,
+    };
 
     let payload = reader.read_all().await?;
-    Ok(PyPutResult(store.put_opts(path, payload, opts).await?))
+    match store.put_opts(path, payload, opts).await {
+        Ok(result) => Ok(PyPutResult(result)),
+        Err(err @ object_store::Error::Precondition { .. }) => match expected {
+            Some(expected) => {
+                Err(update_precondition_error(store.as_ref(), path, err, expected).await)
+            }
+            None => Err(err.into()),
+        },
+        Err(err) => Err(err.into()),
+    }
 }
 
 async fn put_multipart_inner(
@@ -444,51 +582,317 @@ async fn put_multipart_inner(
         opts.tags = tags.into_inner();
     }
 
-    let upload = store.put_multipart_opts(path, opts).await?;
-    let mut writer = WriteMultipart::new_with_chunk_size(upload, chunk_size);
+    let pull = matches!(reader, PutInput::Pull(_));
+    let mut chunks = reader.into_chunks(chunk_size);
+
+    // Push-based sources pick multipart uploads without knowing their size, so upload those
+    // that end within the first part with a single request
+    let mut first_part = vec![];
+    let mut first_part_size = 0;
+    while !pull && first_part_size < chunk_size {
+        let Some(chunk) = chunks.next().await else {
+            let mut put_opts = PutOptions::default();
+            put_opts.attributes = opts.attributes;
+            put_opts.tags = opts.tags;
+            let payload = PutPayload::from_iter(first_part);
+            return Ok(PyPutResult(store.put_opts(path, payload, put_opts).await?));
+        };
+        let chunk = chunk?;
+        first_part_size += chunk.len();
+        first_part.push(chunk);
+    }
+    let chunks = stream::iter(first_part.into_iter().map(Ok)).chain(chunks);
+
+    Ok(PyPutResult(
+        put_multipart_stream(
+            store.as_ref(),
+            path,
+            chunks,
+            opts,
+            chunk_size,
+            max_concurrency,
+        )
+        .await?,
+    ))
+}
+
+/// How [`confirm_put`] polls for an upload to become visible.
+struct ConfirmOptions {
+    max_attempts: usize,
+    init_backoff: Duration,
+    max_backoff: Duration,
+}
 
-    // Make sure to call abort if the multipart upload failed for any reason
-    match write_multipart(&mut writer, reader, chunk_size, max_concurrency).await {
-        Ok(()) => Ok(PyPutResult(writer.finish().await?)),
-        Err(err) => {
-            writer.abort().await?;
-            Err(err)
+impl ConfirmOptions {
+    fn try_new(
+        max_attempts: usize,
+        init_backoff: Duration,
+        max_backoff: Duration,
+    ) -> PyResult<Self> {
+        if max_attempts == 0 {
+            return Err(PyValueError::new_err("max_attempts must be greater than 0"));
         }
+        Ok(Self {
+            max_attempts,
+            init_backoff,
+            max_backoff,
+        })
     }
 }
 
-async fn write_multipart(
-    writer: &mut WriteMultipart,
-    reader: PutInput,
-    chunk_size: usize,
-    max_concurrency: usize,
-) -> PyObjectStoreResult<()> {
-    // Match across pull, push, async push
-    match reader {
-        PutInput::Pull(mut pull_reader) => loop {
-            let mut scratch_buffer = vec![0; chunk_size];
-            let read_size = pull_reader.read(&mut scratch_buffer)?;
-            if read_size == 0 {
-                break;
-            } else {
-                writer.wait_for_capacity(max_concurrency).await?;
-                writer.write(&scratch_buffer[0..read_size]);
+/// Whether `meta` describes the object written with `result`.
+fn is_confirmed(meta: &ObjectMeta, result: &PutResult, expected_size: Option<usize>) -> bool {
+    let e_tag_matches = result.e_tag.is_none() || meta.e_tag == result.e_tag;
+    let version_matches = result.version.is_none() || meta.version == result.version;
+    let size_matches = expected_size.map_or(true, |size| meta.size == size);
+    e_tag_matches && version_matches && size_matches
+}
+
+/// Poll `head` until the object written with `result` is visible at `path`.
+async fn confirm_put(
+    store: &dyn ObjectStore,
+    path: &Path,
+    result: &PutResult,
+    expected_size: Option<usize>,
+    options: &ConfirmOptions,
+) -> PyObjectStoreResult<PyObjectMeta> {
+    let mut backoff = Backoff::new(options.init_backoff, options.max_backoff);
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let last_seen = match store.head(path).await {
+            Ok(meta) if is_confirmed(&meta, result, expected_size) => {
+                return Ok(PyObjectMeta::new(meta))
             }
-        },
-        PutInput::SyncPush(push_reader) => {
-            for buf in push_reader {
-                writer.wait_for_capacity(max_concurrency).await?;
-                writer.put(buf?);
+            Ok(meta) => format!("e_tag {:?} and size {}", meta.e_tag, meta.size),
+            Err(object_store::Error::NotFound { .. }) => "no object".to_string(),
+            Err(err) => return Err(err.into()),
+        };
+        if attempt >= options.max_attempts {
+            return Err(object_store::Error::Generic {
+                store: "put_and_confirm",
+                source: format!(
+                    "Uploaded {path} with e_tag {:?}, but found {last_seen} after {attempt} \
+                     attempts",
+                    result.e_tag
+                )
+                .into(),
             }
+            .into());
         }
-        PutInput::AsyncPush(mut push_reader) => {
-            // Note: I believe that only one __anext__ call can happen at a time
-            while let Some(buf) = push_reader.next_chunk().await? {
-                writer.wait_for_capacity(max_concurrency).await?;
-                writer.put(buf);
+        backoff.wait().await;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn put_and_confirm_inner(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    mut file: PutInput,
+    attributes: Option<PyAttributes>,
+    tags: Option<PyTagSet>,
+    use_multipart: bool,
+    chunk_size: usize,
+    max_concurrency: usize,
+    options: ConfirmOptions,
+) -> PyObjectStoreResult<PyObjectMeta> {
+    let expected_size = match &mut file {
+        PutInput::Pull(pull_source) => Some(pull_source.nbytes()?),
+        _ => None,
+    };
+    let result = if use_multipart {
+        put_multipart_inner(
+            store.clone(),
+            &path,
+            file,
+            chunk_size,
+            max_concurrency,
+            attributes,
+            tags,
+        )
+        .await?
+    } else {
+        put_inner(store.clone(), &path, file, attributes, tags, None).await?
+    };
+    confirm_put(store.as_ref(), &path, &result.0, expected_size, &options).await
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, file, *, attributes = None, tags = None, use_multipart = None, chunk_size = 5242880, max_concurrency = 12, max_attempts = 10, init_backoff = Duration::from_millis(100), max_backoff = Duration::from_secs(5)))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn put_and_confirm(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    mut file: PutInput,
+    attributes: Option<PyAttributes>,
+    tags: Option<PyTagSet>,
+    use_multipart: Option<bool>,
+    chunk_size: usize,
+    max_concurrency: usize,
+    max_attempts: usize,
+    init_backoff: Duration,
+    max_backoff: Duration,
+) -> PyObjectStoreResult<PyObjectMeta> {
+    if matches!(file, PutInput::AsyncPush(_)) {
+        return Err(PyValueError::new_err(
+            "Async input not allowed in 'put_and_confirm'. Use 'put_and_confirm_async'.",
+        )
+        .into());
+    }
+    let options = ConfirmOptions::try_new(max_attempts, init_backoff, max_backoff)?;
+    let use_multipart = match use_multipart {
+        Some(use_multipart) => use_multipart,
+        None => file.use_multipart(chunk_size)?,
+    };
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(put_and_confirm_inner(
+            store.into_inner(),
+            path.into(),
+            file,
+            attributes,
+            tags,
+            use_multipart,
+            chunk_size,
+            max_concurrency,
+            options,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, file, *, attributes = None, tags = None, use_multipart = None, chunk_size = 5242880, max_concurrency = 12, max_attempts = 10, init_backoff = Duration::from_millis(100), max_backoff = Duration::from_secs(5)))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn put_and_confirm_async(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    mut file: PutInput,
+    attributes: Option<PyAttributes>,
+    tags: Option<PyTagSet>,
+    use_multipart: Option<bool>,
+    chunk_size: usize,
+    max_concurrency: usize,
+    max_attempts: usize,
+    init_backoff: Duration,
+    max_backoff: Duration,
+) -> PyResult<Bound<PyAny>> {
+    let options = ConfirmOptions::try_new(max_attempts, init_backoff, max_backoff)?;
+    let use_multipart = match use_multipart {
+        Some(use_multipart) => use_multipart,
+        None => file.use_multipart(chunk_size)?,
+    };
+    future_into_py(py, async move {
+        Ok(put_and_confirm_inner(
+            store.into_inner(),
+            path.into(),
+            file,
+            attributes,
+            tags,
+            use_multipart,
+            chunk_size,
+            max_concurrency,
+            options,
+        )
+        .await?)
+    })
+}
+
+/// The result of each upload of [`put_many`], in the order of its items.
+pub(crate) struct PyPutManyResults(Vec<PyObjectStoreResult<PyPutResult>>);
+
+impl<'py> IntoPyObject<'py> for PyPutManyResults {
+    type Target = PyList;
+    type Output = Bound<'py, PyList>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let results = self
+            .0
+            .into_iter()
+            .map(|result| match result {
+                Ok(result) => Ok(result.into_pyobject(py)?.into_any()),
+                // Failed uploads are returned as their exception, rather than raised
+                Err(err) => Ok(PyErr::from(err).into_value(py).into_bound(py).into_any()),
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, results)
+    }
+}
+
+async fn put_many_inner(
+    store: Arc<dyn ObjectStore>,
+    items: Vec<(String, PutInput)>,
+    chunk_size: usize,
+    max_concurrency: Concurrency,
+) -> PyPutManyResults {
+    let uploads = items
+        .into_iter()
+        .enumerate()
+        .map(|(index, (path, mut file))| {
+            let store = store.clone();
+            async move {
+                let path = Path::from(path);
+                let result = match file.use_multipart(chunk_size) {
+                    // The objects are uploaded concurrently, so the parts of each one are
+                    // uploaded one at a time
+                    Ok(true) => {
+                        put_multipart_inner(store, &path, file, chunk_size, 1, None, None).await
+                    }
+                    Ok(false) => put_inner(store, &path, file, None, None, None).await,
+                    Err(err) => Err(err),
+                };
+                Ok::<_, Infallible>((index, result))
             }
-        }
+        });
+    let mut results = buffer_unordered(uploads, max_concurrency)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap_or_else(|never| match never {});
+    results.sort_unstable_by_key(|(index, _)| *index);
+    PyPutManyResults(results.into_iter().map(|(_, result)| result).collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, items, *, chunk_size = 5242880, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn put_many(
+    py: Python,
+    store: JournaledStore,
+    items: Vec<(String, PutInput)>,
+    chunk_size: usize,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<PyPutManyResults> {
+    if items
+        .iter()
+        .any(|(_, file)| matches!(file, PutInput::AsyncPush(_)))
+    {
+        return Err(PyValueError::new_err(
+            "Async input not allowed in 'put_many'. Use 'put_many_async'.",
+        )
+        .into());
     }
+    let runtime = get_runtime(py)?;
+    Ok(py.allow_threads(|| {
+        runtime.block_on(put_many_inner(
+            store.into_inner(),
+            items,
+            chunk_size,
+            max_concurrency,
+        ))
+    }))
+}
 
-    Ok(())
+#[pyfunction]
+#[pyo3(signature = (store, items, *, chunk_size = 5242880, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn put_many_async(
+    py: Python,
+    store: JournaledStore,
+    items: Vec<(String, PutInput)>,
+    chunk_size: usize,
+    max_concurrency: Concurrency,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        Ok(put_many_inner(store.into_inner(), items, chunk_size, max_concurrency).await)
+    })
 }
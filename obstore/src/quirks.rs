@@ -0,0 +1,29 @@
+use pyo3::prelude::*;
+use pyo3_object_store::Quirks;
+
+#[pyfunction]
+#[pyo3(signature = (
+    pattern,
+    *,
+    ignores_if_none_match = false,
+    emulate_if_none_match = false,
+    lacks_delete_objects = false,
+    lacks_upload_part_copy = false,
+))]
+pub(crate) fn register_quirks(
+    pattern: &str,
+    ignores_if_none_match: bool,
+    emulate_if_none_match: bool,
+    lacks_delete_objects: bool,
+    lacks_upload_part_copy: bool,
+) {
+    pyo3_object_store::register_quirks(
+        pattern,
+        Quirks {
+            ignores_if_none_match,
+            emulate_if_none_match,
+            lacks_delete_objects,
+            lacks_upload_part_copy,
+        },
+    );
+}
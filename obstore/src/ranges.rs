@@ -0,0 +1,328 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{GetOptions, GetRange, GetResult, GetResultPayload, ObjectStore};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_object_store::{InvalidRangeError, PyObjectStoreError, PyObjectStoreResult};
+
+/// The gap below which `object_store` merges ranges in `get_ranges`.
+///
+/// This mirrors `OBJECT_STORE_COALESCE_DEFAULT`, which isn't public.
+const DEFAULT_MAX_GAP: usize = 1024 * 1024;
+
+/// The number of merged ranges that `object_store` fetches at once in `get_ranges`.
+///
+/// This mirrors `OBJECT_STORE_COALESCE_PARALLEL`, which isn't public.
+const MAX_CONCURRENT_REQUESTS: usize = 10;
+
+/// Merge `ranges` that are at most `max_gap` bytes apart, and split requests larger than
+/// `max_request_size`.
+///
+/// Merging follows `object_store`'s `merge_ranges`: ranges are sorted by their start, and
+/// each range is merged into the previous request if it overlaps it or starts within
+/// `max_gap` of its end.
+fn plan(
+    mut ranges: Vec<Range<usize>>,
+    max_gap: usize,
+    max_request_size: Option<usize>,
+) -> Vec<Range<usize>> {
+    ranges.sort_unstable_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if let Some(last) = merged.last_mut() {
+            let within_gap = range
+                .start
+                .checked_sub(last.end)
+                .map_or(true, |gap| gap <= max_gap);
+            let end = last.end.max(range.end);
+            let fits = max_request_size.map_or(true, |max| end - last.start <= max);
+            if within_gap && fits {
+                last.end = end;
+                continue;
+            }
+        }
+        merged.push(range);
+    }
+
+    let Some(max_request_size) = max_request_size else {
+        return merged;
+    };
+    let mut planned = Vec::with_capacity(merged.len());
+    for range in merged {
+        let mut start = range.start;
+        while range.end - start > max_request_size {
+            planned.push(start..start + max_request_size);
+            start += max_request_size;
+        }
+        planned.push(start..range.end);
+    }
+    planned
+}
+
+#[pyfunction]
+#[pyo3(signature = (ranges, *, max_gap = DEFAULT_MAX_GAP, max_request_size = None))]
+pub(crate) fn plan_ranges(
+    ranges: Vec<(usize, usize)>,
+    max_gap: usize,
+    max_request_size: Option<usize>,
+) -> PyResult<Vec<(usize, usize)>> {
+    if max_request_size == Some(0) {
+        return Err(PyValueError::new_err("max_request_size must be positive"));
+    }
+    let ranges = ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start > end {
+                Err(PyValueError::new_err(format!(
+                    "Range start {start} is after its end {end}"
+                )))
+            } else {
+                Ok(start..end)
+            }
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(plan(ranges, max_gap, max_request_size)
+        .into_iter()
+        .map(|range| (range.start, range.end))
+        .collect())
+}
+
+/// Reject bounded ranges whose start is after their end.
+pub(crate) fn check_bounded(range: &Range<usize>) -> PyResult<()> {
+    if range.start > range.end {
+        return Err(InvalidRangeError::new_err(format!(
+            "Range start {} is after its end {}",
+            range.start, range.end
+        )));
+    }
+    Ok(())
+}
+
+/// Resolve `range` against an object of `size` bytes.
+///
+/// Ranges are truncated to the object, so that a range starting exactly at its end is empty.
+/// Only ranges starting past the end of the object are invalid.
+fn resolve(range: &GetRange, size: usize) -> PyObjectStoreResult<Range<usize>> {
+    let resolved = match range {
+        GetRange::Bounded(range) => range.start..range.end.min(size),
+        GetRange::Offset(offset) => *offset..size,
+        GetRange::Suffix(suffix) => size.saturating_sub(*suffix)..size,
+    };
+    if resolved.start > size {
+        return Err(InvalidRangeError::new_err(format!(
+            "Range starts at {}, past the end of the object of {size} bytes",
+            resolved.start
+        ))
+        .into());
+    }
+    Ok(resolved)
+}
+
+/// Whether `err` may have been caused by a range that the store couldn't satisfy.
+///
+/// Stores disagree on which ranges they can satisfy, and report the ones they can't in
+/// different ways (e.g. `416` responses or errors of their own), all of which surface as
+/// generic errors.
+fn may_be_range_error(err: &object_store::Error) -> bool {
+    matches!(err, object_store::Error::Generic { .. })
+}
+
+/// Some stores reject requests for empty ranges, so these are answered from the object's
+/// metadata instead.
+fn is_empty(range: &GetRange) -> bool {
+    match range {
+        GetRange::Bounded(range) => range.is_empty(),
+        GetRange::Offset(_) => false,
+        GetRange::Suffix(suffix) => *suffix == 0,
+    }
+}
+
+/// Pick the error to surface when the metadata request issued to resolve a range failed.
+fn head_error(
+    head_err: object_store::Error,
+    err: Option<object_store::Error>,
+) -> PyObjectStoreError {
+    // The error of the original request is more informative, if there was one
+    err.unwrap_or(head_err).into()
+}
+
+/// [`ObjectStore::get_opts`], with the range semantics documented for `get` normalized across
+/// stores.
+pub(crate) async fn get_opts(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+    options: GetOptions,
+) -> PyObjectStoreResult<GetResult> {
+    let range = match &options.range {
+        Some(range) if !options.head => range.clone(),
+        _ => return Ok(store.get_opts(path, options).await?),
+    };
+    if let GetRange::Bounded(range) = &range {
+        check_bounded(range)?;
+    }
+    let err = if is_empty(&range) {
+        None
+    } else {
+        match store.get_opts(path, options.clone()).await {
+            Err(err) if may_be_range_error(&err) => Some(err),
+            result => return Ok(result?),
+        }
+    };
+
+    let head_options = GetOptions {
+        range: None,
+        head: true,
+        ..options.clone()
+    };
+    let head = match store.get_opts(path, head_options).await {
+        Ok(head) => head,
+        Err(head_err) => return Err(head_error(head_err, err)),
+    };
+    let resolved = resolve(&range, head.meta.size)?;
+    if resolved.is_empty() {
+        return Ok(GetResult {
+            payload: GetResultPayload::Stream(futures::stream::empty().boxed()),
+            meta: head.meta,
+            range: resolved,
+            attributes: head.attributes,
+        });
+    }
+    match err {
+        // The range was already satisfiable, so the store failed for another reason
+        Some(err) if matches!(&range, GetRange::Bounded(range) if *range == resolved) => {
+            Err(err.into())
+        }
+        _ => {
+            let options = GetOptions {
+                range: Some(GetRange::Bounded(resolved)),
+                ..options
+            };
+            Ok(store.get_opts(path, options).await?)
+        }
+    }
+}
+
+/// [`ObjectStore::get_range`], with the range semantics documented for `get_range` normalized
+/// across stores.
+pub(crate) async fn get_range(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+    range: Range<usize>,
+) -> PyObjectStoreResult<Bytes> {
+    let mut ranges = get_ranges(store, path, &[range]).await?;
+    Ok(ranges.remove(0))
+}
+
+/// [`ObjectStore::get_ranges`], with the range semantics documented for `get_ranges`
+/// normalized across stores.
+pub(crate) async fn get_ranges(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+    ranges: &[Range<usize>],
+) -> PyObjectStoreResult<Vec<Bytes>> {
+    for range in ranges {
+        check_bounded(range)?;
+    }
+    let err = if ranges.iter().any(|range| range.is_empty()) {
+        None
+    } else {
+        let result = if let [range] = ranges {
+            store
+                .get_range(path, range.clone())
+                .await
+                .map(|buf| vec![buf])
+        } else {
+            store.get_ranges(path, ranges).await
+        };
+        match result {
+            Err(err) if may_be_range_error(&err) => Some(err),
+            result => return Ok(result?),
+        }
+    };
+
+    let meta = match store.head(path).await {
+        Ok(meta) => meta,
+        Err(head_err) => return Err(head_error(head_err, err)),
+    };
+    let resolved = ranges
+        .iter()
+        .map(|range| resolve(&GetRange::Bounded(range.clone()), meta.size))
+        .collect::<PyObjectStoreResult<Vec<_>>>()?;
+    if let Some(err) = err {
+        if resolved == ranges {
+            // The ranges were already satisfiable, so the store failed for another reason
+            return Err(err.into());
+        }
+    }
+
+    let fetch = resolved
+        .iter()
+        .filter(|range| !range.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut fetched = if fetch.is_empty() {
+        vec![]
+    } else {
+        store.get_ranges(path, &fetch).await?
+    }
+    .into_iter();
+    Ok(resolved
+        .iter()
+        .map(|range| {
+            if range.is_empty() {
+                Bytes::new()
+            } else {
+                fetched.next().unwrap()
+            }
+        })
+        .collect())
+}
+
+/// [`get_ranges`], with `options` such as a version or precondition applied to each request.
+///
+/// `object_store` has no `get_ranges` that takes options, so the ranges are merged as it would
+/// merge them, and each merged range is fetched with [`get_opts`].
+pub(crate) async fn get_ranges_opts(
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+    ranges: &[Range<usize>],
+    options: GetOptions,
+) -> PyObjectStoreResult<Vec<Bytes>> {
+    for range in ranges {
+        check_bounded(range)?;
+    }
+    let planned = plan(ranges.to_vec(), DEFAULT_MAX_GAP, None);
+    let fetched = futures::stream::iter(planned.iter().map(|range| {
+        let options = GetOptions {
+            range: Some(GetRange::Bounded(range.clone())),
+            ..options.clone()
+        };
+        async move {
+            let result = get_opts(store, path, options).await?;
+            let size = result.meta.size;
+            let start = result.range.start;
+            Ok::<_, PyObjectStoreError>((size, start, result.bytes().await?))
+        }
+    }))
+    .buffered(MAX_CONCURRENT_REQUESTS)
+    .try_collect::<Vec<_>>()
+    .await?;
+
+    ranges
+        .iter()
+        .map(|range| {
+            let index = planned
+                .iter()
+                .position(|planned| planned.start <= range.start && range.end <= planned.end)
+                .unwrap();
+            let (size, start, buf) = &fetched[index];
+            let resolved = resolve(&GetRange::Bounded(range.clone()), *size)?;
+            Ok(buf.slice(resolved.start - start..resolved.end - start))
+        })
+        .collect()
+}
@@ -0,0 +1,279 @@
+//! A block cache shared by the readers of the same version of an object.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetRange, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult,
+};
+
+/// The size of the blocks that objects are cached in, which is also the buffer size of the
+/// readers using the cache.
+pub(crate) const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// The total size of the blocks kept in the cache.
+const MAX_CACHE_SIZE: usize = 256 * 1024 * 1024;
+
+/// The number of blocks of a read that are fetched at once.
+const FETCH_CONCURRENCY: usize = 4;
+
+/// Identifies a version of an object.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FileKey {
+    /// The address of the store, so that objects of different stores aren't mixed up
+    store: usize,
+    path: Path,
+    e_tag: String,
+}
+
+type Fetch = Shared<BoxFuture<'static, Result<Bytes, Arc<object_store::Error>>>>;
+
+struct CachedBlock {
+    /// The request fetching the block, which holds the block once it has completed
+    fetch: Fetch,
+    size: usize,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct BlockCache {
+    blocks: HashMap<(FileKey, usize), CachedBlock>,
+    size: usize,
+    clock: u64,
+}
+
+impl BlockCache {
+    /// Get the block at `index`, starting to fetch it with `fetch` if it isn't cached.
+    fn get_or_fetch(
+        &mut self,
+        key: &FileKey,
+        index: usize,
+        size: usize,
+        fetch: impl FnOnce() -> BoxFuture<'static, Result<Bytes, Arc<object_store::Error>>>,
+    ) -> Fetch {
+        self.clock += 1;
+        let clock = self.clock;
+        if let Some(block) = self.blocks.get_mut(&(key.clone(), index)) {
+            block.last_used = clock;
+            return block.fetch.clone();
+        }
+        let fetch = fetch().shared();
+        self.blocks.insert(
+            (key.clone(), index),
+            CachedBlock {
+                fetch: fetch.clone(),
+                size,
+                last_used: clock,
+            },
+        );
+        self.size += size;
+        self.evict();
+        fetch
+    }
+
+    /// Drop the least recently used blocks until the cache fits in [`MAX_CACHE_SIZE`].
+    fn evict(&mut self) {
+        while self.size > MAX_CACHE_SIZE {
+            let Some(oldest) = self
+                .blocks
+                .iter()
+                .min_by_key(|(_, block)| block.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            let block = self.blocks.remove(&oldest).unwrap();
+            self.size -= block.size;
+        }
+    }
+
+    /// Drop a block whose fetch failed, so that it's fetched again by the next read.
+    fn remove_failed(&mut self, key: &FileKey, index: usize) {
+        let key = (key.clone(), index);
+        let failed = self
+            .blocks
+            .get(&key)
+            .is_some_and(|block| matches!(block.fetch.peek(), Some(Err(_))));
+        if failed {
+            let block = self.blocks.remove(&key).unwrap();
+            self.size -= block.size;
+        }
+    }
+}
+
+fn cache() -> &'static Mutex<BlockCache> {
+    static CACHE: OnceLock<Mutex<BlockCache>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// An [`ObjectStore`] wrapper serving range reads of one object from the shared block cache.
+///
+/// Blocks are requested with the e-tag of the object as an `If-Match` condition, so that the
+/// cache never mixes the data of different versions of an object. Requests for any other path
+/// are passed through.
+#[derive(Debug)]
+pub(crate) struct SharedCacheStore {
+    inner: Arc<dyn ObjectStore>,
+    key: FileKey,
+    size: usize,
+}
+
+impl SharedCacheStore {
+    /// Wrap `inner` to read the object described by `meta` through the cache, if it has an
+    /// e-tag to identify its version.
+    pub(crate) fn wrap(inner: Arc<dyn ObjectStore>, meta: &ObjectMeta) -> Arc<dyn ObjectStore> {
+        let Some(e_tag) = meta.e_tag.clone() else {
+            return inner;
+        };
+        let key = FileKey {
+            store: Arc::as_ptr(&inner) as *const () as usize,
+            path: meta.location.clone(),
+            e_tag,
+        };
+        Arc::new(Self {
+            inner,
+            key,
+            size: meta.size,
+        })
+    }
+
+    fn block(&self, index: usize) -> Fetch {
+        let start = index * BLOCK_SIZE;
+        let range = start..(start + BLOCK_SIZE).min(self.size);
+        let inner = self.inner.clone();
+        let path = self.key.path.clone();
+        let e_tag = self.key.e_tag.clone();
+        cache()
+            .lock()
+            .unwrap()
+            .get_or_fetch(&self.key, index, range.len(), move || {
+                async move {
+                    let options = GetOptions {
+                        if_match: Some(e_tag),
+                        range: Some(GetRange::Bounded(range)),
+                        ..Default::default()
+                    };
+                    inner.get_opts(&path, options).await?.bytes().await
+                }
+                .map(|result| result.map_err(Arc::new))
+                .boxed()
+            })
+    }
+
+    async fn read_block(&self, index: usize) -> object_store::Result<Bytes> {
+        self.block(index).await.map_err(|err| {
+            cache().lock().unwrap().remove_failed(&self.key, index);
+            object_store::Error::Generic {
+                store: "SharedCache",
+                source: Box::new(err),
+            }
+        })
+    }
+}
+
+impl Display for SharedCacheStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SharedCacheStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for SharedCacheStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        if *location != self.key.path || range.end > self.size || range.is_empty() {
+            return self.inner.get_range(location, range).await;
+        }
+        let first = range.start / BLOCK_SIZE;
+        let last = (range.end - 1) / BLOCK_SIZE;
+        let blocks = futures::stream::iter(first..=last)
+            .map(|index| self.read_block(index))
+            .buffered(FETCH_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        if let [block] = blocks.as_slice() {
+            let offset = first * BLOCK_SIZE;
+            return Ok(block.slice(range.start - offset..range.end - offset));
+        }
+        let mut buffer = BytesMut::with_capacity(range.len());
+        for (index, block) in (first..=last).zip(blocks) {
+            let offset = index * BLOCK_SIZE;
+            let start = range.start.max(offset) - offset;
+            let end = range.end.min(offset + block.len()) - offset;
+            buffer.extend_from_slice(&block[start..end]);
+        }
+        Ok(buffer.freeze())
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
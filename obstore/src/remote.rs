@@ -0,0 +1,396 @@
+//! Ingest data from arbitrary HTTP(S) URLs into a store.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use futures::stream::TryStreamExt;
+use glob::{MatchOptions, Pattern};
+
+use object_store::path::Path;
+use object_store::{ObjectStore, WriteMultipart};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_object_store::{PyObjectStoreError, PyObjectStoreResult};
+use reqwest::{Client, Response, StatusCode};
+use serde::Deserialize;
+use url::Url;
+
+use crate::journal::JournaledStore;
+use crate::put::PyPutResult;
+use crate::runtime::{future_into_py, get_runtime};
+
+const STORE: &str = "HTTP";
+
+/// Match `include_glob` like a shell would, so that `*` doesn't cross directories.
+const GLOB_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+pub(crate) fn parse_url(url: &str) -> PyResult<Url> {
+    Url::parse(url).map_err(|err| PyValueError::new_err(format!("Invalid URL {url}: {err}")))
+}
+
+/// Convert a failed download into the closest matching [`object_store::Error`].
+fn download_error(url: &Url, err: reqwest::Error) -> object_store::Error {
+    match err.status() {
+        Some(StatusCode::NOT_FOUND) => object_store::Error::NotFound {
+            path: url.to_string(),
+            source: Box::new(err),
+        },
+        Some(StatusCode::UNAUTHORIZED) => object_store::Error::Unauthenticated {
+            path: url.to_string(),
+            source: Box::new(err),
+        },
+        Some(StatusCode::FORBIDDEN) => object_store::Error::PermissionDenied {
+            path: url.to_string(),
+            source: Box::new(err),
+        },
+        _ => object_store::Error::Generic {
+            store: STORE,
+            source: Box::new(err),
+        },
+    }
+}
+
+/// Issue a GET request for `url`, failing on any non-success status.
+pub(crate) async fn fetch(client: &Client, url: &Url) -> object_store::Result<Response> {
+    client
+        .get(url.clone())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| download_error(url, err))
+}
+
+/// Stream the body of `response` into `writer`, one network chunk at a time.
+async fn write_response(
+    url: &Url,
+    mut response: Response,
+    writer: &mut WriteMultipart,
+    max_concurrency: usize,
+) -> object_store::Result<()> {
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| download_error(url, err))?
+    {
+        writer.wait_for_capacity(max_concurrency).await?;
+        writer.put(chunk);
+    }
+    Ok(())
+}
+
+/// Download `url` into `path`, streaming through a multipart upload when `streaming` is set.
+pub(crate) async fn put_from_url_inner(
+    client: &Client,
+    store: &Arc<dyn ObjectStore>,
+    path: &Path,
+    url: &Url,
+    streaming: bool,
+    chunk_size: usize,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<PyPutResult> {
+    let response = fetch(client, url).await?;
+
+    // Small responses of known length aren't worth the extra requests of a multipart upload
+    let fits_in_chunk = response
+        .content_length()
+        .is_some_and(|len| len <= chunk_size as u64);
+    if !streaming || fits_in_chunk {
+        let body = response
+            .bytes()
+            .await
+            .map_err(|err| download_error(url, err))?;
+        return Ok(PyPutResult::new(store.put(path, body.into()).await?));
+    }
+
+    let upload = store.put_multipart(path).await?;
+    let mut writer = WriteMultipart::new_with_chunk_size(upload, chunk_size);
+
+    // Make sure to call abort if the download or upload failed for any reason
+    match write_response(url, response, &mut writer, max_concurrency).await {
+        Ok(()) => Ok(PyPutResult::new(writer.finish().await?)),
+        Err(err) => {
+            writer.abort().await?;
+            Err(err.into())
+        }
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, url, *, streaming = true, chunk_size = 5242880, max_concurrency = 12))]
+pub(crate) fn put_from_url(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    url: String,
+    streaming: bool,
+    chunk_size: usize,
+    max_concurrency: usize,
+) -> PyObjectStoreResult<PyPutResult> {
+    let url = parse_url(&url)?;
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(put_from_url_inner(
+            &Client::new(),
+            store.as_ref(),
+            &path.into(),
+            &url,
+            streaming,
+            chunk_size,
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, url, *, streaming = true, chunk_size = 5242880, max_concurrency = 12))]
+pub(crate) fn put_from_url_async(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    url: String,
+    streaming: bool,
+    chunk_size: usize,
+    max_concurrency: usize,
+) -> PyResult<Bound<PyAny>> {
+    let url = parse_url(&url)?;
+    future_into_py(py, async move {
+        let result = put_from_url_inner(
+            &Client::new(),
+            store.as_ref(),
+            &path.into(),
+            &url,
+            streaming,
+            chunk_size,
+            max_concurrency,
+        )
+        .await?;
+        Ok(result)
+    })
+}
+
+/// An entry of a JSON directory index, in the format of nginx's `autoindex_format json`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonIndexEntry {
+    Name(String),
+    Entry {
+        name: String,
+        #[serde(rename = "type", default)]
+        kind: Option<String>,
+    },
+}
+
+impl JsonIndexEntry {
+    /// The entry as a link relative to the index, with a trailing slash for directories.
+    fn into_href(self) -> String {
+        match self {
+            Self::Name(name) => name,
+            Self::Entry { name, kind } if kind.as_deref() == Some("directory") => {
+                format!("{}/", name.trim_end_matches('/'))
+            }
+            Self::Entry { name, .. } => name,
+        }
+    }
+}
+
+/// Extract the targets of all `href` attributes in an HTML document.
+///
+/// This is intentionally simple: directory listings generated by web servers are regular enough
+/// that a full HTML parser isn't needed.
+fn html_hrefs(html: &str) -> Vec<String> {
+    let mut hrefs = vec![];
+    let lower = html.to_ascii_lowercase();
+    let mut pos = 0;
+    while let Some(found) = lower[pos..].find("href=") {
+        let start = pos + found + "href=".len();
+        let rest = &html[start..];
+        let (href, len) = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => match rest[1..].find(quote) {
+                Some(end) => (&rest[1..end + 1], end + 2),
+                None => break,
+            },
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                (&rest[..end], end)
+            }
+        };
+        hrefs.push(href.replace("&amp;", "&"));
+        pos = start + len;
+    }
+    hrefs
+}
+
+/// Parse the links of a directory index, which may be either HTML or JSON.
+async fn index_links(url: &Url, response: Response) -> PyObjectStoreResult<Vec<String>> {
+    let is_json = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("json"));
+    let body = response
+        .text()
+        .await
+        .map_err(|err| download_error(url, err))?;
+
+    if is_json || body.trim_start().starts_with('[') {
+        let entries: Vec<JsonIndexEntry> = serde_json::from_str(&body).map_err(|err| {
+            PyValueError::new_err(format!("Could not parse JSON index at {url}: {err}"))
+        })?;
+        Ok(entries.into_iter().map(JsonIndexEntry::into_href).collect())
+    } else {
+        Ok(html_hrefs(&body))
+    }
+}
+
+/// A file found while crawling an index.
+struct MirrorEntry {
+    url: Url,
+    /// The path of the file relative to the root index, percent-decoded
+    relative: Path,
+}
+
+/// Find all files below `root`, following links to subdirectories.
+///
+/// Links that point outside of `root`, such as parent directories or column sorting links of
+/// Apache-style listings, are ignored.
+async fn crawl_index(client: &Client, root: &Url) -> PyObjectStoreResult<Vec<MirrorEntry>> {
+    let mut root = root.clone();
+    if !root.path().ends_with('/') {
+        root.set_path(&format!("{}/", root.path()));
+    }
+
+    let mut files = vec![];
+    let mut visited = HashSet::from([root.clone()]);
+    let mut pending = vec![root.clone()];
+    while let Some(dir) = pending.pop() {
+        let response = fetch(client, &dir).await?;
+        for href in index_links(&dir, response).await? {
+            let Ok(mut link) = dir.join(&href) else {
+                continue;
+            };
+            if link.query().is_some() {
+                continue;
+            }
+            link.set_fragment(None);
+            let Some(relative) = link.as_str().strip_prefix(root.as_str()) else {
+                continue;
+            };
+            if relative.is_empty() {
+                continue;
+            }
+
+            if relative.ends_with('/') {
+                if visited.insert(link.clone()) {
+                    pending.push(link);
+                }
+            } else if let Ok(relative) = Path::from_url_path(relative) {
+                files.push(MirrorEntry {
+                    url: link,
+                    relative,
+                });
+            }
+        }
+    }
+    Ok(files)
+}
+
+async fn mirror_http_inner(
+    index_url: Url,
+    store: Arc<dyn ObjectStore>,
+    prefix: Option<Path>,
+    include_glob: Option<Pattern>,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<Vec<String>> {
+    let client = Client::new();
+    let files = crawl_index(&client, &index_url).await?;
+
+    let uploads = files
+        .into_iter()
+        .filter(|entry| {
+            include_glob.as_ref().map_or(true, |pattern| {
+                pattern.matches_with(entry.relative.as_ref(), GLOB_OPTIONS)
+            })
+        })
+        .map(|entry| {
+            let path = match &prefix {
+                Some(prefix) => Path::from_iter(prefix.parts().chain(entry.relative.parts())),
+                None => entry.relative,
+            };
+            let (client, store) = (&client, &store);
+            async move {
+                put_from_url_inner(client, store, &path, &entry.url, true, 5 * 1024 * 1024, 2)
+                    .await?;
+                Ok::<_, PyObjectStoreError>(path.to_string())
+            }
+        });
+    let mut paths: Vec<String> = buffer_unordered(uploads, max_concurrency)
+        .try_collect()
+        .await?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn parse_glob(include_glob: Option<String>) -> PyResult<Option<Pattern>> {
+    include_glob
+        .map(|glob| {
+            Pattern::new(&glob)
+                .map_err(|err| PyValueError::new_err(format!("Invalid glob pattern {glob}: {err}")))
+        })
+        .transpose()
+}
+
+#[pyfunction]
+#[pyo3(signature = (index_url, store, prefix = None, *, include_glob = None, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn mirror_http(
+    py: Python,
+    index_url: String,
+    store: JournaledStore,
+    prefix: Option<String>,
+    include_glob: Option<String>,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<Vec<String>> {
+    let index_url = parse_url(&index_url)?;
+    let include_glob = parse_glob(include_glob)?;
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(mirror_http_inner(
+            index_url,
+            store.into_inner(),
+            prefix.map(|s| s.into()),
+            include_glob,
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (index_url, store, prefix = None, *, include_glob = None, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn mirror_http_async(
+    py: Python,
+    index_url: String,
+    store: JournaledStore,
+    prefix: Option<String>,
+    include_glob: Option<String>,
+    max_concurrency: Concurrency,
+) -> PyResult<Bound<PyAny>> {
+    let index_url = parse_url(&index_url)?;
+    let include_glob = parse_glob(include_glob)?;
+    future_into_py(py, async move {
+        let paths = mirror_http_inner(
+            index_url,
+            store.into_inner(),
+            prefix.map(|s| s.into()),
+            include_glob,
+            max_concurrency,
+        )
+        .await?;
+        Ok(paths)
+    })
+}
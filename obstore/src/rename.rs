@@ -2,7 +2,7 @@ use object_store::ObjectStore;
 use pyo3::prelude::*;
 use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
 
-use crate::runtime::get_runtime;
+use crate::runtime::{future_into_py, get_runtime};
 
 #[pyfunction]
 #[pyo3(signature = (store, from_, to, *, overwrite = true))]
@@ -38,7 +38,7 @@ pub(crate) fn rename_async(
 ) -> PyResult<Bound<PyAny>> {
     let from_ = from_.into();
     let to = to.into();
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    future_into_py(py, async move {
         let fut = if overwrite {
             store.as_ref().rename(&from_, &to)
         } else {
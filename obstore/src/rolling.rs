@@ -0,0 +1,424 @@
+//! Rolling uploads of appended records, for shipping logs and events to a bucket.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use futures::TryStreamExt;
+use object_store::path::Path;
+use object_store::{ObjectStore, PutMode, PutOptions, PutPayload, PutPayloadMut};
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use pyo3_bytes::PyBytes;
+use pyo3_object_store::clock::{self, Instant};
+use pyo3_object_store::{PyObjectStore, PyObjectStoreResult};
+use tokio::sync::Mutex;
+
+use crate::runtime::{future_into_py, get_runtime};
+use crate::shutdown::{register_writer, Finalize, Finalized};
+
+/// The number of names tried for an object before giving up, if other writers keep creating
+/// objects with the same names.
+const MAX_NAME_ATTEMPTS: usize = 100;
+
+/// How the objects published by a [`RollingWriter`] are named.
+pub(crate) enum Naming {
+    /// The UTC time of the first record in the object
+    Timestamp,
+    /// A zero-padded counter, continuing after the highest existing one under the prefix
+    Sequence,
+    /// A Python callable of `(index, opened_at)` returning the name
+    Callable(PyObject),
+}
+
+impl<'py> FromPyObject<'py> for Naming {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(s) = ob.downcast::<PyString>() {
+            match s.to_cow()?.as_ref() {
+                "timestamp" => Ok(Self::Timestamp),
+                "sequence" => Ok(Self::Sequence),
+                other => Err(PyValueError::new_err(format!(
+                    "Unknown naming strategy {other}, expected 'timestamp' or 'sequence'"
+                ))),
+            }
+        } else if ob.is_callable() {
+            Ok(Self::Callable(ob.clone().unbind()))
+        } else {
+            Err(PyValueError::new_err(
+                "naming must be 'timestamp', 'sequence' or a callable",
+            ))
+        }
+    }
+}
+
+/// Progress of a [`RollingWriter`], readable while a write holds the writer's lock.
+#[derive(Default)]
+pub(crate) struct RollingStats {
+    pub(crate) bytes_buffered: AtomicUsize,
+    pub(crate) objects_published: AtomicUsize,
+}
+
+/// Buffers appended records and publishes them as a new object under a prefix once the buffer
+/// reaches `max_size` or has been open for `max_interval`.
+///
+/// Each object is uploaded in a single request, so it becomes visible with all its records or
+/// not at all.
+pub(crate) struct RollingWriter {
+    store: Arc<dyn ObjectStore>,
+    prefix: String,
+    suffix: String,
+    max_size: usize,
+    max_interval: Option<Duration>,
+    naming: Naming,
+    buffer: PutPayloadMut,
+    /// When the first record of the current buffer was written
+    opened: Option<(Instant, DateTime<Utc>)>,
+    /// The next sequence number, found by listing the prefix on the first roll
+    next_sequence: Option<u64>,
+    /// Whether the store supports `PutMode::Create`, used to avoid overwriting objects
+    create_supported: bool,
+    /// Written at the start of every object, e.g. the header row of a CSV file
+    header: Option<Bytes>,
+    pub(crate) stats: Arc<RollingStats>,
+}
+
+impl RollingWriter {
+    pub(crate) fn try_new(
+        store: PyObjectStore,
+        prefix: String,
+        max_size: usize,
+        max_interval: Option<Duration>,
+        naming: Option<Naming>,
+        suffix: String,
+    ) -> PyResult<Self> {
+        if max_size == 0 {
+            return Err(PyValueError::new_err("max_size must be greater than 0"));
+        }
+        if max_interval.is_some_and(|max_interval| max_interval.is_zero()) {
+            return Err(PyValueError::new_err("max_interval must be greater than 0"));
+        }
+        Ok(Self {
+            store: store.into_inner(),
+            prefix: prefix.trim_end_matches('/').to_string(),
+            suffix,
+            max_size,
+            max_interval,
+            naming: naming.unwrap_or(Naming::Timestamp),
+            buffer: PutPayloadMut::new(),
+            opened: None,
+            next_sequence: None,
+            create_supported: true,
+            header: None,
+            stats: Default::default(),
+        })
+    }
+
+    /// Set the data written at the start of every object, unless it has already been set.
+    pub(crate) fn set_header(&mut self, header: Bytes) {
+        if self.header.is_none() {
+            self.header = Some(header);
+        }
+    }
+
+    fn is_due(&self) -> bool {
+        match (self.max_interval, self.opened) {
+            (Some(max_interval), Some((opened, _))) => opened.elapsed() >= max_interval,
+            _ => false,
+        }
+    }
+
+    pub(crate) async fn write(&mut self, buf: Bytes) -> PyObjectStoreResult<Option<String>> {
+        let mut published = None;
+        if self.is_due() {
+            published = self.roll().await?;
+        }
+        if self.opened.is_none() {
+            self.opened = Some((Instant::now(), clock::now()));
+            if let Some(header) = &self.header {
+                self.buffer.push(header.clone());
+            }
+        }
+        self.buffer.push(buf);
+        if self.buffer.content_length() >= self.max_size {
+            published = self.roll().await?;
+        }
+        self.stats
+            .bytes_buffered
+            .store(self.buffer.content_length(), Ordering::Relaxed);
+        Ok(published)
+    }
+
+    /// Publish the buffered records as a new object, returning its path.
+    pub(crate) async fn roll(&mut self) -> PyObjectStoreResult<Option<String>> {
+        let Some((_, opened_at)) = self.opened else {
+            return Ok(None);
+        };
+        let payload = std::mem::take(&mut self.buffer).freeze();
+        match self.publish(payload.clone(), opened_at).await {
+            Ok(path) => {
+                self.opened = None;
+                self.stats.bytes_buffered.store(0, Ordering::Relaxed);
+                self.stats.objects_published.fetch_add(1, Ordering::Relaxed);
+                Ok(Some(path.to_string()))
+            }
+            Err(err) => {
+                // Keep the records, so that a later write or flush retries publishing them
+                for chunk in &payload {
+                    self.buffer.push(chunk.clone());
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn publish(
+        &mut self,
+        payload: PutPayload,
+        opened_at: DateTime<Utc>,
+    ) -> PyObjectStoreResult<Path> {
+        for attempt in 0..MAX_NAME_ATTEMPTS {
+            let path = self.path(attempt, opened_at).await?;
+            match self.put(&path, payload.clone()).await {
+                Ok(()) => {
+                    if let Some(sequence) = self.next_sequence.as_mut() {
+                        *sequence += 1;
+                    }
+                    return Ok(path);
+                }
+                Err(object_store::Error::AlreadyExists { .. })
+                    if !matches!(self.naming, Naming::Callable(_)) =>
+                {
+                    if let Some(sequence) = self.next_sequence.as_mut() {
+                        *sequence += 1;
+                    }
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Err(PyIOError::new_err(format!(
+            "Could not find an unused object name under {} after {MAX_NAME_ATTEMPTS} attempts",
+            self.prefix
+        ))
+        .into())
+    }
+
+    /// The path to publish the current buffer to, on the given attempt.
+    async fn path(
+        &mut self,
+        attempt: usize,
+        opened_at: DateTime<Utc>,
+    ) -> PyObjectStoreResult<Path> {
+        let name = match &self.naming {
+            Naming::Timestamp => {
+                let timestamp = opened_at.format("%Y%m%dT%H%M%S%.6fZ");
+                if attempt == 0 {
+                    format!("{timestamp}{}", self.suffix)
+                } else {
+                    format!("{timestamp}-{attempt}{}", self.suffix)
+                }
+            }
+            Naming::Sequence => {
+                let sequence = match self.next_sequence {
+                    Some(sequence) => sequence,
+                    None => {
+                        let sequence = self.find_next_sequence().await?;
+                        self.next_sequence = Some(sequence);
+                        sequence
+                    }
+                };
+                format!("{sequence:020}{}", self.suffix)
+            }
+            Naming::Callable(naming) => {
+                let index = self.stats.objects_published.load(Ordering::Relaxed);
+                Python::with_gil(|py| naming.call1(py, (index, opened_at))?.extract::<String>(py))?
+            }
+        };
+        Ok(Path::from(format!("{}/{name}", self.prefix)))
+    }
+
+    /// One more than the highest sequence number of the objects under the prefix.
+    async fn find_next_sequence(&self) -> PyObjectStoreResult<u64> {
+        let prefix = Path::from(self.prefix.as_str());
+        let objects = self
+            .store
+            .list(Some(&prefix))
+            .try_collect::<Vec<_>>()
+            .await?;
+        let highest = objects
+            .iter()
+            .filter_map(|meta| {
+                let name = meta
+                    .location
+                    .filename()?
+                    .strip_suffix(self.suffix.as_str())?;
+                if name.len() != 20 {
+                    return None;
+                }
+                name.parse::<u64>().ok()
+            })
+            .max();
+        Ok(highest.map_or(0, |highest| highest + 1))
+    }
+
+    async fn put(&mut self, path: &Path, payload: PutPayload) -> object_store::Result<()> {
+        if self.create_supported {
+            let opts = PutOptions {
+                mode: PutMode::Create,
+                ..Default::default()
+            };
+            match self.store.put_opts(path, payload.clone(), opts).await {
+                Ok(_) => return Ok(()),
+                Err(object_store::Error::NotImplemented) => self.create_supported = false,
+                Err(err) => return Err(err),
+            }
+        }
+        self.store.put(path, payload).await?;
+        Ok(())
+    }
+}
+
+impl RollingWriter {
+    /// Wrap the writer to be shared with a Python object, registering it to be published on
+    /// shutdown.
+    pub(crate) fn into_shared(self) -> Arc<Mutex<Option<Self>>> {
+        let writer = Arc::new(Mutex::new(Some(self)));
+        let weak = Arc::downgrade(&writer);
+        register_writer(weak);
+        writer
+    }
+}
+
+impl Finalize for Mutex<Option<RollingWriter>> {
+    /// Publish the buffered records, like `close`.
+    fn finalize(self: Arc<Self>) -> BoxFuture<'static, Finalized> {
+        Box::pin(async move {
+            let mut guard = self.lock().await;
+            let Some(writer) = guard.as_mut() else {
+                return Finalized::Closed;
+            };
+            let result = writer.roll().await;
+            guard.take();
+            match result {
+                Ok(published) => Finalized::Flushed(published.into_iter().collect()),
+                Err(err) => Finalized::Failed(format!("Failed to publish records: {err}")),
+            }
+        })
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, *, max_size = 67108864, max_interval = None, naming = None, suffix = String::new()))]
+pub(crate) fn open_rolling_writer(
+    store: PyObjectStore,
+    prefix: String,
+    max_size: usize,
+    max_interval: Option<Duration>,
+    naming: Option<Naming>,
+    suffix: String,
+) -> PyResult<PyRollingWriter> {
+    let writer = RollingWriter::try_new(store, prefix, max_size, max_interval, naming, suffix)?;
+    Ok(PyRollingWriter::new(writer, false))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, *, max_size = 67108864, max_interval = None, naming = None, suffix = String::new()))]
+pub(crate) fn open_rolling_writer_async(
+    py: Python,
+    store: PyObjectStore,
+    prefix: String,
+    max_size: usize,
+    max_interval: Option<Duration>,
+    naming: Option<Naming>,
+    suffix: String,
+) -> PyResult<Bound<PyAny>> {
+    let writer = RollingWriter::try_new(store, prefix, max_size, max_interval, naming, suffix)?;
+    future_into_py(py, async move { Ok(PyRollingWriter::new(writer, true)) })
+}
+
+#[pyclass(name = "RollingWriter", frozen)]
+pub(crate) struct PyRollingWriter {
+    writer: Arc<Mutex<Option<RollingWriter>>>,
+    stats: Arc<RollingStats>,
+    r#async: bool,
+}
+
+impl PyRollingWriter {
+    fn new(writer: RollingWriter, r#async: bool) -> Self {
+        Self {
+            stats: writer.stats.clone(),
+            writer: writer.into_shared(),
+            r#async,
+        }
+    }
+
+    /// Run `f` to completion, or return it as a Python awaitable for an async writer.
+    fn run<'py, F>(&self, py: Python<'py>, f: F) -> PyResult<PyObject>
+    where
+        F: std::future::Future<Output = PyResult<Option<String>>> + Send + 'static,
+    {
+        if self.r#async {
+            Ok(future_into_py(py, f)?.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            let out = py.allow_threads(|| runtime.block_on(f))?;
+            Ok(out.into_pyobject(py)?.unbind())
+        }
+    }
+}
+
+#[pymethods]
+impl PyRollingWriter {
+    #[getter]
+    fn bytes_buffered(&self) -> usize {
+        self.stats.bytes_buffered.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn objects_published(&self) -> usize {
+        self.stats.objects_published.load(Ordering::Relaxed)
+    }
+
+    fn write<'py>(&'py self, py: Python<'py>, buf: PyBytes) -> PyResult<PyObject> {
+        self.run(py, write(self.writer.clone(), buf.into_inner()))
+    }
+
+    fn flush<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        self.run(py, flush(self.writer.clone()))
+    }
+
+    fn close<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        self.run(py, close(self.writer.clone()))
+    }
+}
+
+async fn write(writer: Arc<Mutex<Option<RollingWriter>>>, buf: Bytes) -> PyResult<Option<String>> {
+    let mut writer = writer.lock().await;
+    let writer = writer
+        .as_mut()
+        .ok_or(PyIOError::new_err("Writer has already been closed."))?;
+    Ok(writer.write(buf).await?)
+}
+
+pub(crate) async fn flush(writer: Arc<Mutex<Option<RollingWriter>>>) -> PyResult<Option<String>> {
+    let mut writer = writer.lock().await;
+    let writer = writer
+        .as_mut()
+        .ok_or(PyIOError::new_err("Writer has already been closed."))?;
+    Ok(writer.roll().await?)
+}
+
+pub(crate) async fn close(writer: Arc<Mutex<Option<RollingWriter>>>) -> PyResult<Option<String>> {
+    let mut guard = writer.lock().await;
+    let writer = guard
+        .as_mut()
+        .ok_or(PyIOError::new_err("Writer has already been closed."))?;
+    let published = writer.roll().await?;
+    // Only close the writer once its records are published, so that a failed close can be
+    // retried
+    guard.take();
+    Ok(published)
+}
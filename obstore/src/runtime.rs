@@ -1,14 +1,166 @@
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
-use pyo3::exceptions::PyValueError;
+use futures::future::{AbortHandle, Abortable};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::sync::GILOnceCell;
 use tokio::runtime::Runtime;
 
 static RUNTIME: GILOnceCell<Arc<Runtime>> = GILOnceCell::new();
 
-/// Get the tokio runtime for sync requests
-pub(crate) fn get_runtime(py: Python<'_>) -> PyResult<Arc<Runtime>> {
+/// The number of futures returned to Python by [`future_into_py`] that haven't completed yet.
+static PENDING_FUTURES: AtomicUsize = AtomicUsize::new(0);
+
+/// The handles to abort the futures counted in [`PENDING_FUTURES`], keyed by a unique ID.
+static ABORT_HANDLES: Mutex<BTreeMap<u64, AbortHandle>> = Mutex::new(BTreeMap::new());
+
+static NEXT_FUTURE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The number of the futures counted in [`PENDING_FUTURES`] that each event loop awaits, keyed
+/// by the address of the loop, which outlives the futures it awaits.
+static LOOP_FUTURES: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+/// The number of synchronous calls currently blocking on [`RUNTIME`].
+static SYNC_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Set by `shutdown`, after which no new operations are started.
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Counts a future in [`PENDING_FUTURES`], and in [`LOOP_FUTURES`] for the event loop awaiting
+/// it, for as long as it is alive.
+struct PendingFuture {
+    id: u64,
+    event_loop: usize,
+}
+
+impl PendingFuture {
+    fn new(abort: AbortHandle, event_loop: &Bound<PyAny>) -> Self {
+        let id = NEXT_FUTURE_ID.fetch_add(1, Ordering::Relaxed);
+        let event_loop = event_loop.as_ptr() as usize;
+        ABORT_HANDLES.lock().unwrap().insert(id, abort);
+        *LOOP_FUTURES.lock().unwrap().entry(event_loop).or_default() += 1;
+        PENDING_FUTURES.fetch_add(1, Ordering::Relaxed);
+        Self { id, event_loop }
+    }
+}
+
+impl Drop for PendingFuture {
+    fn drop(&mut self) {
+        ABORT_HANDLES.lock().unwrap().remove(&self.id);
+        let mut loop_futures = LOOP_FUTURES.lock().unwrap();
+        if let Some(count) = loop_futures.get_mut(&self.event_loop) {
+            *count -= 1;
+            if *count == 0 {
+                loop_futures.remove(&self.event_loop);
+            }
+        }
+        drop(loop_futures);
+        PENDING_FUTURES.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Counts a call in [`SYNC_CALLS`] for as long as it is alive.
+struct SyncCall;
+
+impl SyncCall {
+    fn new() -> Self {
+        SYNC_CALLS.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Drop for SyncCall {
+    fn drop(&mut self) {
+        SYNC_CALLS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn check_not_shut_down() -> PyResult<()> {
+    if SHUT_DOWN.load(Ordering::Relaxed) {
+        return Err(PyRuntimeError::new_err(
+            "obstore has been shut down, so no new operations can be started.",
+        ));
+    }
+    Ok(())
+}
+
+/// Stop accepting new operations.
+pub(crate) fn begin_shutdown() {
+    SHUT_DOWN.store(true, Ordering::Relaxed);
+}
+
+/// The number of async operations and of sync calls in flight.
+pub(crate) fn in_flight() -> (usize, usize) {
+    (
+        PENDING_FUTURES.load(Ordering::Relaxed),
+        SYNC_CALLS.load(Ordering::Relaxed),
+    )
+}
+
+/// Abort all pending async operations, returning how many were aborted.
+pub(crate) fn abort_pending() -> usize {
+    let handles = ABORT_HANDLES.lock().unwrap();
+    for handle in handles.values() {
+        handle.abort();
+    }
+    handles.len()
+}
+
+/// Fail if called on a thread running an asyncio event loop while obstore futures awaited by
+/// that loop are pending.
+///
+/// Blocking that thread also blocks the event loop, so pending futures that need the loop to make
+/// progress (e.g. an async iterator passed to `put_async`) can't complete. If the blocking call
+/// in turn waits on them, the process hangs without any error. The futures of loops on other
+/// threads keep making progress, so they don't count.
+fn check_event_loop(py: Python<'_>) -> PyResult<()> {
+    if PENDING_FUTURES.load(Ordering::Relaxed) == 0 {
+        return Ok(());
+    }
+    let running_loop = py
+        .import(intern!(py, "asyncio"))?
+        .call_method0(intern!(py, "_get_running_loop"))?;
+    if running_loop.is_none() {
+        return Ok(());
+    }
+    let event_loop = running_loop.as_ptr() as usize;
+    if !LOOP_FUTURES.lock().unwrap().contains_key(&event_loop) {
+        return Ok(());
+    }
+    Err(PyRuntimeError::new_err(
+        "A synchronous obstore function was called from a thread running an asyncio event \
+         loop while obstore async operations are pending. Blocking this thread blocks the \
+         event loop, which can stall or deadlock those operations. Use the `_async` variant \
+         of this function instead, or call it from another thread, e.g. with \
+         `asyncio.to_thread`.",
+    ))
+}
+
+/// The tokio runtime for sync requests, which tracks the calls blocking on it.
+pub(crate) struct SyncRuntime(Arc<Runtime>);
+
+impl SyncRuntime {
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let _call = SyncCall::new();
+        self.0.block_on(future)
+    }
+
+    /// Run `future` in the background. It isn't tracked as a call in flight, so it has to be
+    /// stopped on shutdown by other means, such as registering it to be finalized.
+    pub(crate) fn spawn<F>(&self, future: F) -> tokio::task::JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.0.spawn(future)
+    }
+}
+
+fn runtime(py: Python<'_>) -> PyResult<Arc<Runtime>> {
     let runtime = RUNTIME.get_or_try_init(py, || {
         Ok::<_, PyErr>(Arc::new(Runtime::new().map_err(|err| {
             PyValueError::new_err(format!("Could not create tokio runtime. {}", err))
@@ -16,3 +168,37 @@ pub(crate) fn get_runtime(py: Python<'_>) -> PyResult<Arc<Runtime>> {
     })?;
     Ok(runtime.clone())
 }
+
+/// Get the tokio runtime for sync requests
+pub(crate) fn get_runtime(py: Python<'_>) -> PyResult<SyncRuntime> {
+    check_not_shut_down()?;
+    check_event_loop(py)?;
+    Ok(SyncRuntime(runtime(py)?))
+}
+
+/// Get the tokio runtime for the work done by `shutdown` itself, which is neither tracked nor
+/// rejected.
+pub(crate) fn get_shutdown_runtime(py: Python<'_>) -> PyResult<Arc<Runtime>> {
+    runtime(py)
+}
+
+/// Convert a Rust future into a Python awaitable, tracking it so that sync functions can detect
+/// being called from the event loop while it is pending, and so that `shutdown` can abort it.
+pub(crate) fn future_into_py<F, T>(py: Python, fut: F) -> PyResult<Bound<PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'py> IntoPyObject<'py>,
+{
+    check_not_shut_down()?;
+    let locals = pyo3_async_runtimes::tokio::get_current_locals(py)?;
+    let (abort, registration) = AbortHandle::new_pair();
+    let pending = PendingFuture::new(abort, &locals.event_loop(py));
+    pyo3_async_runtimes::tokio::future_into_py_with_locals(py, locals, async move {
+        let _pending = pending;
+        Abortable::new(fut, registration).await.unwrap_or_else(|_| {
+            Err(PyRuntimeError::new_err(
+                "The operation was cancelled by obstore.shutdown.",
+            ))
+        })
+    })
+}
@@ -0,0 +1,105 @@
+//! Stopping obstore cleanly before the process exits.
+
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::runtime::{abort_pending, begin_shutdown, get_shutdown_runtime, in_flight};
+
+/// How often `shutdown` checks whether in-flight operations have completed.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// What happened to an open writer when it was finalized by `shutdown`.
+pub(crate) enum Finalized {
+    /// The writer had already been closed.
+    Closed,
+    /// The writer's buffered data was published to these objects.
+    Flushed(Vec<String>),
+    /// The writer's multipart upload to this path was aborted.
+    Aborted(String),
+    /// Finalizing the writer failed.
+    Failed(String),
+}
+
+/// A writer holding state that has to be cleaned up before exiting, such as an incomplete
+/// multipart upload.
+pub(crate) trait Finalize: Send + Sync {
+    fn finalize(self: Arc<Self>) -> BoxFuture<'static, Finalized>;
+}
+
+/// The writers that have been opened, which are finalized on shutdown if they are still alive.
+static OPEN_WRITERS: Mutex<Vec<Weak<dyn Finalize>>> = Mutex::new(Vec::new());
+
+/// Register a writer to be finalized on shutdown.
+pub(crate) fn register_writer(writer: Weak<dyn Finalize>) {
+    let mut writers = OPEN_WRITERS.lock().unwrap();
+    writers.retain(|writer| writer.strong_count() > 0);
+    writers.push(writer);
+}
+
+/// Wait until there are no operations in flight, or until `deadline` has passed.
+fn wait_for_in_flight(deadline: Option<Instant>) {
+    while in_flight() != (0, 0) {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (*, timeout = None))]
+pub(crate) fn shutdown(py: Python, timeout: Option<Duration>) -> PyResult<Bound<PyDict>> {
+    begin_shutdown();
+    let runtime = get_shutdown_runtime(py)?;
+
+    let (cancelled, still_running, finalized) = py.allow_threads(|| {
+        wait_for_in_flight(timeout.map(|timeout| Instant::now() + timeout));
+        let cancelled = abort_pending();
+        let (_, still_running) = in_flight();
+
+        let writers = std::mem::take(&mut *OPEN_WRITERS.lock().unwrap());
+        let finalized = runtime.block_on(async move {
+            let finalize = futures::future::join_all(
+                writers
+                    .iter()
+                    .filter_map(Weak::upgrade)
+                    .map(|writer| writer.finalize()),
+            );
+            match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, finalize)
+                    .await
+                    .unwrap_or_else(|_| {
+                        vec![Finalized::Failed(
+                            "Timed out finalizing open writers".to_string(),
+                        )]
+                    }),
+                None => finalize.await,
+            }
+        });
+        (cancelled, still_running, finalized)
+    });
+
+    let mut flushed = vec![];
+    let mut aborted_uploads = vec![];
+    let mut errors = vec![];
+    for result in finalized {
+        match result {
+            Finalized::Closed => {}
+            Finalized::Flushed(paths) => flushed.extend(paths),
+            Finalized::Aborted(path) => aborted_uploads.push(path),
+            Finalized::Failed(err) => errors.push(err),
+        }
+    }
+
+    let report = PyDict::new(py);
+    report.set_item("cancelled", cancelled)?;
+    report.set_item("still_running", still_running)?;
+    report.set_item("flushed", flushed)?;
+    report.set_item("aborted_uploads", aborted_uploads)?;
+    report.set_item("errors", errors)?;
+    Ok(report)
+}
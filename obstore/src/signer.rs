@@ -4,7 +4,6 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 use http::Method;
-use object_store::aws::AmazonS3;
 use object_store::azure::MicrosoftAzure;
 use object_store::gcp::GoogleCloudStorage;
 use object_store::path::Path;
@@ -15,26 +14,33 @@ use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
 use pyo3::types::PyString;
 use pyo3_object_store::{
-    PyAzureStore, PyGCSStore, PyObjectStoreError, PyObjectStoreResult, PyS3Store,
+    prefixed_path, PyAzureStore, PyGCSStore, PyObjectStoreError, PyObjectStoreResult, PyS3Store,
+    RegionAwareS3,
 };
 use url::Url;
 
 use crate::path::PyPaths;
-use crate::runtime::get_runtime;
+use crate::runtime::{future_into_py, get_runtime};
 
 #[derive(Debug)]
 pub(crate) enum SignCapableStore {
-    S3(Arc<AmazonS3>),
-    Gcs(Arc<GoogleCloudStorage>),
+    S3(Arc<RegionAwareS3>),
+    /// A GCS store with the bucket and prefix of its `GCSStore`, which signed paths are under
+    Gcs(Arc<GoogleCloudStorage>, String, Option<Path>),
     Azure(Arc<MicrosoftAzure>),
 }
 
 impl<'py> FromPyObject<'py> for SignCapableStore {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
         if let Ok(store) = ob.downcast::<PyS3Store>() {
-            Ok(Self::S3(store.borrow().as_ref().clone()))
+            Ok(Self::S3(store.borrow().region_aware().clone()))
         } else if let Ok(store) = ob.downcast::<PyGCSStore>() {
-            Ok(Self::Gcs(store.borrow().as_ref().clone()))
+            let store = store.get();
+            Ok(Self::Gcs(
+                store.as_ref().clone(),
+                store.bucket().to_string(),
+                store.prefix().cloned(),
+            ))
         } else if let Ok(store) = ob.downcast::<PyAzureStore>() {
             Ok(Self::Azure(store.borrow().as_ref().clone()))
         } else {
@@ -79,7 +85,10 @@ impl Signer for SignCapableStore {
     {
         match self {
             Self::S3(inner) => inner.signed_url(method, path, expires_in),
-            Self::Gcs(inner) => inner.signed_url(method, path, expires_in),
+            Self::Gcs(inner, _, prefix) => {
+                let path = prefixed_path(prefix.as_ref(), path);
+                Box::pin(async move { inner.signed_url(method, &path, expires_in).await })
+            }
             Self::Azure(inner) => inner.signed_url(method, path, expires_in),
         }
     }
@@ -97,7 +106,13 @@ impl Signer for SignCapableStore {
     {
         match self {
             Self::S3(inner) => inner.signed_urls(method, paths, expires_in),
-            Self::Gcs(inner) => inner.signed_urls(method, paths, expires_in),
+            Self::Gcs(inner, _, prefix) => {
+                let paths = paths
+                    .iter()
+                    .map(|path| prefixed_path(prefix.as_ref(), path))
+                    .collect::<Vec<_>>();
+                Box::pin(async move { inner.signed_urls(method, &paths, expires_in).await })
+            }
             Self::Azure(inner) => inner.signed_urls(method, paths, expires_in),
         }
     }
@@ -184,7 +199,7 @@ pub(crate) fn sign_async(
     expires_in: Duration,
 ) -> PyResult<Bound<PyAny>> {
     let method = method.0;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    future_into_py(py, async move {
         match paths {
             PyPaths::One(path) => {
                 let url = store
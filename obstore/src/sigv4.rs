@@ -0,0 +1,107 @@
+//! AWS Signature Version 4, for the AWS APIs that object_store doesn't call itself.
+
+use chrono::Utc;
+use object_store::aws::AwsCredential;
+use reqwest::{Client, Method, RequestBuilder};
+use ring::digest::{digest, SHA256};
+use ring::hmac;
+use url::Url;
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Percent-encode everything but the unreserved characters, as SigV4 requires.
+pub(crate) fn uri_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes())
+        .as_ref()
+        .to_vec()
+}
+
+/// Build a request for `url`, signed for `service` in `region`.
+///
+/// `headers` are sent and signed along with the headers SigV4 requires.
+pub(crate) fn signed_request(
+    credential: &AwsCredential,
+    method: Method,
+    url: Url,
+    region: &str,
+    service: &str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+) -> RequestBuilder {
+    let now = Utc::now();
+    let date_time = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let body_hash = hex(digest(&SHA256, &body).as_ref());
+
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+    let mut headers = headers;
+    headers.push(("host", host));
+    headers.push(("x-amz-content-sha256", body_hash.clone()));
+    headers.push(("x-amz-date", date_time.clone()));
+    if let Some(token) = &credential.token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    headers.sort_unstable_by_key(|(name, _)| *name);
+
+    let mut query = url
+        .query_pairs()
+        .map(|(key, value)| format!("{}={}", uri_encode(&key), uri_encode(&value)))
+        .collect::<Vec<_>>();
+    query.sort_unstable();
+    let canonical_headers = headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+        .collect::<String>();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "{method}\n{}\n{}\n{canonical_headers}\n{signed_headers}\n{body_hash}",
+        url.path(),
+        query.join("&")
+    );
+
+    let scope = format!("{date}/{region}/{service}/aws4_request");
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{date_time}\n{scope}\n{}",
+        hex(digest(&SHA256, canonical_request.as_bytes()).as_ref())
+    );
+    let mut key = format!("AWS4{}", credential.secret_key).into_bytes();
+    for part in [date.as_str(), region, service, "aws4_request"] {
+        key = hmac_sha256(&key, part);
+    }
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={}",
+        credential.key_id,
+        hex(&hmac_sha256(&key, &string_to_sign))
+    );
+
+    // These are infrequent administrative requests, which don't benefit from pooled connections
+    let mut request = Client::new()
+        .request(method, url)
+        .header("authorization", authorization)
+        .body(body);
+    // reqwest sets the host header from the URL
+    for (name, value) in headers.into_iter().filter(|(name, _)| *name != "host") {
+        request = request.header(name, value);
+    }
+    request
+}
@@ -0,0 +1,368 @@
+//! Record sinks that serialize rows to NDJSON or CSV and publish them through a
+//! [`RollingWriter`].
+
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use bytes::Bytes;
+use pyo3::exceptions::{PyIOError, PyTypeError, PyValueError};
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use pyo3_object_store::PyObjectStore;
+use serde_json::{Number, Value};
+use tokio::sync::Mutex;
+
+use crate::rolling::{self, Naming, RollingStats, RollingWriter};
+use crate::runtime::{future_into_py, get_runtime};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RecordFormat {
+    Ndjson,
+    Csv,
+}
+
+impl RecordFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Ndjson => ".ndjson",
+            Self::Csv => ".csv",
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for RecordFormat {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let s = ob.extract::<String>()?.to_lowercase();
+        match s.as_str() {
+            "ndjson" => Ok(Self::Ndjson),
+            "csv" => Ok(Self::Csv),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown record format {s}, expected 'ndjson' or 'csv'"
+            ))),
+        }
+    }
+}
+
+/// Convert a Python value to JSON.
+///
+/// Dates and times are converted to their ISO 8601 representation.
+fn to_json(ob: &Bound<PyAny>) -> PyResult<Value> {
+    let py = ob.py();
+    if ob.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = ob.downcast::<PyBool>() {
+        Ok(Value::Bool(b.is_true()))
+    } else if ob.is_instance_of::<PyInt>() {
+        if let Ok(i) = ob.extract::<i64>() {
+            Ok(Value::from(i))
+        } else {
+            Ok(Value::from(ob.extract::<u64>()?))
+        }
+    } else if let Ok(f) = ob.downcast::<PyFloat>() {
+        Ok(Number::from_f64(f.value()).map_or(Value::Null, Value::Number))
+    } else if let Ok(s) = ob.downcast::<PyString>() {
+        Ok(Value::String(s.to_cow()?.into_owned()))
+    } else if let Ok(dict) = ob.downcast::<PyDict>() {
+        dict.iter()
+            .map(|(key, value)| Ok((key.extract::<String>()?, to_json(&value)?)))
+            .collect::<PyResult<serde_json::Map<_, _>>>()
+            .map(Value::Object)
+    } else if ob.is_instance_of::<PyList>() || ob.is_instance_of::<PyTuple>() {
+        ob.try_iter()?
+            .map(|item| to_json(&item?))
+            .collect::<PyResult<Vec<_>>>()
+            .map(Value::Array)
+    } else if ob.hasattr(intern!(py, "isoformat"))? {
+        Ok(Value::String(
+            ob.call_method0(intern!(py, "isoformat"))?.extract()?,
+        ))
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "Object of type {} is not serializable",
+            ob.get_type().name()?
+        )))
+    }
+}
+
+/// Append `field` to a CSV line, quoting it if necessary.
+fn write_csv_field(line: &mut Vec<u8>, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        line.push(b'"');
+        line.extend_from_slice(field.replace('"', "\"\"").as_bytes());
+        line.push(b'"');
+    } else {
+        line.extend_from_slice(field.as_bytes());
+    }
+}
+
+fn write_csv_line<'a>(buf: &mut Vec<u8>, fields: impl Iterator<Item = &'a str>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            buf.push(b',');
+        }
+        write_csv_field(buf, field);
+    }
+    buf.push(b'\n');
+}
+
+/// The text of a CSV cell. Nested lists and dicts are written as JSON.
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The columns of a sink, fixed by the `columns` argument or the keys of the first row.
+struct Schema {
+    columns: Vec<String>,
+    /// Whether the schema was passed explicitly, in which case NDJSON rows are checked too
+    explicit: bool,
+}
+
+impl Schema {
+    fn check(&self, key: &str) -> PyResult<()> {
+        if !self.columns.iter().any(|column| column == key) {
+            return Err(PyValueError::new_err(format!(
+                "Row has column {key}, which is not one of the sink's columns {:?}",
+                self.columns
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Serializes rows, holding the schema they have to conform to.
+struct Encoder {
+    format: RecordFormat,
+    schema: OnceLock<Schema>,
+}
+
+impl Encoder {
+    fn new(format: RecordFormat, columns: Option<Vec<String>>) -> Self {
+        let schema = OnceLock::new();
+        if let Some(columns) = columns {
+            let _ = schema.set(Schema {
+                columns,
+                explicit: true,
+            });
+        }
+        Self { format, schema }
+    }
+
+    /// The header row written at the start of each object, if any.
+    fn header(&self) -> Option<Bytes> {
+        match (self.format, self.schema.get()) {
+            (RecordFormat::Csv, Some(schema)) => {
+                let mut header = vec![];
+                write_csv_line(&mut header, schema.columns.iter().map(String::as_str));
+                Some(header.into())
+            }
+            _ => None,
+        }
+    }
+
+    fn encode_row(&self, buf: &mut Vec<u8>, row: &Bound<PyAny>) -> PyResult<()> {
+        let row = row
+            .downcast::<PyDict>()
+            .map_err(|_| PyTypeError::new_err("Rows must be dicts"))?;
+        let mut cells = Vec::with_capacity(row.len());
+        for (key, value) in row.iter() {
+            cells.push((key.extract::<String>()?, to_json(&value)?));
+        }
+        if self.format == RecordFormat::Csv {
+            self.schema.get_or_init(|| Schema {
+                columns: cells.iter().map(|(key, _)| key.clone()).collect(),
+                explicit: false,
+            });
+        }
+
+        match (self.format, self.schema.get()) {
+            (RecordFormat::Csv, Some(schema)) => {
+                for (key, _) in &cells {
+                    schema.check(key)?;
+                }
+                let line = schema
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        cells
+                            .iter()
+                            .find(|(key, _)| key == column)
+                            .map(|(_, value)| csv_cell(value))
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<_>>();
+                write_csv_line(buf, line.iter().map(String::as_str));
+            }
+            (_, schema) => {
+                if let Some(schema) = schema.filter(|schema| schema.explicit) {
+                    for (key, _) in &cells {
+                        schema.check(key)?;
+                    }
+                }
+                // Written by hand rather than through a serde_json::Map, to keep the order of
+                // the keys
+                buf.push(b'{');
+                for (i, (key, value)) in cells.iter().enumerate() {
+                    if i > 0 {
+                        buf.push(b',');
+                    }
+                    serde_json::to_writer(&mut *buf, key).map_err(to_py_err)?;
+                    buf.push(b':');
+                    serde_json::to_writer(&mut *buf, value).map_err(to_py_err)?;
+                }
+                buf.extend_from_slice(b"}\n");
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_py_err(err: serde_json::Error) -> PyErr {
+    PyValueError::new_err(format!("Could not serialize row: {err}"))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, *, format = RecordFormat::Ndjson, columns = None, max_size = 67108864, max_interval = None, naming = None, suffix = None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_record_sink(
+    store: PyObjectStore,
+    prefix: String,
+    format: RecordFormat,
+    columns: Option<Vec<String>>,
+    max_size: usize,
+    max_interval: Option<Duration>,
+    naming: Option<Naming>,
+    suffix: Option<String>,
+) -> PyResult<PyRecordSink> {
+    let suffix = suffix.unwrap_or_else(|| format.extension().to_string());
+    let writer = RollingWriter::try_new(store, prefix, max_size, max_interval, naming, suffix)?;
+    Ok(PyRecordSink::new(
+        writer,
+        Encoder::new(format, columns),
+        false,
+    ))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, prefix, *, format = RecordFormat::Ndjson, columns = None, max_size = 67108864, max_interval = None, naming = None, suffix = None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_record_sink_async(
+    py: Python,
+    store: PyObjectStore,
+    prefix: String,
+    format: RecordFormat,
+    columns: Option<Vec<String>>,
+    max_size: usize,
+    max_interval: Option<Duration>,
+    naming: Option<Naming>,
+    suffix: Option<String>,
+) -> PyResult<Bound<PyAny>> {
+    let suffix = suffix.unwrap_or_else(|| format.extension().to_string());
+    let writer = RollingWriter::try_new(store, prefix, max_size, max_interval, naming, suffix)?;
+    let encoder = Encoder::new(format, columns);
+    future_into_py(
+        py,
+        async move { Ok(PyRecordSink::new(writer, encoder, true)) },
+    )
+}
+
+#[pyclass(name = "RecordSink", frozen)]
+pub(crate) struct PyRecordSink {
+    writer: Arc<Mutex<Option<RollingWriter>>>,
+    encoder: Encoder,
+    stats: Arc<RollingStats>,
+    r#async: bool,
+}
+
+impl PyRecordSink {
+    fn new(writer: RollingWriter, encoder: Encoder, r#async: bool) -> Self {
+        Self {
+            stats: writer.stats.clone(),
+            writer: writer.into_shared(),
+            encoder,
+            r#async,
+        }
+    }
+
+    /// Run `f` to completion, or return it as a Python awaitable for an async sink.
+    fn run<F>(&self, py: Python, f: F) -> PyResult<PyObject>
+    where
+        F: std::future::Future<Output = PyResult<Option<String>>> + Send + 'static,
+    {
+        if self.r#async {
+            Ok(future_into_py(py, f)?.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            let out = py.allow_threads(|| runtime.block_on(f))?;
+            Ok(out.into_pyobject(py)?.unbind())
+        }
+    }
+
+    /// Append serialized rows to the writer as a single chunk, so that they end up in the same
+    /// object.
+    fn write_encoded(&self, py: Python, buf: Vec<u8>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        let header = self.encoder.header();
+        self.run(py, async move {
+            let mut writer = writer.lock().await;
+            let writer = writer
+                .as_mut()
+                .ok_or(PyIOError::new_err("Writer has already been closed."))?;
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            if let Some(header) = header {
+                writer.set_header(header);
+            }
+            Ok(writer.write(buf.into()).await?)
+        })
+    }
+}
+
+#[pymethods]
+impl PyRecordSink {
+    #[getter]
+    fn bytes_buffered(&self) -> usize {
+        self.stats.bytes_buffered.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn objects_published(&self) -> usize {
+        self.stats.objects_published.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn columns(&self) -> Option<Vec<String>> {
+        self.encoder
+            .schema
+            .get()
+            .map(|schema| schema.columns.clone())
+    }
+
+    fn write_row(&self, py: Python, row: &Bound<PyAny>) -> PyResult<PyObject> {
+        let mut buf = vec![];
+        self.encoder.encode_row(&mut buf, row)?;
+        self.write_encoded(py, buf)
+    }
+
+    fn write_rows(&self, py: Python, rows: &Bound<PyAny>) -> PyResult<PyObject> {
+        let mut buf = vec![];
+        for row in rows.try_iter()? {
+            self.encoder.encode_row(&mut buf, &row?)?;
+        }
+        self.write_encoded(py, buf)
+    }
+
+    fn flush(&self, py: Python) -> PyResult<PyObject> {
+        self.run(py, rolling::flush(self.writer.clone()))
+    }
+
+    fn close(&self, py: Python) -> PyResult<PyObject> {
+        self.run(py, rolling::close(self.writer.clone()))
+    }
+}
@@ -0,0 +1,190 @@
+//! Snapshot tokens: compact, self-contained lists of the ETags of a set of objects, so that
+//! readers holding the same token read the same versions of those objects.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use futures::stream::TryStreamExt;
+use object_store::path::Path;
+use object_store::{GetOptions, ObjectStore};
+use obstore_core::concurrency::{buffer_unordered, Concurrency};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3_object_store::clock;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreError, PyObjectStoreResult};
+use serde::{Deserialize, Serialize};
+
+use crate::get::{PyGetOptions, PyGetResult};
+use crate::ranges;
+use crate::runtime::{future_into_py, get_runtime};
+
+/// Prefix identifying the format of a snapshot token.
+const TOKEN_PREFIX: &str = "obs1.";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Pin {
+    #[serde(rename = "e")]
+    e_tag: String,
+    #[serde(rename = "v", default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    /// The Unix timestamp in milliseconds after which the token is no longer accepted
+    #[serde(rename = "x", default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<i64>,
+    #[serde(rename = "p")]
+    pins: BTreeMap<String, Pin>,
+}
+
+impl Snapshot {
+    fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("snapshot serializes to JSON");
+        format!("{TOKEN_PREFIX}{}", URL_SAFE_NO_PAD.encode(json))
+    }
+
+    fn decode(token: &str) -> PyResult<Self> {
+        let invalid = || PyValueError::new_err("Invalid snapshot token");
+        let encoded = token.strip_prefix(TOKEN_PREFIX).ok_or_else(invalid)?;
+        let json = URL_SAFE_NO_PAD.decode(encoded).map_err(|_| invalid())?;
+        let snapshot: Self = serde_json::from_slice(&json).map_err(|_| invalid())?;
+        if let Some(expires_at) = snapshot.expires_at {
+            if clock::now().timestamp_millis() >= expires_at {
+                return Err(PyValueError::new_err("Snapshot token has expired"));
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+async fn snapshot_token_inner(
+    store: Arc<dyn ObjectStore>,
+    paths: Vec<Path>,
+    expires_in: Option<Duration>,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<String> {
+    let heads = paths.into_iter().map(|path| {
+        let store = store.clone();
+        async move { store.head(&path).await }
+    });
+    let metas = buffer_unordered(heads, max_concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let mut pins = BTreeMap::new();
+    for meta in metas {
+        let e_tag = meta.e_tag.ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Object {} has no ETag and cannot be pinned",
+                meta.location
+            ))
+        })?;
+        pins.insert(
+            meta.location.to_string(),
+            Pin {
+                e_tag,
+                version: meta.version,
+            },
+        );
+    }
+    let expires_at = expires_in
+        .map(|expires_in| clock::now().timestamp_millis() + expires_in.as_millis() as i64);
+    Ok(Snapshot { expires_at, pins }.encode())
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, paths, *, expires_in = None, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn snapshot_token(
+    py: Python,
+    store: PyObjectStore,
+    paths: Vec<String>,
+    expires_in: Option<Duration>,
+    max_concurrency: Concurrency,
+) -> PyObjectStoreResult<String> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| {
+        runtime.block_on(snapshot_token_inner(
+            store.into_inner(),
+            paths.into_iter().map(Path::from).collect(),
+            expires_in,
+            max_concurrency,
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, paths, *, expires_in = None, max_concurrency = Concurrency::Fixed(12)))]
+pub(crate) fn snapshot_token_async(
+    py: Python,
+    store: PyObjectStore,
+    paths: Vec<String>,
+    expires_in: Option<Duration>,
+    max_concurrency: Concurrency,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        let token = snapshot_token_inner(
+            store.into_inner(),
+            paths.into_iter().map(Path::from).collect(),
+            expires_in,
+            max_concurrency,
+        )
+        .await?;
+        Ok(token)
+    })
+}
+
+/// The options for reading `path` as pinned by `token`.
+fn pinned_options(token: &str, path: &Path, options: Option<PyGetOptions>) -> PyResult<GetOptions> {
+    let mut snapshot = Snapshot::decode(token)?;
+    let pin = snapshot
+        .pins
+        .remove(path.as_ref())
+        .ok_or_else(|| PyKeyError::new_err(format!("{path} is not pinned by the snapshot")))?;
+    let options = options.map(GetOptions::from).unwrap_or_default();
+    Ok(GetOptions {
+        if_match: Some(pin.e_tag),
+        version: pin.version.or_else(|| options.version.clone()),
+        ..options
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, token, path, *, options = None))]
+pub(crate) fn get_pinned(
+    py: Python,
+    store: PyObjectStore,
+    token: &str,
+    path: String,
+    options: Option<PyGetOptions>,
+) -> PyObjectStoreResult<PyGetResult> {
+    let path = Path::from(path);
+    let options = pinned_options(token, &path, options)?;
+    let runtime = get_runtime(py)?;
+    let store = store.into_inner();
+    py.allow_threads(|| {
+        let out = runtime.block_on(ranges::get_opts(&store, &path, options))?;
+        Ok::<_, PyObjectStoreError>(PyGetResult::new(out))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, token, path, *, options = None))]
+pub(crate) fn get_pinned_async(
+    py: Python,
+    store: PyObjectStore,
+    token: &str,
+    path: String,
+    options: Option<PyGetOptions>,
+) -> PyResult<Bound<PyAny>> {
+    let path = Path::from(path);
+    let options = pinned_options(token, &path, options)?;
+    let store = store.into_inner();
+    future_into_py(py, async move {
+        let out = ranges::get_opts(&store, &path, options).await?;
+        Ok(PyGetResult::new(out))
+    })
+}
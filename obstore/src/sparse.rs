@@ -0,0 +1,537 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use object_store::multipart::PartId;
+use object_store::path::Path;
+use object_store::{PutPayload, PutResult, WriteMultipart};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_bytes::PyBytes;
+use pyo3_object_store::PyObjectStoreResult;
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+use crate::journal::{JournaledStore, PendingUpload, Uploads};
+use crate::multipart::{check_part_size, take_writer, writer_mut};
+use crate::put::PyPutResult;
+use crate::runtime::{future_into_py, get_runtime};
+use crate::shutdown::{register_writer, Finalize, Finalized};
+
+/// Where the regions of a [`SparseWriter`] are uploaded to.
+enum Assembly {
+    /// Contiguous runs of regions from the start of the object are passed to the upload in
+    /// order, for stores that don't expose the IDs of their uploads.
+    InOrder {
+        writer: WriteMultipart,
+        /// The number of bytes from the start of the object passed to `writer`
+        flushed: usize,
+    },
+    /// Each part is uploaded by its index as soon as all of its bytes have been written.
+    ByIndex(IndexedUpload),
+}
+
+/// A multipart upload whose parts are uploaded by their index, in any order.
+struct IndexedUpload {
+    uploads: Uploads,
+    upload: PendingUpload,
+    /// The indices of the parts that have been handed to `tasks`
+    started: BTreeSet<usize>,
+    tasks: JoinSet<object_store::Result<(usize, PartId)>>,
+    parts: Vec<(usize, PartId)>,
+}
+
+impl IndexedUpload {
+    async fn try_new(uploads: Uploads, path: &Path) -> object_store::Result<Self> {
+        let upload = uploads.create(path).await?;
+        Ok(Self {
+            uploads,
+            upload,
+            started: BTreeSet::new(),
+            tasks: JoinSet::new(),
+            parts: vec![],
+        })
+    }
+
+    /// Wait until fewer than `max_concurrency` parts are in flight.
+    ///
+    /// With `max_concurrency` set to 0, this waits for all outstanding parts.
+    async fn wait_for_capacity(&mut self, max_concurrency: usize) -> object_store::Result<()> {
+        while !self.tasks.is_empty() && self.tasks.len() >= max_concurrency {
+            let part = self
+                .tasks
+                .join_next()
+                .await
+                .unwrap()
+                .map_err(|source| object_store::Error::JoinError { source })??;
+            self.parts.push(part);
+        }
+        Ok(())
+    }
+
+    fn put_part(&mut self, path: &Path, index: usize, chunks: Vec<Bytes>) {
+        self.started.insert(index);
+        let store = self.uploads.store().clone();
+        let path = path.clone();
+        let upload_id = self.upload.upload_id.clone();
+        self.tasks.spawn(async move {
+            let payload = PutPayload::from_iter(chunks);
+            let part = store.put_part(&path, &upload_id, index, payload).await?;
+            Ok((index, part))
+        });
+    }
+
+    async fn finish(&mut self, path: &Path) -> object_store::Result<PutResult> {
+        self.wait_for_capacity(0).await?;
+        let mut parts = std::mem::take(&mut self.parts);
+        parts.sort_unstable_by_key(|(index, _)| *index);
+        let parts = parts.into_iter().map(|(_, part)| part).collect();
+        let result = self
+            .uploads
+            .store()
+            .complete_multipart(path, &self.upload.upload_id, parts)
+            .await?;
+        self.upload.end().await;
+        Ok(result)
+    }
+
+    async fn abort(&mut self, path: &Path) -> object_store::Result<()> {
+        self.tasks.shutdown().await;
+        self.uploads
+            .store()
+            .abort_multipart(path, &self.upload.upload_id)
+            .await?;
+        self.upload.end().await;
+        Ok(())
+    }
+}
+
+/// Whether every byte of `range` is in `region` or a region of `pending`.
+fn is_covered(
+    pending: &BTreeMap<usize, Bytes>,
+    region: &Range<usize>,
+    range: &Range<usize>,
+) -> bool {
+    let mut regions = pending
+        .range(..range.end)
+        .rev()
+        .map(|(start, buf)| *start..start + buf.len())
+        .take_while(|other| other.end > range.start)
+        .chain([region.clone()])
+        .collect::<Vec<_>>();
+    regions.sort_unstable_by_key(|other| other.start);
+    let mut covered = range.start;
+    for other in regions {
+        if other.start > covered {
+            break;
+        }
+        covered = covered.max(other.end);
+    }
+    covered >= range.end
+}
+
+/// Remove `range` from `pending` if all of its bytes have been written, and return them,
+/// keeping the parts of the regions outside of `range`.
+fn take_range(pending: &mut BTreeMap<usize, Bytes>, range: &Range<usize>) -> Option<Vec<Bytes>> {
+    let (&first, _) = pending
+        .range(..=range.start)
+        .next_back()
+        .filter(|(start, buf)| *start + buf.len() > range.start)?;
+    let mut starts = vec![];
+    let mut covered = first;
+    for (&start, buf) in pending.range(first..) {
+        if start != covered {
+            break;
+        }
+        starts.push(start);
+        covered = start + buf.len();
+        if covered >= range.end {
+            break;
+        }
+    }
+    if covered < range.end {
+        return None;
+    }
+    let chunks = starts
+        .into_iter()
+        .map(|start| {
+            let buf = pending.remove(&start).unwrap();
+            let end = start + buf.len();
+            if start < range.start {
+                pending.insert(start, buf.slice(..range.start - start));
+            }
+            if end > range.end {
+                pending.insert(range.end, buf.slice(range.end - start..));
+            }
+            buf.slice(range.start.max(start) - start..range.end.min(end) - start)
+        })
+        .collect();
+    Some(chunks)
+}
+
+/// Assembles regions written at arbitrary offsets into a single multipart upload.
+///
+/// Regions are buffered until they can be uploaded. For stores that expose the IDs of their
+/// uploads, that's as soon as they fill a part, which is uploaded by its index. For other
+/// stores, it's once they form a contiguous run starting at the end of the data that has
+/// already been handed to the upload, at which point they are uploaded in order.
+struct SparseWriter {
+    path: Path,
+    assembly: Assembly,
+    /// Regions that can't be uploaded yet, keyed by their offset
+    pending: BTreeMap<usize, Bytes>,
+    /// The total size of `pending`
+    pending_size: usize,
+    max_pending_size: usize,
+    part_size: usize,
+    max_concurrency: usize,
+}
+
+impl SparseWriter {
+    async fn try_new(
+        store: JournaledStore,
+        path: Path,
+        part_size: usize,
+        max_concurrency: usize,
+        max_pending_size: usize,
+    ) -> PyObjectStoreResult<Self> {
+        let assembly = match store.uploads().cloned() {
+            Some(uploads) => Assembly::ByIndex(IndexedUpload::try_new(uploads, &path).await?),
+            None => {
+                let upload = store.into_inner().put_multipart(&path).await?;
+                Assembly::InOrder {
+                    writer: WriteMultipart::new_with_chunk_size(upload, part_size),
+                    flushed: 0,
+                }
+            }
+        };
+        Ok(Self {
+            path,
+            assembly,
+            pending: BTreeMap::new(),
+            pending_size: 0,
+            max_pending_size,
+            part_size,
+            max_concurrency,
+        })
+    }
+
+    /// Whether any byte of `offset..end` was part of a region that has been uploaded.
+    fn overlaps_uploaded(&self, offset: usize, end: usize) -> bool {
+        match &self.assembly {
+            Assembly::InOrder { flushed, .. } => offset < *flushed,
+            Assembly::ByIndex(upload) => upload
+                .started
+                .range(offset / self.part_size..=(end - 1) / self.part_size)
+                .next()
+                .is_some(),
+        }
+    }
+
+    /// Check that `offset..offset + len` doesn't overlap any region written so far.
+    fn check_overlap(&self, offset: usize, len: usize) -> PyResult<()> {
+        let end = offset + len;
+        let overlaps_prev = self
+            .pending
+            .range(..offset)
+            .next_back()
+            .is_some_and(|(start, buf)| start + buf.len() > offset);
+        let overlaps_next = self
+            .pending
+            .range(offset..)
+            .next()
+            .is_some_and(|(start, _)| *start < end);
+        if self.overlaps_uploaded(offset, end) || overlaps_prev || overlaps_next {
+            return Err(PyValueError::new_err(format!(
+                "Region {offset}..{end} overlaps a previously written region"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The number of bytes of `offset..end` that would be left buffered once it's written.
+    fn buffered_size(&self, offset: usize, end: usize) -> usize {
+        match &self.assembly {
+            Assembly::InOrder { flushed, .. } if offset == *flushed => 0,
+            Assembly::InOrder { .. } => end - offset,
+            Assembly::ByIndex(_) => {
+                // The parts in between are uploaded as soon as they're written
+                let (first, last) = (offset / self.part_size, (end - 1) / self.part_size);
+                let parts = if first == last {
+                    vec![first]
+                } else {
+                    vec![first, last]
+                };
+                parts
+                    .into_iter()
+                    .map(|index| index * self.part_size..(index + 1) * self.part_size)
+                    .filter(|part| !is_covered(&self.pending, &(offset..end), part))
+                    .map(|part| end.min(part.end) - offset.max(part.start))
+                    .sum()
+            }
+        }
+    }
+
+    /// Check that writing `offset..end` leaves at most `max_pending_size` bytes buffered.
+    fn check_pending_size(&self, offset: usize, end: usize) -> PyResult<()> {
+        if self.pending_size + self.buffered_size(offset, end) > self.max_pending_size {
+            return Err(PyValueError::new_err(format!(
+                "Cannot buffer region {offset}..{end}: {} bytes are already buffered waiting \
+                 for earlier regions, and max_pending_size is {}. Write the missing regions \
+                 first.",
+                self.pending_size, self.max_pending_size
+            )));
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, offset: usize, buf: Bytes) -> PyObjectStoreResult<()> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let end = offset + buf.len();
+        self.check_overlap(offset, buf.len())?;
+        self.check_pending_size(offset, end)?;
+        self.pending_size += buf.len();
+        self.pending.insert(offset, buf);
+
+        match &mut self.assembly {
+            Assembly::InOrder { writer, flushed } => {
+                while let Some(entry) = self.pending.first_entry() {
+                    if *entry.key() != *flushed {
+                        break;
+                    }
+                    let buf = entry.remove();
+                    *flushed += buf.len();
+                    self.pending_size -= buf.len();
+                    writer.wait_for_capacity(self.max_concurrency).await?;
+                    writer.put(buf);
+                }
+            }
+            Assembly::ByIndex(upload) => {
+                for index in offset / self.part_size..=(end - 1) / self.part_size {
+                    let range = index * self.part_size..(index + 1) * self.part_size;
+                    if let Some(chunks) = take_range(&mut self.pending, &range) {
+                        self.pending_size -= self.part_size;
+                        upload.wait_for_capacity(self.max_concurrency).await?;
+                        upload.put_part(&self.path, index, chunks);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The first range of bytes up to the end of the last region that was never written.
+    fn first_gap(&self) -> Option<Range<usize>> {
+        let (mut covered, mut regions) = match &self.assembly {
+            Assembly::InOrder { flushed, .. } => (*flushed, vec![]),
+            Assembly::ByIndex(upload) => {
+                let parts = upload.started.iter();
+                let parts = parts.map(|index| index * self.part_size..(index + 1) * self.part_size);
+                (0, parts.collect::<Vec<_>>())
+            }
+        };
+        regions.extend(
+            self.pending
+                .iter()
+                .map(|(start, buf)| *start..start + buf.len()),
+        );
+        // The regions don't overlap, so they're contiguous if each starts where the last ended
+        regions.sort_unstable_by_key(|region| region.start);
+        for region in regions {
+            if region.start > covered {
+                return Some(covered..region.start);
+            }
+            covered = region.end;
+        }
+        None
+    }
+
+    /// Check that every byte up to the end of the last region has been written.
+    fn check_complete(&self) -> PyResult<()> {
+        if let Some(gap) = self.first_gap() {
+            return Err(PyValueError::new_err(format!(
+                "Cannot finish with a gap: bytes {}..{} were never written",
+                gap.start, gap.end
+            )));
+        }
+        Ok(())
+    }
+
+    async fn finish(self) -> PyObjectStoreResult<PyPutResult> {
+        match self.assembly {
+            Assembly::InOrder { writer, .. } => Ok(PyPutResult::new(writer.finish().await?)),
+            Assembly::ByIndex(mut upload) => {
+                // Without gaps, what's left is the last part, which can be shorter than the
+                // others, or empty if nothing was written
+                let chunks = self.pending.into_values().collect::<Vec<_>>();
+                if !chunks.is_empty() || upload.started.is_empty() {
+                    let index = upload.started.last().map_or(0, |index| index + 1);
+                    upload.put_part(&self.path, index, chunks);
+                }
+                match upload.finish(&self.path).await {
+                    Ok(result) => Ok(PyPutResult::new(result)),
+                    Err(err) => {
+                        // The writer is closed either way, so abort here rather than leave the
+                        // parts uploaded so far behind
+                        let _ = upload.abort(&self.path).await;
+                        Err(err.into())
+                    }
+                }
+            }
+        }
+    }
+
+    async fn abort(self) -> PyObjectStoreResult<()> {
+        match self.assembly {
+            Assembly::InOrder { writer, .. } => writer.abort().await?,
+            Assembly::ByIndex(mut upload) => upload.abort(&self.path).await?,
+        }
+        Ok(())
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, part_size = 5242880, max_concurrency = 12, max_pending_size = 268435456))]
+pub(crate) fn open_sparse_writer(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    part_size: usize,
+    max_concurrency: usize,
+    max_pending_size: usize,
+) -> PyObjectStoreResult<PySparseWriter> {
+    check_part_size(part_size)?;
+    let runtime = get_runtime(py)?;
+    let writer = py.allow_threads(|| {
+        runtime.block_on(SparseWriter::try_new(
+            store,
+            path.into(),
+            part_size,
+            max_concurrency,
+            max_pending_size,
+        ))
+    })?;
+    Ok(PySparseWriter::new(writer, false))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, part_size = 5242880, max_concurrency = 12, max_pending_size = 268435456))]
+pub(crate) fn open_sparse_writer_async(
+    py: Python,
+    store: JournaledStore,
+    path: String,
+    part_size: usize,
+    max_concurrency: usize,
+    max_pending_size: usize,
+) -> PyResult<Bound<PyAny>> {
+    check_part_size(part_size)?;
+    future_into_py(py, async move {
+        let writer = SparseWriter::try_new(
+            store,
+            path.into(),
+            part_size,
+            max_concurrency,
+            max_pending_size,
+        )
+        .await?;
+        Ok(PySparseWriter::new(writer, true))
+    })
+}
+
+#[pyclass(name = "SparseWriter", frozen)]
+pub(crate) struct PySparseWriter {
+    writer: Arc<Mutex<Option<SparseWriter>>>,
+    r#async: bool,
+}
+
+impl PySparseWriter {
+    fn new(writer: SparseWriter, r#async: bool) -> Self {
+        let writer = Arc::new(Mutex::new(Some(writer)));
+        let weak = Arc::downgrade(&writer);
+        register_writer(weak);
+        Self { writer, r#async }
+    }
+}
+
+impl Finalize for Mutex<Option<SparseWriter>> {
+    /// Abort the upload, as regions may still be missing.
+    fn finalize(self: Arc<Self>) -> BoxFuture<'static, Finalized> {
+        Box::pin(async move {
+            let Some(writer) = self.lock().await.take() else {
+                return Finalized::Closed;
+            };
+            let path = writer.path.to_string();
+            match writer.abort().await {
+                Ok(()) => Finalized::Aborted(path),
+                Err(err) => Finalized::Failed(format!("Failed to abort upload to {path}: {err}")),
+            }
+        })
+    }
+}
+
+#[pymethods]
+impl PySparseWriter {
+    fn write<'py>(&'py self, py: Python<'py>, offset: usize, buf: PyBytes) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, write(writer, offset, buf.into_inner()))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(write(writer, offset, buf.into_inner())))?;
+            Ok(py.None())
+        }
+    }
+
+    fn finish<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, finish(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            let out = py.allow_threads(|| runtime.block_on(finish(writer)))?;
+            Ok(out.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+
+    fn abort<'py>(&'py self, py: Python<'py>) -> PyResult<PyObject> {
+        let writer = self.writer.clone();
+        if self.r#async {
+            let out = future_into_py(py, abort(writer))?;
+            Ok(out.unbind())
+        } else {
+            let runtime = get_runtime(py)?;
+            py.allow_threads(|| runtime.block_on(abort(writer)))?;
+            Ok(py.None())
+        }
+    }
+}
+
+async fn write(
+    writer: Arc<Mutex<Option<SparseWriter>>>,
+    offset: usize,
+    buf: Bytes,
+) -> PyResult<()> {
+    let mut writer = writer.lock().await;
+    writer_mut(&mut writer)?.write(offset, buf).await?;
+    Ok(())
+}
+
+async fn finish(writer: Arc<Mutex<Option<SparseWriter>>>) -> PyResult<PyPutResult> {
+    let mut writer = writer.lock().await;
+    // Don't consume the writer on a gap, so that the caller can still fill it in or abort
+    writer_mut(&mut writer)?.check_complete()?;
+    let writer = writer.take().unwrap();
+    Ok(writer.finish().await?)
+}
+
+async fn abort(writer: Arc<Mutex<Option<SparseWriter>>>) -> PyResult<()> {
+    let writer = take_writer(&writer).await?;
+    writer.abort().await?;
+    Ok(())
+}
@@ -0,0 +1,342 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use futures::FutureExt;
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult, UploadPart,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use crate::events::count_retries;
+
+tokio::task_local! {
+    /// The stats of the operation whose request is being polled, which its retries are counted
+    /// in.
+    static CURRENT: Arc<TransferStats>;
+}
+
+/// Count a retry in the stats of the operation whose request is being polled, if it tracks any.
+///
+/// object_store only reports retries as tracing events, so this is called by the subscriber for
+/// each of them, on the thread polling the request retried.
+pub(crate) fn retried() {
+    let _ = CURRENT.try_with(|stats| stats.retries.fetch_add(1, Ordering::Relaxed));
+}
+
+#[derive(Debug)]
+struct TransferStats {
+    bytes_sent: AtomicUsize,
+    bytes_received: AtomicUsize,
+    retries: AtomicUsize,
+    started: Instant,
+    first_byte: OnceLock<Duration>,
+}
+
+impl TransferStats {
+    fn new() -> Self {
+        Self {
+            bytes_sent: AtomicUsize::new(0),
+            bytes_received: AtomicUsize::new(0),
+            retries: AtomicUsize::new(0),
+            started: Instant::now(),
+            first_byte: OnceLock::new(),
+        }
+    }
+
+    /// Record that the first response data arrived, if it hasn't been recorded already.
+    fn responded(&self) {
+        self.first_byte.get_or_init(|| self.started.elapsed());
+    }
+
+    fn sent(&self, n: usize) {
+        self.bytes_sent.fetch_add(n, Ordering::Relaxed);
+    }
+
+    fn received(&self, n: usize) {
+        self.bytes_received.fetch_add(n, Ordering::Relaxed);
+    }
+}
+
+/// Wrap `store` to count the bytes transferred through it, if `return_stats` is set.
+pub(crate) fn track(
+    store: Arc<dyn ObjectStore>,
+    return_stats: bool,
+) -> (Arc<dyn ObjectStore>, Option<PyTransferStats>) {
+    if !return_stats {
+        return (store, None);
+    }
+    count_retries();
+    let stats = Arc::new(TransferStats::new());
+    let store = Arc::new(CountingStore {
+        inner: store,
+        stats: stats.clone(),
+    });
+    (store, Some(PyTransferStats(stats)))
+}
+
+/// The result of an operation, paired with its [`PyTransferStats`] if they were requested.
+///
+/// This converts to `result` on its own, or to a `(result, stats)` tuple.
+pub(crate) struct WithStats<T> {
+    result: T,
+    stats: Option<PyTransferStats>,
+}
+
+impl<T> WithStats<T> {
+    pub(crate) fn new(result: T, stats: Option<PyTransferStats>) -> Self {
+        Self { result, stats }
+    }
+}
+
+impl<'py, T: IntoPyObject<'py>> IntoPyObject<'py> for WithStats<T> {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let result = self
+            .result
+            .into_pyobject(py)
+            .map_err(Into::into)?
+            .into_any();
+        match self.stats {
+            Some(stats) => {
+                Ok(PyTuple::new(py, [result, stats.into_pyobject(py)?.into_any()])?.into_any())
+            }
+            None => Ok(result),
+        }
+    }
+}
+
+/// Bytes transferred by a single operation.
+///
+/// The counts are updated as data is transferred, so for a streamed download they keep
+/// growing while the stream is consumed.
+#[pyclass(name = "TransferStats", frozen)]
+pub(crate) struct PyTransferStats(Arc<TransferStats>);
+
+#[pymethods]
+impl PyTransferStats {
+    #[getter]
+    fn bytes_sent(&self) -> usize {
+        self.0.bytes_sent.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn bytes_received(&self) -> usize {
+        self.0.bytes_received.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn retries(&self) -> usize {
+        self.0.retries.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn time_to_first_byte(&self) -> Option<Duration> {
+        self.0.first_byte.get().copied()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "TransferStats(bytes_sent={}, bytes_received={}, retries={}, time_to_first_byte={:?})",
+            self.bytes_sent(),
+            self.bytes_received(),
+            self.retries(),
+            self.time_to_first_byte()
+        )
+    }
+}
+
+/// A store that counts the payload bytes of requests and responses, and the retries of requests,
+/// in [`TransferStats`].
+#[derive(Debug)]
+struct CountingStore {
+    inner: Arc<dyn ObjectStore>,
+    stats: Arc<TransferStats>,
+}
+
+impl CountingStore {
+    /// Run `request` with its retries counted.
+    async fn counted<T>(&self, request: impl Future<Output = T>) -> T {
+        CURRENT.scope(self.stats.clone(), request).await
+    }
+}
+
+impl Display for CountingStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CountingStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CountingStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        let size = payload.content_length();
+        let result = self
+            .counted(self.inner.put_opts(location, payload, opts))
+            .await?;
+        self.stats.responded();
+        self.stats.sent(size);
+        Ok(result)
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let upload = self
+            .counted(self.inner.put_multipart_opts(location, opts))
+            .await?;
+        Ok(Box::new(CountingUpload {
+            inner: upload,
+            stats: self.stats.clone(),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let result = self.counted(self.inner.get_opts(location, options)).await?;
+        let meta = result.meta.clone();
+        let range = result.range.clone();
+        let attributes = result.attributes.clone();
+        let stats = self.stats.clone();
+        let stream = result
+            .into_stream()
+            .inspect_ok(move |bytes| {
+                stats.responded();
+                stats.received(bytes.len());
+            })
+            .boxed();
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(stream),
+            meta,
+            range,
+            attributes,
+        })
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        let bytes = self.counted(self.inner.get_range(location, range)).await?;
+        self.stats.responded();
+        self.stats.received(bytes.len());
+        Ok(bytes)
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        let buffers = self
+            .counted(self.inner.get_ranges(location, ranges))
+            .await?;
+        self.stats.responded();
+        self.stats
+            .received(buffers.iter().map(|buf| buf.len()).sum());
+        Ok(buffers)
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+/// A multipart upload that counts each uploaded part in [`TransferStats`].
+#[derive(Debug)]
+struct CountingUpload {
+    inner: Box<dyn MultipartUpload>,
+    stats: Arc<TransferStats>,
+}
+
+#[async_trait]
+impl MultipartUpload for CountingUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let size = data.content_length();
+        let stats = self.stats.clone();
+        // Scoped within the future, as the upload may be polled from another task
+        CURRENT
+            .scope(stats.clone(), self.inner.put_part(data))
+            .map(move |result| {
+                result?;
+                stats.responded();
+                stats.sent(size);
+                Ok(())
+            })
+            .boxed()
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        let result = CURRENT
+            .scope(self.stats.clone(), self.inner.complete())
+            .await?;
+        self.stats.responded();
+        Ok(result)
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.inner.abort().await
+    }
+}
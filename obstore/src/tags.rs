@@ -1,8 +1,30 @@
+//! Object tags, which can be set when uploading and read or replaced afterwards.
+//!
+//! object_store only supports setting tags on upload, so the tagging requests are made here
+//! with the credentials of the store.
+
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use object_store::TagSet;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use indexmap::IndexMap;
+use object_store::path::Path;
+use object_store::{CredentialProvider, TagSet};
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
+use pyo3_object_store::{PyObjectStore, PyObjectStoreResult, PyS3Store, RegionAwareS3};
+use reqwest::Method;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::bucket::{check_response, parse_xml, request_error, to_xml};
+use crate::copy::encode_path;
+use crate::runtime::{future_into_py, get_runtime};
+use crate::sigv4::signed_request;
+
+const S3: &str = "S3";
 
 pub(crate) struct PyTagSet(TagSet);
 
@@ -22,3 +44,171 @@ impl<'py> FromPyObject<'py> for PyTagSet {
         Ok(Self(tag_set))
     }
 }
+
+/// A store whose object tags can be read and replaced, which is only an S3 store.
+pub(crate) struct TaggingStore(Option<Arc<RegionAwareS3>>);
+
+impl<'py> FromPyObject<'py> for TaggingStore {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(store) = ob.downcast::<PyS3Store>() {
+            Ok(Self(Some(store.get().region_aware().clone())))
+        } else {
+            // Other stores are rejected with NotSupportedError when they're used
+            ob.extract::<PyObjectStore>()?;
+            Ok(Self(None))
+        }
+    }
+}
+
+impl TaggingStore {
+    fn s3(&self) -> object_store::Result<&RegionAwareS3> {
+        self.0
+            .as_deref()
+            .ok_or_else(|| object_store::Error::NotSupported {
+                source: "Object tags can only be read and replaced for an S3Store".into(),
+            })
+    }
+}
+
+// S3 uses XML documents, which are (de)serialized from these mirrors of its schema.
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename = "Tagging")]
+struct S3Tagging {
+    #[serde(rename = "TagSet", default)]
+    tag_set: S3TagSet,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct S3TagSet {
+    #[serde(rename = "Tag", default)]
+    tags: Vec<S3Tag>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct S3Tag {
+    #[serde(rename = "Key")]
+    key: String,
+    #[serde(rename = "Value")]
+    value: String,
+}
+
+/// Send a request for the tags of the object at `path`.
+async fn s3_tagging_request(
+    store: &RegionAwareS3,
+    method: Method,
+    path: &Path,
+    body: Option<String>,
+) -> object_store::Result<String> {
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let url =
+        Url::parse(&format!("{bucket_url}/{}?tagging", encode_path(path))).map_err(|err| {
+            object_store::Error::Generic {
+                store: S3,
+                source: Box::new(err),
+            }
+        })?;
+
+    let mut headers = vec![];
+    let body = body.map(String::into_bytes).unwrap_or_default();
+    if method == Method::PUT {
+        // S3 requires a checksum of the tagging document
+        let checksum = STANDARD.encode(digest(&SHA256, &body));
+        headers.push(("content-type", "application/xml".to_string()));
+        headers.push(("x-amz-checksum-sha256", checksum));
+        headers.push(("x-amz-sdk-checksum-algorithm", "SHA256".to_string()));
+    }
+    let response = signed_request(&credential, method, url, &region, "s3", headers, body)
+        .send()
+        .await
+        .map_err(|err| request_error(S3, err))?;
+    match check_response(S3, response).await {
+        Err(object_store::Error::NotFound { source, .. }) => Err(object_store::Error::NotFound {
+            path: path.to_string(),
+            source,
+        }),
+        result => result,
+    }
+}
+
+async fn get_tags_inner(
+    store: TaggingStore,
+    path: Path,
+) -> PyObjectStoreResult<IndexMap<String, String>> {
+    let xml = s3_tagging_request(store.s3()?, Method::GET, &path, None).await?;
+    let tagging: S3Tagging = parse_xml(&xml)?;
+    Ok(tagging
+        .tag_set
+        .tags
+        .into_iter()
+        .map(|tag| (tag.key, tag.value))
+        .collect())
+}
+
+async fn put_tags_inner(
+    store: TaggingStore,
+    path: Path,
+    tags: IndexMap<String, String>,
+) -> PyObjectStoreResult<()> {
+    let s3 = store.s3()?;
+    if tags.is_empty() {
+        s3_tagging_request(s3, Method::DELETE, &path, None).await?;
+    } else {
+        let tagging = S3Tagging {
+            tag_set: S3TagSet {
+                tags: tags
+                    .into_iter()
+                    .map(|(key, value)| S3Tag { key, value })
+                    .collect(),
+            },
+        };
+        s3_tagging_request(s3, Method::PUT, &path, Some(to_xml(&tagging)?)).await?;
+    }
+    Ok(())
+}
+
+#[pyfunction]
+pub(crate) fn get_tags(
+    py: Python,
+    store: TaggingStore,
+    path: String,
+) -> PyObjectStoreResult<IndexMap<String, String>> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(get_tags_inner(store, path.into())))
+}
+
+#[pyfunction]
+pub(crate) fn get_tags_async(
+    py: Python,
+    store: TaggingStore,
+    path: String,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(
+        py,
+        async move { Ok(get_tags_inner(store, path.into()).await?) },
+    )
+}
+
+#[pyfunction]
+pub(crate) fn put_tags(
+    py: Python,
+    store: TaggingStore,
+    path: String,
+    tags: IndexMap<String, String>,
+) -> PyObjectStoreResult<()> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(put_tags_inner(store, path.into(), tags)))
+}
+
+#[pyfunction]
+pub(crate) fn put_tags_async(
+    py: Python,
+    store: TaggingStore,
+    path: String,
+    tags: IndexMap<String, String>,
+) -> PyResult<Bound<PyAny>> {
+    future_into_py(py, async move {
+        Ok(put_tags_inner(store, path.into(), tags).await?)
+    })
+}
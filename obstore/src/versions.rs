@@ -0,0 +1,359 @@
+//! Restores and reads of the past versions of objects in versioned S3 buckets, which object_store
+//! has no request to list.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use object_store::path::Path;
+use object_store::{GetOptions, ObjectStore};
+use pyo3::prelude::*;
+use pyo3_object_store::{
+    PyObjectStore, PyObjectStoreError, PyObjectStoreResult, PyS3Store, RegionAwareS3,
+};
+use reqwest::Method;
+use serde::Deserialize;
+use url::Url;
+
+use crate::bucket::{check_response, parse_xml, request_error};
+use crate::copy::encode_path;
+use crate::get::{PyGetOptions, PyGetResult};
+use crate::ranges;
+use crate::runtime::{future_into_py, get_runtime};
+use crate::sigv4::{signed_request, uri_encode};
+
+const S3: &str = "S3";
+
+// Mirrors of the schema of the ListObjectVersions response. Its versions and delete markers are
+// interleaved, so they're read along with the other elements as a single sequence.
+
+#[derive(Debug, Deserialize)]
+struct S3ListObjectVersions {
+    #[serde(rename = "$value", default)]
+    elements: Vec<S3ListElement>,
+}
+
+#[derive(Debug, Deserialize)]
+enum S3ListElement {
+    IsTruncated(bool),
+    NextKeyMarker(String),
+    NextVersionIdMarker(String),
+    Version(S3Version),
+    DeleteMarker(S3Version),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct S3Version {
+    #[serde(rename = "Key")]
+    key: String,
+    #[serde(rename = "VersionId")]
+    version_id: String,
+    #[serde(rename = "LastModified")]
+    last_modified: String,
+}
+
+/// A version of an object, or a delete marker.
+#[derive(Debug)]
+struct ObjectVersion {
+    version_id: String,
+    last_modified: DateTime<Utc>,
+    delete_marker: bool,
+}
+
+fn generic_error(
+    source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> object_store::Error {
+    object_store::Error::Generic {
+        store: S3,
+        source: source.into(),
+    }
+}
+
+/// A store whose versions are listed, with the S3 store it is, if any, as only S3 stores can
+/// list them.
+pub(crate) struct VersionedStore {
+    store: Arc<dyn ObjectStore>,
+    s3: Option<Arc<RegionAwareS3>>,
+}
+
+impl<'py> FromPyObject<'py> for VersionedStore {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Checked when the versions are needed, to fail like other unsupported operations
+        Ok(Self {
+            store: ob.extract::<PyObjectStore>()?.into_inner(),
+            s3: ob
+                .downcast::<PyS3Store>()
+                .ok()
+                .map(|store| store.get().region_aware().clone()),
+        })
+    }
+}
+
+impl VersionedStore {
+    fn s3(&self) -> object_store::Result<Arc<RegionAwareS3>> {
+        self.s3
+            .clone()
+            .ok_or_else(|| object_store::Error::NotSupported {
+                source: "Object versions can only be listed in an S3Store".into(),
+            })
+    }
+}
+
+/// List the versions and delete markers of the object at `path`, newest first.
+async fn list_versions(
+    store: &RegionAwareS3,
+    path: &Path,
+) -> object_store::Result<Vec<ObjectVersion>> {
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let mut versions = vec![];
+    let mut marker: Option<(String, String)> = None;
+    loop {
+        let mut url = format!(
+            "{bucket_url}/?versions&prefix={}",
+            uri_encode(path.as_ref())
+        );
+        if let Some((key, version_id)) = &marker {
+            url.push_str(&format!(
+                "&key-marker={}&version-id-marker={}",
+                uri_encode(key),
+                uri_encode(version_id)
+            ));
+        }
+        let url = Url::parse(&url).map_err(generic_error)?;
+        let response = signed_request(&credential, Method::GET, url, &region, "s3", vec![], vec![])
+            .send()
+            .await
+            .map_err(|err| request_error(S3, err))?;
+        let page: S3ListObjectVersions = parse_xml(&check_response(S3, response).await?)?;
+
+        let (mut is_truncated, mut next_key, mut next_version_id) = (false, None, None);
+        for element in page.elements {
+            let (version, delete_marker) = match element {
+                S3ListElement::IsTruncated(value) => {
+                    is_truncated = value;
+                    continue;
+                }
+                S3ListElement::NextKeyMarker(key) => {
+                    next_key = Some(key);
+                    continue;
+                }
+                S3ListElement::NextVersionIdMarker(version_id) => {
+                    next_version_id = Some(version_id);
+                    continue;
+                }
+                S3ListElement::Version(version) => (version, false),
+                S3ListElement::DeleteMarker(version) => (version, true),
+                S3ListElement::Other => continue,
+            };
+            if version.key != path.as_ref() {
+                // Keys are listed in order, in which the path comes before the keys it prefixes
+                return Ok(versions);
+            }
+            let last_modified = DateTime::parse_from_rfc3339(&version.last_modified)
+                .map_err(generic_error)?
+                .with_timezone(&Utc);
+            versions.push(ObjectVersion {
+                version_id: version.version_id,
+                last_modified,
+                delete_marker,
+            });
+        }
+        marker = match (next_key, next_version_id) {
+            (Some(key), Some(version_id)) if is_truncated => Some((key, version_id)),
+            _ => return Ok(versions),
+        };
+    }
+}
+
+/// Permanently delete the version, or delete marker, `version_id` of the object at `path`.
+async fn delete_version(
+    store: &RegionAwareS3,
+    path: &Path,
+    version_id: &str,
+) -> object_store::Result<()> {
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let url = Url::parse(&format!(
+        "{bucket_url}/{}?versionId={}",
+        encode_path(path),
+        uri_encode(version_id)
+    ))
+    .map_err(generic_error)?;
+    let response = signed_request(
+        &credential,
+        Method::DELETE,
+        url,
+        &region,
+        "s3",
+        vec![],
+        vec![],
+    )
+    .send()
+    .await
+    .map_err(|err| request_error(S3, err))?;
+    check_response(S3, response).await?;
+    Ok(())
+}
+
+/// Copy the version `version_id` of the object at `path` over its current version, returning
+/// the ID of the new version.
+async fn copy_version(
+    store: &RegionAwareS3,
+    path: &Path,
+    version_id: &str,
+) -> object_store::Result<String> {
+    let credential = store.current().credentials().get_credential().await?;
+    let (bucket_url, region) = store.bucket_url();
+    let url = Url::parse(&format!("{bucket_url}/{}", encode_path(path))).map_err(generic_error)?;
+    let copy_source = format!(
+        "/{}/{}?versionId={}",
+        store.bucket(),
+        encode_path(path),
+        uri_encode(version_id)
+    );
+    let headers = vec![("x-amz-copy-source", copy_source)];
+    let response = signed_request(
+        &credential,
+        Method::PUT,
+        url,
+        &region,
+        "s3",
+        headers,
+        vec![],
+    )
+    .send()
+    .await
+    .map_err(|err| request_error(S3, err))?;
+    let new_version_id = response
+        .headers()
+        .get("x-amz-version-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    check_response(S3, response).await?;
+    new_version_id.ok_or_else(|| generic_error("The copy of the version has no version ID"))
+}
+
+async fn undelete_inner(
+    store: Arc<RegionAwareS3>,
+    path: Path,
+    copy: bool,
+) -> PyObjectStoreResult<String> {
+    let versions = list_versions(&store, &path).await?;
+    let Some(restored) = versions.iter().position(|version| !version.delete_marker) else {
+        return Err(object_store::Error::NotFound {
+            path: path.to_string(),
+            source: "The object has no version to restore".into(),
+        }
+        .into());
+    };
+    let (markers, restored) = (&versions[..restored], &versions[restored]);
+    if markers.is_empty() {
+        // Not deleted
+        return Ok(restored.version_id.clone());
+    }
+    if copy {
+        return Ok(copy_version(&store, &path, &restored.version_id).await?);
+    }
+    // An object deleted more than once has a marker for each delete
+    for marker in markers {
+        delete_version(&store, &path, &marker.version_id).await?;
+    }
+    Ok(restored.version_id.clone())
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, copy = false))]
+pub(crate) fn undelete(
+    py: Python,
+    store: VersionedStore,
+    path: String,
+    copy: bool,
+) -> PyObjectStoreResult<String> {
+    let store = store.s3()?;
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(undelete_inner(store, path.into(), copy)))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, *, copy = false))]
+pub(crate) fn undelete_async(
+    py: Python,
+    store: VersionedStore,
+    path: String,
+    copy: bool,
+) -> PyResult<Bound<PyAny>> {
+    let store = store.s3().map_err(PyObjectStoreError::from)?;
+    future_into_py(py, async move {
+        Ok(undelete_inner(store, path.into(), copy).await?)
+    })
+}
+
+/// The options for reading the version of the object at `path` that was current at `as_of`.
+async fn version_at(
+    store: &RegionAwareS3,
+    path: &Path,
+    as_of: DateTime<Utc>,
+    options: Option<PyGetOptions>,
+) -> object_store::Result<GetOptions> {
+    let versions = list_versions(store, path).await?;
+    let not_found = |reason: &str| object_store::Error::NotFound {
+        path: path.to_string(),
+        source: format!("The object {reason} at {as_of}").into(),
+    };
+    let version = versions
+        .into_iter()
+        .find(|version| version.last_modified <= as_of)
+        .ok_or_else(|| not_found("didn't exist yet"))?;
+    if version.delete_marker {
+        return Err(not_found("was deleted"));
+    }
+    let options = options.map(GetOptions::from).unwrap_or_default();
+    Ok(GetOptions {
+        version: Some(version.version_id),
+        ..options
+    })
+}
+
+async fn get_at_inner(
+    store: VersionedStore,
+    path: Path,
+    as_of: DateTime<Utc>,
+    options: Option<PyGetOptions>,
+) -> PyObjectStoreResult<PyGetResult> {
+    let s3 = store.s3()?;
+    let options = version_at(&s3, &path, as_of, options).await?;
+    // Read through the store itself, rather than with a signed request, like any other read
+    let out = ranges::get_opts(&store.store, &path, options).await?;
+    Ok(PyGetResult::new(out))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, as_of, *, options = None))]
+pub(crate) fn get_at(
+    py: Python,
+    store: VersionedStore,
+    path: String,
+    as_of: DateTime<Utc>,
+    options: Option<PyGetOptions>,
+) -> PyObjectStoreResult<PyGetResult> {
+    let runtime = get_runtime(py)?;
+    py.allow_threads(|| runtime.block_on(get_at_inner(store, path.into(), as_of, options)))
+}
+
+#[pyfunction]
+#[pyo3(signature = (store, path, as_of, *, options = None))]
+pub(crate) fn get_at_async(
+    py: Python,
+    store: VersionedStore,
+    path: String,
+    as_of: DateTime<Utc>,
+    options: Option<PyGetOptions>,
+) -> PyResult<Bound<PyAny>> {
+    // Fail on other stores when called, as `undelete_async` does
+    store.s3().map_err(PyObjectStoreError::from)?;
+    future_into_py(py, async move {
+        Ok(get_at_inner(store, path.into(), as_of, options).await?)
+    })
+}
@@ -96,5 +96,76 @@ class Bytes(Buffer):
         to their corresponding uppercase counterpart.
         """
 
+    def split_at(self, n: int, /) -> tuple[Bytes, Bytes]:
+        """
+        Split the buffer in two at index `n` without copying, returning `bytes[:n]` and
+        `bytes[n:]`.
+
+        Negative indices count from the end of the buffer. Raises `IndexError` if `n`
+        is out of range.
+        """
+
+    def view(self, start: int, end: int | None = None, /) -> Bytes:
+        """
+        Return `bytes[start:end]` without copying.
+
+        If `end` is not provided, the view extends to the end of the buffer. Negative
+        indices count from the end of the buffer. Raises `IndexError` if either index
+        is out of range.
+        """
+
+    def reader(self) -> BytesReader:
+        """
+        Return a binary file-like object that reads from this buffer without copying.
+
+        This can be passed to parsers that expect a file, or used to walk a large buffer
+        piece by piece:
+
+        ```py
+        import struct
+
+        reader = buffer.reader()
+        (length,) = struct.unpack("<I", reader.read(4))
+        payload = reader.read(length)
+        ```
+        """
+
     def to_bytes(self) -> bytes:
         """Copy this buffer's contents into a Python `bytes` object."""
+
+class BytesReader:
+    """
+    A binary file-like object over a `Bytes` buffer, created by `Bytes.reader()`.
+
+    Reads return `Bytes` slices of the underlying buffer rather than copies.
+    """
+
+    def read(self, size: int = -1, /) -> Bytes:
+        """
+        Read up to `size` bytes. If `size` is negative or not provided, read until the
+        end of the buffer.
+        """
+    def readline(self, size: int = -1, /) -> Bytes:
+        """
+        Read until the next newline, including it, or the end of the buffer. If `size`
+        is non-negative, at most `size` bytes are read.
+        """
+    def seek(self, offset: int, whence: int = 0, /) -> int:
+        """
+        Move to the byte `offset`, relative to the start of the buffer if `whence` is
+        `0`, to the current position if `1`, or to the end of the buffer if `2`.
+        Returns the new position.
+        """
+    def tell(self) -> int:
+        """Return the current position."""
+    def readable(self) -> bool: ...
+    def seekable(self) -> bool: ...
+    def writable(self) -> bool: ...
+    def close(self) -> None:
+        """Close the reader."""
+    @property
+    def closed(self) -> bool:
+        """Whether the reader has been closed."""
+    def __enter__(self) -> BytesReader: ...
+    def __exit__(self, exc_type, exc_value, traceback) -> None: ...
+    def __repr__(self) -> str: ...
@@ -10,6 +10,8 @@ use pyo3::exceptions::{PyIndexError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::{ffi, IntoPyObjectExt};
 
+use crate::reader::PyBytesReader;
+
 /// A wrapper around a [`bytes::Bytes`][].
 ///
 /// This implements both import and export via the Python buffer protocol.
@@ -62,6 +64,17 @@ impl PyBytes {
     pub fn as_slice(&self) -> &[u8] {
         self.as_ref()
     }
+
+    /// Resolve a Python-style index, where negative values count from the end, to an offset
+    /// into the buffer.
+    fn offset(&self, index: isize) -> PyResult<usize> {
+        let len = self.0.len() as isize;
+        let offset = if index < 0 { index + len } else { index };
+        if !(0..=len).contains(&offset) {
+            return Err(PyIndexError::new_err("Index out of range"));
+        }
+        Ok(offset as usize)
+    }
 }
 
 impl From<PyBytes> for Bytes {
@@ -319,6 +332,34 @@ impl PyBytes {
         self.0.to_ascii_uppercase().into()
     }
 
+    /// Split the buffer in two at index `n` without copying, returning `bytes[:n]` and
+    /// `bytes[n:]`.
+    #[pyo3(signature = (n, /))]
+    fn split_at(&self, n: isize) -> PyResult<(PyBytes, PyBytes)> {
+        let n = self.offset(n)?;
+        Ok((self.0.slice(..n).into(), self.0.slice(n..).into()))
+    }
+
+    /// Return `bytes[start:end]` without copying. If `end` is not provided, the view extends
+    /// to the end of the buffer.
+    #[pyo3(signature = (start, end = None, /))]
+    fn view(&self, start: isize, end: Option<isize>) -> PyResult<PyBytes> {
+        let start = self.offset(start)?;
+        let end = match end {
+            Some(end) => self.offset(end)?,
+            None => self.0.len(),
+        };
+        if start > end {
+            return Err(PyValueError::new_err("start must not be after end"));
+        }
+        Ok(self.0.slice(start..end).into())
+    }
+
+    /// Return a binary file-like object that reads from this buffer without copying.
+    fn reader(&self) -> PyBytesReader {
+        PyBytesReader::new(self.0.clone())
+    }
+
     /// Copy this buffer's contents to a Python `bytes` object
     fn to_bytes<'py>(&'py self, py: Python<'py>) -> Bound<'py, pyo3::types::PyBytes> {
         pyo3::types::PyBytes::new(py, &self.0)
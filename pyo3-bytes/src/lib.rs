@@ -2,5 +2,7 @@
 #![warn(missing_docs)]
 
 mod bytes;
+mod reader;
 
 pub use bytes::PyBytes;
+pub use reader::PyBytesReader;
@@ -0,0 +1,154 @@
+//! A file-like reader over a buffer
+
+use bytes::Bytes;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::PyBytes;
+
+/// A binary file-like object reading from a [`bytes::Bytes`][].
+///
+/// Reads return [`PyBytes`] slices of the underlying buffer, so they don't copy any data.
+#[pyclass(name = "BytesReader", weakref)]
+#[derive(Debug)]
+pub struct PyBytesReader {
+    buffer: Bytes,
+    position: usize,
+    closed: bool,
+}
+
+impl PyBytesReader {
+    /// Construct a new [PyBytesReader], positioned at the start of `buffer`
+    pub fn new(buffer: Bytes) -> Self {
+        Self {
+            buffer,
+            position: 0,
+            closed: false,
+        }
+    }
+
+    fn check_closed(&self) -> PyResult<()> {
+        if self.closed {
+            return Err(PyValueError::new_err("I/O operation on closed file."));
+        }
+        Ok(())
+    }
+
+    /// Return up to `size` bytes from the current position, advancing past them.
+    fn take(&mut self, size: usize) -> PyBytes {
+        let start = self.position.min(self.buffer.len());
+        let end = start.saturating_add(size).min(self.buffer.len());
+        self.position = self.position.max(end);
+        self.buffer.slice(start..end).into()
+    }
+
+    /// The number of bytes left to read.
+    fn remaining(&self) -> usize {
+        self.buffer.len().saturating_sub(self.position)
+    }
+}
+
+#[pymethods]
+impl PyBytesReader {
+    /// Read up to `size` bytes. If `size` is negative or not provided, read until the end of
+    /// the buffer.
+    #[pyo3(signature = (size = -1, /))]
+    fn read(&mut self, size: isize) -> PyResult<PyBytes> {
+        self.check_closed()?;
+        let size = if size < 0 {
+            self.remaining()
+        } else {
+            size as usize
+        };
+        Ok(self.take(size))
+    }
+
+    /// Read until the next newline, including it, or the end of the buffer. If `size` is
+    /// non-negative, at most `size` bytes are read.
+    #[pyo3(signature = (size = -1, /))]
+    fn readline(&mut self, size: isize) -> PyResult<PyBytes> {
+        self.check_closed()?;
+        let line_len = self.buffer[self.position.min(self.buffer.len())..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|index| index + 1)
+            .unwrap_or(self.remaining());
+        let size = if size < 0 {
+            line_len
+        } else {
+            line_len.min(size as usize)
+        };
+        Ok(self.take(size))
+    }
+
+    /// Move to the byte `offset`, relative to the start of the buffer if `whence` is 0, to the
+    /// current position if 1, or to the end of the buffer if 2. Returns the new position.
+    #[pyo3(signature = (offset, whence = 0, /))]
+    fn seek(&mut self, offset: isize, whence: usize) -> PyResult<usize> {
+        self.check_closed()?;
+        let origin = match whence {
+            0 => 0,
+            1 => self.position,
+            2 => self.buffer.len(),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid whence ({whence}, should be 0, 1 or 2)"
+                )))
+            }
+        };
+        let position = origin as isize + offset;
+        if position < 0 {
+            return Err(PyValueError::new_err(format!(
+                "Negative seek position {position}"
+            )));
+        }
+        self.position = position as usize;
+        Ok(self.position)
+    }
+
+    /// Return the current position.
+    fn tell(&self) -> PyResult<usize> {
+        self.check_closed()?;
+        Ok(self.position)
+    }
+
+    fn readable(&self) -> PyResult<bool> {
+        self.check_closed()?;
+        Ok(true)
+    }
+
+    fn seekable(&self) -> PyResult<bool> {
+        self.check_closed()?;
+        Ok(true)
+    }
+
+    fn writable(&self) -> PyResult<bool> {
+        self.check_closed()?;
+        Ok(false)
+    }
+
+    /// Close the reader. Closing it doesn't release the buffer, which is shared with the
+    /// `Bytes` the reader was created from.
+    fn close(&mut self) {
+        self.closed = true;
+    }
+
+    #[getter]
+    fn closed(&self) -> bool {
+        self.closed
+    }
+
+    fn __enter__(slf: PyRef<Self>) -> PyResult<PyRef<Self>> {
+        slf.check_closed()?;
+        Ok(slf)
+    }
+
+    fn __exit__(&mut self, _exc_type: PyObject, _exc_value: PyObject, _traceback: PyObject) {
+        self.close();
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BytesReader({})", self.buffer.len())
+    }
+}
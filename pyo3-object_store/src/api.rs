@@ -3,7 +3,9 @@ use pyo3::prelude::*;
 
 use crate::error::*;
 use crate::{
-    PyAzureStore, PyGCSStore, PyHttpStore, PyLocalStore, PyMemoryStore, PyPrefixStore, PyS3Store,
+    PyAzureStore, PyCircuitBreakerStore, PyDefaultGetOptionsStore, PyGCSStore, PyGuardrailStore,
+    PyHttpStore, PyLaneStore, PyLocalStore, PyMemoryStore, PyPrefixStatsStore, PyPrefixStore,
+    PyResolvingStore, PyS3Store, PySignedUrlStore, PyTrashStore,
 };
 
 /// Export the default Python API as a submodule named `store` within the given parent module
@@ -53,6 +55,14 @@ pub fn register_store_module(
     child_module.add_class::<PyMemoryStore>()?;
     child_module.add_class::<PyS3Store>()?;
     child_module.add_class::<PyPrefixStore>()?;
+    child_module.add_class::<PyGuardrailStore>()?;
+    child_module.add_class::<PyTrashStore>()?;
+    child_module.add_class::<PyCircuitBreakerStore>()?;
+    child_module.add_class::<PyResolvingStore>()?;
+    child_module.add_class::<PyPrefixStatsStore>()?;
+    child_module.add_class::<PySignedUrlStore>()?;
+    child_module.add_class::<PyDefaultGetOptionsStore>()?;
+    child_module.add_class::<PyLaneStore>()?;
 
     parent_module.add_submodule(&child_module)?;
 
@@ -99,6 +109,36 @@ pub fn register_exceptions_module(
         "UnknownConfigurationKeyError",
         py.get_type::<UnknownConfigurationKeyError>(),
     )?;
+    child_module.add("InvalidRangeError", py.get_type::<InvalidRangeError>())?;
+
+    parent_module.add_submodule(&child_module)?;
+
+    py.import(intern!(py, "sys"))?
+        .getattr(intern!(py, "modules"))?
+        .set_item(full_module_string.as_str(), &child_module)?;
+
+    // needs to be set *after* `add_submodule()`
+    child_module.setattr("__name__", full_module_string)?;
+
+    Ok(())
+}
+
+/// Export the functions reading request metrics as a submodule named `metrics` within the given
+/// parent module
+///
+/// Once metrics are enabled with `metrics.enable()`, every store extracted as a
+/// [`PyObjectStore`][crate::PyObjectStore] records its requests, which are read with
+/// `metrics.snapshot()`.
+pub fn register_metrics_module(
+    py: Python<'_>,
+    parent_module: &Bound<'_, PyModule>,
+    parent_module_str: &str,
+) -> PyResult<()> {
+    let full_module_string = format!("{}.metrics", parent_module_str);
+
+    let child_module = PyModule::new(parent_module.py(), "metrics")?;
+
+    crate::metrics::add_functions(&child_module)?;
 
     parent_module.add_submodule(&child_module)?;
 
@@ -1,45 +1,168 @@
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::ops::Range;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-use object_store::aws::{AmazonS3, AmazonS3Builder, AmazonS3ConfigKey};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::lock::Mutex;
+use futures::stream::{self, BoxStream, StreamExt};
+use http::Method;
+use object_store::aws::{resolve_bucket_region, AmazonS3, AmazonS3Builder, AmazonS3ConfigKey};
+use object_store::path::Path;
+use object_store::signer::Signer;
+use object_store::{
+    ClientOptions, GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMode, PutMultipartOpts, PutOptions, PutPayload, PutResult,
+};
+use pyo3::exceptions::PyValueError;
 use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
 use pyo3::types::PyType;
+use url::Url;
 
 use crate::client::PyClientOptions;
 use crate::config::PyConfigValue;
+use crate::credentials::PyCredentialProvider;
 use crate::error::{PyObjectStoreError, PyObjectStoreResult};
+use crate::list::owned_list;
+use crate::object_url::{object_url, parse_base_url};
+use crate::prefix::with_prefix;
+use crate::quirks::{quirks_for, Quirks};
 use crate::retry::PyRetryConfig;
 
+/// How many objects are deleted at once by endpoints without bulk deletes.
+const FALLBACK_DELETE_CONCURRENCY: usize = 10;
+
 /// A Python-facing wrapper around an [`AmazonS3`].
+///
+/// Requests are made through a [`RegionAwareS3`], which rebuilds the [`AmazonS3`] for the
+/// region of the bucket if S3 redirects them to another region.
 #[pyclass(name = "S3Store", frozen)]
-pub struct PyS3Store(Arc<AmazonS3>);
+pub struct PyS3Store {
+    store: Arc<RegionAwareS3>,
+    /// The store as built by the constructor, before any region discovery
+    built: Arc<AmazonS3>,
+    /// The `s3://` URL of the bucket
+    base_url: Url,
+}
 
 impl AsRef<Arc<AmazonS3>> for PyS3Store {
+    /// The [`AmazonS3`] built for the configured region.
+    ///
+    /// Requests made through it aren't redirected to the region of the bucket. Use
+    /// [`PyS3Store::region_aware`] to make requests as this store does.
     fn as_ref(&self) -> &Arc<AmazonS3> {
-        &self.0
+        &self.built
     }
 }
 
 impl PyS3Store {
-    /// Consume self and return the underlying [`AmazonS3`].
+    /// Consume self and return the underlying [`AmazonS3`], built for the region of the bucket
+    /// if it has been discovered.
     pub fn into_inner(self) -> Arc<AmazonS3> {
-        self.0
+        self.store.current()
+    }
+
+    /// The [`RegionAwareS3`] requests to this store are made through.
+    pub fn region_aware(&self) -> &Arc<RegionAwareS3> {
+        &self.store
+    }
+
+    fn try_new(
+        mut builder: AmazonS3Builder,
+        client_options: Option<ClientOptions>,
+        force_path_style: Option<bool>,
+        url: Option<&str>,
+    ) -> PyObjectStoreResult<Self> {
+        if let Some(force_path_style) = force_path_style {
+            if force_path_style {
+                check_path_style(&builder, url)?;
+            }
+            builder = builder.with_virtual_hosted_style_request(!force_path_style);
+        }
+        let store = Arc::new(RegionAwareS3::try_new(
+            builder,
+            client_options,
+            url.map(String::from),
+        )?);
+        let built = store.current();
+        let base_url = parse_base_url(&format!("s3://{}", store.bucket()))?;
+        Ok(Self {
+            store,
+            built,
+            base_url,
+        })
+    }
+}
+
+/// Check that nothing configured on `builder` requires virtual-hosted-style requests.
+///
+/// Mixing the two addressing styles otherwise only surfaces as 404s or redirects once requests
+/// are made.
+fn check_path_style(builder: &AmazonS3Builder, url: Option<&str>) -> PyResult<()> {
+    if builder
+        .get_config_value(&AmazonS3ConfigKey::VirtualHostedStyleRequest)
+        .is_some_and(|value| value == "true")
+    {
+        return Err(PyValueError::new_err(
+            "force_path_style=True conflicts with virtual_hosted_style_request=True.",
+        ));
+    }
+    if let Some(url) = url.filter(|url| is_virtual_hosted_url(url)) {
+        return Err(PyValueError::new_err(format!(
+            "force_path_style=True conflicts with the virtual-hosted-style URL {url}. Use a \
+             path-style URL (https://s3.<region>.amazonaws.com/<bucket>) or an s3:// URL \
+             instead."
+        )));
+    }
+    let bucket = builder.get_config_value(&AmazonS3ConfigKey::Bucket);
+    let endpoint = builder.get_config_value(&AmazonS3ConfigKey::Endpoint);
+    if let (Some(bucket), Some(endpoint)) = (bucket, endpoint) {
+        let host = Url::parse(&endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_string()));
+        if host.is_some_and(|host| host.starts_with(&format!("{bucket}."))) {
+            return Err(PyValueError::new_err(format!(
+                "force_path_style=True conflicts with the endpoint {endpoint}, which already \
+                 includes the bucket {bucket} in its host name. Use an endpoint without the \
+                 bucket instead."
+            )));
+        }
     }
+    Ok(())
+}
+
+/// Whether `url` is an `https://<bucket>.s3.<region>.amazonaws.com` URL.
+fn is_virtual_hosted_url(url: &str) -> bool {
+    let Ok(url) = Url::parse(url) else {
+        return false;
+    };
+    let Some((bucket, rest)) = url.host_str().and_then(|host| host.split_once('.')) else {
+        return false;
+    };
+    url.scheme() == "https"
+        && bucket != "s3"
+        && (rest.starts_with("s3.") || rest.starts_with("s3-"))
+        && rest.ends_with(".amazonaws.com")
 }
 
 #[pymethods]
 impl PyS3Store {
     // Create from parameters
     #[new]
-    #[pyo3(signature = (bucket, *, config=None, client_options=None, retry_config=None, **kwargs))]
+    #[pyo3(signature = (bucket, *, config=None, client_options=None, retry_config=None, force_path_style=None, credential_provider=None, **kwargs))]
     fn new(
         bucket: String,
         config: Option<PyAmazonS3Config>,
         client_options: Option<PyClientOptions>,
         retry_config: Option<PyRetryConfig>,
+        force_path_style: Option<bool>,
+        credential_provider: Option<PyObject>,
         kwargs: Option<PyAmazonS3Config>,
     ) -> PyObjectStoreResult<Self> {
         let mut builder = AmazonS3Builder::new().with_bucket_name(bucket);
@@ -49,24 +172,32 @@ impl PyS3Store {
         if let Some(kwargs) = kwargs {
             builder = kwargs.apply_config(builder);
         }
-        if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
+        let client_options = client_options.map(ClientOptions::from);
+        if let Some(client_options) = &client_options {
+            builder = builder.with_client_options(client_options.clone())
         }
         if let Some(retry_config) = retry_config {
             builder = builder.with_retry(retry_config.into())
         }
-        Ok(Self(Arc::new(builder.build()?)))
+        if let Some(credential_provider) = credential_provider {
+            builder =
+                builder.with_credentials(Arc::new(PyCredentialProvider::new(credential_provider)));
+        }
+        Self::try_new(builder, client_options, force_path_style, None)
     }
 
     // Create from env variables
     #[classmethod]
-    #[pyo3(signature = (bucket=None, *, config=None, client_options=None, retry_config=None, **kwargs))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (bucket=None, *, config=None, client_options=None, retry_config=None, force_path_style=None, credential_provider=None, **kwargs))]
     fn from_env(
         _cls: &Bound<PyType>,
         bucket: Option<String>,
         config: Option<PyAmazonS3Config>,
         client_options: Option<PyClientOptions>,
         retry_config: Option<PyRetryConfig>,
+        force_path_style: Option<bool>,
+        credential_provider: Option<PyObject>,
         kwargs: Option<PyAmazonS3Config>,
     ) -> PyObjectStoreResult<Self> {
         let mut builder = AmazonS3Builder::from_env();
@@ -79,19 +210,25 @@ impl PyS3Store {
         if let Some(kwargs) = kwargs {
             builder = kwargs.apply_config(builder);
         }
-        if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
+        let client_options = client_options.map(ClientOptions::from);
+        if let Some(client_options) = &client_options {
+            builder = builder.with_client_options(client_options.clone())
         }
         if let Some(retry_config) = retry_config {
             builder = builder.with_retry(retry_config.into())
         }
-        Ok(Self(Arc::new(builder.build()?)))
+        if let Some(credential_provider) = credential_provider {
+            builder =
+                builder.with_credentials(Arc::new(PyCredentialProvider::new(credential_provider)));
+        }
+        Self::try_new(builder, client_options, force_path_style, None)
     }
 
     // Create from an existing boto3.Session or botocore.session.Session object
     // https://stackoverflow.com/a/36291428
     #[classmethod]
-    #[pyo3(signature = (session, bucket, *, config=None, client_options=None, retry_config=None, **kwargs))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (session, bucket, *, config=None, client_options=None, retry_config=None, force_path_style=None, **kwargs))]
     fn from_session(
         _cls: &Bound<PyType>,
         py: Python,
@@ -100,6 +237,7 @@ impl PyS3Store {
         config: Option<PyAmazonS3Config>,
         client_options: Option<PyClientOptions>,
         retry_config: Option<PyRetryConfig>,
+        force_path_style: Option<bool>,
         kwargs: Option<PyAmazonS3Config>,
     ) -> PyObjectStoreResult<Self> {
         // boto3.Session has a region_name attribute, but botocore.session.Session does not.
@@ -109,31 +247,18 @@ impl PyS3Store {
             None
         };
 
+        // Read the session's credentials on refresh, rather than freezing them now, so that
+        // refreshable credentials such as assumed roles don't expire
         let creds = session.call_method0(intern!(py, "get_credentials"))?;
-        let frozen_creds = creds.call_method0(intern!(py, "get_frozen_credentials"))?;
-
-        let access_key = frozen_creds
-            .getattr(intern!(py, "access_key"))?
-            .extract::<Option<String>>()?;
-        let secret_key = frozen_creds
-            .getattr(intern!(py, "secret_key"))?
-            .extract::<Option<String>>()?;
-        let token = frozen_creds
-            .getattr(intern!(py, "token"))?
-            .extract::<Option<String>>()?;
 
         let mut builder = AmazonS3Builder::new().with_bucket_name(bucket);
         if let Some(region) = region {
             builder = builder.with_region(region);
         }
-        if let Some(access_key) = access_key {
-            builder = builder.with_access_key_id(access_key);
-        }
-        if let Some(secret_key) = secret_key {
-            builder = builder.with_secret_access_key(secret_key);
-        }
-        if let Some(token) = token {
-            builder = builder.with_token(token);
+        if !creds.is_none() {
+            builder = builder.with_credentials(Arc::new(
+                PyCredentialProvider::from_session_credentials(creds.unbind()),
+            ));
         }
         if let Some(config) = config {
             builder = config.apply_config(builder);
@@ -141,24 +266,28 @@ impl PyS3Store {
         if let Some(kwargs) = kwargs {
             builder = kwargs.apply_config(builder);
         }
-        if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
+        let client_options = client_options.map(ClientOptions::from);
+        if let Some(client_options) = &client_options {
+            builder = builder.with_client_options(client_options.clone())
         }
         if let Some(retry_config) = retry_config {
             builder = builder.with_retry(retry_config.into())
         }
 
-        Ok(Self(Arc::new(builder.build()?)))
+        Self::try_new(builder, client_options, force_path_style, None)
     }
 
     #[classmethod]
-    #[pyo3(signature = (url, *, config=None, client_options=None, retry_config=None, **kwargs))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (url, *, config=None, client_options=None, retry_config=None, force_path_style=None, credential_provider=None, **kwargs))]
     fn from_url(
         _cls: &Bound<PyType>,
         url: &str,
         config: Option<PyAmazonS3Config>,
         client_options: Option<PyClientOptions>,
         retry_config: Option<PyRetryConfig>,
+        force_path_style: Option<bool>,
+        credential_provider: Option<PyObject>,
         kwargs: Option<PyAmazonS3Config>,
     ) -> PyObjectStoreResult<Self> {
         let mut builder = AmazonS3Builder::from_env().with_url(url);
@@ -168,21 +297,495 @@ impl PyS3Store {
         if let Some(kwargs) = kwargs {
             builder = kwargs.apply_config(builder);
         }
-        if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
+        let client_options = client_options.map(ClientOptions::from);
+        if let Some(client_options) = &client_options {
+            builder = builder.with_client_options(client_options.clone())
         }
         if let Some(retry_config) = retry_config {
             builder = builder.with_retry(retry_config.into())
         }
-        Ok(Self(Arc::new(builder.build()?)))
+        if let Some(credential_provider) = credential_provider {
+            builder =
+                builder.with_credentials(Arc::new(PyCredentialProvider::new(credential_provider)));
+        }
+        Self::try_new(builder, client_options, force_path_style, Some(url))
+    }
+
+    /// The canonical `s3://` URL of the object at `path`.
+    fn url_for(&self, path: &str) -> String {
+        object_url(&self.base_url, path)
+    }
+
+    #[pyo3(signature = (*, client_options=None, retry_config=None, prefix=None))]
+    fn with_options(
+        &self,
+        py: Python,
+        client_options: Option<PyClientOptions>,
+        retry_config: Option<PyRetryConfig>,
+        prefix: Option<String>,
+    ) -> PyObjectStoreResult<PyObject> {
+        let store = if client_options.is_none() && retry_config.is_none() {
+            // Share the existing store, including its connection pool
+            Self {
+                store: self.store.clone(),
+                built: self.built.clone(),
+                base_url: self.base_url.clone(),
+            }
+        } else {
+            // The builder already includes the addressing style and any discovered region
+            let mut builder = self.store.builder();
+            let client_options = client_options.map(ClientOptions::from);
+            if let Some(client_options) = &client_options {
+                builder = builder.with_client_options(client_options.clone())
+            }
+            if let Some(retry_config) = retry_config {
+                builder = builder.with_retry(retry_config.into())
+            }
+            let client_options = client_options.unwrap_or(self.store.client_options.clone());
+            Self::try_new(
+                builder,
+                Some(client_options),
+                None,
+                self.store.url.as_deref(),
+            )?
+        };
+        let inner = store.store.clone();
+        Ok(with_prefix(py, store, inner, prefix)?)
+    }
+
+    /// The endpoint requests are sent to.
+    #[getter]
+    fn endpoint_url(&self) -> String {
+        self.store.endpoint().endpoint
+    }
+
+    /// The quirks registered for the endpoint of the store.
+    #[getter]
+    fn quirks(&self) -> Quirks {
+        self.store.quirks()
+    }
+
+    /// The region requests are signed for.
+    #[getter]
+    fn region(&self) -> String {
+        self.store.endpoint().region
+    }
+
+    /// Whether the bucket is addressed in the host name or in the path of requests.
+    #[getter]
+    fn addressing_style(&self) -> &'static str {
+        if self.store.endpoint().virtual_hosted_style {
+            "virtual"
+        } else {
+            "path"
+        }
+    }
+
+    /// Whether requests are sent over HTTPS.
+    #[getter]
+    fn using_https(&self) -> bool {
+        self.store.endpoint().endpoint.starts_with("https://")
     }
 
     fn __repr__(&self) -> String {
-        let repr = self.0.to_string();
+        let repr = self.store.to_string();
         repr.replacen("AmazonS3", "S3Store", 1)
     }
 }
 
+/// Whether `err` is S3 redirecting a request because the bucket is in another region.
+///
+/// S3 answers such requests with a `301 PermanentRedirect` without a `Location` header, which
+/// object_store reports as a generic error naming the region as the likely cause.
+/// `tests::detects_region_redirects` checks that the message still does.
+fn is_region_redirect(err: &object_store::Error) -> bool {
+    matches!(err, object_store::Error::Generic { .. })
+        && err.to_string().contains("incorrectly configured region")
+}
+
+/// The endpoint configuration of an [`AmazonS3`].
+struct S3Endpoint {
+    bucket: String,
+    endpoint: String,
+    region: String,
+    virtual_hosted_style: bool,
+}
+
+impl S3Endpoint {
+    /// Resolve the endpoint configuration that `AmazonS3Builder::build` derives from `builder`.
+    ///
+    /// The built store doesn't expose its configuration and URLs passed to `with_url` are only
+    /// parsed in `build`, so this mirrors how `build` applies `url` and its defaults.
+    /// `tests::resolves_url_endpoints` checks this against URLs signed by the built store.
+    fn resolve(builder: &AmazonS3Builder, url: Option<&str>) -> Self {
+        let mut bucket = builder.get_config_value(&AmazonS3ConfigKey::Bucket);
+        let mut region = builder.get_config_value(&AmazonS3ConfigKey::Region);
+        let mut endpoint = builder.get_config_value(&AmazonS3ConfigKey::Endpoint);
+        let mut virtual_hosted_style = builder
+            .get_config_value(&AmazonS3ConfigKey::VirtualHostedStyleRequest)
+            .is_some_and(|value| value == "true");
+
+        // Settings derived from the URL override those of the configuration
+        if let Some(url) = url.and_then(|url| Url::parse(url).ok()) {
+            let parts = url
+                .host_str()
+                .unwrap_or_default()
+                .splitn(4, '.')
+                .collect::<Vec<_>>();
+            let first_segment = || url.path_segments().and_then(|mut s| s.next());
+            match (url.scheme(), parts.as_slice()) {
+                ("s3" | "s3a", _) => bucket = url.host_str().map(String::from),
+                ("https", ["s3", url_region, "amazonaws", "com"]) => {
+                    region = Some(url_region.to_string());
+                    if let Some(segment) = first_segment() {
+                        bucket = Some(segment.to_string());
+                    }
+                }
+                ("https", [url_bucket, "s3", url_region, "amazonaws.com"]) => {
+                    bucket = Some(url_bucket.to_string());
+                    region = Some(url_region.to_string());
+                    virtual_hosted_style = true;
+                }
+                ("https", [account, "r2", "cloudflarestorage", "com"]) => {
+                    region = Some("auto".to_string());
+                    endpoint = Some(format!("https://{account}.r2.cloudflarestorage.com"));
+                    if let Some(segment) = first_segment() {
+                        bucket = Some(segment.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let bucket = bucket.unwrap_or_default();
+        let region = region.unwrap_or_else(|| "us-east-1".to_string());
+        let endpoint = endpoint.unwrap_or_else(|| {
+            if virtual_hosted_style {
+                format!("https://{bucket}.s3.{region}.amazonaws.com")
+            } else {
+                format!("https://s3.{region}.amazonaws.com")
+            }
+        });
+        Self {
+            bucket,
+            endpoint,
+            region,
+            virtual_hosted_style,
+        }
+    }
+}
+
+/// The [`AmazonS3`] currently in use, and the builder it was built from.
+#[derive(Debug)]
+struct BuiltS3 {
+    builder: AmazonS3Builder,
+    store: Arc<AmazonS3>,
+}
+
+/// An [`AmazonS3`] that discovers the region of its bucket when S3 redirects a request to
+/// another region.
+///
+/// The first redirected request looks up the bucket's region, rebuilds the store for that
+/// region, and is retried. The store is rebuilt at most once, and never for custom endpoints,
+/// which aren't addressed by region.
+#[derive(Debug)]
+pub struct RegionAwareS3 {
+    built: RwLock<BuiltS3>,
+    /// The bucket, which is the same for every region the store is built for
+    bucket: String,
+    client_options: ClientOptions,
+    /// The URL the builder was configured with, if any
+    url: Option<String>,
+    /// Whether the region has been discovered
+    discovered: Mutex<bool>,
+}
+
+impl RegionAwareS3 {
+    fn try_new(
+        builder: AmazonS3Builder,
+        client_options: Option<ClientOptions>,
+        url: Option<String>,
+    ) -> object_store::Result<Self> {
+        let bucket = S3Endpoint::resolve(&builder, url.as_deref()).bucket;
+        let store = Arc::new(builder.clone().build()?);
+        Ok(Self {
+            built: RwLock::new(BuiltS3 { builder, store }),
+            bucket,
+            client_options: client_options.unwrap_or_default(),
+            url,
+            discovered: Mutex::new(false),
+        })
+    }
+
+    /// The [`AmazonS3`] currently in use.
+    pub fn current(&self) -> Arc<AmazonS3> {
+        self.built.read().unwrap().store.clone()
+    }
+
+    /// The builder of the store currently in use.
+    fn builder(&self) -> AmazonS3Builder {
+        self.built.read().unwrap().builder.clone()
+    }
+
+    /// The endpoint configuration of the store currently in use.
+    fn endpoint(&self) -> S3Endpoint {
+        let built = self.built.read().unwrap();
+        S3Endpoint::resolve(&built.builder, self.url.as_deref())
+    }
+
+    /// The URL of the bucket, for bucket-level requests, and the region they are signed for.
+    pub fn bucket_url(&self) -> (String, String) {
+        let endpoint = self.endpoint();
+        let url = if endpoint.virtual_hosted_style {
+            endpoint.endpoint
+        } else {
+            format!(
+                "{}/{}",
+                endpoint.endpoint.trim_end_matches('/'),
+                self.bucket
+            )
+        };
+        (url, endpoint.region)
+    }
+
+    /// The name of the bucket of the store.
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// The quirks registered for the endpoint of the store.
+    pub fn quirks(&self) -> Quirks {
+        quirks_for(&self.endpoint().endpoint)
+    }
+
+    /// Rebuild the store for the region of its bucket, returning whether it was rebuilt.
+    async fn discover_region(&self) -> object_store::Result<bool> {
+        if self
+            .builder()
+            .get_config_value(&AmazonS3ConfigKey::Endpoint)
+            .is_some()
+        {
+            return Ok(false);
+        }
+        // Hold the lock while discovering, so that concurrent redirected requests wait for
+        // the rebuilt store
+        let mut discovered = self.discovered.lock().await;
+        if !*discovered {
+            let region = resolve_bucket_region(&self.bucket, &self.client_options).await?;
+            let mut built = self.built.write().unwrap();
+            let builder = built.builder.clone().with_region(region);
+            built.store = Arc::new(builder.clone().build()?);
+            built.builder = builder;
+            *discovered = true;
+        }
+        Ok(true)
+    }
+
+    /// Call `request` with the current store, retrying it once for the bucket's region if it
+    /// was redirected.
+    async fn call<T, F, Fut>(&self, request: F) -> object_store::Result<T>
+    where
+        F: Fn(Arc<AmazonS3>) -> Fut,
+        Fut: Future<Output = object_store::Result<T>>,
+    {
+        match request(self.current()).await {
+            Err(err) if is_region_redirect(&err) => {
+                if self.discover_region().await? {
+                    request(self.current()).await
+                } else {
+                    Err(err)
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+impl Display for RegionAwareS3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.current())
+    }
+}
+
+#[async_trait]
+impl ObjectStore for RegionAwareS3 {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        mut opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        let quirks = self.quirks();
+        if matches!(opts.mode, PutMode::Create)
+            && (quirks.ignores_if_none_match || quirks.emulate_if_none_match)
+        {
+            if !quirks.emulate_if_none_match {
+                // The endpoint would overwrite the object
+                return Err(object_store::Error::NotImplemented);
+            }
+            // Check that the object doesn't exist first, as opted into. This isn't atomic, so
+            // a concurrent put can still be overwritten
+            match self.head(location).await {
+                Ok(_) => {
+                    return Err(object_store::Error::AlreadyExists {
+                        path: location.to_string(),
+                        source: "Object already exists".into(),
+                    })
+                }
+                Err(object_store::Error::NotFound { .. }) => opts.mode = PutMode::Overwrite,
+                Err(err) => return Err(err),
+            }
+        }
+        self.call(|store| {
+            let (payload, opts) = (payload.clone(), opts.clone());
+            async move { store.put_opts(location, payload, opts).await }
+        })
+        .await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.call(|store| {
+            let opts = opts.clone();
+            async move { store.put_multipart_opts(location, opts).await }
+        })
+        .await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.call(|store| {
+            let options = options.clone();
+            async move { store.get_opts(location, options).await }
+        })
+        .await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.call(|store| {
+            let range = range.clone();
+            async move { store.get_range(location, range).await }
+        })
+        .await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.call(|store| async move { store.get_ranges(location, ranges).await })
+            .await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.call(|store| async move { store.head(location).await })
+            .await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.delete(location).await })
+            .await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        if self.quirks().lacks_delete_objects {
+            return locations
+                .map(move |location| async move {
+                    let location = location?;
+                    self.delete(&location).await?;
+                    Ok(location)
+                })
+                .buffered(FALLBACK_DELETE_CONCURRENCY)
+                .boxed();
+        }
+        // Keep the bulk deletes of the underlying store, without retrying them. Batches are
+        // at most the 1000 keys S3 accepts in a single request.
+        let store = self.current();
+        locations
+            .ready_chunks(1000)
+            .then(move |locations| {
+                let store = store.clone();
+                async move {
+                    store
+                        .delete_stream(stream::iter(locations).boxed())
+                        .collect::<Vec<_>>()
+                        .await
+                }
+            })
+            .flat_map(stream::iter)
+            .boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        // Listings are streamed, so they aren't retried
+        owned_list(self.current(), prefix, None)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        owned_list(self.current(), prefix, Some(offset))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.call(|store| async move { store.list_with_delimiter(prefix).await })
+            .await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.copy(from, to).await })
+            .await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.rename(from, to).await })
+            .await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.copy_if_not_exists(from, to).await })
+            .await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.rename_if_not_exists(from, to).await })
+            .await
+    }
+}
+
+#[async_trait]
+impl Signer for RegionAwareS3 {
+    async fn signed_url(
+        &self,
+        method: Method,
+        path: &Path,
+        expires_in: Duration,
+    ) -> object_store::Result<Url> {
+        self.current().signed_url(method, path, expires_in).await
+    }
+
+    async fn signed_urls(
+        &self,
+        method: Method,
+        paths: &[Path],
+        expires_in: Duration,
+    ) -> object_store::Result<Vec<Url>> {
+        self.current().signed_urls(method, paths, expires_in).await
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct PyAmazonS3ConfigKey(AmazonS3ConfigKey);
 
@@ -211,3 +814,117 @@ impl PyAmazonS3Config {
         builder
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use object_store::RetryConfig;
+
+    use super::*;
+
+    fn builder() -> AmazonS3Builder {
+        AmazonS3Builder::new()
+            .with_access_key_id("key")
+            .with_secret_access_key("secret")
+    }
+
+    /// Assert that requests are sent below the bucket URL `RegionAwareS3` resolves, signed for
+    /// its region, by comparing it with a URL signed by the built store.
+    async fn assert_resolved(builder: AmazonS3Builder, url: Option<&str>) {
+        let store = RegionAwareS3::try_new(builder, None, url.map(String::from)).unwrap();
+        let (bucket_url, region) = store.bucket_url();
+        let signed = store
+            .current()
+            .signed_url(
+                Method::GET,
+                &Path::from("file.txt"),
+                Duration::from_secs(60),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            format!(
+                "{}://{}{}",
+                signed.scheme(),
+                signed.authority(),
+                signed.path()
+            ),
+            format!("{bucket_url}/file.txt"),
+        );
+        let credential = signed
+            .query_pairs()
+            .find(|(key, _)| key == "X-Amz-Credential")
+            .unwrap()
+            .1;
+        assert!(credential.ends_with(&format!("/{region}/s3/aws4_request")));
+    }
+
+    #[tokio::test]
+    async fn resolves_configured_endpoints() {
+        assert_resolved(builder().with_bucket_name("bucket"), None).await;
+        assert_resolved(
+            builder()
+                .with_bucket_name("bucket")
+                .with_region("eu-west-1"),
+            None,
+        )
+        .await;
+        assert_resolved(
+            builder()
+                .with_bucket_name("bucket")
+                .with_region("eu-west-1")
+                .with_virtual_hosted_style_request(true),
+            None,
+        )
+        .await;
+        assert_resolved(
+            builder()
+                .with_bucket_name("bucket")
+                .with_endpoint("http://localhost:9000")
+                .with_allow_http(true),
+            None,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn resolves_url_endpoints() {
+        for url in [
+            "s3://bucket",
+            "s3a://bucket",
+            "https://s3.eu-west-2.amazonaws.com/bucket",
+            "https://bucket.s3.eu-west-2.amazonaws.com",
+            "https://account.r2.cloudflarestorage.com/bucket",
+        ] {
+            assert_resolved(builder().with_url(url), Some(url)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn detects_region_redirects() {
+        // S3 redirects requests for buckets in other regions without a Location header
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 4096]);
+            stream
+                .write_all(b"HTTP/1.1 301 Moved Permanently\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+        });
+        let store = builder()
+            .with_bucket_name("bucket")
+            .with_endpoint(endpoint)
+            .with_allow_http(true)
+            .with_retry(RetryConfig {
+                max_retries: 0,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let err = store.head(&Path::from("file.txt")).await.unwrap_err();
+        assert!(is_region_redirect(&err), "{err}");
+    }
+}
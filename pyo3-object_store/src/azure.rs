@@ -6,29 +6,146 @@ use object_store::azure::{AzureConfigKey, MicrosoftAzure, MicrosoftAzureBuilder}
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
 use pyo3::types::PyType;
+use url::Url;
 
 use crate::client::PyClientOptions;
 use crate::config::PyConfigValue;
 use crate::error::{PyObjectStoreError, PyObjectStoreResult};
+use crate::object_url::{object_url, parse_base_url};
+use crate::prefix::with_prefix;
 use crate::retry::PyRetryConfig;
 
 /// A Python-facing wrapper around a [`MicrosoftAzure`].
 #[pyclass(name = "AzureStore", frozen)]
-pub struct PyAzureStore(Arc<MicrosoftAzure>);
+pub struct PyAzureStore {
+    store: Arc<MicrosoftAzure>,
+    /// The `https://` URL of the container
+    base_url: Url,
+    /// The endpoint of the storage account
+    endpoint: String,
+    /// The builder of `store`, used to derive stores with other options
+    builder: MicrosoftAzureBuilder,
+    /// The URL the builder was configured with, if any
+    url: Option<String>,
+}
 
 impl AsRef<Arc<MicrosoftAzure>> for PyAzureStore {
     fn as_ref(&self) -> &Arc<MicrosoftAzure> {
-        &self.0
+        &self.store
     }
 }
 
 impl PyAzureStore {
     /// Consume self and return the underlying [`MicrosoftAzure`].
     pub fn into_inner(self) -> Arc<MicrosoftAzure> {
-        self.0
+        self.store
+    }
+
+    /// The `https://` URL of the container.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn try_new(builder: MicrosoftAzureBuilder, url: Option<&str>) -> PyObjectStoreResult<Self> {
+        let location = AzureLocation::resolve(&builder, url);
+        let store = Arc::new(builder.clone().build()?);
+        let base_url = parse_base_url(&format!("{}/{}", location.endpoint, location.container))?;
+        Ok(Self {
+            store,
+            base_url,
+            endpoint: location.endpoint,
+            builder,
+            url: url.map(String::from),
+        })
     }
 }
 
+/// The container of a [`MicrosoftAzure`] and the endpoint of its storage account.
+struct AzureLocation {
+    container: String,
+    endpoint: String,
+}
+
+impl AzureLocation {
+    /// Resolve the location that `MicrosoftAzureBuilder::build` derives from `builder`.
+    ///
+    /// The built store doesn't expose its configuration and URLs passed to `with_url` are only
+    /// parsed in `build`, so this mirrors how `build` applies `url` and its defaults.
+    /// `tests::resolves_url_locations` checks this against URLs signed by the built store.
+    fn resolve(builder: &MicrosoftAzureBuilder, url: Option<&str>) -> Self {
+        let mut account = builder.get_config_value(&AzureConfigKey::AccountName);
+        let mut container = builder.get_config_value(&AzureConfigKey::ContainerName);
+        let mut fabric = builder
+            .get_config_value(&AzureConfigKey::UseFabricEndpoint)
+            .is_some_and(|value| value == "true");
+
+        // Settings derived from the URL override those of the configuration
+        if let Some(url) = url.and_then(|url| Url::parse(url).ok()) {
+            let host = url.host_str().unwrap_or_default();
+            let first_segment = || url.path_segments().and_then(|mut s| s.next());
+            match url.scheme() {
+                "az" | "adl" | "azure" => container = Some(host.to_string()),
+                "abfs" | "abfss" if url.username().is_empty() => container = Some(host.to_string()),
+                "abfs" | "abfss" => {
+                    container = Some(url.username().to_string());
+                    if let Some(name) = host.strip_suffix(".dfs.core.windows.net") {
+                        account = Some(name.to_string());
+                    } else if let Some(name) = host.strip_suffix(".dfs.fabric.microsoft.com") {
+                        account = Some(name.to_string());
+                        fabric = true;
+                    }
+                }
+                "https" => match host.split_once('.') {
+                    Some((name, "dfs.core.windows.net" | "blob.core.windows.net")) => {
+                        account = Some(name.to_string());
+                        if let Some(segment) = first_segment() {
+                            container = Some(segment.to_string());
+                        }
+                    }
+                    Some((name, "dfs.fabric.microsoft.com" | "blob.fabric.microsoft.com")) => {
+                        account = Some(name.to_string());
+                        if let Some(segment) = first_segment().filter(|s| !s.is_empty()) {
+                            container = Some(segment.to_string());
+                        }
+                        fabric = true;
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+
+        let use_emulator = builder
+            .get_config_value(&AzureConfigKey::UseEmulator)
+            .is_some_and(|value| value == "true");
+        let endpoint = if use_emulator {
+            let account = account.unwrap_or_else(|| EMULATOR_ACCOUNT.to_string());
+            let url = std::env::var("AZURITE_BLOB_STORAGE_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:10000".to_string());
+            format!("{}/{account}", url.trim_end_matches('/'))
+        } else if let Some(endpoint) = builder.get_config_value(&AzureConfigKey::Endpoint) {
+            endpoint.trim_end_matches('/').to_string()
+        } else if fabric {
+            format!(
+                "https://{}.blob.fabric.microsoft.com",
+                account.unwrap_or_default()
+            )
+        } else {
+            format!(
+                "https://{}.blob.core.windows.net",
+                account.unwrap_or_default()
+            )
+        };
+        Self {
+            container: container.unwrap_or_default(),
+            endpoint,
+        }
+    }
+}
+
+/// The account of the Azurite emulator.
+const EMULATOR_ACCOUNT: &str = "devstoreaccount1";
+
 #[pymethods]
 impl PyAzureStore {
     // Create from parameters
@@ -54,7 +171,7 @@ impl PyAzureStore {
         if let Some(retry_config) = retry_config {
             builder = builder.with_retry(retry_config.into())
         }
-        Ok(Self(Arc::new(builder.build()?)))
+        Self::try_new(builder, None)
     }
 
     // Create from env variables
@@ -81,7 +198,7 @@ impl PyAzureStore {
         if let Some(retry_config) = retry_config {
             builder = builder.with_retry(retry_config.into())
         }
-        Ok(Self(Arc::new(builder.build()?)))
+        Self::try_new(builder, None)
     }
 
     #[classmethod]
@@ -107,11 +224,59 @@ impl PyAzureStore {
         if let Some(retry_config) = retry_config {
             builder = builder.with_retry(retry_config.into())
         }
-        Ok(Self(Arc::new(builder.build()?)))
+        Self::try_new(builder, Some(url))
+    }
+
+    #[pyo3(signature = (*, client_options=None, retry_config=None, prefix=None))]
+    fn with_options(
+        &self,
+        py: Python,
+        client_options: Option<PyClientOptions>,
+        retry_config: Option<PyRetryConfig>,
+        prefix: Option<String>,
+    ) -> PyObjectStoreResult<PyObject> {
+        let store = if client_options.is_none() && retry_config.is_none() {
+            // Share the existing store, including its connection pool and cached credentials
+            Self {
+                store: self.store.clone(),
+                base_url: self.base_url.clone(),
+                endpoint: self.endpoint.clone(),
+                builder: self.builder.clone(),
+                url: self.url.clone(),
+            }
+        } else {
+            let mut builder = self.builder.clone();
+            if let Some(client_options) = client_options {
+                builder = builder.with_client_options(client_options.into())
+            }
+            if let Some(retry_config) = retry_config {
+                builder = builder.with_retry(retry_config.into())
+            }
+            Self::try_new(builder, self.url.as_deref())?
+        };
+        let inner = store.as_ref().clone();
+        Ok(with_prefix(py, store, inner, prefix)?)
+    }
+
+    /// The canonical `https://` URL of the object at `path`.
+    fn url_for(&self, path: &str) -> String {
+        object_url(&self.base_url, path)
+    }
+
+    /// The endpoint requests are sent to.
+    #[getter]
+    fn endpoint_url(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Whether requests are sent over HTTPS.
+    #[getter]
+    fn using_https(&self) -> bool {
+        self.endpoint.starts_with("https://")
     }
 
     fn __repr__(&self) -> String {
-        let repr = self.0.to_string();
+        let repr = self.store.to_string();
         repr.replacen("MicrosoftAzure", "AzureStore", 1)
     }
 }
@@ -144,3 +309,86 @@ impl PyAzureConfig {
         builder
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::Method;
+    use object_store::path::Path;
+    use object_store::signer::Signer;
+
+    use super::*;
+
+    fn builder() -> MicrosoftAzureBuilder {
+        // Access keys sign URLs without fetching a delegation key
+        MicrosoftAzureBuilder::new().with_access_key("a2V5")
+    }
+
+    /// Assert that objects are addressed below the container URL `AzureLocation` resolves, by
+    /// comparing it with a URL signed by the built store.
+    async fn assert_resolved(builder: MicrosoftAzureBuilder, url: Option<&str>) {
+        let location = AzureLocation::resolve(&builder, url);
+        let signed = builder
+            .build()
+            .unwrap()
+            .signed_url(
+                Method::GET,
+                &Path::from("file.txt"),
+                Duration::from_secs(60),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            format!(
+                "{}://{}{}",
+                signed.scheme(),
+                signed.authority(),
+                signed.path()
+            ),
+            format!("{}/{}/file.txt", location.endpoint, location.container),
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_configured_locations() {
+        assert_resolved(
+            builder()
+                .with_account("account")
+                .with_container_name("container"),
+            None,
+        )
+        .await;
+        assert_resolved(
+            builder()
+                .with_account("account")
+                .with_container_name("container")
+                .with_use_fabric_endpoint(true),
+            None,
+        )
+        .await;
+        assert_resolved(
+            builder()
+                .with_container_name("container")
+                .with_use_emulator(true),
+            None,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn resolves_url_locations() {
+        for url in [
+            "az://container",
+            "abfs://container",
+            "abfss://container@account.dfs.core.windows.net",
+            "abfss://container@account.dfs.fabric.microsoft.com",
+            "https://account.blob.core.windows.net/container",
+            "https://account.dfs.core.windows.net/container",
+            "https://account.blob.fabric.microsoft.com/container",
+        ] {
+            let builder = builder().with_account("account").with_url(url);
+            assert_resolved(builder, Some(url)).await;
+        }
+    }
+}
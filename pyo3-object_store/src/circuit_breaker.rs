@@ -0,0 +1,557 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::{self, FutureExt};
+use futures::stream::{self, BoxStream, StreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOpts,
+    PutOptions, PutPayload, PutResult, UploadPart,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::list::owned_list;
+use crate::PyObjectStore;
+
+const STORE: &str = "CircuitBreakerStore";
+
+/// The wait before the first retry, doubled for each further retry of the same request.
+const INIT_BACKOFF: Duration = Duration::from_millis(100);
+
+const MAX_BACKOFF: Duration = Duration::from_secs(15);
+
+#[derive(Debug, thiserror::Error)]
+#[error("Circuit breaker is open after {failures} of {requests} recent requests failed")]
+struct CircuitOpenError {
+    failures: usize,
+    requests: usize,
+}
+
+/// Whether `err` indicates that the store is unhealthy.
+///
+/// Errors like a missing object or a failed precondition are valid responses and don't count
+/// against the store. Errors from the underlying client, e.g. connection failures or server
+/// errors that persisted through the client's own retries, are reported as `Generic`.
+fn is_failure(err: &object_store::Error) -> bool {
+    matches!(err, object_store::Error::Generic { store, .. } if *store != STORE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl State {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BreakerConfig {
+    failure_threshold: f64,
+    min_requests: usize,
+    window: Duration,
+    open_duration: Duration,
+    max_retries: usize,
+    retry_budget: f64,
+    min_retries: usize,
+    on_state_change: Option<PyObject>,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: State,
+    /// When the circuit last opened
+    opened: Instant,
+    /// Whether a request is probing the store while half-open
+    probing: bool,
+    window_started: Instant,
+    requests: usize,
+    failures: usize,
+    retries: usize,
+}
+
+impl BreakerState {
+    fn open_error(&self) -> object_store::Error {
+        object_store::Error::Generic {
+            store: STORE,
+            source: Box::new(CircuitOpenError {
+                failures: self.failures,
+                requests: self.requests,
+            }),
+        }
+    }
+
+    fn reset_window(&mut self) {
+        self.window_started = Instant::now();
+        self.requests = 0;
+        self.failures = 0;
+        self.retries = 0;
+    }
+}
+
+#[derive(Debug)]
+struct Breaker {
+    config: BreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl Breaker {
+    /// Move to `state`, returning the transition to report to the callback.
+    fn transition(&self, current: &mut BreakerState, state: State) -> Option<(State, State)> {
+        if current.state == state {
+            return None;
+        }
+        let old = current.state;
+        current.state = state;
+        current.probing = false;
+        if state == State::Open {
+            // Keep the counts that opened the circuit for the error message
+            current.opened = Instant::now();
+        } else {
+            current.reset_window();
+        }
+        Some((old, state))
+    }
+
+    /// Call the `on_state_change` callback. This must not be called with the state locked.
+    fn notify(&self, transition: Option<(State, State)>) {
+        let (Some(callback), Some((old, new))) = (&self.config.on_state_change, transition) else {
+            return;
+        };
+        Python::with_gil(|py| {
+            if let Err(err) = callback.call1(py, (old.as_str(), new.as_str())) {
+                err.write_unraisable(py, Some(callback.bind(py)));
+            }
+        });
+    }
+
+    /// Check whether a request may be sent to the store.
+    fn admit(self: &Arc<Self>) -> object_store::Result<Permit> {
+        let mut state = self.state.lock().unwrap();
+        let mut transition = None;
+        if state.state == State::Open {
+            if state.opened.elapsed() < self.config.open_duration {
+                return Err(state.open_error());
+            }
+            transition = self.transition(&mut state, State::HalfOpen);
+        }
+        let probe = state.state == State::HalfOpen;
+        if probe {
+            // Only let a single request through until the store has recovered
+            if state.probing {
+                return Err(state.open_error());
+            }
+            state.probing = true;
+        }
+        drop(state);
+        self.notify(transition);
+        Ok(Permit {
+            breaker: self.clone(),
+            probe,
+        })
+    }
+
+    fn record(&self, failed: bool, probe: bool) {
+        let mut state = self.state.lock().unwrap();
+        let transition = if probe {
+            let next = if failed { State::Open } else { State::Closed };
+            self.transition(&mut state, next)
+        } else if state.state == State::Closed {
+            if state.window_started.elapsed() >= self.config.window {
+                state.reset_window();
+            }
+            state.requests += 1;
+            if failed {
+                state.failures += 1;
+            }
+            let failure_rate = state.failures as f64 / state.requests as f64;
+            if state.requests >= self.config.min_requests
+                && failure_rate >= self.config.failure_threshold
+            {
+                self.transition(&mut state, State::Open)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        drop(state);
+        self.notify(transition);
+    }
+
+    /// Take a retry from the budget, if it isn't exhausted.
+    fn try_retry(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.state != State::Closed {
+            return false;
+        }
+        let budget = (state.requests as f64 * self.config.retry_budget) as usize;
+        if state.retries >= budget.max(self.config.min_retries) {
+            return false;
+        }
+        state.retries += 1;
+        true
+    }
+}
+
+/// Permission to send one request to the store, which must be resolved with its outcome.
+///
+/// A permit that is dropped without an outcome, e.g. because the request was cancelled, frees
+/// the half-open probe without counting a success or failure.
+struct Permit {
+    breaker: Arc<Breaker>,
+    probe: bool,
+}
+
+impl Permit {
+    fn finish<T>(mut self, result: &object_store::Result<T>) {
+        let failed = result.as_ref().is_err_and(is_failure);
+        self.breaker.record(failed, self.probe);
+        // Recording the outcome of a probe already moved the circuit out of half-open
+        self.probe = false;
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if self.probe {
+            self.breaker.state.lock().unwrap().probing = false;
+        }
+    }
+}
+
+/// A store that stops sending requests to `inner` while it's failing, and retries failed
+/// requests within a retry budget shared by all requests through it.
+///
+/// The circuit opens once at least `failure_threshold` of the requests within a window have
+/// failed, failing requests without sending them for `open_duration`. It then lets one probe
+/// request through, closing again if it succeeds.
+#[derive(Debug)]
+pub struct CircuitBreakerStore {
+    inner: Arc<dyn ObjectStore>,
+    breaker: Arc<Breaker>,
+}
+
+impl CircuitBreakerStore {
+    /// Wrap `inner`, calling `on_state_change` with the old and new states whenever the circuit
+    /// changes state.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        inner: Arc<dyn ObjectStore>,
+        failure_threshold: f64,
+        min_requests: usize,
+        window: Duration,
+        open_duration: Duration,
+        max_retries: usize,
+        retry_budget: f64,
+        min_retries: usize,
+        on_state_change: Option<PyObject>,
+    ) -> Self {
+        let now = Instant::now();
+        Self {
+            inner,
+            breaker: Arc::new(Breaker {
+                config: BreakerConfig {
+                    failure_threshold,
+                    min_requests,
+                    window,
+                    open_duration,
+                    max_retries,
+                    retry_budget,
+                    min_retries,
+                    on_state_change,
+                },
+                state: Mutex::new(BreakerState {
+                    state: State::Closed,
+                    opened: now,
+                    probing: false,
+                    window_started: now,
+                    requests: 0,
+                    failures: 0,
+                    retries: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Call `request`, retrying failures while the retry budget allows.
+    async fn call<T, F, Fut>(&self, request: F) -> object_store::Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = object_store::Result<T>>,
+    {
+        let mut backoff = INIT_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            let permit = self.breaker.admit()?;
+            let result = request().await;
+            permit.finish(&result);
+            match result {
+                Err(err)
+                    if is_failure(&err)
+                        && attempt < self.breaker.config.max_retries
+                        && self.breaker.try_retry() =>
+                {
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Wrap a listing, whose outcome is decided by its first item.
+    fn guard_list(
+        &self,
+        list: impl FnOnce() -> BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let mut permit = match self.breaker.admit() {
+            Ok(permit) => Some(permit),
+            Err(err) => return stream::once(future::ready(Err(err))).boxed(),
+        };
+        list()
+            .map(move |item| {
+                if let Some(permit) = permit.take() {
+                    permit.finish(&item);
+                }
+                item
+            })
+            .boxed()
+    }
+}
+
+impl Display for CircuitBreakerStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CircuitBreakerStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CircuitBreakerStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.call(|| self.inner.put_opts(location, payload.clone(), opts.clone()))
+            .await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let upload = self
+            .call(|| self.inner.put_multipart_opts(location, opts.clone()))
+            .await?;
+        Ok(Box::new(CircuitBreakerUpload {
+            inner: upload,
+            breaker: self.breaker.clone(),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.call(|| self.inner.get_opts(location, options.clone()))
+            .await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.call(|| self.inner.get_range(location, range.clone()))
+            .await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.call(|| self.inner.get_ranges(location, ranges)).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.call(|| self.inner.head(location)).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.call(|| self.inner.delete(location)).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Forwarded so that stores keep deleting in bulk. Like a listing, the stream is admitted
+        // once, and its outcome is decided by its first item.
+        let mut permit = match self.breaker.admit() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                return locations
+                    .map(|_| Err(self.breaker.state.lock().unwrap().open_error()))
+                    .boxed()
+            }
+        };
+        self.inner
+            .delete_stream(locations)
+            .map(move |item| {
+                if let Some(permit) = permit.take() {
+                    permit.finish(&item);
+                }
+                item
+            })
+            .boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.guard_list(|| owned_list(self.inner.clone(), prefix, None))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.guard_list(|| owned_list(self.inner.clone(), prefix, Some(offset)))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.call(|| self.inner.list_with_delimiter(prefix)).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|| self.inner.copy(from, to)).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|| self.inner.rename(from, to)).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|| self.inner.copy_if_not_exists(from, to)).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|| self.inner.rename_if_not_exists(from, to))
+            .await
+    }
+}
+
+/// A multipart upload whose requests go through the [`Breaker`] of its store.
+///
+/// Parts aren't retried, as their payload has been handed to the underlying upload.
+#[derive(Debug)]
+struct CircuitBreakerUpload {
+    inner: Box<dyn MultipartUpload>,
+    breaker: Arc<Breaker>,
+}
+
+#[async_trait]
+impl MultipartUpload for CircuitBreakerUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let permit = match self.breaker.admit() {
+            Ok(permit) => permit,
+            Err(err) => return future::ready(Err(err)).boxed(),
+        };
+        self.inner
+            .put_part(data)
+            .map(move |result| {
+                permit.finish(&result);
+                result
+            })
+            .boxed()
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        let permit = self.breaker.admit()?;
+        let result = self.inner.complete().await;
+        permit.finish(&result);
+        result
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.inner.abort().await
+    }
+}
+
+/// A Python-facing wrapper around a [`CircuitBreakerStore`].
+#[pyclass(name = "CircuitBreakerStore", frozen)]
+pub struct PyCircuitBreakerStore(Arc<CircuitBreakerStore>);
+
+impl AsRef<Arc<CircuitBreakerStore>> for PyCircuitBreakerStore {
+    fn as_ref(&self) -> &Arc<CircuitBreakerStore> {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PyCircuitBreakerStore {
+    #[new]
+    #[pyo3(signature = (store, *, failure_threshold=0.5, min_requests=10, window=Duration::from_secs(60), open_duration=Duration::from_secs(30), max_retries=0, retry_budget=0.1, min_retries=10, on_state_change=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        store: PyObjectStore,
+        failure_threshold: f64,
+        min_requests: usize,
+        window: Duration,
+        open_duration: Duration,
+        max_retries: usize,
+        retry_budget: f64,
+        min_retries: usize,
+        on_state_change: Option<PyObject>,
+    ) -> PyResult<Self> {
+        if !(failure_threshold > 0.0 && failure_threshold <= 1.0) {
+            return Err(PyValueError::new_err(
+                "failure_threshold must be greater than 0 and at most 1",
+            ));
+        }
+        Ok(Self(Arc::new(CircuitBreakerStore::new(
+            store.into_inner(),
+            failure_threshold,
+            min_requests,
+            window,
+            open_duration,
+            max_retries,
+            retry_budget,
+            min_retries,
+            on_state_change,
+        ))))
+    }
+
+    /// The current state of the circuit.
+    #[getter]
+    fn state(&self) -> &'static str {
+        let breaker = &self.0.breaker;
+        let state = breaker.state.lock().unwrap();
+        // An open circuit only moves to half-open on the next request, so report it as such
+        // once it would let that request through
+        if state.state == State::Open && state.opened.elapsed() >= breaker.config.open_duration {
+            State::HalfOpen.as_str()
+        } else {
+            state.state.as_str()
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
@@ -4,12 +4,13 @@ use std::str::FromStr;
 use object_store::{ClientConfigKey, ClientOptions};
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
+use pyo3::types::PyDict;
 
 use crate::config::PyConfigValue;
 use crate::error::PyObjectStoreError;
 
 /// A wrapper around `ClientConfigKey` that implements [`FromPyObject`].
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PyClientConfigKey(ClientConfigKey);
 
 impl<'py> FromPyObject<'py> for PyClientConfigKey {
@@ -21,22 +22,37 @@ impl<'py> FromPyObject<'py> for PyClientConfigKey {
 }
 
 /// A wrapper around `ClientOptions` that implements [`FromPyObject`].
-#[derive(Debug)]
-pub struct PyClientOptions(ClientOptions);
+///
+/// The options are kept as they were passed, so that they can be returned to Python.
+#[derive(Debug, Clone)]
+pub struct PyClientOptions(HashMap<PyClientConfigKey, PyConfigValue>);
 
 impl<'py> FromPyObject<'py> for PyClientOptions {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        let py_input = ob.extract::<HashMap<PyClientConfigKey, PyConfigValue>>()?;
-        let mut options = ClientOptions::new();
-        for (key, value) in py_input.into_iter() {
-            options = options.with_config(key.0, value.0);
+        Ok(Self(ob.extract()?))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for PyClientOptions {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        for (key, value) in self.0 {
+            dict.set_item(key.0.as_ref(), value.0)?;
         }
-        Ok(Self(options))
+        Ok(dict)
     }
 }
 
 impl From<PyClientOptions> for ClientOptions {
     fn from(value: PyClientOptions) -> Self {
-        value.0
+        let mut options = ClientOptions::new();
+        for (key, value) in value.0.into_iter() {
+            options = options.with_config(key.0, value.0);
+        }
+        options
     }
 }
@@ -0,0 +1,126 @@
+//! The source of time and randomness for retries and other time-based decisions.
+//!
+//! By default these are the system clock and an unseeded random number generator. A virtual
+//! clock and a seed can be set instead, so that retry behavior can be reproduced exactly.
+//! The retries made by object_store itself within each request always use system time and
+//! unseeded jitter.
+
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The current time of the virtual clock, if one has been set.
+static VIRTUAL_NOW: RwLock<Option<DateTime<Utc>>> = RwLock::new(None);
+
+/// The generator of jitter, if a seed has been set.
+static SEEDED_RNG: Mutex<Option<StdRng>> = Mutex::new(None);
+
+/// The current time, of the virtual clock if one has been set.
+pub fn now() -> DateTime<Utc> {
+    VIRTUAL_NOW.read().unwrap().unwrap_or_else(Utc::now)
+}
+
+/// A point in time that elapsed time is measured from, like [`std::time::Instant`].
+///
+/// Elapsed time is measured with the virtual clock if one was set when the instant was taken,
+/// and with a monotonic clock otherwise.
+#[derive(Debug, Clone, Copy)]
+pub struct Instant {
+    monotonic: std::time::Instant,
+    virtual_at: Option<DateTime<Utc>>,
+}
+
+impl Instant {
+    /// The current instant.
+    pub fn now() -> Self {
+        Self {
+            monotonic: std::time::Instant::now(),
+            virtual_at: *VIRTUAL_NOW.read().unwrap(),
+        }
+    }
+
+    /// The time elapsed since this instant.
+    pub fn elapsed(&self) -> Duration {
+        match self.virtual_at {
+            Some(at) => (now() - at).to_std().unwrap_or_default(),
+            None => self.monotonic.elapsed(),
+        }
+    }
+}
+
+/// Sleep for `duration`.
+///
+/// With a virtual clock, this advances the clock by `duration` and returns right away.
+pub async fn sleep(duration: Duration) {
+    let advanced = match VIRTUAL_NOW.write().unwrap().as_mut() {
+        Some(now) => {
+            *now += duration;
+            true
+        }
+        None => false,
+    };
+    if advanced {
+        tokio::task::yield_now().await;
+    } else {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Use a virtual clock starting at `start`, or the system clock if `None`.
+pub fn set_clock(start: Option<DateTime<Utc>>) {
+    *VIRTUAL_NOW.write().unwrap() = start;
+}
+
+/// Advance the virtual clock by `duration`, returning `false` if there is none.
+pub fn advance_clock(duration: Duration) -> bool {
+    match VIRTUAL_NOW.write().unwrap().as_mut() {
+        Some(now) => {
+            *now += duration;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Seed the generator of jitter, or go back to an unseeded generator if `None`.
+pub fn seed_jitter(seed: Option<u64>) {
+    *SEEDED_RNG.lock().unwrap() = seed.map(StdRng::seed_from_u64);
+}
+
+/// A random fraction in `[0, 1)`, from the seeded generator if a seed has been set.
+fn random_fraction() -> f64 {
+    match SEEDED_RNG.lock().unwrap().as_mut() {
+        Some(rng) => rng.gen(),
+        None => rand::thread_rng().gen(),
+    }
+}
+
+/// Exponential backoff between the attempts of an operation, with each delay drawn at random
+/// from the upper half of the current backoff.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    next: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    /// Start with a backoff of `init`, doubling it after each attempt up to `max`.
+    pub fn new(init: Duration, max: Duration) -> Self {
+        Self { next: init, max }
+    }
+
+    /// The delay before the next attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next.mul_f64(0.5 + random_fraction() / 2.0);
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+
+    /// Sleep until the next attempt.
+    pub async fn wait(&mut self) {
+        sleep(self.next_delay()).await
+    }
+}
@@ -0,0 +1,185 @@
+//! Credentials for S3 that are fetched by calling back into Python.
+//!
+//! object_store refreshes credentials through a [`CredentialProvider`]. This implements one
+//! around a Python callable, sync or async, so that credentials from any source, such as an
+//! STS role assumption, can be refreshed as they expire.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use futures::lock::Mutex;
+use object_store::aws::AwsCredential;
+use object_store::CredentialProvider;
+use pyo3::exceptions::PyTypeError;
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::clock;
+
+/// Refresh credentials this long before they expire.
+const MIN_TTL: Duration = Duration::from_secs(300);
+
+/// How long the frozen credentials of a boto3 session are used before they're read again.
+///
+/// botocore refreshes expiring credentials at least ten minutes before they expire, so they
+/// stay valid for this long after they're read.
+const SESSION_TTL: Duration = Duration::from_secs(300);
+
+/// Credentials returned by a Python credential provider.
+struct PyAwsCredential {
+    credential: AwsCredential,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl<'py> FromPyObject<'py> for PyAwsCredential {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let dict = ob.downcast::<PyDict>().map_err(|_| {
+            PyTypeError::new_err(
+                "Credential providers must return a dict with access_key_id and \
+                 secret_access_key keys",
+            )
+        })?;
+        let get = |key: &str| -> PyResult<Option<Bound<'py, PyAny>>> {
+            Ok(dict.get_item(key)?.filter(|value| !value.is_none()))
+        };
+        let required = |key: &str| -> PyResult<String> {
+            get(key)?
+                .ok_or_else(|| {
+                    PyTypeError::new_err(format!("Credential provider returned no {key}"))
+                })?
+                .extract()
+        };
+        Ok(Self {
+            credential: AwsCredential {
+                key_id: required("access_key_id")?,
+                secret_key: required("secret_access_key")?,
+                token: get("token")?.map(|token| token.extract()).transpose()?,
+            },
+            expires_at: get("expires_at")?
+                .map(|expires_at| expires_at.extract())
+                .transpose()?,
+        })
+    }
+}
+
+/// Where credentials are fetched from.
+#[derive(Debug)]
+enum Source {
+    /// A callable returning a credential dict, or an awaitable of one
+    Callable(PyObject),
+    /// The credentials of a boto3 or botocore session, read with `get_frozen_credentials`
+    Session(PyObject),
+}
+
+/// The result of calling a credential provider.
+enum Called {
+    Ready(PyObject),
+    Pending(BoxFuture<'static, PyResult<PyObject>>),
+}
+
+/// Credentials, with the time they expire at if they do.
+type Cached = (Arc<AwsCredential>, Option<DateTime<Utc>>);
+
+/// Fetches S3 credentials from Python, caching them until shortly before they expire.
+#[derive(Debug)]
+pub(crate) struct PyCredentialProvider {
+    source: Source,
+    cache: Mutex<Option<Cached>>,
+}
+
+impl PyCredentialProvider {
+    /// Fetch credentials by calling `callable`.
+    pub(crate) fn new(callable: PyObject) -> Self {
+        Self {
+            source: Source::Callable(callable),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Fetch credentials from the credentials object of a boto3 or botocore session, which
+    /// refreshes them itself if they come from a refreshable source.
+    pub(crate) fn from_session_credentials(credentials: PyObject) -> Self {
+        Self {
+            source: Source::Session(credentials),
+            cache: Mutex::new(None),
+        }
+    }
+
+    async fn fetch(&self) -> PyResult<(AwsCredential, Option<DateTime<Utc>>)> {
+        match &self.source {
+            Source::Callable(callable) => {
+                let called = Python::with_gil(|py| -> PyResult<_> {
+                    let result = callable.bind(py).call0()?;
+                    if !result.hasattr(intern!(py, "__await__"))? {
+                        return Ok(Called::Ready(result.unbind()));
+                    }
+                    // Await on the calling event loop, or run the coroutine to completion if
+                    // there is none, as in synchronous calls
+                    match pyo3_async_runtimes::tokio::into_future(result.clone()) {
+                        Ok(future) => Ok(Called::Pending(Box::pin(future))),
+                        Err(_) => Ok(Called::Ready(
+                            py.import(intern!(py, "asyncio"))?
+                                .call_method1(intern!(py, "run"), (result,))?
+                                .unbind(),
+                        )),
+                    }
+                })?;
+                let result = match called {
+                    Called::Ready(result) => result,
+                    Called::Pending(future) => future.await?,
+                };
+                Python::with_gil(|py| {
+                    let credential = result.extract::<PyAwsCredential>(py)?;
+                    Ok((credential.credential, credential.expires_at))
+                })
+            }
+            Source::Session(credentials) => Python::with_gil(|py| {
+                let frozen = credentials
+                    .bind(py)
+                    .call_method0(intern!(py, "get_frozen_credentials"))?;
+                let credential = AwsCredential {
+                    key_id: frozen.getattr(intern!(py, "access_key"))?.extract()?,
+                    secret_key: frozen.getattr(intern!(py, "secret_key"))?.extract()?,
+                    token: frozen.getattr(intern!(py, "token"))?.extract()?,
+                };
+                // Treated as expiring just after they're due to be read again
+                let expires_at = clock::now() + SESSION_TTL + MIN_TTL;
+                Ok((credential, Some(expires_at)))
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for PyCredentialProvider {
+    type Credential = AwsCredential;
+
+    async fn get_credential(&self) -> object_store::Result<Arc<AwsCredential>> {
+        let mut cache = self.cache.lock().await;
+        if let Some((credential, expires_at)) = cache.as_ref() {
+            let fresh = match expires_at {
+                Some(expires_at) => (*expires_at - clock::now())
+                    .to_std()
+                    .is_ok_and(|ttl| ttl > MIN_TTL),
+                None => true,
+            };
+            if fresh {
+                return Ok(credential.clone());
+            }
+        }
+        let (credential, expires_at) =
+            self.fetch()
+                .await
+                .map_err(|err| object_store::Error::Generic {
+                    store: "S3",
+                    source: Box::new(err),
+                })?;
+        let credential = Arc::new(credential);
+        *cache = Some((credential.clone(), expires_at));
+        Ok(credential)
+    }
+}
@@ -3,9 +3,11 @@
 
 use pyo3::exceptions::{PyFileNotFoundError, PyIOError, PyNotImplementedError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::{create_exception, DowncastError};
+use pyo3::{create_exception, intern, DowncastError};
 use thiserror::Error;
 
+use crate::hints::PermissionHint;
+
 // Base exception
 create_exception!(
     pyo3_object_store,
@@ -81,6 +83,12 @@ create_exception!(
     ObstoreError,
     "A Python-facing exception wrapping [object_store::Error::UnknownConfigurationKey]."
 );
+create_exception!(
+    pyo3_object_store,
+    InvalidRangeError,
+    ObstoreError,
+    "A Python-facing exception raised when a byte range is invalid or starts past the end of the object."
+);
 
 /// The Error variants returned by this crate.
 #[derive(Error, Debug)]
@@ -99,57 +107,227 @@ pub enum PyObjectStoreError {
     IOError(#[from] std::io::Error),
 }
 
+impl PyObjectStoreError {
+    /// Name the store that raised this error, such as `"S3"`, to be set as the `store` attribute
+    /// of the exception raised for it.
+    ///
+    /// The store named by an error of object_store itself, or named already, is kept. Errors
+    /// that didn't come from object_store aren't named.
+    pub fn with_store(self, store: &'static str) -> Self {
+        match self {
+            Self::ObjectStoreError(err) => Self::ObjectStoreError(name_store(err, store)),
+            err => err,
+        }
+    }
+}
+
+/// The source of an error, wrapped with the name of the store that raised it.
+#[derive(Debug)]
+struct StoreName {
+    store: &'static str,
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl std::fmt::Display for StoreName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Transparent, so that messages are unchanged
+        write!(f, "{}", self.source)
+    }
+}
+
+impl std::error::Error for StoreName {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Name `store` in `err`, by wrapping its source, unless it names a store already.
+pub(crate) fn name_store(err: object_store::Error, store: &'static str) -> object_store::Error {
+    if store_name(&err).is_some() {
+        return err;
+    }
+    let named = |source| Box::new(StoreName { store, source });
+    match err {
+        object_store::Error::NotFound { path, source } => object_store::Error::NotFound {
+            path,
+            source: named(source),
+        },
+        object_store::Error::NotSupported { source } => object_store::Error::NotSupported {
+            source: named(source),
+        },
+        object_store::Error::AlreadyExists { path, source } => object_store::Error::AlreadyExists {
+            path,
+            source: named(source),
+        },
+        object_store::Error::Precondition { path, source } => object_store::Error::Precondition {
+            path,
+            source: named(source),
+        },
+        object_store::Error::NotModified { path, source } => object_store::Error::NotModified {
+            path,
+            source: named(source),
+        },
+        object_store::Error::PermissionDenied { path, source } => {
+            object_store::Error::PermissionDenied {
+                path,
+                source: named(source),
+            }
+        }
+        object_store::Error::Unauthenticated { path, source } => {
+            object_store::Error::Unauthenticated {
+                path,
+                source: named(source),
+            }
+        }
+        // The sources of the others can't be wrapped
+        err => err,
+    }
+}
+
+/// The store named in `err`, by object_store or by [`name_store`].
+fn store_name(err: &object_store::Error) -> Option<&'static str> {
+    if let object_store::Error::Generic { store, .. }
+    | object_store::Error::UnknownConfigurationKey { store, .. } = err
+    {
+        return Some(store);
+    }
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(named) = err.downcast_ref::<StoreName>() {
+            return Some(named.store);
+        }
+        source = err.source();
+    }
+    None
+}
+
 impl From<PyObjectStoreError> for PyErr {
     fn from(error: PyObjectStoreError) -> Self {
-        // #? gives "pretty-printing" in the errors
-        // https://doc.rust-lang.org/std/fmt/trait.Debug.html
         match error {
             PyObjectStoreError::PyErr(err) => err,
-            PyObjectStoreError::ObjectStoreError(ref err) => match err {
-                object_store::Error::Generic {
-                    store: _,
-                    source: _,
-                } => GenericError::new_err(format!("{err:#?}")),
-                object_store::Error::NotFound { path: _, source: _ } => {
-                    PyFileNotFoundError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::InvalidPath { source: _ } => {
-                    InvalidPathError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::JoinError { source: _ } => {
-                    JoinError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::NotSupported { source: _ } => {
-                    NotSupportedError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::AlreadyExists { path: _, source: _ } => {
-                    AlreadyExistsError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::Precondition { path: _, source: _ } => {
-                    PreconditionError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::NotModified { path: _, source: _ } => {
-                    NotModifiedError::new_err(format!("{err:#?}"))
+            PyObjectStoreError::ObjectStoreError(ref err) => {
+                let message = error_message(err);
+                let py_err = match err {
+                    object_store::Error::Generic { .. } => GenericError::new_err(message),
+                    object_store::Error::NotFound { .. } => PyFileNotFoundError::new_err(message),
+                    object_store::Error::InvalidPath { .. } => InvalidPathError::new_err(message),
+                    object_store::Error::JoinError { .. } => JoinError::new_err(message),
+                    object_store::Error::NotSupported { .. } => NotSupportedError::new_err(message),
+                    object_store::Error::AlreadyExists { .. } => {
+                        AlreadyExistsError::new_err(message)
+                    }
+                    object_store::Error::Precondition { .. } => PreconditionError::new_err(message),
+                    object_store::Error::NotModified { .. } => NotModifiedError::new_err(message),
+                    object_store::Error::NotImplemented => PyNotImplementedError::new_err(message),
+                    object_store::Error::PermissionDenied { .. } => {
+                        PermissionDeniedError::new_err(message)
+                    }
+                    object_store::Error::Unauthenticated { .. } => {
+                        UnauthenticatedError::new_err(message)
+                    }
+                    object_store::Error::UnknownConfigurationKey { .. } => {
+                        UnknownConfigurationKeyError::new_err(message)
+                    }
+                    _ => GenericError::new_err(message),
+                };
+                let path = match err {
+                    object_store::Error::NotFound { path, .. }
+                    | object_store::Error::AlreadyExists { path, .. }
+                    | object_store::Error::Precondition { path, .. }
+                    | object_store::Error::NotModified { path, .. }
+                    | object_store::Error::PermissionDenied { path, .. }
+                    | object_store::Error::Unauthenticated { path, .. } => {
+                        Some(path.as_str()).filter(|path| !path.is_empty())
+                    }
+                    _ => None,
+                };
+                let py_err = with_attributes(py_err, path, store_name(err), status_code(err));
+                if let object_store::Error::PermissionDenied { source, .. } = err {
+                    let hint = source
+                        .downcast_ref::<PermissionHint>()
+                        .map(|hint| hint.hint);
+                    Python::with_gil(|py| {
+                        let _ = py_err.value(py).setattr(intern!(py, "hint"), hint);
+                    });
                 }
-                object_store::Error::NotImplemented => {
-                    PyNotImplementedError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::PermissionDenied { path: _, source: _ } => {
-                    PermissionDeniedError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::Unauthenticated { path: _, source: _ } => {
-                    UnauthenticatedError::new_err(format!("{err:#?}"))
-                }
-                object_store::Error::UnknownConfigurationKey { store: _, key: _ } => {
-                    UnknownConfigurationKeyError::new_err(format!("{err:#?}"))
-                }
-                _ => GenericError::new_err(format!("{err:#?}")),
-            },
-            PyObjectStoreError::IOError(err) => PyIOError::new_err(format!("{err:#?}")),
+                py_err
+            }
+            PyObjectStoreError::IOError(ref err) => {
+                with_attributes(PyIOError::new_err(error_message(err)), None, None, None)
+            }
         }
     }
 }
 
+/// The message of `err`, followed by those of its sources that it doesn't include already.
+fn error_message(err: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        let part = err.to_string();
+        if !message.contains(&part) {
+            message.push_str(": ");
+            message.push_str(&part);
+        }
+        source = err.source();
+    }
+    message
+}
+
+/// The HTTP status of the response that caused `err`, if it was caused by one.
+///
+/// object_store doesn't expose the errors of its HTTP client, so this falls back to the
+/// status in their messages, which read like `... status 404 Not Found ...`, or like
+/// `HTTP status server error (503 Service Unavailable) ...` for those of reqwest.
+pub fn status_code(err: &(dyn std::error::Error + 'static)) -> Option<u16> {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(status) = err
+            .downcast_ref::<reqwest::Error>()
+            .and_then(|err| err.status())
+        {
+            return Some(status.as_u16());
+        }
+        source = err.source();
+    }
+    let message = error_message(err);
+    [
+        "status ",
+        "status code: ",
+        "status client error (",
+        "status server error (",
+    ]
+    .iter()
+    .find_map(|prefix| {
+        message.match_indices(prefix).find_map(|(i, _)| {
+            let rest = &message[i + prefix.len()..];
+            let digits = rest.get(..3)?;
+            if rest[3..].starts_with(|c: char| c.is_ascii_digit()) {
+                return None;
+            }
+            let status = digits.parse::<u16>().ok()?;
+            (100..600).contains(&status).then_some(status)
+        })
+    })
+}
+
+/// Set the `path`, `store` and `status_code` attributes of `err`, to those that are known.
+fn with_attributes(
+    err: PyErr,
+    path: Option<&str>,
+    store: Option<&str>,
+    status_code: Option<u16>,
+) -> PyErr {
+    Python::with_gil(|py| {
+        let value = err.value(py);
+        // Setting attributes on exception instances doesn't fail
+        let _ = value.setattr(intern!(py, "path"), path);
+        let _ = value.setattr(intern!(py, "store"), store);
+        let _ = value.setattr(intern!(py, "status_code"), status_code);
+    });
+    err
+}
+
 impl<'a, 'py> From<DowncastError<'a, 'py>> for PyObjectStoreError {
     fn from(other: DowncastError<'a, 'py>) -> Self {
         Self::PyErr(PyValueError::new_err(format!(
@@ -161,3 +339,83 @@ impl<'a, 'py> From<DowncastError<'a, 'py>> for PyObjectStoreError {
 
 /// A type wrapper around `Result<T, PyObjectStoreError>`.
 pub type PyObjectStoreResult<T> = Result<T, PyObjectStoreError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Messages of object_store 0.11.2 errors, which these tests can't construct.
+    const CLIENT_ERROR: &str = "Client error with status 404 Not Found: No Body";
+    const SERVER_ERROR: &str = "Server error, body contains Error, with status 500 Internal \
+                                Server Error: <Error><Code>InternalError</Code></Error>";
+    const RETRIED_ERROR: &str = "Error after 3 retries in 1.52s, max_retries:3, \
+                                 retry_timeout:180s, source:HTTP status server error (503 \
+                                 Service Unavailable) for url (http://localhost/bucket/file)";
+    const CONNECT_ERROR: &str = "Error after 0 retries in 1.2ms, max_retries:0, \
+                                 retry_timeout:1s, source:error sending request for url \
+                                 (http://127.0.0.1:1/bucket/file)";
+
+    fn not_found(source: &str) -> object_store::Error {
+        object_store::Error::NotFound {
+            path: "file".to_string(),
+            source: source.into(),
+        }
+    }
+
+    fn generic(source: &str) -> object_store::Error {
+        object_store::Error::Generic {
+            store: "S3",
+            source: source.into(),
+        }
+    }
+
+    #[test]
+    fn status_code_from_message() {
+        assert_eq!(status_code(&not_found(CLIENT_ERROR)), Some(404));
+        assert_eq!(status_code(&generic(SERVER_ERROR)), Some(500));
+        assert_eq!(status_code(&generic(RETRIED_ERROR)), Some(503));
+        assert_eq!(status_code(&generic(CONNECT_ERROR)), None);
+    }
+
+    #[test]
+    fn status_code_needs_three_digits() {
+        assert_eq!(status_code(&generic("Unexpected status 4040")), None);
+        assert_eq!(status_code(&generic("Unexpected status 42")), None);
+        assert_eq!(status_code(&generic("Unexpected status 999 Unknown")), None);
+    }
+
+    #[test]
+    fn name_store_keeps_message() {
+        let named = name_store(not_found(CLIENT_ERROR), "S3");
+        assert_eq!(store_name(&named), Some("S3"));
+        assert_eq!(named.to_string(), not_found(CLIENT_ERROR).to_string());
+        assert_eq!(
+            error_message(&named),
+            error_message(&not_found(CLIENT_ERROR))
+        );
+        assert_eq!(status_code(&named), Some(404));
+    }
+
+    #[test]
+    fn name_store_keeps_first_name() {
+        let named = name_store(name_store(not_found(CLIENT_ERROR), "S3"), "InMemory");
+        assert_eq!(store_name(&named), Some("S3"));
+        // Named by object_store itself
+        let generic = name_store(generic(SERVER_ERROR), "InMemory");
+        assert_eq!(store_name(&generic), Some("S3"));
+        // Not nameable
+        assert_eq!(
+            store_name(&name_store(object_store::Error::NotImplemented, "S3")),
+            None
+        );
+    }
+
+    #[test]
+    fn with_store() {
+        let err = PyObjectStoreError::from(not_found(CLIENT_ERROR)).with_store("GCS");
+        let PyObjectStoreError::ObjectStoreError(err) = err else {
+            panic!("expected an object_store error");
+        };
+        assert_eq!(store_name(&err), Some("GCS"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Credentials for Google Cloud workload identity federation.
+//!
+//! Workload identity federation, e.g. on Kubernetes outside of GKE, uses application default
+//! credentials files of type `external_account`. These describe how to exchange a token issued
+//! by another identity provider, read from a file, for a Google access token. object_store only
+//! understands service account and authorized user credentials, so this implements the token
+//! exchange as a [`CredentialProvider`].
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use object_store::gcp::GcpCredential;
+use object_store::CredentialProvider;
+use serde::Deserialize;
+
+use crate::error::PyObjectStoreResult;
+
+const DEFAULT_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// The lifetime requested for impersonated service account tokens.
+const IMPERSONATION_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// Refresh tokens this long before they expire.
+const MIN_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Deserialize)]
+struct ExternalAccount {
+    audience: String,
+    subject_token_type: String,
+    token_url: String,
+    service_account_impersonation_url: Option<String>,
+    credential_source: CredentialSource,
+}
+
+#[derive(Debug, Deserialize)]
+struct CredentialSource {
+    file: Option<String>,
+    format: Option<CredentialFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CredentialFormat {
+    r#type: String,
+    subject_token_field_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StsTokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImpersonationResponse {
+    access_token: String,
+}
+
+fn generic_error(message: impl Into<String>) -> object_store::Error {
+    object_store::Error::Generic {
+        store: "GCS",
+        source: message.into().into(),
+    }
+}
+
+/// Exchanges the subject token of an `external_account` credentials file for a Google access
+/// token, caching it until shortly before it expires.
+#[derive(Debug)]
+pub(crate) struct ExternalAccountProvider {
+    account: ExternalAccount,
+    token_file: String,
+    client: reqwest::Client,
+    cache: Mutex<Option<(Arc<GcpCredential>, Instant)>>,
+}
+
+impl ExternalAccountProvider {
+    /// Load the credentials file at `path` if it is an `external_account` file.
+    ///
+    /// Returns `None` for any other file, including files that can't be read, so that
+    /// object_store reports those as it would otherwise.
+    pub(crate) fn from_file(path: &str) -> PyObjectStoreResult<Option<Self>> {
+        let Ok(contents) = std::fs::read(path) else {
+            return Ok(None);
+        };
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(&contents) else {
+            return Ok(None);
+        };
+        if value.get("type").and_then(|t| t.as_str()) != Some("external_account") {
+            return Ok(None);
+        }
+        let account: ExternalAccount = serde_json::from_value(value).map_err(|err| {
+            generic_error(format!(
+                "Invalid external account credentials in {path}: {err}"
+            ))
+        })?;
+        let token_file = account.credential_source.file.clone().ok_or_else(|| {
+            generic_error(format!(
+                "External account credentials in {path} must use a file credential source"
+            ))
+        })?;
+        Ok(Some(Self {
+            account,
+            token_file,
+            client: reqwest::Client::new(),
+            cache: Mutex::new(None),
+        }))
+    }
+
+    /// Read the subject token, which the platform may rotate at any time.
+    fn subject_token(&self) -> object_store::Result<String> {
+        let contents = std::fs::read_to_string(&self.token_file).map_err(|err| {
+            generic_error(format!(
+                "Could not read token file {}: {err}",
+                self.token_file
+            ))
+        })?;
+        match &self.account.credential_source.format {
+            Some(format) if format.r#type == "json" => {
+                let field = format
+                    .subject_token_field_name
+                    .as_deref()
+                    .ok_or_else(|| generic_error("Missing subject_token_field_name"))?;
+                let value: serde_json::Value = serde_json::from_str(&contents)
+                    .map_err(|err| generic_error(format!("Invalid token file: {err}")))?;
+                value
+                    .get(field)
+                    .and_then(|token| token.as_str())
+                    .map(String::from)
+                    .ok_or_else(|| generic_error(format!("Token file has no field {field}")))
+            }
+            _ => Ok(contents.trim().to_string()),
+        }
+    }
+
+    async fn fetch_token(&self) -> object_store::Result<(Arc<GcpCredential>, Instant)> {
+        let subject_token = self.subject_token()?;
+        let response: StsTokenResponse = self
+            .client
+            .post(&self.account.token_url)
+            .form(&[
+                (
+                    "grant_type",
+                    "urn:ietf:params:oauth:grant-type:token-exchange",
+                ),
+                ("audience", self.account.audience.as_str()),
+                ("scope", DEFAULT_SCOPE),
+                (
+                    "requested_token_type",
+                    "urn:ietf:params:oauth:token-type:access_token",
+                ),
+                ("subject_token", subject_token.as_str()),
+                (
+                    "subject_token_type",
+                    self.account.subject_token_type.as_str(),
+                ),
+            ])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| generic_error(format!("Token exchange failed: {err}")))?
+            .json()
+            .await
+            .map_err(|err| generic_error(format!("Invalid token exchange response: {err}")))?;
+        let expires_in = Duration::from_secs(response.expires_in.unwrap_or(3600));
+
+        let Some(impersonation_url) = &self.account.service_account_impersonation_url else {
+            let credential = GcpCredential {
+                bearer: response.access_token,
+            };
+            return Ok((Arc::new(credential), Instant::now() + expires_in));
+        };
+
+        let response: ImpersonationResponse = self
+            .client
+            .post(impersonation_url)
+            .bearer_auth(&response.access_token)
+            .json(&serde_json::json!({
+                "scope": [DEFAULT_SCOPE],
+                "lifetime": format!("{}s", IMPERSONATION_LIFETIME.as_secs()),
+            }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| generic_error(format!("Service account impersonation failed: {err}")))?
+            .json()
+            .await
+            .map_err(|err| generic_error(format!("Invalid impersonation response: {err}")))?;
+        let credential = GcpCredential {
+            bearer: response.access_token,
+        };
+        Ok((
+            Arc::new(credential),
+            Instant::now() + IMPERSONATION_LIFETIME,
+        ))
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for ExternalAccountProvider {
+    type Credential = GcpCredential;
+
+    async fn get_credential(&self) -> object_store::Result<Arc<GcpCredential>> {
+        let mut cache = self.cache.lock().await;
+        if let Some((credential, expiry)) = cache.as_ref() {
+            if expiry.saturating_duration_since(Instant::now()) > MIN_TTL {
+                return Ok(credential.clone());
+            }
+        }
+        let (credential, expiry) = self.fetch_token().await?;
+        *cache = Some((credential.clone(), expiry));
+        Ok(credential)
+    }
+}
@@ -1,87 +1,251 @@
 use std::collections::HashMap;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 
-use object_store::gcp::{GoogleCloudStorage, GoogleCloudStorageBuilder, GoogleConfigKey};
+use object_store::gcp::{
+    GcpCredentialProvider, GoogleCloudStorage, GoogleCloudStorageBuilder, GoogleConfigKey,
+};
+use object_store::path::Path;
+use object_store::prefix::PrefixStore;
+use object_store::ObjectStore;
+use pyo3::exceptions::PyValueError;
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
-use pyo3::types::PyType;
+use pyo3::types::{PyDict, PyTuple, PyType};
+use url::Url;
 
 use crate::client::PyClientOptions;
 use crate::config::PyConfigValue;
 use crate::error::{PyObjectStoreError, PyObjectStoreResult};
+use crate::external_account::ExternalAccountProvider;
+use crate::object_url::{object_url, parse_base_url};
+use crate::prefix::prefixed_path;
 use crate::retry::PyRetryConfig;
 
+/// The configuration that determines which credentials a store authenticates with.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct CredentialKey(Vec<(String, String)>);
+
+/// The credential providers of the stores built so far, by the configuration of their
+/// credentials.
+///
+/// Credential providers cache the OAuth token they fetch until it expires, so sharing them
+/// lets stores with the same credentials reuse a single token instead of each fetching their
+/// own.
+static SHARED_CREDENTIALS: OnceLock<Mutex<HashMap<CredentialKey, GcpCredentialProvider>>> =
+    OnceLock::new();
+
+fn build_with_shared_credentials(
+    mut builder: GoogleCloudStorageBuilder,
+    credentials: CredentialKey,
+) -> object_store::Result<GoogleCloudStorage> {
+    // Hold the lock while building, so that stores built concurrently with the same
+    // credentials share a single provider
+    let mut shared = SHARED_CREDENTIALS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap();
+    if let Some(provider) = shared.get(&credentials) {
+        builder = builder.with_credentials(provider.clone());
+    }
+    let store = builder.build()?;
+    shared
+        .entry(credentials)
+        .or_insert_with(|| store.credentials().clone());
+    Ok(store)
+}
+
+/// The endpoint of the JSON API, which `GoogleCloudStorage` always uses.
+const GCS_ENDPOINT: &str = "https://storage.googleapis.com";
+
 /// A Python-facing wrapper around a [`GoogleCloudStorage`].
 #[pyclass(name = "GCSStore", frozen)]
-pub struct PyGCSStore(Arc<GoogleCloudStorage>);
+pub struct PyGCSStore {
+    store: Arc<GoogleCloudStorage>,
+    /// `store` with the prefix applied, which operations on this store go through
+    prefixed: Arc<dyn ObjectStore>,
+    bucket: String,
+    /// The `gs://` URL of the bucket
+    base_url: Url,
+    /// The configuration the store was constructed with, used to reconstruct it when it's
+    /// unpickled
+    options: StoreOptions,
+    /// The builder of `store`, used to derive stores with other options
+    builder: GoogleCloudStorageBuilder,
+}
+
+/// The configuration a [`PyGCSStore`] is constructed with.
+#[derive(Debug, Clone)]
+struct StoreOptions {
+    prefix: Option<Path>,
+    config: PyGoogleConfig,
+    client_options: Option<PyClientOptions>,
+    retry_config: Option<PyRetryConfig>,
+}
 
 impl AsRef<Arc<GoogleCloudStorage>> for PyGCSStore {
     fn as_ref(&self) -> &Arc<GoogleCloudStorage> {
-        &self.0
+        &self.store
     }
 }
 
 impl PyGCSStore {
     /// Consume self and return the underlying [`GoogleCloudStorage`].
+    ///
+    /// This doesn't apply the prefix of the store, see [`Self::prefix`].
     pub fn into_inner(self) -> Arc<GoogleCloudStorage> {
-        self.0
+        self.store
+    }
+
+    /// The store that operations on this store go through, with its prefix applied.
+    pub fn object_store(&self) -> Arc<dyn ObjectStore> {
+        self.prefixed.clone()
+    }
+
+    /// The prefix applied to the paths of all operations on this store, if any.
+    pub fn prefix(&self) -> Option<&Path> {
+        self.options.prefix.as_ref()
+    }
+
+    /// Build a store from `builder`, which is configured with `options` here.
+    fn build(
+        builder: GoogleCloudStorageBuilder,
+        options: StoreOptions,
+    ) -> PyObjectStoreResult<Self> {
+        let credentials = options.config.credential_key();
+        let mut builder = options.config.clone().apply_config(builder)?;
+        if let Some(client_options) = options.client_options.clone() {
+            builder = builder.with_client_options(client_options.into())
+        }
+        if let Some(retry_config) = options.retry_config.clone() {
+            builder = builder.with_retry(retry_config.into())
+        }
+        Self::try_new(builder, Some(credentials), options)
+    }
+
+    /// Build a store, sharing the credentials of other stores built with the same
+    /// `credentials` key.
+    fn try_new(
+        builder: GoogleCloudStorageBuilder,
+        credentials: Option<CredentialKey>,
+        options: StoreOptions,
+    ) -> PyObjectStoreResult<Self> {
+        let store = match credentials {
+            Some(credentials) => {
+                Arc::new(build_with_shared_credentials(builder.clone(), credentials)?)
+            }
+            None => Arc::new(builder.clone().build()?),
+        };
+        // Stores derived from this one in `with_options` keep using the same credentials
+        let builder = builder.with_credentials(store.credentials().clone());
+        // The bucket can also be set in the config, which has been applied to the builder.
+        // URLs aren't passed to the builder, so it's always set once the store is built
+        let bucket = builder
+            .get_config_value(&GoogleConfigKey::Bucket)
+            .unwrap_or_default();
+        let base_url = parse_base_url(&format!("gs://{bucket}"))?;
+        Ok(Self {
+            prefixed: with_store_prefix(store.clone(), options.prefix.clone()),
+            store,
+            bucket,
+            base_url,
+            options,
+            builder,
+        })
+    }
+
+    /// A store sharing `self.store`, including its connection pool and cached credentials.
+    fn with_store_options(&self, options: StoreOptions) -> Self {
+        Self {
+            store: self.store.clone(),
+            prefixed: with_store_prefix(self.store.clone(), options.prefix.clone()),
+            bucket: self.bucket.clone(),
+            base_url: self.base_url.clone(),
+            options,
+            builder: self.builder.clone(),
+        }
+    }
+}
+
+fn with_store_prefix(store: Arc<GoogleCloudStorage>, prefix: Option<Path>) -> Arc<dyn ObjectStore> {
+    let store: Arc<dyn ObjectStore> = store;
+    match prefix {
+        Some(prefix) => Arc::new(PrefixStore::new(store, prefix)),
+        None => store,
+    }
+}
+
+/// Parse a `gs://<bucket>/<prefix>` URL into its bucket and prefix.
+///
+/// `GoogleCloudStorageBuilder::with_url` only takes the bucket from URLs, ignoring their path.
+fn parse_url(url: &str) -> PyObjectStoreResult<(String, Option<Path>)> {
+    let parsed = Url::parse(url)
+        .map_err(|err| PyValueError::new_err(format!("Invalid GCS URL {url}: {err}")))?;
+    if parsed.scheme() != "gs" {
+        return Err(PyValueError::new_err(format!(
+            "Unknown URL scheme for GCS: {url}, expected gs://<bucket>/<path>"
+        ))
+        .into());
     }
+    let bucket = match parsed.host_str() {
+        Some(bucket) if !bucket.is_empty() => bucket.to_string(),
+        _ => {
+            return Err(PyValueError::new_err(format!("GCS URL {url} has no bucket")).into());
+        }
+    };
+    let prefix = Path::from_url_path(parsed.path()).map_err(object_store::Error::from)?;
+    let prefix = (prefix.parts().count() > 0).then_some(prefix);
+    Ok((bucket, prefix))
 }
 
 #[pymethods]
 impl PyGCSStore {
     // Create from parameters
     #[new]
-    #[pyo3(signature = (bucket, *, config=None, client_options=None, retry_config=None, **kwargs))]
+    #[pyo3(signature = (bucket, *, prefix=None, config=None, client_options=None, retry_config=None, **kwargs))]
     fn new(
         bucket: String,
+        prefix: Option<String>,
         config: Option<PyGoogleConfig>,
         client_options: Option<PyClientOptions>,
         retry_config: Option<PyRetryConfig>,
         kwargs: Option<PyGoogleConfig>,
     ) -> PyObjectStoreResult<Self> {
-        let mut builder = GoogleCloudStorageBuilder::new().with_bucket_name(bucket);
-        if let Some(config) = config {
-            builder = config.apply_config(builder);
-        }
-        if let Some(kwargs) = kwargs {
-            builder = kwargs.apply_config(builder);
-        }
-        if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
-        }
-        if let Some(retry_config) = retry_config {
-            builder = builder.with_retry(retry_config.into())
-        }
-        Ok(Self(Arc::new(builder.build()?)))
+        let options = StoreOptions {
+            prefix: prefix.map(Path::from),
+            config: PyGoogleConfig::default().merge(config).merge(kwargs),
+            client_options,
+            retry_config,
+        };
+        Self::build(
+            GoogleCloudStorageBuilder::new().with_bucket_name(bucket),
+            options,
+        )
     }
 
     // Create from env variables
     #[classmethod]
-    #[pyo3(signature = (bucket, *, config=None, client_options=None, retry_config=None, **kwargs))]
+    #[pyo3(signature = (bucket, *, prefix=None, config=None, client_options=None, retry_config=None, **kwargs))]
     fn from_env(
         _cls: &Bound<PyType>,
         bucket: String,
+        prefix: Option<String>,
         config: Option<PyGoogleConfig>,
         client_options: Option<PyClientOptions>,
         retry_config: Option<PyRetryConfig>,
         kwargs: Option<PyGoogleConfig>,
     ) -> PyObjectStoreResult<Self> {
-        let mut builder = GoogleCloudStorageBuilder::from_env().with_bucket_name(bucket);
-        if let Some(config) = config {
-            builder = config.apply_config(builder);
-        }
-        if let Some(kwargs) = kwargs {
-            builder = kwargs.apply_config(builder);
-        }
-        if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
-        }
-        if let Some(retry_config) = retry_config {
-            builder = builder.with_retry(retry_config.into())
-        }
-        Ok(Self(Arc::new(builder.build()?)))
+        let options = StoreOptions {
+            prefix: prefix.map(Path::from),
+            config: PyGoogleConfig::from_env().merge(config).merge(kwargs),
+            client_options,
+            retry_config,
+        };
+        Self::build(
+            GoogleCloudStorageBuilder::new().with_bucket_name(bucket),
+            options,
+        )
     }
 
     #[classmethod]
@@ -94,29 +258,122 @@ impl PyGCSStore {
         retry_config: Option<PyRetryConfig>,
         kwargs: Option<PyGoogleConfig>,
     ) -> PyObjectStoreResult<Self> {
-        let mut builder = GoogleCloudStorageBuilder::from_env().with_url(url);
-        if let Some(config) = config {
-            builder = config.apply_config(builder);
+        let (bucket, prefix) = parse_url(url)?;
+        let options = StoreOptions {
+            prefix,
+            config: PyGoogleConfig::from_env().merge(config).merge(kwargs),
+            client_options,
+            retry_config,
+        };
+        Self::build(
+            GoogleCloudStorageBuilder::new().with_bucket_name(bucket),
+            options,
+        )
+    }
+
+    fn __getnewargs_ex__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(Bound<'py, PyTuple>, Bound<'py, PyDict>)> {
+        let args = PyTuple::new(py, [&self.bucket])?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item(intern!(py, "prefix"), self.prefix_str())?;
+        kwargs.set_item(intern!(py, "config"), self.options.config.clone())?;
+        kwargs.set_item(
+            intern!(py, "client_options"),
+            self.options.client_options.clone(),
+        )?;
+        kwargs.set_item(
+            intern!(py, "retry_config"),
+            self.options.retry_config.clone(),
+        )?;
+        Ok((args, kwargs))
+    }
+
+    #[pyo3(signature = (*, client_options=None, retry_config=None, prefix=None))]
+    fn with_options(
+        &self,
+        client_options: Option<PyClientOptions>,
+        retry_config: Option<PyRetryConfig>,
+        prefix: Option<String>,
+    ) -> PyObjectStoreResult<Self> {
+        let mut options = self.options.clone();
+        if let Some(prefix) = prefix {
+            // Nested under the prefix of this store, as a `PrefixStore` wrapping it would be
+            options.prefix = Some(prefixed_path(self.prefix(), &Path::from(prefix)));
         }
-        if let Some(kwargs) = kwargs {
-            builder = kwargs.apply_config(builder);
+        if client_options.is_none() && retry_config.is_none() {
+            return Ok(self.with_store_options(options));
         }
+        let mut builder = self.builder.clone();
         if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
+            builder = builder.with_client_options(client_options.clone().into());
+            options.client_options = Some(client_options);
         }
         if let Some(retry_config) = retry_config {
-            builder = builder.with_retry(retry_config.into())
+            builder = builder.with_retry(retry_config.clone().into());
+            options.retry_config = Some(retry_config);
         }
-        Ok(Self(Arc::new(builder.build()?)))
+        Self::try_new(builder, None, options)
+    }
+
+    /// The canonical `gs://` URL of the object at `path`.
+    fn url_for(&self, path: &str) -> String {
+        let path = prefixed_path(self.prefix(), &Path::from(path));
+        object_url(&self.base_url, path.as_ref())
+    }
+
+    /// The bucket of the store.
+    #[getter]
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// The prefix applied to all paths, if any.
+    #[getter(prefix)]
+    fn prefix_str(&self) -> Option<String> {
+        self.prefix().map(|prefix| prefix.to_string())
+    }
+
+    /// The configuration the store was constructed with.
+    #[getter]
+    fn config(&self) -> PyGoogleConfig {
+        self.options.config.clone()
+    }
+
+    /// The HTTP client options the store was constructed with.
+    #[getter]
+    fn client_options(&self) -> Option<PyClientOptions> {
+        self.options.client_options.clone()
+    }
+
+    /// The retry configuration the store was constructed with.
+    #[getter]
+    fn retry_config(&self) -> Option<PyRetryConfig> {
+        self.options.retry_config.clone()
+    }
+
+    /// The endpoint requests are sent to.
+    #[getter]
+    fn endpoint_url(&self) -> &'static str {
+        GCS_ENDPOINT
+    }
+
+    /// Whether requests are sent over HTTPS.
+    #[getter]
+    fn using_https(&self) -> bool {
+        true
     }
 
     fn __repr__(&self) -> String {
-        let repr = self.0.to_string();
-        repr.replacen("GoogleCloudStorage", "GCSStore", 1)
+        match self.prefix() {
+            Some(prefix) => format!("GCSStore(bucket=\"{}\", prefix=\"{prefix}\")", self.bucket),
+            None => format!("GCSStore(bucket=\"{}\")", self.bucket),
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PyGoogleConfigKey(GoogleConfigKey);
 
 impl<'py> FromPyObject<'py> for PyGoogleConfigKey {
@@ -127,7 +384,7 @@ impl<'py> FromPyObject<'py> for PyGoogleConfigKey {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PyGoogleConfig(HashMap<PyGoogleConfigKey, PyConfigValue>);
 
 impl<'py> FromPyObject<'py> for PyGoogleConfig {
@@ -136,11 +393,100 @@ impl<'py> FromPyObject<'py> for PyGoogleConfig {
     }
 }
 
+impl<'py> IntoPyObject<'py> for PyGoogleConfig {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        for (key, value) in self.0 {
+            dict.set_item(key.0.as_ref(), value.0)?;
+        }
+        Ok(dict)
+    }
+}
+
 impl PyGoogleConfig {
-    fn apply_config(self, mut builder: GoogleCloudStorageBuilder) -> GoogleCloudStorageBuilder {
+    /// Read configuration from the environment, like [`GoogleCloudStorageBuilder::from_env`].
+    ///
+    /// This is collected into a config rather than applied to a builder directly, so that
+    /// application credentials from the environment go through the same handling of
+    /// `external_account` files as those passed explicitly.
+    fn from_env() -> Self {
+        let mut config = HashMap::new();
+        if let Ok(service_account_path) = std::env::var("SERVICE_ACCOUNT") {
+            config.insert(
+                PyGoogleConfigKey(GoogleConfigKey::ServiceAccount),
+                PyConfigValue(service_account_path),
+            );
+        }
+        for (os_key, os_value) in std::env::vars_os() {
+            if let (Some(key), Some(value)) = (os_key.to_str(), os_value.to_str()) {
+                if key.starts_with("GOOGLE_") {
+                    if let Ok(config_key) = key.to_ascii_lowercase().parse() {
+                        config.insert(
+                            PyGoogleConfigKey(config_key),
+                            PyConfigValue(value.to_string()),
+                        );
+                    }
+                }
+            }
+        }
+        Self(config)
+    }
+
+    /// The configuration that determines which credentials a store built from `self` uses.
+    fn credential_key(&self) -> CredentialKey {
+        let mut credentials = [
+            GoogleConfigKey::ServiceAccount,
+            GoogleConfigKey::ServiceAccountKey,
+            GoogleConfigKey::ApplicationCredentials,
+        ]
+        .into_iter()
+        .filter_map(|key| {
+            let name = key.as_ref().to_string();
+            let value = self.0.get(&PyGoogleConfigKey(key))?;
+            Some((name, value.0.clone()))
+        })
+        .collect::<Vec<_>>();
+        credentials.sort();
+        CredentialKey(credentials)
+    }
+
+    /// Override values in `self` with those in `other`.
+    fn merge(mut self, other: Option<Self>) -> Self {
+        if let Some(other) = other {
+            self.0.extend(other.0);
+        }
+        self
+    }
+
+    fn apply_config(
+        mut self,
+        mut builder: GoogleCloudStorageBuilder,
+    ) -> PyObjectStoreResult<GoogleCloudStorageBuilder> {
+        // object_store fails to parse `external_account` application credentials, used for
+        // workload identity federation, so take them out of the config and handle them with a
+        // separate credential provider. An explicit service account still takes precedence.
+        let application_credentials = PyGoogleConfigKey(GoogleConfigKey::ApplicationCredentials);
+        if let Some(path) = self.0.get(&application_credentials) {
+            if let Some(provider) = ExternalAccountProvider::from_file(&path.0)? {
+                self.0.remove(&application_credentials);
+                let has_service_account = self.0.keys().any(|key| {
+                    matches!(
+                        key.0,
+                        GoogleConfigKey::ServiceAccount | GoogleConfigKey::ServiceAccountKey
+                    )
+                });
+                if !has_service_account {
+                    builder = builder.with_credentials(Arc::new(provider));
+                }
+            }
+        }
         for (key, value) in self.0.into_iter() {
             builder = builder.with_config(key.0, value.0);
         }
-        builder
+        Ok(builder)
     }
 }
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetRange, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::list::owned_list;
+use crate::PyObjectStore;
+
+/// The number of chunks of a split range that are fetched at once.
+const RANGE_CHUNK_CONCURRENCY: usize = 8;
+
+/// Conditions applied to every read through a [`DefaultGetOptionsStore`], unless a call
+/// sets its own.
+#[derive(Debug, Clone, Default)]
+pub struct GetDefaults {
+    /// The default `If-Match` condition.
+    pub if_match: Option<String>,
+    /// The default `If-None-Match` condition.
+    pub if_none_match: Option<String>,
+    /// The default `If-Modified-Since` condition.
+    pub if_modified_since: Option<DateTime<Utc>>,
+    /// The default `If-Unmodified-Since` condition.
+    pub if_unmodified_since: Option<DateTime<Utc>>,
+    /// The object version to read by default.
+    pub version: Option<String>,
+}
+
+impl GetDefaults {
+    /// Fill the options that `options` leaves unset from these defaults.
+    fn merge(&self, options: GetOptions) -> GetOptions {
+        GetOptions {
+            if_match: options.if_match.or_else(|| self.if_match.clone()),
+            if_none_match: options.if_none_match.or_else(|| self.if_none_match.clone()),
+            if_modified_since: options.if_modified_since.or(self.if_modified_since),
+            if_unmodified_since: options.if_unmodified_since.or(self.if_unmodified_since),
+            version: options.version.or_else(|| self.version.clone()),
+            range: options.range,
+            head: options.head,
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for GetDefaults {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Update to use derive(FromPyObject) when default is implemented:
+        // https://github.com/PyO3/pyo3/issues/4643
+        let dict = ob.extract::<HashMap<String, Bound<PyAny>>>()?;
+        for key in dict.keys() {
+            if ![
+                "if_match",
+                "if_none_match",
+                "if_modified_since",
+                "if_unmodified_since",
+                "version",
+            ]
+            .contains(&key.as_str())
+            {
+                return Err(PyValueError::new_err(format!(
+                    "Unsupported default get option {key}"
+                )));
+            }
+        }
+        Ok(Self {
+            if_match: dict.get("if_match").map(|x| x.extract()).transpose()?,
+            if_none_match: dict.get("if_none_match").map(|x| x.extract()).transpose()?,
+            if_modified_since: dict
+                .get("if_modified_since")
+                .map(|x| x.extract())
+                .transpose()?,
+            if_unmodified_since: dict
+                .get("if_unmodified_since")
+                .map(|x| x.extract())
+                .transpose()?,
+            version: dict.get("version").map(|x| x.extract()).transpose()?,
+        })
+    }
+}
+
+/// An [`ObjectStore`] wrapper that applies default [`GetOptions`] to every read, and
+/// optionally splits large range requests into concurrent chunks.
+#[derive(Debug)]
+pub struct DefaultGetOptionsStore {
+    inner: Arc<dyn ObjectStore>,
+    defaults: GetDefaults,
+    range_chunk_size: Option<usize>,
+}
+
+impl DefaultGetOptionsStore {
+    /// Wrap `inner`, merging `defaults` into the options of each read.
+    ///
+    /// If `range_chunk_size` is set, byte ranges larger than it are fetched as concurrent
+    /// requests of at most that many bytes.
+    pub fn new(
+        inner: Arc<dyn ObjectStore>,
+        defaults: GetDefaults,
+        range_chunk_size: Option<usize>,
+    ) -> Self {
+        Self {
+            inner,
+            defaults,
+            range_chunk_size,
+        }
+    }
+
+    async fn get_range_once(
+        &self,
+        location: &Path,
+        range: Range<usize>,
+    ) -> object_store::Result<Bytes> {
+        let options = GetOptions {
+            range: Some(GetRange::Bounded(range)),
+            ..Default::default()
+        };
+        self.get_opts(location, options).await?.bytes().await
+    }
+}
+
+impl Display for DefaultGetOptionsStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DefaultGetOptionsStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for DefaultGetOptionsStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.inner
+            .get_opts(location, self.defaults.merge(options))
+            .await
+    }
+
+    // `get`, `get_ranges` and `head` are left to their default implementations, which
+    // call `get_opts` and `get_range` and so apply the defaults too.
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        let chunk_size = match self.range_chunk_size {
+            Some(chunk_size) if range.len() > chunk_size => chunk_size,
+            _ => return self.get_range_once(location, range).await,
+        };
+        let chunks = (range.start..range.end)
+            .step_by(chunk_size)
+            .map(|start| start..(start + chunk_size).min(range.end));
+        let parts = stream::iter(chunks)
+            .map(|chunk| self.get_range_once(location, chunk))
+            .buffered(RANGE_CHUNK_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        let mut buffer = BytesMut::with_capacity(range.len());
+        for part in parts {
+            buffer.extend_from_slice(&part);
+        }
+        Ok(buffer.freeze())
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Forwarded so that stores keep deleting in bulk
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        owned_list(self.inner.clone(), prefix, None)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        owned_list(self.inner.clone(), prefix, Some(offset))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+/// A Python-facing wrapper around a [`DefaultGetOptionsStore`].
+#[pyclass(name = "DefaultGetOptionsStore", frozen)]
+pub struct PyDefaultGetOptionsStore(Arc<DefaultGetOptionsStore>);
+
+impl AsRef<Arc<DefaultGetOptionsStore>> for PyDefaultGetOptionsStore {
+    fn as_ref(&self) -> &Arc<DefaultGetOptionsStore> {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PyDefaultGetOptionsStore {
+    #[new]
+    #[pyo3(signature = (store, *, options=None, range_chunk_size=None))]
+    fn new(
+        store: PyObjectStore,
+        options: Option<GetDefaults>,
+        range_chunk_size: Option<usize>,
+    ) -> PyResult<Self> {
+        if range_chunk_size == Some(0) {
+            return Err(PyValueError::new_err("range_chunk_size must be at least 1"));
+        }
+        Ok(Self(Arc::new(DefaultGetOptionsStore::new(
+            store.into_inner(),
+            options.unwrap_or_default(),
+            range_chunk_size,
+        ))))
+    }
+
+    #[getter]
+    fn options<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let defaults = &self.0.defaults;
+        let dict = PyDict::new(py);
+        if let Some(if_match) = &defaults.if_match {
+            dict.set_item("if_match", if_match)?;
+        }
+        if let Some(if_none_match) = &defaults.if_none_match {
+            dict.set_item("if_none_match", if_none_match)?;
+        }
+        if let Some(if_modified_since) = defaults.if_modified_since {
+            dict.set_item("if_modified_since", if_modified_since)?;
+        }
+        if let Some(if_unmodified_since) = defaults.if_unmodified_since {
+            dict.set_item("if_unmodified_since", if_unmodified_since)?;
+        }
+        if let Some(version) = &defaults.version {
+            dict.set_item("version", version)?;
+        }
+        Ok(dict)
+    }
+
+    #[getter]
+    fn range_chunk_size(&self) -> Option<usize> {
+        self.0.range_chunk_size
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
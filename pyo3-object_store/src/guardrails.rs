@@ -0,0 +1,488 @@
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::{self, FutureExt};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOpts,
+    PutOptions, PutPayload, PutResult, UploadPart,
+};
+use pyo3::prelude::*;
+
+use crate::list::owned_list;
+use crate::PyObjectStore;
+
+const STORE: &str = "GuardrailStore";
+
+/// Length of the window used for `max_total_upload_bytes_per_hour`.
+const UPLOAD_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, thiserror::Error)]
+enum GuardrailError {
+    #[error("Path \"{path}\" is within the denied prefix \"{prefix}\"")]
+    DeniedPrefix { path: Path, prefix: Path },
+
+    #[error("Operation \"{operation}\" on path \"{path}\" is not allowed on a read-only store")]
+    ReadOnly { path: Path, operation: &'static str },
+
+    #[error("Operation \"{operation}\" on path \"{path}\" was rejected by the authorize callback")]
+    Unauthorized { path: Path, operation: &'static str },
+
+    #[error("The authorize callback raised an exception")]
+    AuthorizeCallback {
+        #[from]
+        source: PyErr,
+    },
+
+    #[error("Object \"{path}\" of {size} bytes exceeds max_object_size of {max} bytes")]
+    ObjectTooLarge { path: Path, size: usize, max: usize },
+
+    #[error(
+        "Uploading {size} bytes to \"{path}\" would exceed max_total_upload_bytes_per_hour of {max} bytes ({used} bytes already uploaded in the current window)"
+    )]
+    UploadBudgetExceeded {
+        path: Path,
+        size: usize,
+        used: usize,
+        max: usize,
+    },
+}
+
+impl From<GuardrailError> for object_store::Error {
+    fn from(err: GuardrailError) -> Self {
+        let denied_path = match &err {
+            GuardrailError::DeniedPrefix { path, .. }
+            | GuardrailError::ReadOnly { path, .. }
+            | GuardrailError::Unauthorized { path, .. } => Some(path.to_string()),
+            _ => None,
+        };
+        match denied_path {
+            Some(path) => Self::PermissionDenied {
+                path,
+                source: Box::new(err),
+            },
+            None => Self::Generic {
+                store: STORE,
+                source: Box::new(err),
+            },
+        }
+    }
+}
+
+/// The kind of access passed to the `authorize` callback.
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Get,
+    Head,
+    Put,
+    Delete,
+    List,
+}
+
+impl Operation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::Head => "head",
+            Self::Put => "put",
+            Self::Delete => "delete",
+            Self::List => "list",
+        }
+    }
+
+    /// Whether this operation modifies the store.
+    fn is_write(&self) -> bool {
+        matches!(self, Self::Put | Self::Delete)
+    }
+}
+
+/// Bytes uploaded within the current window.
+#[derive(Debug)]
+struct UploadWindow {
+    started: Instant,
+    bytes: usize,
+}
+
+/// The limits enforced by a [`GuardrailStore`].
+#[derive(Debug)]
+struct Guardrails {
+    max_object_size: Option<usize>,
+    max_total_upload_bytes_per_hour: Option<usize>,
+    deny_prefixes: Vec<Path>,
+    read_only: bool,
+    authorize: Option<PyObject>,
+    upload_window: Mutex<UploadWindow>,
+}
+
+impl Guardrails {
+    fn denied_prefix(&self, location: &Path) -> Option<&Path> {
+        self.deny_prefixes
+            .iter()
+            .find(|prefix| location.prefix_matches(prefix))
+    }
+
+    /// Check that `operation` is allowed on `location`, first against the denied prefixes and
+    /// `read_only`, then against the user-provided `authorize` callback.
+    fn check(&self, location: &Path, operation: Operation) -> object_store::Result<()> {
+        if let Some(prefix) = self.denied_prefix(location) {
+            return Err(GuardrailError::DeniedPrefix {
+                path: location.clone(),
+                prefix: prefix.clone(),
+            }
+            .into());
+        }
+
+        if self.read_only && operation.is_write() {
+            return Err(GuardrailError::ReadOnly {
+                path: location.clone(),
+                operation: operation.as_str(),
+            }
+            .into());
+        }
+
+        if let Some(authorize) = &self.authorize {
+            let allowed = Python::with_gil(|py| {
+                authorize
+                    .call1(py, (location.as_ref(), operation.as_str()))?
+                    .is_truthy(py)
+            })
+            .map_err(GuardrailError::from)?;
+            if !allowed {
+                return Err(GuardrailError::Unauthorized {
+                    path: location.clone(),
+                    operation: operation.as_str(),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check a list operation. Listing the root of the store is passed to the `authorize`
+    /// callback as an empty path.
+    fn check_list(&self, prefix: Option<&Path>) -> object_store::Result<()> {
+        let root = Path::default();
+        self.check(prefix.unwrap_or(&root), Operation::List)
+    }
+
+    fn check_object_size(&self, location: &Path, size: usize) -> object_store::Result<()> {
+        match self.max_object_size {
+            Some(max) if size > max => Err(GuardrailError::ObjectTooLarge {
+                path: location.clone(),
+                size,
+                max,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record `size` uploaded bytes against the hourly budget, failing if the budget would be
+    /// exceeded.
+    fn record_upload(&self, location: &Path, size: usize) -> object_store::Result<()> {
+        let Some(max) = self.max_total_upload_bytes_per_hour else {
+            return Ok(());
+        };
+
+        let mut window = self.upload_window.lock().unwrap();
+        if window.started.elapsed() >= UPLOAD_WINDOW {
+            window.started = Instant::now();
+            window.bytes = 0;
+        }
+        if window.bytes + size > max {
+            return Err(GuardrailError::UploadBudgetExceeded {
+                path: location.clone(),
+                size,
+                used: window.bytes,
+                max,
+            }
+            .into());
+        }
+        window.bytes += size;
+        Ok(())
+    }
+}
+
+/// An [`ObjectStore`] wrapper that enforces client-side usage limits before delegating to an
+/// inner store.
+#[derive(Debug)]
+pub struct GuardrailStore {
+    inner: Arc<dyn ObjectStore>,
+    guardrails: Arc<Guardrails>,
+}
+
+impl GuardrailStore {
+    /// Wrap `inner` with the provided limits.
+    ///
+    /// If `read_only` is set, every put and delete is rejected. If provided, `authorize` is a
+    /// Python callable of `(path, operation)` consulted before every operation. A falsy return
+    /// value rejects the operation.
+    pub fn new(
+        inner: Arc<dyn ObjectStore>,
+        max_object_size: Option<usize>,
+        max_total_upload_bytes_per_hour: Option<usize>,
+        deny_prefixes: Vec<Path>,
+        read_only: bool,
+        authorize: Option<PyObject>,
+    ) -> Self {
+        Self {
+            inner,
+            guardrails: Arc::new(Guardrails {
+                max_object_size,
+                max_total_upload_bytes_per_hour,
+                deny_prefixes,
+                read_only,
+                authorize,
+                upload_window: Mutex::new(UploadWindow {
+                    started: Instant::now(),
+                    bytes: 0,
+                }),
+            }),
+        }
+    }
+
+    fn filter_list(
+        &self,
+        stream: BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        if self.guardrails.deny_prefixes.is_empty() {
+            return stream;
+        }
+        let guardrails = self.guardrails.clone();
+        stream
+            .try_filter(move |meta| {
+                future::ready(guardrails.denied_prefix(&meta.location).is_none())
+            })
+            .boxed()
+    }
+}
+
+impl Display for GuardrailStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GuardrailStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for GuardrailStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.guardrails.check(location, Operation::Put)?;
+        let size = payload.content_length();
+        self.guardrails.check_object_size(location, size)?;
+        self.guardrails.record_upload(location, size)?;
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.guardrails.check(location, Operation::Put)?;
+        let upload = self.inner.put_multipart_opts(location, opts).await?;
+        Ok(Box::new(GuardrailUpload {
+            inner: upload,
+            location: location.clone(),
+            guardrails: self.guardrails.clone(),
+            written: 0,
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let operation = if options.head {
+            Operation::Head
+        } else {
+            Operation::Get
+        };
+        self.guardrails.check(location, operation)?;
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.guardrails.check(location, Operation::Get)?;
+        self.inner.get_range(location, range).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.guardrails.check(location, Operation::Get)?;
+        self.inner.get_ranges(location, ranges).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.guardrails.check(location, Operation::Head)?;
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.guardrails.check(location, Operation::Delete)?;
+        self.inner.delete(location).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        let locations = locations
+            .map(move |location| {
+                let location = location?;
+                self.guardrails.check(&location, Operation::Delete)?;
+                Ok(location)
+            })
+            .boxed();
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        if let Err(err) = self.guardrails.check_list(prefix) {
+            return stream::once(future::ready(Err(err))).boxed();
+        }
+        self.filter_list(owned_list(self.inner.clone(), prefix, None))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        if let Err(err) = self.guardrails.check_list(prefix) {
+            return stream::once(future::ready(Err(err))).boxed();
+        }
+        self.filter_list(owned_list(self.inner.clone(), prefix, Some(offset)))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.guardrails.check_list(prefix)?;
+        let mut result = self.inner.list_with_delimiter(prefix).await?;
+        result
+            .objects
+            .retain(|meta| self.guardrails.denied_prefix(&meta.location).is_none());
+        result
+            .common_prefixes
+            .retain(|prefix| self.guardrails.denied_prefix(prefix).is_none());
+        Ok(result)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.guardrails.check(from, Operation::Get)?;
+        self.guardrails.check(to, Operation::Put)?;
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.guardrails.check(from, Operation::Delete)?;
+        self.guardrails.check(to, Operation::Put)?;
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.guardrails.check(from, Operation::Get)?;
+        self.guardrails.check(to, Operation::Put)?;
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.guardrails.check(from, Operation::Delete)?;
+        self.guardrails.check(to, Operation::Put)?;
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+/// A multipart upload that counts each part against the [`Guardrails`] of its store.
+#[derive(Debug)]
+struct GuardrailUpload {
+    inner: Box<dyn MultipartUpload>,
+    location: Path,
+    guardrails: Arc<Guardrails>,
+    written: usize,
+}
+
+#[async_trait]
+impl MultipartUpload for GuardrailUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let size = data.content_length();
+        self.written += size;
+        let checked = self
+            .guardrails
+            .check_object_size(&self.location, self.written)
+            .and_then(|_| self.guardrails.record_upload(&self.location, size));
+        match checked {
+            Ok(()) => self.inner.put_part(data),
+            Err(err) => future::ready(Err(err)).boxed(),
+        }
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        self.inner.complete().await
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.inner.abort().await
+    }
+}
+
+/// A Python-facing wrapper around a [`GuardrailStore`].
+#[pyclass(name = "GuardrailStore", frozen)]
+pub struct PyGuardrailStore(Arc<GuardrailStore>);
+
+impl AsRef<Arc<GuardrailStore>> for PyGuardrailStore {
+    fn as_ref(&self) -> &Arc<GuardrailStore> {
+        &self.0
+    }
+}
+
+impl From<GuardrailStore> for PyGuardrailStore {
+    fn from(store: GuardrailStore) -> Self {
+        Self(Arc::new(store))
+    }
+}
+
+#[pymethods]
+impl PyGuardrailStore {
+    #[new]
+    #[pyo3(signature = (store, *, max_object_size=None, max_total_upload_bytes_per_hour=None, deny_prefixes=None, read_only=false, authorize=None))]
+    fn new(
+        store: PyObjectStore,
+        max_object_size: Option<usize>,
+        max_total_upload_bytes_per_hour: Option<usize>,
+        deny_prefixes: Option<Vec<String>>,
+        read_only: bool,
+        authorize: Option<PyObject>,
+    ) -> Self {
+        let deny_prefixes = deny_prefixes
+            .unwrap_or_default()
+            .into_iter()
+            .map(Path::from)
+            .collect();
+        Self(Arc::new(GuardrailStore::new(
+            store.into_inner(),
+            max_object_size,
+            max_total_upload_bytes_per_hour,
+            deny_prefixes,
+            read_only,
+            authorize,
+        )))
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
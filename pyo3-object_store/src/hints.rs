@@ -0,0 +1,463 @@
+//! Hints about which permission is likely missing, added to the permission errors of every store.
+//!
+//! Every store extracted as a [`PyObjectStore`][crate::PyObjectStore] is wrapped in a
+//! [`PermissionHintStore`]. Once hints are enabled with [`enable_permission_hints`], when one of
+//! its requests is denied, it probes which read requests the credentials may make on the same
+//! path with [`diagnose_permissions`], and adds the resulting hint to the error. The exception
+//! raised for it then carries the hint in its message and in its `hint` attribute. While hints
+//! are disabled, which is the default, errors are passed through untouched.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{Future, StreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOpts,
+    PutOptions, PutPayload, PutResult,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::list::owned_list;
+
+/// Whether permission errors are followed by probes to add hints to them.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Start adding hints to permission errors.
+pub fn enable_permission_hints() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop adding hints to permission errors.
+pub fn disable_permission_hints() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Whether permission errors are followed by probes to add hints to them.
+pub fn permission_hints_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The outcome of a single lightweight request issued to learn about permissions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProbeOutcome {
+    /// The request was authorized. A missing object still counts as authorized.
+    Allowed,
+    /// The request was rejected with a permission error.
+    Denied,
+    /// The credentials themselves were rejected.
+    Unauthenticated,
+    /// The request failed for an unrelated reason.
+    Inconclusive,
+}
+
+impl<T> From<object_store::Result<T>> for ProbeOutcome {
+    fn from(value: object_store::Result<T>) -> Self {
+        match value {
+            Ok(_) | Err(object_store::Error::NotFound { .. }) => Self::Allowed,
+            Err(object_store::Error::PermissionDenied { .. }) => Self::Denied,
+            Err(object_store::Error::Unauthenticated { .. }) => Self::Unauthenticated,
+            Err(_) => Self::Inconclusive,
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for ProbeOutcome {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let allowed = match self {
+            Self::Allowed => Some(true),
+            Self::Denied | Self::Unauthenticated => Some(false),
+            Self::Inconclusive => None,
+        };
+        Ok(allowed.into_pyobject(py)?.into_any())
+    }
+}
+
+/// Which read requests on a path were allowed, as found by [`diagnose_permissions`].
+#[derive(Debug)]
+pub struct PermissionReport {
+    list: ProbeOutcome,
+    head: ProbeOutcome,
+    get: ProbeOutcome,
+}
+
+impl PermissionReport {
+    /// A human-readable guess at which permission is missing.
+    pub fn hint(&self) -> Option<&'static str> {
+        use ProbeOutcome::*;
+
+        let outcomes = [self.list, self.head, self.get];
+        if outcomes.contains(&Unauthenticated) {
+            return Some(
+                "The credentials were rejected. Check that they are valid, not expired, and \
+                 belong to the expected account or project.",
+            );
+        }
+        match (self.list, self.head, self.get) {
+            (Denied, Denied, Denied) => Some(
+                "All read requests were denied. The credentials likely have no read access \
+                 to this bucket or prefix (e.g. s3:ListBucket and s3:GetObject).",
+            ),
+            (Allowed, Denied, _) | (Allowed, _, Denied) => Some(
+                "Listing is allowed but reading the object is denied. The credentials likely \
+                 lack object read permission (e.g. s3:GetObject), or the object is encrypted \
+                 with a key the credentials cannot use.",
+            ),
+            (Denied, Allowed, Allowed) => Some(
+                "Reading is allowed but listing is denied. The credentials likely lack list \
+                 permission (e.g. s3:ListBucket). Without it, S3 also reports missing objects as \
+                 permission errors instead of not found.",
+            ),
+            (Allowed, Allowed, Allowed) => Some(
+                "Listing and reading are allowed. If an operation still failed with a \
+                 permission error, it likely requires write or delete permission (e.g. \
+                 s3:PutObject or s3:DeleteObject).",
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for PermissionReport {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("list", self.list)?;
+        dict.set_item("head", self.head)?;
+        dict.set_item("get", self.get)?;
+        dict.set_item("hint", self.hint())?;
+        Ok(dict)
+    }
+}
+
+/// The parent prefix of `path`, or `None` for paths at the root of the store.
+fn parent(path: &Path) -> Option<Path> {
+    let parts = path.parts().collect::<Vec<_>>();
+    match parts.split_last() {
+        Some((_, rest)) if !rest.is_empty() => Some(Path::from_iter(rest.iter().cloned())),
+        _ => None,
+    }
+}
+
+/// Probe which read requests `store` may make on `path`: listing its parent prefix, and a head
+/// and a get request of the object. No data is written, and the body of the object isn't read.
+pub async fn diagnose_permissions(store: &dyn ObjectStore, path: &Path) -> PermissionReport {
+    let prefix = parent(path);
+    let list = async { ProbeOutcome::from(store.list(prefix.as_ref()).next().await.transpose()) };
+    let head = async { ProbeOutcome::from(store.head(path).await) };
+    // Dropping the GetResult without reading the body keeps this request cheap
+    let get = async { ProbeOutcome::from(store.get(path).await) };
+    let (list, head, get) = futures::join!(list, head, get);
+    PermissionReport { list, head, get }
+}
+
+/// A permission error, with a hint about which permission is likely missing.
+#[derive(Debug)]
+pub(crate) struct PermissionHint {
+    source: Box<dyn Error + Send + Sync>,
+    pub(crate) hint: &'static str,
+}
+
+impl Display for PermissionHint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n\nHint: {}", self.source, self.hint)
+    }
+}
+
+impl Error for PermissionHint {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// A store that adds a hint to its permission errors, from probes of the path denied.
+#[derive(Debug)]
+pub struct PermissionHintStore {
+    inner: Arc<dyn ObjectStore>,
+}
+
+impl PermissionHintStore {
+    /// Wrap `inner`.
+    pub fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Display for PermissionHintStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PermissionHintStore({})", self.inner)
+    }
+}
+
+/// Add a hint to `err` if it's a permission error, from probes of `path` with `store`.
+async fn with_hint(
+    store: &dyn ObjectStore,
+    path: &Path,
+    err: object_store::Error,
+) -> object_store::Error {
+    let object_store::Error::PermissionDenied {
+        path: denied,
+        source,
+    } = err
+    else {
+        return err;
+    };
+    // Denials already hinted by a store this one wraps keep their hint
+    if !permission_hints_enabled() || source.is::<PermissionHint>() {
+        return object_store::Error::PermissionDenied {
+            path: denied,
+            source,
+        };
+    }
+    let source = match diagnose_permissions(store, path).await.hint() {
+        Some(hint) => Box::new(PermissionHint { source, hint }),
+        None => source,
+    };
+    object_store::Error::PermissionDenied {
+        path: denied,
+        source,
+    }
+}
+
+impl PermissionHintStore {
+    /// Await `request` to `path`, adding a hint to its error if it's denied.
+    async fn hinted<T>(
+        &self,
+        path: &Path,
+        request: impl Future<Output = object_store::Result<T>>,
+    ) -> object_store::Result<T> {
+        match request.await {
+            Err(err) => Err(with_hint(self.inner.as_ref(), path, err).await),
+            result => result,
+        }
+    }
+
+    /// List through `list`, adding a hint to its errors if they're denied.
+    fn hinted_list(
+        &self,
+        prefix: Option<&Path>,
+        list: BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let inner = self.inner.clone();
+        let prefix = prefix.cloned().unwrap_or_default();
+        list.then(move |result| {
+            let (inner, prefix) = (inner.clone(), prefix.clone());
+            async move {
+                match result {
+                    Err(err) => Err(with_hint(inner.as_ref(), &prefix, err).await),
+                    result => result,
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl ObjectStore for PermissionHintStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.hinted(location, self.inner.put_opts(location, payload, opts))
+            .await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.hinted(location, self.inner.put_multipart_opts(location, opts))
+            .await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.hinted(location, self.inner.get_opts(location, options))
+            .await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.hinted(location, self.inner.get_range(location, range))
+            .await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.hinted(location, self.inner.get_ranges(location, ranges))
+            .await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.hinted(location, self.inner.head(location)).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.hinted(location, self.inner.delete(location)).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Bulk deletes report the paths denied in their errors, which aren't probed
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.hinted_list(prefix, owned_list(self.inner.clone(), prefix, None))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.hinted_list(prefix, owned_list(self.inner.clone(), prefix, Some(offset)))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        let path = prefix.cloned().unwrap_or_default();
+        self.hinted(&path, self.inner.list_with_delimiter(prefix))
+            .await
+    }
+
+    // Copies and renames probe the source, as the hint for a readable source is that writing is
+    // likely denied.
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.hinted(from, self.inner.copy(from, to)).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.hinted(from, self.inner.rename(from, to)).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.hinted(from, self.inner.copy_if_not_exists(from, to))
+            .await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.hinted(from, self.inner.rename_if_not_exists(from, to))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    /// A store that denies every request but listing.
+    #[derive(Debug)]
+    struct DenyingStore(InMemory);
+
+    impl Display for DenyingStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "DenyingStore")
+        }
+    }
+
+    fn denied<T>(location: &Path) -> object_store::Result<T> {
+        Err(object_store::Error::PermissionDenied {
+            path: location.to_string(),
+            source: "Access Denied".into(),
+        })
+    }
+
+    #[async_trait]
+    impl ObjectStore for DenyingStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            _payload: PutPayload,
+            _opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            denied(location)
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            _opts: PutMultipartOpts,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            denied(location)
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            _options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            denied(location)
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            denied(location)
+        }
+
+        fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, object_store::Result<ObjectMeta>> {
+            self.0.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.0.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, _from: &Path, to: &Path) -> object_store::Result<()> {
+            denied(to)
+        }
+
+        async fn copy_if_not_exists(&self, _from: &Path, to: &Path) -> object_store::Result<()> {
+            denied(to)
+        }
+    }
+
+    #[tokio::test]
+    async fn adds_hints_to_permission_errors() {
+        enable_permission_hints();
+        let store = PermissionHintStore::new(Arc::new(DenyingStore(InMemory::new())));
+        let err = store.get(&Path::from("dir/file.txt")).await.unwrap_err();
+        let object_store::Error::PermissionDenied { source, .. } = err else {
+            panic!("Expected a permission error, got {err}");
+        };
+        let hint = source.downcast_ref::<PermissionHint>().unwrap();
+        assert!(hint.hint.starts_with("Listing is allowed but reading"));
+        assert!(source.to_string().starts_with("Access Denied\n\nHint: "));
+    }
+
+    #[tokio::test]
+    async fn passes_other_errors_through() {
+        let store = PermissionHintStore::new(Arc::new(InMemory::new()));
+        let err = store.get(&Path::from("missing.txt")).await.unwrap_err();
+        assert!(matches!(err, object_store::Error::NotFound { .. }));
+    }
+}
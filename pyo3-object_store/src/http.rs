@@ -1,31 +1,64 @@
 use std::sync::Arc;
 
 use object_store::http::{HttpBuilder, HttpStore};
+use object_store::{ClientOptions, RetryConfig};
 use pyo3::prelude::*;
 use pyo3::types::PyType;
+use url::Url;
 
 use crate::error::PyObjectStoreResult;
+use crate::object_url::{object_url, parse_base_url};
+use crate::prefix::with_prefix;
 use crate::retry::PyRetryConfig;
 use crate::PyClientOptions;
 
 /// A Python-facing wrapper around a [`HttpStore`].
 #[pyclass(name = "HTTPStore", frozen)]
-pub struct PyHttpStore(Arc<HttpStore>);
+pub struct PyHttpStore {
+    store: Arc<HttpStore>,
+    /// The URL passed to `from_url`
+    base_url: Url,
+    /// The options `store` was built with, used to derive stores with other options
+    client_options: Option<ClientOptions>,
+    retry_config: Option<RetryConfig>,
+}
 
 impl AsRef<Arc<HttpStore>> for PyHttpStore {
     fn as_ref(&self) -> &Arc<HttpStore> {
-        &self.0
+        &self.store
     }
 }
 
 impl PyHttpStore {
     /// Consume self and return the underlying [`HttpStore`].
     pub fn into_inner(self) -> Arc<HttpStore> {
-        self.0
+        self.store
     }
 
     fn __repr__(&self) -> String {
-        self.0.to_string()
+        self.store.to_string()
+    }
+
+    fn try_new(
+        url: &str,
+        client_options: Option<ClientOptions>,
+        retry_config: Option<RetryConfig>,
+    ) -> PyObjectStoreResult<Self> {
+        let mut builder = HttpBuilder::new().with_url(url);
+        if let Some(client_options) = &client_options {
+            builder = builder.with_client_options(client_options.clone())
+        }
+        if let Some(retry_config) = &retry_config {
+            builder = builder.with_retry(retry_config.clone())
+        }
+        let store = Arc::new(builder.build()?);
+        let base_url = parse_base_url(url)?;
+        Ok(Self {
+            store,
+            base_url,
+            client_options,
+            retry_config,
+        })
     }
 }
 
@@ -39,13 +72,44 @@ impl PyHttpStore {
         client_options: Option<PyClientOptions>,
         retry_config: Option<PyRetryConfig>,
     ) -> PyObjectStoreResult<Self> {
-        let mut builder = HttpBuilder::new().with_url(url);
-        if let Some(client_options) = client_options {
-            builder = builder.with_client_options(client_options.into())
-        }
-        if let Some(retry_config) = retry_config {
-            builder = builder.with_retry(retry_config.into())
-        }
-        Ok(Self(Arc::new(builder.build()?)))
+        Self::try_new(
+            url,
+            client_options.map(Into::into),
+            retry_config.map(Into::into),
+        )
+    }
+
+    #[pyo3(signature = (*, client_options=None, retry_config=None, prefix=None))]
+    fn with_options(
+        &self,
+        py: Python,
+        client_options: Option<PyClientOptions>,
+        retry_config: Option<PyRetryConfig>,
+        prefix: Option<String>,
+    ) -> PyObjectStoreResult<PyObject> {
+        let store = if client_options.is_none() && retry_config.is_none() {
+            // Share the existing store, including its connection pool
+            Self {
+                store: self.store.clone(),
+                base_url: self.base_url.clone(),
+                client_options: self.client_options.clone(),
+                retry_config: self.retry_config.clone(),
+            }
+        } else {
+            Self::try_new(
+                self.base_url.as_str(),
+                client_options
+                    .map(Into::into)
+                    .or(self.client_options.clone()),
+                retry_config.map(Into::into).or(self.retry_config.clone()),
+            )?
+        };
+        let inner = store.store.clone();
+        Ok(with_prefix(py, store, inner, prefix)?)
+    }
+
+    /// The URL of the object at `path`.
+    fn url_for(&self, path: &str) -> String {
+        object_url(&self.base_url, path)
     }
 }
@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::FutureExt;
+use futures::stream::{self, BoxStream, StreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult, UploadPart,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::list::owned_list;
+use crate::PyObjectStore;
+
+const STORE: &str = "LaneStore";
+
+/// The number of paths deleted under one permit by [`ObjectStore::delete_stream`], the most S3
+/// accepts in a single request.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+/// A class of traffic, with its own limit on the number of concurrent requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LaneKind {
+    /// Latency-sensitive requests, such as metadata lookups made while serving a user.
+    Interactive,
+    /// Throughput-oriented requests, such as bulk transfers.
+    Batch,
+}
+
+impl LaneKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Interactive => "interactive",
+            Self::Batch => "batch",
+        }
+    }
+
+    fn default_max_concurrency(&self) -> usize {
+        match self {
+            Self::Interactive => 64,
+            Self::Batch => 16,
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for LaneKind {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let s = ob.extract::<String>()?.to_lowercase();
+        match s.as_str() {
+            "interactive" => Ok(Self::Interactive),
+            "batch" => Ok(Self::Batch),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown lane {s}, expected 'interactive' or 'batch'"
+            ))),
+        }
+    }
+}
+
+/// The concurrency limit of a lane, shared by every [`LaneStore`] in the lane.
+#[derive(Debug)]
+struct Lane {
+    kind: LaneKind,
+    max_concurrency: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+impl Lane {
+    async fn acquire(&self) -> object_store::Result<OwnedSemaphorePermit> {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|source| object_store::Error::Generic {
+                store: STORE,
+                source: Box::new(source),
+            })
+    }
+
+    fn in_flight(&self) -> usize {
+        self.max_concurrency - self.semaphore.available_permits()
+    }
+}
+
+/// The lanes of the process, created by the first store in each.
+fn lanes() -> &'static Mutex<HashMap<LaneKind, Arc<Lane>>> {
+    static LANES: OnceLock<Mutex<HashMap<LaneKind, Arc<Lane>>>> = OnceLock::new();
+    LANES.get_or_init(Default::default)
+}
+
+/// Get the lane of `kind`, creating it with `max_concurrency` if it doesn't exist yet.
+fn get_lane(kind: LaneKind, max_concurrency: Option<usize>) -> PyResult<Arc<Lane>> {
+    let mut lanes = lanes().lock().unwrap();
+    if let Some(lane) = lanes.get(&kind) {
+        if max_concurrency.is_some_and(|max_concurrency| max_concurrency != lane.max_concurrency) {
+            return Err(PyValueError::new_err(format!(
+                "The {} lane already has a max_concurrency of {}",
+                kind.as_str(),
+                lane.max_concurrency
+            )));
+        }
+        return Ok(lane.clone());
+    }
+    let max_concurrency = max_concurrency.unwrap_or(kind.default_max_concurrency());
+    if max_concurrency == 0 {
+        return Err(PyValueError::new_err("max_concurrency must be at least 1"));
+    }
+    let lane = Arc::new(Lane {
+        kind,
+        max_concurrency,
+        semaphore: Arc::new(Semaphore::new(max_concurrency)),
+    });
+    lanes.insert(kind, lane.clone());
+    Ok(lane)
+}
+
+/// An [`ObjectStore`] wrapper that limits the number of concurrent requests of a lane.
+///
+/// The limit is shared by all stores in the same lane, so that a burst of batch requests
+/// waits for batch permits rather than taking those of interactive requests.
+#[derive(Debug)]
+pub struct LaneStore {
+    inner: Arc<dyn ObjectStore>,
+    lane: Arc<Lane>,
+}
+
+impl LaneStore {
+    /// Wrap `inner` in the lane of `kind`, creating the lane with `max_concurrency` (or its
+    /// default) if it doesn't exist yet.
+    pub fn try_new(
+        inner: Arc<dyn ObjectStore>,
+        kind: LaneKind,
+        max_concurrency: Option<usize>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            inner,
+            lane: get_lane(kind, max_concurrency)?,
+        })
+    }
+
+    async fn call<T>(
+        &self,
+        request: impl Future<Output = object_store::Result<T>>,
+    ) -> object_store::Result<T> {
+        let _permit = self.lane.acquire().await?;
+        request.await
+    }
+
+    /// Wrap a listing, holding a permit until it has been fully consumed.
+    fn guard_list(
+        &self,
+        list: BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let lane = self.lane.clone();
+        stream::once(async move {
+            match lane.acquire().await {
+                Ok(permit) => hold(list, permit),
+                Err(err) => stream::once(async move { Err(err) }).boxed(),
+            }
+        })
+        .flatten()
+        .boxed()
+    }
+}
+
+/// Hold `permit` until `stream` is dropped.
+fn hold<T: Send + 'static>(
+    stream: BoxStream<'static, T>,
+    permit: OwnedSemaphorePermit,
+) -> BoxStream<'static, T> {
+    stream
+        .map(move |item| {
+            let _ = &permit;
+            item
+        })
+        .boxed()
+}
+
+impl Display for LaneStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "LaneStore({}, lane={})",
+            self.inner,
+            self.lane.kind.as_str()
+        )
+    }
+}
+
+#[async_trait]
+impl ObjectStore for LaneStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.call(self.inner.put_opts(location, payload, opts))
+            .await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let upload = self
+            .call(self.inner.put_multipart_opts(location, opts))
+            .await?;
+        Ok(Box::new(LaneUpload {
+            inner: upload,
+            lane: self.lane.clone(),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let permit = self.lane.acquire().await?;
+        let mut result = self.inner.get_opts(location, options).await?;
+        // The body is still being downloaded, so keep the permit until it has been read
+        result.payload = match result.payload {
+            GetResultPayload::Stream(stream) => GetResultPayload::Stream(hold(stream, permit)),
+            payload => payload,
+        };
+        Ok(result)
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.call(self.inner.get_range(location, range)).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.call(self.inner.get_ranges(location, ranges)).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.call(self.inner.head(location)).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.call(self.inner.delete(location)).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Forwarded so that stores keep deleting in bulk, with each batch taking one permit
+        // rather than holding one for the whole stream
+        locations
+            .ready_chunks(DELETE_BATCH_SIZE)
+            .then(move |locations| async move {
+                let _permit = match self.lane.acquire().await {
+                    Ok(permit) => permit,
+                    Err(err) => return vec![Err(err)],
+                };
+                self.inner
+                    .delete_stream(stream::iter(locations).boxed())
+                    .collect::<Vec<_>>()
+                    .await
+            })
+            .flat_map(stream::iter)
+            .boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.guard_list(owned_list(self.inner.clone(), prefix, None))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.guard_list(owned_list(self.inner.clone(), prefix, Some(offset)))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.call(self.inner.list_with_delimiter(prefix)).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(self.inner.copy(from, to)).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(self.inner.rename(from, to)).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(self.inner.copy_if_not_exists(from, to)).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(self.inner.rename_if_not_exists(from, to)).await
+    }
+}
+
+/// A multipart upload whose parts each take a permit of its store's lane.
+#[derive(Debug)]
+struct LaneUpload {
+    inner: Box<dyn MultipartUpload>,
+    lane: Arc<Lane>,
+}
+
+#[async_trait]
+impl MultipartUpload for LaneUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let lane = self.lane.clone();
+        // Call `put_part` right away, as uploads number parts in the order it's called. The
+        // request is only sent once the part is polled, after a permit was acquired
+        let part = self.inner.put_part(data);
+        async move {
+            let _permit = lane.acquire().await?;
+            part.await
+        }
+        .boxed()
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        let _permit = self.lane.acquire().await?;
+        self.inner.complete().await
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.inner.abort().await
+    }
+}
+
+/// A Python-facing wrapper around a [`LaneStore`].
+#[pyclass(name = "LaneStore", frozen)]
+pub struct PyLaneStore(Arc<LaneStore>);
+
+impl AsRef<Arc<LaneStore>> for PyLaneStore {
+    fn as_ref(&self) -> &Arc<LaneStore> {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PyLaneStore {
+    #[new]
+    #[pyo3(signature = (store, *, lane, max_concurrency=None))]
+    fn new(store: PyObjectStore, lane: LaneKind, max_concurrency: Option<usize>) -> PyResult<Self> {
+        Ok(Self(Arc::new(LaneStore::try_new(
+            store.into_inner(),
+            lane,
+            max_concurrency,
+        )?)))
+    }
+
+    #[getter]
+    fn lane(&self) -> &'static str {
+        self.0.lane.kind.as_str()
+    }
+
+    #[getter]
+    fn max_concurrency(&self) -> usize {
+        self.0.lane.max_concurrency
+    }
+
+    /// The number of requests of the lane currently in flight, across all of its stores.
+    #[getter]
+    fn in_flight(&self) -> usize {
+        self.0.lane.in_flight()
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
@@ -4,25 +4,57 @@
 mod api;
 mod aws;
 mod azure;
+mod circuit_breaker;
 mod client;
+pub mod clock;
 mod config;
+mod credentials;
 pub(crate) mod error;
+mod external_account;
 mod gcp;
+mod get_defaults;
+mod guardrails;
+mod hints;
 mod http;
+mod lanes;
+mod list;
 mod local;
 mod memory;
+mod metrics;
+mod named;
+mod object_url;
 mod prefix;
+mod prefix_stats;
+mod quirks;
+mod resolving;
 mod retry;
+mod signed_url;
 mod store;
+mod trash;
 
-pub use api::{register_exceptions_module, register_store_module};
-pub use aws::PyS3Store;
+pub use api::{register_exceptions_module, register_metrics_module, register_store_module};
+pub use aws::{PyS3Store, RegionAwareS3};
 pub use azure::PyAzureStore;
+pub use circuit_breaker::{CircuitBreakerStore, PyCircuitBreakerStore};
 pub use client::{PyClientConfigKey, PyClientOptions};
-pub use error::{PyObjectStoreError, PyObjectStoreResult};
+pub use error::{status_code, InvalidRangeError, PyObjectStoreError, PyObjectStoreResult};
 pub use gcp::PyGCSStore;
+pub use get_defaults::{DefaultGetOptionsStore, GetDefaults, PyDefaultGetOptionsStore};
+pub use guardrails::{GuardrailStore, PyGuardrailStore};
+pub use hints::{
+    diagnose_permissions, disable_permission_hints, enable_permission_hints,
+    permission_hints_enabled, PermissionHintStore, PermissionReport,
+};
 pub use http::PyHttpStore;
+pub use lanes::{LaneKind, LaneStore, PyLaneStore};
 pub use local::PyLocalStore;
 pub use memory::PyMemoryStore;
-pub use prefix::PyPrefixStore;
+pub use metrics::{disable_metrics, enable_metrics, reset_metrics, MetricsStore};
+pub use named::NamedStore;
+pub use prefix::{prefixed_path, PyPrefixStore};
+pub use prefix_stats::{PrefixStatsStore, PyPrefixStatsStore};
+pub use quirks::{quirks_for, register_quirks, Quirks};
+pub use resolving::{PyResolvingStore, ResolvingStore};
+pub use signed_url::{PySignedUrlStore, SignedUrlStore};
 pub use store::PyObjectStore;
+pub use trash::{PyTrashStore, TrashStore};
@@ -0,0 +1,68 @@
+//! Listings that own the store they list.
+//!
+//! With the object_store patched for obstore listings are `'static`, but with object_store
+//! 0.11, which this crate is published against, they borrow the store they're made from.
+//! Stores wrapping other stores return `'static` listings on both, so [`owned_list`] drives
+//! the listing of the store they wrap within a future that owns it.
+//!
+//! Bulk deletes borrow the store they're made from on both, which [`owned_delete_stream`]
+//! works around the same way for stores that hand out the store they wrap as an [`Arc`].
+
+use std::sync::Arc;
+
+use futures::channel::mpsc;
+use futures::stream::{self, BoxStream};
+use futures::{SinkExt, StreamExt};
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore};
+
+/// List `store` under `prefix`, after `offset` if it's set, as a stream owning `store`.
+pub(crate) fn owned_list<S: ObjectStore + ?Sized>(
+    store: Arc<S>,
+    prefix: Option<&Path>,
+    offset: Option<&Path>,
+) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+    let prefix = prefix.cloned();
+    let offset = offset.cloned();
+    let (mut sender, receiver) = mpsc::channel(0);
+    let forward = async move {
+        let mut list = match &offset {
+            Some(offset) => store.list_with_offset(prefix.as_ref(), offset),
+            None => store.list(prefix.as_ref()),
+        };
+        while let Some(item) = list.next().await {
+            if sender.send(item).await.is_err() {
+                // The listing was dropped
+                break;
+            }
+        }
+    };
+    // Polling the forwarding future alongside the receiver drives the listing as it's read
+    stream::select(
+        stream::once(forward).filter_map(|()| async { None }),
+        receiver,
+    )
+    .boxed()
+}
+
+/// Delete `locations` from `store` in bulk, as a stream owning `store`.
+pub(crate) fn owned_delete_stream<'a, S: ObjectStore + ?Sized>(
+    store: Arc<S>,
+    locations: BoxStream<'a, object_store::Result<Path>>,
+) -> BoxStream<'a, object_store::Result<Path>> {
+    let (mut sender, receiver) = mpsc::channel(0);
+    let forward = async move {
+        let mut deleted = store.delete_stream(locations);
+        while let Some(item) = deleted.next().await {
+            if sender.send(item).await.is_err() {
+                // The deletes were dropped
+                break;
+            }
+        }
+    };
+    stream::select(
+        stream::once(forward).filter_map(|()| async { None }),
+        receiver,
+    )
+    .boxed()
+}
@@ -1,46 +1,90 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use object_store::local::LocalFileSystem;
 use object_store::ObjectStoreScheme;
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::intern;
 use pyo3::prelude::*;
-use pyo3::types::PyType;
+use pyo3::pybacked::PyBackedStr;
+use pyo3::types::{PyDict, PyTuple, PyType};
 use url::Url;
 
 use crate::error::PyObjectStoreResult;
 
 /// A Python-facing wrapper around a [`LocalFileSystem`].
 #[pyclass(name = "LocalStore", frozen)]
-pub struct PyLocalStore(Arc<LocalFileSystem>);
+pub struct PyLocalStore {
+    store: Arc<LocalFileSystem>,
+    /// The absolute path of the directory the store is rooted at, if any
+    prefix: Option<PathBuf>,
+    config: PyLocalConfig,
+}
 
 impl AsRef<Arc<LocalFileSystem>> for PyLocalStore {
     fn as_ref(&self) -> &Arc<LocalFileSystem> {
-        &self.0
+        &self.store
     }
 }
 
 impl PyLocalStore {
     /// Consume self and return the underlying [`LocalFileSystem`].
     pub fn into_inner(self) -> Arc<LocalFileSystem> {
-        self.0
+        self.store
+    }
+
+    fn try_new(prefix: Option<PathBuf>, config: PyLocalConfig) -> PyObjectStoreResult<Self> {
+        let prefix = match prefix {
+            Some(prefix) => {
+                if config.mkdir.unwrap_or(false) {
+                    std::fs::create_dir_all(&prefix)?;
+                }
+                // Kept absolute, so that the store is rooted at the same directory when
+                // it's unpickled by a process with another working directory
+                Some(std::fs::canonicalize(&prefix).map_err(|source| {
+                    object_store::Error::Generic {
+                        store: "LocalFileSystem",
+                        source: format!("Unable to canonicalize {}: {source}", prefix.display())
+                            .into(),
+                    }
+                })?)
+            }
+            None => None,
+        };
+        let fs = match &prefix {
+            Some(prefix) => LocalFileSystem::new_with_prefix(prefix)?,
+            None => LocalFileSystem::new(),
+        };
+        let fs = fs.with_automatic_cleanup(config.automatic_cleanup.unwrap_or(false));
+        Ok(Self {
+            store: Arc::new(fs),
+            prefix,
+            config,
+        })
     }
 }
 
 #[pymethods]
 impl PyLocalStore {
     #[new]
-    #[pyo3(signature = (prefix = None))]
-    fn py_new(prefix: Option<std::path::PathBuf>) -> PyObjectStoreResult<Self> {
-        let fs = if let Some(prefix) = prefix {
-            LocalFileSystem::new_with_prefix(prefix)?
-        } else {
-            LocalFileSystem::new()
-        };
-        Ok(Self(Arc::new(fs)))
+    #[pyo3(signature = (prefix = None, *, config = None, **kwargs))]
+    fn py_new(
+        prefix: Option<PathBuf>,
+        config: Option<PyLocalConfig>,
+        kwargs: Option<PyLocalConfig>,
+    ) -> PyObjectStoreResult<Self> {
+        let config = PyLocalConfig::default().merge(config).merge(kwargs);
+        Self::try_new(prefix, config)
     }
 
     #[classmethod]
-    fn from_url(_cls: &Bound<PyType>, url: &str) -> PyObjectStoreResult<Self> {
+    #[pyo3(signature = (url, *, config = None, **kwargs))]
+    fn from_url(
+        _cls: &Bound<PyType>,
+        url: &str,
+        config: Option<PyLocalConfig>,
+        kwargs: Option<PyLocalConfig>,
+    ) -> PyObjectStoreResult<Self> {
         let url = Url::parse(url).map_err(|err| PyValueError::new_err(err.to_string()))?;
         let (scheme, path) = ObjectStoreScheme::parse(&url).map_err(object_store::Error::from)?;
 
@@ -53,12 +97,111 @@ impl PyLocalStore {
         // Hopefully this also works on Windows.
         let root = std::path::Path::new("/");
         let full_path = root.join(path.as_ref());
-        let fs = LocalFileSystem::new_with_prefix(full_path)?;
-        Ok(Self(Arc::new(fs)))
+        let config = PyLocalConfig::default().merge(config).merge(kwargs);
+        Self::try_new(Some(full_path), config)
+    }
+
+    fn __getnewargs_ex__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(Bound<'py, PyTuple>, Bound<'py, PyDict>)> {
+        let args = PyTuple::new(py, [&self.prefix])?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item(intern!(py, "config"), self.config)?;
+        Ok((args, kwargs))
+    }
+
+    /// The `file://` URL of the object at `path`.
+    fn url_for(&self, path: &str) -> PyObjectStoreResult<String> {
+        let local_path = self.store.path_to_filesystem(&path.into())?;
+        let url = Url::from_file_path(&local_path).map_err(|_| {
+            PyValueError::new_err(format!("Cannot convert {} to a URL", local_path.display()))
+        })?;
+        Ok(url.to_string())
+    }
+
+    /// The absolute path of the directory the store is rooted at, if any.
+    #[getter]
+    fn prefix<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.prefix
+            .as_ref()
+            .map(|prefix| {
+                py.import(intern!(py, "pathlib"))?
+                    .getattr(intern!(py, "Path"))?
+                    .call1((prefix,))
+            })
+            .transpose()
+    }
+
+    /// The configuration the store was constructed with.
+    #[getter]
+    fn config(&self) -> PyLocalConfig {
+        self.config
     }
 
     fn __repr__(&self) -> String {
-        let repr = self.0.to_string();
-        repr.replacen("LocalFileSystem", "LocalStore", 1)
+        match &self.prefix {
+            Some(prefix) => format!("LocalStore(prefix=\"{}\")", prefix.display()),
+            None => "LocalStore".to_string(),
+        }
+    }
+}
+
+/// The configuration of a [`PyLocalStore`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PyLocalConfig {
+    /// Whether empty directories are removed when the objects in them are deleted
+    automatic_cleanup: Option<bool>,
+    /// Whether the prefix directory is created if it doesn't exist
+    mkdir: Option<bool>,
+}
+
+impl<'py> FromPyObject<'py> for PyLocalConfig {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let mut config = Self::default();
+        for (key, value) in ob.downcast::<PyDict>()?.iter() {
+            let key = key.extract::<PyBackedStr>()?;
+            match key.to_lowercase().as_str() {
+                "automatic_cleanup" => config.automatic_cleanup = Some(value.extract()?),
+                "mkdir" => config.mkdir = Some(value.extract()?),
+                _ => {
+                    return Err(PyTypeError::new_err(format!(
+                        "Unknown LocalStore config key: {}",
+                        &*key
+                    )))
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for PyLocalConfig {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        if let Some(automatic_cleanup) = self.automatic_cleanup {
+            dict.set_item("automatic_cleanup", automatic_cleanup)?;
+        }
+        if let Some(mkdir) = self.mkdir {
+            dict.set_item("mkdir", mkdir)?;
+        }
+        Ok(dict)
+    }
+}
+
+impl PyLocalConfig {
+    /// Override values in `self` with those in `other`.
+    fn merge(self, other: Option<Self>) -> Self {
+        match other {
+            Some(other) => Self {
+                automatic_cleanup: other.automatic_cleanup.or(self.automatic_cleanup),
+                mkdir: other.mkdir.or(self.mkdir),
+            },
+            None => self,
+        }
     }
 }
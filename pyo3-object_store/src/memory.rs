@@ -1,9 +1,19 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use bytes::Bytes;
+use futures::TryStreamExt;
 use object_store::memory::InMemory;
+use object_store::path::Path;
+use object_store::ObjectStore;
 use pyo3::intern;
 use pyo3::prelude::*;
-use pyo3::types::PyString;
+use pyo3::pybacked::PyBackedBytes;
+use pyo3::types::{PyBytes, PyDict, PyString, PyTuple};
+use url::Url;
+
+use crate::error::PyObjectStoreResult;
+use crate::object_url::object_url;
 
 /// A Python-facing wrapper around an [`InMemory`].
 #[pyclass(name = "MemoryStore", frozen)]
@@ -15,21 +25,72 @@ impl AsRef<Arc<InMemory>> for PyMemoryStore {
     }
 }
 
-impl<'py> PyMemoryStore {
+impl PyMemoryStore {
     /// Consume self and return the underlying [`InMemory`].
     pub fn into_inner(self) -> Arc<InMemory> {
         self.0
     }
 
-    fn __repr__(&'py self, py: Python<'py>) -> &'py Bound<'py, PyString> {
-        intern!(py, "MemoryStore")
+    /// The paths and contents of all objects in the store, in order of their paths.
+    ///
+    /// [`InMemory`] completes its operations without waiting on anything, so they're run
+    /// to completion here rather than on a runtime.
+    fn snapshot(&self) -> object_store::Result<Vec<(Path, Bytes)>> {
+        futures::executor::block_on(async {
+            let metas = self.0.list(None).try_collect::<Vec<_>>().await?;
+            let mut objects = Vec::with_capacity(metas.len());
+            for meta in metas {
+                // Skip objects deleted since they were listed
+                let bytes = match self.0.get(&meta.location).await {
+                    Ok(result) => result.bytes().await?,
+                    Err(object_store::Error::NotFound { .. }) => continue,
+                    Err(err) => return Err(err),
+                };
+                objects.push((meta.location, bytes));
+            }
+            Ok(objects)
+        })
     }
 }
 
 #[pymethods]
 impl PyMemoryStore {
     #[new]
-    fn py_new() -> Self {
-        Self(Arc::new(InMemory::new()))
+    #[pyo3(signature = (objects = None))]
+    fn py_new(objects: Option<HashMap<String, PyBackedBytes>>) -> PyObjectStoreResult<Self> {
+        let store = InMemory::new();
+        for (path, data) in objects.unwrap_or_default() {
+            let data = Bytes::copy_from_slice(&data);
+            futures::executor::block_on(store.put(&Path::from(path), data.into()))?;
+        }
+        Ok(Self(Arc::new(store)))
+    }
+
+    /// The paths and contents of all objects in the store.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyObjectStoreResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (path, data) in self.snapshot()? {
+            dict.set_item(path.as_ref(), PyBytes::new(py, &data))?;
+        }
+        Ok(dict)
+    }
+
+    fn __getnewargs_ex__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyObjectStoreResult<(Bound<'py, PyTuple>, Bound<'py, PyDict>)> {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item(intern!(py, "objects"), self.to_dict(py)?)?;
+        Ok((PyTuple::empty(py), kwargs))
+    }
+
+    /// The `memory:///` URL of the object at `path`.
+    fn url_for(&self, path: &str) -> String {
+        let base_url = Url::parse("memory:///").unwrap();
+        object_url(&base_url, path)
+    }
+
+    fn __repr__<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+        intern!(py, "MemoryStore").clone()
     }
 }
@@ -0,0 +1,566 @@
+//! Process-wide metrics and tracing spans of the requests made through every store.
+//!
+//! Every store passed to an operation is wrapped in a [`MetricsStore`], which records the
+//! count, errors, bytes and latency of its requests once metrics are enabled, and traces each
+//! request with a span of the `obstore` target. While metrics are disabled, which is the
+//! default, and no subscriber is interested in the spans, requests are passed through untouched.
+
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use futures::FutureExt;
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult, UploadPart,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use tracing::{Instrument, Span};
+
+use crate::list::owned_list;
+
+/// Whether requests are being recorded.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The upper bounds of the buckets of the latency histograms, in milliseconds.
+const LATENCY_BUCKETS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// The kinds of requests that are recorded.
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Get,
+    Put,
+    List,
+    Head,
+    Delete,
+    Copy,
+    Rename,
+}
+
+impl Operation {
+    const ALL: [Self; 7] = [
+        Self::Get,
+        Self::Put,
+        Self::List,
+        Self::Head,
+        Self::Delete,
+        Self::Copy,
+        Self::Rename,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::Put => "put",
+            Self::List => "list",
+            Self::Head => "head",
+            Self::Delete => "delete",
+            Self::Copy => "copy",
+            Self::Rename => "rename",
+        }
+    }
+
+    /// The span tracing a request to `path`, which is disabled unless a subscriber is
+    /// interested in it.
+    fn span(self, path: Option<&Path>) -> Span {
+        macro_rules! request_span {
+            ($name:literal) => {
+                tracing::info_span!(
+                    target: "obstore",
+                    $name,
+                    path = path.map(|path| path.as_ref()),
+                    from = tracing::field::Empty,
+                    error = tracing::field::Empty,
+                )
+            };
+        }
+        match self {
+            Self::Get => request_span!("get"),
+            Self::Put => request_span!("put"),
+            Self::List => request_span!("list"),
+            Self::Head => request_span!("head"),
+            Self::Delete => request_span!("delete"),
+            Self::Copy => request_span!("copy"),
+            Self::Rename => request_span!("rename"),
+        }
+    }
+}
+
+/// The aggregated metrics of one kind of request.
+#[derive(Debug, Clone, Copy)]
+struct OperationMetrics {
+    count: u64,
+    errors: u64,
+    bytes: u64,
+    total_latency: Duration,
+    max_latency: Duration,
+    /// The number of requests in each latency bucket, the last counting those slower than
+    /// the largest bound
+    latency_buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl OperationMetrics {
+    const EMPTY: Self = Self {
+        count: 0,
+        errors: 0,
+        bytes: 0,
+        total_latency: Duration::ZERO,
+        max_latency: Duration::ZERO,
+        latency_buckets: [0; LATENCY_BUCKETS_MS.len() + 1],
+    };
+
+    fn record(&mut self, latency: Duration, failed: bool) {
+        self.count += 1;
+        if failed {
+            self.errors += 1;
+        }
+        self.total_latency += latency;
+        self.max_latency = self.max_latency.max(latency);
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| latency <= Duration::from_millis(*bound))
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.latency_buckets[bucket] += 1;
+    }
+}
+
+impl<'py> IntoPyObject<'py> for OperationMetrics {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("count", self.count)?;
+        dict.set_item("errors", self.errors)?;
+        dict.set_item("bytes", self.bytes)?;
+        dict.set_item("total_latency", self.total_latency)?;
+        dict.set_item("max_latency", self.max_latency)?;
+        let bounds = LATENCY_BUCKETS_MS
+            .iter()
+            .map(|bound| Some(Duration::from_millis(*bound)))
+            .chain([None]);
+        dict.set_item(
+            "latency_histogram",
+            bounds.zip(self.latency_buckets).collect::<Vec<_>>(),
+        )?;
+        Ok(dict)
+    }
+}
+
+/// The metrics of each kind of request, indexed by [`Operation`].
+static METRICS: Mutex<[OperationMetrics; Operation::ALL.len()]> =
+    Mutex::new([OperationMetrics::EMPTY; Operation::ALL.len()]);
+
+fn with_metrics(operation: Operation, f: impl FnOnce(&mut OperationMetrics)) {
+    f(&mut METRICS.lock().unwrap()[operation as usize])
+}
+
+/// Start recording requests made through every store.
+pub fn enable_metrics() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop recording requests, keeping the metrics recorded so far.
+pub fn disable_metrics() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Clear the metrics recorded so far.
+pub fn reset_metrics() {
+    *METRICS.lock().unwrap() = [OperationMetrics::EMPTY; Operation::ALL.len()];
+}
+
+/// A request being timed and traced, which must be resolved with its outcome to be recorded.
+///
+/// Its span is closed once the timer is dropped.
+struct Timer {
+    operation: Operation,
+    started: Instant,
+    /// Whether metrics were enabled when the request started
+    recording: bool,
+    span: Span,
+}
+
+impl Timer {
+    /// Start timing a request to `path`, if metrics are enabled or its span is traced.
+    fn start(operation: Operation, path: Option<&Path>) -> Option<Self> {
+        let recording = ENABLED.load(Ordering::Relaxed);
+        let span = operation.span(path);
+        if !recording && span.is_disabled() {
+            return None;
+        }
+        Some(Self {
+            operation,
+            started: Instant::now(),
+            recording,
+            span,
+        })
+    }
+
+    /// Record the source of a copy or rename on its span.
+    fn with_source(self, from: &Path) -> Self {
+        self.span.record("from", from.as_ref());
+        self
+    }
+
+    fn finish<T>(&self, result: &object_store::Result<T>, bytes: usize) {
+        if let Err(err) = result {
+            self.span.record("error", tracing::field::display(err));
+        }
+        if !self.recording {
+            return;
+        }
+        let latency = self.started.elapsed();
+        with_metrics(self.operation, |metrics| {
+            metrics.record(latency, result.is_err());
+            metrics.bytes += bytes as u64;
+        });
+    }
+}
+
+/// Await `request`, timing and tracing it with `timer` if there is one.
+async fn timed<T>(
+    timer: Option<Timer>,
+    request: impl std::future::Future<Output = object_store::Result<T>>,
+) -> object_store::Result<T> {
+    let Some(timer) = timer else {
+        return request.await;
+    };
+    let result = request.instrument(timer.span.clone()).await;
+    timer.finish(&result, 0);
+    result
+}
+
+/// An [`ObjectStore`] wrapper that records the metrics of its requests while metrics are
+/// enabled, and traces each of them with a span.
+///
+/// Spans are named after the kind of request, such as `get` or `list`, with the `path` of the
+/// request, the `from` path of copies and renames, and the `error` of failed requests.
+///
+/// Downloads are timed until their response arrives, and their bytes are counted as they're
+/// read. Listings are timed until their first result arrives. Each part of a multipart upload,
+/// and the requests starting and completing it, are recorded as separate puts.
+#[derive(Debug)]
+pub struct MetricsStore {
+    inner: Arc<dyn ObjectStore>,
+}
+
+impl MetricsStore {
+    /// Wrap `inner` to record the metrics of its requests.
+    pub fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self { inner }
+    }
+
+    /// Wrap a listing, which is recorded once its first item arrives, or once it ends if it's
+    /// empty.
+    fn time_list(
+        &self,
+        prefix: Option<&Path>,
+        stream: BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let Some(timer) = Timer::start(Operation::List, prefix) else {
+            return stream;
+        };
+        let timer = Arc::new(Mutex::new(Some(timer)));
+        let first = timer.clone();
+        stream
+            .map(move |item| {
+                if let Some(timer) = first.lock().unwrap().take() {
+                    timer.finish(&item, 0);
+                }
+                item
+            })
+            .chain(futures::stream::poll_fn(move |_| {
+                if let Some(timer) = timer.lock().unwrap().take() {
+                    timer.finish(&Ok::<_, object_store::Error>(()), 0);
+                }
+                Poll::Ready(None)
+            }))
+            .boxed()
+    }
+}
+
+impl Display for MetricsStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Transparent, as every store is wrapped
+        write!(f, "{}", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for MetricsStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        let Some(timer) = Timer::start(Operation::Put, Some(location)) else {
+            return self.inner.put_opts(location, payload, opts).await;
+        };
+        let size = payload.content_length();
+        let result = self
+            .inner
+            .put_opts(location, payload, opts)
+            .instrument(timer.span.clone())
+            .await;
+        timer.finish(&result, if result.is_ok() { size } else { 0 });
+        result
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let upload = timed(
+            Timer::start(Operation::Put, Some(location)),
+            self.inner.put_multipart_opts(location, opts),
+        )
+        .await?;
+        Ok(Box::new(MetricsUpload {
+            inner: upload,
+            location: location.clone(),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let Some(timer) = Timer::start(Operation::Get, Some(location)) else {
+            return self.inner.get_opts(location, options).await;
+        };
+        let result = self
+            .inner
+            .get_opts(location, options)
+            .instrument(timer.span.clone())
+            .await;
+        timer.finish(&result, 0);
+        let result = result?;
+        if !timer.recording {
+            return Ok(result);
+        }
+        let meta = result.meta.clone();
+        let range = result.range.clone();
+        let attributes = result.attributes.clone();
+        let stream = result
+            .into_stream()
+            .inspect_ok(|bytes| {
+                with_metrics(Operation::Get, |metrics| {
+                    metrics.bytes += bytes.len() as u64
+                })
+            })
+            .boxed();
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(stream),
+            meta,
+            range,
+            attributes,
+        })
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        let Some(timer) = Timer::start(Operation::Get, Some(location)) else {
+            return self.inner.get_range(location, range).await;
+        };
+        let result = self
+            .inner
+            .get_range(location, range)
+            .instrument(timer.span.clone())
+            .await;
+        let size = result.as_ref().map_or(0, |bytes| bytes.len());
+        timer.finish(&result, size);
+        result
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        let Some(timer) = Timer::start(Operation::Get, Some(location)) else {
+            return self.inner.get_ranges(location, ranges).await;
+        };
+        let result = self
+            .inner
+            .get_ranges(location, ranges)
+            .instrument(timer.span.clone())
+            .await;
+        let size = result
+            .as_ref()
+            .map_or(0, |buffers| buffers.iter().map(|buf| buf.len()).sum());
+        timer.finish(&result, size);
+        result
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        timed(
+            Timer::start(Operation::Head, Some(location)),
+            self.inner.head(location),
+        )
+        .await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        timed(
+            Timer::start(Operation::Delete, Some(location)),
+            self.inner.delete(location),
+        )
+        .await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Forwarded so that stores keep deleting in bulk, with each path recorded as a
+        // delete timed from the start of the stream
+        let Some(timer) = Timer::start(Operation::Delete, None) else {
+            return self.inner.delete_stream(locations);
+        };
+        self.inner
+            .delete_stream(locations)
+            .map(move |result| {
+                timer.finish(&result, 0);
+                result
+            })
+            .boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.time_list(prefix, owned_list(self.inner.clone(), prefix, None))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.time_list(prefix, owned_list(self.inner.clone(), prefix, Some(offset)))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        timed(
+            Timer::start(Operation::List, prefix),
+            self.inner.list_with_delimiter(prefix),
+        )
+        .await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        timed(
+            Timer::start(Operation::Copy, Some(to)).map(|timer| timer.with_source(from)),
+            self.inner.copy(from, to),
+        )
+        .await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        timed(
+            Timer::start(Operation::Rename, Some(to)).map(|timer| timer.with_source(from)),
+            self.inner.rename(from, to),
+        )
+        .await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        timed(
+            Timer::start(Operation::Copy, Some(to)).map(|timer| timer.with_source(from)),
+            self.inner.copy_if_not_exists(from, to),
+        )
+        .await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        timed(
+            Timer::start(Operation::Rename, Some(to)).map(|timer| timer.with_source(from)),
+            self.inner.rename_if_not_exists(from, to),
+        )
+        .await
+    }
+}
+
+/// A multipart upload whose parts are recorded as puts.
+#[derive(Debug)]
+struct MetricsUpload {
+    inner: Box<dyn MultipartUpload>,
+    location: Path,
+}
+
+#[async_trait]
+impl MultipartUpload for MetricsUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let Some(timer) = Timer::start(Operation::Put, Some(&self.location)) else {
+            return self.inner.put_part(data);
+        };
+        let size = data.content_length();
+        self.inner
+            .put_part(data)
+            .instrument(timer.span.clone())
+            .map(move |result| {
+                timer.finish(&result, if result.is_ok() { size } else { 0 });
+                result
+            })
+            .boxed()
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        let timer = Timer::start(Operation::Put, Some(&self.location));
+        timed(timer, self.inner.complete()).await
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.inner.abort().await
+    }
+}
+
+#[pyfunction]
+fn enable() {
+    enable_metrics();
+}
+
+#[pyfunction]
+fn disable() {
+    disable_metrics();
+}
+
+#[pyfunction]
+fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[pyfunction]
+fn snapshot(py: Python) -> PyResult<Bound<PyDict>> {
+    let metrics = *METRICS.lock().unwrap();
+    let dict = PyDict::new(py);
+    for operation in Operation::ALL {
+        dict.set_item(operation.name(), metrics[operation as usize])?;
+    }
+    Ok(dict)
+}
+
+#[pyfunction]
+fn reset() {
+    reset_metrics();
+}
+
+/// Add the functions of the `metrics` module to `module`.
+pub(crate) fn add_functions(module: &Bound<PyModule>) -> PyResult<()> {
+    module.add_wrapped(wrap_pyfunction!(enable))?;
+    module.add_wrapped(wrap_pyfunction!(disable))?;
+    module.add_wrapped(wrap_pyfunction!(is_enabled))?;
+    module.add_wrapped(wrap_pyfunction!(snapshot))?;
+    module.add_wrapped(wrap_pyfunction!(reset))?;
+    Ok(())
+}
@@ -0,0 +1,190 @@
+//! The name of the store implementation that raised an error, set as the `store` attribute of the
+//! exception raised for it.
+//!
+//! object_store only names the store in its generic errors, so every store implementation
+//! extracted as a [`PyObjectStore`][crate::PyObjectStore] is wrapped in a [`NamedStore`], which
+//! names the store in the others as [`PyObjectStoreError::with_store`][crate::PyObjectStoreError::with_store]
+//! does.
+
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::{BoxStream, TryStreamExt};
+use futures::{FutureExt, StreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult, UploadPart,
+};
+
+use crate::error::name_store;
+use crate::list::owned_list;
+
+/// A store that names itself in the errors of its requests.
+#[derive(Debug)]
+pub struct NamedStore {
+    inner: Arc<dyn ObjectStore>,
+    name: &'static str,
+}
+
+impl NamedStore {
+    /// Wrap `inner`, named `name` in its errors, using the names object_store gives its stores
+    /// in generic errors, such as `"S3"`.
+    pub fn new(inner: Arc<dyn ObjectStore>, name: &'static str) -> Self {
+        Self { inner, name }
+    }
+
+    fn named<T>(&self, result: object_store::Result<T>) -> object_store::Result<T> {
+        result.map_err(|err| name_store(err, self.name))
+    }
+}
+
+impl Display for NamedStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Transparent, as every store implementation is wrapped
+        write!(f, "{}", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for NamedStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.named(self.inner.put_opts(location, payload, opts).await)
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let upload = self.named(self.inner.put_multipart_opts(location, opts).await)?;
+        Ok(Box::new(NamedUpload {
+            inner: upload,
+            name: self.name,
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let result = self.named(self.inner.get_opts(location, options).await)?;
+        let GetResultPayload::Stream(stream) = result.payload else {
+            // Local files are read by the caller, so there are no requests left to name
+            return Ok(result);
+        };
+        let name = self.name;
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(
+                stream.map_err(move |err| name_store(err, name)).boxed(),
+            ),
+            ..result
+        })
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.named(self.inner.get_range(location, range).await)
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.named(self.inner.get_ranges(location, ranges).await)
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.named(self.inner.head(location).await)
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.named(self.inner.delete(location).await)
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Forwarded so that stores keep deleting in bulk
+        self.inner
+            .delete_stream(locations)
+            .map_err(|err| name_store(err, self.name))
+            .boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let name = self.name;
+        owned_list(self.inner.clone(), prefix, None)
+            .map_err(move |err| name_store(err, name))
+            .boxed()
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let name = self.name;
+        owned_list(self.inner.clone(), prefix, Some(offset))
+            .map_err(move |err| name_store(err, name))
+            .boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.named(self.inner.list_with_delimiter(prefix).await)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.named(self.inner.copy(from, to).await)
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.named(self.inner.rename(from, to).await)
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.named(self.inner.copy_if_not_exists(from, to).await)
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.named(self.inner.rename_if_not_exists(from, to).await)
+    }
+}
+
+/// A multipart upload that names its store in the errors of its requests.
+#[derive(Debug)]
+struct NamedUpload {
+    inner: Box<dyn MultipartUpload>,
+    name: &'static str,
+}
+
+#[async_trait]
+impl MultipartUpload for NamedUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let name = self.name;
+        self.inner
+            .put_part(data)
+            .map(move |result| result.map_err(|err| name_store(err, name)))
+            .boxed()
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        let result = self.inner.complete().await;
+        result.map_err(|err| name_store(err, self.name))
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        let result = self.inner.abort().await;
+        result.map_err(|err| name_store(err, self.name))
+    }
+}
@@ -0,0 +1,23 @@
+use object_store::path::Path;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
+use url::Url;
+
+/// Parse the URL of the root of a store.
+pub(crate) fn parse_base_url(url: &str) -> PyResult<Url> {
+    Url::parse(url).map_err(|err| PyValueError::new_err(format!("Invalid store URL {url}: {err}")))
+}
+
+/// Format the URL of the object at `path` within the store rooted at `base`.
+///
+/// Each part of the path is percent-encoded as needed, so that the URL can be parsed back into the
+/// same path.
+pub(crate) fn object_url(base: &Url, path: &str) -> String {
+    let path = Path::from(path);
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .expect("store URLs can be a base")
+        .pop_if_empty()
+        .extend(path.parts().map(|part| part.as_ref().to_string()));
+    url.to_string()
+}
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use pyo3::prelude::*;
+use pyo3::{PyClass, PyClassInitializer};
 
+use object_store::path::Path;
 use object_store::prefix::PrefixStore;
 use object_store::ObjectStore;
 
@@ -28,3 +30,31 @@ impl PyPrefixStore {
         self.0.to_string()
     }
 }
+
+/// `path` under `prefix`, as a [`PrefixStore`] with that prefix addresses it.
+pub fn prefixed_path(prefix: Option<&Path>, path: &Path) -> Path {
+    match prefix {
+        Some(prefix) => prefix.parts().chain(path.parts()).collect(),
+        None => path.clone(),
+    }
+}
+
+/// Return `store` to Python, or `inner`, the store it makes requests through, wrapped in a
+/// [`PyPrefixStore`] if `prefix` is set.
+pub(crate) fn with_prefix<T>(
+    py: Python,
+    store: T,
+    inner: Arc<dyn ObjectStore>,
+    prefix: Option<String>,
+) -> PyResult<PyObject>
+where
+    T: PyClass + Into<PyClassInitializer<T>>,
+{
+    match prefix {
+        Some(prefix) => {
+            let store = PyPrefixStore(Arc::new(PrefixStore::new(inner, prefix)));
+            Ok(Py::new(py, store)?.into_any())
+        }
+        None => Ok(Py::new(py, store)?.into_any()),
+    }
+}
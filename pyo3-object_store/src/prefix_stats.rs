@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::{BoxStream, StreamExt};
+use futures::FutureExt;
+use object_store::path::{Path, DELIMITER};
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOpts,
+    PutOptions, PutPayload, PutResult, UploadPart,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::list::owned_list;
+use crate::PyObjectStore;
+
+/// Aggregated statistics of the sampled requests to one prefix.
+#[derive(Debug, Clone, Default)]
+struct PrefixStats {
+    requests: usize,
+    errors: usize,
+    total_latency: Duration,
+    max_latency: Duration,
+}
+
+impl PrefixStats {
+    fn record(&mut self, latency: Duration, failed: bool) {
+        self.requests += 1;
+        if failed {
+            self.errors += 1;
+        }
+        self.total_latency += latency;
+        self.max_latency = self.max_latency.max(latency);
+    }
+}
+
+#[derive(Debug)]
+struct Sampler {
+    depth: usize,
+    sample_rate: f64,
+    /// The number of requests seen, sampled or not
+    seen: AtomicU64,
+    prefixes: Mutex<HashMap<String, PrefixStats>>,
+}
+
+impl Sampler {
+    /// The first `depth` segments of `location`, which requests are grouped by.
+    fn prefix(&self, location: &Path) -> String {
+        location
+            .parts()
+            .take(self.depth)
+            .map(|part| part.as_ref().to_string())
+            .collect::<Vec<_>>()
+            .join(DELIMITER)
+    }
+
+    /// Whether the next request should be sampled.
+    ///
+    /// Rather than sampling at random, this samples evenly spaced requests so that exactly
+    /// `sample_rate` of them are recorded.
+    fn sample(&self) -> bool {
+        if self.sample_rate >= 1.0 {
+            return true;
+        }
+        let n = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * self.sample_rate).floor() > (n * self.sample_rate).floor()
+    }
+
+    /// Start timing a request to `location`, if it is sampled.
+    fn start(self: &Arc<Self>, location: &Path) -> Option<Sample> {
+        self.sample().then(|| Sample {
+            sampler: self.clone(),
+            prefix: self.prefix(location),
+            started: Instant::now(),
+        })
+    }
+
+    fn hotspots(&self, limit: Option<usize>) -> Vec<(String, PrefixStats)> {
+        let mut hotspots = self
+            .prefixes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(prefix, stats)| (prefix.clone(), stats.clone()))
+            .collect::<Vec<_>>();
+        hotspots.sort_by(|(a_prefix, a), (b_prefix, b)| {
+            b.requests
+                .cmp(&a.requests)
+                .then(b.total_latency.cmp(&a.total_latency))
+                .then(a_prefix.cmp(b_prefix))
+        });
+        if let Some(limit) = limit {
+            hotspots.truncate(limit);
+        }
+        hotspots
+    }
+}
+
+/// A sampled request, which must be resolved with its outcome to be recorded.
+struct Sample {
+    sampler: Arc<Sampler>,
+    prefix: String,
+    started: Instant,
+}
+
+impl Sample {
+    fn finish<T>(self, result: &object_store::Result<T>) {
+        let latency = self.started.elapsed();
+        self.sampler
+            .prefixes
+            .lock()
+            .unwrap()
+            .entry(self.prefix)
+            .or_default()
+            .record(latency, result.is_err());
+    }
+}
+
+/// An [`ObjectStore`] wrapper that samples requests and aggregates their count, errors and
+/// latency by the first path segments of their location.
+#[derive(Debug)]
+pub struct PrefixStatsStore {
+    inner: Arc<dyn ObjectStore>,
+    sampler: Arc<Sampler>,
+}
+
+impl PrefixStatsStore {
+    /// Wrap `inner`, grouping requests by their first `depth` path segments and recording
+    /// `sample_rate` of them.
+    pub fn new(inner: Arc<dyn ObjectStore>, depth: usize, sample_rate: f64) -> Self {
+        Self {
+            inner,
+            sampler: Arc::new(Sampler {
+                depth,
+                sample_rate,
+                seen: AtomicU64::new(0),
+                prefixes: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    async fn time<T>(
+        &self,
+        location: &Path,
+        request: impl std::future::Future<Output = object_store::Result<T>>,
+    ) -> object_store::Result<T> {
+        let sample = self.sampler.start(location);
+        let result = request.await;
+        if let Some(sample) = sample {
+            sample.finish(&result);
+        }
+        result
+    }
+
+    /// Wrap a listing, which is recorded once its first item arrives.
+    fn time_list(
+        &self,
+        prefix: Option<&Path>,
+        stream: BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let root = Path::default();
+        let Some(sample) = self.sampler.start(prefix.unwrap_or(&root)) else {
+            return stream;
+        };
+        let mut sample = Some(sample);
+        stream
+            .map(move |item| {
+                if let Some(sample) = sample.take() {
+                    sample.finish(&item);
+                }
+                item
+            })
+            .boxed()
+    }
+}
+
+impl Display for PrefixStatsStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PrefixStatsStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for PrefixStatsStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.time(location, self.inner.put_opts(location, payload, opts))
+            .await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let upload = self
+            .time(location, self.inner.put_multipart_opts(location, opts))
+            .await?;
+        Ok(Box::new(PrefixStatsUpload {
+            inner: upload,
+            location: location.clone(),
+            sampler: self.sampler.clone(),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.time(location, self.inner.get_opts(location, options))
+            .await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.time(location, self.inner.get_range(location, range))
+            .await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.time(location, self.inner.get_ranges(location, ranges))
+            .await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.time(location, self.inner.head(location)).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.time(location, self.inner.delete(location)).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Forwarded so that stores keep deleting in bulk. The requests the paths are batched
+        // into aren't visible, so each deleted path is recorded under its own prefix with the
+        // time since the deletes started, and the errors under the root.
+        let started = Instant::now();
+        let root = Path::default();
+        self.inner
+            .delete_stream(locations)
+            .map(move |item| {
+                if self.sampler.sample() {
+                    let location = item.as_ref().unwrap_or(&root);
+                    let sample = Sample {
+                        sampler: self.sampler.clone(),
+                        prefix: self.sampler.prefix(location),
+                        started,
+                    };
+                    sample.finish(&item);
+                }
+                item
+            })
+            .boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.time_list(prefix, owned_list(self.inner.clone(), prefix, None))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.time_list(prefix, owned_list(self.inner.clone(), prefix, Some(offset)))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        let root = Path::default();
+        self.time(
+            prefix.unwrap_or(&root),
+            self.inner.list_with_delimiter(prefix),
+        )
+        .await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.time(to, self.inner.copy(from, to)).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.time(to, self.inner.rename(from, to)).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.time(to, self.inner.copy_if_not_exists(from, to)).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.time(to, self.inner.rename_if_not_exists(from, to))
+            .await
+    }
+}
+
+/// A multipart upload whose parts are sampled by the [`Sampler`] of its store.
+#[derive(Debug)]
+struct PrefixStatsUpload {
+    inner: Box<dyn MultipartUpload>,
+    location: Path,
+    sampler: Arc<Sampler>,
+}
+
+#[async_trait]
+impl MultipartUpload for PrefixStatsUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let Some(sample) = self.sampler.start(&self.location) else {
+            return self.inner.put_part(data);
+        };
+        self.inner
+            .put_part(data)
+            .map(move |result| {
+                sample.finish(&result);
+                result
+            })
+            .boxed()
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        let sample = self.sampler.start(&self.location);
+        let result = self.inner.complete().await;
+        if let Some(sample) = sample {
+            sample.finish(&result);
+        }
+        result
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.inner.abort().await
+    }
+}
+
+/// A Python-facing wrapper around a [`PrefixStatsStore`].
+#[pyclass(name = "PrefixStatsStore", frozen)]
+pub struct PyPrefixStatsStore(Arc<PrefixStatsStore>);
+
+impl AsRef<Arc<PrefixStatsStore>> for PyPrefixStatsStore {
+    fn as_ref(&self) -> &Arc<PrefixStatsStore> {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PyPrefixStatsStore {
+    #[new]
+    #[pyo3(signature = (store, *, depth=1, sample_rate=1.0))]
+    fn new(store: PyObjectStore, depth: usize, sample_rate: f64) -> PyResult<Self> {
+        if depth == 0 {
+            return Err(PyValueError::new_err("depth must be at least 1"));
+        }
+        if !(sample_rate > 0.0 && sample_rate <= 1.0) {
+            return Err(PyValueError::new_err(
+                "sample_rate must be greater than 0 and at most 1",
+            ));
+        }
+        Ok(Self(Arc::new(PrefixStatsStore::new(
+            store.into_inner(),
+            depth,
+            sample_rate,
+        ))))
+    }
+
+    #[getter]
+    fn depth(&self) -> usize {
+        self.0.sampler.depth
+    }
+
+    #[getter]
+    fn sample_rate(&self) -> f64 {
+        self.0.sampler.sample_rate
+    }
+
+    /// The sampled prefixes, ordered by their number of requests.
+    #[pyo3(signature = (limit=10))]
+    fn hotspots<'py>(
+        &self,
+        py: Python<'py>,
+        limit: Option<usize>,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.0
+            .sampler
+            .hotspots(limit)
+            .into_iter()
+            .map(|(prefix, stats)| {
+                let dict = PyDict::new(py);
+                dict.set_item("prefix", prefix)?;
+                dict.set_item("requests", stats.requests)?;
+                dict.set_item("errors", stats.errors)?;
+                dict.set_item(
+                    "mean_latency",
+                    stats.total_latency / stats.requests.max(1) as u32,
+                )?;
+                dict.set_item("max_latency", stats.max_latency)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// Clear the statistics recorded so far.
+    fn reset(&self) {
+        self.0.sampler.prefixes.lock().unwrap().clear();
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
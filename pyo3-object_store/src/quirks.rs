@@ -0,0 +1,134 @@
+//! Known gaps in S3-compatible implementations, keyed by endpoint.
+//!
+//! Stores look up the quirks of their endpoint on each request, so that they fall back to
+//! requests the endpoint does support. Quirks can be registered at runtime for in-house
+//! implementations.
+
+use std::sync::{OnceLock, RwLock};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use url::Url;
+
+/// The gaps of an S3-compatible implementation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quirks {
+    /// Conditional puts with `If-None-Match: *` overwrite existing objects, so they're
+    /// refused.
+    pub ignores_if_none_match: bool,
+    /// Conditional puts with `If-None-Match: *` are emulated by checking that the object
+    /// doesn't exist first, which isn't atomic. Implies `ignores_if_none_match`.
+    pub emulate_if_none_match: bool,
+    /// Bulk deletes with `DeleteObjects` aren't supported.
+    pub lacks_delete_objects: bool,
+    /// Multipart copies with `UploadPartCopy` aren't supported.
+    pub lacks_upload_part_copy: bool,
+}
+
+impl Quirks {
+    fn union(self, other: Self) -> Self {
+        Self {
+            ignores_if_none_match: self.ignores_if_none_match || other.ignores_if_none_match,
+            emulate_if_none_match: self.emulate_if_none_match || other.emulate_if_none_match,
+            lacks_delete_objects: self.lacks_delete_objects || other.lacks_delete_objects,
+            lacks_upload_part_copy: self.lacks_upload_part_copy || other.lacks_upload_part_copy,
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Quirks {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("ignores_if_none_match", self.ignores_if_none_match)?;
+        dict.set_item("emulate_if_none_match", self.emulate_if_none_match)?;
+        dict.set_item("lacks_delete_objects", self.lacks_delete_objects)?;
+        dict.set_item("lacks_upload_part_copy", self.lacks_upload_part_copy)?;
+        Ok(dict)
+    }
+}
+
+/// The quirks of the endpoints whose host matches `pattern`, in which `*` matches any run of
+/// characters.
+#[derive(Debug)]
+struct QuirkRule {
+    pattern: String,
+    quirks: Quirks,
+}
+
+fn rules() -> &'static RwLock<Vec<QuirkRule>> {
+    static RULES: OnceLock<RwLock<Vec<QuirkRule>>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        RwLock::new(vec![
+            // The S3-compatible XML API of Google Cloud Storage
+            QuirkRule {
+                pattern: "storage.googleapis.com".to_string(),
+                quirks: Quirks {
+                    lacks_delete_objects: true,
+                    lacks_upload_part_copy: true,
+                    ..Default::default()
+                },
+            },
+            QuirkRule {
+                pattern: "*.storage.googleapis.com".to_string(),
+                quirks: Quirks {
+                    lacks_delete_objects: true,
+                    lacks_upload_part_copy: true,
+                    ..Default::default()
+                },
+            },
+        ])
+    })
+}
+
+/// Whether `host` matches `pattern`, in which `*` matches any run of characters.
+fn matches(pattern: &str, host: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == host,
+        Some((prefix, rest)) => {
+            let Some(host) = host.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=host.len())
+                .filter(|i| host.is_char_boundary(*i))
+                .any(|i| matches(rest, &host[i..]))
+        }
+    }
+}
+
+/// The host of `endpoint`, with its port if it has one.
+fn host(endpoint: &str) -> String {
+    match Url::parse(endpoint) {
+        Ok(url) => match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => endpoint.to_string(),
+        },
+        Err(_) => endpoint.to_string(),
+    }
+}
+
+/// The quirks of `endpoint`, combining those of all rules matching its host.
+pub fn quirks_for(endpoint: &str) -> Quirks {
+    let host = host(endpoint).to_lowercase();
+    rules()
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|rule| matches(&rule.pattern, &host))
+        .fold(Quirks::default(), |quirks, rule| quirks.union(rule.quirks))
+}
+
+/// Register the quirks of the endpoints whose host matches `pattern`, replacing any quirks
+/// registered before for the same pattern.
+pub fn register_quirks(pattern: &str, quirks: Quirks) {
+    let pattern = pattern.to_lowercase();
+    let mut rules = rules().write().unwrap();
+    rules.retain(|rule| rule.pattern != pattern);
+    if quirks != Quirks::default() {
+        rules.push(QuirkRule { pattern, quirks });
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOpts,
+    PutOptions, PutPayload, PutResult,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::list::{owned_delete_stream, owned_list};
+use crate::PyObjectStore;
+
+const STORE: &str = "ResolvingStore";
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to create a store for the resolved endpoint")]
+struct ResolveError {
+    #[from]
+    source: PyErr,
+}
+
+impl From<ResolveError> for object_store::Error {
+    fn from(err: ResolveError) -> Self {
+        Self::Generic {
+            store: STORE,
+            source: Box::new(err),
+        }
+    }
+}
+
+/// Whether `err` was caused by a failure to connect to the endpoint.
+fn is_connect_error(err: &object_store::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+    while let Some(err) = source {
+        if err
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|err| err.is_connect())
+        {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Where endpoints come from.
+#[derive(Debug)]
+enum Endpoints {
+    /// A Python callable returning the endpoint to use.
+    Callback(PyObject),
+    /// A fixed list of endpoints, tried in turn.
+    Static(Vec<String>),
+}
+
+/// The store currently in use.
+#[derive(Debug)]
+struct Resolved {
+    endpoint: String,
+    store: Arc<dyn ObjectStore>,
+    /// Incremented on every re-resolution, so that concurrent requests failing against the
+    /// same store only re-resolve once.
+    generation: usize,
+}
+
+/// An [`ObjectStore`] wrapper that resolves the endpoint of its inner store at runtime, and
+/// re-resolves it when requests fail to connect.
+#[derive(Debug)]
+pub struct ResolvingStore {
+    factory: PyObject,
+    endpoints: Endpoints,
+    resolved: RwLock<Resolved>,
+}
+
+impl ResolvingStore {
+    fn resolve(
+        py: Python,
+        factory: &PyObject,
+        endpoints: &Endpoints,
+        generation: usize,
+    ) -> PyResult<(String, Arc<dyn ObjectStore>)> {
+        let endpoint = match endpoints {
+            Endpoints::Callback(callback) => callback.call0(py)?.extract::<String>(py)?,
+            Endpoints::Static(endpoints) => endpoints[generation % endpoints.len()].clone(),
+        };
+        let store = factory
+            .call1(py, (&endpoint,))?
+            .extract::<PyObjectStore>(py)?
+            .into_inner();
+        Ok((endpoint, store))
+    }
+
+    fn current(&self) -> (Arc<dyn ObjectStore>, usize) {
+        let resolved = self.resolved.read().unwrap();
+        (resolved.store.clone(), resolved.generation)
+    }
+
+    /// Replace the store resolved in `generation` with a store for a newly resolved endpoint.
+    fn re_resolve(&self, generation: usize) -> object_store::Result<()> {
+        if self.resolved.read().unwrap().generation != generation {
+            // Another request has already re-resolved
+            return Ok(());
+        }
+        let (endpoint, store) = Python::with_gil(|py| {
+            Self::resolve(py, &self.factory, &self.endpoints, generation + 1)
+        })
+        .map_err(ResolveError::from)?;
+        let mut resolved = self.resolved.write().unwrap();
+        if resolved.generation == generation {
+            *resolved = Resolved {
+                endpoint,
+                store,
+                generation: generation + 1,
+            };
+        }
+        Ok(())
+    }
+
+    /// Call `request` with the current store, retrying once with a re-resolved store if it
+    /// fails to connect.
+    async fn call<T, F, Fut>(&self, request: F) -> object_store::Result<T>
+    where
+        F: Fn(Arc<dyn ObjectStore>) -> Fut,
+        Fut: Future<Output = object_store::Result<T>>,
+    {
+        let (store, generation) = self.current();
+        match request(store).await {
+            Err(err) if is_connect_error(&err) => {
+                self.re_resolve(generation)?;
+                request(self.current().0).await
+            }
+            result => result,
+        }
+    }
+}
+
+impl Display for ResolvingStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let resolved = self.resolved.read().unwrap();
+        write!(
+            f,
+            "ResolvingStore(endpoint=\"{}\", {})",
+            resolved.endpoint, resolved.store
+        )
+    }
+}
+
+#[async_trait]
+impl ObjectStore for ResolvingStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.call(|store| {
+            let (payload, opts) = (payload.clone(), opts.clone());
+            async move { store.put_opts(location, payload, opts).await }
+        })
+        .await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        // Parts are uploaded to the endpoint the upload was created on
+        self.call(|store| {
+            let opts = opts.clone();
+            async move { store.put_multipart_opts(location, opts).await }
+        })
+        .await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.call(|store| {
+            let options = options.clone();
+            async move { store.get_opts(location, options).await }
+        })
+        .await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.call(|store| {
+            let range = range.clone();
+            async move { store.get_range(location, range).await }
+        })
+        .await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.call(|store| async move { store.get_ranges(location, ranges).await })
+            .await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.call(|store| async move { store.head(location).await })
+            .await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.delete(location).await })
+            .await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // Forwarded so that stores keep deleting in bulk. Like listings, they aren't retried.
+        let (store, _) = self.current();
+        owned_delete_stream(store, locations)
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        // Listings are streamed, so they aren't retried
+        let (store, _) = self.current();
+        owned_list(store, prefix, None)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let (store, _) = self.current();
+        owned_list(store, prefix, Some(offset))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.call(|store| async move { store.list_with_delimiter(prefix).await })
+            .await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.copy(from, to).await })
+            .await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.rename(from, to).await })
+            .await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.copy_if_not_exists(from, to).await })
+            .await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.call(|store| async move { store.rename_if_not_exists(from, to).await })
+            .await
+    }
+}
+
+/// The endpoints passed from Python: a callable or a sequence of endpoints.
+enum PyEndpoints {
+    Callback(PyObject),
+    Static(Vec<String>),
+}
+
+impl<'py> FromPyObject<'py> for PyEndpoints {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if ob.is_callable() {
+            Ok(Self::Callback(ob.clone().unbind()))
+        } else if ob.is_instance_of::<PyString>() {
+            Err(PyValueError::new_err(
+                "endpoints must be a callable or a sequence of endpoints, not a string",
+            ))
+        } else {
+            Ok(Self::Static(ob.extract()?))
+        }
+    }
+}
+
+/// A Python-facing wrapper around a [`ResolvingStore`].
+#[pyclass(name = "ResolvingStore", frozen)]
+pub struct PyResolvingStore(Arc<ResolvingStore>);
+
+impl AsRef<Arc<ResolvingStore>> for PyResolvingStore {
+    fn as_ref(&self) -> &Arc<ResolvingStore> {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PyResolvingStore {
+    #[new]
+    fn new(py: Python, factory: PyObject, endpoints: PyEndpoints) -> PyResult<Self> {
+        let endpoints = match endpoints {
+            PyEndpoints::Callback(callback) => Endpoints::Callback(callback),
+            PyEndpoints::Static(endpoints) if endpoints.is_empty() => {
+                return Err(PyValueError::new_err("endpoints must not be empty"));
+            }
+            PyEndpoints::Static(endpoints) => Endpoints::Static(endpoints),
+        };
+        let (endpoint, store) = ResolvingStore::resolve(py, &factory, &endpoints, 0)?;
+        Ok(Self(Arc::new(ResolvingStore {
+            factory,
+            endpoints,
+            resolved: RwLock::new(Resolved {
+                endpoint,
+                store,
+                generation: 0,
+            }),
+        })))
+    }
+
+    /// The endpoint currently in use.
+    #[getter]
+    fn endpoint(&self) -> String {
+        self.0.resolved.read().unwrap().endpoint.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
@@ -2,8 +2,9 @@ use std::time::Duration;
 
 use object_store::{BackoffConfig, RetryConfig};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
-#[derive(Debug, FromPyObject)]
+#[derive(Debug, Clone, FromPyObject)]
 #[pyo3(from_item_all)]
 pub struct PyBackoffConfig {
     init_backoff: Duration,
@@ -11,6 +12,20 @@ pub struct PyBackoffConfig {
     base: f64,
 }
 
+impl<'py> IntoPyObject<'py> for PyBackoffConfig {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("init_backoff", self.init_backoff)?;
+        dict.set_item("max_backoff", self.max_backoff)?;
+        dict.set_item("base", self.base)?;
+        Ok(dict)
+    }
+}
+
 impl From<PyBackoffConfig> for BackoffConfig {
     fn from(value: PyBackoffConfig) -> Self {
         BackoffConfig {
@@ -21,7 +36,7 @@ impl From<PyBackoffConfig> for BackoffConfig {
     }
 }
 
-#[derive(Debug, FromPyObject)]
+#[derive(Debug, Clone, FromPyObject)]
 #[pyo3(from_item_all)]
 pub struct PyRetryConfig {
     backoff: PyBackoffConfig,
@@ -29,6 +44,20 @@ pub struct PyRetryConfig {
     retry_timeout: Duration,
 }
 
+impl<'py> IntoPyObject<'py> for PyRetryConfig {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let dict = PyDict::new(py);
+        dict.set_item("backoff", self.backoff)?;
+        dict.set_item("max_retries", self.max_retries)?;
+        dict.set_item("retry_timeout", self.retry_timeout)?;
+        Ok(dict)
+    }
+}
+
 impl From<PyRetryConfig> for RetryConfig {
     fn from(value: PyRetryConfig) -> Self {
         RetryConfig {
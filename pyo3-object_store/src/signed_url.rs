@@ -0,0 +1,344 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::future;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{
+    Attribute, Attributes, GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload,
+    ObjectMeta, ObjectStore, PutMode, PutMultipartOpts, PutOptions, PutPayload, PutResult,
+};
+use pyo3::prelude::*;
+use reqwest::header::{self, HeaderMap, HeaderName};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use url::Url;
+
+const STORE: &str = "SignedURLStore";
+
+/// The number of objects deleted at once by [`ObjectStore::delete_stream`].
+const DELETE_CONCURRENCY: usize = 10;
+
+/// The headers that are returned as, and sent from, the attributes of an object.
+fn attribute_headers() -> [(Attribute, HeaderName); 5] {
+    [
+        (Attribute::CacheControl, header::CACHE_CONTROL),
+        (Attribute::ContentDisposition, header::CONTENT_DISPOSITION),
+        (Attribute::ContentEncoding, header::CONTENT_ENCODING),
+        (Attribute::ContentLanguage, header::CONTENT_LANGUAGE),
+        (Attribute::ContentType, header::CONTENT_TYPE),
+    ]
+}
+
+#[derive(Debug, thiserror::Error)]
+enum SignedUrlError {
+    #[error("The sign callback raised an exception")]
+    SignCallback {
+        #[from]
+        source: PyErr,
+    },
+
+    #[error("The sign callback returned an invalid URL \"{url}\": {source}")]
+    InvalidUrl {
+        url: String,
+        source: url::ParseError,
+    },
+
+    #[error("Request to the signed URL for \"{path}\" failed: {source}")]
+    Request { path: Path, source: reqwest::Error },
+
+    #[error("Request to the signed URL for \"{path}\" failed with status {status}")]
+    Status { path: Path, status: StatusCode },
+}
+
+impl From<SignedUrlError> for object_store::Error {
+    fn from(err: SignedUrlError) -> Self {
+        Self::Generic {
+            store: STORE,
+            source: Box::new(err),
+        }
+    }
+}
+
+/// Map an unsuccessful response to the error object_store reports for it.
+fn status_error(path: &Path, status: StatusCode, mode: Option<&PutMode>) -> object_store::Error {
+    let source = Box::new(SignedUrlError::Status {
+        path: path.clone(),
+        status,
+    });
+    let path = path.to_string();
+    match status {
+        StatusCode::NOT_FOUND => object_store::Error::NotFound { path, source },
+        StatusCode::NOT_MODIFIED => object_store::Error::NotModified { path, source },
+        StatusCode::PRECONDITION_FAILED if matches!(mode, Some(PutMode::Create)) => {
+            object_store::Error::AlreadyExists { path, source }
+        }
+        StatusCode::PRECONDITION_FAILED => object_store::Error::Precondition { path, source },
+        StatusCode::UNAUTHORIZED => object_store::Error::Unauthenticated { path, source },
+        StatusCode::FORBIDDEN => object_store::Error::PermissionDenied { path, source },
+        _ => object_store::Error::Generic {
+            store: STORE,
+            source,
+        },
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// The total size of the object, from `Content-Range` for range requests and from
+/// `Content-Length` otherwise.
+fn object_size(headers: &HeaderMap) -> usize {
+    header_str(headers, &header::CONTENT_RANGE)
+        .and_then(|range| range.rsplit_once('/'))
+        .and_then(|(_, total)| total.parse().ok())
+        .or_else(|| header_str(headers, &header::CONTENT_LENGTH)?.parse().ok())
+        .unwrap_or(0)
+}
+
+/// The byte range of the object in the response.
+fn response_range(headers: &HeaderMap, size: usize) -> std::ops::Range<usize> {
+    header_str(headers, &header::CONTENT_RANGE)
+        .and_then(|range| range.strip_prefix("bytes ")?.split_once('/'))
+        .and_then(|(range, _)| range.split_once('-'))
+        .and_then(|(start, end)| Some(start.parse().ok()?..end.parse::<usize>().ok()? + 1))
+        .unwrap_or(0..size)
+}
+
+fn object_meta(location: &Path, headers: &HeaderMap) -> ObjectMeta {
+    ObjectMeta {
+        location: location.clone(),
+        last_modified: header_str(headers, &header::LAST_MODIFIED)
+            .and_then(|date| DateTime::parse_from_rfc2822(date).ok())
+            .map(|date| date.with_timezone(&Utc))
+            .unwrap_or_default(),
+        size: object_size(headers),
+        e_tag: header_str(headers, &header::ETAG).map(String::from),
+        version: None,
+    }
+}
+
+fn attributes(headers: &HeaderMap) -> Attributes {
+    let mut attributes = Attributes::new();
+    for (attribute, name) in attribute_headers() {
+        if let Some(value) = header_str(headers, &name) {
+            attributes.insert(attribute, value.to_string().into());
+        }
+    }
+    attributes
+}
+
+/// An [`ObjectStore`] whose requests are sent to pre-signed URLs obtained from a Python
+/// callable, so that it never holds credentials itself.
+#[derive(Debug)]
+pub struct SignedUrlStore {
+    sign: PyObject,
+    client: reqwest::Client,
+}
+
+impl SignedUrlStore {
+    /// Create a store calling `sign` with `(method, path)` for the URL of every request.
+    pub fn new(sign: PyObject) -> Self {
+        Self {
+            sign,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn request(&self, method: Method, location: &Path) -> object_store::Result<RequestBuilder> {
+        let url = Python::with_gil(|py| {
+            self.sign
+                .call1(py, (method.as_str(), location.as_ref()))?
+                .extract::<String>(py)
+        })
+        .map_err(SignedUrlError::from)?;
+        let url = Url::parse(&url).map_err(|source| SignedUrlError::InvalidUrl { url, source })?;
+        Ok(self.client.request(method, url))
+    }
+
+    async fn send(
+        location: &Path,
+        request: RequestBuilder,
+        mode: Option<&PutMode>,
+    ) -> object_store::Result<Response> {
+        let response = request
+            .send()
+            .await
+            .map_err(|source| SignedUrlError::Request {
+                path: location.clone(),
+                source,
+            })?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(response)
+        } else {
+            Err(status_error(location, status, mode))
+        }
+    }
+}
+
+impl Display for SignedUrlStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SignedURLStore")
+    }
+}
+
+#[async_trait]
+impl ObjectStore for SignedUrlStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        let mut request = self
+            .request(Method::PUT, location)?
+            .body(Bytes::from(payload));
+        request = match &opts.mode {
+            PutMode::Overwrite => request,
+            PutMode::Create => request.header(header::IF_NONE_MATCH, "*"),
+            PutMode::Update(version) => match &version.e_tag {
+                Some(e_tag) => request.header(header::IF_MATCH, e_tag),
+                None => return Err(object_store::Error::NotImplemented),
+            },
+        };
+        for (attribute, name) in attribute_headers() {
+            if let Some(value) = opts.attributes.get(&attribute) {
+                request = request.header(name, value.as_ref());
+            }
+        }
+        let response = Self::send(location, request, Some(&opts.mode)).await?;
+        Ok(PutResult {
+            e_tag: header_str(response.headers(), &header::ETAG).map(String::from),
+            version: None,
+        })
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        _location: &Path,
+        _opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        Err(object_store::Error::NotImplemented)
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let method = if options.head {
+            Method::HEAD
+        } else {
+            Method::GET
+        };
+        let mut request = self.request(method, location)?;
+        if let Some(range) = &options.range {
+            request = request.header(header::RANGE, range.to_string());
+        }
+        if let Some(e_tag) = &options.if_match {
+            request = request.header(header::IF_MATCH, e_tag);
+        }
+        if let Some(e_tag) = &options.if_none_match {
+            request = request.header(header::IF_NONE_MATCH, e_tag);
+        }
+        if let Some(date) = options.if_modified_since {
+            request = request.header(header::IF_MODIFIED_SINCE, http_date(date));
+        }
+        if let Some(date) = options.if_unmodified_since {
+            request = request.header(header::IF_UNMODIFIED_SINCE, http_date(date));
+        }
+
+        let response = Self::send(location, request, None).await?;
+        let headers = response.headers();
+        let meta = object_meta(location, headers);
+        let range = response_range(headers, meta.size);
+        let attributes = attributes(headers);
+        let path = location.clone();
+        let stream = response
+            .bytes_stream()
+            .map_err(move |source| {
+                object_store::Error::from(SignedUrlError::Request {
+                    path: path.clone(),
+                    source,
+                })
+            })
+            .boxed();
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(stream),
+            meta,
+            range,
+            attributes,
+        })
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        let request = self.request(Method::DELETE, location)?;
+        Self::send(location, request, None).await?;
+        Ok(())
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        // There's no store behind this one to delete in bulk, and a signed URL only signs a
+        // single request, so every object is deleted with its own signed DELETE
+        locations
+            .map(move |location| async move {
+                let location = location?;
+                self.delete(&location).await?;
+                Ok(location)
+            })
+            .buffered(DELETE_CONCURRENCY)
+            .boxed()
+    }
+
+    fn list(&self, _prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        stream::once(future::ready(Err(object_store::Error::NotImplemented))).boxed()
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        _prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        Err(object_store::Error::NotImplemented)
+    }
+
+    async fn copy(&self, _from: &Path, _to: &Path) -> object_store::Result<()> {
+        Err(object_store::Error::NotImplemented)
+    }
+
+    async fn copy_if_not_exists(&self, _from: &Path, _to: &Path) -> object_store::Result<()> {
+        Err(object_store::Error::NotImplemented)
+    }
+}
+
+/// A Python-facing wrapper around a [`SignedUrlStore`].
+#[pyclass(name = "SignedURLStore", frozen)]
+pub struct PySignedUrlStore(Arc<SignedUrlStore>);
+
+impl AsRef<Arc<SignedUrlStore>> for PySignedUrlStore {
+    fn as_ref(&self) -> &Arc<SignedUrlStore> {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PySignedUrlStore {
+    #[new]
+    fn new(sign: PyObject) -> Self {
+        Self(Arc::new(SignedUrlStore::new(sign)))
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
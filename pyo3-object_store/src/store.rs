@@ -7,7 +7,10 @@ use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
 
 use crate::{
-    PyAzureStore, PyGCSStore, PyHttpStore, PyLocalStore, PyMemoryStore, PyPrefixStore, PyS3Store,
+    MetricsStore, NamedStore, PermissionHintStore, PyAzureStore, PyCircuitBreakerStore,
+    PyDefaultGetOptionsStore, PyGCSStore, PyGuardrailStore, PyHttpStore, PyLaneStore, PyLocalStore,
+    PyMemoryStore, PyPrefixStatsStore, PyPrefixStore, PyResolvingStore, PyS3Store,
+    PySignedUrlStore, PyTrashStore,
 };
 
 /// A wrapper around a Rust ObjectStore instance that allows any rust-native implementation of
@@ -18,20 +21,57 @@ pub struct PyObjectStore(Arc<dyn ObjectStore>);
 
 impl<'py> FromPyObject<'py> for PyObjectStore {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Wrappers are wrapped too, so that denials by a wrapper such as a GuardrailStore are
+        // probed through it
+        let store = Self::extract_store(ob)?;
+        Ok(Self(Arc::new(PermissionHintStore::new(store.0))))
+    }
+}
+
+impl AsRef<Arc<dyn ObjectStore>> for PyObjectStore {
+    fn as_ref(&self) -> &Arc<dyn ObjectStore> {
+        &self.0
+    }
+}
+
+impl PyObjectStore {
+    fn extract_store(ob: &Bound<PyAny>) -> PyResult<Self> {
         if let Ok(store) = ob.downcast::<PyS3Store>() {
-            Ok(Self(store.get().as_ref().clone()))
+            Ok(Self::instrumented(store.get().region_aware().clone(), "S3"))
         } else if let Ok(store) = ob.downcast::<PyAzureStore>() {
-            Ok(Self(store.get().as_ref().clone()))
+            Ok(Self::instrumented(
+                store.get().as_ref().clone(),
+                "MicrosoftAzure",
+            ))
         } else if let Ok(store) = ob.downcast::<PyGCSStore>() {
-            Ok(Self(store.get().as_ref().clone()))
+            Ok(Self::instrumented(store.get().object_store(), "GCS"))
         } else if let Ok(store) = ob.downcast::<PyHttpStore>() {
-            Ok(Self(store.get().as_ref().clone()))
+            Ok(Self::instrumented(store.get().as_ref().clone(), "HTTP"))
         } else if let Ok(store) = ob.downcast::<PyLocalStore>() {
-            Ok(Self(store.get().as_ref().clone()))
+            Ok(Self::instrumented(
+                store.get().as_ref().clone(),
+                "LocalFileSystem",
+            ))
         } else if let Ok(store) = ob.downcast::<PyMemoryStore>() {
-            Ok(Self(store.get().as_ref().clone()))
+            Ok(Self::instrumented(store.get().as_ref().clone(), "InMemory"))
         } else if let Ok(store) = ob.downcast::<PyPrefixStore>() {
             Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PyGuardrailStore>() {
+            Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PyTrashStore>() {
+            Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PyCircuitBreakerStore>() {
+            Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PyResolvingStore>() {
+            Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PyPrefixStatsStore>() {
+            Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PySignedUrlStore>() {
+            Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PyDefaultGetOptionsStore>() {
+            Ok(Self(store.get().as_ref().clone()))
+        } else if let Ok(store) = ob.downcast::<PyLaneStore>() {
+            Ok(Self(store.get().as_ref().clone()))
         } else {
             let py = ob.py();
             // Check for object-store instance from other library
@@ -47,6 +87,14 @@ impl<'py> FromPyObject<'py> for PyObjectStore {
                 "MemoryStore",
                 "S3Store",
                 "PrefixStore",
+                "GuardrailStore",
+                "TrashStore",
+                "CircuitBreakerStore",
+                "ResolvingStore",
+                "PrefixStatsStore",
+                "SignedURLStore",
+                "DefaultGetOptionsStore",
+                "LaneStore",
             ]
             .contains(&cls_name.as_ref())
             {
@@ -60,15 +108,15 @@ impl<'py> FromPyObject<'py> for PyObjectStore {
             )))
         }
     }
-}
 
-impl AsRef<Arc<dyn ObjectStore>> for PyObjectStore {
-    fn as_ref(&self) -> &Arc<dyn ObjectStore> {
-        &self.0
+    /// Wrap a store that isn't a wrapper itself in a [`MetricsStore`], so that each request is
+    /// recorded once however many wrappers it passes through, and in a [`NamedStore`] named
+    /// `name`.
+    fn instrumented(store: Arc<dyn ObjectStore>, name: &'static str) -> Self {
+        let store = Arc::new(NamedStore::new(store, name));
+        Self(Arc::new(MetricsStore::new(store)))
     }
-}
 
-impl PyObjectStore {
     /// Consume self and return the underlying [`ObjectStore`].
     pub fn into_inner(self) -> Arc<dyn ObjectStore> {
         self.0
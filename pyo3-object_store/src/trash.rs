@@ -0,0 +1,235 @@
+use std::fmt::Display;
+use std::ops::Range;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use futures::future;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOpts,
+    PutOptions, PutPayload, PutResult,
+};
+use pyo3::prelude::*;
+
+use crate::list::owned_list;
+use crate::{clock, PyObjectStore};
+
+/// Format of the directory that deleted objects are moved into, e.g. `20241014T113107.123456Z`.
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.6fZ";
+
+/// An [`ObjectStore`] wrapper that moves deleted objects into a timestamped trash prefix instead
+/// of deleting them.
+///
+/// Objects deleted at time `t` are moved to `{trash_prefix}/{t}/{path}`. Deleting an object that
+/// is already within the trash prefix deletes it permanently. The trash prefix is hidden from
+/// listings that don't explicitly list within it.
+#[derive(Debug)]
+pub struct TrashStore {
+    inner: Arc<dyn ObjectStore>,
+    trash_prefix: Path,
+}
+
+impl TrashStore {
+    /// Wrap `inner`, moving deleted objects below `trash_prefix`.
+    pub fn new(inner: Arc<dyn ObjectStore>, trash_prefix: Path) -> Self {
+        Self {
+            inner,
+            trash_prefix,
+        }
+    }
+
+    fn in_trash(&self, location: &Path) -> bool {
+        location.prefix_matches(&self.trash_prefix)
+    }
+
+    fn trash_location(&self, location: &Path, deleted_at: DateTime<Utc>) -> Path {
+        let deleted_at = self
+            .trash_prefix
+            .child(deleted_at.format(TIMESTAMP_FORMAT).to_string());
+        location
+            .parts()
+            .fold(deleted_at, |path, part| path.child(part))
+    }
+
+    /// Whether listing `prefix` should include the trash.
+    fn lists_trash(&self, prefix: Option<&Path>) -> bool {
+        prefix.is_some_and(|prefix| self.in_trash(prefix))
+    }
+
+    fn filter_list(
+        &self,
+        prefix: Option<&Path>,
+        stream: BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        if self.lists_trash(prefix) {
+            return stream;
+        }
+        let trash_prefix = self.trash_prefix.clone();
+        stream
+            .try_filter(move |meta| future::ready(!meta.location.prefix_matches(&trash_prefix)))
+            .boxed()
+    }
+
+    /// Permanently delete objects that were moved to the trash more than `older_than` ago.
+    ///
+    /// Returns the number of objects deleted.
+    pub async fn purge_trash(&self, older_than: TimeDelta) -> object_store::Result<usize> {
+        let cutoff = clock::now() - older_than;
+        let listing = self
+            .inner
+            .list_with_delimiter(Some(&self.trash_prefix))
+            .await?;
+
+        let mut purged = 0;
+        for prefix in listing.common_prefixes {
+            // Ignore anything in the trash that wasn't put there by this store
+            let Some(deleted_at) = prefix
+                .filename()
+                .and_then(|name| NaiveDateTime::parse_from_str(name, TIMESTAMP_FORMAT).ok())
+            else {
+                continue;
+            };
+            if deleted_at.and_utc() >= cutoff {
+                continue;
+            }
+
+            let locations = self
+                .inner
+                .list(Some(&prefix))
+                .map_ok(|meta| meta.location)
+                .boxed();
+            purged += self
+                .inner
+                .delete_stream(locations)
+                .try_fold(0, |count, _| future::ready(Ok(count + 1)))
+                .await?;
+        }
+        Ok(purged)
+    }
+}
+
+impl Display for TrashStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TrashStore({}, \"{}\")", self.inner, self.trash_prefix)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for TrashStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> object_store::Result<Bytes> {
+        self.inner.get_range(location, range).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.inner.get_ranges(location, ranges).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        if self.in_trash(location) {
+            return self.inner.delete(location).await;
+        }
+        let trash_location = self.trash_location(location, clock::now());
+        self.inner.rename(location, &trash_location).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.filter_list(prefix, owned_list(self.inner.clone(), prefix, None))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.filter_list(prefix, owned_list(self.inner.clone(), prefix, Some(offset)))
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        let mut result = self.inner.list_with_delimiter(prefix).await?;
+        if !self.lists_trash(prefix) {
+            result.objects.retain(|meta| !self.in_trash(&meta.location));
+            result
+                .common_prefixes
+                .retain(|prefix| !self.in_trash(prefix));
+        }
+        Ok(result)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+/// A Python-facing wrapper around a [`TrashStore`].
+#[pyclass(name = "TrashStore", frozen)]
+pub struct PyTrashStore(Arc<TrashStore>);
+
+impl AsRef<Arc<TrashStore>> for PyTrashStore {
+    fn as_ref(&self) -> &Arc<TrashStore> {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PyTrashStore {
+    #[new]
+    #[pyo3(signature = (store, *, trash_prefix=".trash".to_string()))]
+    fn new(store: PyObjectStore, trash_prefix: String) -> Self {
+        Self(Arc::new(TrashStore::new(
+            store.into_inner(),
+            trash_prefix.into(),
+        )))
+    }
+
+    fn __repr__(&self) -> String {
+        self.0.to_string()
+    }
+}
@@ -1,3 +1,14 @@
+import os
+import threading
+from datetime import timedelta
+from functools import partial
+from http.server import (
+    BaseHTTPRequestHandler,
+    HTTPServer,
+    SimpleHTTPRequestHandler,
+    ThreadingHTTPServer,
+)
+
 import boto3
 import pytest
 import urllib3
@@ -5,7 +16,7 @@ from botocore import UNSIGNED
 from botocore.client import Config
 from moto.moto_server.threaded_moto_server import ThreadedMotoServer
 
-from obstore.store import S3Store
+from obstore.store import HTTPStore, S3Store
 
 TEST_BUCKET_NAME = "test"
 
@@ -51,3 +62,75 @@ def s3_store(s3):
             "AWS_ALLOW_HTTP": "true",
         },
     )
+
+
+class IndexRequestHandler(SimpleHTTPRequestHandler):
+    """Serve `index.json` for directories that contain one, like a JSON autoindex."""
+
+    def translate_path(self, path):
+        translated = super().translate_path(path)
+        index = os.path.join(translated, "index.json")
+        return index if os.path.isfile(index) else translated
+
+
+@pytest.fixture()
+def http_server(tmp_path):
+    """Fixture serving the files in a temporary directory over HTTP.
+
+    Yields the directory and the base URL it is served at.
+    """
+    handler = partial(IndexRequestHandler, directory=str(tmp_path))
+    server = ThreadingHTTPServer(("localhost", 0), handler)
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    host, port = server.server_address
+    yield tmp_path, f"http://{host}:{port}"
+    server.shutdown()
+    server.server_close()
+
+
+@pytest.fixture
+def flaky_server():
+    """A server failing the first request with a 503, and serving later ones."""
+    requests = []
+
+    class FlakyHandler(BaseHTTPRequestHandler):
+        def do_GET(self):
+            requests.append(self.path)
+            if len(requests) == 1:
+                self.send_response(503)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+                return
+            body = b"foo"
+            self.send_response(200)
+            self.send_header("Content-Length", str(len(body)))
+            self.send_header("ETag", '"etag"')
+            self.send_header("Last-Modified", "Wed, 01 Jan 2025 00:00:00 GMT")
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, *args):
+            pass
+
+    server = HTTPServer(("127.0.0.1", 0), FlakyHandler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    yield f"http://127.0.0.1:{server.server_port}"
+    server.shutdown()
+
+
+@pytest.fixture
+def flaky_store(flaky_server):
+    return HTTPStore.from_url(
+        flaky_server,
+        client_options={"allow_http": True},
+        retry_config={
+            "max_retries": 3,
+            "backoff": {
+                "init_backoff": timedelta(milliseconds=10),
+                "max_backoff": timedelta(milliseconds=10),
+                "base": 2,
+            },
+            "retry_timeout": timedelta(seconds=10),
+        },
+    )
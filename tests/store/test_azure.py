@@ -0,0 +1,11 @@
+from obstore.store import AzureStore
+
+
+def test_endpoint_introspection():
+    store = AzureStore("container", account_name="account")
+    assert store.endpoint_url == "https://account.blob.core.windows.net"
+    assert store.using_https
+
+    store = AzureStore("container", use_emulator=True)
+    assert store.endpoint_url == "http://127.0.0.1:10000/devstoreaccount1"
+    assert not store.using_https
@@ -0,0 +1,85 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import GenericError
+from obstore.store import CircuitBreakerStore, HTTPStore, MemoryStore
+
+NO_RETRY = {
+    "max_retries": 0,
+    "backoff": {
+        "base": 2,
+        "init_backoff": timedelta(milliseconds=10),
+        "max_backoff": timedelta(milliseconds=10),
+    },
+    "retry_timeout": timedelta(seconds=1),
+}
+
+
+def unreachable_store() -> HTTPStore:
+    return HTTPStore.from_url("http://127.0.0.1:1", retry_config=NO_RETRY)
+
+
+def test_circuit_opens():
+    transitions = []
+    store = CircuitBreakerStore(
+        unreachable_store(),
+        min_requests=2,
+        on_state_change=lambda old, new: transitions.append((old, new)),
+    )
+    assert store.state == "closed"
+
+    for _ in range(2):
+        with pytest.raises(GenericError):
+            obs.head(store, "file.txt")
+
+    assert store.state == "open"
+    assert transitions == [("closed", "open")]
+
+    with pytest.raises(GenericError, match="Circuit breaker is open"):
+        obs.head(store, "file.txt")
+
+
+def test_half_open_probe_reopens():
+    transitions = []
+    store = CircuitBreakerStore(
+        unreachable_store(),
+        min_requests=1,
+        open_duration=timedelta(0),
+        on_state_change=lambda old, new: transitions.append((old, new)),
+    )
+
+    with pytest.raises(GenericError):
+        obs.head(store, "file.txt")
+    assert store.state == "half_open"
+
+    with pytest.raises(GenericError):
+        obs.head(store, "file.txt")
+    assert transitions == [
+        ("closed", "open"),
+        ("open", "half_open"),
+        ("half_open", "open"),
+    ]
+
+
+def test_not_found_is_not_a_failure():
+    store = CircuitBreakerStore(MemoryStore(), min_requests=1)
+
+    for _ in range(5):
+        with pytest.raises(FileNotFoundError):
+            obs.head(store, "missing.txt")
+
+    assert store.state == "closed"
+
+
+def test_invalid_threshold():
+    with pytest.raises(ValueError):
+        CircuitBreakerStore(MemoryStore(), failure_threshold=0)
+
+
+def test_repr():
+    store = CircuitBreakerStore(MemoryStore())
+    assert repr(store).startswith("CircuitBreakerStore")
+    obs.put(store, "file.txt", b"foo")
+    assert obs.get(store, "file.txt").bytes() == b"foo"
@@ -0,0 +1,163 @@
+import json
+import pickle
+import threading
+from datetime import timedelta
+from http.server import BaseHTTPRequestHandler, HTTPServer
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import GenericError
+from obstore.store import GCSStore
+
+
+def test_external_account_credentials(tmp_path):
+    credentials = tmp_path / "credentials.json"
+    credentials.write_text(
+        json.dumps(
+            {
+                "type": "external_account",
+                "audience": "//iam.googleapis.com/projects/1/locations/global/workloadIdentityPools/pool/providers/provider",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "http://127.0.0.1:1/v1/token",
+                "credential_source": {"file": str(tmp_path / "missing-token")},
+            }
+        )
+    )
+
+    # object_store can't parse external account credentials on its own
+    store = GCSStore("bucket", google_application_credentials=str(credentials))
+
+    with pytest.raises(GenericError, match="token file"):
+        obs.head(store, "file.txt")
+
+
+def test_external_account_credentials_requires_file_source(tmp_path):
+    credentials = tmp_path / "credentials.json"
+    credentials.write_text(
+        json.dumps(
+            {
+                "type": "external_account",
+                "audience": "audience",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "http://127.0.0.1:1/v1/token",
+                "credential_source": {"url": "http://127.0.0.1:1/token"},
+            }
+        )
+    )
+
+    with pytest.raises(GenericError, match="file credential source"):
+        GCSStore("bucket", google_application_credentials=str(credentials))
+
+
+def test_endpoint_introspection():
+    store = GCSStore("bucket")
+    assert store.endpoint_url == "https://storage.googleapis.com"
+    assert store.using_https
+
+
+def test_from_url_prefix():
+    store = GCSStore.from_url("gs://bucket/path/to")
+    assert store.bucket == "bucket"
+    assert store.prefix == "path/to"
+    assert repr(store) == 'GCSStore(bucket="bucket", prefix="path/to")'
+    assert store.url_for("file.txt") == "gs://bucket/path/to/file.txt"
+
+    store = GCSStore.from_url("gs://bucket")
+    assert store.prefix is None
+    assert repr(store) == 'GCSStore(bucket="bucket")'
+
+    with pytest.raises(ValueError, match="scheme"):
+        GCSStore.from_url("s3://bucket/path")
+
+
+def test_with_options_nests_prefix():
+    store = GCSStore("bucket", prefix="a").with_options(prefix="b")
+    assert isinstance(store, GCSStore)
+    assert store.prefix == "a/b"
+
+
+def test_pickle():
+    retry_config = {
+        "max_retries": 3,
+        "backoff": {
+            "base": 2,
+            "init_backoff": timedelta(seconds=1),
+            "max_backoff": timedelta(seconds=10),
+        },
+        "retry_timeout": timedelta(minutes=1),
+    }
+    store = GCSStore(
+        "bucket",
+        prefix="path/to",
+        config={"google_bucket": "bucket"},
+        client_options={"timeout": "10s"},
+        retry_config=retry_config,
+    )
+
+    restored = pickle.loads(pickle.dumps(store))
+    assert isinstance(restored, GCSStore)
+    assert restored.bucket == "bucket"
+    assert restored.prefix == "path/to"
+    assert restored.config == {"google_bucket": "bucket"}
+    assert restored.client_options == {"timeout": "10s"}
+    assert restored.retry_config == retry_config
+
+
+def test_credentials_shared_across_stores(tmp_path):
+    token_requests = []
+
+    class TokenHandler(BaseHTTPRequestHandler):
+        def do_POST(self):
+            token_requests.append(self.path)
+            body = json.dumps({"access_token": "token", "expires_in": 3600}).encode()
+            self.send_response(200)
+            self.send_header("Content-Type", "application/json")
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, *args):
+            pass
+
+    server = HTTPServer(("127.0.0.1", 0), TokenHandler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+
+    token = tmp_path / "token"
+    token.write_text("subject-token")
+    credentials = tmp_path / "credentials.json"
+    credentials.write_text(
+        json.dumps(
+            {
+                "type": "external_account",
+                "audience": "audience",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": f"http://127.0.0.1:{server.server_port}/v1/token",
+                "credential_source": {"file": str(token)},
+            }
+        )
+    )
+
+    try:
+        for bucket in ["bucket-1", "bucket-2"]:
+            store = GCSStore(
+                bucket,
+                google_application_credentials=str(credentials),
+                # Fail requests to GCS quickly, after the token has been fetched
+                client_options={"proxy_url": "http://127.0.0.1:1"},
+                retry_config={
+                    "max_retries": 0,
+                    "backoff": {
+                        "base": 2,
+                        "init_backoff": timedelta(milliseconds=1),
+                        "max_backoff": timedelta(milliseconds=1),
+                    },
+                    "retry_timeout": timedelta(seconds=1),
+                },
+            )
+            with pytest.raises(Exception):  # noqa: B017
+                obs.head(store, "file.txt")
+    finally:
+        server.shutdown()
+
+    assert len(token_requests) == 1
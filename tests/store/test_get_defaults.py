@@ -0,0 +1,47 @@
+import pytest
+
+import obstore as obs
+from obstore.exceptions import PreconditionError
+from obstore.store import DefaultGetOptionsStore, MemoryStore
+
+
+def test_default_options():
+    memory = MemoryStore()
+    e_tag = obs.put(memory, "file.txt", b"foo")["e_tag"]
+    store = DefaultGetOptionsStore(memory, options={"if_match": e_tag})
+    assert store.options == {"if_match": e_tag}
+
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+    assert obs.head(store, "file.txt")["e_tag"] == e_tag
+
+    new_e_tag = obs.put(memory, "file.txt", b"bar")["e_tag"]
+    with pytest.raises(PreconditionError):
+        obs.get(store, "file.txt")
+    with pytest.raises(PreconditionError):
+        obs.get_range(store, "file.txt", start=0, end=2)
+
+    # Options passed to the call take precedence over the defaults
+    result = obs.get(store, "file.txt", options={"if_match": new_e_tag})
+    assert result.bytes() == b"bar"
+
+
+def test_range_chunk_size():
+    memory = MemoryStore()
+    data = bytes(range(100))
+    obs.put(memory, "file.bin", data)
+    store = DefaultGetOptionsStore(memory, range_chunk_size=7)
+    assert store.range_chunk_size == 7
+
+    assert obs.get_range(store, "file.bin", start=3, end=95) == data[3:95]
+    assert obs.get_range(store, "file.bin", start=0, end=5) == data[:5]
+    assert obs.get_ranges(store, "file.bin", starts=[0, 50], ends=[30, 100]) == [
+        data[:30],
+        data[50:],
+    ]
+
+
+def test_invalid_options():
+    with pytest.raises(ValueError):
+        DefaultGetOptionsStore(MemoryStore(), options={"range": [0, 10]})
+    with pytest.raises(ValueError):
+        DefaultGetOptionsStore(MemoryStore(), range_chunk_size=0)
@@ -0,0 +1,131 @@
+import pytest
+
+import obstore as obs
+from obstore.exceptions import GenericError, PermissionDeniedError
+from obstore.store import GuardrailStore, MemoryStore
+
+
+def test_deny_prefixes():
+    memory_store = MemoryStore()
+    obs.put(memory_store, "private/secret.txt", b"foo")
+    obs.put(memory_store, "public/data.txt", b"bar")
+
+    store = GuardrailStore(memory_store, deny_prefixes=["private"])
+
+    with pytest.raises(PermissionDeniedError):
+        obs.get(store, "private/secret.txt")
+
+    with pytest.raises(PermissionDeniedError):
+        obs.put(store, "private/other.txt", b"foo")
+
+    with pytest.raises(PermissionDeniedError):
+        obs.copy(store, "public/data.txt", "private/data.txt")
+
+    # Denied paths are removed from list results
+    paths = [meta["path"] for meta in obs.list(store).collect()]
+    assert paths == ["public/data.txt"]
+
+    result = obs.list_with_delimiter(store)
+    assert result["common_prefixes"] == ["public"]
+
+    assert obs.get(store, "public/data.txt").bytes() == b"bar"
+
+
+def test_max_object_size():
+    store = GuardrailStore(MemoryStore(), max_object_size=10)
+
+    obs.put(store, "small.txt", b"foo")
+
+    with pytest.raises(GenericError):
+        obs.put(store, "large.txt", b"a" * 11)
+
+    with pytest.raises(GenericError):
+        obs.put(store, "large.txt", b"a" * 100, use_multipart=True, chunk_size=8)
+
+
+def test_max_total_upload_bytes_per_hour():
+    store = GuardrailStore(MemoryStore(), max_total_upload_bytes_per_hour=10)
+
+    obs.put(store, "file1.txt", b"a" * 6)
+
+    with pytest.raises(GenericError):
+        obs.put(store, "file2.txt", b"a" * 6)
+
+    obs.put(store, "file3.txt", b"a" * 4)
+
+
+def test_repr():
+    store = GuardrailStore(MemoryStore())
+    assert repr(store).startswith("GuardrailStore")
+
+
+def test_authorize():
+    calls = []
+
+    def authorize(path: str, operation: str) -> bool:
+        calls.append((path, operation))
+        return path.startswith("tenant-a/")
+
+    store = GuardrailStore(MemoryStore(), authorize=authorize)
+
+    obs.put(store, "tenant-a/file.txt", b"foo")
+    assert obs.get(store, "tenant-a/file.txt").bytes() == b"foo"
+
+    with pytest.raises(PermissionDeniedError):
+        obs.put(store, "tenant-b/file.txt", b"foo")
+
+    with pytest.raises(PermissionDeniedError):
+        obs.list(store).collect()
+
+    assert ("tenant-a/file.txt", "put") in calls
+    assert ("tenant-a/file.txt", "get") in calls
+    assert ("tenant-b/file.txt", "put") in calls
+    assert ("", "list") in calls
+
+
+def test_authorize_copy():
+    def authorize(path: str, operation: str) -> bool:
+        return not (path.startswith("readonly/") and operation in ("put", "delete"))
+
+    memory_store = MemoryStore()
+    obs.put(memory_store, "readonly/file.txt", b"foo")
+    store = GuardrailStore(memory_store, authorize=authorize)
+
+    obs.copy(store, "readonly/file.txt", "scratch/file.txt")
+
+    with pytest.raises(PermissionDeniedError):
+        obs.rename(store, "readonly/file.txt", "scratch/other.txt")
+
+
+def test_read_only():
+    memory_store = MemoryStore()
+    obs.put(memory_store, "file.txt", b"foo")
+
+    store = GuardrailStore(memory_store, read_only=True)
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+    assert [meta["path"] for meta in obs.list(store).collect()] == ["file.txt"]
+
+    with pytest.raises(PermissionDeniedError):
+        obs.put(store, "other.txt", b"foo")
+
+    with pytest.raises(PermissionDeniedError):
+        obs.delete(store, "file.txt")
+
+    with pytest.raises(PermissionDeniedError):
+        obs.copy(store, "file.txt", "copy.txt")
+
+
+def test_open_public(http_server):
+    root, url = http_server
+    (root / "file.txt").write_bytes(b"foo")
+
+    store = obs.open_public(url)
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+
+    with pytest.raises(PermissionDeniedError):
+        obs.put(store, "file.txt", b"bar")
+
+
+def test_open_public_unsupported_scheme():
+    with pytest.raises(ValueError, match="gs://"):
+        obs.open_public("gs://bucket")
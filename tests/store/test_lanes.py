@@ -0,0 +1,30 @@
+import pytest
+
+import obstore as obs
+from obstore.store import LaneStore, MemoryStore
+
+
+def test_lane_store():
+    memory = MemoryStore()
+    store = LaneStore(memory, lane="interactive")
+    assert store.lane == "interactive"
+    assert store.max_concurrency == 64
+
+    obs.put(store, "file.txt", b"foo")
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+    assert [meta["path"] for meta in obs.list(store).collect()] == ["file.txt"]
+    assert store.in_flight == 0
+
+
+def test_lane_limit_is_shared():
+    batch = LaneStore(MemoryStore(), lane="batch")
+    other = LaneStore(MemoryStore(), lane="batch")
+    assert other.max_concurrency == batch.max_concurrency
+
+    with pytest.raises(ValueError, match="already has a max_concurrency"):
+        LaneStore(MemoryStore(), lane="batch", max_concurrency=batch.max_concurrency + 1)
+
+
+def test_unknown_lane():
+    with pytest.raises(ValueError, match="Unknown lane"):
+        LaneStore(MemoryStore(), lane="background")  # type: ignore
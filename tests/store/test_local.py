@@ -1,3 +1,4 @@
+import pickle
 from pathlib import Path
 
 import pytest
@@ -17,7 +18,35 @@ def test_local_store():
 
 def test_repr():
     store = LocalStore(HERE)
-    assert repr(store).startswith("LocalStore")
+    assert repr(store) == f'LocalStore(prefix="{HERE.absolute().resolve()}")'
+    assert repr(LocalStore()) == "LocalStore"
+
+
+def test_prefix_and_config(tmp_path):
+    prefix = tmp_path / "new" / "directory"
+    store = LocalStore(prefix, mkdir=True, automatic_cleanup=True)
+    assert prefix.is_dir()
+    assert store.prefix == prefix.resolve()
+    assert store.config == {"mkdir": True, "automatic_cleanup": True}
+    assert LocalStore().prefix is None
+
+    with pytest.raises(TypeError, match="Unknown LocalStore config key"):
+        LocalStore(tmp_path, config={"unknown": True})
+
+
+def test_pickle(tmp_path, monkeypatch):
+    cwd = Path.cwd()
+    monkeypatch.chdir(tmp_path)
+    (tmp_path / "dir").mkdir()
+    store = LocalStore("dir", automatic_cleanup=True)
+    obs.put(store, "file.txt", b"hello")
+
+    # Unpickled stores are rooted at the same directory in any working directory
+    monkeypatch.chdir(cwd)
+    restored = pickle.loads(pickle.dumps(store))
+    assert restored.prefix == (tmp_path / "dir").resolve()
+    assert restored.config == {"automatic_cleanup": True}
+    assert obs.get(restored, "file.txt").bytes() == b"hello"
 
 
 def test_local_from_url():
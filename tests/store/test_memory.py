@@ -0,0 +1,27 @@
+import pickle
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_construct_with_objects():
+    store = MemoryStore({"b.txt": b"b", "data/a.txt": bytearray(b"a")})
+    assert obs.get(store, "data/a.txt").bytes() == b"a"
+    assert store.to_dict() == {"b.txt": b"b", "data/a.txt": b"a"}
+    assert repr(store) == "MemoryStore"
+
+
+def test_to_dict_empty():
+    assert MemoryStore().to_dict() == {}
+
+
+def test_pickle():
+    store = MemoryStore()
+    obs.put(store, "data/file.txt", b"hello")
+
+    restored = pickle.loads(pickle.dumps(store))
+    assert obs.get(restored, "data/file.txt").bytes() == b"hello"
+
+    # The unpickled store is a copy
+    obs.put(restored, "other.txt", b"other")
+    assert store.to_dict() == {"data/file.txt": b"hello"}
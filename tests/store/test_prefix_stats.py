@@ -0,0 +1,50 @@
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore, PrefixStatsStore
+
+
+def test_hotspots():
+    store = PrefixStatsStore(MemoryStore(), depth=2)
+    for i in range(3):
+        obs.put(store, f"data/a/{i}.txt", b"foo")
+    obs.put(store, "data/b/0.txt", b"foo")
+    obs.get(store, "data/a/0.txt")
+
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "data/b/missing.txt")
+
+    hotspots = store.hotspots()
+    assert [h["prefix"] for h in hotspots] == ["data/a", "data/b"]
+    assert hotspots[0]["requests"] == 4
+    assert hotspots[0]["errors"] == 0
+    assert hotspots[1]["requests"] == 2
+    assert hotspots[1]["errors"] == 1
+    assert hotspots[0]["max_latency"] >= hotspots[0]["mean_latency"]
+
+    assert len(store.hotspots(limit=1)) == 1
+
+    store.reset()
+    assert store.hotspots() == []
+
+
+def test_sample_rate():
+    store = PrefixStatsStore(MemoryStore(), sample_rate=0.25)
+    for i in range(8):
+        obs.put(store, f"data/{i}.txt", b"foo")
+
+    assert store.hotspots()[0]["requests"] == 2
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError):
+        PrefixStatsStore(MemoryStore(), depth=0)
+    with pytest.raises(ValueError):
+        PrefixStatsStore(MemoryStore(), sample_rate=0)
+
+
+def test_repr():
+    store = PrefixStatsStore(MemoryStore())
+    assert repr(store).startswith("PrefixStatsStore")
+    assert store.depth == 1
+    assert store.sample_rate == 1.0
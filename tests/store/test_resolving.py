@@ -0,0 +1,66 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import GenericError
+from obstore.store import HTTPStore, MemoryStore, ResolvingStore
+
+NO_RETRY = {
+    "max_retries": 0,
+    "backoff": {
+        "base": 2,
+        "init_backoff": timedelta(milliseconds=10),
+        "max_backoff": timedelta(milliseconds=10),
+    },
+    "retry_timeout": timedelta(seconds=1),
+}
+
+UNREACHABLE = "http://127.0.0.1:1"
+
+
+def make_factory(memory: MemoryStore):
+    def factory(endpoint: str):
+        if endpoint == "memory":
+            return memory
+        return HTTPStore.from_url(endpoint, retry_config=NO_RETRY)
+
+    return factory
+
+
+def test_static_endpoints_rotate_on_connection_error():
+    memory = MemoryStore()
+    store = ResolvingStore(make_factory(memory), [UNREACHABLE, "memory"])
+    assert store.endpoint == UNREACHABLE
+
+    obs.put(store, "file.txt", b"foo")
+    assert store.endpoint == "memory"
+    assert obs.get(memory, "file.txt").bytes() == b"foo"
+
+
+def test_callback_endpoint():
+    memory = MemoryStore()
+    calls = []
+
+    def resolve():
+        calls.append(None)
+        return "memory"
+
+    store = ResolvingStore(make_factory(memory), resolve)
+    obs.put(store, "file.txt", b"foo")
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+    # Only resolved at construction, as no request failed to connect
+    assert len(calls) == 1
+
+
+def test_connection_error_after_re_resolving():
+    store = ResolvingStore(make_factory(MemoryStore()), [UNREACHABLE])
+    with pytest.raises(GenericError):
+        obs.put(store, "file.txt", b"foo")
+
+
+def test_invalid_endpoints():
+    with pytest.raises(ValueError):
+        ResolvingStore(make_factory(MemoryStore()), [])
+    with pytest.raises(ValueError):
+        ResolvingStore(make_factory(MemoryStore()), "memory")
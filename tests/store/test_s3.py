@@ -1,3 +1,5 @@
+from datetime import datetime, timedelta, timezone
+
 import pytest
 
 import obstore as obs
@@ -20,3 +22,139 @@ async def test_get_async(s3_store: S3Store):
 def test_construct_store_boolean_config():
     # Should allow boolean parameter
     S3Store("bucket", skip_signature=True)
+
+
+def test_force_path_style_conflicts():
+    with pytest.raises(ValueError, match="force_path_style"):
+        S3Store(
+            "bucket",
+            region="us-east-1",
+            force_path_style=True,
+            virtual_hosted_style_request=True,
+        )
+
+    with pytest.raises(ValueError, match="force_path_style"):
+        S3Store.from_url(
+            "https://bucket.s3.us-east-1.amazonaws.com",
+            force_path_style=True,
+        )
+
+    with pytest.raises(ValueError, match="force_path_style"):
+        S3Store(
+            "bucket",
+            endpoint="https://bucket.minio.example.com",
+            force_path_style=True,
+        )
+
+
+def test_force_path_style():
+    S3Store("bucket", region="us-east-1", force_path_style=True)
+    S3Store.from_url("s3://bucket", region="us-east-1", force_path_style=False)
+
+
+def test_region():
+    assert S3Store("bucket", region="eu-west-1").region == "eu-west-1"
+    assert S3Store("bucket", skip_signature=True).region == "us-east-1"
+
+
+def test_endpoint_introspection():
+    store = S3Store("bucket", region="eu-west-1")
+    assert store.endpoint_url == "https://s3.eu-west-1.amazonaws.com"
+    assert store.addressing_style == "path"
+    assert store.using_https
+
+    store = S3Store("bucket", region="eu-west-1", force_path_style=False)
+    assert store.endpoint_url == "https://bucket.s3.eu-west-1.amazonaws.com"
+    assert store.addressing_style == "virtual"
+
+    store = S3Store("bucket", endpoint="http://localhost:9000", allow_http=True)
+    assert store.endpoint_url == "http://localhost:9000"
+    assert store.region == "us-east-1"
+    assert not store.using_https
+
+    url = "https://bucket.s3.ap-south-1.amazonaws.com"
+    store = S3Store.from_url(url, region="eu-west-1")
+    assert store.region == "ap-south-1"
+    assert store.addressing_style == "virtual"
+
+    url = "https://account.r2.cloudflarestorage.com/bucket"
+    store = S3Store.from_url(url)
+    assert store.endpoint_url == "https://account.r2.cloudflarestorage.com"
+    assert store.region == "auto"
+
+
+def _provider_store(s3: str, credential_provider) -> S3Store:
+    return S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ALLOW_HTTP": "true",
+        },
+        credential_provider=credential_provider,
+    )
+
+
+def test_credential_provider(s3: str):
+    calls = []
+
+    def provider():
+        calls.append(None)
+        return {"access_key_id": "testing", "secret_access_key": "testing"}
+
+    store = _provider_store(s3, provider)
+    assert obs.get(store, "afile").bytes() == b"hello world"
+    assert obs.get(store, "afile").bytes() == b"hello world"
+    # Credentials without an expiry are fetched once
+    assert len(calls) == 1
+
+
+def test_credential_provider_refreshes_expiring_credentials(s3: str):
+    start = datetime(2025, 1, 1, tzinfo=timezone.utc)
+    obs.set_clock(start)
+    calls = []
+
+    def provider():
+        calls.append(None)
+        return {
+            "access_key_id": "testing",
+            "secret_access_key": "testing",
+            "token": "token",
+            "expires_at": start + timedelta(hours=1),
+        }
+
+    try:
+        store = _provider_store(s3, provider)
+        obs.head(store, "afile")
+        obs.advance_clock(timedelta(minutes=30))
+        obs.head(store, "afile")
+        assert len(calls) == 1
+
+        # Within five minutes of the expiry
+        obs.advance_clock(timedelta(minutes=26))
+        obs.head(store, "afile")
+        assert len(calls) == 2
+    finally:
+        obs.set_clock(None)
+
+
+@pytest.mark.asyncio
+async def test_async_credential_provider(s3: str):
+    async def provider():
+        return {"access_key_id": "testing", "secret_access_key": "testing"}
+
+    store = _provider_store(s3, provider)
+    resp = await obs.get_async(store, "afile")
+    assert await resp.bytes_async() == b"hello world"
+
+    # Sync operations run the provider with asyncio.run
+    assert obs.get(_provider_store(s3, provider), "afile").bytes() == b"hello world"
+
+
+def test_credential_provider_error(s3: str):
+    def provider():
+        raise RuntimeError("no credentials for you")
+
+    store = _provider_store(s3, provider)
+    with pytest.raises(Exception, match="no credentials for you"):
+        obs.head(store, "afile")
@@ -0,0 +1,68 @@
+import boto3
+import pytest
+
+import obstore as obs
+from obstore.exceptions import GenericError
+from obstore.store import SignedURLStore
+
+CLIENT_METHODS = {
+    "GET": "get_object",
+    "HEAD": "head_object",
+    "PUT": "put_object",
+    "DELETE": "delete_object",
+}
+
+
+@pytest.fixture()
+def signed_url_store(s3: str):
+    client = boto3.client(
+        "s3",
+        region_name="us-east-1",
+        endpoint_url=s3,
+        aws_access_key_id="testing",
+        aws_secret_access_key="testing",
+    )
+    signed = []
+
+    def sign(method: str, path: str) -> str:
+        signed.append((method, path))
+        return client.generate_presigned_url(
+            CLIENT_METHODS[method],
+            Params={"Bucket": "test", "Key": path},
+            ExpiresIn=60,
+        )
+
+    return SignedURLStore(sign), signed
+
+
+def test_get_put_delete(signed_url_store):
+    store, signed = signed_url_store
+
+    assert obs.get(store, "afile").bytes() == b"hello world"
+
+    obs.put(store, "file.txt", b"foobar")
+    assert obs.head(store, "file.txt")["size"] == 6
+    assert obs.get_range(store, "file.txt", start=1, end=3) == b"oo"
+
+    obs.delete(store, "file.txt")
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "file.txt")
+
+    assert ("PUT", "file.txt") in signed
+    assert ("DELETE", "file.txt") in signed
+
+
+def test_list_not_supported(signed_url_store):
+    store, _ = signed_url_store
+    with pytest.raises(NotImplementedError):
+        obs.list_with_delimiter(store)
+
+
+def test_sign_callback_error():
+    def sign(method: str, path: str) -> str:
+        raise ValueError("no signing service")
+
+    store = SignedURLStore(sign)
+    with pytest.raises(GenericError, match="SignCallback"):
+        obs.get(store, "file.txt")
+    assert repr(store) == "SignedURLStore"
@@ -0,0 +1,82 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore, TrashStore
+
+
+def test_delete_moves_to_trash():
+    memory_store = MemoryStore()
+    store = TrashStore(memory_store)
+
+    obs.put(store, "data/file.txt", b"foo")
+    obs.delete(store, "data/file.txt")
+
+    with pytest.raises(FileNotFoundError):
+        obs.get(store, "data/file.txt")
+
+    paths = [meta["path"] for meta in obs.list(memory_store).collect()]
+    assert len(paths) == 1
+    assert paths[0].startswith(".trash/")
+    assert paths[0].endswith("/data/file.txt")
+    assert obs.get(store, paths[0]).bytes() == b"foo"
+
+
+def test_trash_hidden_from_list():
+    store = TrashStore(MemoryStore(), trash_prefix="deleted")
+
+    obs.put(store, "a.txt", b"foo")
+    obs.put(store, "b.txt", b"bar")
+    obs.delete(store, "a.txt")
+
+    paths = [meta["path"] for meta in obs.list(store).collect()]
+    assert paths == ["b.txt"]
+
+    result = obs.list_with_delimiter(store)
+    assert result["common_prefixes"] == []
+    assert [meta["path"] for meta in result["objects"]] == ["b.txt"]
+
+    # Listing within the trash prefix shows deleted objects
+    trashed = [meta["path"] for meta in obs.list(store, "deleted").collect()]
+    assert len(trashed) == 1
+    assert trashed[0].endswith("/a.txt")
+
+
+def test_delete_within_trash_is_permanent():
+    memory_store = MemoryStore()
+    store = TrashStore(memory_store)
+
+    obs.put(store, "file.txt", b"foo")
+    obs.delete(store, "file.txt")
+    trashed = obs.list(store, ".trash").collect()[0]["path"]
+
+    obs.delete(store, trashed)
+    assert obs.list(memory_store).collect() == []
+
+
+def test_purge_trash():
+    memory_store = MemoryStore()
+    store = TrashStore(memory_store)
+
+    obs.put(store, "a.txt", b"foo")
+    obs.put(store, "b.txt", b"bar")
+    obs.delete(store, ["a.txt", "b.txt"])
+
+    assert obs.purge_trash(store, timedelta(days=1)) == 0
+    assert len(obs.list(memory_store).collect()) == 2
+
+    assert obs.purge_trash(store, timedelta(0)) == 2
+    assert obs.list(memory_store).collect() == []
+
+
+@pytest.mark.asyncio
+async def test_purge_trash_async():
+    memory_store = MemoryStore()
+    store = TrashStore(memory_store)
+
+    await obs.put_async(store, "file.txt", b"foo")
+    await obs.delete_async(store, "file.txt")
+
+    assert await obs.purge_trash_async(store, timedelta(0)) == 1
+    assert await obs.list(memory_store).collect_async() == []
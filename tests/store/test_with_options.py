@@ -0,0 +1,40 @@
+from datetime import timedelta
+
+import obstore as obs
+from obstore.store import HTTPStore, PrefixStore, S3Store
+
+
+def test_with_options_prefix():
+    store = HTTPStore.from_url("https://example.com")
+    assert isinstance(store.with_options(), HTTPStore)
+    prefixed = store.with_options(prefix="data")
+    assert isinstance(prefixed, PrefixStore)
+
+
+def test_with_options_prefix_requests(http_server):
+    root, url = http_server
+    (root / "data").mkdir()
+    (root / "data" / "file.txt").write_bytes(b"foo")
+
+    store = HTTPStore.from_url(url, client_options={"allow_http": True})
+    prefixed = store.with_options(prefix="data")
+    assert obs.get(prefixed, "file.txt").bytes() == b"foo"
+    assert obs.head(prefixed, "file.txt")["size"] == 3
+
+
+def test_with_options_retry_config():
+    store = S3Store("bucket", region="eu-west-1")
+    derived = store.with_options(
+        retry_config={
+            "max_retries": 20,
+            "backoff": {
+                "base": 2,
+                "init_backoff": timedelta(seconds=1),
+                "max_backoff": timedelta(minutes=1),
+            },
+            "retry_timeout": timedelta(minutes=30),
+        },
+    )
+    assert isinstance(derived, S3Store)
+    assert derived.region == "eu-west-1"
+    assert derived.url_for("file.txt") == store.url_for("file.txt")
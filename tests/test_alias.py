@@ -0,0 +1,96 @@
+import pytest
+
+import obstore as obs
+from obstore.exceptions import PreconditionError
+from obstore.store import MemoryStore
+
+
+def test_put_alias():
+    store = MemoryStore()
+    obs.put(store, "v1/data.bin", b"v1")
+    obs.put(store, "v2/data.bin", b"v2")
+
+    obs.put_alias(store, "latest/data.bin", "v1/data.bin")
+    assert obs.get(store, "latest/data.bin", resolve_aliases=True).bytes() == b"v1"
+
+    obs.put_alias(store, "latest/data.bin", "v2/data.bin")
+    assert obs.get(store, "latest/data.bin", resolve_aliases=True).bytes() == b"v2"
+    assert obs.resolve_alias(store, "latest/data.bin") == "v2/data.bin"
+
+    # Without resolution the alias object itself is returned
+    assert obs.get(store, "latest/data.bin").bytes() != b"v2"
+
+
+def test_nested_alias():
+    store = MemoryStore()
+    obs.put(store, "data.bin", b"foo")
+    obs.put_alias(store, "a", "data.bin")
+    obs.put_alias(store, "b", "a")
+
+    assert obs.resolve_alias(store, "b") == "data.bin"
+    assert obs.resolve_alias(store, "data.bin") == "data.bin"
+
+
+def test_alias_loop():
+    store = MemoryStore()
+    obs.put_alias(store, "a", "b")
+    obs.put_alias(store, "b", "a")
+
+    with pytest.raises(ValueError, match="loop"):
+        obs.get(store, "a", resolve_aliases=True)
+
+
+def test_alias_missing_target():
+    store = MemoryStore()
+    obs.put_alias(store, "a", "missing")
+
+    with pytest.raises(FileNotFoundError):
+        obs.get(store, "a", resolve_aliases=True)
+
+
+@pytest.mark.asyncio
+async def test_put_alias_async():
+    store = MemoryStore()
+    obs.put(store, "data.bin", b"foo")
+    await obs.put_alias_async(store, "alias", "data.bin")
+
+    resp = await obs.get_async(store, "alias", resolve_aliases=True)
+    assert await resp.bytes_async() == b"foo"
+    assert await obs.resolve_alias_async(store, "alias") == "data.bin"
+
+
+def test_set_pointer():
+    store = MemoryStore()
+
+    obs.set_pointer(store, "current", "v1")
+    assert obs.read_pointer(store, "current") == "v1"
+
+    obs.set_pointer(store, "current", "v2", expected_current="v1")
+    assert obs.read_pointer(store, "current") == "v2"
+
+    with pytest.raises(PreconditionError):
+        obs.set_pointer(store, "current", "v3", expected_current="v1")
+    assert obs.read_pointer(store, "current") == "v2"
+
+
+def test_set_pointer_missing():
+    store = MemoryStore()
+
+    with pytest.raises(PreconditionError):
+        obs.set_pointer(store, "current", "v1", expected_current="v0")
+
+
+def test_read_pointer_not_a_pointer():
+    store = MemoryStore()
+    obs.put(store, "file", b"foo")
+
+    with pytest.raises(ValueError):
+        obs.read_pointer(store, "file")
+
+
+def test_pointer_resolves_as_alias():
+    store = MemoryStore()
+    obs.put(store, "v1/data.bin", b"foo")
+    obs.set_pointer(store, "current", "v1/data.bin")
+
+    assert obs.get(store, "current", resolve_aliases=True).bytes() == b"foo"
@@ -0,0 +1,70 @@
+import asyncio
+import json
+
+import pytest
+
+import obstore as obs
+from obstore.background import drain, submit
+from obstore.store import MemoryStore
+
+
+def test_submit():
+    store = MemoryStore()
+
+    task = submit(lambda: obs.put_async(store, "file.txt", b"foo"))
+    task.result(timeout=10)
+    assert task.status() == "done"
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+
+
+def test_submit_coroutine():
+    store = MemoryStore()
+
+    async def upload():
+        await obs.put_async(store, "file.txt", b"foo")
+        return "uploaded"
+
+    task = submit(upload())
+    assert task.result(timeout=10) == "uploaded"
+
+
+def test_submit_marker():
+    store = MemoryStore()
+
+    task = submit(
+        lambda: obs.put_async(store, "file.txt", b"foo"),
+        marker=(store, "file.txt.done"),
+    )
+    task.result(timeout=10)
+    marker = json.loads(obs.get(store, "file.txt.done").bytes().to_bytes())
+    assert marker == {"status": "done", "error": None}
+
+
+def test_submit_failure_marker():
+    store = MemoryStore()
+
+    task = submit(
+        lambda: obs.get_async(store, "missing.txt"),
+        marker=(store, "missing.txt.done"),
+    )
+    with pytest.raises(FileNotFoundError):
+        task.result(timeout=10)
+    assert task.status() == "failed"
+
+    marker = json.loads(obs.get(store, "missing.txt.done").bytes().to_bytes())
+    assert marker["status"] == "failed"
+    assert marker["error"]
+
+
+def test_cancel():
+    store = MemoryStore()
+
+    async def slow():
+        await asyncio.sleep(60)
+        await obs.put_async(store, "file.txt", b"foo")
+
+    task = submit(slow(), marker=(store, "file.txt.done"))
+    assert task.cancel()
+    assert drain(timeout=10)
+    assert task.status() == "cancelled"
+    assert obs.list(store).collect() == []
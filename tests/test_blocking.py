@@ -0,0 +1,37 @@
+import asyncio
+
+import pytest
+
+import obstore as obs
+from obstore.blocking import Client
+from obstore.store import MemoryStore
+
+
+def test_client():
+    store = MemoryStore()
+    client = Client(store)
+
+    client.put("file.txt", b"foo")
+    assert client.get("file.txt").bytes() == b"foo"
+    assert client.get_range("file.txt", 0, 2) == b"fo"
+    assert client.head("file.txt")["size"] == 3
+
+    client.copy("file.txt", "copy.txt")
+    client.rename("copy.txt", "moved.txt")
+    assert [meta["path"] for meta in client.list()] == ["file.txt", "moved.txt"]
+
+    client.delete(["file.txt", "moved.txt"])
+    assert client.list_with_delimiter()["objects"] == []
+
+
+@pytest.mark.asyncio
+async def test_client_refuses_event_loop_thread():
+    store = MemoryStore()
+    await obs.put_async(store, "file.txt", b"foo")
+    client = Client(store)
+
+    with pytest.raises(RuntimeError, match="get_async"):
+        client.get("file.txt")
+
+    result = await asyncio.to_thread(client.get, "file.txt")
+    assert result.bytes() == b"foo"
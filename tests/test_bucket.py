@@ -0,0 +1,79 @@
+import pytest
+
+import obstore as obs
+from obstore.exceptions import NotSupportedError
+from obstore.store import AzureStore, S3Store
+
+
+@pytest.fixture()
+def store(s3: str):
+    # Bucket requests are signed with the store's credentials
+    return S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+
+
+def test_bucket_cors(store: S3Store):
+    assert obs.get_bucket_cors(store) == []
+
+    rule = {
+        "allowed_origins": ["https://example.com"],
+        "allowed_methods": ["GET", "PUT"],
+        "allowed_headers": ["*"],
+        "expose_headers": ["ETag"],
+        "max_age_seconds": 3600,
+    }
+    obs.put_bucket_cors(store, [rule])
+    assert obs.get_bucket_cors(store) == [rule]
+
+    obs.put_bucket_cors(store, [])
+    assert obs.get_bucket_cors(store) == []
+
+
+def test_lifecycle_rules(store: S3Store):
+    assert obs.get_lifecycle_rules(store) == []
+
+    rules = [
+        {
+            "id": "expire-logs",
+            "prefix": "logs/",
+            "expiration_days": 30,
+            "transitions": [{"days": 7, "storage_class": "GLACIER"}],
+        },
+        {"id": "cleanup", "enabled": False, "abort_incomplete_multipart_days": 7},
+    ]
+    obs.put_lifecycle_rules(store, rules)
+
+    result = obs.get_lifecycle_rules(store)
+    assert [rule["id"] for rule in result] == ["expire-logs", "cleanup"]
+    assert result[0]["prefix"] == "logs/"
+    assert result[0]["enabled"]
+    assert result[0]["expiration_days"] == 30
+    assert result[0]["transitions"] == [{"days": 7, "storage_class": "GLACIER"}]
+    assert not result[1]["enabled"]
+    assert result[1]["abort_incomplete_multipart_days"] == 7
+
+    obs.put_lifecycle_rules(store, [])
+    assert obs.get_lifecycle_rules(store) == []
+
+
+def test_azure_not_supported():
+    store = AzureStore("container", account_name="account")
+    with pytest.raises(NotSupportedError):
+        obs.get_bucket_cors(store)
+
+
+@pytest.mark.asyncio
+async def test_bucket_cors_async(store: S3Store):
+    rule = {"allowed_origins": ["*"], "allowed_methods": ["GET"]}
+    await obs.put_bucket_cors_async(store, [rule])
+    [result] = await obs.get_bucket_cors_async(store)
+    assert result["allowed_origins"] == ["*"]
+    assert result["allowed_methods"] == ["GET"]
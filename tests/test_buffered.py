@@ -1,6 +1,9 @@
+from datetime import datetime, timedelta, timezone
+
 import pytest
 
 import obstore as obs
+from obstore.exceptions import AlreadyExistsError
 from obstore.store import MemoryStore
 
 
@@ -49,3 +52,158 @@ async def test_readable_file_async():
 
     file = await obs.open_async(store, path)
     assert memoryview(data[:20]) == memoryview(await file.read(20))
+
+
+def test_readable_file_shared_cache():
+    store = MemoryStore()
+
+    data = bytes(range(256)) * (12 * 1024)
+    path = "shared.bin"
+    obs.put(store, path, data)
+
+    first = obs.open(store, path, shared_cache=True)
+    second = obs.open(store, path, shared_cache=True)
+    assert memoryview(data) == memoryview(first.read())
+
+    # Reads spanning a block boundary are assembled from the cached blocks
+    second.seek(1024 * 1024 - 10)
+    assert memoryview(data[1024 * 1024 - 10 : 1024 * 1024 + 10]) == memoryview(
+        second.read(20)
+    )
+
+
+def test_readable_file_shared_cache_new_version():
+    store = MemoryStore()
+
+    path = "shared.bin"
+    obs.put(store, path, b"a" * 4096)
+    assert obs.open(store, path, shared_cache=True).read().to_bytes() == b"a" * 4096
+
+    # A new version of the object has a different e-tag, so isn't read from the cache
+    obs.put(store, path, b"b" * 4096)
+    assert obs.open(store, path, shared_cache=True).read().to_bytes() == b"b" * 4096
+
+
+@pytest.mark.asyncio
+async def test_readable_file_shared_cache_async():
+    store = MemoryStore()
+
+    data = b"the quick brown fox jumps over the lazy dog\n" * 5000
+    path = "shared.txt"
+    await obs.put_async(store, path, data)
+
+    files = [await obs.open_async(store, path, shared_cache=True) for _ in range(4)]
+    for file in files:
+        assert memoryview(data) == memoryview(await file.read())
+
+
+def test_open_writer():
+    store = MemoryStore()
+
+    with obs.open_writer(store, "file.txt") as f:
+        assert f.write(b"foo") == 3
+        f.write(b"bar")
+        # Nothing is visible until the file is closed
+        with pytest.raises(FileNotFoundError):
+            obs.head(store, "file.txt")
+    assert f.closed
+    assert obs.get(store, "file.txt").bytes() == b"foobar"
+
+    with pytest.raises(ValueError, match="closed file"):
+        f.write(b"baz")
+
+
+def test_open_writer_multipart():
+    store = MemoryStore()
+
+    data = b"the quick brown fox jumps over the lazy dog," * 1000
+    with obs.open_writer(store, "big.txt", buffer_size=1024) as f:
+        for i in range(0, len(data), 100):
+            f.write(data[i : i + 100])
+    assert obs.get(store, "big.txt").bytes() == data
+
+
+def test_open_writer_aborts_on_exception():
+    store = MemoryStore()
+
+    with pytest.raises(RuntimeError):
+        with obs.open_writer(store, "file.txt") as f:
+            f.write(b"foo")
+            raise RuntimeError("oops")
+    assert f.closed
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "file.txt")
+
+
+@pytest.mark.asyncio
+async def test_open_writer_async():
+    store = MemoryStore()
+
+    async with await obs.open_writer_async(
+        store, "file.txt", attributes={"Content-Type": "text/plain"}
+    ) as f:
+        await f.write(b"foo")
+        await f.flush()
+    assert f.closed
+
+    result = await obs.get_async(store, "file.txt")
+    assert await result.bytes_async() == b"foo"
+    assert result.attributes["Content-Type"] == "text/plain"
+
+
+def test_open_writer_lease():
+    store = MemoryStore()
+
+    with obs.open_writer(store, "file.txt", lease=timedelta(minutes=1)) as f:
+        f.write(b"foo")
+        assert obs.head(store, "file.txt.lease")
+        # A second writer fails when opening the path, rather than when closing
+        with pytest.raises(AlreadyExistsError):
+            obs.open_writer(store, "file.txt", lease=timedelta(minutes=1))
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "file.txt.lease")
+
+    # Released once closed
+    with obs.open_writer(store, "file.txt", lease=timedelta(minutes=1)) as f:
+        f.write(b"bar")
+    assert obs.get(store, "file.txt").bytes() == b"bar"
+
+
+def test_open_writer_lease_released_on_abort():
+    store = MemoryStore()
+
+    with pytest.raises(RuntimeError):
+        with obs.open_writer(store, "file.txt", lease=timedelta(minutes=1)) as f:
+            raise RuntimeError("oops")
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "file.txt.lease")
+
+
+def test_open_writer_takes_over_expired_lease():
+    store = MemoryStore()
+    obs.set_clock(datetime(2025, 1, 1, tzinfo=timezone.utc))
+    try:
+        # Never closed, as if the writer crashed
+        stale = obs.open_writer(store, "file.txt", lease=timedelta(minutes=1))
+        stale.write(b"foo")
+        obs.advance_clock(timedelta(minutes=2))
+
+        with obs.open_writer(store, "file.txt", lease=timedelta(minutes=1)) as f:
+            f.write(b"bar")
+        assert obs.get(store, "file.txt").bytes() == b"bar"
+    finally:
+        obs.set_clock(None)
+    stale.abort()
+
+
+@pytest.mark.asyncio
+async def test_open_writer_lease_async():
+    store = MemoryStore()
+
+    f = await obs.open_writer_async(store, "file.txt", lease=timedelta(minutes=1))
+    with pytest.raises(AlreadyExistsError):
+        await obs.open_writer_async(store, "file.txt", lease=timedelta(minutes=1))
+    await f.close()
+    with pytest.raises(FileNotFoundError):
+        await obs.head_async(store, "file.txt.lease")
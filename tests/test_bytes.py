@@ -0,0 +1,49 @@
+import pytest
+
+from obstore import Bytes
+
+
+def test_split_at():
+    buffer = Bytes(b"hello world")
+    head, tail = buffer.split_at(5)
+    assert head == b"hello"
+    assert tail == b" world"
+
+    head, tail = buffer.split_at(-5)
+    assert head == b"hello "
+    assert tail == b"world"
+
+    with pytest.raises(IndexError):
+        buffer.split_at(12)
+
+
+def test_view():
+    buffer = Bytes(b"hello world")
+    assert buffer.view(6) == b"world"
+    assert buffer.view(0, 5) == b"hello"
+    assert buffer.view(-5, -1) == b"worl"
+    assert len(buffer.view(3, 3)) == 0
+
+    with pytest.raises(ValueError):
+        buffer.view(5, 2)
+    with pytest.raises(IndexError):
+        buffer.view(0, 20)
+
+
+def test_reader():
+    buffer = Bytes(b"first line\nsecond line\n")
+    with buffer.reader() as reader:
+        assert reader.readable()
+        assert reader.readline() == b"first line\n"
+        assert reader.tell() == 11
+        assert reader.read(6) == b"second"
+        assert reader.seek(-5, 2) == 18
+        assert reader.read() == b"line\n"
+        assert reader.read() == b""
+
+        reader.seek(0)
+        assert reader.readline(5) == b"first"
+
+    assert reader.closed
+    with pytest.raises(ValueError):
+        reader.read()
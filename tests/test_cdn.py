@@ -0,0 +1,116 @@
+import base64
+from datetime import timedelta
+from urllib.parse import parse_qs, urlsplit
+
+import boto3
+import pytest
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+import obstore as obs
+from obstore.store import S3Store
+
+
+
+@pytest.fixture()
+def private_key():
+    return rsa.generate_private_key(public_exponent=65537, key_size=2048)
+
+
+def pem(private_key) -> str:
+    return private_key.private_bytes(
+        serialization.Encoding.PEM,
+        serialization.PrivateFormat.PKCS8,
+        serialization.NoEncryption(),
+    ).decode()
+
+
+def test_sign_cdn_url(private_key):
+    url = obs.sign_cdn_url(
+        "https://d111111abcdef8.cloudfront.net",
+        "videos/intro.mp4",
+        timedelta(hours=1),
+        {"key_pair_id": "K2JCJMDEHXQW5F", "private_key": pem(private_key)},
+    )
+    parts = urlsplit(url)
+    assert parts.netloc == "d111111abcdef8.cloudfront.net"
+    assert parts.path == "/videos/intro.mp4"
+
+    query = parse_qs(parts.query)
+    assert query["Key-Pair-Id"] == ["K2JCJMDEHXQW5F"]
+    expires = query["Expires"][0]
+    policy = (
+        '{"Statement":[{"Resource":"https://d111111abcdef8.cloudfront.net/videos/'
+        'intro.mp4","Condition":{"DateLessThan":{"AWS:EpochTime":' + expires + "}}}]}"
+    )
+    signature = query["Signature"][0]
+    signature = signature.replace("-", "+").replace("_", "=").replace("~", "/")
+    private_key.public_key().verify(
+        base64.b64decode(signature),
+        policy.encode(),
+        padding.PKCS1v15(),
+        hashes.SHA1(),
+    )
+
+
+def test_sign_cdn_url_invalid_key():
+    with pytest.raises(ValueError, match="private_key"):
+        obs.sign_cdn_url(
+            "https://d111111abcdef8.cloudfront.net",
+            "a.txt",
+            timedelta(hours=1),
+            {"key_pair_id": "K2JCJMDEHXQW5F", "private_key": "not a key"},
+        )
+
+
+def test_invalidate(s3: str):
+    cloudfront = boto3.client(
+        "cloudfront",
+        region_name="us-east-1",
+        endpoint_url=s3,
+        aws_access_key_id="testing",
+        aws_secret_access_key="testing",
+    )
+    distribution = cloudfront.create_distribution(
+        DistributionConfig={
+            "CallerReference": "test",
+            "Origins": {
+                "Quantity": 1,
+                "Items": [
+                    {
+                        "Id": "origin",
+                        "DomainName": "test.s3.amazonaws.com",
+                        "S3OriginConfig": {"OriginAccessIdentity": ""},
+                    }
+                ],
+            },
+            "DefaultCacheBehavior": {
+                "TargetOriginId": "origin",
+                "ViewerProtocolPolicy": "allow-all",
+            },
+            "Comment": "",
+            "Enabled": True,
+        }
+    )
+    distribution_id = distribution["Distribution"]["Id"]
+
+    store = S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+    invalidation_id = obs.invalidate(
+        store, distribution_id, ["index.html", "/assets/*"], endpoint=s3
+    )
+    assert invalidation_id
+
+    invalidation = cloudfront.get_invalidation(
+        DistributionId=distribution_id, Id=invalidation_id
+    )
+    paths = invalidation["Invalidation"]["InvalidationBatch"]["Paths"]["Items"]
+    assert paths == ["/index.html", "/assets/*"]
@@ -0,0 +1,45 @@
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore, TrashStore
+
+START = datetime(2025, 1, 1, tzinfo=timezone.utc)
+
+
+@pytest.fixture(autouse=True)
+def virtual_clock():
+    obs.set_clock(START)
+    yield
+    # The clock and seed are shared by the whole process
+    obs.set_clock(None)
+    obs.seed_jitter(None)
+
+
+def test_purge_trash_with_virtual_clock():
+    store = TrashStore(MemoryStore())
+    obs.put(store, "file.txt", b"foo")
+    obs.delete(store, "file.txt")
+
+    assert obs.purge_trash(store, timedelta(days=7)) == 0
+    obs.advance_clock(timedelta(days=8))
+    assert obs.purge_trash(store, timedelta(days=7)) == 1
+
+
+def test_advance_clock_without_virtual_clock():
+    obs.set_clock(None)
+    with pytest.raises(RuntimeError, match="virtual clock"):
+        obs.advance_clock(timedelta(seconds=1))
+
+
+def test_manifest_report_uses_virtual_clock():
+    store = MemoryStore()
+    obs.put(
+        store,
+        "manifest.ndjson",
+        b'{"source": "missing.txt", "operation": "delete"}\n',
+    )
+    report = obs.run_manifest(store, "manifest.ndjson")
+    assert report["started_at"] == START.isoformat()
+
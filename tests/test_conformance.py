@@ -0,0 +1,58 @@
+import pytest
+
+import obstore as obs
+from obstore.conformance import SUITES, run
+from obstore.store import LocalStore, MemoryStore
+
+
+def test_check_store_conformance():
+    store = MemoryStore()
+    results = obs.check_store_conformance(store)
+    assert len(results) > 0
+    assert all(result["passed"] for result in results), results
+    assert all(result["error"] is None for result in results)
+
+    # The test objects are cleaned up
+    assert obs.list(store).collect() == []
+
+
+def test_check_store_conformance_local(tmp_path):
+    store = LocalStore(tmp_path)
+    results = obs.check_store_conformance(store, "checks")
+    assert all(result["passed"] for result in results), results
+
+
+@pytest.mark.asyncio
+async def test_check_store_conformance_async():
+    results = await obs.check_store_conformance_async(MemoryStore())
+    assert all(result["passed"] for result in results), results
+
+
+def test_run():
+    store = MemoryStore()
+    report = run(store)
+    assert report["passed"], report["checks"]
+    assert {check["suite"] for check in report["checks"]} == set(SUITES)
+
+    # The test objects are cleaned up
+    assert obs.list(store).collect() == []
+
+
+def test_run_suites():
+    report = run(MemoryStore(), suites=["list"])
+    assert len(report["checks"]) > 0
+    assert all(check["suite"] == "list" for check in report["checks"])
+
+
+def test_run_reports_deviations(tmp_path):
+    # Local stores don't support conditional updates
+    report = run(LocalStore(tmp_path), suites=["conditional"])
+    assert not report["passed"]
+    failed = [check for check in report["checks"] if not check["passed"]]
+    assert [check["name"] for check in failed] == ["update_version"]
+    assert failed[0]["error"] is not None
+
+
+def test_run_unknown_suite():
+    with pytest.raises(ValueError, match="Unknown suites"):
+        run(MemoryStore(), suites=["unknown"])  # type: ignore[list-item]
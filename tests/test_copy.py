@@ -0,0 +1,117 @@
+import pytest
+
+import obstore as obs
+from obstore.exceptions import AlreadyExistsError
+from obstore.store import MemoryStore, S3Store
+
+MiB = 1024 * 1024
+
+
+@pytest.fixture()
+def store(s3: str):
+    # Part copies are signed with the store's credentials
+    return S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+
+
+def test_copy_memory():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+    obs.copy(store, "a.txt", "b.txt")
+    assert obs.get(store, "b.txt").bytes() == b"foo"
+
+
+def test_multipart_copy(store: S3Store):
+    data = bytes(range(256)) * (48 * 1024)
+    obs.put(store, "big.bin", data)
+
+    parts = []
+    obs.copy(
+        store,
+        "big.bin",
+        "big-copy.bin",
+        multipart_threshold=5 * MiB,
+        part_size=5 * MiB,
+        max_concurrency=2,
+        on_part_complete=lambda index, size: parts.append((index, size)),
+    )
+    assert obs.get(store, "big-copy.bin").bytes() == data
+    assert sorted(parts) == [(0, 5 * MiB), (1, 5 * MiB), (2, 2 * MiB)]
+
+
+@pytest.mark.asyncio
+async def test_multipart_copy_async(store: S3Store):
+    data = b"x" * (6 * MiB)
+    await obs.put_async(store, "big.bin", data)
+    await obs.copy_async(
+        store,
+        "big.bin",
+        "big-copy.bin",
+        multipart_threshold=5 * MiB,
+        part_size=5 * MiB,
+    )
+    assert (await obs.get_async(store, "big-copy.bin")).bytes() == data
+
+
+def test_multipart_copy_callback_error(store: S3Store):
+    obs.put(store, "big.bin", b"x" * (6 * MiB))
+
+    def on_part_complete(index: int, size: int) -> None:
+        raise ValueError("stop")
+
+    with pytest.raises(ValueError, match="stop"):
+        obs.copy(
+            store,
+            "big.bin",
+            "big-copy.bin",
+            multipart_threshold=5 * MiB,
+            part_size=5 * MiB,
+            on_part_complete=on_part_complete,
+        )
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "big-copy.bin")
+
+
+def test_multipart_copy_invalid_part_size():
+    store = MemoryStore()
+    with pytest.raises(ValueError, match="part_size"):
+        obs.copy(store, "a", "b", part_size=1024)
+
+
+def test_copy_if_not_exists():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"a")
+    obs.put(store, "b.txt", b"b")
+
+    obs.copy(store, "a.txt", "c.txt", overwrite=False)
+    assert obs.get(store, "c.txt").bytes() == b"a"
+
+    with pytest.raises(AlreadyExistsError):
+        obs.copy(store, "a.txt", "b.txt", overwrite=False)
+    assert obs.get(store, "b.txt").bytes() == b"b"
+
+
+@pytest.mark.asyncio
+async def test_rename_if_not_exists_async():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"a")
+    obs.put(store, "b.txt", b"b")
+
+    with pytest.raises(AlreadyExistsError):
+        await obs.rename_async(store, "a.txt", "b.txt", overwrite=False)
+    # Neither object was touched
+    assert obs.get(store, "a.txt").bytes() == b"a"
+    assert obs.get(store, "b.txt").bytes() == b"b"
+
+    await obs.rename_async(store, "a.txt", "c.txt", overwrite=False)
+    assert obs.get(store, "c.txt").bytes() == b"a"
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "a.txt")
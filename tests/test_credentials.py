@@ -0,0 +1,99 @@
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+from obstore.credentials import KeyringCredentialCache
+
+
+class MemoryKeyring:
+    def __init__(self):
+        self.passwords = {}
+
+    def get_password(self, service, name):
+        return self.passwords.get((service, name))
+
+    def set_password(self, service, name, password):
+        self.passwords[(service, name)] = password
+
+    def delete_password(self, service, name):
+        del self.passwords[(service, name)]
+
+
+def credential(expires_in: timedelta | None):
+    expires_at = None
+    if expires_in is not None:
+        expires_at = datetime.now(timezone.utc) + expires_in
+    return {
+        "access_key_id": "key",
+        "secret_access_key": "secret",
+        "token": "token",
+        "expires_at": expires_at,
+    }
+
+
+def test_keyring_credential_cache():
+    backend = MemoryKeyring()
+    calls = []
+
+    def provider():
+        calls.append(None)
+        return credential(timedelta(hours=1))
+
+    first = KeyringCredentialCache(provider, name="role", backend=backend)
+    fetched = first()
+    assert len(calls) == 1
+
+    # A provider in another process reads the credentials from the keyring
+    second = KeyringCredentialCache(provider, name="role", backend=backend)
+    assert second() == fetched
+    assert len(calls) == 1
+
+    second.clear()
+    second()
+    assert len(calls) == 2
+
+
+def test_keyring_credential_cache_refreshes_expiring():
+    backend = MemoryKeyring()
+    calls = []
+
+    def provider():
+        calls.append(None)
+        return credential(timedelta(minutes=1))
+
+    cache = KeyringCredentialCache(provider, name="role", backend=backend)
+    cache()
+    cache()
+    assert len(calls) == 2
+
+
+def test_keyring_credential_cache_skips_long_lived():
+    backend = MemoryKeyring()
+    cache = KeyringCredentialCache(
+        lambda: credential(None), name="role", backend=backend
+    )
+    assert cache()["expires_at"] is None
+    assert backend.passwords == {}
+
+
+def test_keyring_credential_cache_ignores_unreadable():
+    backend = MemoryKeyring()
+    backend.set_password("obstore", "role", "not json")
+    cache = KeyringCredentialCache(
+        lambda: credential(timedelta(hours=1)), name="role", backend=backend
+    )
+    assert cache()["access_key_id"] == "key"
+    assert backend.get_password("obstore", "role") != "not json"
+
+
+@pytest.mark.asyncio
+async def test_keyring_credential_cache_async():
+    backend = MemoryKeyring()
+
+    async def provider():
+        return credential(timedelta(hours=1))
+
+    cache = KeyringCredentialCache(provider, name="role", backend=backend)
+    fetched = await cache()
+    # Cached credentials are returned without awaiting the provider
+    assert cache() == fetched
@@ -0,0 +1,75 @@
+import os
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+KiB = 1024
+
+
+def test_put_get_dedup():
+    store = MemoryStore()
+
+    data = os.urandom(256 * KiB)
+    result = obs.put_dedup(
+        store,
+        "file.bin",
+        data,
+        min_chunk_size=4 * KiB,
+        avg_chunk_size=16 * KiB,
+        max_chunk_size=64 * KiB,
+    )
+    assert result["size"] == len(data)
+    assert result["chunks"] > 1
+    assert result["new_bytes"] == len(data)
+
+    assert obs.get_dedup(store, "file.bin") == data
+
+
+def test_put_dedup_near_identical():
+    store = MemoryStore()
+    kwargs = {
+        "min_chunk_size": 4 * KiB,
+        "avg_chunk_size": 16 * KiB,
+        "max_chunk_size": 64 * KiB,
+    }
+
+    data = os.urandom(512 * KiB)
+    obs.put_dedup(store, "v1.bin", data, **kwargs)
+
+    # Insert a few bytes in the middle; content-defined chunking only re-uploads the
+    # chunks around the edit.
+    edited = data[: 200 * KiB] + b"hello" + data[200 * KiB :]
+    result = obs.put_dedup(store, "v2.bin", edited, **kwargs)
+    assert result["new_bytes"] < len(edited) // 2
+
+    assert obs.get_dedup(store, "v1.bin") == data
+    assert obs.get_dedup(store, "v2.bin") == edited
+
+    chunks = obs.list(store, ".chunks").collect()
+    assert sum(meta["size"] for meta in chunks) < len(data) + len(edited)
+
+
+def test_get_dedup_invalid_manifest():
+    store = MemoryStore()
+    obs.put(store, "file.bin", b"not a manifest")
+
+    with pytest.raises(ValueError):
+        obs.get_dedup(store, "file.bin")
+
+
+def test_put_dedup_invalid_chunk_sizes():
+    store = MemoryStore()
+
+    with pytest.raises(ValueError):
+        obs.put_dedup(store, "file.bin", b"foo", min_chunk_size=1024, avg_chunk_size=512)
+
+
+@pytest.mark.asyncio
+async def test_put_get_dedup_async():
+    store = MemoryStore()
+
+    data = os.urandom(64 * KiB)
+    await obs.put_dedup_async(store, "file.bin", data, min_chunk_size=4 * KiB)
+    assert await obs.get_dedup_async(store, "file.bin") == data
@@ -73,3 +73,98 @@ def test_delete_many_local_fs():
                 store,
                 ["file1.txt", "file2.txt", "file3.txt"],
             )
+
+
+def test_delete_many_local_fs_ignore_not_found(tmp_path):
+    store = LocalStore(tmp_path)
+
+    obs.put(store, "file1.txt", b"foo")
+    obs.delete(store, ["file1.txt", "missing.txt"], ignore_not_found=True)
+    assert obs.list(store).collect() == []
+
+    with pytest.raises(FileNotFoundError):
+        obs.delete(store, "missing.txt")
+    obs.delete(store, "missing.txt", ignore_not_found=True)
+
+
+def test_delete_many_local_fs_return_results(tmp_path):
+    store = LocalStore(tmp_path)
+
+    obs.put(store, "file1.txt", b"foo")
+    obs.put(store, "file3.txt", b"baz")
+
+    paths = ["file1.txt", "missing.txt", "file3.txt"]
+    results = obs.delete(store, paths, return_results=True)
+    assert results[0] is None
+    assert isinstance(results[1], FileNotFoundError)
+    assert results[2] is None
+    # The failure didn't stop the remaining deletes
+    assert obs.list(store).collect() == []
+
+    [result] = obs.delete(store, "file1.txt", return_results=True)
+    assert isinstance(result, FileNotFoundError)
+    results = obs.delete(
+        store,
+        ["missing.txt"],
+        ignore_not_found=True,
+        return_results=True,
+    )
+    assert results == [None]
+
+
+def test_delete_many_return_results_batches(tmp_path):
+    store = LocalStore(tmp_path)
+
+    # More than one batch of S3's bulk deletes, with a failure in the second
+    paths = [f"file{i}.txt" for i in range(1500)]
+    for path in paths[:1200] + paths[1201:]:
+        obs.put(store, path, b"foo")
+
+    results = obs.delete(store, paths, return_results=True)
+    assert len(results) == len(paths)
+    assert [i for i, result in enumerate(results) if result is not None] == [1200]
+    assert isinstance(results[1200], FileNotFoundError)
+
+
+@pytest.mark.asyncio
+async def test_delete_async_return_results():
+    store = MemoryStore()
+
+    obs.put(store, "file1.txt", b"foo")
+    results = await obs.delete_async(store, ["file1.txt"], return_results=True)
+    assert results == [None]
+    assert await obs.delete_async(store, ["file1.txt"]) is None
+
+
+def test_delete_prefix():
+    store = MemoryStore()
+
+    for i in range(2500):
+        obs.put(store, f"data/{i}.txt", b"foo")
+    obs.put(store, "database.txt", b"bar")
+
+    assert obs.delete_prefix(store, "data") == 2500
+    assert [meta["path"] for meta in obs.list(store).collect()] == ["database.txt"]
+    assert obs.delete_prefix(store, "data") == 0
+
+
+def test_delete_prefix_local_fs(tmp_path):
+    store = LocalStore(tmp_path)
+
+    obs.put(store, "dir/a.txt", b"foo")
+    obs.put(store, "dir/nested/b.txt", b"bar")
+    obs.put(store, "other.txt", b"baz")
+
+    assert obs.delete_prefix(store, "dir", ignore_not_found=True) == 2
+    assert [meta["path"] for meta in obs.list(store).collect()] == ["other.txt"]
+
+
+@pytest.mark.asyncio
+async def test_delete_prefix_async():
+    store = MemoryStore()
+
+    obs.put(store, "data/a.txt", b"foo")
+    obs.put(store, "data/b.txt", b"bar")
+
+    assert await obs.delete_prefix_async(store, "data", max_concurrency=1) == 2
+    assert obs.list(store).collect() == []
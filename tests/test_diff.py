@@ -0,0 +1,36 @@
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_diff_objects_identical():
+    store = MemoryStore()
+    obs.put(store, "a", b"0123456789" * 10)
+    obs.put(store, "b", b"0123456789" * 10)
+
+    assert obs.diff_objects(store, "a", store, "b", block_size=16) == []
+
+
+def test_diff_objects():
+    store_a = MemoryStore()
+    store_b = MemoryStore()
+
+    data = bytearray(b"\x00" * 100)
+    obs.put(store_a, "file", bytes(data))
+    data[20] = 1
+    data[33] = 1
+    data[80] = 1
+    obs.put(store_b, "file", bytes(data + b"extra"))
+
+    ranges = obs.diff_objects(store_a, "file", store_b, "file", block_size=16)
+    assert ranges == [(16, 48), (80, 105)]
+
+
+@pytest.mark.asyncio
+async def test_diff_objects_async():
+    store = MemoryStore()
+    obs.put(store, "a", b"foo")
+    obs.put(store, "b", b"bar")
+
+    assert await obs.diff_objects_async(store, "a", store, "b") == [(0, 3)]
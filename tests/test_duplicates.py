@@ -0,0 +1,51 @@
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def paths(duplicates):
+    return [[meta["path"] for meta in group] for group in duplicates]
+
+
+def test_find_duplicates_sha256():
+    store = MemoryStore()
+    obs.put(store, "data/a.txt", b"foo")
+    obs.put(store, "data/b.txt", b"bar")
+    obs.put(store, "data/c.txt", b"foo")
+    obs.put(store, "data/d.txt", b"longer")
+    obs.put(store, "other/e.txt", b"foo")
+
+    assert paths(obs.find_duplicates(store, by="sha256")) == [
+        ["data/a.txt", "data/c.txt", "other/e.txt"],
+    ]
+    assert paths(obs.find_duplicates(store, "data", by="sha256")) == [
+        ["data/a.txt", "data/c.txt"],
+    ]
+    assert paths(
+        obs.find_duplicates(store, "data", by="sha256", max_concurrency={"max": 2}),
+    ) == [["data/a.txt", "data/c.txt"]]
+
+
+def test_find_duplicates_etag():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+    obs.put(store, "b.txt", b"foo")
+
+    # Every put to a MemoryStore gets a new ETag
+    assert obs.find_duplicates(store) == []
+
+
+def test_find_duplicates_invalid_by():
+    with pytest.raises(ValueError):
+        obs.find_duplicates(MemoryStore(), by="md5")  # type: ignore
+
+
+@pytest.mark.asyncio
+async def test_find_duplicates_async():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+    obs.put(store, "b.txt", b"foo")
+
+    duplicates = await obs.find_duplicates_async(store, by="sha256")
+    assert paths(duplicates) == [["a.txt", "b.txt"]]
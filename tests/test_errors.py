@@ -0,0 +1,66 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import AlreadyExistsError, GenericError
+from obstore.store import MemoryStore, S3Store
+
+
+def test_not_found_attributes():
+    store = MemoryStore()
+
+    with pytest.raises(FileNotFoundError) as excinfo:
+        obs.get(store, "missing.txt")
+    err = excinfo.value
+    assert err.path == "missing.txt"
+    # Named by the store it was raised by, as object_store names it
+    assert err.store == "InMemory"
+    assert err.status_code is None
+    # The message is displayed rather than debug-printed
+    assert err.args == (str(err),)
+    assert "missing.txt" in str(err)
+    assert "NotFound {" not in str(err)
+
+
+def test_already_exists_attributes():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+
+    with pytest.raises(AlreadyExistsError) as excinfo:
+        obs.put(store, "file.txt", b"bar", mode="create")
+    assert excinfo.value.path == "file.txt"
+
+
+def test_status_code(s3_store):
+    with pytest.raises(FileNotFoundError) as excinfo:
+        obs.get(s3_store, "missing")
+    assert excinfo.value.path == "missing"
+    assert excinfo.value.store == "S3"
+    assert excinfo.value.status_code == 404
+
+
+def test_generic_error_store():
+    store = S3Store(
+        "bucket",
+        endpoint="http://127.0.0.1:1",
+        region="us-east-1",
+        skip_signature=True,
+        client_options={"allow_http": True},
+        retry_config={
+            "max_retries": 0,
+            "backoff": {
+                "base": 2,
+                "init_backoff": timedelta(milliseconds=1),
+                "max_backoff": timedelta(milliseconds=1),
+            },
+            "retry_timeout": timedelta(seconds=1),
+        },
+    )
+
+    # The request fails without a response
+    with pytest.raises(GenericError) as excinfo:
+        obs.head(store, "file.txt")
+    assert excinfo.value.store == "S3"
+    assert excinfo.value.path is None
+    assert excinfo.value.status_code is None
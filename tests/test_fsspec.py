@@ -120,3 +120,32 @@ def test_atomic_write(fs):
 def test_cat_ranges_error(fs):
     with pytest.raises(ValueError):
         fs.cat_ranges(["path"], [], [])
+
+
+def test_info_directory(fs):
+    fs.pipe_file("dir/nested/bfile", b"data")
+    assert fs.info("dir")["type"] == "directory"
+    assert fs.isdir("dir/nested")
+    assert fs.info("dir/nested/bfile")["type"] == "file"
+    with pytest.raises(FileNotFoundError):
+        fs.info("missing")
+
+
+def test_open_write(fs):
+    with fs.open("dir/written", "wb") as f:
+        f.write(b"foo")
+        f.write(b"bar")
+    assert fs.cat_file("dir/written") == b"foobar"
+
+
+def test_mv(fs):
+    fs.pipe_file("src", b"data")
+    fs.mv("src", "dst")
+    assert fs.cat_file("dst") == b"data"
+    assert not fs.exists("src")
+
+    fs.makedirs("newdir", exist_ok=True)
+    fs.pipe({"dir/a": b"a", "dir/b": b"b"})
+    fs.mv("dir", "moved", recursive=True)
+    assert fs.find("moved", detail=False) == ["moved/a", "moved/b"]
+    assert not fs.exists("dir")
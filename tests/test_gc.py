@@ -0,0 +1,54 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def make_store():
+    store = MemoryStore()
+    obs.put(store, "data/a.parquet", b"foo")
+    obs.put(store, "data/b.parquet", b"barbaz")
+    obs.put(store, "data/c.parquet", b"qux")
+    obs.put(store, "other/d.parquet", b"foo")
+    return store
+
+
+def test_gc_dry_run():
+    store = make_store()
+    report = obs.gc(store, "data", ["data/a.parquet", "data/missing.parquet"])
+    assert report == {
+        "unreferenced": ["data/b.parquet", "data/c.parquet"],
+        "unreferenced_bytes": 9,
+        "referenced": 1,
+        "missing": 1,
+        "skipped_recent": 0,
+        "deleted": 0,
+    }
+    assert len(obs.list(store).collect()) == 4
+
+
+def test_gc_delete():
+    store = make_store()
+    referenced = (f"data/{name}.parquet" for name in ["a"])
+    report = obs.gc(store, "data", referenced, dry_run=False)
+    assert report["deleted"] == 2
+    paths = sorted(meta["path"] for meta in obs.list(store).collect())
+    assert paths == ["data/a.parquet", "other/d.parquet"]
+
+
+def test_gc_min_age():
+    store = make_store()
+    report = obs.gc(store, "data", [], dry_run=False, min_age=timedelta(hours=1))
+    assert report["skipped_recent"] == 3
+    assert report["deleted"] == 0
+    assert len(obs.list(store).collect()) == 4
+
+
+@pytest.mark.asyncio
+async def test_gc_async():
+    store = make_store()
+    report = await obs.gc_async(store, "data", ["data/a.parquet"], dry_run=False)
+    assert report["deleted"] == 2
+    assert len(obs.list(store, "data").collect()) == 1
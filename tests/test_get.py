@@ -1,7 +1,8 @@
 import pytest
 
 import obstore as obs
-from obstore.store import MemoryStore
+from obstore.exceptions import InvalidRangeError, PreconditionError
+from obstore.store import LocalStore, MemoryStore
 
 
 def test_stream_sync():
@@ -47,6 +48,22 @@ async def test_stream_async():
     assert pos == len(data)
 
 
+def test_stream_min_chunk_size(tmp_path):
+    store = LocalStore(tmp_path)
+
+    data = b"the quick brown fox jumps over the lazy dog," * 5000
+    path = "big-data.txt"
+
+    obs.put(store, path, data)
+    resp = obs.get(store, path)
+    chunks = list(resp.stream(min_chunk_size=100 * 1024))
+
+    # The local store reads in chunks smaller than min_chunk_size, which get merged
+    assert all(isinstance(chunk, bytes) for chunk in chunks)
+    assert all(len(chunk) >= 100 * 1024 for chunk in chunks[:-1])
+    assert b"".join(chunks) == data
+
+
 def test_get_with_options():
     store = MemoryStore()
 
@@ -122,3 +139,128 @@ def test_get_ranges():
 
     for start, end, buffer in zip(starts, ends, buffers):
         assert memoryview(buffer) == data[start:end]
+
+
+def test_get_ranges_with_options():
+    store = MemoryStore()
+
+    data = b"the quick brown fox jumps over the lazy dog," * 100
+    path = "big-data.txt"
+
+    e_tag = obs.put(store, path, data)["e_tag"]
+    options = {"if_match": e_tag}
+    assert obs.get_range(store, path, 5, 15, options=options) == data[5:15]
+
+    starts = [5, 10, 2000, len(data) - 5]
+    ends = [15, 20, 2010, len(data) + 5]
+    buffers = obs.get_ranges(store, path, starts, ends, options=options)
+    for start, end, buffer in zip(starts, ends, buffers):
+        assert memoryview(buffer) == data[start:end]
+
+    obs.put(store, path, b"overwritten")
+    with pytest.raises(PreconditionError):
+        obs.get_range(store, path, 0, 5, options=options)
+    with pytest.raises(PreconditionError):
+        obs.get_ranges(store, path, [0], [5], options=options)
+
+
+def test_get_range_options_reject_range():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"data")
+
+    with pytest.raises(ValueError, match="range"):
+        obs.get_range(store, "file.txt", 0, 2, options={"range": (0, 1)})
+
+
+@pytest.mark.asyncio
+async def test_get_ranges_with_options_async():
+    store = MemoryStore()
+
+    data = b"the quick brown fox jumps over the lazy dog," * 100
+    path = "big-data.txt"
+
+    e_tag = (await obs.put_async(store, path, data))["e_tag"]
+    options = {"if_match": e_tag}
+    buffer = await obs.get_range_async(store, path, 5, 15, options=options)
+    assert buffer == data[5:15]
+    buffers = await obs.get_ranges_async(store, path, [0, 20], [10, 30], options=options)
+    assert [bytes(buffer) for buffer in buffers] == [data[0:10], data[20:30]]
+
+
+@pytest.mark.parametrize("local", [False, True])
+def test_get_range_edge_cases(local, tmp_path):
+    store = LocalStore(tmp_path) if local else MemoryStore()
+    obs.put(store, "small.txt", b"abc")
+    obs.put(store, "empty.txt", b"")
+
+    assert obs.get_range(store, "small.txt", 1, 1) == b""
+    assert obs.get_range(store, "small.txt", 1, 10) == b"bc"
+    assert obs.get_range(store, "small.txt", 3, 10) == b""
+    assert obs.get_range(store, "empty.txt", 0, 10) == b""
+    assert obs.get_ranges(store, "small.txt", [0, 1, 2], [1, 1, 10]) == [
+        b"a",
+        b"",
+        b"c",
+    ]
+
+    with pytest.raises(InvalidRangeError):
+        obs.get_range(store, "small.txt", 5, 10)
+    with pytest.raises(InvalidRangeError):
+        obs.get_range(store, "small.txt", 2, 1)
+
+
+def test_get_with_options_edge_cases():
+    store = MemoryStore()
+    obs.put(store, "small.txt", b"abc")
+    obs.put(store, "empty.txt", b"")
+
+    def get(path, range):
+        return obs.get(store, path, options={"range": range}).bytes()
+
+    assert get("small.txt", {"suffix": 10}) == b"abc"
+    assert get("small.txt", {"suffix": 0}) == b""
+    assert get("empty.txt", {"suffix": 10}) == b""
+    assert get("small.txt", {"offset": 3}) == b""
+    assert get("empty.txt", {"offset": 0}) == b""
+    assert get("small.txt", (2, 2)) == b""
+
+    with pytest.raises(InvalidRangeError):
+        get("small.txt", {"offset": 4})
+    with pytest.raises(InvalidRangeError):
+        get("small.txt", (2, 1))
+
+
+def test_get_many():
+    store = MemoryStore()
+    paths = [f"file{i}.txt" for i in range(20)]
+    for path in paths:
+        obs.put(store, path, path.encode())
+
+    results = dict(obs.get_many(store, paths, max_concurrency=4))
+    assert sorted(results) == sorted(paths)
+    for path, buffer in results.items():
+        assert buffer == path.encode()
+
+
+@pytest.mark.asyncio
+async def test_get_many_async():
+    store = MemoryStore()
+    paths = [f"file{i}.txt" for i in range(20)]
+    for path in paths:
+        obs.put(store, path, path.encode())
+
+    results = {path: buffer async for path, buffer in obs.get_many(store, paths)}
+    assert sorted(results) == sorted(paths)
+    for path, buffer in results.items():
+        assert buffer == path.encode()
+
+
+def test_get_many_missing():
+    store = MemoryStore()
+    obs.put(store, "exists.txt", b"data")
+
+    stream = obs.get_many(store, ["missing.txt"])
+    with pytest.raises(FileNotFoundError):
+        next(stream)
+    with pytest.raises(StopIteration):
+        next(stream)
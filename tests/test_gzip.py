@@ -0,0 +1,82 @@
+import gzip
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+CHUNKS = [bytes([i]) * 1000 for i in range(10)]
+DATA = b"".join(CHUNKS)
+
+
+def put_members(store: MemoryStore, path: str):
+    # One gzip member per chunk, as written by appending compressed chunks
+    obs.put(store, path, b"".join(gzip.compress(chunk) for chunk in CHUNKS))
+
+
+def test_create_and_read_index():
+    store = MemoryStore()
+    put_members(store, "log.gz")
+
+    index = obs.create_gzip_index(store, "log.gz")
+    assert len(index.members) == 10
+    assert index.members[0] == (0, 0)
+    assert [u for _, u in index.members] == [i * 1000 for i in range(10)]
+    assert index.uncompressed_size == len(DATA)
+
+    read = obs.read_gzip_index(store, "log.gz")
+    assert read.members == index.members
+    assert read.compressed_size == index.compressed_size
+    assert read.uncompressed_size == len(DATA)
+
+
+def test_get_gzip_range():
+    store = MemoryStore()
+    put_members(store, "log.gz")
+    obs.create_gzip_index(store, "log.gz")
+
+    for start, end in [(0, 10), (995, 1005), (2500, 7300), (9990, 20000)]:
+        assert obs.get_gzip_range(store, "log.gz", start, end) == DATA[start:end]
+
+
+def test_compressed_range():
+    store = MemoryStore()
+    put_members(store, "log.gz")
+    index = obs.create_gzip_index(store, "log.gz")
+
+    start, end = index.compressed_range(2500, 3500)
+    assert start == index.members[2][0]
+    assert end == index.members[4][0]
+    assert index.compressed_range(9500, 10000)[1] == index.compressed_size
+
+
+def test_single_member():
+    store = MemoryStore()
+    obs.put(store, "data.gz", gzip.compress(DATA))
+    index = obs.create_gzip_index(store, "data.gz", index_path="index/data.gzi")
+
+    assert index.members == [(0, 0)]
+    assert obs.get_gzip_range(store, "data.gz", 4000, 4010, index=index) == DATA[
+        4000:4010
+    ]
+    read = obs.read_gzip_index(store, "data.gz", index_path="index/data.gzi")
+    assert read.uncompressed_size == len(DATA)
+
+
+def test_missing_index():
+    store = MemoryStore()
+    put_members(store, "log.gz")
+    with pytest.raises(FileNotFoundError):
+        obs.get_gzip_range(store, "log.gz", 0, 10)
+
+
+@pytest.mark.asyncio
+async def test_get_gzip_range_async():
+    store = MemoryStore()
+    put_members(store, "log.gz")
+    await obs.create_gzip_index_async(store, "log.gz")
+
+    index = await obs.read_gzip_index_async(store, "log.gz")
+    assert await obs.get_gzip_range_async(store, "log.gz", 10, 2010, index=index) == (
+        DATA[10:2010]
+    )
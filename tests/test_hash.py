@@ -0,0 +1,23 @@
+import hashlib
+
+from obstore import Bytes
+from obstore.hash import crc32c, md5, xxh3
+
+
+def test_crc32c():
+    assert crc32c(b"123456789") == 0xE3069283
+    assert crc32c(memoryview(b"123456789")) == 0xE3069283
+    assert crc32c(b"6789", value=crc32c(b"12345")) == 0xE3069283
+    assert crc32c(b"") == 0
+
+
+def test_xxh3():
+    assert xxh3(b"") == 0x2D06800538D394C2
+    assert xxh3(b"foo") == xxh3(Bytes(b"foo"))
+    assert xxh3(b"foo") != xxh3(b"foo", seed=1)
+
+
+def test_md5():
+    data = b"hello world" * 1000
+    assert md5(data) == hashlib.md5(data).digest()
+    assert md5(Bytes(data)) == hashlib.md5(data).digest()
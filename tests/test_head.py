@@ -0,0 +1,42 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import GenericError
+from obstore.store import HTTPStore, MemoryStore
+
+
+def test_warm_up():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+
+    found = obs.warm_up(store, ["file.txt", "missing.txt"], connections=4)
+    assert list(found) == ["file.txt"]
+    assert found["file.txt"]["size"] == 3
+
+    assert obs.warm_up(store) == {}
+
+
+def test_warm_up_raises_store_errors():
+    retry_config = {
+        "max_retries": 0,
+        "backoff": {
+            "base": 2,
+            "init_backoff": timedelta(milliseconds=10),
+            "max_backoff": timedelta(milliseconds=10),
+        },
+        "retry_timeout": timedelta(seconds=1),
+    }
+    store = HTTPStore.from_url("http://127.0.0.1:1", retry_config=retry_config)
+    with pytest.raises(GenericError):
+        obs.warm_up(store)
+
+
+@pytest.mark.asyncio
+async def test_warm_up_async():
+    store = MemoryStore()
+    await obs.put_async(store, "file.txt", b"foo")
+
+    found = await obs.warm_up_async(store, ["file.txt"])
+    assert found["file.txt"]["path"] == "file.txt"
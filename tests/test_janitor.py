@@ -0,0 +1,114 @@
+import time
+from datetime import timedelta
+
+import boto3
+import pytest
+from botocore import UNSIGNED
+from botocore.client import Config
+
+import obstore as obs
+from obstore.exceptions import NotFoundError
+from obstore.store import S3Store
+
+
+@pytest.fixture
+def signed_s3_store(s3: str):
+    # ListMultipartUploads is a signed request, so the store needs credentials
+    return S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+
+
+def wait_for_sweep(janitor: obs.MultipartJanitor):
+    deadline = time.monotonic() + 10
+    while janitor.sweeps == 0:
+        assert time.monotonic() < deadline
+        time.sleep(0.05)
+
+
+def test_janitor_aborts_stale_uploads(signed_s3_store: S3Store):
+    stale = obs.create_multipart(signed_s3_store, "uploads/stale.bin")
+    other = obs.create_multipart(signed_s3_store, "other/file.bin")
+
+    janitor = obs.start_multipart_janitor(
+        signed_s3_store,
+        "uploads/",
+        older_than=timedelta(0),
+        interval=timedelta(hours=1),
+    )
+    wait_for_sweep(janitor)
+    assert janitor.running
+    janitor.stop()
+    assert not janitor.running
+
+    assert janitor.aborted == 1
+    assert janitor.last_error is None
+    with pytest.raises(NotFoundError):
+        obs.put_part(signed_s3_store, "uploads/stale.bin", stale, 0, b"foo")
+    # Outside of the prefix
+    obs.abort_multipart(signed_s3_store, "other/file.bin", other)
+
+
+def test_janitor_aborts_raw_keys(s3: str, signed_s3_store: S3Store):
+    client = boto3.client(
+        "s3",
+        config=Config(signature_version=UNSIGNED),
+        region_name="us-east-1",
+        endpoint_url=s3,
+    )
+    # Written by another client, with a key that isn't a normalized path
+    client.create_multipart_upload(Bucket="test", Key="raw//file.bin")
+
+    janitor = obs.start_multipart_janitor(
+        signed_s3_store,
+        "raw/",
+        older_than=timedelta(0),
+        interval=timedelta(hours=1),
+    )
+    wait_for_sweep(janitor)
+    janitor.stop()
+
+    assert janitor.aborted == 1
+    assert janitor.last_error is None
+    uploads = client.list_multipart_uploads(Bucket="test", Prefix="raw/")
+    assert uploads.get("Uploads", []) == []
+
+
+def test_janitor_keeps_recent_uploads(signed_s3_store: S3Store):
+    upload_id = obs.create_multipart(signed_s3_store, "uploads/recent.bin")
+
+    janitor = obs.start_multipart_janitor(
+        signed_s3_store,
+        None,
+        # moto reports every upload as initiated in 2010
+        older_than=timedelta(days=365 * 100),
+        interval=timedelta(hours=1),
+    )
+    wait_for_sweep(janitor)
+    janitor.stop()
+
+    assert janitor.aborted == 0
+    obs.abort_multipart(signed_s3_store, "uploads/recent.bin", upload_id)
+
+
+@pytest.mark.asyncio
+async def test_janitor_stop_async(signed_s3_store: S3Store):
+    janitor = obs.start_multipart_janitor(
+        signed_s3_store, None, timedelta(days=1), timedelta(hours=1)
+    )
+    await janitor.stop_async()
+    assert not janitor.running
+    # Stopping again does nothing
+    janitor.stop()
+
+
+def test_janitor_interval(signed_s3_store: S3Store):
+    with pytest.raises(ValueError):
+        obs.start_multipart_janitor(signed_s3_store, None, timedelta(0), timedelta(0))
@@ -0,0 +1,96 @@
+import json
+
+import boto3
+import pytest
+from botocore import UNSIGNED
+from botocore.client import Config
+
+import obstore as obs
+from obstore.store import MemoryStore, S3Store
+from tests.conftest import TEST_BUCKET_NAME
+
+
+@pytest.fixture
+def journal(tmp_path):
+    path = tmp_path / "uploads.journal"
+    obs.set_journal(path)
+    yield path
+    obs.set_journal(None)
+
+
+def incomplete_uploads(s3: str) -> list:
+    client = boto3.client(
+        "s3",
+        config=Config(signature_version=UNSIGNED),
+        region_name="us-east-1",
+        endpoint_url=s3,
+    )
+    uploads = client.list_multipart_uploads(Bucket=TEST_BUCKET_NAME)
+    return [upload["Key"] for upload in uploads.get("Uploads", [])]
+
+
+def test_recover_aborts_incomplete_uploads(s3: str, s3_store: S3Store, journal):
+    finished = obs.open_multipart_writer(s3_store, "finished.bin")
+    finished.write(b"foo")
+    finished.finish()
+
+    # Left incomplete, as if the process had crashed
+    obs.open_multipart_writer(s3_store, "interrupted.bin").write(b"bar")
+    assert incomplete_uploads(s3) == ["interrupted.bin"]
+
+    records = [json.loads(line) for line in journal.read_text().splitlines()]
+    assert [(r["event"], r["path"]) for r in records] == [
+        ("begin", "finished.bin"),
+        ("end", "finished.bin"),
+        ("begin", "interrupted.bin"),
+    ]
+
+    obs.set_journal(None)
+    assert obs.recover(journal, s3_store) == ["interrupted.bin"]
+    assert incomplete_uploads(s3) == []
+
+    # Recovered uploads are marked as ended
+    assert obs.recover(journal, s3_store) == []
+
+
+def test_uploads_to_other_stores_are_not_journaled(journal):
+    store = MemoryStore()
+    writer = obs.open_multipart_writer(store, "file.bin")
+    writer.write(b"foo")
+    writer.finish()
+    assert journal.read_text() == ""
+
+    with pytest.raises(ValueError, match="recovered"):
+        obs.recover(journal, store)
+
+
+def test_put_and_copy_are_journaled(s3: str, journal):
+    # Part copies are signed with the store's credentials
+    store = S3Store.from_url(
+        f"s3://{TEST_BUCKET_NAME}/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+    data = b"x" * (6 * 1024 * 1024)
+    obs.put(store, "put.bin", data, use_multipart=True)
+    obs.copy(
+        store,
+        "put.bin",
+        "copy.bin",
+        multipart_threshold=5 * 1024 * 1024,
+        part_size=5 * 1024 * 1024,
+    )
+
+    records = [json.loads(line) for line in journal.read_text().splitlines()]
+    assert [(r["event"], r["path"]) for r in records] == [
+        ("begin", "put.bin"),
+        ("end", "put.bin"),
+        ("begin", "copy.bin"),
+        ("end", "copy.bin"),
+    ]
+    assert {r["store"] for r in records} == {f"s3://{TEST_BUCKET_NAME}"}
@@ -1,8 +1,10 @@
+import json
+
 import pytest
 from arro3.core import RecordBatch
 
 import obstore as obs
-from obstore.store import MemoryStore
+from obstore.store import MemoryStore, S3Store
 
 
 def test_list():
@@ -17,6 +19,53 @@ def test_list():
     assert len(result) == 3
 
 
+
+def test_list_shares_equal_values(s3_store: S3Store):
+    obs.put(s3_store, "shared/file1.txt", b"foo")
+    obs.put(s3_store, "shared/file2.txt", b"foo")
+
+    objects = obs.list(s3_store, "shared/").collect()
+    assert objects[0]["e_tag"] == objects[1]["e_tag"]
+    # Objects with the same contents share a Python object for their e-tag
+    assert objects[0]["e_tag"] is objects[1]["e_tag"]
+
+    objects = obs.list_with_delimiter(s3_store, "shared/")["objects"]
+    assert objects[0]["e_tag"] is objects[1]["e_tag"]
+
+
+def test_list_collect_limit():
+    store = MemoryStore()
+    for i in range(5):
+        obs.put(store, f"file{i}.txt", b"foo")
+
+    stream = obs.list(store)
+    assert len(stream.collect(limit=2)) == 2
+
+    # The rest of the listing is still available
+    assert len(stream.collect()) == 3
+
+
+def test_list_collect_timeout():
+    store = MemoryStore()
+    obs.put(store, "file1.txt", b"foo")
+    obs.put(store, "file2.txt", b"bar")
+
+    assert len(obs.list(store).collect(timeout=10, partial=False)) == 2
+
+    with pytest.raises(ValueError):
+        obs.list(store).collect(timeout=-1)
+
+
+@pytest.mark.asyncio
+async def test_list_collect_limit_async():
+    store = MemoryStore()
+    for i in range(5):
+        await obs.put_async(store, f"file{i}.txt", b"foo")
+
+    stream = obs.list(store)
+    assert len(await stream.collect_async(limit=4, timeout=10)) == 4
+    assert len(await stream.collect_async()) == 1
+
 def test_list_as_arrow():
     store = MemoryStore()
 
@@ -38,6 +87,31 @@ def test_list_as_arrow():
     assert batch.num_rows == 100
 
 
+def test_list_with_delimiter_as_arrow():
+    store = MemoryStore()
+
+    for i in range(10):
+        obs.put(store, f"file{i}.txt", b"foo")
+    obs.put(store, "dir/nested.txt", b"foo")
+
+    result = obs.list_with_delimiter(store, return_arrow=True)
+    assert result["common_prefixes"] == ["dir"]
+    batch = result["objects"]
+    assert isinstance(batch, RecordBatch)
+    assert batch.num_rows == 10
+    assert batch.schema.names == ["path", "last_modified", "size", "e_tag", "version"]
+
+
+@pytest.mark.asyncio
+async def test_list_with_delimiter_as_arrow_async():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+
+    result = await obs.list_with_delimiter_async(store, return_arrow=True)
+    assert isinstance(result["objects"], RecordBatch)
+    assert result["objects"].num_rows == 1
+
+
 @pytest.mark.asyncio
 async def test_list_stream_async():
     store = MemoryStore()
@@ -58,3 +132,135 @@ async def test_list_stream_async():
     batch = await stream.collect_async()
     assert isinstance(batch, RecordBatch)
     assert batch.num_rows == 100
+
+
+def test_list_collect_dataframe_pandas():
+    pd = pytest.importorskip("pandas")
+
+    store = MemoryStore()
+    for i in range(10):
+        obs.put(store, f"file{i}.txt", b"foo")
+
+    df = obs.list(store).collect_dataframe(engine="pandas")
+    assert isinstance(df, pd.DataFrame)
+    assert len(df) == 10
+    assert list(df.columns) == ["path", "last_modified", "size", "e_tag", "version"]
+    assert (df["size"] == 3).all()
+
+
+def test_list_collect_dataframe_polars():
+    pl = pytest.importorskip("polars")
+
+    store = MemoryStore()
+    for i in range(10):
+        obs.put(store, f"file{i}.txt", b"foo")
+
+    df = obs.list(store).collect_dataframe()
+    assert isinstance(df, pl.DataFrame)
+    assert df.height == 10
+
+
+def test_list_collect_dataframe_invalid_engine():
+    store = MemoryStore()
+
+    with pytest.raises(ValueError):
+        obs.list(store).collect_dataframe(engine="spark")  # type: ignore
+
+
+@pytest.mark.asyncio
+async def test_list_collect_dataframe_async():
+    pytest.importorskip("pandas")
+
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+
+    df = await obs.list(store).collect_dataframe_async(engine="pandas")
+    assert len(df) == 1
+
+
+def test_list_to_ndjson(tmp_path):
+    store = MemoryStore()
+    for i in range(5):
+        obs.put(store, f"data/file{i}.txt", b"foo")
+    obs.put(store, "other/file.txt", b"foo")
+
+    dest = tmp_path / "inventory.ndjson"
+    assert obs.list_to_ndjson(store, "data", dest) == 5
+
+    records = [json.loads(line) for line in dest.read_text().splitlines()]
+    assert sorted(record["path"] for record in records) == [
+        f"data/file{i}.txt" for i in range(5)
+    ]
+    assert all(record["size"] == 3 for record in records)
+
+
+def test_list_to_ndjson_dest_store():
+    store = MemoryStore()
+    dest_store = MemoryStore()
+    obs.put(store, "file1.txt", b"foo")
+    obs.put(store, "file2.txt", b"bar")
+
+    assert obs.list_to_ndjson(store, None, "inventory.ndjson", dest_store=dest_store) == 2
+
+    lines = obs.get(dest_store, "inventory.ndjson").bytes().to_bytes().splitlines()
+    assert len(lines) == 2
+    assert json.loads(lines[0])["path"] in ("file1.txt", "file2.txt")
+
+
+def test_list_to_ndjson_dest_store_local_path(tmp_path):
+    store = MemoryStore()
+
+    with pytest.raises(TypeError, match="dest_store"):
+        obs.list_to_ndjson(
+            store, None, tmp_path / "inventory.ndjson", dest_store=MemoryStore()
+        )
+
+
+@pytest.mark.asyncio
+async def test_list_to_ndjson_async(tmp_path):
+    store = MemoryStore()
+    for i in range(3):
+        await obs.put_async(store, f"file{i}.txt", b"foo")
+
+    dest = tmp_path / "inventory.ndjson"
+    assert await obs.list_to_ndjson_async(store, None, dest) == 3
+    assert len(dest.read_text().splitlines()) == 3
+
+
+def test_total_size():
+    store = MemoryStore()
+    obs.put(store, "top.txt", b"foo")
+    obs.put(store, "data/a/file1.txt", b"foobar")
+    obs.put(store, "data/b/file2.txt", b"x" * 10)
+    obs.put(store, "data/file3.txt", b"baz")
+
+    assert obs.total_size(store) == {"count": 4, "size": 22}
+    assert obs.total_size(store, "data", max_concurrency=1) == {"count": 3, "size": 19}
+    assert obs.total_size(store, "missing") == {"count": 0, "size": 0}
+
+
+def test_total_size_adaptive_concurrency():
+    store = MemoryStore()
+    for i in range(20):
+        obs.put(store, f"data/{i}/file.txt", b"foo")
+
+    assert obs.total_size(store, "data", max_concurrency={}) == {
+        "count": 20,
+        "size": 60,
+    }
+    assert obs.total_size(
+        store,
+        "data",
+        max_concurrency={"initial": 1, "min": 1, "max": 2},
+    ) == {"count": 20, "size": 60}
+
+    with pytest.raises(ValueError):
+        obs.total_size(store, max_concurrency={"initial": 8, "max": 4})
+
+
+@pytest.mark.asyncio
+async def test_total_size_async():
+    store = MemoryStore()
+    obs.put(store, "data/a/file1.txt", b"foobar")
+
+    assert await obs.total_size_async(store, "data") == {"count": 1, "size": 6}
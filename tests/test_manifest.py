@@ -0,0 +1,93 @@
+import json
+
+import pyarrow as pa
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_run_csv_manifest():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"a")
+    obs.put(store, "b.txt", b"b")
+    obs.put(store, "c.txt", b"c")
+    manifest = "source,destination,operation\na.txt,copied/a.txt,\nb.txt,moved/b.txt,move\nc.txt,,delete\n"
+    obs.put(store, "job.csv", manifest.encode())
+
+    report = obs.run_manifest(store, "job.csv")
+    assert report["total"] == 3
+    assert report["succeeded"] == 3
+    assert report["failed"] == 0
+
+    assert obs.get(store, "copied/a.txt").bytes() == b"a"
+    assert obs.get(store, "moved/b.txt").bytes() == b"b"
+    paths = {meta["path"] for meta in obs.list(store).collect()}
+    assert "b.txt" not in paths
+    assert "c.txt" not in paths
+
+    written = json.loads(bytes(obs.get(store, "job.csv.report.json").bytes()))
+    assert written["succeeded"] == 3
+
+
+def test_run_ndjson_manifest_between_stores():
+    source = MemoryStore()
+    destination = MemoryStore()
+    obs.put(source, "a.txt", b"a")
+    obs.put(destination, "b.txt", b"existing")
+    obs.put(source, "b.txt", b"b")
+    lines = [
+        {"source": "a.txt"},
+        {"source": "b.txt", "if_not_exists": True},
+        {"source": "missing.txt"},
+    ]
+    obs.put(source, "job.ndjson", "\n".join(json.dumps(line) for line in lines).encode())
+
+    report = obs.run_manifest(source, "job.ndjson", destination_store=destination)
+    assert report["succeeded"] == 1
+    assert report["skipped"] == 1
+    assert report["failed"] == 1
+    assert report["errors"][0]["row"] == 2
+    assert report["errors"][0]["source"] == "missing.txt"
+
+    assert obs.get(destination, "a.txt").bytes() == b"a"
+    assert obs.get(destination, "b.txt").bytes() == b"existing"
+
+
+def test_resume_from_checkpoint():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"a")
+    table = pa.table({"source": ["a.txt", "b.txt"], "destination": ["x/a", "x/b"]})
+    sink = pa.BufferOutputStream()
+    with pa.ipc.new_file(sink, table.schema) as writer:
+        writer.write_table(table)
+    obs.put(store, "job.arrow", sink.getvalue().to_pybytes())
+
+    report = obs.run_manifest(store, "job.arrow", checkpoint="job.checkpoint")
+    assert (report["succeeded"], report["failed"], report["resumed"]) == (1, 1, 0)
+
+    # Only the failed row is run again
+    obs.put(store, "b.txt", b"b")
+    obs.delete(store, "x/a")
+    report = obs.run_manifest(store, "job.arrow", checkpoint="job.checkpoint")
+    assert (report["succeeded"], report["failed"], report["resumed"]) == (1, 0, 1)
+    assert obs.get(store, "x/b").bytes() == b"b"
+    with pytest.raises(FileNotFoundError):
+        obs.get(store, "x/a")
+
+
+@pytest.mark.asyncio
+async def test_run_manifest_async():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"a")
+    obs.put(store, "job.ndjson", b'{"source": "a.txt", "destination": "b.txt"}\n')
+    report = await obs.run_manifest_async(store, "job.ndjson", report="report.json")
+    assert report["succeeded"] == 1
+    assert obs.get(store, "report.json").bytes()
+
+
+def test_invalid_manifest():
+    store = MemoryStore()
+    obs.put(store, "job.csv", b"path\na.txt\n")
+    with pytest.raises(ValueError, match="source"):
+        obs.run_manifest(store, "job.csv")
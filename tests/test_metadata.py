@@ -0,0 +1,59 @@
+import pyarrow as pa
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_apply_metadata():
+    store = MemoryStore()
+    obs.put(store, "a.html", b"<html>", attributes={"Content-Disposition": "inline"})
+    obs.put(store, "b.js", b"foo()")
+
+    updates = pa.table(
+        {
+            "path": ["a.html", "b.js"],
+            "content_type": ["text/html", "text/javascript"],
+            "cache_control": [None, "max-age=60"],
+            "metadata:origin": ["migration", "migration"],
+        }
+    )
+    assert obs.apply_metadata(store, updates) == 2
+
+    a = obs.get(store, "a.html")
+    assert a.attributes == {
+        "Content-Disposition": "inline",
+        "Content-Type": "text/html",
+        "origin": "migration",
+    }
+    assert a.bytes() == b"<html>"
+    assert obs.get(store, "b.js").attributes == {
+        "Content-Type": "text/javascript",
+        "Cache-Control": "max-age=60",
+        "origin": "migration",
+    }
+
+
+def test_apply_metadata_invalid_columns():
+    store = MemoryStore()
+    with pytest.raises(ValueError, match="path"):
+        obs.apply_metadata(store, pa.table({"content_type": ["text/plain"]}))
+    with pytest.raises(ValueError, match="Unexpected column"):
+        obs.apply_metadata(store, pa.table({"path": ["a"], "colour": ["red"]}))
+
+
+def test_apply_metadata_missing_object():
+    store = MemoryStore()
+    updates = pa.table({"path": ["missing"], "content_type": ["text/plain"]})
+    with pytest.raises(FileNotFoundError):
+        obs.apply_metadata(store, updates)
+
+
+@pytest.mark.asyncio
+async def test_apply_metadata_async():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+
+    updates = pa.table({"path": ["a.txt"], "content_type": ["text/plain"]})
+    assert await obs.apply_metadata_async(store, updates) == 1
+    assert obs.get(store, "a.txt").attributes == {"Content-Type": "text/plain"}
@@ -0,0 +1,98 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+import obstore.metrics
+from obstore.exceptions import NotFoundError
+from obstore.store import MemoryStore, PrefixStore
+
+
+@pytest.fixture
+def metrics():
+    obstore.metrics.reset()
+    obstore.metrics.enable()
+    yield
+    # Metrics are shared by the whole process
+    obstore.metrics.disable()
+    obstore.metrics.reset()
+
+
+def test_disabled_by_default():
+    assert not obstore.metrics.is_enabled()
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+    assert obstore.metrics.snapshot()["put"]["count"] == 0
+
+
+def test_put_and_get(metrics):
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foobar")
+
+    result = obs.get(store, "file.txt")
+    # Bytes are counted as the response is read
+    assert obstore.metrics.snapshot()["get"]["bytes"] == 0
+    assert result.bytes() == b"foobar"
+
+    snapshot = obstore.metrics.snapshot()
+    assert snapshot["put"]["count"] == 1
+    assert snapshot["put"]["bytes"] == 6
+    assert snapshot["get"]["count"] == 1
+    assert snapshot["get"]["bytes"] == 6
+    assert snapshot["get"]["errors"] == 0
+    assert snapshot["head"]["count"] == 0
+
+
+def test_errors(metrics):
+    store = MemoryStore()
+    with pytest.raises(NotFoundError):
+        obs.head(store, "missing.txt")
+
+    head = obstore.metrics.snapshot()["head"]
+    assert head["count"] == 1
+    assert head["errors"] == 1
+
+
+def test_list_and_delete(metrics):
+    store = MemoryStore()
+    obs.put(store, "a/1.txt", b"foo")
+    obs.put(store, "a/2.txt", b"foo")
+
+    assert len(obs.list(store, "a").collect()) == 2
+    # Empty listings are recorded once they end
+    assert obs.list(store, "b").collect() == []
+    obs.delete(store, ["a/1.txt", "a/2.txt"])
+
+    snapshot = obstore.metrics.snapshot()
+    assert snapshot["list"]["count"] == 2
+    assert snapshot["delete"]["count"] == 2
+
+
+def test_wrapped_store_recorded_once(metrics):
+    store = PrefixStore(MemoryStore(), "prefix")
+    obs.put(store, "file.txt", b"foo")
+    assert obstore.metrics.snapshot()["put"]["count"] == 1
+
+
+def test_latency_histogram(metrics):
+    store = MemoryStore()
+    for i in range(5):
+        obs.put(store, f"{i}.txt", b"foo")
+
+    put = obstore.metrics.snapshot()["put"]
+    histogram = put["latency_histogram"]
+    assert sum(count for _, count in histogram) == 5
+    assert histogram[0][0] == timedelta(milliseconds=1)
+    assert histogram[-1][0] is None
+    assert put["max_latency"] <= put["total_latency"]
+
+
+def test_disable_keeps_metrics(metrics):
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+    obstore.metrics.disable()
+    obs.put(store, "file.txt", b"foo")
+    assert obstore.metrics.snapshot()["put"]["count"] == 1
+
+    obstore.metrics.reset()
+    assert obstore.metrics.snapshot()["put"]["count"] == 0
@@ -0,0 +1,142 @@
+import boto3
+import pytest
+from botocore import UNSIGNED
+from botocore.client import Config
+
+import obstore as obs
+from obstore.exceptions import NotSupportedError
+from obstore.store import LocalStore, MemoryStore, S3Store
+from tests.conftest import TEST_BUCKET_NAME
+
+
+def test_multipart_writer_stats():
+    store = MemoryStore()
+    completed = []
+
+    writer = obs.open_multipart_writer(
+        store,
+        "file.bin",
+        part_size=4,
+        on_part_complete=lambda index, size: completed.append((index, size)),
+    )
+    writer.write(b"abcdef")
+    assert writer.bytes_buffered == 2
+
+    writer.flush()
+    assert writer.parts_in_flight == 0
+    assert writer.parts_completed == 1
+    assert writer.bytes_uploaded == 4
+
+    writer.write(b"ghij")
+    writer.finish()
+
+    assert obs.get(store, "file.bin").bytes() == b"abcdefghij"
+    assert sorted(completed) == [(0, 4), (1, 4), (2, 2)]
+
+
+def test_multipart_writer_callback_error():
+    store = MemoryStore()
+
+    def on_part_complete(index, size):
+        raise ValueError("boom")
+
+    writer = obs.open_multipart_writer(
+        store, "file.bin", part_size=4, on_part_complete=on_part_complete
+    )
+    writer.write(b"abcd")
+    with pytest.raises(ValueError, match="boom"):
+        writer.flush()
+    writer.abort()
+
+
+def test_multipart_writer_finish_error_aborts(s3: str, s3_store: S3Store):
+    def on_part_complete(index, size):
+        raise ValueError("boom")
+
+    writer = obs.open_multipart_writer(
+        s3_store, "file.bin", on_part_complete=on_part_complete
+    )
+    writer.write(b"foo")
+    with pytest.raises(ValueError, match="boom"):
+        writer.finish()
+
+    client = boto3.client(
+        "s3",
+        config=Config(signature_version=UNSIGNED),
+        region_name="us-east-1",
+        endpoint_url=s3,
+    )
+    uploads = client.list_multipart_uploads(Bucket=TEST_BUCKET_NAME)
+    assert uploads.get("Uploads", []) == []
+
+
+def test_multipart_writer_closed():
+    store = MemoryStore()
+
+    writer = obs.open_multipart_writer(store, "file.bin")
+    writer.write(b"foo")
+    writer.finish()
+
+    with pytest.raises(IOError):
+        writer.write(b"bar")
+
+
+def test_multipart_writer_invalid_part_size():
+    store = MemoryStore()
+
+    with pytest.raises(ValueError):
+        obs.open_multipart_writer(store, "file.bin", part_size=0)
+
+
+@pytest.mark.asyncio
+async def test_multipart_writer_async():
+    store = MemoryStore()
+
+    writer = await obs.open_multipart_writer_async(store, "file.bin", part_size=3)
+    await writer.write(b"foo")
+    await writer.write(b"bar")
+    await writer.flush()
+    assert writer.parts_completed == 2
+    await writer.finish()
+
+    assert obs.get(store, "file.bin").bytes() == b"foobar"
+
+
+def test_multipart_by_upload_id():
+    store = MemoryStore()
+
+    upload_id = obs.create_multipart(store, "file.bin")
+    # Parts can be uploaded out of order
+    second = obs.put_part(store, "file.bin", upload_id, 1, b"world")
+    first = obs.put_part(store, "file.bin", upload_id, 0, b"hello ")
+    obs.complete_multipart(store, "file.bin", upload_id, [first, second])
+
+    assert obs.get(store, "file.bin").bytes() == b"hello world"
+
+
+def test_multipart_by_upload_id_abort():
+    store = MemoryStore()
+
+    upload_id = obs.create_multipart(store, "file.bin")
+    obs.put_part(store, "file.bin", upload_id, 0, b"data")
+    obs.abort_multipart(store, "file.bin", upload_id)
+
+    with pytest.raises(FileNotFoundError):
+        obs.head(store, "file.bin")
+
+
+def test_multipart_by_upload_id_not_supported(tmp_path):
+    store = LocalStore(tmp_path)
+    with pytest.raises(NotSupportedError):
+        obs.create_multipart(store, "file.bin")
+
+
+@pytest.mark.asyncio
+async def test_multipart_by_upload_id_async():
+    store = MemoryStore()
+
+    upload_id = await obs.create_multipart_async(store, "file.bin")
+    part = await obs.put_part_async(store, "file.bin", upload_id, 0, b"data")
+    await obs.complete_multipart_async(store, "file.bin", upload_id, [part])
+
+    assert (await obs.get_async(store, "file.bin")).bytes() == b"data"
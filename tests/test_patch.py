@@ -0,0 +1,70 @@
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore, S3Store
+
+MiB = 1024 * 1024
+
+
+def test_patch_range():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"the quick brown fox")
+
+    obs.patch_range(store, "file.txt", 4, b"QUICK")
+    assert obs.get(store, "file.txt").bytes() == b"the QUICK brown fox"
+
+
+def test_patch_range_extends_object():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"the quick brown fox")
+
+    obs.patch_range(store, "file.txt", 16, b"dog jumps")
+    assert obs.get(store, "file.txt").bytes() == b"the quick brown dog jumps"
+
+
+def test_patch_range_offset_past_end():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+
+    with pytest.raises(ValueError):
+        obs.patch_range(store, "file.txt", 4, b"bar")
+
+
+def test_patch_range_multipart():
+    store = MemoryStore()
+    data = bytes(range(256)) * 16
+    obs.put(store, "file.bin", data)
+
+    result = obs.patch_range(store, "file.bin", 1000, b"x" * 100, chunk_size=256)
+    expected = data[:1000] + b"x" * 100 + data[1100:]
+    assert obs.get(store, "file.bin").bytes() == expected
+    assert result["e_tag"] == obs.head(store, "file.bin")["e_tag"]
+
+
+@pytest.mark.asyncio
+async def test_patch_range_async():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"the quick brown fox")
+
+    await obs.patch_range_async(store, "file.txt", 0, b"THE")
+    assert obs.get(store, "file.txt").bytes() == b"THE quick brown fox"
+
+
+def test_patch_range_s3_part_copy(s3: str):
+    store = S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+    data = bytes(range(256)) * (48 * 1024)
+    obs.put(store, "big.bin", data)
+
+    offset = 6 * MiB
+    obs.patch_range(store, "big.bin", offset, b"x" * 100, chunk_size=MiB)
+    expected = data[:offset] + b"x" * 100 + data[offset + 100 :]
+    assert obs.get(store, "big.bin").bytes() == expected
@@ -0,0 +1,59 @@
+import pytest
+
+import obstore as obs
+from obstore.store import HTTPStore, LocalStore, MemoryStore, S3Store
+
+
+def test_url_for():
+    store = S3Store("bucket", region="us-east-1", skip_signature=True)
+    assert store.url_for("path/to/file.txt") == "s3://bucket/path/to/file.txt"
+    assert store.url_for("with space.txt") == "s3://bucket/with%20space.txt"
+
+    store = HTTPStore.from_url("https://example.com/data/")
+    assert store.url_for("file.txt") == "https://example.com/data/file.txt"
+
+    assert MemoryStore().url_for("a/b.txt") == "memory:///a/b.txt"
+
+
+def test_url_for_local(tmp_path):
+    store = LocalStore(tmp_path)
+    url = store.url_for("dir/file.txt")
+    assert url == (tmp_path / "dir" / "file.txt").as_uri()
+
+
+@pytest.mark.parametrize(
+    ("url", "expected_spec", "expected_path"),
+    [
+        ("s3://bucket/a/b.txt", {"store": "S3Store", "url": "s3://bucket"}, "a/b.txt"),
+        (
+            "https://bucket.s3.us-east-1.amazonaws.com/a/b.txt",
+            {"store": "S3Store", "url": "https://bucket.s3.us-east-1.amazonaws.com"},
+            "a/b.txt",
+        ),
+        ("gs://bucket/a.txt", {"store": "GCSStore", "url": "gs://bucket"}, "a.txt"),
+        (
+            "https://account.blob.core.windows.net/container/a.txt",
+            {
+                "store": "AzureStore",
+                "url": "https://account.blob.core.windows.net/container",
+            },
+            "a.txt",
+        ),
+        ("memory:///a.txt", {"store": "MemoryStore", "url": "memory:///"}, "a.txt"),
+    ],
+)
+def test_parse_object_url(url, expected_spec, expected_path):
+    spec, path = obs.parse_object_url(url)
+    assert spec == expected_spec
+    assert path == expected_path
+
+
+def test_parse_object_url_roundtrip():
+    store = S3Store("bucket", region="us-east-1", skip_signature=True)
+    _, path = obs.parse_object_url(store.url_for("dir/with space.txt"))
+    assert path == "dir/with space.txt"
+
+
+def test_parse_object_url_invalid():
+    with pytest.raises(ValueError):
+        obs.parse_object_url("not a url")
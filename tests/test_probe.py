@@ -0,0 +1,124 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import PermissionDeniedError
+from obstore.store import GuardrailStore, MemoryStore, S3Store
+
+
+def test_diagnose_permissions_allowed():
+    store = MemoryStore()
+    obs.put(store, "dir/file.txt", b"foo")
+
+    report = obs.diagnose_permissions(store, "dir/file.txt")
+    assert report["list"] is True
+    assert report["head"] is True
+    assert report["get"] is True
+    assert report["hint"] is not None
+
+
+def test_diagnose_permissions_missing_object():
+    store = MemoryStore()
+
+    report = obs.diagnose_permissions(store, "missing.txt")
+    assert report["list"] is True
+    assert report["head"] is True
+    assert report["get"] is True
+
+
+def test_diagnose_permissions_denied_read():
+    def authorize(path: str, operation: str) -> bool:
+        return operation == "list"
+
+    memory_store = MemoryStore()
+    obs.put(memory_store, "dir/file.txt", b"foo")
+    store = GuardrailStore(memory_store, authorize=authorize)
+
+    report = obs.diagnose_permissions(store, "dir/file.txt")
+    assert report["list"] is True
+    assert report["head"] is False
+    assert report["get"] is False
+    assert report["hint"] is not None
+    assert "reading the object is denied" in report["hint"]
+
+
+def test_probe():
+    store = MemoryStore()
+
+    result = obs.probe(store, requests=3)
+    assert result["reachable"] is True
+    assert result["authenticated"] is True
+    assert result["authorized"] is True
+    assert result["error"] is None
+    assert result["region"] is None
+
+    latency = result["latency_ms"]
+    assert latency is not None
+    assert latency["min"] <= latency["p50"] <= latency["p90"] <= latency["max"]
+
+
+def test_probe_region(s3_store: S3Store):
+    result = obs.probe(s3_store, requests=1)
+    assert result["reachable"] is True
+    assert result["region"] == "us-east-1"
+
+
+def test_probe_unreachable():
+    # Nothing listens on port 1, so the connection is refused without any response
+    store = S3Store(
+        "bucket",
+        endpoint="http://127.0.0.1:1",
+        region="us-east-1",
+        skip_signature=True,
+        client_options={"allow_http": True},
+        retry_config={
+            "max_retries": 0,
+            "backoff": {
+                "base": 2,
+                "init_backoff": timedelta(milliseconds=1),
+                "max_backoff": timedelta(milliseconds=1),
+            },
+            "retry_timeout": timedelta(seconds=1),
+        },
+    )
+
+    result = obs.probe(store)
+    assert result["reachable"] is False
+    assert result["authenticated"] is None
+    assert result["error"] is not None
+    assert result["region"] is None
+    assert result["latency_ms"] is None
+
+
+def test_probe_unauthorized():
+    store = GuardrailStore(MemoryStore(), authorize=lambda path, op: False)
+
+    result = obs.probe(store)
+    assert result["reachable"] is True
+    assert result["authenticated"] is True
+    assert result["authorized"] is False
+    assert result["error"] is not None
+
+
+def test_permission_hints():
+    def authorize(path: str, operation: str) -> bool:
+        return operation == "list"
+
+    memory_store = MemoryStore()
+    obs.put(memory_store, "dir/file.txt", b"foo")
+    store = GuardrailStore(memory_store, authorize=authorize)
+
+    with pytest.raises(PermissionDeniedError) as exc_info:
+        obs.get(store, "dir/file.txt")
+    assert exc_info.value.hint is None
+
+    obs.set_permission_hints(True)
+    try:
+        with pytest.raises(PermissionDeniedError) as exc_info:
+            obs.get(store, "dir/file.txt")
+    finally:
+        obs.set_permission_hints(False)
+    assert exc_info.value.hint is not None
+    assert "reading the object is denied" in exc_info.value.hint
+    assert exc_info.value.hint in str(exc_info.value)
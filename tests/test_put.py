@@ -3,8 +3,8 @@ import itertools
 import pytest
 
 import obstore as obs
-from obstore.exceptions import AlreadyExistsError
-from obstore.store import MemoryStore
+from obstore.exceptions import AlreadyExistsError, PreconditionError
+from obstore.store import MemoryStore, S3Store
 
 
 def test_put_non_multipart():
@@ -94,3 +94,134 @@ def test_put_sync_iterable():
     obs.put(store, path, iterator)
 
     assert obs.get(store, path).bytes() == data
+
+
+def test_put_generator_uploads_small_streams_in_one_request(s3_store: S3Store):
+    def small():
+        yield b"foo"
+        yield b"bar"
+
+    # Multipart e-tags end with the number of parts
+    result = obs.put(s3_store, "small.txt", small())
+    assert "-" not in result["e_tag"]
+    assert obs.get(s3_store, "small.txt").bytes() == b"foobar"
+
+    chunk = b"x" * 1024 * 1024
+
+    def large():
+        yield from itertools.repeat(chunk, 6)
+
+    result = obs.put(s3_store, "large.txt", large())
+    assert result["e_tag"].strip('"').endswith("-2")
+    assert obs.get(s3_store, "large.txt").bytes() == chunk * 6
+
+
+def test_put_and_confirm():
+    store = MemoryStore()
+
+    meta = obs.put_and_confirm(store, "file.txt", b"foo")
+    assert meta["path"] == "file.txt"
+    assert meta["size"] == 3
+    assert meta["e_tag"] == obs.head(store, "file.txt")["e_tag"]
+
+    meta = obs.put_and_confirm(store, "iter.txt", iter([b"foo", b"bar"]))
+    assert meta["size"] == 6
+
+
+def test_put_and_confirm_invalid_attempts():
+    store = MemoryStore()
+
+    with pytest.raises(ValueError):
+        obs.put_and_confirm(store, "file.txt", b"foo", max_attempts=0)
+
+
+@pytest.mark.asyncio
+async def test_put_and_confirm_async():
+    store = MemoryStore()
+
+    meta = await obs.put_and_confirm_async(store, "file.txt", b"foo")
+    assert meta["size"] == 3
+
+
+def test_put_many():
+    store = MemoryStore()
+    obs.put(store, "exists.txt", b"old")
+
+    items = [(f"file{i}.txt", f"data{i}".encode()) for i in range(20)]
+    items.append(("iterable.txt", iter([b"chunk1", b"chunk2"])))
+    results = obs.put_many(store, items, max_concurrency=4)
+
+    assert len(results) == len(items)
+    assert all("e_tag" in result for result in results)
+    for i in range(20):
+        assert obs.get(store, f"file{i}.txt").bytes() == f"data{i}".encode()
+    assert obs.get(store, "iterable.txt").bytes() == b"chunk1chunk2"
+
+
+def test_put_many_returns_errors():
+    store = MemoryStore()
+
+    def failing():
+        yield b"start"
+        raise ValueError("upload failed")
+
+    results = obs.put_many(store, [("ok.txt", b"ok"), ("fail.txt", failing())])
+    assert "e_tag" in results[0]
+    assert isinstance(results[1], Exception)
+    assert obs.get(store, "ok.txt").bytes() == b"ok"
+
+
+@pytest.mark.asyncio
+async def test_put_many_async():
+    store = MemoryStore()
+
+    async def chunks():
+        yield b"async"
+        yield b"chunks"
+
+    results = await obs.put_many_async(
+        store,
+        [("bytes.txt", b"bytes"), ("async.txt", chunks())],
+    )
+    assert len(results) == 2
+    assert (await obs.get_async(store, "async.txt")).bytes() == b"asyncchunks"
+
+
+def test_put_many_rejects_async_input():
+    store = MemoryStore()
+
+    async def chunks():
+        yield b"async"
+
+    with pytest.raises(ValueError, match="put_many_async"):
+        obs.put_many(store, [("async.txt", chunks())])
+
+
+def test_put_result_attributes():
+    store = MemoryStore()
+
+    result = obs.put(store, "file.txt", b"foo")
+    assert isinstance(result, obs.PutResult)
+    assert result.e_tag == obs.head(store, "file.txt")["e_tag"]
+    assert result["e_tag"] == result.e_tag
+    assert result.version is None
+    assert result.update_mode() == {"e_tag": result.e_tag, "version": None}
+
+    with pytest.raises(KeyError):
+        result["size"]
+
+
+def test_put_update_precondition_error():
+    store = MemoryStore()
+
+    first = obs.put(store, "file.txt", b"foo")
+    second = obs.put(store, "file.txt", b"bar", mode=first.update_mode())
+
+    with pytest.raises(PreconditionError) as excinfo:
+        obs.put(store, "file.txt", b"baz", mode=first.update_mode())
+    err = excinfo.value
+    assert err.path == "file.txt"
+    assert err.expected_e_tag == first.e_tag
+    assert err.expected_version is None
+    assert err.actual_e_tag == second.e_tag
+    assert obs.get(store, "file.txt").bytes() == b"bar"
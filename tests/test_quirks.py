@@ -0,0 +1,104 @@
+from urllib.parse import urlparse
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import AlreadyExistsError
+from obstore.store import S3Store
+
+MiB = 1024 * 1024
+
+
+@pytest.fixture()
+def store(s3: str):
+    store = S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+    yield store
+    # Quirks are registered for the whole process
+    obs.register_quirks(urlparse(s3).netloc)
+
+
+def test_default_quirks(store: S3Store):
+    assert store.quirks == {
+        "ignores_if_none_match": False,
+        "emulate_if_none_match": False,
+        "lacks_delete_objects": False,
+        "lacks_upload_part_copy": False,
+    }
+
+    gcs = S3Store("bucket", endpoint="https://storage.googleapis.com")
+    assert gcs.quirks["lacks_delete_objects"]
+    assert gcs.quirks["lacks_upload_part_copy"]
+
+
+def test_register_quirks_pattern(store: S3Store):
+    host = urlparse(store.endpoint_url).netloc
+    obs.register_quirks(host, lacks_delete_objects=True)
+    assert store.quirks["lacks_delete_objects"]
+
+    obs.register_quirks(host)
+    assert not store.quirks["lacks_delete_objects"]
+
+    obs.register_quirks("*.internal.example.com", ignores_if_none_match=True)
+    try:
+        internal = S3Store("bucket", endpoint="https://s3.internal.example.com")
+        assert internal.quirks["ignores_if_none_match"]
+    finally:
+        obs.register_quirks("*.internal.example.com")
+
+
+def test_lacks_delete_objects(store: S3Store):
+    obs.register_quirks(urlparse(store.endpoint_url).netloc, lacks_delete_objects=True)
+
+    paths = [f"file{i}.txt" for i in range(5)]
+    for path in paths:
+        obs.put(store, path, b"foo")
+    obs.delete(store, paths)
+    assert obs.list(store, "file").collect() == []
+
+
+def test_ignores_if_none_match(store: S3Store):
+    obs.register_quirks(urlparse(store.endpoint_url).netloc, ignores_if_none_match=True)
+
+    with pytest.raises(NotImplementedError):
+        obs.put(store, "file.txt", b"foo", mode="create")
+    obs.put(store, "file.txt", b"foo")
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+
+
+def test_emulate_if_none_match(store: S3Store):
+    obs.register_quirks(urlparse(store.endpoint_url).netloc, emulate_if_none_match=True)
+
+    obs.put(store, "file.txt", b"foo", mode="create")
+    with pytest.raises(AlreadyExistsError):
+        obs.put(store, "file.txt", b"bar", mode="create")
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+
+
+def test_lacks_upload_part_copy(store: S3Store):
+    obs.register_quirks(
+        urlparse(store.endpoint_url).netloc, lacks_upload_part_copy=True
+    )
+
+    data = b"x" * (6 * MiB)
+    obs.put(store, "big.bin", data)
+    parts = []
+    obs.copy(
+        store,
+        "big.bin",
+        "big-copy.bin",
+        multipart_threshold=5 * MiB,
+        part_size=5 * MiB,
+        on_part_complete=lambda index, size: parts.append(index),
+    )
+    assert obs.get(store, "big-copy.bin").bytes() == data
+    # Streamed through the client rather than copied in parts
+    assert parts == []
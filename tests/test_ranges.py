@@ -0,0 +1,37 @@
+import pytest
+
+import obstore as obs
+
+
+def test_plan_ranges_merges_nearby():
+    ranges = [(20, 30), (0, 10), (5_000_000, 5_000_010)]
+    assert obs.plan_ranges(ranges) == [(0, 30), (5_000_000, 5_000_010)]
+    assert obs.plan_ranges(ranges, max_gap=5) == [
+        (0, 10),
+        (20, 30),
+        (5_000_000, 5_000_010),
+    ]
+
+
+def test_plan_ranges_overlapping():
+    assert obs.plan_ranges([(0, 100), (10, 20), (50, 150)], max_gap=0) == [(0, 150)]
+
+
+def test_plan_ranges_max_request_size():
+    assert obs.plan_ranges([(0, 100)], max_request_size=40) == [
+        (0, 40),
+        (40, 80),
+        (80, 100),
+    ]
+    # Ranges that would exceed the maximum together aren't merged
+    assert obs.plan_ranges([(0, 30), (35, 60)], max_request_size=50) == [
+        (0, 30),
+        (35, 60),
+    ]
+
+
+def test_plan_ranges_invalid():
+    with pytest.raises(ValueError):
+        obs.plan_ranges([(10, 0)])
+    with pytest.raises(ValueError):
+        obs.plan_ranges([(0, 10)], max_request_size=0)
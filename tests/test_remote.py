@@ -0,0 +1,105 @@
+import json
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_put_from_url(http_server):
+    root, url = http_server
+    data = b"the quick brown fox jumps over the lazy dog" * 1024
+    (root / "data.bin").write_bytes(data)
+
+    store = MemoryStore()
+    obs.put_from_url(store, "copy.bin", f"{url}/data.bin", chunk_size=5 * 1024)
+    assert obs.get(store, "copy.bin").bytes() == data
+
+
+def test_put_from_url_not_streaming(http_server):
+    root, url = http_server
+    (root / "file.txt").write_bytes(b"foo")
+
+    store = MemoryStore()
+    obs.put_from_url(store, "file.txt", f"{url}/file.txt", streaming=False)
+    assert obs.get(store, "file.txt").bytes() == b"foo"
+
+
+def test_put_from_url_not_found(http_server):
+    _, url = http_server
+
+    store = MemoryStore()
+    with pytest.raises(FileNotFoundError):
+        obs.put_from_url(store, "missing.txt", f"{url}/missing.txt")
+
+    assert obs.list(store).collect() == []
+
+
+def test_put_from_url_invalid_url():
+    with pytest.raises(ValueError):
+        obs.put_from_url(MemoryStore(), "file.txt", "not a url")
+
+
+@pytest.mark.asyncio
+async def test_put_from_url_async(http_server):
+    root, url = http_server
+    (root / "file.txt").write_bytes(b"foo")
+
+    store = MemoryStore()
+    await obs.put_from_url_async(store, "file.txt", f"{url}/file.txt")
+    resp = await obs.get_async(store, "file.txt")
+    assert await resp.bytes_async() == b"foo"
+
+
+def test_mirror_http(http_server):
+    root, url = http_server
+    (root / "a.csv").write_bytes(b"a")
+    (root / "sub").mkdir()
+    (root / "sub" / "b.csv").write_bytes(b"b")
+    (root / "sub" / "c.txt").write_bytes(b"c")
+
+    store = MemoryStore()
+    paths = obs.mirror_http(f"{url}/", store, "mirror")
+    assert paths == ["mirror/a.csv", "mirror/sub/b.csv", "mirror/sub/c.txt"]
+    assert obs.get(store, "mirror/sub/b.csv").bytes() == b"b"
+
+
+def test_mirror_http_include_glob(http_server):
+    root, url = http_server
+    (root / "a.csv").write_bytes(b"a")
+    (root / "sub").mkdir()
+    (root / "sub" / "b.csv").write_bytes(b"b")
+    (root / "sub" / "c.txt").write_bytes(b"c")
+
+    store = MemoryStore()
+    assert obs.mirror_http(url, store, include_glob="*.csv") == ["a.csv"]
+
+    store = MemoryStore()
+    paths = obs.mirror_http(url, store, include_glob="**/*.csv")
+    assert paths == ["a.csv", "sub/b.csv"]
+
+
+def test_mirror_http_json_index(http_server):
+    root, url = http_server
+    (root / "top.txt").write_bytes(b"top")
+    (root / "data").mkdir()
+    (root / "data" / "file.txt").write_bytes(b"foo")
+    index = [
+        {"name": "data", "type": "directory"},
+        {"name": "top.txt", "type": "file"},
+    ]
+    (root / "index.json").write_text(json.dumps(index))
+    (root / "data" / "index.json").write_text(json.dumps(["file.txt"]))
+
+    store = MemoryStore()
+    assert obs.mirror_http(url, store) == ["data/file.txt", "top.txt"]
+    assert obs.get(store, "top.txt").bytes() == b"top"
+
+
+@pytest.mark.asyncio
+async def test_mirror_http_async(http_server):
+    root, url = http_server
+    (root / "file.txt").write_bytes(b"foo")
+
+    store = MemoryStore()
+    assert await obs.mirror_http_async(url, store, "prefix") == ["prefix/file.txt"]
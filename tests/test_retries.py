@@ -0,0 +1,57 @@
+import sys
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+
+
+@pytest.fixture
+def events():
+    events = []
+    obs.set_retry_hook(events.append)
+    yield events
+    # The hook is shared by the whole process
+    obs.set_retry_hook(None)
+
+
+def test_retry_hook(flaky_store, events):
+    assert obs.get(flaky_store, "file.txt").bytes() == b"foo"
+
+    assert len(events) == 1
+    assert events[0]["attempt"] == 1
+    assert events[0]["max_retries"] == 3
+    assert events[0]["delay"] <= timedelta(milliseconds=10)
+    assert "server error" in events[0]["message"]
+
+
+@pytest.mark.asyncio
+async def test_retry_hook_async(flaky_store, events):
+    resp = await obs.get_async(flaky_store, "file.txt")
+    assert await resp.bytes_async() == b"foo"
+    assert [event["attempt"] for event in events] == [1]
+
+
+def test_retry_hook_unset(flaky_store, events):
+    obs.set_retry_hook(None)
+    assert obs.get(flaky_store, "file.txt").bytes() == b"foo"
+    assert events == []
+
+
+def test_retry_hook_raising(flaky_store, monkeypatch):
+    def hook(event):
+        raise ValueError("hook failed")
+
+    unraisable = []
+    monkeypatch.setattr(sys, "unraisablehook", unraisable.append)
+    obs.set_retry_hook(hook)
+    try:
+        assert obs.get(flaky_store, "file.txt").bytes() == b"foo"
+    finally:
+        obs.set_retry_hook(None)
+    assert isinstance(unraisable[0].exc_value, ValueError)
+
+
+def test_retry_hook_not_callable():
+    with pytest.raises(TypeError, match="callable"):
+        obs.set_retry_hook("hook")  # type: ignore
@@ -0,0 +1,83 @@
+import time
+from datetime import datetime, timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_roll_on_size():
+    store = MemoryStore()
+    writer = obs.open_rolling_writer(store, "logs", max_size=10, naming="sequence")
+
+    assert writer.write(b"12345") is None
+    assert writer.bytes_buffered == 5
+    assert writer.write(b"67890") == "logs/00000000000000000000"
+    assert writer.bytes_buffered == 0
+    writer.write(b"abc")
+    assert writer.close() == "logs/00000000000000000001"
+    assert writer.objects_published == 2
+
+    assert obs.get(store, "logs/00000000000000000000").bytes() == b"1234567890"
+    assert obs.get(store, "logs/00000000000000000001").bytes() == b"abc"
+
+    with pytest.raises(IOError):
+        writer.write(b"more")
+
+
+def test_sequence_continues_after_existing_objects():
+    store = MemoryStore()
+    obs.put(store, "logs/00000000000000000007.log", b"old")
+
+    writer = obs.open_rolling_writer(store, "logs/", naming="sequence", suffix=".log")
+    writer.write(b"new")
+    assert writer.flush() == "logs/00000000000000000008.log"
+    assert writer.flush() is None
+
+
+def test_roll_on_interval():
+    store = MemoryStore()
+    writer = obs.open_rolling_writer(
+        store, "logs", max_interval=timedelta(milliseconds=50)
+    )
+    writer.write(b"first")
+    time.sleep(0.1)
+    path = writer.write(b"second")
+    assert path is not None
+    assert path.startswith("logs/")
+    assert obs.get(store, path).bytes() == b"first"
+
+    last = writer.close()
+    assert last is not None
+    assert obs.get(store, last).bytes() == b"second"
+
+
+def test_callable_naming():
+    store = MemoryStore()
+
+    def naming(index: int, opened_at: datetime) -> str:
+        return f"{opened_at:%Y/%m/%d}/part-{index}.ndjson"
+
+    writer = obs.open_rolling_writer(store, "events", naming=naming)
+    writer.write(b'{"a": 1}\n')
+    path = writer.close()
+    assert path is not None
+    assert path.startswith("events/")
+    assert path.endswith("/part-0.ndjson")
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError):
+        obs.open_rolling_writer(MemoryStore(), "logs", max_size=0)
+    with pytest.raises(ValueError):
+        obs.open_rolling_writer(MemoryStore(), "logs", naming="random")  # type: ignore
+
+
+@pytest.mark.asyncio
+async def test_rolling_writer_async():
+    store = MemoryStore()
+    writer = await obs.open_rolling_writer_async(store, "logs", naming="sequence")
+    await writer.write(b"foo")
+    assert await writer.close() == "logs/00000000000000000000"
+    assert obs.get(store, "logs/00000000000000000000").bytes() == b"foo"
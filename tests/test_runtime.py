@@ -0,0 +1,82 @@
+import asyncio
+import threading
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+@pytest.mark.asyncio
+async def test_sync_call_in_event_loop_with_pending_futures():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+    release = asyncio.Event()
+
+    async def chunks():
+        await release.wait()
+        yield b"bar"
+
+    # The upload can't complete until the event loop runs the iterator again
+    task = asyncio.create_task(obs.put_async(store, "other.txt", chunks()))
+    await asyncio.sleep(0.1)
+
+    with pytest.raises(RuntimeError, match="_async"):
+        obs.get(store, "file.txt")
+
+    release.set()
+    await task
+
+    # Nothing is pending anymore, so sync calls are allowed again
+    assert obs.get(store, "other.txt").bytes() == b"bar"
+
+
+@pytest.mark.asyncio
+async def test_sync_call_in_worker_thread_with_pending_futures():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+    release = asyncio.Event()
+
+    async def chunks():
+        await release.wait()
+        yield b"bar"
+
+    task = asyncio.create_task(obs.put_async(store, "other.txt", chunks()))
+    await asyncio.sleep(0.1)
+
+    result = await asyncio.to_thread(obs.get, store, "file.txt")
+    assert result.bytes() == b"foo"
+
+    release.set()
+    await task
+
+
+@pytest.mark.asyncio
+async def test_sync_call_in_event_loop_with_futures_of_other_loop():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+    other_loop = asyncio.new_event_loop()
+    thread = threading.Thread(target=other_loop.run_forever)
+    thread.start()
+    release = asyncio.Event()
+
+    async def chunks():
+        await release.wait()
+        yield b"bar"
+
+    async def put():
+        await obs.put_async(store, "other.txt", chunks())
+
+    try:
+        future = asyncio.run_coroutine_threadsafe(put(), other_loop)
+        await asyncio.sleep(0.1)
+
+        # Pending on a loop that isn't blocked by this call
+        assert obs.get(store, "file.txt").bytes() == b"foo"
+
+        other_loop.call_soon_threadsafe(release.set)
+        await asyncio.wrap_future(future)
+    finally:
+        other_loop.call_soon_threadsafe(other_loop.stop)
+        thread.join()
+        other_loop.close()
@@ -0,0 +1,97 @@
+import json
+import subprocess
+import sys
+import textwrap
+
+# Shutting down can't be undone, so each test runs in its own interpreter.
+
+
+def run(script: str) -> dict:
+    prelude = textwrap.dedent(
+        """
+        import asyncio
+        import json
+        from datetime import timedelta
+
+        import obstore as obs
+        from obstore.store import MemoryStore
+        """,
+    )
+    out = subprocess.run(
+        [sys.executable, "-c", prelude + textwrap.dedent(script)],
+        capture_output=True,
+        check=True,
+        text=True,
+    )
+    return json.loads(out.stdout)
+
+
+def test_shutdown_rejects_new_operations():
+    out = run(
+        """
+        store = MemoryStore()
+        obs.put(store, "file.txt", b"foo")
+        report = obs.shutdown()
+        try:
+            obs.get(store, "file.txt")
+        except RuntimeError as err:
+            error = str(err)
+        print(json.dumps({"report": report, "error": error}))
+        """,
+    )
+    assert out["report"] == {
+        "cancelled": 0,
+        "still_running": 0,
+        "flushed": [],
+        "aborted_uploads": [],
+        "errors": [],
+    }
+    assert "shut down" in out["error"]
+
+
+def test_shutdown_aborts_open_uploads_and_flushes_rolling_writers():
+    out = run(
+        """
+        store = MemoryStore()
+        multipart = obs.open_multipart_writer(store, "upload.bin")
+        multipart.write(b"foo")
+        closed = obs.open_multipart_writer(store, "closed.bin")
+        closed.finish()
+        rolling = obs.open_rolling_writer(store, "logs", naming="sequence")
+        rolling.write(b"bar")
+        report = obs.shutdown()
+        print(json.dumps(report))
+        """,
+    )
+    assert out["aborted_uploads"] == ["upload.bin"]
+    assert out["flushed"] == ["logs/00000000000000000000"]
+    assert out["errors"] == []
+
+
+def test_shutdown_cancels_pending_operations_after_timeout():
+    out = run(
+        """
+        async def main():
+            store = MemoryStore()
+            release = asyncio.Event()
+
+            async def chunks():
+                await release.wait()
+                yield b"bar"
+
+            task = asyncio.create_task(obs.put_async(store, "file.txt", chunks()))
+            await asyncio.sleep(0.1)
+            # The upload can't make progress while the event loop is blocked
+            report = obs.shutdown(timeout=timedelta(milliseconds=100))
+            try:
+                await task
+            except RuntimeError as err:
+                error = str(err)
+            return report, error
+
+        report, error = asyncio.run(main())
+        print(json.dumps({"report": report, "error": error}))
+        """,
+    )
+    assert out["report"]["cancelled"] == 1
+    assert "cancelled" in out["error"]
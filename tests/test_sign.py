@@ -0,0 +1,83 @@
+import base64
+import json
+from datetime import timedelta
+from urllib.parse import parse_qs, urlsplit
+
+import pytest
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric import rsa
+
+import obstore as obs
+from obstore.store import AzureStore, GCSStore, MemoryStore, S3Store
+
+
+@pytest.fixture()
+def service_account_key() -> str:
+    private_key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    pem = private_key.private_bytes(
+        serialization.Encoding.PEM,
+        serialization.PrivateFormat.PKCS8,
+        serialization.NoEncryption(),
+    ).decode()
+    return json.dumps(
+        {
+            "type": "service_account",
+            "private_key": pem,
+            "private_key_id": "key-id",
+            "client_email": "signer@project.iam.gserviceaccount.com",
+        }
+    )
+
+
+def test_sign_gcs(service_account_key):
+    store = GCSStore(
+        "bucket", prefix="data", google_service_account_key=service_account_key
+    )
+
+    url = obs.sign(store, "GET", "file.txt", timedelta(minutes=5))
+    parts = urlsplit(url)
+    assert parts.path == "/bucket/data/file.txt"
+    query = parse_qs(parts.query)
+    assert query["X-Goog-Algorithm"] == ["GOOG4-RSA-SHA256"]
+    assert query["X-Goog-Expires"] == ["300"]
+    assert "X-Goog-Signature" in query
+
+    urls = obs.sign(store, "PUT", ["a.txt", "b.txt"], timedelta(minutes=5))
+    assert [urlsplit(url).path for url in urls] == [
+        "/bucket/data/a.txt",
+        "/bucket/data/b.txt",
+    ]
+
+
+def test_sign_azure():
+    account_key = base64.b64encode(b"0" * 64).decode()
+    store = AzureStore("container", account_name="account", account_key=account_key)
+
+    url = obs.sign(store, "GET", "file.txt", timedelta(minutes=5))
+    parts = urlsplit(url)
+    assert parts.netloc == "account.blob.core.windows.net"
+    assert parts.path == "/container/file.txt"
+    assert "sig" in parse_qs(parts.query)
+
+
+def test_sign_many():
+    store = S3Store(
+        "bucket",
+        region="us-east-1",
+        aws_access_key_id="testing",
+        aws_secret_access_key="testing",
+    )
+    paths = [f"part-{i}.parquet" for i in range(1000)]
+
+    urls = obs.sign(store, "GET", paths, timedelta(minutes=5))
+    assert [urlsplit(url).path.rsplit("/", 1)[-1] for url in urls] == paths
+    assert all("X-Amz-Signature" in parse_qs(urlsplit(url).query) for url in urls)
+
+    # Any sequence of paths is accepted
+    urls = obs.sign(store, "GET", tuple(paths[:2]), timedelta(minutes=5))
+    assert len(urls) == 2
+
+
+def test_sign_unsupported_store():
+    with pytest.raises(ValueError, match="Expected an S3Store"):
+        obs.sign(MemoryStore(), "GET", "file.txt", timedelta(minutes=5))
@@ -0,0 +1,65 @@
+import json
+from datetime import datetime, timezone
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_ndjson_sink():
+    store = MemoryStore()
+    sink = obs.open_record_sink(store, "events", naming="sequence")
+    sink.write_row({"id": 1, "name": "a", "tags": ["x"], "score": 0.5})
+    sink.write_rows([{"id": 2, "name": None}, {"id": 3, "ok": True}])
+    path = sink.close()
+    assert path == "events/00000000000000000000.ndjson"
+
+    lines = obs.get(store, path).bytes().to_bytes().decode().splitlines()
+    assert [json.loads(line) for line in lines] == [
+        {"id": 1, "name": "a", "tags": ["x"], "score": 0.5},
+        {"id": 2, "name": None},
+        {"id": 3, "ok": True},
+    ]
+
+
+def test_csv_sink():
+    store = MemoryStore()
+    sink = obs.open_record_sink(
+        store, "events", format="csv", naming="sequence", max_size=60
+    )
+    time = datetime(2025, 1, 1, tzinfo=timezone.utc)
+    assert sink.write_row({"id": 1, "note": 'say "hi", ok', "time": time}) is None
+    assert sink.columns == ["id", "note", "time"]
+    assert sink.write_row({"id": 2}) == "events/00000000000000000000.csv"
+    sink.write_row({"id": 3, "time": None})
+    assert sink.close() == "events/00000000000000000001.csv"
+
+    first = obs.get(store, "events/00000000000000000000.csv").bytes().to_bytes()
+    assert first == (
+        b"id,note,time\n"
+        b'1,"say ""hi"", ok",2025-01-01T00:00:00+00:00\n'
+        b"2,,\n"
+    )
+    second = obs.get(store, "events/00000000000000000001.csv").bytes().to_bytes()
+    assert second == b"id,note,time\n3,,\n"
+
+
+def test_schema_is_enforced():
+    sink = obs.open_record_sink(MemoryStore(), "events", columns=["id"])
+    with pytest.raises(ValueError):
+        sink.write_row({"id": 1, "other": 2})
+    with pytest.raises(TypeError):
+        sink.write_row({"id": object()})
+    with pytest.raises(TypeError):
+        sink.write_rows([1])  # type: ignore
+
+
+@pytest.mark.asyncio
+async def test_record_sink_async():
+    store = MemoryStore()
+    sink = await obs.open_record_sink_async(store, "events", naming="sequence")
+    await sink.write_row({"id": 1})
+    path = await sink.close()
+    assert path is not None
+    assert obs.get(store, path).bytes() == b'{"id":1}\n'
@@ -0,0 +1,60 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.exceptions import PreconditionError
+from obstore.store import MemoryStore
+
+
+def test_get_pinned():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+    obs.put(store, "b.txt", b"bar")
+
+    token = obs.snapshot_token(store, ["a.txt", "b.txt"])
+    assert obs.get_pinned(store, token, "a.txt").bytes() == b"foo"
+    assert obs.get_pinned(store, token, "b.txt", options={"range": [1, 3]}).bytes() == b"ar"
+
+    obs.put(store, "a.txt", b"changed")
+    with pytest.raises(PreconditionError):
+        obs.get_pinned(store, token, "a.txt")
+
+    with pytest.raises(KeyError):
+        obs.get_pinned(store, token, "c.txt")
+
+
+def test_invalid_token():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+
+    with pytest.raises(ValueError, match="Invalid snapshot token"):
+        obs.get_pinned(store, "not-a-token", "a.txt")
+
+
+def test_expired_token():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+
+    token = obs.snapshot_token(store, ["a.txt"], expires_in=timedelta(hours=1))
+    assert obs.get_pinned(store, token, "a.txt").bytes() == b"foo"
+
+    token = obs.snapshot_token(store, ["a.txt"], expires_in=timedelta(0))
+    with pytest.raises(ValueError, match="expired"):
+        obs.get_pinned(store, token, "a.txt")
+
+
+def test_missing_object():
+    store = MemoryStore()
+    with pytest.raises(FileNotFoundError):
+        obs.snapshot_token(store, ["missing.txt"])
+
+
+@pytest.mark.asyncio
+async def test_get_pinned_async():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+
+    token = await obs.snapshot_token_async(store, ["a.txt"])
+    result = await obs.get_pinned_async(store, token, "a.txt")
+    assert await result.bytes_async() == b"foo"
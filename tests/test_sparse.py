@@ -0,0 +1,93 @@
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore, S3Store
+
+MiB = 1024 * 1024
+
+
+def test_sparse_writer_out_of_order():
+    store = MemoryStore()
+
+    writer = obs.open_sparse_writer(store, "file.bin", part_size=8)
+    writer.write(10, b"klmnopqrst")
+    writer.write(5, b"fghij")
+    writer.write(0, b"abcde")
+    writer.finish()
+
+    assert obs.get(store, "file.bin").bytes() == b"abcdefghijklmnopqrst"
+
+
+def test_sparse_writer_overlap():
+    store = MemoryStore()
+
+    writer = obs.open_sparse_writer(store, "file.bin")
+    writer.write(5, b"fghij")
+    with pytest.raises(ValueError):
+        writer.write(8, b"xyz")
+    writer.abort()
+
+
+def test_sparse_writer_gap():
+    store = MemoryStore()
+
+    writer = obs.open_sparse_writer(store, "file.bin")
+    writer.write(5, b"fghij")
+    with pytest.raises(ValueError):
+        writer.finish()
+
+    # The writer stays open, so the gap can still be filled
+    writer.write(0, b"abcde")
+    writer.finish()
+    assert obs.get(store, "file.bin").bytes() == b"abcdefghij"
+
+
+@pytest.mark.asyncio
+async def test_sparse_writer_async():
+    store = MemoryStore()
+
+    writer = await obs.open_sparse_writer_async(store, "file.bin")
+    await writer.write(3, b"bar")
+    await writer.write(0, b"foo")
+    await writer.finish()
+
+    assert obs.get(store, "file.bin").bytes() == b"foobar"
+
+
+def test_sparse_writer_max_pending_size():
+    store = MemoryStore()
+
+    writer = obs.open_sparse_writer(store, "file.bin", max_pending_size=8)
+    writer.write(4, b"efgh")
+    with pytest.raises(ValueError, match="max_pending_size"):
+        writer.write(8, b"ijklm")
+
+    # Regions that can be uploaded right away aren't buffered
+    writer.write(0, b"abcd")
+    writer.write(8, b"ijklmnopqrstuvwxyz")
+    writer.finish()
+    assert obs.get(store, "file.bin").bytes() == b"abcdefghijklmnopqrstuvwxyz"
+
+
+def test_sparse_writer_s3_uploads_parts_by_index(s3_store: S3Store):
+    part = 5 * MiB
+    data = bytes(range(256)) * (11 * MiB // 256)
+
+    writer = obs.open_sparse_writer(
+        s3_store, "file.bin", part_size=part, max_pending_size=part
+    )
+    # Each full part is uploaded as soon as it's written, so none of these are held back
+    writer.write(2 * part, data[2 * part :])
+    writer.write(part, data[part : 2 * part])
+    writer.write(0, data[:part])
+    writer.finish()
+
+    assert obs.get(s3_store, "file.bin").bytes() == data
+
+
+def test_sparse_writer_s3_gap(s3_store: S3Store):
+    writer = obs.open_sparse_writer(s3_store, "file.bin", part_size=5 * MiB)
+    writer.write(5 * MiB, b"x" * (5 * MiB))
+    with pytest.raises(ValueError, match="never written"):
+        writer.finish()
+    writer.abort()
@@ -0,0 +1,84 @@
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+def test_put_return_stats():
+    store = MemoryStore()
+
+    result, stats = obs.put(store, "file.txt", b"foo", return_stats=True)
+    assert result["e_tag"] is not None
+    assert stats.bytes_sent == 3
+    assert stats.bytes_received == 0
+
+
+def test_put_multipart_return_stats():
+    store = MemoryStore()
+
+    data = b"x" * 100
+    _, stats = obs.put(store, "file.txt", iter([data] * 10), return_stats=True)
+    assert stats.bytes_sent == 1000
+
+
+def test_get_return_stats():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foobar")
+
+    result, stats = obs.get(store, "file.txt", return_stats=True)
+    # Counts are updated as the response is read
+    assert stats.bytes_received == 0
+    assert result.bytes() == b"foobar"
+    assert stats.bytes_received == 6
+
+
+def test_time_to_first_byte():
+    store = MemoryStore()
+
+    _, stats = obs.put(store, "file.txt", b"foobar", return_stats=True)
+    assert isinstance(stats.time_to_first_byte, timedelta)
+
+    result, stats = obs.get(store, "file.txt", return_stats=True)
+    assert stats.time_to_first_byte is None
+    result.bytes()
+    assert isinstance(stats.time_to_first_byte, timedelta)
+
+
+def test_get_range_return_stats():
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foobar")
+
+    buf, stats = obs.get_range(store, "file.txt", 0, 3, return_stats=True)
+    assert stats.retries == 0
+    assert buf == b"foo"
+    assert stats.bytes_received == 3
+
+    bufs, stats = obs.get_ranges(store, "file.txt", [0, 3], [2, 6], return_stats=True)
+    assert bufs == [b"fo", b"bar"]
+    assert stats.bytes_received == 5
+
+
+def test_return_stats_retries(flaky_store):
+    result, stats = obs.get(flaky_store, "file.txt", return_stats=True)
+    assert result.bytes() == b"foo"
+    assert stats.retries == 1
+    assert stats.bytes_received == 3
+
+
+@pytest.mark.asyncio
+async def test_get_async_return_stats():
+    store = MemoryStore()
+    await obs.put_async(store, "file.txt", b"foobar")
+
+    result, stats = await obs.get_async(store, "file.txt", return_stats=True)
+    assert await result.bytes_async() == b"foobar"
+    assert stats.bytes_received == 6
+
+
+def test_return_stats_default():
+    store = MemoryStore()
+
+    result = obs.put(store, "file.txt", b"foo")
+    assert isinstance(result, dict)
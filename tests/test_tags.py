@@ -0,0 +1,58 @@
+import pytest
+
+import obstore as obs
+from obstore.exceptions import NotSupportedError
+from obstore.store import MemoryStore, S3Store
+
+
+@pytest.fixture()
+def store(s3: str):
+    # Tag requests are signed with the store's credentials
+    return S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+
+
+def test_tags(store: S3Store):
+    assert obs.get_tags(store, "afile") == {}
+
+    obs.put_tags(store, "afile", {"team": "data", "tier": "hot"})
+    assert obs.get_tags(store, "afile") == {"team": "data", "tier": "hot"}
+
+    obs.put_tags(store, "afile", {"tier": "cold"})
+    assert obs.get_tags(store, "afile") == {"tier": "cold"}
+
+    obs.put_tags(store, "afile", {})
+    assert obs.get_tags(store, "afile") == {}
+
+
+def test_tags_set_on_put(store: S3Store):
+    obs.put(store, "tagged", b"data", tags={"source": "upload"})
+    assert obs.get_tags(store, "tagged") == {"source": "upload"}
+
+
+def test_tags_missing_object(store: S3Store):
+    with pytest.raises(FileNotFoundError):
+        obs.get_tags(store, "missing")
+
+
+def test_tags_not_supported():
+    store = MemoryStore()
+    obs.put(store, "file", b"data")
+    with pytest.raises(NotSupportedError):
+        obs.get_tags(store, "file")
+    with pytest.raises(NotSupportedError):
+        obs.put_tags(store, "file", {"key": "value"})
+
+
+@pytest.mark.asyncio
+async def test_tags_async(store: S3Store):
+    await obs.put_tags_async(store, "afile", {"team": "data"})
+    assert await obs.get_tags_async(store, "afile") == {"team": "data"}
@@ -0,0 +1,107 @@
+import logging
+from datetime import timedelta
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+
+
+@pytest.fixture
+def tracing():
+    obs.enable_tracing()
+    yield
+    obs.disable_tracing()
+
+
+def test_spans_logged(tracing, caplog):
+    store = MemoryStore()
+    with caplog.at_level(logging.INFO, logger="obstore"):
+        obs.put(store, "file.txt", b"foo")
+
+    records = [r for r in caplog.records if r.name == "obstore"]
+    assert len(records) == 1
+    record = records[0]
+    assert record.levelno == logging.INFO
+    assert record.getMessage().startswith("put{path=file.txt}: closed after")
+    assert record.fields["path"] == "file.txt"
+    assert isinstance(record.duration, timedelta)
+
+
+def test_span_records_error(tracing, caplog):
+    store = MemoryStore()
+    with caplog.at_level(logging.INFO, logger="obstore"):
+        with pytest.raises(FileNotFoundError):
+            obs.head(store, "missing.txt")
+
+    (record,) = [r for r in caplog.records if r.name == "obstore"]
+    assert record.fields["path"] == "missing.txt"
+    assert "error" in record.fields
+
+
+def test_copy_records_source(tracing, caplog):
+    store = MemoryStore()
+    obs.put(store, "file.txt", b"foo")
+    with caplog.at_level(logging.INFO, logger="obstore"):
+        obs.copy(store, "file.txt", "copy.txt")
+
+    (record,) = [r for r in caplog.records if r.name == "obstore"]
+    assert record.fields == {"path": "copy.txt", "from": "file.txt"}
+
+
+def test_level_filters_spans(caplog):
+    obs.enable_tracing(level="warning")
+    try:
+        with caplog.at_level(logging.DEBUG, logger="obstore"):
+            obs.put(MemoryStore(), "file.txt", b"foo")
+    finally:
+        obs.disable_tracing()
+
+    assert not [r for r in caplog.records if r.name == "obstore"]
+
+
+def test_disable_tracing(caplog):
+    obs.enable_tracing()
+    obs.disable_tracing()
+    with caplog.at_level(logging.DEBUG, logger="obstore"):
+        obs.put(MemoryStore(), "file.txt", b"foo")
+
+    assert not [r for r in caplog.records if r.name == "obstore"]
+
+
+def test_invalid_options():
+    with pytest.raises(ValueError, match="level"):
+        obs.enable_tracing(level="verbose")  # type: ignore[arg-type]
+    with pytest.raises(ValueError, match="output"):
+        obs.enable_tracing(output="stdout")  # type: ignore[arg-type]
+    with pytest.raises(ValueError, match="tracer_provider"):
+        obs.enable_tracing(tracer_provider=object())
+
+
+def test_opentelemetry_spans():
+    pytest.importorskip("opentelemetry.sdk")
+    from opentelemetry.sdk.trace import TracerProvider
+    from opentelemetry.sdk.trace.export import SimpleSpanProcessor
+    from opentelemetry.sdk.trace.export.in_memory_span_exporter import (
+        InMemorySpanExporter,
+    )
+    from opentelemetry.trace import StatusCode
+
+    exporter = InMemorySpanExporter()
+    provider = TracerProvider()
+    provider.add_span_processor(SimpleSpanProcessor(exporter))
+
+    obs.enable_tracing(output="opentelemetry", tracer_provider=provider)
+    try:
+        store = MemoryStore()
+        obs.put(store, "file.txt", b"foo")
+        with pytest.raises(FileNotFoundError):
+            obs.head(store, "missing.txt")
+    finally:
+        obs.disable_tracing()
+
+    put, head = exporter.get_finished_spans()
+    assert put.name == "put"
+    assert put.attributes["path"] == "file.txt"
+    assert head.name == "head"
+    assert head.status.status_code == StatusCode.ERROR
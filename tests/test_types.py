@@ -0,0 +1,50 @@
+from collections.abc import AsyncIterator, Iterable, Iterator
+
+import pytest
+
+import obstore as obs
+from obstore.store import MemoryStore
+from obstore.types import (
+    AsyncReadableFile,
+    Bytes,
+    BytesStream,
+    GetResult,
+    ListStream,
+    ReadableFile,
+)
+
+
+def test_returned_objects_are_instances():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+
+    assert isinstance(obs.list(store), ListStream)
+    result = obs.get(store, "a.txt")
+    assert isinstance(result, GetResult)
+    assert isinstance(result.stream(), BytesStream)
+    assert isinstance(obs.get_range(store, "a.txt", 0, 1), Bytes)
+    assert isinstance(obs.open(store, "a.txt"), ReadableFile)
+
+
+@pytest.mark.asyncio
+async def test_async_file_is_instance():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+    assert isinstance(await obs.open_async(store, "a.txt"), AsyncReadableFile)
+
+
+def test_abc_registration():
+    store = MemoryStore()
+    obs.put(store, "a.txt", b"foo")
+
+    stream = obs.list(store)
+    assert isinstance(stream, AsyncIterator)
+    assert isinstance(stream, Iterator)
+    assert isinstance(obs.get(store, "a.txt").stream(), AsyncIterator)
+    assert isinstance(obs.get(store, "a.txt"), Iterable)
+
+
+def test_list_stream_generic():
+    alias = ListStream[list]
+    assert alias.__origin__ is ListStream
+    assert alias.__args__ == (list,)
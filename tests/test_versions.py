@@ -0,0 +1,124 @@
+import time
+from datetime import datetime, timedelta, timezone
+
+import boto3
+import pytest
+from botocore import UNSIGNED
+from botocore.client import Config
+
+import obstore as obs
+from obstore.exceptions import NotSupportedError
+from obstore.store import MemoryStore, S3Store
+
+
+@pytest.fixture
+def client(s3: str):
+    client = boto3.client(
+        "s3",
+        config=Config(signature_version=UNSIGNED),
+        region_name="us-east-1",
+        endpoint_url=s3,
+    )
+    client.put_bucket_versioning(
+        Bucket="test", VersioningConfiguration={"Status": "Enabled"}
+    )
+    return client
+
+
+@pytest.fixture
+def versioned_store(s3: str, client):
+    # ListObjectVersions is a signed request, so the store needs credentials
+    return S3Store.from_url(
+        "s3://test/",
+        config={
+            "AWS_ENDPOINT_URL": s3,
+            "AWS_REGION": "us-east-1",
+            "AWS_ACCESS_KEY_ID": "testing",
+            "AWS_SECRET_ACCESS_KEY": "testing",
+            "AWS_ALLOW_HTTP": "true",
+        },
+    )
+
+
+def test_undelete(versioned_store: S3Store, client):
+    obs.put(versioned_store, "file.txt", b"foo")
+    obs.put(versioned_store, "file.txt", b"bar")
+    # Another object with the path as a prefix
+    obs.put(versioned_store, "file.txt.bak", b"baz")
+    latest = obs.head(versioned_store, "file.txt")["version"]
+    obs.delete(versioned_store, "file.txt")
+    obs.delete(versioned_store, "file.txt")
+
+    assert obs.undelete(versioned_store, "file.txt") == latest
+    assert obs.get(versioned_store, "file.txt").bytes() == b"bar"
+    versions = client.list_object_versions(Bucket="test", Prefix="file.txt")
+    assert "DeleteMarkers" not in versions
+
+    # Not deleted anymore
+    assert obs.undelete(versioned_store, "file.txt") == latest
+
+
+def test_undelete_copy(versioned_store: S3Store, client):
+    obs.put(versioned_store, "file.txt", b"foo")
+    obs.delete(versioned_store, "file.txt")
+
+    version = obs.undelete(versioned_store, "file.txt", copy=True)
+    assert obs.head(versioned_store, "file.txt")["version"] == version
+    assert obs.get(versioned_store, "file.txt").bytes() == b"foo"
+    versions = client.list_object_versions(Bucket="test", Prefix="file.txt")
+    assert len(versions["DeleteMarkers"]) == 1
+    assert len(versions["Versions"]) == 2
+
+
+@pytest.mark.asyncio
+async def test_undelete_async(versioned_store: S3Store):
+    await obs.put_async(versioned_store, "file.txt", b"foo")
+    await obs.delete_async(versioned_store, "file.txt")
+
+    await obs.undelete_async(versioned_store, "file.txt")
+    assert obs.get(versioned_store, "file.txt").bytes() == b"foo"
+
+
+def test_undelete_missing(versioned_store: S3Store):
+    with pytest.raises(FileNotFoundError):
+        obs.undelete(versioned_store, "missing.txt")
+
+
+def test_undelete_not_s3():
+    with pytest.raises(NotSupportedError):
+        obs.undelete(MemoryStore(), "file.txt")
+
+
+def test_get_at(versioned_store: S3Store):
+    before = datetime.now(timezone.utc) - timedelta(seconds=10)
+    obs.put(versioned_store, "file.txt", b"foo")
+    # LastModified has a precision of a second or less
+    time.sleep(1.1)
+    first = datetime.now(timezone.utc)
+    time.sleep(1.1)
+    obs.put(versioned_store, "file.txt", b"bar")
+    time.sleep(1.1)
+    second = datetime.now(timezone.utc)
+    time.sleep(1.1)
+    obs.delete(versioned_store, "file.txt")
+
+    assert obs.get_at(versioned_store, "file.txt", first).bytes() == b"foo"
+    assert obs.get_at(versioned_store, "file.txt", second).bytes() == b"bar"
+    with pytest.raises(FileNotFoundError):
+        obs.get_at(versioned_store, "file.txt", before)
+    with pytest.raises(FileNotFoundError):
+        obs.get_at(versioned_store, "file.txt", datetime.now(timezone.utc))
+
+
+@pytest.mark.asyncio
+async def test_get_at_async(versioned_store: S3Store):
+    await obs.put_async(versioned_store, "file.txt", b"foo")
+    as_of = datetime.now(timezone.utc) + timedelta(seconds=10)
+
+    result = await obs.get_at_async(versioned_store, "file.txt", as_of)
+    assert await result.bytes_async() == b"foo"
+
+
+def test_get_at_not_s3():
+    with pytest.raises(NotSupportedError):
+        obs.get_at(MemoryStore(), "file.txt", datetime.now(timezone.utc))